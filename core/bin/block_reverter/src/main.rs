@@ -8,8 +8,8 @@ use tokio::{
 };
 use zksync_block_reverter::{
     eth_client::{
-        clients::{Client, PKSigningClient, L1},
-        EthInterface,
+        clients::{Client, PKSigningClient, RemoteSigningClient, L1},
+        BoundEthInterface, EthInterface,
     },
     BlockReverter, BlockReverterEthConfig, NodeRole,
 };
@@ -60,7 +60,8 @@ enum Command {
         #[arg(long = "operator-address")]
         operator_address: Address,
     },
-    /// Sends revert transaction to L1.
+    /// Sends revert transaction to L1. The transaction is signed by the remote signer if it's specified
+    /// in the Ethereum config (`eth.remote_signer`); otherwise, the operator private key is used.
     #[command(name = "send-eth-transaction")]
     SendEthTransaction {
         /// L1 batch number used to revert to.
@@ -272,39 +273,54 @@ async fn main() -> anyhow::Result<()> {
             let eth_client = Client::http(l1_secrets.l1_rpc_url.clone())
                 .context("Ethereum client")?
                 .build();
-            let reverter_private_key = if let Some(wallets_config) = wallets_config {
-                wallets_config
-                    .eth_sender
-                    .unwrap()
-                    .operator
-                    .private_key()
-                    .to_owned()
-            } else {
-                #[allow(deprecated)]
-                eth_sender
-                    .sender
-                    .context("eth_sender_config")?
-                    .private_key()
-                    .context("eth_sender_config.private_key")?
-                    .context("eth_sender_config.private_key is not set")?
-            };
-
             let priority_fee_per_gas = priority_fee_per_gas.unwrap_or(default_priority_fee_per_gas);
             let l1_chain_id = eth_client
                 .fetch_chain_id()
                 .await
                 .context("cannot fetch Ethereum chain ID")?;
-            let eth_client = PKSigningClient::new_raw(
-                reverter_private_key,
-                contracts.diamond_proxy_addr,
-                priority_fee_per_gas,
-                l1_chain_id,
-                Box::new(eth_client),
-            );
+            let eth_client: Box<dyn BoundEthInterface> =
+                if let Some(remote_signer_config) = &eth_sender.remote_signer {
+                    let signer = RemoteSigningClient::create_signer(
+                        remote_signer_config,
+                        remote_signer_config.operator_address,
+                    )
+                    .context("failed creating remote signer")?;
+                    Box::new(RemoteSigningClient::new_raw(
+                        signer,
+                        contracts.diamond_proxy_addr,
+                        priority_fee_per_gas,
+                        l1_chain_id,
+                        Box::new(eth_client),
+                    ))
+                } else {
+                    let reverter_private_key = if let Some(wallets_config) = wallets_config {
+                        wallets_config
+                            .eth_sender
+                            .unwrap()
+                            .operator
+                            .private_key()
+                            .to_owned()
+                    } else {
+                        #[allow(deprecated)]
+                        eth_sender
+                            .sender
+                            .context("eth_sender_config")?
+                            .private_key()
+                            .context("eth_sender_config.private_key")?
+                            .context("eth_sender_config.private_key is not set")?
+                    };
+                    Box::new(PKSigningClient::new_raw(
+                        reverter_private_key,
+                        contracts.diamond_proxy_addr,
+                        priority_fee_per_gas,
+                        l1_chain_id,
+                        Box::new(eth_client),
+                    ))
+                };
 
             block_reverter
                 .send_ethereum_revert_transaction(
-                    &eth_client,
+                    eth_client.as_ref(),
                     &config,
                     L1BatchNumber(l1_batch_number),
                     nonce,
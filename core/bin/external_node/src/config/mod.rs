@@ -251,6 +251,15 @@ pub(crate) struct OptionalENConfig {
     /// Limit for fee history block range.
     #[serde(default = "OptionalENConfig::default_fee_history_limit")]
     pub fee_history_limit: u64,
+    /// Maximum number of heap words recorded by the struct logger for a single instruction.
+    #[serde(default = "OptionalENConfig::default_struct_logger_memory_limit")]
+    pub struct_logger_memory_limit: usize,
+    /// Maximum number of instructions recorded by the struct logger for a single transaction.
+    #[serde(default = "OptionalENConfig::default_struct_logger_step_limit")]
+    pub struct_logger_step_limit: usize,
+    /// Maximum total size in MiBs of data recorded by the struct logger for a single transaction.
+    #[serde(default = "OptionalENConfig::default_struct_logger_size_limit_mb")]
+    struct_logger_size_limit_mb: usize,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     #[serde(default = "OptionalENConfig::default_max_batch_request_size")]
     pub max_batch_request_size: usize,
@@ -439,6 +448,15 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, history (events, call traces and transaction execution info) of L1 batches will be pruned after
+    /// the batch timestamp is this old (in seconds). Unlike `pruning_enabled`, L1 batches, L2 blocks, transactions
+    /// and storage state are retained, so this can be used to run a "history-light" node. History pruning is enabled
+    /// independently of `pruning_enabled`.
+    pruning_history_retention_sec: Option<u64>,
+    /// Addresses with retained history if history pruning is enabled. Events emitted by these addresses, and events,
+    /// call traces and execution info of transactions calling these addresses are not pruned.
+    #[serde(default)]
+    pub pruning_history_retained_addresses: Vec<Address>,
     /// Gateway RPC URL, needed for operating during migration.
     #[allow(dead_code)]
     pub gateway_url: Option<SensitiveUrl>,
@@ -480,6 +498,21 @@ impl OptionalENConfig {
                 web3_json_rpc.fee_history_limit,
                 default_fee_history_limit
             ),
+            struct_logger_memory_limit: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.struct_logger_memory_limit,
+                default_struct_logger_memory_limit
+            ),
+            struct_logger_step_limit: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.struct_logger_step_limit,
+                default_struct_logger_step_limit
+            ),
+            struct_logger_size_limit_mb: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.struct_logger_size_limit_mb,
+                default_struct_logger_size_limit_mb
+            ),
             max_batch_request_size: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.max_batch_request_size,
@@ -641,6 +674,15 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_history_retention_sec: load_config!(
+                general_config.pruning,
+                history_retention_sec
+            ),
+            pruning_history_retained_addresses: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.history_retained_addresses.clone())
+                .unwrap_or_default(),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
@@ -753,6 +795,18 @@ impl OptionalENConfig {
         1_024
     }
 
+    const fn default_struct_logger_memory_limit() -> usize {
+        1_024
+    }
+
+    const fn default_struct_logger_step_limit() -> usize {
+        100_000
+    }
+
+    const fn default_struct_logger_size_limit_mb() -> usize {
+        64
+    }
+
     const fn default_max_batch_request_size() -> usize {
         500 // The default limit is chosen to be reasonably permissive.
     }
@@ -821,6 +875,11 @@ impl OptionalENConfig {
         Duration::from_millis(self.merkle_tree_processing_delay_ms)
     }
 
+    /// Returns the maximum size of data recorded by the struct logger in bytes.
+    pub fn struct_logger_size_limit(&self) -> usize {
+        self.struct_logger_size_limit_mb * BYTES_IN_MEGABYTE
+    }
+
     /// Returns the size of factory dependencies cache in bytes.
     pub fn factory_deps_cache_size(&self) -> usize {
         self.factory_deps_cache_size_mb * BYTES_IN_MEGABYTE
@@ -897,6 +956,10 @@ impl OptionalENConfig {
         Duration::from_secs(self.pruning_data_retention_sec)
     }
 
+    pub fn pruning_history_retention(&self) -> Option<Duration> {
+        self.pruning_history_retention_sec.map(Duration::from_secs)
+    }
+
     #[cfg(test)]
     fn mock() -> Self {
         // Set all values to their defaults
@@ -1047,6 +1110,12 @@ pub(crate) struct ExperimentalENConfig {
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
 
+    // API
+    /// Path to the RocksDB historical state archive. If specified, the archive is kept in sync with Postgres
+    /// and is used by the API server to serve storage reads for past L2 blocks, including blocks already pruned
+    /// from Postgres (only for `eth_getStorageAt`).
+    pub state_archive_path: Option<PathBuf>,
+
     // Snapshot recovery
     /// L1 batch number of the snapshot to use during recovery. Specifying this parameter is mostly useful for testing.
     pub snapshots_recovery_l1_batch: Option<L1BatchNumber>,
@@ -1054,6 +1123,9 @@ pub(crate) struct ExperimentalENConfig {
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[serde(default)]
     pub snapshots_recovery_drop_storage_key_preimages: bool,
+    /// Path to a portable snapshot archive (created by the snapshots creator `export` command) to recover from.
+    /// If set, the snapshot is read from the archive, and the snapshot object store doesn't need to be configured.
+    pub snapshots_recovery_archive_path: Option<PathBuf>,
     /// Approximate chunk size (measured in the number of entries) to recover in a single iteration.
     /// Reasonable values are order of 100,000 (meaning an iteration takes several seconds).
     ///
@@ -1089,8 +1161,10 @@ impl ExperimentalENConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_archive_path: None,
             snapshots_recovery_l1_batch: None,
             snapshots_recovery_drop_storage_key_preimages: false,
+            snapshots_recovery_archive_path: None,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
             commitment_generator_max_parallelism: None,
@@ -1113,6 +1187,10 @@ impl ExperimentalENConfig {
                 general_config.db_config,
                 experimental.state_keeper_db_max_open_files
             ),
+            state_archive_path: load_config!(
+                general_config.db_config,
+                experimental.state_archive_path
+            ),
             snapshots_recovery_l1_batch: load_config!(general_config.snapshot_recovery, l1_batch),
             snapshots_recovery_tree_chunk_size: load_optional_config_or_default!(
                 general_config.snapshot_recovery,
@@ -1127,6 +1205,10 @@ impl ExperimentalENConfig {
                 .snapshot_recovery
                 .as_ref()
                 .map_or(false, |config| config.drop_storage_key_preimages),
+            snapshots_recovery_archive_path: general_config
+                .snapshot_recovery
+                .as_ref()
+                .and_then(|config| config.archive_path.as_ref().map(PathBuf::from)),
             commitment_generator_max_parallelism: general_config
                 .commitment_generator
                 .as_ref()
@@ -1200,15 +1282,17 @@ impl ApiComponentConfig {
 #[derive(Debug, Deserialize)]
 pub struct TreeComponentConfig {
     pub api_port: Option<u16>,
+    /// Directory in which tree checkpoints can be created via the tree API.
+    pub api_checkpoints_dir: Option<PathBuf>,
 }
 
 impl TreeComponentConfig {
     fn from_configs(general_config: &GeneralConfig) -> Self {
-        let api_port = general_config
-            .api_config
-            .as_ref()
-            .map(|a| a.merkle_tree.port);
-        TreeComponentConfig { api_port }
+        let merkle_tree_api = general_config.api_config.as_ref().map(|a| &a.merkle_tree);
+        TreeComponentConfig {
+            api_port: merkle_tree_api.map(|api| api.port),
+            api_checkpoints_dir: merkle_tree_api.and_then(|api| api.checkpoints_dir.clone()),
+        }
     }
 }
 
@@ -1356,7 +1440,10 @@ impl ExternalNodeConfig {
             api_component: ApiComponentConfig {
                 tree_api_remote_url: None,
             },
-            tree_component: TreeComponentConfig { api_port: None },
+            tree_component: TreeComponentConfig {
+                api_port: None,
+                api_checkpoints_dir: None,
+            },
         }
     }
 
@@ -1396,6 +1483,9 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
             l2_testnet_paymaster_addr: config.remote.l2_testnet_paymaster_addr,
             req_entities_limit: config.optional.req_entities_limit,
             fee_history_limit: config.optional.fee_history_limit,
+            struct_logger_memory_limit: config.optional.struct_logger_memory_limit,
+            struct_logger_step_limit: config.optional.struct_logger_step_limit,
+            struct_logger_size_limit: config.optional.struct_logger_size_limit(),
             base_token_address: Some(config.remote.base_token_addr),
             filters_disabled: config.optional.filters_disabled,
             dummy_verifier: config.remote.dummy_verifier,
@@ -13,6 +13,7 @@ use zksync_config::{
 };
 use zksync_metadata_calculator::{MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig};
 use zksync_node_api_server::{tx_sender::ApiContracts, web3::Namespace};
+use zksync_node_db_pruner::HistoryPruningConfig;
 use zksync_node_framework::{
     implementations::layers::{
         batch_status_updater::BatchStatusUpdaterLayer,
@@ -110,12 +111,15 @@ impl ExternalNodeBuilder {
                 .optional
                 .slow_query_threshold()
                 .map(|d| d.as_millis() as u64),
+            max_replica_lag_sec: None,
+            replica_health_check_interval_ms: None,
             test_server_url: None,
             test_prover_url: None,
         };
         let secrets = DatabaseSecrets {
             server_url: Some(self.config.postgres.database_url()),
             server_replica_url: Some(self.config.postgres.database_url()),
+            server_replica_urls: vec![],
             prover_url: None,
         };
         let pools_layer = PoolsLayerBuilder::empty(config, secrets)
@@ -248,12 +252,24 @@ impl ExternalNodeBuilder {
     }
 
     fn add_pruning_layer(mut self) -> anyhow::Result<Self> {
-        if self.config.optional.pruning_enabled {
-            let layer = PruningLayer::new(
+        let history_retention = self.config.optional.pruning_history_retention();
+        if self.config.optional.pruning_enabled || history_retention.is_some() {
+            let mut layer = PruningLayer::new(
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
-            );
+            )
+            .with_l1_batch_pruning(self.config.optional.pruning_enabled);
+            if let Some(minimum_l1_batch_age) = history_retention {
+                layer = layer.with_history_pruning(HistoryPruningConfig {
+                    minimum_l1_batch_age,
+                    retained_addresses: self
+                        .config
+                        .optional
+                        .pruning_history_retained_addresses
+                        .clone(),
+                });
+            }
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -359,6 +375,7 @@ impl ExternalNodeBuilder {
                     .tree_component
                     .api_port
                     .context("should contain tree api port")?,
+                checkpoints_dir: self.config.tree_component.api_checkpoints_dir.clone(),
             };
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
@@ -380,13 +397,16 @@ impl ExternalNodeBuilder {
         };
         let max_vm_concurrency = self.config.optional.vm_concurrency_limit;
         let api_contracts = ApiContracts::load_from_disk_blocking(); // TODO (BFT-138): Allow to dynamically reload API contracts;
-        let tx_sender_layer = TxSenderLayer::new(
+        let mut tx_sender_layer = TxSenderLayer::new(
             (&self.config).into(),
             postgres_storage_config,
             max_vm_concurrency,
             api_contracts,
         )
         .with_whitelisted_tokens_for_aa_cache(true);
+        if let Some(path) = &self.config.experimental.state_archive_path {
+            tx_sender_layer = tx_sender_layer.with_state_archive(path.clone());
+        }
 
         self.node.add_layer(ProxySinkLayer);
         self.node.add_layer(tx_sender_layer);
@@ -502,6 +522,7 @@ impl ExternalNodeBuilder {
                         .experimental
                         .snapshots_recovery_drop_storage_key_preimages,
                     object_store_config: config.optional.snapshots_recovery_object_store.clone(),
+                    archive_path: config.experimental.snapshots_recovery_archive_path.clone(),
                 });
         self.node.add_layer(ExternalNodeInitStrategyLayer {
             l2_chain_id: self.config.required.l2_chain_id,
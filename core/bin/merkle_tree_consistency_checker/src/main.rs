@@ -1,10 +1,15 @@
-use std::{path::Path, time::Instant};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
 use anyhow::Context as _;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use zksync_config::{configs::ObservabilityConfig, DBConfig};
 use zksync_env_config::FromEnv;
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper};
+use zksync_metadata_calculator::api_server::TreeApiHttpClient;
 use zksync_storage::RocksDB;
 use zksync_types::L1BatchNumber;
 
@@ -20,10 +25,61 @@ struct Cli {
     /// applied to it last. If not specified, the latest tree version is checked.
     #[arg(long = "l1-batch")]
     l1_batch: Option<u32>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Creates a consistent checkpoint of the Merkle tree at the specified L1 batch.
+    Checkpoint {
+        /// L1 batch number to create the checkpoint for.
+        #[arg(long = "l1-batch")]
+        l1_batch: u32,
+        /// Path to create the checkpoint at. Must not exist.
+        #[arg(long)]
+        path: PathBuf,
+        /// URL of the Merkle tree API of a running node. If specified, the checkpoint is created by the node
+        /// without stopping it, and `path` is treated as the checkpoint name inside the checkpoints directory
+        /// configured on the node. Otherwise, the tree RocksDB is opened directly, which requires the node
+        /// to be stopped.
+        #[arg(long)]
+        tree_api_url: Option<String>,
+    },
+    /// Restores the Merkle tree from a checkpoint by copying it to the Merkle tree path from the config
+    /// (which must be empty) and verifying tree consistency at the specified L1 batch.
+    Restore {
+        /// L1 batch number to restore the tree at. Tree versions after it are truncated.
+        #[arg(long = "l1-batch")]
+        l1_batch: u32,
+        /// Path to the checkpoint created with the `checkpoint` command.
+        #[arg(long)]
+        checkpoint_path: PathBuf,
+    },
 }
 
 impl Cli {
     fn run(self, config: &DBConfig) -> anyhow::Result<()> {
+        match self.command {
+            None => Self::verify(config, self.l1_batch),
+            Some(Command::Checkpoint {
+                l1_batch,
+                path,
+                tree_api_url: Some(url),
+            }) => Self::create_checkpoint_via_api(&url, L1BatchNumber(l1_batch), path),
+            Some(Command::Checkpoint {
+                l1_batch,
+                path,
+                tree_api_url: None,
+            }) => Self::create_checkpoint(config, L1BatchNumber(l1_batch), &path),
+            Some(Command::Restore {
+                l1_batch,
+                checkpoint_path,
+            }) => Self::restore(config, L1BatchNumber(l1_batch), &checkpoint_path),
+        }
+    }
+
+    fn verify(config: &DBConfig, l1_batch: Option<u32>) -> anyhow::Result<()> {
         let db_path = &config.merkle_tree.path;
         tracing::info!("Verifying consistency of Merkle tree at {db_path}");
         let start = Instant::now();
@@ -32,7 +88,7 @@ impl Cli {
         let tree =
             ZkSyncTree::new_lightweight(db.into()).context("cannot initialize Merkle tree")?;
 
-        let l1_batch_number = if let Some(number) = self.l1_batch {
+        let l1_batch_number = if let Some(number) = l1_batch {
             L1BatchNumber(number)
         } else {
             let next_number = tree.next_l1_batch_number();
@@ -49,6 +105,103 @@ impl Cli {
         tracing::info!("Merkle tree verified in {:?}", start.elapsed());
         Ok(())
     }
+
+    fn create_checkpoint_via_api(
+        url: &str,
+        l1_batch_number: L1BatchNumber,
+        path: PathBuf,
+    ) -> anyhow::Result<()> {
+        tracing::info!(
+            "Requesting Merkle tree checkpoint for L1 batch #{l1_batch_number} from tree API at {url}"
+        );
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let name = path
+            .to_str()
+            .with_context(|| format!("checkpoint name `{}` is not valid UTF-8", path.display()))?
+            .to_owned();
+        let client = TreeApiHttpClient::new(url);
+        let checkpoint = runtime
+            .block_on(client.create_checkpoint(l1_batch_number, name))
+            .context("failed creating Merkle tree checkpoint via tree API")?;
+        tracing::info!(
+            "Created Merkle tree checkpoint for L1 batch #{l1_batch_number} at `{}` on the node; root hash: {:?}",
+            checkpoint.path.display(),
+            checkpoint.root_hash
+        );
+        Ok(())
+    }
+
+    fn create_checkpoint(
+        config: &DBConfig,
+        l1_batch_number: L1BatchNumber,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let db_path = &config.merkle_tree.path;
+        tracing::info!(
+            "Creating checkpoint of Merkle tree at {db_path} for L1 batch #{l1_batch_number}"
+        );
+        let start = Instant::now();
+        let db =
+            RocksDB::new(Path::new(db_path)).context("failed initializing Merkle tree RocksDB")?;
+        let tree =
+            ZkSyncTree::new_lightweight(db.into()).context("cannot initialize Merkle tree")?;
+        let root_hash = tree.reader().create_checkpoint(path, l1_batch_number)?;
+        tracing::info!(
+            "Created Merkle tree checkpoint at `{}` in {:?}; root hash: {root_hash:?}",
+            path.display(),
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    fn restore(
+        config: &DBConfig,
+        l1_batch_number: L1BatchNumber,
+        checkpoint_path: &Path,
+    ) -> anyhow::Result<()> {
+        let db_path = Path::new(&config.merkle_tree.path);
+        let is_empty = !db_path.exists()
+            || fs::read_dir(db_path)
+                .with_context(|| format!("failed reading `{}`", db_path.display()))?
+                .next()
+                .is_none();
+        anyhow::ensure!(
+            is_empty,
+            "Merkle tree path `{}` is not empty; remove it before restoring the tree from a checkpoint",
+            db_path.display()
+        );
+
+        tracing::info!(
+            "Restoring Merkle tree at `{}` from checkpoint `{}`",
+            db_path.display(),
+            checkpoint_path.display()
+        );
+        let start = Instant::now();
+        fs::create_dir_all(db_path)
+            .with_context(|| format!("failed creating `{}`", db_path.display()))?;
+        let entries = fs::read_dir(checkpoint_path)
+            .with_context(|| format!("failed reading `{}`", checkpoint_path.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            anyhow::ensure!(
+                entry.file_type()?.is_file(),
+                "unexpected non-file entry `{}` in the checkpoint",
+                entry.path().display()
+            );
+            fs::copy(entry.path(), db_path.join(entry.file_name()))
+                .with_context(|| format!("failed copying `{}`", entry.path().display()))?;
+        }
+
+        let db = RocksDBWrapper::new(db_path).context("failed initializing Merkle tree RocksDB")?;
+        let root_hash = ZkSyncTree::finalize_checkpoint(db, l1_batch_number)?;
+        tracing::info!(
+            "Restored Merkle tree at L1 batch #{l1_batch_number} in {:?}; root hash: {root_hash:?}",
+            start.elapsed()
+        );
+        Ok(())
+    }
 }
 
 fn main() -> anyhow::Result<()> {
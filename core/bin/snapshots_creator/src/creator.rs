@@ -1,6 +1,6 @@
 //! [`SnapshotCreator`] and tightly related types.
 
-use std::{sync::Arc, time::Duration};
+use std::{ops, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::Semaphore;
@@ -25,6 +25,8 @@ use crate::tests::HandleEvent;
 struct SnapshotProgress {
     version: SnapshotVersion,
     l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot if this is a delta snapshot.
+    base_l1_batch_number: Option<L1BatchNumber>,
     /// `true` if the snapshot is new (i.e., its progress is not recovered from Postgres).
     is_new_snapshot: bool,
     chunk_count: u64,
@@ -32,10 +34,16 @@ struct SnapshotProgress {
 }
 
 impl SnapshotProgress {
-    fn new(version: SnapshotVersion, l1_batch_number: L1BatchNumber, chunk_count: u64) -> Self {
+    fn new(
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        chunk_count: u64,
+    ) -> Self {
         Self {
             version,
             l1_batch_number,
+            base_l1_batch_number,
             is_new_snapshot: true,
             chunk_count,
             remaining_chunk_ids: (0..chunk_count).collect(),
@@ -53,6 +61,7 @@ impl SnapshotProgress {
         Self {
             version: snapshot.version,
             l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number: snapshot.base_l1_batch_number,
             is_new_snapshot: false,
             chunk_count: snapshot.storage_logs_filepaths.len() as u64,
             remaining_chunk_ids,
@@ -82,6 +91,7 @@ impl SnapshotCreator {
         semaphore: &Semaphore,
         progress: &SnapshotProgress,
         l2_block_number: L2BlockNumber,
+        delta_l2_blocks: Option<&ops::RangeInclusive<L2BlockNumber>>,
         chunk_id: u64,
     ) -> anyhow::Result<()> {
         let chunk_count = progress.chunk_count;
@@ -120,12 +130,20 @@ impl SnapshotCreator {
                 self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
                     .await?
             }
-            SnapshotVersion::Version1 => {
-                let logs = conn
-                    .snapshots_creator_dal()
-                    .get_storage_logs_chunk(l2_block_number, l1_batch_number, hashed_keys_range)
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
+                let mut dal = conn.snapshots_creator_dal();
+                let logs = if let Some(delta_l2_blocks) = delta_l2_blocks {
+                    dal.get_storage_logs_delta_chunk(
+                        delta_l2_blocks.clone(),
+                        l1_batch_number,
+                        hashed_keys_range,
+                    )
                     .await
-                    .context("error fetching storage logs")?;
+                } else {
+                    dal.get_storage_logs_chunk(l2_block_number, l1_batch_number, hashed_keys_range)
+                        .await
+                };
+                let logs = logs.context("error fetching storage logs")?;
                 drop(conn);
 
                 let latency = latency.observe();
@@ -189,6 +207,7 @@ impl SnapshotCreator {
     async fn process_factory_deps(
         &self,
         l2_block_number: L2BlockNumber,
+        delta_l2_blocks: Option<&ops::RangeInclusive<L2BlockNumber>>,
         l1_batch_number: L1BatchNumber,
     ) -> anyhow::Result<String> {
         let mut conn = self.connect_to_replica().await?;
@@ -196,10 +215,13 @@ impl SnapshotCreator {
         tracing::info!("Loading factory deps from Postgres...");
         let latency =
             METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
-        let factory_deps = conn
-            .snapshots_creator_dal()
-            .get_all_factory_deps(l2_block_number)
-            .await?;
+        let mut dal = conn.snapshots_creator_dal();
+        let factory_deps = if let Some(delta_l2_blocks) = delta_l2_blocks {
+            dal.get_factory_deps_in_l2_blocks(delta_l2_blocks.clone())
+                .await?
+        } else {
+            dal.get_all_factory_deps(l2_block_number).await?
+        };
         drop(conn);
         let latency = latency.observe();
         tracing::info!("Loaded {} factory deps in {latency:?}", factory_deps.len());
@@ -241,6 +263,10 @@ impl SnapshotCreator {
     ) -> anyhow::Result<Option<SnapshotProgress>> {
         let snapshot_version = SnapshotVersion::try_from(config.version)
             .context("invalid snapshot version specified in config")?;
+        anyhow::ensure!(
+            !snapshot_version.is_delta(),
+            "Snapshot version {snapshot_version:?} is reserved for delta snapshots; enable `delta` in the config instead"
+        );
 
         // Sanity check: the selected L1 batch should have Merkle tree data; otherwise, it could be impossible
         // to recover from the generated snapshot.
@@ -254,13 +280,31 @@ impl SnapshotCreator {
                 )
             })?;
 
-        let distinct_storage_logs_keys_count = conn
-            .snapshots_creator_dal()
-            .get_distinct_storage_logs_keys_count(l1_batch_number)
-            .await?;
+        let base_l1_batch_number = if config.delta {
+            Self::select_delta_base(config, snapshot_version, l1_batch_number, conn).await?
+        } else {
+            None
+        };
+        let snapshot_version = if base_l1_batch_number.is_some() {
+            SnapshotVersion::Version2
+        } else {
+            snapshot_version
+        };
+
+        let storage_logs_keys_count = if let Some(base_l1_batch_number) = base_l1_batch_number {
+            let delta_l2_blocks =
+                Self::delta_l2_blocks(base_l1_batch_number, l1_batch_number, conn).await?;
+            conn.snapshots_creator_dal()
+                .get_storage_logs_count_in_l2_blocks(delta_l2_blocks)
+                .await?
+        } else {
+            conn.snapshots_creator_dal()
+                .get_distinct_storage_logs_keys_count(l1_batch_number)
+                .await?
+        };
         let chunk_size = config.storage_logs_chunk_size;
         // We force the minimum number of chunks to avoid situations where only one chunk is created in tests.
-        let chunk_count = distinct_storage_logs_keys_count
+        let chunk_count = storage_logs_keys_count
             .div_ceil(chunk_size)
             .max(min_chunk_count);
 
@@ -271,10 +315,97 @@ impl SnapshotCreator {
         Ok(Some(SnapshotProgress::new(
             snapshot_version,
             l1_batch_number,
+            base_l1_batch_number,
             chunk_count,
         )))
     }
 
+    /// Selects the base snapshot for a delta snapshot. Returns `Ok(None)` if a full snapshot should be created instead.
+    async fn select_delta_base(
+        config: &SnapshotsCreatorConfig,
+        snapshot_version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        anyhow::ensure!(
+            snapshot_version == SnapshotVersion::Version1,
+            "Delta snapshots are only supported for snapshot version 1, requested {snapshot_version:?}"
+        );
+
+        let Some(base_snapshot) = conn
+            .snapshots_dal()
+            .get_newest_complete_snapshot_before(l1_batch_number)
+            .await?
+        else {
+            tracing::info!(
+                "No complete snapshots before L1 batch #{l1_batch_number}; creating a full snapshot"
+            );
+            return Ok(None);
+        };
+        if !matches!(
+            base_snapshot.version,
+            SnapshotVersion::Version1 | SnapshotVersion::Version2
+        ) {
+            tracing::info!(
+                "Newest complete snapshot for L1 batch #{} has unsupported version {:?}; creating a full snapshot",
+                base_snapshot.l1_batch_number,
+                base_snapshot.version
+            );
+            return Ok(None);
+        }
+
+        // Compute the number of deltas the base snapshot is built from.
+        let mut chain_length = 0_u32;
+        let mut snapshot = base_snapshot.clone();
+        while let Some(base_l1_batch_number) = snapshot.base_l1_batch_number {
+            chain_length += 1;
+            snapshot = conn
+                .snapshots_dal()
+                .get_snapshot_metadata(base_l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "Snapshot for L1 batch #{} refers to missing base snapshot for L1 batch #{base_l1_batch_number}",
+                        snapshot.l1_batch_number
+                    )
+                })?;
+        }
+        if chain_length >= config.max_delta_chain_length {
+            tracing::info!(
+                "Snapshot for L1 batch #{} is at the end of a delta chain of length {chain_length} \
+                 (max allowed: {}); creating a full snapshot",
+                base_snapshot.l1_batch_number,
+                config.max_delta_chain_length
+            );
+            return Ok(None);
+        }
+
+        tracing::info!(
+            "Creating delta snapshot for L1 batch #{l1_batch_number} based on snapshot for L1 batch #{}",
+            base_snapshot.l1_batch_number
+        );
+        Ok(Some(base_snapshot.l1_batch_number))
+    }
+
+    /// Returns the range of L2 blocks covered by a delta snapshot.
+    async fn delta_l2_blocks(
+        base_l1_batch_number: L1BatchNumber,
+        l1_batch_number: L1BatchNumber,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<ops::RangeInclusive<L2BlockNumber>> {
+        let (_, last_base_l2_block) = conn
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(base_l1_batch_number)
+            .await?
+            .with_context(|| format!("No L2 blocks for base L1 batch #{base_l1_batch_number}"))?;
+        let (_, last_l2_block) = conn
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("No L2 blocks for L1 batch #{l1_batch_number}"))?;
+        Ok((last_base_l2_block + 1)..=last_l2_block)
+    }
+
     /// Returns `Ok(None)` if a snapshot should not be created / resumed.
     async fn load_or_initialize_snapshot_progress(
         &self,
@@ -366,6 +497,14 @@ impl SnapshotCreator {
             .get_l2_block_range_of_l1_batch(progress.l1_batch_number)
             .await?
             .context("No L2 blocks for L1 batch")?;
+        let delta_l2_blocks = if let Some(base_l1_batch_number) = progress.base_l1_batch_number {
+            Some(
+                Self::delta_l2_blocks(base_l1_batch_number, progress.l1_batch_number, &mut conn)
+                    .await?,
+            )
+        } else {
+            None
+        };
         drop(conn);
 
         METRICS.storage_logs_chunks_count.set(progress.chunk_count);
@@ -377,22 +516,36 @@ impl SnapshotCreator {
 
         if progress.is_new_snapshot {
             let factory_deps_output_file = self
-                .process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
+                .process_factory_deps(
+                    last_l2_block_number_in_batch,
+                    delta_l2_blocks.as_ref(),
+                    progress.l1_batch_number,
+                )
                 .await?;
 
             let mut master_conn = self
                 .master_pool
                 .connection_tagged("snapshots_creator")
                 .await?;
-            master_conn
-                .snapshots_dal()
-                .add_snapshot(
+            let mut dal = master_conn.snapshots_dal();
+            if let Some(base_l1_batch_number) = progress.base_l1_batch_number {
+                dal.add_delta_snapshot(
                     progress.version,
                     progress.l1_batch_number,
+                    base_l1_batch_number,
                     progress.chunk_count,
                     &factory_deps_output_file,
                 )
                 .await?;
+            } else {
+                dal.add_snapshot(
+                    progress.version,
+                    progress.l1_batch_number,
+                    progress.chunk_count,
+                    &factory_deps_output_file,
+                )
+                .await?;
+            }
         }
 
         METRICS
@@ -408,6 +561,7 @@ impl SnapshotCreator {
                     &semaphore,
                     &progress,
                     last_l2_block_number_in_batch,
+                    delta_l2_blocks.as_ref(),
                     chunk_id,
                 )
             });
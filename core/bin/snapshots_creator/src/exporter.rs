@@ -0,0 +1,177 @@
+//! Export of snapshots into portable single-file archives.
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_object_store::{ObjectStore, ObjectStoreArchiveWriter, StoredObject};
+use zksync_types::{
+    snapshots::{
+        SnapshotArchiveBlockData, SnapshotArchiveMetadata, SnapshotFactoryDependencies,
+        SnapshotHeader, SnapshotStorageLogsChunk, SnapshotStorageLogsChunkMetadata,
+        SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber, L2BlockNumber,
+};
+
+use crate::verifier::resolve_snapshot_chain;
+
+/// Exports snapshots from the object store into an archive that can be used by the snapshots applier
+/// without access to the object store or the main node snapshots API.
+#[derive(Debug)]
+pub(crate) struct SnapshotExporter {
+    pub blob_store: Arc<dyn ObjectStore>,
+    pub pool: ConnectionPool<Core>,
+}
+
+impl SnapshotExporter {
+    /// Exports the snapshot for the specified L1 batch (or the newest complete snapshot if not specified)
+    /// into an archive at `output_path`. If the snapshot is a delta, all snapshots in its chain are exported as well.
+    pub async fn export(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+        output_path: &Path,
+    ) -> anyhow::Result<SnapshotArchiveMetadata> {
+        let mut conn = self.pool.connection_tagged("snapshots_creator").await?;
+        let l1_batch_number = if let Some(number) = l1_batch_number {
+            number
+        } else {
+            let snapshots = conn.snapshots_dal().get_all_complete_snapshots().await?;
+            *snapshots
+                .snapshots_l1_batch_numbers
+                .first()
+                .context("no complete snapshots in Postgres")?
+        };
+        let chain = resolve_snapshot_chain(&mut conn, l1_batch_number).await?;
+
+        let mut headers = Vec::with_capacity(chain.len());
+        let mut blocks = Vec::with_capacity(chain.len());
+        for snapshot in chain {
+            let (_, l2_block_number) = conn
+                .blocks_dal()
+                .get_l2_block_range_of_l1_batch(snapshot.l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "missing L2 blocks for L1 batch #{}",
+                        snapshot.l1_batch_number
+                    )
+                })?;
+            blocks.push(
+                Self::load_block_data(&mut conn, snapshot.l1_batch_number, l2_block_number).await?,
+            );
+            let storage_logs_chunks = snapshot
+                .storage_logs_filepaths
+                .into_iter()
+                .enumerate()
+                .map(|(chunk_id, filepath)| SnapshotStorageLogsChunkMetadata {
+                    chunk_id: chunk_id as u64,
+                    // `unwrap()` is safe: the snapshot chain only contains complete snapshots
+                    filepath: filepath.unwrap(),
+                })
+                .collect();
+            headers.push(SnapshotHeader {
+                version: snapshot.version.into(),
+                l1_batch_number: snapshot.l1_batch_number,
+                l2_block_number,
+                base_l1_batch_number: snapshot.base_l1_batch_number,
+                storage_logs_chunks,
+                factory_deps_filepath: snapshot.factory_deps_filepath,
+            });
+        }
+        drop(conn);
+
+        tracing::info!(
+            "Exporting snapshot for L1 batch #{l1_batch_number} (snapshot chain: {:?}) to `{}`",
+            headers
+                .iter()
+                .map(|header| header.l1_batch_number)
+                .collect::<Vec<_>>(),
+            output_path.display()
+        );
+
+        let mut writer = ObjectStoreArchiveWriter::create(output_path)
+            .await
+            .with_context(|| format!("failed creating archive at `{}`", output_path.display()))?;
+        for header in &headers {
+            let factory_deps_key = SnapshotFactoryDependencies::encode_key(header.l1_batch_number);
+            writer
+                .copy_from(
+                    &*self.blob_store,
+                    SnapshotFactoryDependencies::BUCKET,
+                    &factory_deps_key,
+                )
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed exporting factory deps for L1 batch #{}",
+                        header.l1_batch_number
+                    )
+                })?;
+
+            for chunk in &header.storage_logs_chunks {
+                let key = SnapshotStorageLogsStorageKey {
+                    l1_batch_number: header.l1_batch_number,
+                    chunk_id: chunk.chunk_id,
+                };
+                // Chunk keys don't depend on the snapshot version, so we can use any chunk type here.
+                let chunk_key = <SnapshotStorageLogsChunk as StoredObject>::encode_key(key);
+                writer
+                    .copy_from(
+                        &*self.blob_store,
+                        <SnapshotStorageLogsChunk as StoredObject>::BUCKET,
+                        &chunk_key,
+                    )
+                    .await
+                    .with_context(|| format!("failed exporting storage logs chunk {key:?}"))?;
+            }
+            tracing::info!(
+                "Exported {} storage logs chunks for L1 batch #{}",
+                header.storage_logs_chunks.len(),
+                header.l1_batch_number
+            );
+        }
+
+        let metadata = SnapshotArchiveMetadata {
+            snapshots: headers,
+            blocks,
+        };
+        let manifest = writer
+            .finish(serde_json::to_value(&metadata)?)
+            .await
+            .context("failed finalizing archive")?;
+        tracing::info!(
+            "Exported snapshot for L1 batch #{l1_batch_number} with {} objects to `{}`",
+            manifest.objects.len(),
+            output_path.display()
+        );
+        Ok(metadata)
+    }
+
+    /// Loads main node data necessary to recover from a snapshot without querying the main node.
+    async fn load_block_data(
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<SnapshotArchiveBlockData> {
+        let l1_batch = conn
+            .blocks_web3_dal()
+            .get_l1_batch_details(l1_batch_number)
+            .await?
+            .with_context(|| format!("missing details for L1 batch #{l1_batch_number}"))?;
+        let l2_block = conn
+            .blocks_web3_dal()
+            .get_block_details(l2_block_number)
+            .await?
+            .with_context(|| format!("missing details for L2 block #{l2_block_number}"))?;
+        let tokens = conn
+            .tokens_web3_dal()
+            .get_all_tokens(Some(l2_block_number))
+            .await?;
+        Ok(SnapshotArchiveBlockData {
+            l1_batch,
+            l2_block,
+            tokens,
+        })
+    }
+}
@@ -16,14 +16,17 @@ use zksync_config::configs::PrometheusConfig;
 use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStoreFactory;
+use zksync_types::L1BatchNumber;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
-use crate::creator::SnapshotCreator;
+use crate::{creator::SnapshotCreator, exporter::SnapshotExporter, verifier::SnapshotVerifier};
 
 mod creator;
+mod exporter;
 mod metrics;
 #[cfg(test)]
 mod tests;
+mod verifier;
 
 async fn maybe_enable_prometheus_metrics(
     prometheus_config: Option<PrometheusConfig>,
@@ -57,6 +60,36 @@ struct Opt {
     /// Path to the secrets file.
     #[structopt(long)]
     secrets_path: Option<std::path::PathBuf>,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Creates a new snapshot (or resumes creating a snapshot). This is the default command.
+    Create,
+    /// Verifies a snapshot by rebuilding the Merkle tree from its storage logs and comparing the tree root hash
+    /// with the root hash of the snapshot L1 batch.
+    Verify {
+        /// L1 batch of the snapshot to verify. If not specified, the newest complete snapshot will be verified.
+        #[structopt(long)]
+        l1_batch_number: Option<u32>,
+        /// Path to an empty directory to build the Merkle tree in. If not specified, a temporary directory
+        /// will be used.
+        #[structopt(long)]
+        tree_path: Option<std::path::PathBuf>,
+    },
+    /// Exports a snapshot (including its base snapshots if it is a delta) into a single portable archive file,
+    /// which can be used to recover a node without access to the snapshots object store.
+    Export {
+        /// L1 batch of the snapshot to export. If not specified, the newest complete snapshot will be exported.
+        #[structopt(long)]
+        l1_batch_number: Option<u32>,
+        /// Path to the archive file to create.
+        #[structopt(long)]
+        output: std::path::PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -95,20 +128,63 @@ async fn main() -> anyhow::Result<()> {
     .build()
     .await?;
 
-    let master_pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
-        .build()
-        .await?;
-
-    let creator = SnapshotCreator {
-        blob_store,
-        master_pool,
-        replica_pool,
-        #[cfg(test)]
-        event_listener: Box::new(()),
-    };
-    creator.run(creator_config, MIN_CHUNK_COUNT).await?;
+    match opt.command.unwrap_or(Command::Create) {
+        Command::Create => {
+            let master_pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
+                .build()
+                .await?;
+
+            let creator = SnapshotCreator {
+                blob_store,
+                master_pool,
+                replica_pool,
+                #[cfg(test)]
+                event_listener: Box::new(()),
+            };
+            creator.run(creator_config, MIN_CHUNK_COUNT).await?;
+            tracing::info!("Finished running snapshot creator!");
+        }
+        Command::Verify {
+            l1_batch_number,
+            tree_path,
+        } => {
+            let temp_dir;
+            let tree_path = if let Some(path) = &tree_path {
+                path.as_path()
+            } else {
+                temp_dir = tempfile::TempDir::new()
+                    .context("failed creating temporary directory for Merkle tree")?;
+                temp_dir.path()
+            };
+
+            let verifier = SnapshotVerifier {
+                blob_store,
+                pool: replica_pool,
+            };
+            let report = verifier
+                .verify(l1_batch_number.map(L1BatchNumber), tree_path)
+                .await?;
+            tracing::info!("Snapshot verification report: {report:#?}");
+            anyhow::ensure!(
+                report.is_ok(),
+                "snapshot for L1 batch #{} is invalid",
+                report.l1_batch_number
+            );
+        }
+        Command::Export {
+            l1_batch_number,
+            output,
+        } => {
+            let exporter = SnapshotExporter {
+                blob_store,
+                pool: replica_pool,
+            };
+            exporter
+                .export(l1_batch_number.map(L1BatchNumber), &output)
+                .await?;
+        }
+    }
 
-    tracing::info!("Finished running snapshot creator!");
     stop_sender.send(true).ok();
     if let Some(prometheus_exporter_task) = prometheus_exporter_task {
         prometheus_exporter_task
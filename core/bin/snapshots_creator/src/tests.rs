@@ -12,15 +12,17 @@ use std::{
 use rand::{thread_rng, Rng};
 use zksync_config::SnapshotsCreatorConfig;
 use zksync_dal::{Connection, CoreDal};
-use zksync_object_store::{MockObjectStore, ObjectStore};
+use zksync_merkle_tree::{MerkleTree, PatchSet, TreeEntry};
+use zksync_object_store::{ArchiveObjectStore, MockObjectStore, ObjectStore};
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData, L2BlockHeader},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotArchiveMetadata, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotStorageLog, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
     },
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
-    H256,
+    H256, U256,
 };
 
 use super::*;
@@ -31,6 +33,8 @@ const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     storage_logs_chunk_size: 1_000_000,
     concurrent_queries_count: 10,
     object_store: None,
+    delta: false,
+    max_delta_chain_length: 10,
 };
 const SEQUENTIAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     concurrent_queries_count: 1,
@@ -169,6 +173,7 @@ async fn create_l2_block(
 
 async fn create_l1_batch(
     conn: &mut Connection<'_, Core>,
+    tree: &mut MerkleTree<PatchSet>,
     l1_batch_number: L1BatchNumber,
     logs_for_initial_writes: &[StorageLog],
 ) {
@@ -189,12 +194,31 @@ async fn create_l1_batch(
         .insert_initial_writes(l1_batch_number, &written_keys)
         .await
         .unwrap();
+
+    let indices = conn
+        .storage_logs_dal()
+        .get_l1_batches_and_indices_for_initial_writes(&written_keys)
+        .await
+        .unwrap();
+    let tree_entries = logs_for_initial_writes
+        .iter()
+        .map(|log| {
+            let hashed_key = log.key.hashed_key();
+            let (_, leaf_index) = indices[&hashed_key];
+            TreeEntry::new(
+                U256::from_little_endian(hashed_key.as_bytes()),
+                leaf_index,
+                log.value,
+            )
+        })
+        .collect();
+    let output = tree.extend(tree_entries).unwrap();
     conn.blocks_dal()
         .save_l1_batch_tree_data(
             l1_batch_number,
             &L1BatchTreeData {
-                hash: H256::zero(),
-                rollup_last_leaf_index: 1,
+                hash: output.root_hash,
+                rollup_last_leaf_index: output.leaf_count + 1,
             },
         )
         .await
@@ -212,6 +236,7 @@ async fn prepare_postgres(
         .unwrap();
 
     let mut outputs = ExpectedOutputs::default();
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
     for block_number in 0..block_count {
         let logs = gen_storage_logs(rng, 100);
         create_l2_block(conn, L2BlockNumber(block_number), logs.clone()).await;
@@ -223,7 +248,7 @@ async fn prepare_postgres(
             .unwrap();
 
         // Since we generate `logs` randomly, all of them are written the first time.
-        create_l1_batch(conn, L1BatchNumber(block_number), &logs).await;
+        create_l1_batch(conn, &mut tree, L1BatchNumber(block_number), &logs).await;
 
         if block_number + 1 < block_count {
             let factory_deps =
@@ -572,3 +597,233 @@ async fn creator_fails_if_specified_l1_batch_is_missing() {
         .await
         .unwrap_err();
 }
+
+#[tokio::test]
+async fn persisting_delta_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let base_l1_batch_number = L1BatchNumber(4);
+    let mut config = TEST_CONFIG;
+    config.l1_batch_number = Some(base_l1_batch_number);
+    config.delta = true;
+    // There are no snapshots yet, so a full snapshot should be created.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config.clone(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let base_snapshot = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(base_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("no base snapshot");
+    assert!(!base_snapshot.is_delta());
+    assert_eq!(base_snapshot.version, SnapshotVersion::Version1);
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    config.l1_batch_number = Some(snapshot_l1_batch_number);
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("no delta snapshot");
+    assert!(snapshot.is_complete());
+    assert_eq!(snapshot.base_l1_batch_number, Some(base_l1_batch_number));
+    assert_eq!(snapshot.version, SnapshotVersion::Version2);
+
+    // Since all generated keys are unique, the delta should contain exactly the logs written after the base snapshot.
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        actual_logs.extend(chunk.storage_logs);
+    }
+    let expected_logs: HashSet<_> = expected_outputs
+        .storage_logs
+        .iter()
+        .filter(|log| {
+            log.l1_batch_number_of_initial_write > base_l1_batch_number
+                && log.l1_batch_number_of_initial_write <= snapshot_l1_batch_number
+        })
+        .cloned()
+        .collect();
+    assert_eq!(actual_logs, expected_logs);
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(factory_deps.len(), 40); // 10 deps for each of 4 L2 blocks
+    for dep in &factory_deps {
+        assert!(expected_outputs.deps.contains(dep));
+    }
+}
+
+#[tokio::test]
+async fn delta_snapshot_chain_length_is_limited() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let mut config = TEST_CONFIG;
+    config.delta = true;
+    config.max_delta_chain_length = 1;
+    for (l1_batch_number, expected_base) in [(2, None), (4, Some(2)), (6, None), (8, Some(6))] {
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+        config.l1_batch_number = Some(l1_batch_number);
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+            .run(config.clone(), MIN_CHUNK_COUNT)
+            .await
+            .unwrap();
+
+        let snapshot = conn
+            .snapshots_dal()
+            .get_snapshot_metadata(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no snapshot");
+        assert_eq!(
+            snapshot.base_l1_batch_number,
+            expected_base.map(L1BatchNumber)
+        );
+        if !snapshot.is_delta() {
+            assert_storage_logs(&*object_store, l1_batch_number, &expected_outputs).await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn verifying_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    let verifier = SnapshotVerifier {
+        blob_store: object_store.clone(),
+        pool: pool.clone(),
+    };
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let report = verifier.verify(None, temp_dir.path()).await.unwrap();
+    assert!(report.is_ok(), "{report:#?}");
+    assert_eq!(report.l1_batch_number, snapshot_l1_batch_number);
+    assert_eq!(report.snapshot_chain, [snapshot_l1_batch_number]);
+    assert_eq!(report.chunks.len(), MIN_CHUNK_COUNT as usize);
+    assert_eq!(report.key_count, expected_outputs.storage_logs.len() as u64);
+
+    // Corrupt the snapshot by duplicating a log in another chunk.
+    let key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: snapshot_l1_batch_number,
+        chunk_id: 0,
+    };
+    let mut chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+    let other_key = SnapshotStorageLogsStorageKey { chunk_id: 1, ..key };
+    let other_chunk: SnapshotStorageLogsChunk = object_store.get(other_key).await.unwrap();
+    let duplicate_log = other_chunk.storage_logs[0].clone();
+    chunk.storage_logs.push(duplicate_log.clone());
+    object_store.put(key, &chunk).await.unwrap();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let report = verifier
+        .verify(Some(snapshot_l1_batch_number), temp_dir.path())
+        .await
+        .unwrap();
+    assert!(!report.is_ok());
+    assert!(report.root_hash_matches());
+    assert_eq!(report.chunks[0].misplaced_key_count, 1);
+    assert_eq!(report.duplicate_logs, [duplicate_log]);
+
+    // Remove a log from the chunk altogether.
+    chunk.storage_logs.truncate(chunk.storage_logs.len() - 2);
+    object_store.put(key, &chunk).await.unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let report = verifier
+        .verify(Some(snapshot_l1_batch_number), temp_dir.path())
+        .await
+        .unwrap();
+    assert!(!report.root_hash_matches());
+    assert_eq!(report.missing_index_count, 1);
+}
+
+#[tokio::test]
+async fn exporting_snapshot_to_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    let exporter = SnapshotExporter {
+        blob_store: object_store.clone(),
+        pool: pool.clone(),
+    };
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.archive");
+    let metadata = exporter.export(None, &archive_path).await.unwrap();
+    assert_eq!(metadata.snapshots.len(), 1);
+    let header = &metadata.snapshots[0];
+    assert_eq!(header.l1_batch_number, snapshot_l1_batch_number);
+    assert_eq!(header.base_l1_batch_number, None);
+    assert_eq!(header.storage_logs_chunks.len(), MIN_CHUNK_COUNT as usize);
+    // Main node data necessary for recovery must be included into the archive.
+    assert_eq!(metadata.blocks.len(), 1);
+    assert_eq!(metadata.blocks[0].l1_batch.number, snapshot_l1_batch_number);
+    assert_eq!(metadata.blocks[0].l2_block.number, header.l2_block_number);
+    assert_eq!(
+        metadata.blocks[0].l2_block.l1_batch_number,
+        snapshot_l1_batch_number
+    );
+
+    let archive = ArchiveObjectStore::open(&archive_path).await.unwrap();
+    assert_eq!(archive.object_count(), MIN_CHUNK_COUNT as usize + 1);
+    let archived_metadata: SnapshotArchiveMetadata =
+        serde_json::from_value(archive.metadata().clone()).unwrap();
+    assert_eq!(
+        archived_metadata.snapshots[0].storage_logs_chunks,
+        header.storage_logs_chunks
+    );
+    assert_eq!(
+        archived_metadata.blocks[0].l2_block.number,
+        header.l2_block_number
+    );
+
+    let archive: Arc<dyn ObjectStore> = Arc::new(archive);
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let expected_chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        let chunk: SnapshotStorageLogsChunk = archive.get(key).await.unwrap();
+        assert_eq!(chunk.storage_logs, expected_chunk.storage_logs);
+    }
+    let expected_deps: SnapshotFactoryDependencies =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    let deps: SnapshotFactoryDependencies = archive.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(deps.factory_deps, expected_deps.factory_deps);
+}
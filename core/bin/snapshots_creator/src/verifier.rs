@@ -0,0 +1,426 @@
+//! Offline verification of snapshots against the Merkle tree root hash of the snapshot L1 batch.
+
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use sha2::{Digest, Sha256};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_merkle_tree::{recovery::MerkleTreeRecovery, RocksDBWrapper, TreeEntry};
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    snapshots::{
+        uniform_hashed_keys_chunk, SnapshotMetadata, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey, SnapshotVersion,
+    },
+    L1BatchNumber, StorageKey, H256, U256,
+};
+
+/// Maximum number of problematic entries / indices included into a [`SnapshotVerificationReport`].
+const MAX_REPORTED_ENTRIES: usize = 100;
+
+/// Report for a single storage logs chunk.
+#[derive(Debug)]
+pub(crate) struct ChunkReport {
+    /// L1 batch of the snapshot containing the chunk. Differs from the verified L1 batch for chunks of base snapshots
+    /// if a delta snapshot is verified.
+    pub l1_batch_number: L1BatchNumber,
+    pub chunk_id: u64,
+    pub log_count: usize,
+    /// SHA-256 digest of the chunk logs sorted by hashed key. Each log is encoded as `hashed_key || value ||
+    /// l1_batch_number_of_initial_write (4 bytes, BE) || enumeration_index (8 bytes, BE)`.
+    pub checksum: H256,
+    /// Number of logs with hashed keys outside the key range of the chunk.
+    pub misplaced_key_count: usize,
+}
+
+/// Results of verifying a snapshot.
+#[derive(Debug)]
+pub(crate) struct SnapshotVerificationReport {
+    pub l1_batch_number: L1BatchNumber,
+    /// L1 batches of the verified snapshots ordered from the full snapshot to the verified one.
+    pub snapshot_chain: Vec<L1BatchNumber>,
+    pub chunks: Vec<ChunkReport>,
+    /// Number of distinct keys in the snapshot.
+    pub key_count: u64,
+    /// Number of keys expected based on the Merkle tree data of the L1 batch.
+    pub expected_key_count: u64,
+    /// Root hash of the Merkle tree committed for the L1 batch.
+    pub expected_root_hash: H256,
+    /// Root hash of the Merkle tree rebuilt from the snapshot.
+    pub root_hash: H256,
+    /// Logs for keys or enumeration indices that were already present in the snapshot (capped).
+    pub duplicate_logs: Vec<SnapshotStorageLog>,
+    pub duplicate_log_count: usize,
+    /// Logs that are invalid on their own, e.g. have zero enumeration index (capped).
+    pub invalid_logs: Vec<SnapshotStorageLog>,
+    pub invalid_log_count: usize,
+    /// Enumeration indices not covered by the snapshot (capped).
+    pub missing_indices: Vec<u64>,
+    pub missing_index_count: u64,
+}
+
+impl SnapshotVerificationReport {
+    pub fn root_hash_matches(&self) -> bool {
+        self.root_hash == self.expected_root_hash
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.root_hash_matches()
+            && self.key_count == self.expected_key_count
+            && self.duplicate_log_count == 0
+            && self.invalid_log_count == 0
+            && self.missing_index_count == 0
+            && self
+                .chunks
+                .iter()
+                .all(|chunk| chunk.misplaced_key_count == 0)
+    }
+
+    fn report_duplicate(&mut self, log: &SnapshotStorageLog) {
+        tracing::warn!("Duplicate storage log in snapshot: {log:?}");
+        self.duplicate_log_count += 1;
+        if self.duplicate_logs.len() < MAX_REPORTED_ENTRIES {
+            self.duplicate_logs.push(log.clone());
+        }
+    }
+
+    fn report_invalid(&mut self, log: &SnapshotStorageLog) {
+        tracing::warn!("Invalid storage log in snapshot: {log:?}");
+        self.invalid_log_count += 1;
+        if self.invalid_logs.len() < MAX_REPORTED_ENTRIES {
+            self.invalid_logs.push(log.clone());
+        }
+    }
+}
+
+/// Tracks enumeration indices of the processed storage logs as a bitset.
+#[derive(Debug)]
+struct EnumerationIndices {
+    /// Bit `i` is set if index `i + 1` was seen.
+    seen_words: Vec<u64>,
+    len: u64,
+}
+
+impl EnumerationIndices {
+    fn new(expected_key_count: u64) -> Self {
+        Self {
+            seen_words: vec![0; expected_key_count.div_ceil(64) as usize],
+            len: expected_key_count,
+        }
+    }
+
+    /// Returns `None` if the index is out of bounds, or whether the index was seen before.
+    fn insert(&mut self, index: u64) -> Option<bool> {
+        let bit = index.checked_sub(1).filter(|&bit| bit < self.len)?;
+        let word = &mut self.seen_words[(bit / 64) as usize];
+        let mask = 1_u64 << (bit % 64);
+        let was_seen = *word & mask != 0;
+        *word |= mask;
+        Some(was_seen)
+    }
+
+    fn missing(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..self.len)
+            .filter(|&bit| self.seen_words[(bit / 64) as usize] & (1 << (bit % 64)) == 0)
+            .map(|bit| bit + 1)
+    }
+}
+
+/// Returns the snapshot chain ordered from the full snapshot to the snapshot for `l1_batch_number`.
+pub(crate) async fn resolve_snapshot_chain(
+    conn: &mut zksync_dal::Connection<'_, Core>,
+    l1_batch_number: L1BatchNumber,
+) -> anyhow::Result<Vec<SnapshotMetadata>> {
+    let mut chain = vec![];
+    let mut next_l1_batch_number = Some(l1_batch_number);
+    while let Some(number) = next_l1_batch_number {
+        let snapshot = conn
+            .snapshots_dal()
+            .get_snapshot_metadata(number)
+            .await?
+            .with_context(|| format!("no snapshot for L1 batch #{number} in Postgres"))?;
+        anyhow::ensure!(
+            snapshot.is_complete(),
+            "snapshot for L1 batch #{number} is incomplete"
+        );
+        next_l1_batch_number = snapshot.base_l1_batch_number;
+        chain.push(snapshot);
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Verifies storage snapshots by rebuilding the Merkle tree from them.
+#[derive(Debug)]
+pub(crate) struct SnapshotVerifier {
+    pub blob_store: Arc<dyn ObjectStore>,
+    pub pool: ConnectionPool<Core>,
+}
+
+impl SnapshotVerifier {
+    /// Verifies the snapshot for the specified L1 batch (or the newest complete snapshot if not specified).
+    /// The Merkle tree is rebuilt in a RocksDB instance at `tree_path`, which should be an empty directory.
+    pub async fn verify(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+        tree_path: &Path,
+    ) -> anyhow::Result<SnapshotVerificationReport> {
+        let mut conn = self.pool.connection_tagged("snapshots_creator").await?;
+        let l1_batch_number = if let Some(number) = l1_batch_number {
+            number
+        } else {
+            let snapshots = conn.snapshots_dal().get_all_complete_snapshots().await?;
+            *snapshots
+                .snapshots_l1_batch_numbers
+                .first()
+                .context("no complete snapshots in Postgres")?
+        };
+        let chain = resolve_snapshot_chain(&mut conn, l1_batch_number).await?;
+        let tree_data = conn
+            .blocks_dal()
+            .get_l1_batch_tree_data(l1_batch_number)
+            .await?
+            .with_context(|| format!("no Merkle tree data for L1 batch #{l1_batch_number}"))?;
+        drop(conn);
+
+        let expected_key_count = tree_data.rollup_last_leaf_index.saturating_sub(1);
+        tracing::info!(
+            "Verifying snapshot for L1 batch #{l1_batch_number} (snapshot chain: {:?}); expecting {expected_key_count} keys \
+             and root hash {:?}",
+            chain.iter().map(|snapshot| snapshot.l1_batch_number).collect::<Vec<_>>(),
+            tree_data.hash
+        );
+
+        let mut report = SnapshotVerificationReport {
+            l1_batch_number,
+            snapshot_chain: chain
+                .iter()
+                .map(|snapshot| snapshot.l1_batch_number)
+                .collect(),
+            chunks: vec![],
+            key_count: 0,
+            expected_key_count,
+            expected_root_hash: tree_data.hash,
+            root_hash: H256::zero(),
+            duplicate_logs: vec![],
+            duplicate_log_count: 0,
+            invalid_logs: vec![],
+            invalid_log_count: 0,
+            missing_indices: vec![],
+            missing_index_count: 0,
+        };
+
+        // Logs from delta snapshots override logs from their base snapshots. Delta snapshots are expected to be small,
+        // so we keep them in memory and merge them into the full snapshot chunks.
+        let (full_snapshot, deltas) = chain.split_first().unwrap(); // `unwrap()` is safe: the chain is never empty
+        let mut delta_logs = HashMap::new();
+        for delta in deltas {
+            let mut logs_in_delta = HashMap::new();
+            for chunk_id in 0..delta.storage_logs_filepaths.len() as u64 {
+                let logs = self.load_chunk(delta, chunk_id, &mut report).await?;
+                for log in logs {
+                    if logs_in_delta.insert(log.key, log.clone()).is_some() {
+                        report.report_duplicate(&log);
+                    }
+                }
+            }
+            delta_logs.extend(logs_in_delta);
+        }
+
+        let db = RocksDBWrapper::new(tree_path).with_context(|| {
+            format!(
+                "failed opening RocksDB for Merkle tree at `{}`",
+                tree_path.display()
+            )
+        })?;
+        let mut recovery = MerkleTreeRecovery::new(db, l1_batch_number.0.into())?;
+        let mut indices = EnumerationIndices::new(expected_key_count);
+        for chunk_id in 0..full_snapshot.storage_logs_filepaths.len() as u64 {
+            let logs = self
+                .load_chunk(full_snapshot, chunk_id, &mut report)
+                .await?;
+            let logs = logs
+                .into_iter()
+                .map(|log| delta_logs.remove(&log.key).unwrap_or(log));
+            let entries = Self::check_logs(logs, l1_batch_number, &mut indices, &mut report);
+            recovery = Self::extend_tree(recovery, entries).await?;
+        }
+        // Remaining delta logs correspond to keys initially written after the full snapshot.
+        let entries = Self::check_logs(
+            delta_logs.into_values(),
+            l1_batch_number,
+            &mut indices,
+            &mut report,
+        );
+        recovery = Self::extend_tree(recovery, entries).await?;
+
+        report.root_hash = recovery.root_hash();
+        report.missing_index_count = indices.missing().count() as u64;
+        report.missing_indices = indices.missing().take(MAX_REPORTED_ENTRIES).collect();
+
+        if report.is_ok() {
+            tracing::info!(
+                "Snapshot for L1 batch #{l1_batch_number} is valid: root hash {:?} matches the L1 batch",
+                report.root_hash
+            );
+        } else {
+            tracing::error!(
+                "Snapshot for L1 batch #{l1_batch_number} is invalid: computed root hash {:?} (expected {:?}), \
+                 {} keys (expected {expected_key_count}), {} duplicate logs, {} invalid logs, {} missing indices",
+                report.root_hash,
+                report.expected_root_hash,
+                report.key_count,
+                report.duplicate_log_count,
+                report.invalid_log_count,
+                report.missing_index_count
+            );
+        }
+        Ok(report)
+    }
+
+    async fn load_chunk(
+        &self,
+        snapshot: &SnapshotMetadata,
+        chunk_id: u64,
+        report: &mut SnapshotVerificationReport,
+    ) -> anyhow::Result<Vec<SnapshotStorageLog>> {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot.l1_batch_number,
+            chunk_id,
+        };
+        let mut logs =
+            match snapshot.version {
+                SnapshotVersion::Version0 => {
+                    let chunk: SnapshotStorageLogsChunk<StorageKey> =
+                        self.blob_store.get(key).await.with_context(|| {
+                            format!("failed loading storage logs chunk {key:?}")
+                        })?;
+                    chunk
+                        .storage_logs
+                        .into_iter()
+                        .map(SnapshotStorageLog::drop_key_preimage)
+                        .collect()
+                }
+                SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
+                    let chunk: SnapshotStorageLogsChunk =
+                        self.blob_store.get(key).await.with_context(|| {
+                            format!("failed loading storage logs chunk {key:?}")
+                        })?;
+                    chunk.storage_logs
+                }
+            };
+        logs.sort_unstable_by_key(|log| log.key);
+        // Chunks have non-overlapping key ranges, so duplicate keys in different chunks are reported as misplaced.
+        logs.dedup_by(|log, prev_log| {
+            let is_duplicate = log.key == prev_log.key;
+            if is_duplicate {
+                report.report_duplicate(log);
+            }
+            is_duplicate
+        });
+
+        let chunk_count = snapshot.storage_logs_filepaths.len() as u64;
+        let key_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+        let misplaced_key_count = logs
+            .iter()
+            .filter(|log| !key_range.contains(&log.key))
+            .count();
+        if misplaced_key_count > 0 {
+            tracing::warn!(
+                "Storage logs chunk {key:?} contains {misplaced_key_count} logs outside its key range {key_range:?}"
+            );
+        }
+
+        let mut hasher = Sha256::new();
+        for log in &logs {
+            hasher.update(log.key.as_bytes());
+            hasher.update(log.value.as_bytes());
+            hasher.update(log.l1_batch_number_of_initial_write.0.to_be_bytes());
+            hasher.update(log.enumeration_index.to_be_bytes());
+        }
+        let chunk_report = ChunkReport {
+            l1_batch_number: snapshot.l1_batch_number,
+            chunk_id,
+            log_count: logs.len(),
+            checksum: H256(hasher.finalize().into()),
+            misplaced_key_count,
+        };
+        tracing::info!("Loaded storage logs chunk: {chunk_report:?}");
+        report.chunks.push(chunk_report);
+        Ok(logs)
+    }
+
+    fn check_logs(
+        logs: impl Iterator<Item = SnapshotStorageLog>,
+        l1_batch_number: L1BatchNumber,
+        indices: &mut EnumerationIndices,
+        report: &mut SnapshotVerificationReport,
+    ) -> Vec<TreeEntry> {
+        let mut entries = vec![];
+        for log in logs {
+            if log.l1_batch_number_of_initial_write > l1_batch_number {
+                report.report_invalid(&log);
+                continue;
+            }
+            match indices.insert(log.enumeration_index) {
+                None => {
+                    report.report_invalid(&log);
+                    continue;
+                }
+                Some(true) => {
+                    report.report_duplicate(&log);
+                    continue;
+                }
+                Some(false) => { /* OK */ }
+            }
+
+            report.key_count += 1;
+            entries.push(TreeEntry::new(
+                U256::from_little_endian(log.key.as_bytes()),
+                log.enumeration_index,
+                log.value,
+            ));
+        }
+        entries
+    }
+
+    async fn extend_tree(
+        mut recovery: MerkleTreeRecovery<RocksDBWrapper>,
+        entries: Vec<TreeEntry>,
+    ) -> anyhow::Result<MerkleTreeRecovery<RocksDBWrapper>> {
+        if entries.is_empty() {
+            return Ok(recovery);
+        }
+        tokio::task::spawn_blocking(move || {
+            recovery.extend_random(entries)?;
+            Ok(recovery)
+        })
+        .await
+        .context("panicked while extending Merkle tree")?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumeration_indices_basics() {
+        let mut indices = EnumerationIndices::new(130);
+        assert_eq!(indices.missing().count(), 130);
+
+        assert_eq!(indices.insert(0), None);
+        assert_eq!(indices.insert(131), None);
+        assert_eq!(indices.insert(u64::MAX), None);
+        for index in [1, 64, 65, 128, 130] {
+            assert_eq!(indices.insert(index), Some(false));
+            assert_eq!(indices.insert(index), Some(true));
+        }
+
+        let missing: Vec<_> = indices.missing().collect();
+        assert_eq!(missing.len(), 125);
+        assert_eq!(missing[..3], [2, 3, 4]);
+        assert!(!missing.contains(&64) && !missing.contains(&65) && !missing.contains(&130));
+        assert_eq!(*missing.last().unwrap(), 129);
+    }
+}
@@ -8,6 +8,7 @@ use zksync_config::{
 };
 use zksync_core_leftovers::Component;
 use zksync_default_da_clients::{
+    celestia::wiring_layer::CelestiaClientWiringLayer,
     no_da::wiring_layer::NoDAClientWiringLayer,
     object_store::{config::DAObjectStoreConfig, wiring_layer::ObjectStorageClientWiringLayer},
 };
@@ -19,9 +20,11 @@ use zksync_node_api_server::{
 use zksync_node_framework::{
     implementations::layers::{
         base_token::{
+            aggregated_price_client::AggregatedPriceClientLayer,
             base_token_ratio_persister::BaseTokenRatioPersisterLayer,
-            base_token_ratio_provider::BaseTokenRatioProviderLayer,
+            base_token_ratio_provider::BaseTokenRatioProviderLayer, cmc_client::CmcClientLayer,
             coingecko_client::CoingeckoClientLayer, forced_price_client::ForcedPriceClientLayer,
+            json_path_price_client::JsonPathPriceClientLayer,
             no_op_external_price_api_client::NoOpExternalPriceApiClientLayer,
         },
         circuit_breaker_checker::CircuitBreakerCheckerLayer,
@@ -49,6 +52,7 @@ use zksync_node_framework::{
         prometheus_exporter::PrometheusExporterLayer,
         proof_data_handler::ProofDataHandlerLayer,
         query_eth_client::QueryEthClientLayer,
+        remote_signing_eth_client::RemoteSigningEthClientLayer,
         sigint::SigintHandlerLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
@@ -140,21 +144,36 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_pk_signing_client_layer(mut self) -> anyhow::Result<Self> {
+    fn add_signing_client_layer(mut self) -> anyhow::Result<Self> {
         let eth_config = try_load_config!(self.configs.eth);
-        let wallets = try_load_config!(self.wallets.eth_sender);
-        self.node.add_layer(PKSigningEthClientLayer::new(
-            eth_config,
-            self.contracts_config.clone(),
-            self.genesis_config.settlement_layer_id(),
-            wallets,
-        ));
+        if let Some(remote_signer_config) = eth_config.remote_signer.clone() {
+            self.node.add_layer(RemoteSigningEthClientLayer::new(
+                eth_config,
+                self.contracts_config.clone(),
+                self.genesis_config.settlement_layer_id(),
+                remote_signer_config,
+            ));
+        } else {
+            let wallets = try_load_config!(self.wallets.eth_sender);
+            self.node.add_layer(PKSigningEthClientLayer::new(
+                eth_config,
+                self.contracts_config.clone(),
+                self.genesis_config.settlement_layer_id(),
+                wallets,
+            ));
+        }
         Ok(self)
     }
 
     fn add_query_eth_client_layer(mut self) -> anyhow::Result<Self> {
         let genesis = self.genesis_config.clone();
         let eth_config = try_load_config!(self.secrets.l1);
+        let client_config = self
+            .configs
+            .eth
+            .as_ref()
+            .and_then(|x| x.client.clone())
+            .unwrap_or_default();
         let query_eth_client_layer = QueryEthClientLayer::new(
             genesis.settlement_layer_id(),
             eth_config.l1_rpc_url,
@@ -163,7 +182,9 @@ impl MainNodeBuilder {
                 .as_ref()
                 .and_then(|x| Some(x.gas_adjuster?.settlement_mode))
                 .unwrap_or(SettlementMode::SettlesToL1),
-        );
+        )
+        .with_fallback_urls(eth_config.fallback_l1_rpc_urls)
+        .with_client_config(client_config);
         self.node.add_layer(query_eth_client_layer);
         Ok(self)
     }
@@ -307,9 +328,7 @@ impl MainNodeBuilder {
             latest_values_cache_size: rpc_config.latest_values_cache_size() as u64,
         };
 
-        // On main node we always use master pool sink.
-        self.node.add_layer(MasterPoolSinkLayer);
-        self.node.add_layer(TxSenderLayer::new(
+        let mut tx_sender_layer = TxSenderLayer::new(
             TxSenderConfig::new(
                 &sk_config,
                 &rpc_config,
@@ -321,7 +340,22 @@ impl MainNodeBuilder {
             postgres_storage_caches_config,
             rpc_config.vm_concurrency_limit(),
             ApiContracts::load_from_disk_blocking(), // TODO (BFT-138): Allow to dynamically reload API contracts
+        );
+        let state_archive_path = self
+            .configs
+            .db_config
+            .as_ref()
+            .and_then(|config| config.experimental.state_archive_path.as_ref());
+        if let Some(path) = state_archive_path {
+            tx_sender_layer = tx_sender_layer.with_state_archive(path.into());
+        }
+
+        // On main node we always use master pool sink.
+        let mempool_config = try_load_config!(self.configs.mempool_config);
+        self.node.add_layer(MasterPoolSinkLayer::new(
+            mempool_config.min_replacement_fee_bump_percent,
         ));
+        self.node.add_layer(tx_sender_layer);
         Ok(self)
     }
 
@@ -365,6 +399,7 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            http_rate_limits: rpc_config.http_rate_limits(),
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::http(
@@ -514,6 +549,20 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    /// Adds the Celestia DA client if it's configured, and falls back to the no-DA client otherwise.
+    fn add_da_client_layer(mut self) -> anyhow::Result<Self> {
+        let da_config = try_load_config!(self.configs.da_dispatcher_config);
+        let Some(celestia_config) = da_config.celestia else {
+            return self.add_no_da_client_layer();
+        };
+        let celestia_secrets = self.secrets.celestia.clone();
+        self.node.add_layer(CelestiaClientWiringLayer::new(
+            celestia_config,
+            celestia_secrets,
+        ));
+        Ok(self)
+    }
+
     #[allow(dead_code)]
     fn add_object_storage_da_client_layer(mut self) -> anyhow::Result<Self> {
         let object_store_config = DAObjectStoreConfig::from_env()?;
@@ -531,12 +580,9 @@ impl MainNodeBuilder {
             }
         }
 
-        let state_keeper_config = try_load_config!(self.configs.state_keeper_config);
         let da_config = try_load_config!(self.configs.da_dispatcher_config);
-        self.node.add_layer(DataAvailabilityDispatcherLayer::new(
-            state_keeper_config,
-            da_config,
-        ));
+        self.node
+            .add_layer(DataAvailabilityDispatcherLayer::new(da_config));
 
         Ok(self)
     }
@@ -564,6 +610,15 @@ impl MainNodeBuilder {
             ForcedPriceClientLayer::CLIENT_NAME => {
                 self.node.add_layer(ForcedPriceClientLayer::new(config));
             }
+            CmcClientLayer::CLIENT_NAME => {
+                self.node.add_layer(CmcClientLayer::new(config));
+            }
+            JsonPathPriceClientLayer::CLIENT_NAME => {
+                self.node.add_layer(JsonPathPriceClientLayer::new(config));
+            }
+            AggregatedPriceClientLayer::CLIENT_NAME => {
+                self.node.add_layer(AggregatedPriceClientLayer::new(config));
+            }
             _ => {
                 anyhow::bail!(
                     "Unknown external price API client source: {}",
@@ -735,7 +790,7 @@ impl MainNodeBuilder {
                 }
                 Component::EthTxAggregator => {
                     self = self
-                        .add_pk_signing_client_layer()?
+                        .add_signing_client_layer()?
                         .add_eth_tx_aggregator_layer()?;
                 }
                 Component::EthTxManager => {
@@ -759,7 +814,7 @@ impl MainNodeBuilder {
                     self = self.add_commitment_generator_layer()?;
                 }
                 Component::DADispatcher => {
-                    self = self.add_no_da_client_layer()?.add_da_dispatcher_layer()?;
+                    self = self.add_da_client_layer()?.add_da_dispatcher_layer()?;
                 }
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
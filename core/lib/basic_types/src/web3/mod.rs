@@ -734,6 +734,20 @@ pub struct TransactionReceipt {
     /// Effective gas price
     #[serde(rename = "effectiveGasPrice")]
     pub effective_gas_price: Option<U256>,
+    /// Blob gas used by this transaction (only for EIP-4844 transactions).
+    #[serde(
+        rename = "blobGasUsed",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_used: Option<U256>,
+    /// Blob gas price paid by this transaction (only for EIP-4844 transactions).
+    #[serde(
+        rename = "blobGasPrice",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_price: Option<U256>,
 }
 
 /// Data for offline signed transaction
@@ -2,13 +2,19 @@
 
 use std::time::Duration;
 
-use vise::{Gauge, Global, Metrics};
+use vise::{Gauge, Global, LabeledFamily, Metrics};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "circuit_breaker")]
 pub(crate) struct CircuitBreakerMetrics {
     /// Replication lag for Postgres in seconds.
     pub replication_lag: Gauge<Duration>,
+    /// Replication lag for read-only Postgres replicas in seconds.
+    #[metrics(labels = ["replica"])]
+    pub replica_lag: LabeledFamily<usize, Gauge<Duration>>,
+    /// Whether a read-only Postgres replica is reachable (1) or not (0).
+    #[metrics(labels = ["replica"])]
+    pub replica_healthy: LabeledFamily<usize, Gauge<u64>>,
 }
 
 #[vise::register]
@@ -1,9 +1,26 @@
 use std::time::Duration;
 
+use anyhow::Context as _;
+use tokio::sync::watch;
 use zksync_dal::{ConnectionPool, Core, CoreDal, DalError};
 
 use crate::{metrics::METRICS, CircuitBreaker, CircuitBreakerError};
 
+/// Queries replication lag of the database that `pool` connects to.
+async fn get_replication_lag(
+    pool: &ConnectionPool<Core>,
+    requester: &'static str,
+) -> Result<Duration, CircuitBreakerError> {
+    let lag = pool
+        .connection_tagged(requester)
+        .await?
+        .system_dal()
+        .get_replication_lag()
+        .await
+        .map_err(DalError::generalize)?;
+    Ok(lag)
+}
+
 #[derive(Debug)]
 pub struct ReplicationLagChecker {
     pub pool: ConnectionPool<Core>,
@@ -17,14 +34,7 @@ impl CircuitBreaker for ReplicationLagChecker {
     }
 
     async fn check(&self) -> Result<(), CircuitBreakerError> {
-        let lag = self
-            .pool
-            .connection_tagged("circuit_breaker")
-            .await?
-            .system_dal()
-            .get_replication_lag()
-            .await
-            .map_err(DalError::generalize)?;
+        let lag = get_replication_lag(&self.pool, "circuit_breaker").await?;
         METRICS.replication_lag.set(lag);
 
         match self.replication_lag_limit {
@@ -35,3 +45,131 @@ impl CircuitBreaker for ReplicationLagChecker {
         }
     }
 }
+
+/// Periodically checks health and replication lag of read-only replicas that a connection pool
+/// load-balances connections across, and updates replica statuses accordingly. Replicas that cannot be
+/// reached or lag behind more than allowed by the replica set are excluded from routing.
+#[derive(Debug)]
+pub struct ReplicaSetMonitor {
+    pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+}
+
+impl ReplicaSetMonitor {
+    pub fn new(pool: ConnectionPool<Core>, poll_interval: Duration) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            pool.replica_set().is_some(),
+            "connection pool has no replicas to monitor"
+        );
+        Ok(Self {
+            pool,
+            poll_interval,
+        })
+    }
+
+    async fn check_replicas(&self) -> anyhow::Result<()> {
+        let replicas = self.pool.replica_set().context("no replicas")?;
+        for index in 0..replicas.len() {
+            let replica_pool = self.pool.replica_pool(index).context("no replica pool")?;
+            let lag = tokio::time::timeout(
+                self.poll_interval,
+                get_replication_lag(&replica_pool, "replica_set_monitor"),
+            )
+            .await;
+            match lag {
+                Ok(Ok(lag)) => {
+                    METRICS.replica_lag[&index].set(lag);
+                    METRICS.replica_healthy[&index].set(1);
+                    let was_eligible = replicas.is_eligible(index);
+                    replicas.set_lag(index, lag);
+                    match (was_eligible, replicas.is_eligible(index)) {
+                        (true, false) => tracing::warn!(
+                            "DB replica #{index} lags behind by {lag:?}; excluding it from routing"
+                        ),
+                        (false, true) => {
+                            tracing::info!(
+                                "DB replica #{index} is eligible for routing (lag: {lag:?})"
+                            );
+                        }
+                        _ => { /* no status change */ }
+                    }
+                }
+                Ok(Err(err)) => {
+                    tracing::warn!("Failed checking DB replica #{index}: {err}");
+                    METRICS.replica_healthy[&index].set(0);
+                    replicas.mark_unhealthy(index);
+                }
+                Err(_) => {
+                    tracing::warn!("Timed out checking DB replica #{index}");
+                    METRICS.replica_healthy[&index].set(0);
+                    replicas.mark_unhealthy(index);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Monitoring DB replicas with poll interval {:?}",
+            self.poll_interval
+        );
+        while !*stop_receiver.borrow_and_update() {
+            self.check_replicas().await?;
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Received a stop signal; replica set monitor is shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_db_connection::replicas::{ReplicaSet, ReplicaStatus};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn monitoring_replicas() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let db_url = pool.database_url().clone();
+        assert!(ReplicaSetMonitor::new(pool, Duration::from_secs(1)).is_err());
+
+        let mut unreachable_url = db_url.expose_url().clone();
+        unreachable_url.set_port(Some(1)).unwrap();
+        let replicas = ReplicaSet::new(
+            vec![db_url.clone(), unreachable_url.into()],
+            Duration::from_secs(5),
+        );
+        let pool = ConnectionPool::<Core>::builder(db_url, 2)
+            .set_replicas(replicas.clone())
+            .build()
+            .await
+            .unwrap();
+        let monitor = ReplicaSetMonitor::new(pool, Duration::from_secs(5)).unwrap();
+
+        monitor.check_replicas().await.unwrap();
+        assert_eq!(
+            replicas.status(0),
+            ReplicaStatus::Healthy {
+                lag: Duration::ZERO
+            }
+        );
+        assert!(replicas.is_eligible(0));
+        assert_eq!(replicas.status(1), ReplicaStatus::Unhealthy);
+
+        // Check that the monitor updates statuses when run as a task.
+        replicas.mark_unhealthy(0);
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let monitor_task = tokio::spawn(monitor.run(stop_receiver));
+        while !replicas.is_eligible(0) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(replicas.status(1), ReplicaStatus::Unhealthy);
+        stop_sender.send_replace(true);
+        monitor_task.await.unwrap().unwrap();
+    }
+}
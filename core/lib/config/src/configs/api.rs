@@ -1,8 +1,9 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     num::{NonZeroU32, NonZeroUsize},
+    path::PathBuf,
     str::FromStr,
     time::Duration,
 };
@@ -132,6 +133,123 @@ pub struct MaxResponseSize {
     pub overrides: MaxResponseSizeOverrides,
 }
 
+/// Cost weights of RPC methods used by HTTP rate limiting. Methods not mentioned in the weights have unit cost.
+///
+/// A method name may end with `*`, in which case the weight applies to all methods with the specified prefix
+/// (e.g., `debug_*`). Exact method names take precedence over prefixes; among prefixes, the longest one wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcMethodCosts(HashMap<String, NonZeroU32>);
+
+impl<S: Into<String>> FromIterator<(S, NonZeroU32)> for RpcMethodCosts {
+    fn from_iter<I: IntoIterator<Item = (S, NonZeroU32)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(method_name, cost)| (method_name.into(), cost))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for RpcMethodCosts {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut costs = HashMap::new();
+        for part in s.split(',') {
+            let (method_name, cost) = part
+                .split_once('=')
+                .with_context(|| format!("Part `{part}` doesn't have form <method_name>=<int>"))?;
+            let method_name = method_name.trim();
+            let cost = cost.trim();
+            let cost: NonZeroU32 = cost.parse().with_context(|| {
+                format!("`{cost}` specified for method `{method_name}` is not a valid cost")
+            })?;
+
+            if let Some(prev_cost) = costs.insert(method_name.to_owned(), cost) {
+                anyhow::bail!("Cost for `{method_name}` is redefined from {prev_cost} to {cost}");
+            }
+        }
+        Ok(Self(costs))
+    }
+}
+
+impl RpcMethodCosts {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Returns the cost of the specified method.
+    pub fn get(&self, method_name: &str) -> NonZeroU32 {
+        if let Some(&cost) = self.0.get(method_name) {
+            return cost;
+        }
+        let prefix_match = self.0.iter().filter_map(|(pattern, &cost)| {
+            let prefix = pattern.strip_suffix('*')?;
+            method_name
+                .starts_with(prefix)
+                .then_some((prefix.len(), cost))
+        });
+        prefix_match
+            .max_by_key(|(prefix_len, _)| *prefix_len)
+            .map_or(NonZeroU32::MIN, |(_, cost)| cost)
+    }
+
+    /// Iterates over all specified costs.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, NonZeroU32)> + '_ {
+        self.0
+            .iter()
+            .map(|(method_name, &cost)| (method_name.as_str(), cost))
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcMethodCosts {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParseVisitor;
+
+        impl<'v> de::Visitor<'v> for ParseVisitor {
+            type Value = RpcMethodCosts;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str(
+                    "comma-separated list of <method_name>=<cost> tuples, such as: debug_*=20,eth_getLogs=5",
+                )
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ParseVisitor)
+    }
+}
+
+/// Rate limits for the HTTP JSON-RPC server.
+#[derive(Debug, Clone)]
+pub struct HttpRateLimits {
+    /// Maximum number of requests per minute for a single client IP address. Only applies to requests without an API key.
+    pub requests_per_minute_per_ip: Option<NonZeroU32>,
+    /// Maximum number of requests per minute for a single API key.
+    pub requests_per_minute_per_api_key: Option<NonZeroU32>,
+    /// Recognized API keys.
+    pub api_keys: HashSet<String>,
+    /// Name of the HTTP header containing an API key.
+    pub api_key_header: String,
+    /// URL path prefix under which API keys may be supplied as the path (e.g., `/key/`). If not set, the URL path
+    /// is never interpreted as an API key.
+    pub api_key_path_prefix: Option<String>,
+    /// Name of the HTTP header containing the client IP address, as set by trusted proxies. If not set,
+    /// the client is identified by the peer address of its connection.
+    pub client_ip_header: Option<String>,
+    /// Addresses of reverse proxies trusted to set `client_ip_header`.
+    pub trusted_proxies: HashSet<IpAddr>,
+    /// Costs of RPC methods measured in requests.
+    pub method_costs: RpcMethodCosts,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Web3JsonRpcConfig {
     /// Port to which the HTTP RPC server is listening.
@@ -187,6 +305,15 @@ pub struct Web3JsonRpcConfig {
     pub latest_values_cache_size_mb: Option<usize>,
     /// Limit for fee history block range.
     pub fee_history_limit: Option<u64>,
+    /// Maximum number of heap words recorded by the struct logger for a single instruction. Values requested
+    /// via `memoryLimit` in `debug_*` methods are clamped to this value. Default is 1024.
+    pub struct_logger_memory_limit: Option<usize>,
+    /// Maximum number of instructions recorded by the struct logger for a single transaction. Values requested
+    /// via `limit` in `debug_*` methods are clamped to this value. Default is 100000.
+    pub struct_logger_step_limit: Option<usize>,
+    /// Maximum total size in MiBs of data recorded by the struct logger for a single transaction. Once the limit
+    /// is reached, no more instructions are recorded. Default is 64 MiB.
+    pub struct_logger_size_limit_mb: Option<usize>,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     pub max_batch_request_size: Option<usize>,
     /// Maximum response body size in MiBs. Default is 10 MiB.
@@ -196,8 +323,34 @@ pub struct Web3JsonRpcConfig {
     pub max_response_body_size_overrides_mb: MaxResponseSizeOverrides,
     /// Maximum number of requests per minute for the WebSocket server.
     /// The value is per active connection.
-    /// Note: For HTTP, rate limiting is expected to be configured on the infra level.
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Maximum number of requests per minute for the HTTP server from a single client IP address.
+    /// Requests with a valid API key are not subject to this limit. If not set, IP-based rate limiting is disabled.
+    pub http_requests_per_minute_per_ip: Option<NonZeroU32>,
+    /// Maximum number of requests per minute for the HTTP server using a single API key.
+    /// If not set, requests with a valid API key are not rate-limited.
+    pub http_requests_per_minute_per_api_key: Option<NonZeroU32>,
+    /// API keys recognized by the HTTP server. A key can be supplied either in the header specified by
+    /// `http_api_key_header`, or in the URL path under `http_api_key_path_prefix`. Requests with an unknown key
+    /// are rejected.
+    #[serde(default)]
+    pub http_api_keys: Vec<String>,
+    /// Name of the HTTP header containing an API key. The default value is `x-api-key`.
+    pub http_api_key_header: Option<String>,
+    /// URL path prefix under which an API key can be supplied, e.g. `/key/` for `https://rpc.example.com/key/<key>`.
+    /// If not set, API keys can only be supplied in the header.
+    pub http_api_key_path_prefix: Option<String>,
+    /// Name of the HTTP header containing the client IP address (e.g., `x-forwarded-for`), as set by a reverse proxy
+    /// or load balancer. The header is only trusted if the request comes from one of `http_trusted_proxies`; the client
+    /// address is the right-most address in the header that doesn't belong to a trusted proxy. If not set,
+    /// clients are identified by the peer address of their connection.
+    pub http_client_ip_header: Option<String>,
+    /// IP addresses of reverse proxies trusted to set `http_client_ip_header`.
+    #[serde(default)]
+    pub http_trusted_proxies: Vec<IpAddr>,
+    /// Cost weights of RPC methods for HTTP rate limiting. By default, all methods have unit cost.
+    #[serde(default = "RpcMethodCosts::empty")]
+    pub rpc_method_costs: RpcMethodCosts,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
     /// Polling period for mempool cache update - how often the mempool cache is updated from the database.
@@ -244,10 +397,21 @@ impl Web3JsonRpcConfig {
             initial_writes_cache_size_mb: Default::default(),
             latest_values_cache_size_mb: Default::default(),
             fee_history_limit: Default::default(),
+            struct_logger_memory_limit: Default::default(),
+            struct_logger_step_limit: Default::default(),
+            struct_logger_size_limit_mb: Default::default(),
             max_batch_request_size: Default::default(),
             max_response_body_size_mb: Default::default(),
             max_response_body_size_overrides_mb: MaxResponseSizeOverrides::empty(),
             websocket_requests_per_minute_limit: Default::default(),
+            http_requests_per_minute_per_ip: None,
+            http_requests_per_minute_per_api_key: None,
+            http_api_keys: vec![],
+            http_api_key_header: None,
+            http_api_key_path_prefix: None,
+            http_client_ip_header: None,
+            http_trusted_proxies: vec![],
+            rpc_method_costs: RpcMethodCosts::empty(),
             mempool_cache_update_interval: Default::default(),
             mempool_cache_size: Default::default(),
             tree_api_url: None,
@@ -307,6 +471,19 @@ impl Web3JsonRpcConfig {
         self.fee_history_limit.unwrap_or(1024)
     }
 
+    pub fn struct_logger_memory_limit(&self) -> usize {
+        self.struct_logger_memory_limit.unwrap_or(1_024)
+    }
+
+    pub fn struct_logger_step_limit(&self) -> usize {
+        self.struct_logger_step_limit.unwrap_or(100_000)
+    }
+
+    /// Returns the maximum size of data recorded by the struct logger in bytes.
+    pub fn struct_logger_size_limit(&self) -> usize {
+        self.struct_logger_size_limit_mb.unwrap_or(64) * super::BYTES_IN_MEGABYTE
+    }
+
     pub fn max_batch_request_size(&self) -> usize {
         // The default limit is chosen to be reasonably permissive.
         self.max_batch_request_size.unwrap_or(500)
@@ -326,6 +503,29 @@ impl Web3JsonRpcConfig {
             .unwrap_or(NonZeroU32::new(6000).unwrap())
     }
 
+    /// Returns rate limits for the HTTP server, or `None` if rate limiting is disabled.
+    pub fn http_rate_limits(&self) -> Option<HttpRateLimits> {
+        if self.http_requests_per_minute_per_ip.is_none()
+            && self.http_requests_per_minute_per_api_key.is_none()
+            && self.http_api_keys.is_empty()
+        {
+            return None;
+        }
+        Some(HttpRateLimits {
+            requests_per_minute_per_ip: self.http_requests_per_minute_per_ip,
+            requests_per_minute_per_api_key: self.http_requests_per_minute_per_api_key,
+            api_keys: self.http_api_keys.iter().cloned().collect(),
+            api_key_header: self
+                .http_api_key_header
+                .clone()
+                .unwrap_or_else(|| "x-api-key".to_owned()),
+            api_key_path_prefix: self.http_api_key_path_prefix.clone(),
+            client_ip_header: self.http_client_ip_header.clone(),
+            trusted_proxies: self.http_trusted_proxies.iter().copied().collect(),
+            method_costs: self.rpc_method_costs.clone(),
+        })
+    }
+
     pub fn tree_api_url(&self) -> Option<&str> {
         self.tree_api_url.as_deref()
     }
@@ -385,6 +585,9 @@ pub struct MerkleTreeApiConfig {
     /// Port to bind the Merkle tree API server to.
     #[serde(default = "MerkleTreeApiConfig::default_port")]
     pub port: u16,
+    /// Directory in which tree checkpoints can be created via the API. If not set, checkpoint creation is disabled.
+    #[serde(default)]
+    pub checkpoints_dir: Option<PathBuf>,
 }
 
 impl MerkleTreeApiConfig {
@@ -416,4 +619,24 @@ mod tests {
         assert_eq!(scaled.get("zks_getProof"), Some(32_000));
         assert_eq!(scaled.get("eth_blockNumber"), None);
     }
+
+    #[test]
+    fn working_with_rpc_method_costs() {
+        let costs: RpcMethodCosts = "debug_*=20, debug_traceCall = 50,eth_getLogs=5,eth_*=2"
+            .parse()
+            .unwrap();
+        assert_eq!(costs.iter().len(), 4);
+        assert_eq!(costs.get("debug_traceCall").get(), 50);
+        assert_eq!(costs.get("debug_traceTransaction").get(), 20);
+        assert_eq!(costs.get("eth_getLogs").get(), 5);
+        assert_eq!(costs.get("eth_call").get(), 2);
+        assert_eq!(costs.get("zks_getProof").get(), 1);
+
+        let err = "eth_call=0".parse::<RpcMethodCosts>().unwrap_err();
+        assert!(format!("{err:#}").contains("not a valid cost"), "{err:#}");
+        let err = "eth_call=1,eth_call=2"
+            .parse::<RpcMethodCosts>()
+            .unwrap_err();
+        assert!(err.to_string().contains("redefined"), "{err}");
+    }
 }
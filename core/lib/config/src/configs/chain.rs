@@ -131,6 +131,21 @@ pub struct StateKeeperConfig {
     #[serde(default = "StateKeeperConfig::default_protective_reads_persistence_enabled")]
     pub protective_reads_persistence_enabled: bool,
 
+    /// Cumulative VM execution time (in ms) of transactions in an L1 batch that triggers the batch seal.
+    /// If not set, the batch execution time is not limited.
+    #[serde(default)]
+    pub max_l1_batch_execution_time_ms: Option<u64>,
+    /// Cumulative VM execution time (in ms) of transactions in an L2 block that triggers the block seal.
+    /// If not set, the L2 block execution time is not limited.
+    #[serde(default)]
+    pub max_l2_block_execution_time_ms: Option<u64>,
+    /// Maximum share (greater than 0, up to 1) of `max_gas_per_batch` that transactions calling a single contract
+    /// can use in an L1 batch. Once a transaction calling the contract exceeds the share, the L1 batch is sealed
+    /// and the transaction is moved to the next batch. The first transaction calling a contract in a batch is always
+    /// included. If not set, gas usage per contract is not limited.
+    #[serde(default)]
+    pub max_contract_gas_share: Option<f64>,
+
     // Base system contract hashes, required only for generating genesis config.
     // #PLA-811
     #[deprecated(note = "Use GenesisConfig::bootloader_hash instead")]
@@ -147,6 +162,29 @@ impl StateKeeperConfig {
         true
     }
 
+    /// Returns the cumulative VM execution time limit for an L1 batch.
+    pub fn max_l1_batch_execution_time(&self) -> Option<Duration> {
+        self.max_l1_batch_execution_time_ms
+            .map(Duration::from_millis)
+    }
+
+    /// Returns the cumulative VM execution time limit for an L2 block.
+    pub fn max_l2_block_execution_time(&self) -> Option<Duration> {
+        self.max_l2_block_execution_time_ms
+            .map(Duration::from_millis)
+    }
+
+    /// Checks the consistency of the config values.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(share) = self.max_contract_gas_share {
+            anyhow::ensure!(
+                share > 0.0 && share <= 1.0,
+                "`max_contract_gas_share` must be in (0, 1], got {share}"
+            );
+        }
+        Ok(())
+    }
+
     /// Creates a config object suitable for use in unit tests.
     /// Values mostly repeat the values used in the localhost environment.
     pub fn for_tests() -> Self {
@@ -179,6 +217,9 @@ impl StateKeeperConfig {
             save_call_traces: true,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            max_l1_batch_execution_time_ms: None,
+            max_l2_block_execution_time_ms: None,
+            max_contract_gas_share: None,
             bootloader_hash: None,
             default_aa_hash: None,
             l1_batch_commit_data_generator_mode: L1BatchCommitmentMode::Rollup,
@@ -221,6 +262,16 @@ impl CircuitBreakerConfig {
     }
 }
 
+/// Policy used to order L2 transactions from different accounts in the mempool.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+pub enum MempoolOrderingPolicy {
+    /// Transactions are ordered by the time they were received.
+    #[default]
+    Fifo,
+    /// Transactions with higher priority fee go first.
+    PriorityFee,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct MempoolConfig {
     pub sync_interval_ms: u64,
@@ -229,6 +280,17 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Policy used to order L2 transactions from different accounts.
+    #[serde(default)]
+    pub ordering_policy: MempoolOrderingPolicy,
+    /// Minimum bump (in percent) of both `max_fee_per_gas` and `max_priority_fee_per_gas` required to replace
+    /// a pending transaction with the same nonce. If not set, replacements are always accepted.
+    #[serde(default)]
+    pub min_replacement_fee_bump_percent: Option<u64>,
+    /// Whether to evict transactions of the accounts with the cheapest ready transactions once the mempool
+    /// reaches its capacity. If not set, only the transactions that cannot be executed are purged.
+    #[serde(default)]
+    pub evict_cheapest_on_capacity: bool,
 }
 
 impl MempoolConfig {
@@ -14,6 +14,43 @@ pub struct DADispatcherConfig {
     pub max_rows_to_dispatch: Option<u32>,
     /// The maximum number of retries for the dispatch of a blob.
     pub max_retries: Option<u16>,
+    /// Configuration of the Celestia DA client. If not set, pubdata is not posted to Celestia.
+    #[serde(default)]
+    pub celestia: Option<CelestiaConfig>,
+}
+
+/// Configuration of the client posting pubdata to a Celestia light / bridge node.
+/// The node auth token is a secret, so it's specified separately (see `CelestiaSecrets`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CelestiaConfig {
+    /// URL of the Celestia node JSON-RPC endpoint.
+    pub api_node_url: String,
+    /// Hex-encoded namespace ID (up to 10 bytes) used for the posted blobs.
+    pub namespace: String,
+    /// Gas price (in utia) used for blob submission transactions. If not set, the node estimates it.
+    pub gas_price: Option<f64>,
+    /// Timeout for a single request to the node in milliseconds.
+    #[serde(default = "CelestiaConfig::default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+    /// Maximum size of a single blob in bytes.
+    #[serde(default = "CelestiaConfig::default_blob_size_limit")]
+    pub blob_size_limit: usize,
+}
+
+impl CelestiaConfig {
+    pub const fn default_request_timeout_ms() -> u64 {
+        60_000
+    }
+
+    /// Slightly less than the maximum blob size accepted by Celestia with the default 2 MiB square size.
+    pub const fn default_blob_size_limit() -> usize {
+        1_900_000
+    }
+
+    /// Converts `self.request_timeout_ms` into `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
 }
 
 impl DADispatcherConfig {
@@ -22,6 +59,7 @@ impl DADispatcherConfig {
             polling_interval_ms: Some(DEFAULT_POLLING_INTERVAL_MS),
             max_rows_to_dispatch: Some(DEFAULT_MAX_ROWS_TO_DISPATCH),
             max_retries: Some(DEFAULT_MAX_RETRIES),
+            celestia: None,
         }
     }
 
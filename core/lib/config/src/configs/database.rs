@@ -145,6 +145,11 @@ pub struct PostgresConfig {
     pub long_connection_threshold_ms: Option<u64>,
     /// Threshold in milliseconds to denote a DB query as "slow" and log its details.
     pub slow_query_threshold_ms: Option<u64>,
+    /// Maximum replication lag in seconds for additional read-only replicas to receive connections.
+    /// If not specified, the default value of 10 seconds is used.
+    pub max_replica_lag_sec: Option<u64>,
+    /// Interval in milliseconds between health and replication lag checks for additional read-only replicas.
+    pub replica_health_check_interval_ms: Option<u64>,
     pub test_server_url: Option<String>,
     pub test_prover_url: Option<String>,
 }
@@ -176,4 +181,14 @@ impl PostgresConfig {
     pub fn slow_query_threshold(&self) -> Option<Duration> {
         self.slow_query_threshold_ms.map(Duration::from_millis)
     }
+
+    /// Returns the maximum replication lag for additional read-only replicas.
+    pub fn max_replica_lag(&self) -> Duration {
+        Duration::from_secs(self.max_replica_lag_sec.unwrap_or(10))
+    }
+
+    /// Returns the interval between health checks for additional read-only replicas.
+    pub fn replica_health_check_interval(&self) -> Duration {
+        Duration::from_millis(self.replica_health_check_interval_ms.unwrap_or(1_000))
+    }
 }
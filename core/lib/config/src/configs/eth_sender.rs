@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use anyhow::Context as _;
 use serde::Deserialize;
-use zksync_basic_types::{settlement::SettlementMode, H256};
+use zksync_basic_types::{settlement::SettlementMode, Address, H256};
 use zksync_crypto_primitives::K256PrivateKey;
 
 use crate::EthWatchConfig;
@@ -15,6 +15,10 @@ pub struct EthConfig {
     /// Options related to the `GasAdjuster` submodule.
     pub gas_adjuster: Option<GasAdjusterConfig>,
     pub watcher: Option<EthWatchConfig>,
+    /// Remote signer used to sign L1 transactions instead of private keys from the wallets config.
+    pub remote_signer: Option<RemoteSignerConfig>,
+    /// Options related to the L1 client, e.g. failover among multiple L1 providers.
+    pub client: Option<EthClientConfig>,
 }
 
 impl EthConfig {
@@ -42,6 +46,17 @@ impl EthConfig {
                 pubdata_sending_mode: PubdataSendingMode::Calldata,
                 tx_aggregation_paused: false,
                 tx_aggregation_only_prove_and_execute: false,
+                nonce_recovery_enabled: false,
+                stuck_tx_timeout_blocks: None,
+                max_underpriced_resends: None,
+                commit_hourly_budget_gwei: None,
+                commit_daily_budget_gwei: None,
+                prove_hourly_budget_gwei: None,
+                prove_daily_budget_gwei: None,
+                execute_hourly_budget_gwei: None,
+                execute_daily_budget_gwei: None,
+                non_urgent_base_fee_ceiling_gwei: None,
+                max_non_urgent_deferral_sec: None,
             }),
             gas_adjuster: Some(GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -62,6 +77,8 @@ impl EthConfig {
                 confirmations_for_eth_event: None,
                 eth_node_poll_interval: 0,
             }),
+            remote_signer: None,
+            client: None,
         }
     }
 }
@@ -127,6 +144,33 @@ pub struct SenderConfig {
     /// special mode specifically for gateway migration to decrease number of non-executed batches
     #[serde(default = "SenderConfig::default_tx_aggregation_only_prove_and_execute")]
     pub tx_aggregation_only_prove_and_execute: bool,
+    /// Enables recovery of the operator nonce: nonce gaps are filled with zero-value self-transfers,
+    /// stuck transactions are cancelled, and transactions whose nonce was consumed by another transaction are re-queued.
+    #[serde(default)]
+    pub nonce_recovery_enabled: bool,
+    /// Number of L1 blocks after which a transaction blocking the operator nonce is considered stuck and is cancelled.
+    /// Only has effect if `nonce_recovery_enabled` is set.
+    pub stuck_tx_timeout_blocks: Option<u64>,
+    /// Number of consecutive "replacement transaction underpriced" errors after which a transaction is cancelled.
+    /// Only has effect if `nonce_recovery_enabled` is set.
+    pub max_underpriced_resends: Option<u32>,
+    /// Maximum amount of ETH (in gwei) spent on commit transactions during the last hour.
+    /// Once the budget is exhausted, new commit transactions are deferred until spending in the window drops below it.
+    pub commit_hourly_budget_gwei: Option<u64>,
+    /// Maximum amount of ETH (in gwei) spent on commit transactions during the last 24 hours.
+    pub commit_daily_budget_gwei: Option<u64>,
+    /// Maximum amount of ETH (in gwei) spent on proof transactions during the last hour.
+    pub prove_hourly_budget_gwei: Option<u64>,
+    /// Maximum amount of ETH (in gwei) spent on proof transactions during the last 24 hours.
+    pub prove_daily_budget_gwei: Option<u64>,
+    /// Maximum amount of ETH (in gwei) spent on execute transactions during the last hour.
+    pub execute_hourly_budget_gwei: Option<u64>,
+    /// Maximum amount of ETH (in gwei) spent on execute transactions during the last 24 hours.
+    pub execute_daily_budget_gwei: Option<u64>,
+    /// L1 base fee (in gwei) above which sending of non-urgent (proof and execute) transactions is deferred.
+    pub non_urgent_base_fee_ceiling_gwei: Option<u64>,
+    /// Maximum age of a non-urgent transaction after which it is sent regardless of `non_urgent_base_fee_ceiling_gwei`.
+    pub max_non_urgent_deferral_sec: Option<u64>,
 }
 
 impl SenderConfig {
@@ -162,6 +206,11 @@ impl SenderConfig {
             .map(|pk| pk.parse().unwrap())
     }
 
+    /// Converts `self.max_non_urgent_deferral_sec` into `Duration`.
+    pub fn max_non_urgent_deferral(&self) -> Option<Duration> {
+        self.max_non_urgent_deferral_sec.map(Duration::from_secs)
+    }
+
     const fn default_tx_aggregation_paused() -> bool {
         false
     }
@@ -226,3 +275,84 @@ impl GasAdjusterConfig {
         1.0
     }
 }
+
+/// Configuration of a remote signer (e.g., Web3Signer) managing operator accounts.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RemoteSignerConfig {
+    /// URL of the signer JSON-RPC endpoint supporting `eth_signTransaction`.
+    pub url: String,
+    /// Address of the operator account managed by the signer.
+    pub operator_address: Address,
+    /// Address of the blob operator account managed by the signer, if any.
+    pub blob_operator_address: Option<Address>,
+    /// Timeout for a single signing request in milliseconds.
+    #[serde(default = "RemoteSignerConfig::default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+    /// Path to the PEM-encoded certificate chain used for TLS client authentication.
+    pub tls_client_cert_path: Option<String>,
+    /// Path to the PEM-encoded PKCS #8 private key used for TLS client authentication.
+    pub tls_client_key_path: Option<String>,
+    /// Path to the PEM-encoded root certificate to verify the signer certificate against, in addition to system roots.
+    pub tls_root_cert_path: Option<String>,
+}
+
+impl RemoteSignerConfig {
+    pub const fn default_request_timeout_ms() -> u64 {
+        10_000
+    }
+
+    /// Converts `self.request_timeout_ms` into `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+}
+
+/// Configuration of the L1 client querying multiple L1 providers (the main one and fallbacks specified in `L1Secrets`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EthClientConfig {
+    /// Number of providers that must return identical responses for safety-critical reads (logs,
+    /// transaction receipts and blocks). The default value 1 disables cross-checking responses.
+    #[serde(default = "EthClientConfig::default_quorum")]
+    pub quorum: usize,
+    /// Half-life of penalties for failed requests in provider health scores, in milliseconds.
+    #[serde(default = "EthClientConfig::default_health_recovery_half_life_ms")]
+    pub health_recovery_half_life_ms: u64,
+    /// Timeout for a single provider response in quorum reads, in milliseconds. Providers that don't respond
+    /// in time are treated as failed, so that a single stalled provider doesn't block quorum reads.
+    #[serde(default = "EthClientConfig::default_quorum_request_timeout_ms")]
+    pub quorum_request_timeout_ms: u64,
+}
+
+impl Default for EthClientConfig {
+    fn default() -> Self {
+        Self {
+            quorum: Self::default_quorum(),
+            health_recovery_half_life_ms: Self::default_health_recovery_half_life_ms(),
+            quorum_request_timeout_ms: Self::default_quorum_request_timeout_ms(),
+        }
+    }
+}
+
+impl EthClientConfig {
+    pub const fn default_quorum() -> usize {
+        1
+    }
+
+    pub const fn default_health_recovery_half_life_ms() -> u64 {
+        60_000
+    }
+
+    pub const fn default_quorum_request_timeout_ms() -> u64 {
+        10_000
+    }
+
+    /// Converts `self.health_recovery_half_life_ms` into `Duration`.
+    pub fn health_recovery_half_life(&self) -> Duration {
+        Duration::from_millis(self.health_recovery_half_life_ms)
+    }
+
+    /// Converts `self.quorum_request_timeout_ms` into `Duration`.
+    pub fn quorum_request_timeout(&self) -> Duration {
+        Duration::from_millis(self.quorum_request_timeout_ms)
+    }
+}
@@ -28,6 +28,9 @@ pub struct ExperimentalDBConfig {
     /// correspondingly; otherwise, RocksDB performance can significantly degrade.
     #[serde(default)]
     pub include_indices_and_filters_in_block_cache: bool,
+    /// Path to the RocksDB historical state archive used by the API server. If specified, the archive is kept
+    /// in sync with Postgres and is used to serve storage reads for past L2 blocks.
+    pub state_archive_path: Option<String>,
 }
 
 impl Default for ExperimentalDBConfig {
@@ -40,6 +43,7 @@ impl Default for ExperimentalDBConfig {
                 Self::default_protective_reads_persistence_enabled(),
             processing_delay_ms: Self::default_merkle_tree_processing_delay_ms(),
             include_indices_and_filters_in_block_cache: false,
+            state_archive_path: None,
         }
     }
 }
@@ -16,6 +16,57 @@ pub struct ForcedPriceClientConfig {
     pub fluctuation: Option<u32>,
 }
 
+/// Configuration for a generic price client that fetches a JSON document over HTTP and extracts
+/// the base token price in ETH from it. The URL is taken from `base_url`, in which `{address}`
+/// is substituted with the hex-encoded base token address.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JsonPathPriceClientConfig {
+    /// Dot-separated path to the price in the response, e.g. `data.0.price`. The price may be represented
+    /// either as a number or as a string.
+    pub price_path: String,
+    /// Dot-separated path to the quote timestamp (in seconds since UNIX epoch) in the response. If not set,
+    /// the time of the request is used.
+    pub timestamp_path: Option<String>,
+    /// Name of the HTTP header used to send `api_key`. If not set, the API key is not sent.
+    pub api_key_header: Option<String>,
+}
+
+/// Configuration for a client aggregating quotes from multiple sources.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AggregatedPriceClientConfig {
+    /// Configurations of aggregated sources. Sources cannot be aggregated themselves.
+    #[serde(default)]
+    pub sources: Vec<ExternalPriceApiClientConfig>,
+    /// Minimum number of valid quotes necessary to produce the aggregated quote.
+    #[serde(default = "AggregatedPriceClientConfig::default_min_sources")]
+    pub min_sources: usize,
+    /// Maximum deviation of a quote from the median of all quotes (in percent). Quotes deviating more
+    /// are rejected as outliers.
+    #[serde(default = "AggregatedPriceClientConfig::default_max_deviation_percent")]
+    pub max_deviation_percent: u32,
+    /// Maximum age of a quote. Older quotes are rejected as stale.
+    #[serde(default = "AggregatedPriceClientConfig::default_max_staleness_ms")]
+    pub max_staleness_ms: u64,
+}
+
+impl AggregatedPriceClientConfig {
+    pub const fn default_min_sources() -> usize {
+        1
+    }
+
+    pub const fn default_max_deviation_percent() -> u32 {
+        10
+    }
+
+    pub const fn default_max_staleness_ms() -> u64 {
+        600_000 // 10 minutes
+    }
+
+    pub fn max_staleness(&self) -> Duration {
+        Duration::from_millis(self.max_staleness_ms)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ExternalPriceApiClientConfig {
     pub source: String,
@@ -24,6 +75,10 @@ pub struct ExternalPriceApiClientConfig {
     #[serde(default = "ExternalPriceApiClientConfig::default_timeout")]
     pub client_timeout_ms: u64,
     pub forced: Option<ForcedPriceClientConfig>,
+    /// Configuration for the `json_path` source.
+    pub json_path: Option<JsonPathPriceClientConfig>,
+    /// Configuration for the `aggregated` source.
+    pub aggregated: Option<AggregatedPriceClientConfig>,
 }
 
 impl ExternalPriceApiClientConfig {
@@ -40,4 +40,38 @@ pub enum ObjectStoreMode {
     FileBacked {
         file_backed_base_path: String,
     },
+    /// Store backed by AWS S3 or any S3-compatible storage (e.g., MinIO).
+    ///
+    /// If `s3_credential_file_path` is not specified, credentials are taken from the standard
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and (optionally) `AWS_SESSION_TOKEN` env vars.
+    S3 {
+        bucket_name: String,
+        region: String,
+        /// Custom endpoint URL, e.g. `http://localhost:9000` for a local MinIO deployment.
+        /// If not specified, the AWS S3 endpoint for the `region` is used.
+        endpoint: Option<String>,
+        /// Addressing style for bucket requests. If not specified, path-style addressing is used
+        /// for custom endpoints, and virtual-hosted style for AWS.
+        addressing_style: Option<S3AddressingStyle>,
+        /// Path to an AWS shared credentials file. The profile is selected using the `AWS_PROFILE` env var
+        /// (`default` if not set).
+        s3_credential_file_path: Option<String>,
+    },
+    /// Anonymous access to an S3-compatible bucket (only works for public buckets for read operations).
+    S3AnonymousReadOnly {
+        bucket_name: String,
+        region: String,
+        endpoint: Option<String>,
+        addressing_style: Option<S3AddressingStyle>,
+    },
+}
+
+/// Addressing style for requests to S3-compatible stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum S3AddressingStyle {
+    /// Bucket is encoded in the hostname, e.g. `https://bucket.s3.us-east-1.amazonaws.com/key`.
+    VirtualHosted,
+    /// Bucket is the first path segment, e.g. `http://localhost:9000/bucket/key`.
+    Path,
 }
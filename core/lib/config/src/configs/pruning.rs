@@ -1,6 +1,7 @@
 use std::num::NonZeroU64;
 
 use serde::Deserialize;
+use zksync_basic_types::Address;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PruningConfig {
@@ -16,4 +17,12 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// If set, history (events, call traces and transaction execution info) of L1 batches will be pruned
+    /// after the batch timestamp is this old (in seconds), while the L1 batches themselves are retained according
+    /// to other pruning params. History pruning works even if `enabled` is false.
+    pub history_retention_sec: Option<u64>,
+    /// Addresses with history retained by history pruning: events emitted by these addresses, and events,
+    /// call traces and execution info of transactions calling these addresses are not pruned.
+    #[serde(default)]
+    pub history_retained_addresses: Vec<Address>,
 }
@@ -1,4 +1,5 @@
 use anyhow::Context;
+use secrecy::{ExposeSecret as _, Secret};
 use zksync_basic_types::url::SensitiveUrl;
 
 use crate::configs::consensus::ConsensusSecrets;
@@ -8,11 +9,30 @@ pub struct DatabaseSecrets {
     pub server_url: Option<SensitiveUrl>,
     pub prover_url: Option<SensitiveUrl>,
     pub server_replica_url: Option<SensitiveUrl>,
+    /// Additional read-only replicas that the API server load-balances connections across.
+    /// The replica URL is used as a fallback if none of these replicas is healthy.
+    pub server_replica_urls: Vec<SensitiveUrl>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct L1Secrets {
     pub l1_rpc_url: SensitiveUrl,
+    /// Additional L1 RPC providers. If specified, requests fail over to these providers if the main one
+    /// is unhealthy; they also take part in quorum reads (see `EthClientConfig`).
+    pub fallback_l1_rpc_urls: Vec<SensitiveUrl>,
+}
+
+/// Secrets for the Celestia DA client.
+#[derive(Debug, Clone)]
+pub struct CelestiaSecrets {
+    /// Auth token for the node JSON-RPC API; must have at least the `write` permission.
+    pub auth_token: Secret<String>,
+}
+
+impl PartialEq for CelestiaSecrets {
+    fn eq(&self, other: &Self) -> bool {
+        self.auth_token.expose_secret() == other.auth_token.expose_secret()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +40,7 @@ pub struct Secrets {
     pub consensus: Option<ConsensusSecrets>,
     pub database: Option<DatabaseSecrets>,
     pub l1: Option<L1Secrets>,
+    pub celestia: Option<CelestiaSecrets>,
 }
 
 impl DatabaseSecrets {
@@ -45,4 +45,7 @@ pub struct SnapshotRecoveryConfig {
     pub tree: TreeRecoveryConfig,
     pub postgres: PostgresRecoveryConfig,
     pub object_store: Option<ObjectStoreConfig>,
+    /// Path to a portable snapshot archive to recover from. If set, snapshot data is read from the archive
+    /// instead of the object store, so `object_store` doesn't need to be configured.
+    pub archive_path: Option<String>,
 }
@@ -16,6 +16,15 @@ pub struct SnapshotsCreatorConfig {
     /// - If a snapshot with this L1 batch exists and is incomplete, the creator will continue creating it,
     ///   regardless of whether the specified snapshot `version` matches.
     pub l1_batch_number: Option<L1BatchNumber>,
+    /// Whether to create delta snapshots. A delta snapshot only contains storage logs and factory deps changed
+    /// since the newest complete snapshot before it (the base snapshot), which can be either full or delta itself.
+    /// Delta snapshots require snapshot `version` 1.
+    #[serde(default)]
+    pub delta: bool,
+    /// Maximum number of delta snapshots following a full snapshot. Once this number is reached, the creator
+    /// creates a full snapshot instead of a delta one.
+    #[serde(default = "SnapshotsCreatorConfig::max_delta_chain_length_default")]
+    pub max_delta_chain_length: u32,
     #[serde(default = "SnapshotsCreatorConfig::storage_logs_chunk_size_default")]
     pub storage_logs_chunk_size: u64,
     #[serde(default = "SnapshotsCreatorConfig::concurrent_queries_count")]
@@ -31,4 +40,8 @@ impl SnapshotsCreatorConfig {
     const fn concurrent_queries_count() -> u32 {
         25
     }
+
+    pub const fn max_delta_chain_length_default() -> u32 {
+        10
+    }
 }
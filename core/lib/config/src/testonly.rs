@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::{net::IpAddr, num::NonZeroUsize};
 
 use rand::{distributions::Distribution, Rng};
 use zksync_basic_types::{
@@ -13,7 +13,11 @@ use zksync_consensus_utils::EncodeDist;
 use zksync_crypto_primitives::K256PrivateKey;
 
 use crate::configs::{
-    self, eth_sender::PubdataSendingMode, external_price_api_client::ForcedPriceClientConfig,
+    self,
+    eth_sender::PubdataSendingMode,
+    external_price_api_client::{
+        AggregatedPriceClientConfig, ForcedPriceClientConfig, JsonPathPriceClientConfig,
+    },
 };
 
 trait Sample {
@@ -80,6 +84,9 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             initial_writes_cache_size_mb: self.sample(rng),
             latest_values_cache_size_mb: self.sample(rng),
             fee_history_limit: self.sample(rng),
+            struct_logger_memory_limit: self.sample(rng),
+            struct_logger_step_limit: self.sample(rng),
+            struct_logger_size_limit_mb: self.sample(rng),
             max_batch_request_size: self.sample(rng),
             max_response_body_size_mb: self.sample(rng),
             max_response_body_size_overrides_mb: [
@@ -95,6 +102,22 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             .into_iter()
             .collect(),
             websocket_requests_per_minute_limit: self.sample(rng),
+            http_requests_per_minute_per_ip: self.sample(rng),
+            http_requests_per_minute_per_api_key: self.sample(rng),
+            http_api_keys: self.sample_collect(rng),
+            http_api_key_header: self.sample(rng),
+            http_api_key_path_prefix: self.sample(rng),
+            http_client_ip_header: self.sample(rng),
+            http_trusted_proxies: self
+                .sample_range(rng)
+                .map(|_| IpAddr::from(rng.gen::<[u8; 4]>()))
+                .collect(),
+            rpc_method_costs: [
+                ("debug_*", self.sample(rng)),
+                ("eth_getLogs", self.sample(rng)),
+            ]
+            .into_iter()
+            .collect(),
             tree_api_url: self.sample(rng),
             mempool_cache_update_interval: self.sample(rng),
             mempool_cache_size: self.sample(rng),
@@ -129,6 +152,7 @@ impl Distribution<configs::api::MerkleTreeApiConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::MerkleTreeApiConfig {
         configs::api::MerkleTreeApiConfig {
             port: self.sample(rng),
+            checkpoints_dir: self.sample_opt(|| format!("/{}", rng.gen::<u64>()).into()),
         }
     }
 }
@@ -181,6 +205,9 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             save_call_traces: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
+            max_l1_batch_execution_time_ms: self.sample(rng),
+            max_l2_block_execution_time_ms: self.sample(rng),
+            max_contract_gas_share: self.sample_opt(|| rng.gen_range(0.01..=1.0)),
             // These values are not involved into files serialization skip them
             fee_account_addr: None,
             bootloader_hash: None,
@@ -218,6 +245,19 @@ impl Distribution<configs::chain::MempoolConfig> for EncodeDist {
             stuck_tx_timeout: self.sample(rng),
             remove_stuck_txs: self.sample(rng),
             delay_interval: self.sample(rng),
+            ordering_policy: self.sample(rng),
+            min_replacement_fee_bump_percent: self.sample(rng),
+            evict_cheapest_on_capacity: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::chain::MempoolOrderingPolicy> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::chain::MempoolOrderingPolicy {
+        type T = configs::chain::MempoolOrderingPolicy;
+        match rng.gen_range(0..2) {
+            0 => T::Fifo,
+            _ => T::PriorityFee,
         }
     }
 }
@@ -290,6 +330,7 @@ impl Distribution<configs::ExperimentalDBConfig> for EncodeDist {
             protective_reads_persistence_enabled: self.sample(rng),
             processing_delay_ms: self.sample(rng),
             include_indices_and_filters_in_block_cache: self.sample(rng),
+            state_archive_path: self.sample(rng),
         }
     }
 }
@@ -342,6 +383,8 @@ impl Distribution<configs::database::PostgresConfig> for EncodeDist {
             statement_timeout_sec: self.sample(rng),
             long_connection_threshold_ms: self.sample(rng),
             slow_query_threshold_ms: self.sample(rng),
+            max_replica_lag_sec: self.sample(rng),
+            replica_health_check_interval_ms: self.sample(rng),
             test_server_url: self.sample(rng),
             test_prover_url: self.sample(rng),
         }
@@ -354,6 +397,32 @@ impl Distribution<configs::EthConfig> for EncodeDist {
             sender: self.sample(rng),
             gas_adjuster: self.sample(rng),
             watcher: self.sample(rng),
+            remote_signer: self.sample(rng),
+            client: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::eth_sender::EthClientConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::EthClientConfig {
+        configs::eth_sender::EthClientConfig {
+            quorum: self.sample(rng),
+            health_recovery_half_life_ms: self.sample(rng),
+            quorum_request_timeout_ms: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::eth_sender::RemoteSignerConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::RemoteSignerConfig {
+        configs::eth_sender::RemoteSignerConfig {
+            url: self.sample(rng),
+            operator_address: rng.gen(),
+            blob_operator_address: rng.gen(),
+            request_timeout_ms: self.sample(rng),
+            tls_client_cert_path: self.sample(rng),
+            tls_client_key_path: self.sample(rng),
+            tls_root_cert_path: self.sample(rng),
         }
     }
 }
@@ -412,6 +481,17 @@ impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
             pubdata_sending_mode: PubdataSendingMode::Calldata,
             tx_aggregation_paused: false,
             tx_aggregation_only_prove_and_execute: false,
+            nonce_recovery_enabled: self.sample(rng),
+            stuck_tx_timeout_blocks: self.sample(rng),
+            max_underpriced_resends: self.sample(rng),
+            commit_hourly_budget_gwei: self.sample(rng),
+            commit_daily_budget_gwei: self.sample(rng),
+            prove_hourly_budget_gwei: self.sample(rng),
+            prove_daily_budget_gwei: self.sample(rng),
+            execute_hourly_budget_gwei: self.sample(rng),
+            execute_daily_budget_gwei: self.sample(rng),
+            non_urgent_base_fee_ceiling_gwei: self.sample(rng),
+            max_non_urgent_deferral_sec: self.sample(rng),
         }
     }
 }
@@ -650,7 +730,7 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
 impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::ObjectStoreMode {
         type T = configs::object_store::ObjectStoreMode;
-        match rng.gen_range(0..4) {
+        match rng.gen_range(0..6) {
             0 => T::GCS {
                 bucket_base_url: self.sample(rng),
             },
@@ -661,6 +741,19 @@ impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
             2 => T::FileBacked {
                 file_backed_base_path: self.sample(rng),
             },
+            3 => T::S3 {
+                bucket_name: self.sample(rng),
+                region: self.sample(rng),
+                endpoint: self.sample(rng),
+                addressing_style: self.sample_opt(|| self.sample(rng)),
+                s3_credential_file_path: self.sample(rng),
+            },
+            4 => T::S3AnonymousReadOnly {
+                bucket_name: self.sample(rng),
+                region: self.sample(rng),
+                endpoint: self.sample(rng),
+                addressing_style: self.sample_opt(|| self.sample(rng)),
+            },
             _ => T::GCSAnonymousReadOnly {
                 bucket_base_url: self.sample(rng),
             },
@@ -668,6 +761,16 @@ impl Distribution<configs::object_store::ObjectStoreMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::object_store::S3AddressingStyle> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::object_store::S3AddressingStyle {
+        type T = configs::object_store::S3AddressingStyle;
+        match rng.gen_range(0..2) {
+            0 => T::VirtualHosted,
+            _ => T::Path,
+        }
+    }
+}
+
 impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ObjectStoreConfig {
         configs::ObjectStoreConfig {
@@ -693,6 +796,8 @@ impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
         configs::SnapshotsCreatorConfig {
             l1_batch_number: self.sample_opt(|| L1BatchNumber(rng.gen())),
             version: if rng.gen() { 0 } else { 1 },
+            delta: self.sample(rng),
+            max_delta_chain_length: self.sample(rng),
             storage_logs_chunk_size: self.sample(rng),
             concurrent_queries_count: self.sample(rng),
             object_store: self.sample(rng),
@@ -843,6 +948,9 @@ impl Distribution<configs::secrets::L1Secrets> for EncodeDist {
         use configs::secrets::L1Secrets;
         L1Secrets {
             l1_rpc_url: format!("localhost:{}", rng.gen::<u16>()).parse().unwrap(),
+            fallback_l1_rpc_urls: (0..rng.gen_range(0..3))
+                .map(|_| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap())
+                .collect(),
         }
     }
 }
@@ -853,6 +961,9 @@ impl Distribution<configs::secrets::DatabaseSecrets> for EncodeDist {
         DatabaseSecrets {
             server_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
             server_replica_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
+            server_replica_urls: (0..rng.gen_range(0..3))
+                .map(|_| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap())
+                .collect(),
             prover_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
         }
     }
@@ -865,6 +976,15 @@ impl Distribution<configs::secrets::Secrets> for EncodeDist {
             consensus: self.sample_opt(|| self.sample(rng)),
             database: self.sample_opt(|| self.sample(rng)),
             l1: self.sample_opt(|| self.sample(rng)),
+            celestia: self.sample_opt(|| self.sample(rng)),
+        }
+    }
+}
+
+impl Distribution<configs::secrets::CelestiaSecrets> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::CelestiaSecrets {
+        configs::secrets::CelestiaSecrets {
+            auth_token: String::into(self.sample(rng)),
         }
     }
 }
@@ -940,6 +1060,19 @@ impl Distribution<configs::da_dispatcher::DADispatcherConfig> for EncodeDist {
             polling_interval_ms: self.sample(rng),
             max_rows_to_dispatch: self.sample(rng),
             max_retries: self.sample(rng),
+            celestia: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::da_dispatcher::CelestiaConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::da_dispatcher::CelestiaConfig {
+        configs::da_dispatcher::CelestiaConfig {
+            api_node_url: self.sample(rng),
+            namespace: self.sample(rng),
+            gas_price: self.sample(rng),
+            request_timeout_ms: self.sample(rng),
+            blob_size_limit: self.sample(rng),
         }
     }
 }
@@ -1015,6 +1148,7 @@ impl Distribution<configs::snapshot_recovery::SnapshotRecoveryConfig> for Encode
             tree,
             postgres: self.sample(rng),
             object_store: self.sample(rng),
+            archive_path: self.sample(rng),
         }
     }
 }
@@ -1026,6 +1160,8 @@ impl Distribution<configs::pruning::PruningConfig> for EncodeDist {
             chunk_size: self.sample(rng),
             removal_delay_sec: self.sample_opt(|| rng.gen()),
             data_retention_sec: self.sample(rng),
+            history_retention_sec: self.sample(rng),
+            history_retained_addresses: self.sample_range(rng).map(|_| rng.gen()).collect(),
         }
     }
 }
@@ -1063,22 +1199,47 @@ impl Distribution<configs::external_proof_integration_api::ExternalProofIntegrat
     }
 }
 
+/// Samples a non-aggregated price source. Aggregated sources cannot be nested, so we don't recurse
+/// into `sample()` for them.
+fn sample_price_source<R: Rng + ?Sized>(
+    dist: &EncodeDist,
+    rng: &mut R,
+) -> configs::external_price_api_client::ExternalPriceApiClientConfig {
+    configs::external_price_api_client::ExternalPriceApiClientConfig {
+        source: dist.sample(rng),
+        base_url: dist.sample(rng),
+        api_key: dist.sample(rng),
+        client_timeout_ms: dist.sample(rng),
+        forced: Some(ForcedPriceClientConfig {
+            numerator: dist.sample(rng),
+            denominator: dist.sample(rng),
+            fluctuation: dist.sample(rng),
+        }),
+        json_path: dist.sample_opt(|| JsonPathPriceClientConfig {
+            price_path: dist.sample(rng),
+            timestamp_path: dist.sample(rng),
+            api_key_header: dist.sample(rng),
+        }),
+        aggregated: None,
+    }
+}
+
 impl Distribution<configs::external_price_api_client::ExternalPriceApiClientConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(
         &self,
         rng: &mut R,
     ) -> configs::external_price_api_client::ExternalPriceApiClientConfig {
-        configs::external_price_api_client::ExternalPriceApiClientConfig {
-            source: self.sample(rng),
-            base_url: self.sample(rng),
-            api_key: self.sample(rng),
-            client_timeout_ms: self.sample(rng),
-            forced: Some(ForcedPriceClientConfig {
-                numerator: self.sample(rng),
-                denominator: self.sample(rng),
-                fluctuation: self.sample(rng),
-            }),
-        }
+        let mut config = sample_price_source(self, rng);
+        let sources = (0..rng.gen_range(1..=3))
+            .map(|_| sample_price_source(self, rng))
+            .collect();
+        config.aggregated = self.sample_opt(|| AggregatedPriceClientConfig {
+            sources,
+            min_sources: self.sample(rng),
+            max_deviation_percent: self.sample(rng),
+            max_staleness_ms: self.sample(rng),
+        });
+        config
     }
 }
 
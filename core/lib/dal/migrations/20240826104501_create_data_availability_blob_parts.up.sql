@@ -0,0 +1,19 @@
+-- Parts of the pubdata dispatched as separate blobs if it exceeds the blob size limit of the DA layer.
+-- The `data_availability` row for the L1 batch is only inserted once all parts are dispatched.
+-- `chunk_size` is the blob size limit the pubdata was split with; part `i` covers bytes
+-- `[i * chunk_size, (i + 1) * chunk_size)` of the pubdata.
+CREATE TABLE IF NOT EXISTS data_availability_blob_parts
+(
+    l1_batch_number BIGINT    NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    part_index      INT       NOT NULL,
+    chunk_size      BIGINT    NOT NULL,
+
+    blob_id         TEXT      NOT NULL,
+    inclusion_data  BYTEA,
+    sent_at         TIMESTAMP NOT NULL,
+
+    created_at      TIMESTAMP NOT NULL,
+    updated_at      TIMESTAMP NOT NULL,
+
+    PRIMARY KEY (l1_batch_number, part_index)
+);
@@ -0,0 +1,3 @@
+-- L1 batch of the snapshot this snapshot is based on. Set only for delta snapshots, which contain
+-- storage logs and factory deps changed since the base snapshot.
+ALTER TABLE snapshots ADD COLUMN IF NOT EXISTS base_l1_batch_number BIGINT;
@@ -0,0 +1,12 @@
+-- Log of history pruning, which removes events, call traces and transaction execution info for old L2 blocks
+-- while retaining the blocks themselves.
+CREATE TABLE IF NOT EXISTS history_pruning_log
+(
+    pruned_l1_batch  BIGINT NOT NULL PRIMARY KEY,
+    pruned_miniblock BIGINT NOT NULL,
+    -- Addresses with history retained for all L2 blocks up to and including `pruned_miniblock`.
+    retained_addresses BYTEA[] NOT NULL DEFAULT '{}',
+
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
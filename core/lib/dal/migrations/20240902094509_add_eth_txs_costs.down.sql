@@ -0,0 +1 @@
+DROP TABLE IF EXISTS eth_txs_costs;
@@ -0,0 +1,16 @@
+-- Actual L1 costs of confirmed `eth_txs` computed from their receipts.
+CREATE TABLE IF NOT EXISTS eth_txs_costs
+(
+    eth_tx_id           INT           NOT NULL PRIMARY KEY REFERENCES eth_txs (id) ON DELETE CASCADE,
+    gas_used            BIGINT        NOT NULL,
+    effective_gas_price NUMERIC(80)   NOT NULL,
+    blob_gas_used       BIGINT,
+    blob_gas_price      NUMERIC(80),
+    -- Total cost of the transaction in wei, including blob fees.
+    total_cost          NUMERIC(80)   NOT NULL,
+
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS eth_txs_costs_created_at_idx ON eth_txs_costs (created_at);
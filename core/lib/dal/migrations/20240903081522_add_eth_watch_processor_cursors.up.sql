@@ -0,0 +1,9 @@
+-- Last L1 block processed by each event processor of the Ethereum watcher.
+CREATE TABLE IF NOT EXISTS eth_watch_processor_cursors
+(
+    processor_name          TEXT   NOT NULL PRIMARY KEY,
+    last_processed_l1_block BIGINT NOT NULL,
+
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,8 @@
+-- Hashes of L1 blocks processed by the Ethereum watcher; used to detect L1 reorgs.
+CREATE TABLE IF NOT EXISTS eth_watch_l1_block_hashes
+(
+    l1_block_number BIGINT NOT NULL PRIMARY KEY,
+    l1_block_hash   BYTEA  NOT NULL,
+
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+UPDATE snapshots SET version = 1 WHERE base_l1_batch_number IS NOT NULL AND version = 2;
@@ -0,0 +1,3 @@
+-- Delta snapshots were initially published with the full snapshot version 1. Move them to the dedicated
+-- delta snapshot version so that appliers not aware of delta snapshots reject them.
+UPDATE snapshots SET version = 2 WHERE base_l1_batch_number IS NOT NULL AND version = 1;
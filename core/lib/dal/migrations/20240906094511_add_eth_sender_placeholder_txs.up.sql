@@ -0,0 +1,15 @@
+-- Placeholder transactions (zero-value self-transfers) sent by the eth sender to fill nonce gaps
+-- or to cancel stuck transactions. Persisted so that placeholders can be tracked across restarts.
+CREATE TABLE IF NOT EXISTS eth_sender_placeholder_txs (
+    operator TEXT NOT NULL,
+    nonce BIGINT NOT NULL,
+    cancelled_eth_tx_id INT REFERENCES eth_txs (id) ON DELETE SET NULL,
+    tx_hash BYTEA NOT NULL,
+    base_fee_per_gas BIGINT NOT NULL,
+    priority_fee_per_gas BIGINT NOT NULL,
+    blob_base_fee_per_gas BIGINT,
+    sent_at_block INT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (operator, nonce)
+);
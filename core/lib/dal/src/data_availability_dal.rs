@@ -3,10 +3,13 @@ use zksync_db_connection::{
     error::DalResult,
     instrument::{InstrumentExt, Instrumented},
 };
-use zksync_types::{pubdata_da::DataAvailabilityBlob, L1BatchNumber};
+use zksync_types::{
+    pubdata_da::{DataAvailabilityBlob, DataAvailabilityBlobPart},
+    L1BatchNumber,
+};
 
 use crate::{
-    models::storage_data_availability::{L1BatchDA, StorageDABlob},
+    models::storage_data_availability::{L1BatchDA, StorageDABlob, StorageDABlobPart},
     Core,
 };
 
@@ -147,6 +150,209 @@ impl DataAvailabilityDal<'_, '_> {
         Ok(())
     }
 
+    /// Inserts the blob_id for a part of the pubdata of the given L1 batch split into chunks of `chunk_size` bytes.
+    /// If the part is already present, verifies that its blob_id matches the one provided in the function arguments.
+    pub async fn insert_l1_batch_da_part(
+        &mut self,
+        number: L1BatchNumber,
+        part_index: u32,
+        chunk_size: u64,
+        blob_id: &str,
+        sent_at: chrono::NaiveDateTime,
+    ) -> DalResult<()> {
+        let update_result = sqlx::query!(
+            r#"
+            INSERT INTO
+                data_availability_blob_parts (
+                    l1_batch_number,
+                    part_index,
+                    chunk_size,
+                    blob_id,
+                    sent_at,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, NOW(), NOW())
+            ON CONFLICT DO NOTHING
+            "#,
+            i64::from(number.0),
+            part_index as i32,
+            chunk_size as i64,
+            blob_id,
+            sent_at,
+        )
+        .instrument("insert_l1_batch_da_part")
+        .with_arg("number", &number)
+        .with_arg("part_index", &part_index)
+        .with_arg("chunk_size", &chunk_size)
+        .with_arg("blob_id", &blob_id)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        if update_result.rows_affected() == 0 {
+            tracing::debug!(
+                "L1 batch #{number}: DA blob_id for part {part_index} wasn't updated as it's already present"
+            );
+
+            let instrumentation = Instrumented::new("get_matching_batch_da_part_blob_id")
+                .with_arg("number", &number)
+                .with_arg("part_index", &part_index);
+
+            let query = sqlx::query!(
+                r#"
+                SELECT
+                    blob_id
+                FROM
+                    data_availability_blob_parts
+                WHERE
+                    l1_batch_number = $1
+                    AND part_index = $2
+                "#,
+                i64::from(number.0),
+                part_index as i32,
+            );
+
+            let matched: String = instrumentation
+                .clone()
+                .with(query)
+                .report_latency()
+                .fetch_one(self.storage)
+                .await?
+                .blob_id;
+
+            if matched != blob_id {
+                let err = instrumentation.constraint_error(anyhow::anyhow!(
+                    "Error storing DA blob id. DA blob_id {blob_id} for part {part_index} of L1 batch #{number} does not match the expected value"
+                ));
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the pubdata parts dispatched for the given L1 batch ordered by the part index. The returned list
+    /// is empty if the pubdata is dispatched as a single blob.
+    pub async fn get_l1_batch_da_parts(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> DalResult<Vec<DataAvailabilityBlobPart>> {
+        let parts = sqlx::query_as!(
+            StorageDABlobPart,
+            r#"
+            SELECT
+                l1_batch_number,
+                part_index,
+                chunk_size,
+                blob_id,
+                inclusion_data,
+                sent_at
+            FROM
+                data_availability_blob_parts
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                part_index
+            "#,
+            i64::from(number.0),
+        )
+        .instrument("get_l1_batch_da_parts")
+        .with_arg("number", &number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(parts.into_iter().map(Into::into).collect())
+    }
+
+    /// Removes all pubdata parts dispatched for the given L1 batch, e.g. if they were split with a different chunk size.
+    pub async fn delete_l1_batch_da_parts(&mut self, number: L1BatchNumber) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM data_availability_blob_parts
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(number.0),
+        )
+        .instrument("delete_l1_batch_da_parts")
+        .with_arg("number", &number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Saves the inclusion data for a part of the pubdata of the given L1 batch. If the inclusion data
+    /// is already present, verifies that it matches the one provided in the function arguments.
+    pub async fn save_l1_batch_da_part_inclusion_data(
+        &mut self,
+        number: L1BatchNumber,
+        part_index: u32,
+        da_inclusion_data: &[u8],
+    ) -> DalResult<()> {
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE data_availability_blob_parts
+            SET
+                inclusion_data = $1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND part_index = $3
+                AND inclusion_data IS NULL
+            "#,
+            da_inclusion_data,
+            i64::from(number.0),
+            part_index as i32,
+        )
+        .instrument("save_l1_batch_da_part_inclusion_data")
+        .with_arg("number", &number)
+        .with_arg("part_index", &part_index)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        if update_result.rows_affected() == 0 {
+            tracing::debug!(
+                "L1 batch #{number}: DA data for part {part_index} wasn't updated as it's already present"
+            );
+
+            let instrumentation = Instrumented::new("get_matching_batch_da_part_data")
+                .with_arg("number", &number)
+                .with_arg("part_index", &part_index);
+
+            let query = sqlx::query!(
+                r#"
+                SELECT
+                    inclusion_data
+                FROM
+                    data_availability_blob_parts
+                WHERE
+                    l1_batch_number = $1
+                    AND part_index = $2
+                "#,
+                i64::from(number.0),
+                part_index as i32,
+            );
+
+            let matched: Option<Vec<u8>> = instrumentation
+                .clone()
+                .with(query)
+                .report_latency()
+                .fetch_one(self.storage)
+                .await?
+                .inclusion_data;
+
+            if matched.as_deref() != Some(da_inclusion_data) {
+                let err = instrumentation.constraint_error(anyhow::anyhow!(
+                    "Error storing DA inclusion data. DA data for part {part_index} of L1 batch #{number} does not match the one provided before"
+                ));
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
     /// Assumes that the L1 batches are sorted by number, and returns the first one that is ready for DA dispatch.
     pub async fn get_first_da_blob_awaiting_inclusion(
         &mut self,
@@ -215,3 +421,67 @@ impl DataAvailabilityDal<'_, '_> {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn dispatching_pubdata_parts() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+
+        let number = L1BatchNumber(1);
+        let sent_at = chrono::Utc::now().naive_utc();
+        let mut dal = conn.data_availability_dal();
+        assert!(dal.get_l1_batch_da_parts(number).await.unwrap().is_empty());
+
+        dal.insert_l1_batch_da_part(number, 1, 4, "blob1", sent_at)
+            .await
+            .unwrap();
+        dal.insert_l1_batch_da_part(number, 0, 4, "blob0", sent_at)
+            .await
+            .unwrap();
+        // Repeated insertion with the same blob ID is fine.
+        dal.insert_l1_batch_da_part(number, 0, 4, "blob0", sent_at)
+            .await
+            .unwrap();
+        dal.insert_l1_batch_da_part(number, 0, 4, "other", sent_at)
+            .await
+            .unwrap_err();
+
+        let parts = dal.get_l1_batch_da_parts(number).await.unwrap();
+        let blob_ids: Vec<_> = parts.iter().map(|part| part.blob_id.as_str()).collect();
+        assert_eq!(blob_ids, ["blob0", "blob1"]);
+        assert!(parts.iter().all(|part| part.chunk_size == 4));
+        assert!(parts.iter().all(|part| part.inclusion_data.is_none()));
+
+        dal.save_l1_batch_da_part_inclusion_data(number, 1, &[1, 2])
+            .await
+            .unwrap();
+        dal.save_l1_batch_da_part_inclusion_data(number, 1, &[1, 2])
+            .await
+            .unwrap();
+        dal.save_l1_batch_da_part_inclusion_data(number, 1, &[3])
+            .await
+            .unwrap_err();
+
+        let parts = dal.get_l1_batch_da_parts(number).await.unwrap();
+        assert_eq!(parts[0].inclusion_data, None);
+        assert_eq!(parts[1].inclusion_data, Some(vec![1, 2]));
+
+        dal.delete_l1_batch_da_parts(number).await.unwrap();
+        assert!(dal.get_l1_batch_da_parts(number).await.unwrap().is_empty());
+    }
+}
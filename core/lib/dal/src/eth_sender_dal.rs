@@ -1,13 +1,18 @@
-use std::{convert::TryFrom, str::FromStr};
+use std::{collections::HashMap, convert::TryFrom, str::FromStr, time::Duration};
 
 use anyhow::Context as _;
 use sqlx::types::chrono::{DateTime, Utc};
-use zksync_db_connection::{connection::Connection, interpolate_query, match_query_as};
+use zksync_db_connection::{
+    connection::Connection, error::DalResult, instrument::InstrumentExt, interpolate_query,
+    match_query_as, utils::pg_interval_from_duration,
+};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, EthTxBlobSidecar, TxHistory, TxHistoryToSend},
-    Address, L1BatchNumber, H256, U256,
+    api,
+    eth_sender::{EthTx, EthTxBlobSidecar, EthTxCost, PlaceholderTx, TxHistory, TxHistoryToSend},
+    Address, L1BatchNumber, Nonce, H256, U256,
 };
+use zksync_utils::{bigdecimal_to_u256, u256_to_big_decimal};
 
 use crate::{
     models::storage_eth_tx::{
@@ -404,6 +409,471 @@ impl EthSenderDal<'_, '_> {
         Ok(())
     }
 
+    /// Saves the actual L1 cost of a confirmed transaction. Does nothing if the cost is already saved.
+    pub async fn save_tx_cost(&mut self, eth_tx_id: u32, cost: &EthTxCost) -> anyhow::Result<()> {
+        let gas_used = i64::try_from(cost.gas_used)
+            .map_err(|err| anyhow::anyhow!("Can't convert gas_used to i64: {err}"))?;
+        let blob_gas_used = cost
+            .blob_gas_used
+            .map(i64::try_from)
+            .transpose()
+            .map_err(|err| anyhow::anyhow!("Can't convert blob_gas_used to i64: {err}"))?;
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_txs_costs (
+                    eth_tx_id,
+                    gas_used,
+                    effective_gas_price,
+                    blob_gas_used,
+                    blob_gas_price,
+                    total_cost,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, $6, NOW(), NOW())
+            ON CONFLICT (eth_tx_id) DO NOTHING
+            "#,
+            eth_tx_id as i32,
+            gas_used,
+            u256_to_big_decimal(cost.effective_gas_price),
+            blob_gas_used,
+            cost.blob_gas_price.map(u256_to_big_decimal),
+            u256_to_big_decimal(cost.total_cost())
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the total cost (in wei) of transactions of the specified type confirmed during the last `window`.
+    pub async fn get_spent_l1_fees(
+        &mut self,
+        tx_type: AggregatedActionType,
+        window: Duration,
+    ) -> sqlx::Result<U256> {
+        let window = pg_interval_from_duration(window);
+        let total_cost = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COALESCE(SUM(eth_txs_costs.total_cost), 0) AS "total_cost!"
+            FROM
+                eth_txs_costs
+                JOIN eth_txs ON eth_txs.id = eth_txs_costs.eth_tx_id
+            WHERE
+                eth_txs.tx_type = $1
+                AND eth_txs_costs.created_at >= NOW() - $2::INTERVAL
+            "#,
+            tx_type.to_string(),
+            &window
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(bigdecimal_to_u256(total_cost))
+    }
+
+    /// Returns the maximum total cost (in wei) of transactions of the specified type that were sent, but are not confirmed
+    /// yet. The cost of each transaction is estimated from its latest sending attempt, assuming that the transaction
+    /// uses all of `gas_limit` and pays the maximum fees.
+    pub async fn get_pending_l1_fees_upper_bound(
+        &mut self,
+        tx_type: AggregatedActionType,
+        gas_limit: u64,
+    ) -> sqlx::Result<U256> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                eth_txs.blob_sidecar,
+                latest_attempt.base_fee_per_gas AS "base_fee_per_gas!",
+                latest_attempt.priority_fee_per_gas AS "priority_fee_per_gas!",
+                latest_attempt.blob_base_fee_per_gas
+            FROM
+                eth_txs
+                JOIN LATERAL (
+                    SELECT
+                        base_fee_per_gas,
+                        priority_fee_per_gas,
+                        blob_base_fee_per_gas
+                    FROM
+                        eth_txs_history
+                    WHERE
+                        eth_txs_history.eth_tx_id = eth_txs.id
+                    ORDER BY
+                        eth_txs_history.id DESC
+                    LIMIT
+                        1
+                ) latest_attempt ON TRUE
+            WHERE
+                eth_txs.tx_type = $1
+                AND eth_txs.confirmed_eth_tx_history_id IS NULL
+            "#,
+            tx_type.to_string()
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let total_cost = rows.into_iter().fold(U256::zero(), |acc, row| {
+            let blob_count = row.blob_sidecar.map_or(0, |sidecar| {
+                bincode::deserialize::<EthTxBlobSidecar>(&sidecar)
+                    .expect("EthTxBlobSidecar is encoded correctly; qed")
+                    .blob_count()
+            });
+            let max_fee_per_gas = (row.base_fee_per_gas + row.priority_fee_per_gas) as u64;
+            let cost = EthTxCost::upper_bound(
+                gas_limit,
+                max_fee_per_gas,
+                blob_count,
+                row.blob_base_fee_per_gas.map(|fee| fee as u64),
+            );
+            acc + cost.total_cost()
+        });
+        Ok(total_cost)
+    }
+
+    /// Returns actual L1 costs of the specified L1 batch, or `None` if the batch doesn't exist.
+    /// The cost of each transaction is split evenly among all L1 batches covered by it.
+    pub async fn get_l1_batch_l1_costs(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<api::L1BatchL1Costs>> {
+        let Some(eth_tx_ids) = sqlx::query!(
+            r#"
+            SELECT
+                eth_commit_tx_id,
+                eth_prove_tx_id,
+                eth_execute_tx_id
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l1_batch_l1_costs#eth_tx_ids")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let ids: Vec<_> = [
+            eth_tx_ids.eth_commit_tx_id,
+            eth_tx_ids.eth_prove_tx_id,
+            eth_tx_ids.eth_execute_tx_id,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                eth_txs_costs.eth_tx_id,
+                eth_txs_history.tx_hash,
+                eth_txs_costs.gas_used,
+                eth_txs_costs.effective_gas_price,
+                eth_txs_costs.blob_gas_used,
+                eth_txs_costs.blob_gas_price,
+                eth_txs_costs.total_cost,
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l1_batches
+                    WHERE
+                        eth_commit_tx_id = eth_txs_costs.eth_tx_id
+                        OR eth_prove_tx_id = eth_txs_costs.eth_tx_id
+                        OR eth_execute_tx_id = eth_txs_costs.eth_tx_id
+                ) AS "l1_batch_count!"
+            FROM
+                eth_txs_costs
+                JOIN eth_txs ON eth_txs.id = eth_txs_costs.eth_tx_id
+                JOIN eth_txs_history ON eth_txs_history.id = eth_txs.confirmed_eth_tx_history_id
+            WHERE
+                eth_txs_costs.eth_tx_id = ANY($1)
+            "#,
+            &ids
+        )
+        .instrument("get_l1_batch_l1_costs")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("ids", &ids)
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut costs: HashMap<_, _> = rows
+            .into_iter()
+            .map(|row| {
+                let total_cost = bigdecimal_to_u256(row.total_cost);
+                let l1_batch_count = row.l1_batch_count.max(1) as u32;
+                let cost = api::L1TxCost {
+                    tx_hash: H256::from_str(&row.tx_hash).expect("Incorrect eth_tx hash"),
+                    gas_used: row.gas_used.into(),
+                    effective_gas_price: bigdecimal_to_u256(row.effective_gas_price),
+                    blob_gas_used: row.blob_gas_used.map(U256::from),
+                    blob_gas_price: row.blob_gas_price.map(bigdecimal_to_u256),
+                    total_cost,
+                    l1_batch_count,
+                    l1_batch_cost: total_cost / l1_batch_count,
+                };
+                (row.eth_tx_id, cost)
+            })
+            .collect();
+        let mut take_cost = |eth_tx_id: Option<i32>| eth_tx_id.and_then(|id| costs.remove(&id));
+        let commit = take_cost(eth_tx_ids.eth_commit_tx_id);
+        let prove = take_cost(eth_tx_ids.eth_prove_tx_id);
+        let execute = take_cost(eth_tx_ids.eth_execute_tx_id);
+        let total_cost = [&commit, &prove, &execute]
+            .into_iter()
+            .flatten()
+            .fold(U256::zero(), |acc, cost| acc + cost.l1_batch_cost);
+
+        Ok(Some(api::L1BatchL1Costs {
+            l1_batch_number,
+            commit,
+            prove,
+            execute,
+            total_cost,
+        }))
+    }
+
+    /// Re-queues the specified unconfirmed transactions. Besides them, all unconfirmed transactions of the same operator
+    /// that were never sent and have a nonce not less than the least nonce of the specified transactions are re-queued
+    /// as well, so that transactions queued behind the re-queued range don't end up with conflicting nonces.
+    /// The transactions get consecutive nonces starting from `new_first_nonce` in the order of their current nonces,
+    /// and their sending history is removed, so that the transactions are sent anew.
+    ///
+    /// Returns IDs of the re-queued transactions.
+    pub async fn requeue_txs(
+        &mut self,
+        eth_tx_ids: &[u32],
+        new_first_nonce: u64,
+    ) -> anyhow::Result<Vec<u32>> {
+        let mut eth_tx_ids: Vec<_> = eth_tx_ids.iter().map(|&id| id as i32).collect();
+        let new_first_nonce = i64::try_from(new_first_nonce).context("new_first_nonce")?;
+        let mut transaction = self
+            .storage
+            .start_transaction()
+            .await
+            .context("start_transaction()")?;
+
+        let queued_ids = sqlx::query_scalar!(
+            r#"
+            SELECT
+                queued.id
+            FROM
+                eth_txs AS queued
+                JOIN (
+                    SELECT
+                        from_addr,
+                        is_gateway,
+                        MIN(nonce) AS min_nonce
+                    FROM
+                        eth_txs
+                    WHERE
+                        id = ANY ($1)
+                    GROUP BY
+                        from_addr,
+                        is_gateway
+                ) AS requeued ON queued.from_addr IS NOT DISTINCT FROM requeued.from_addr
+                AND queued.is_gateway = requeued.is_gateway
+            WHERE
+                queued.confirmed_eth_tx_history_id IS NULL
+                AND queued.nonce >= requeued.min_nonce
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        eth_txs_history
+                    WHERE
+                        eth_tx_id = queued.id
+                )
+            "#,
+            &eth_tx_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        eth_tx_ids.extend(queued_ids);
+        eth_tx_ids.sort_unstable();
+        eth_tx_ids.dedup();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_txs_history
+            WHERE
+                eth_tx_id IN (
+                    SELECT
+                        id
+                    FROM
+                        eth_txs
+                    WHERE
+                        id = ANY ($1)
+                        AND confirmed_eth_tx_history_id IS NULL
+                )
+            "#,
+            &eth_tx_ids
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        let ids = sqlx::query_scalar!(
+            r#"
+            UPDATE eth_txs
+            SET
+                nonce = $2 + requeued.nonce_offset,
+                updated_at = NOW()
+            FROM
+                (
+                    SELECT
+                        id,
+                        ROW_NUMBER() OVER (
+                            ORDER BY
+                                nonce,
+                                id
+                        ) - 1 AS nonce_offset
+                    FROM
+                        eth_txs
+                    WHERE
+                        id = ANY ($1)
+                        AND confirmed_eth_tx_history_id IS NULL
+                ) AS requeued
+            WHERE
+                eth_txs.id = requeued.id
+            RETURNING
+                eth_txs.id
+            "#,
+            &eth_tx_ids,
+            new_first_nonce
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        let mut ids: Vec<_> = ids.into_iter().map(|id| id as u32).collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Saves a placeholder transaction sent by the specified operator, replacing the previous placeholder
+    /// with the same nonce if any.
+    pub async fn save_placeholder_tx(
+        &mut self,
+        operator: &str,
+        placeholder: &PlaceholderTx,
+    ) -> anyhow::Result<()> {
+        let base_fee_per_gas =
+            i64::try_from(placeholder.base_fee_per_gas).context("base_fee_per_gas")?;
+        let priority_fee_per_gas =
+            i64::try_from(placeholder.priority_fee_per_gas).context("priority_fee_per_gas")?;
+        let blob_base_fee_per_gas = placeholder
+            .blob_base_fee_per_gas
+            .map(i64::try_from)
+            .transpose()
+            .context("blob_base_fee_per_gas")?;
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_sender_placeholder_txs (
+                    operator,
+                    nonce,
+                    cancelled_eth_tx_id,
+                    tx_hash,
+                    base_fee_per_gas,
+                    priority_fee_per_gas,
+                    blob_base_fee_per_gas,
+                    sent_at_block,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, NOW(), NOW())
+            ON CONFLICT (operator, nonce) DO
+            UPDATE
+            SET
+                cancelled_eth_tx_id = excluded.cancelled_eth_tx_id,
+                tx_hash = excluded.tx_hash,
+                base_fee_per_gas = excluded.base_fee_per_gas,
+                priority_fee_per_gas = excluded.priority_fee_per_gas,
+                blob_base_fee_per_gas = excluded.blob_base_fee_per_gas,
+                sent_at_block = excluded.sent_at_block,
+                updated_at = NOW()
+            "#,
+            operator,
+            i64::from(placeholder.nonce.0),
+            placeholder.cancelled_eth_tx_id.map(|id| id as i32),
+            placeholder.tx_hash.as_bytes(),
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas,
+            placeholder.sent_at_block as i32
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns placeholder transactions of the specified operator ordered by nonce.
+    pub async fn get_placeholder_txs(
+        &mut self,
+        operator: &str,
+    ) -> sqlx::Result<Vec<PlaceholderTx>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                nonce,
+                cancelled_eth_tx_id,
+                tx_hash,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_base_fee_per_gas,
+                sent_at_block
+            FROM
+                eth_sender_placeholder_txs
+            WHERE
+                operator = $1
+            ORDER BY
+                nonce
+            "#,
+            operator
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PlaceholderTx {
+                nonce: Nonce(row.nonce as u32),
+                cancelled_eth_tx_id: row.cancelled_eth_tx_id.map(|id| id as u32),
+                tx_hash: H256::from_slice(&row.tx_hash),
+                base_fee_per_gas: row.base_fee_per_gas as u64,
+                priority_fee_per_gas: row.priority_fee_per_gas as u64,
+                blob_base_fee_per_gas: row.blob_base_fee_per_gas.map(|fee| fee as u64),
+                sent_at_block: row.sent_at_block as u32,
+            })
+            .collect())
+    }
+
+    /// Removes placeholder transactions of the specified operator with nonces below `nonce`.
+    pub async fn remove_placeholder_txs_before(
+        &mut self,
+        operator: &str,
+        nonce: Nonce,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_sender_placeholder_txs
+            WHERE
+                operator = $1
+                AND nonce < $2
+            "#,
+            operator,
+            i64::from(nonce.0)
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     pub async fn set_chain_id(&mut self, eth_tx_id: u32, chain_id: u64) -> anyhow::Result<()> {
         sqlx::query!(
             r#"
@@ -799,3 +1269,404 @@ impl EthSenderDal<'_, '_> {
         self.get_last_sent_eth_tx(eth_tx_id).await.unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        eth_sender::{EthTxBlobSidecarV1, SidecarBlobV1},
+        ProtocolVersion,
+    };
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    async fn save_confirmed_eth_tx(
+        conn: &mut Connection<'_, Core>,
+        nonce: u64,
+        tx_type: AggregatedActionType,
+        tx_hash: H256,
+        cost: &EthTxCost,
+    ) -> u32 {
+        let eth_tx = conn
+            .eth_sender_dal()
+            .save_eth_tx(
+                nonce,
+                vec![],
+                tx_type,
+                Address::default(),
+                1,
+                None,
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        conn.eth_sender_dal()
+            .insert_tx_history(eth_tx.id, 1, 1, None, tx_hash, &[], 1)
+            .await
+            .unwrap();
+        conn.eth_sender_dal()
+            .confirm_tx(tx_hash, cost.gas_used)
+            .await
+            .unwrap();
+        conn.eth_sender_dal()
+            .save_tx_cost(eth_tx.id, cost)
+            .await
+            .unwrap();
+        eth_tx.id
+    }
+
+    #[tokio::test]
+    async fn l1_batch_costs_are_split_among_covered_batches() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=2 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let commit_cost = EthTxCost {
+            gas_used: 100_000.into(),
+            effective_gas_price: 10.into(),
+            blob_gas_used: Some(131_072.into()),
+            blob_gas_price: Some(2.into()),
+        };
+        let commit_tx_id = save_confirmed_eth_tx(
+            &mut conn,
+            0,
+            AggregatedActionType::Commit,
+            H256::repeat_byte(1),
+            &commit_cost,
+        )
+        .await;
+        conn.blocks_dal()
+            .set_eth_tx_id(
+                L1BatchNumber(1)..=L1BatchNumber(2),
+                commit_tx_id,
+                AggregatedActionType::Commit,
+            )
+            .await
+            .unwrap();
+
+        let prove_cost = EthTxCost {
+            gas_used: 50_000.into(),
+            effective_gas_price: 10.into(),
+            blob_gas_used: None,
+            blob_gas_price: None,
+        };
+        let prove_tx_id = save_confirmed_eth_tx(
+            &mut conn,
+            1,
+            AggregatedActionType::PublishProofOnchain,
+            H256::repeat_byte(2),
+            &prove_cost,
+        )
+        .await;
+        conn.blocks_dal()
+            .set_eth_tx_id(
+                L1BatchNumber(1)..=L1BatchNumber(1),
+                prove_tx_id,
+                AggregatedActionType::PublishProofOnchain,
+            )
+            .await
+            .unwrap();
+
+        let costs = conn
+            .eth_sender_dal()
+            .get_l1_batch_l1_costs(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("no costs for L1 batch #1");
+        let commit = costs.commit.unwrap();
+        assert_eq!(commit.tx_hash, H256::repeat_byte(1));
+        assert_eq!(commit.total_cost, commit_cost.total_cost());
+        assert_eq!(commit.total_cost, U256::from(1_262_144));
+        assert_eq!(commit.blob_gas_used, Some(131_072.into()));
+        assert_eq!(commit.l1_batch_count, 2);
+        assert_eq!(commit.l1_batch_cost, U256::from(631_072));
+        let prove = costs.prove.unwrap();
+        assert_eq!(prove.l1_batch_count, 1);
+        assert_eq!(prove.l1_batch_cost, U256::from(500_000));
+        assert_eq!(costs.execute, None);
+        assert_eq!(costs.total_cost, U256::from(1_131_072));
+
+        let costs = conn
+            .eth_sender_dal()
+            .get_l1_batch_l1_costs(L1BatchNumber(2))
+            .await
+            .unwrap()
+            .expect("no costs for L1 batch #2");
+        assert_eq!(costs.prove, None);
+        assert_eq!(costs.total_cost, U256::from(631_072));
+
+        let missing_costs = conn
+            .eth_sender_dal()
+            .get_l1_batch_l1_costs(L1BatchNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(missing_costs, None);
+
+        let spent_on_commits = conn
+            .eth_sender_dal()
+            .get_spent_l1_fees(AggregatedActionType::Commit, Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert_eq!(spent_on_commits, commit_cost.total_cost());
+        let spent_on_executes = conn
+            .eth_sender_dal()
+            .get_spent_l1_fees(AggregatedActionType::Execute, Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert_eq!(spent_on_executes, U256::zero());
+    }
+
+    #[tokio::test]
+    async fn pending_l1_fees_upper_bound() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let confirmed_cost = EthTxCost {
+            gas_used: 100_000.into(),
+            effective_gas_price: 10.into(),
+            blob_gas_used: None,
+            blob_gas_price: None,
+        };
+        save_confirmed_eth_tx(
+            &mut conn,
+            0,
+            AggregatedActionType::Commit,
+            H256::repeat_byte(1),
+            &confirmed_cost,
+        )
+        .await;
+
+        let blob_sidecar = EthTxBlobSidecarV1 {
+            blobs: vec![
+                SidecarBlobV1 {
+                    blob: vec![],
+                    commitment: vec![],
+                    proof: vec![],
+                    versioned_hash: vec![],
+                };
+                2
+            ],
+        };
+        let pending_txs = [
+            (1, AggregatedActionType::Commit, None),
+            (2, AggregatedActionType::Commit, Some(blob_sidecar.into())),
+            (3, AggregatedActionType::Execute, None),
+            // Not sent; shouldn't be taken into account.
+            (4, AggregatedActionType::Commit, None),
+        ];
+        let mut eth_tx_ids = vec![];
+        for (nonce, tx_type, blob_sidecar) in pending_txs {
+            let eth_tx = conn
+                .eth_sender_dal()
+                .save_eth_tx(
+                    nonce,
+                    vec![],
+                    tx_type,
+                    Address::default(),
+                    1,
+                    None,
+                    blob_sidecar,
+                    false,
+                )
+                .await
+                .unwrap();
+            eth_tx_ids.push(eth_tx.id);
+        }
+        let attempts = [
+            (eth_tx_ids[0], 1, 1, None),
+            // Only the latest attempt should be taken into account.
+            (eth_tx_ids[0], 10, 2, None),
+            (eth_tx_ids[1], 5, 1, Some(3)),
+            (eth_tx_ids[2], 100, 100, None),
+        ];
+        for (i, (eth_tx_id, base_fee, priority_fee, blob_fee)) in attempts.into_iter().enumerate() {
+            let tx_hash = H256::from_low_u64_be(i as u64 + 100);
+            conn.eth_sender_dal()
+                .insert_tx_history(eth_tx_id, base_fee, priority_fee, blob_fee, tx_hash, &[], 1)
+                .await
+                .unwrap();
+        }
+
+        let pending_commits = conn
+            .eth_sender_dal()
+            .get_pending_l1_fees_upper_bound(AggregatedActionType::Commit, 1_000)
+            .await
+            .unwrap();
+        let expected_blob_cost = 2 * EthTxCost::GAS_PER_BLOB * 3;
+        assert_eq!(
+            pending_commits,
+            U256::from(12 * 1_000 + 6 * 1_000 + expected_blob_cost)
+        );
+        let pending_executes = conn
+            .eth_sender_dal()
+            .get_pending_l1_fees_upper_bound(AggregatedActionType::Execute, 1_000)
+            .await
+            .unwrap();
+        assert_eq!(pending_executes, U256::from(200 * 1_000));
+        let pending_proofs = conn
+            .eth_sender_dal()
+            .get_pending_l1_fees_upper_bound(AggregatedActionType::PublishProofOnchain, 1_000)
+            .await
+            .unwrap();
+        assert_eq!(pending_proofs, U256::zero());
+    }
+
+    #[tokio::test]
+    async fn requeued_txs_get_consecutive_nonces() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut eth_tx_ids = vec![];
+        for nonce in [3, 5, 6, 7] {
+            let eth_tx = conn
+                .eth_sender_dal()
+                .save_eth_tx(
+                    nonce,
+                    vec![],
+                    AggregatedActionType::Commit,
+                    Address::default(),
+                    1,
+                    None,
+                    None,
+                    false,
+                )
+                .await
+                .unwrap();
+            conn.eth_sender_dal()
+                .insert_tx_history(eth_tx.id, 1, 1, None, H256::from_low_u64_be(nonce), &[], 1)
+                .await
+                .unwrap();
+            eth_tx_ids.push(eth_tx.id);
+        }
+
+        // Txs that are not sent yet: one queued behind the re-queued range, and one of another operator.
+        let mut unsent_tx_ids = vec![];
+        for (nonce, from_addr) in [(8, None), (8, Some(Address::repeat_byte(1)))] {
+            let eth_tx = conn
+                .eth_sender_dal()
+                .save_eth_tx(
+                    nonce,
+                    vec![],
+                    AggregatedActionType::Commit,
+                    Address::default(),
+                    1,
+                    from_addr,
+                    None,
+                    false,
+                )
+                .await
+                .unwrap();
+            unsent_tx_ids.push(eth_tx.id);
+        }
+
+        // The tx with nonce 5 is left intact, e.g. because it is mined.
+        let requeued_ids = [eth_tx_ids[0], eth_tx_ids[2], eth_tx_ids[3]];
+        let ids = conn
+            .eth_sender_dal()
+            .requeue_txs(&requeued_ids, 8)
+            .await
+            .unwrap();
+        assert_eq!(
+            ids,
+            [
+                eth_tx_ids[0],
+                eth_tx_ids[2],
+                eth_tx_ids[3],
+                unsent_tx_ids[0]
+            ]
+        );
+
+        let mut nonces = vec![];
+        for &id in &eth_tx_ids {
+            let eth_tx = conn.eth_sender_dal().get_eth_tx(id).await.unwrap().unwrap();
+            nonces.push(eth_tx.nonce.0);
+            let history = conn
+                .eth_sender_dal()
+                .get_tx_history_to_check(id)
+                .await
+                .unwrap();
+            assert_eq!(history.is_empty(), requeued_ids.contains(&id));
+        }
+        assert_eq!(nonces, [8, 5, 9, 10]);
+
+        let mut unsent_nonces = vec![];
+        for &id in &unsent_tx_ids {
+            let eth_tx = conn.eth_sender_dal().get_eth_tx(id).await.unwrap().unwrap();
+            unsent_nonces.push(eth_tx.nonce.0);
+        }
+        assert_eq!(unsent_nonces, [11, 8]);
+    }
+
+    #[tokio::test]
+    async fn placeholder_txs_are_persisted() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let placeholders: Vec<_> = (0..3)
+            .map(|nonce| PlaceholderTx {
+                nonce: Nonce(nonce),
+                cancelled_eth_tx_id: None,
+                tx_hash: H256::repeat_byte(nonce as u8),
+                base_fee_per_gas: 100,
+                priority_fee_per_gas: 10,
+                blob_base_fee_per_gas: None,
+                sent_at_block: 42,
+            })
+            .collect();
+        for placeholder in &placeholders {
+            conn.eth_sender_dal()
+                .save_placeholder_tx("non_blob", placeholder)
+                .await
+                .unwrap();
+        }
+        let resent_placeholder = PlaceholderTx {
+            tx_hash: H256::repeat_byte(0xff),
+            base_fee_per_gas: 200,
+            blob_base_fee_per_gas: Some(5),
+            sent_at_block: 43,
+            ..placeholders[2].clone()
+        };
+        conn.eth_sender_dal()
+            .save_placeholder_tx("non_blob", &resent_placeholder)
+            .await
+            .unwrap();
+
+        let loaded = conn
+            .eth_sender_dal()
+            .get_placeholder_txs("non_blob")
+            .await
+            .unwrap();
+        assert_eq!(
+            loaded,
+            [
+                placeholders[0].clone(),
+                placeholders[1].clone(),
+                resent_placeholder.clone()
+            ]
+        );
+        let loaded = conn
+            .eth_sender_dal()
+            .get_placeholder_txs("blob")
+            .await
+            .unwrap();
+        assert!(loaded.is_empty());
+
+        conn.eth_sender_dal()
+            .remove_placeholder_txs_before("non_blob", Nonce(2))
+            .await
+            .unwrap();
+        let loaded = conn
+            .eth_sender_dal()
+            .get_placeholder_txs("non_blob")
+            .await
+            .unwrap();
+        assert_eq!(loaded, [resent_placeholder]);
+    }
+}
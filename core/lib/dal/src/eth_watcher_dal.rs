@@ -0,0 +1,277 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::H256;
+
+use crate::Core;
+
+/// DAL for the Ethereum watcher; stores cursors of its event processors and hashes of processed L1 blocks.
+#[derive(Debug)]
+pub struct EthWatcherDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl EthWatcherDal<'_, '_> {
+    /// Returns the last L1 block processed by the specified event processor, or `None` if the processor
+    /// has never persisted its progress.
+    pub async fn get_last_processed_l1_block(
+        &mut self,
+        processor_name: &str,
+    ) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_processed_l1_block
+            FROM
+                eth_watch_processor_cursors
+            WHERE
+                processor_name = $1
+            "#,
+            processor_name
+        )
+        .instrument("get_last_processed_l1_block")
+        .with_arg("processor_name", &processor_name)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| row.last_processed_l1_block as u64))
+    }
+
+    /// Sets the last L1 block processed by the specified event processor.
+    pub async fn set_last_processed_l1_block(
+        &mut self,
+        processor_name: &str,
+        l1_block: u64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watch_processor_cursors (processor_name, last_processed_l1_block, created_at, updated_at)
+            VALUES
+                ($1, $2, NOW(), NOW())
+            ON CONFLICT (processor_name) DO
+            UPDATE
+            SET
+                last_processed_l1_block = $2,
+                updated_at = NOW()
+            "#,
+            processor_name,
+            l1_block as i64
+        )
+        .instrument("set_last_processed_l1_block")
+        .with_arg("processor_name", &processor_name)
+        .with_arg("l1_block", &l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Saves the hash of a processed L1 block, overwriting the previously saved hash (if any).
+    pub async fn insert_l1_block_hash(&mut self, l1_block: u64, hash: H256) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watch_l1_block_hashes (l1_block_number, l1_block_hash, created_at)
+            VALUES
+                ($1, $2, NOW())
+            ON CONFLICT (l1_block_number) DO
+            UPDATE
+            SET
+                l1_block_hash = $2
+            "#,
+            l1_block as i64,
+            hash.as_bytes()
+        )
+        .instrument("insert_l1_block_hash")
+        .with_arg("l1_block", &l1_block)
+        .with_arg("hash", &hash)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the greatest L1 block number with a saved hash.
+    pub async fn get_last_l1_block_with_hash(&mut self) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_block_number) AS "l1_block_number"
+            FROM
+                eth_watch_l1_block_hashes
+            "#
+        )
+        .instrument("get_last_l1_block_with_hash")
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.l1_block_number.map(|number| number as u64))
+    }
+
+    /// Returns the greatest L1 block number with a saved hash preceding the specified block.
+    pub async fn get_last_l1_block_with_hash_before(
+        &mut self,
+        before_l1_block: u64,
+    ) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_block_number) AS "l1_block_number"
+            FROM
+                eth_watch_l1_block_hashes
+            WHERE
+                l1_block_number < $1
+            "#,
+            before_l1_block as i64
+        )
+        .instrument("get_last_l1_block_with_hash_before")
+        .with_arg("before_l1_block", &before_l1_block)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.l1_block_number.map(|number| number as u64))
+    }
+
+    /// Returns saved hashes of L1 blocks starting from the specified block, ordered by block number.
+    pub async fn get_l1_block_hashes(&mut self, from_l1_block: u64) -> DalResult<Vec<(u64, H256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_block_number,
+                l1_block_hash
+            FROM
+                eth_watch_l1_block_hashes
+            WHERE
+                l1_block_number >= $1
+            ORDER BY
+                l1_block_number
+            "#,
+            from_l1_block as i64
+        )
+        .instrument("get_l1_block_hashes")
+        .with_arg("from_l1_block", &from_l1_block)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.l1_block_number as u64,
+                    H256::from_slice(&row.l1_block_hash),
+                )
+            })
+            .collect())
+    }
+
+    /// Removes saved hashes of L1 blocks starting from the specified block (e.g., after an L1 reorg).
+    pub async fn remove_l1_block_hashes_since(&mut self, l1_block: u64) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_l1_block_hashes
+            WHERE
+                l1_block_number >= $1
+            "#,
+            l1_block as i64
+        )
+        .instrument("remove_l1_block_hashes_since")
+        .with_arg("l1_block", &l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes saved hashes of L1 blocks preceding the specified block.
+    pub async fn prune_l1_block_hashes(&mut self, before_l1_block: u64) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_l1_block_hashes
+            WHERE
+                l1_block_number < $1
+            "#,
+            before_l1_block as i64
+        )
+        .instrument("prune_l1_block_hashes")
+        .with_arg("before_l1_block", &before_l1_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn processor_cursors_are_persisted() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let cursor = conn
+            .eth_watcher_dal()
+            .get_last_processed_l1_block("priority_ops")
+            .await
+            .unwrap();
+        assert_eq!(cursor, None);
+
+        conn.eth_watcher_dal()
+            .set_last_processed_l1_block("priority_ops", 10)
+            .await
+            .unwrap();
+        conn.eth_watcher_dal()
+            .set_last_processed_l1_block("custom", 5)
+            .await
+            .unwrap();
+        conn.eth_watcher_dal()
+            .set_last_processed_l1_block("priority_ops", 20)
+            .await
+            .unwrap();
+
+        let cursor = conn
+            .eth_watcher_dal()
+            .get_last_processed_l1_block("priority_ops")
+            .await
+            .unwrap();
+        assert_eq!(cursor, Some(20));
+        let cursor = conn
+            .eth_watcher_dal()
+            .get_last_processed_l1_block("custom")
+            .await
+            .unwrap();
+        assert_eq!(cursor, Some(5));
+    }
+
+    #[tokio::test]
+    async fn l1_block_hashes_are_persisted() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watcher_dal();
+
+        assert_eq!(dal.get_last_l1_block_with_hash().await.unwrap(), None);
+        for l1_block in [10, 15, 20] {
+            dal.insert_l1_block_hash(l1_block, H256::repeat_byte(l1_block as u8))
+                .await
+                .unwrap();
+        }
+        dal.insert_l1_block_hash(20, H256::repeat_byte(0xff))
+            .await
+            .unwrap();
+        assert_eq!(dal.get_last_l1_block_with_hash().await.unwrap(), Some(20));
+        let last_block = dal.get_last_l1_block_with_hash_before(20).await.unwrap();
+        assert_eq!(last_block, Some(15));
+        let last_block = dal.get_last_l1_block_with_hash_before(10).await.unwrap();
+        assert_eq!(last_block, None);
+
+        let hashes = dal.get_l1_block_hashes(12).await.unwrap();
+        assert_eq!(
+            hashes,
+            [(15, H256::repeat_byte(15)), (20, H256::repeat_byte(0xff))]
+        );
+
+        dal.prune_l1_block_hashes(15).await.unwrap();
+        let hashes = dal.get_l1_block_hashes(0).await.unwrap();
+        assert_eq!(hashes.len(), 2);
+        dal.remove_l1_block_hashes_since(20).await.unwrap();
+        let hashes = dal.get_l1_block_hashes(0).await.unwrap();
+        assert_eq!(hashes, [(15, H256::repeat_byte(15))]);
+    }
+}
@@ -1,5 +1,8 @@
 use chrono::NaiveDateTime;
-use zksync_types::{pubdata_da::DataAvailabilityBlob, L1BatchNumber};
+use zksync_types::{
+    pubdata_da::{DataAvailabilityBlob, DataAvailabilityBlobPart},
+    L1BatchNumber,
+};
 
 /// Represents a blob in the data availability layer.
 #[derive(Debug, Clone)]
@@ -21,6 +24,30 @@ impl From<StorageDABlob> for DataAvailabilityBlob {
     }
 }
 
+/// Represents a part of the L1 batch pubdata dispatched as a separate blob.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageDABlobPart {
+    pub l1_batch_number: i64,
+    pub part_index: i32,
+    pub chunk_size: i64,
+    pub blob_id: String,
+    pub inclusion_data: Option<Vec<u8>>,
+    pub sent_at: NaiveDateTime,
+}
+
+impl From<StorageDABlobPart> for DataAvailabilityBlobPart {
+    fn from(part: StorageDABlobPart) -> DataAvailabilityBlobPart {
+        DataAvailabilityBlobPart {
+            l1_batch_number: L1BatchNumber(part.l1_batch_number as u32),
+            part_index: part.part_index as u32,
+            chunk_size: part.chunk_size as u64,
+            blob_id: part.blob_id,
+            inclusion_data: part.inclusion_data,
+            sent_at: part.sent_at.and_utc(),
+        }
+    }
+}
+
 /// A small struct used to store a batch and its data availability, which are retrieved from the database.
 #[derive(Debug)]
 pub struct L1BatchDA {
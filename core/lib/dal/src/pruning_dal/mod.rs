@@ -1,7 +1,8 @@
 use std::ops;
 
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::{L1BatchNumber, L2BlockNumber};
+use zksync_types::{Address, L1BatchNumber, L2BlockNumber, CONTRACT_DEPLOYER_ADDRESS};
+use zksync_vm_interface::VmEvent;
 
 use crate::Core;
 
@@ -33,6 +34,23 @@ pub struct HardPruningStats {
     pub deleted_l2_to_l1_logs: u64,
 }
 
+/// Statistics about a single history pruning iteration.
+#[derive(Debug, Default)]
+pub struct HistoryPruningStats {
+    pub deleted_events: u64,
+    pub deleted_call_traces: u64,
+    pub cleared_transactions: u64,
+}
+
+/// Information about history pruning (see [`PruningDal::prune_history()`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryPruningInfo {
+    pub last_pruned_l1_batch: L1BatchNumber,
+    pub last_pruned_l2_block: L2BlockNumber,
+    /// Addresses with history retained for all L2 blocks up to and including `last_pruned_l2_block`.
+    pub retained_addresses: Vec<Address>,
+}
+
 #[derive(Debug, sqlx::Type)]
 #[sqlx(type_name = "prune_type")]
 enum PruneType {
@@ -196,6 +214,250 @@ impl PruningDal<'_, '_> {
         Ok(stats)
     }
 
+    /// Returns the last L1 batch and L2 block with pruned history (see [`Self::prune_history()`]).
+    pub async fn get_last_history_pruned_batch(
+        &mut self,
+    ) -> DalResult<Option<(L1BatchNumber, L2BlockNumber)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                pruned_l1_batch,
+                pruned_miniblock
+            FROM
+                history_pruning_log
+            ORDER BY
+                pruned_l1_batch DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_last_history_pruned_batch")
+        .report_latency()
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| {
+            (
+                L1BatchNumber(row.pruned_l1_batch as u32),
+                L2BlockNumber(row.pruned_miniblock as u32),
+            )
+        }))
+    }
+
+    /// Returns information about the last history pruning (see [`Self::prune_history()`]).
+    pub async fn get_history_pruning_info(&mut self) -> DalResult<Option<HistoryPruningInfo>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                pruned_l1_batch,
+                pruned_miniblock,
+                retained_addresses
+            FROM
+                history_pruning_log
+            ORDER BY
+                pruned_l1_batch DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_history_pruning_info")
+        .report_latency()
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| HistoryPruningInfo {
+            last_pruned_l1_batch: L1BatchNumber(row.pruned_l1_batch as u32),
+            last_pruned_l2_block: L2BlockNumber(row.pruned_miniblock as u32),
+            retained_addresses: row
+                .retained_addresses
+                .iter()
+                .map(|address| Address::from_slice(address))
+                .collect(),
+        }))
+    }
+
+    /// Prunes history (events, call traces and transaction execution info) for all L2 blocks up to and including
+    /// `last_l2_block_to_prune`, while retaining blocks, transactions and state. History related to `retained_addresses`
+    /// is kept: events emitted by these addresses, and events, call traces and execution info of transactions
+    /// calling these addresses. `ContractDeployed` events are always kept since they are used to
+    /// determine deployed contract addresses in transaction receipts.
+    ///
+    /// The pruning log records addresses with history retained across *all* pruned L2 blocks (i.e., the intersection
+    /// of `retained_addresses` with the previously recorded ones), so that the API can decide whether history for
+    /// a certain address is complete.
+    pub async fn prune_history(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+        retained_addresses: &[Address],
+    ) -> DalResult<HistoryPruningStats> {
+        let prev_info = self.get_history_pruning_info().await?;
+        let first_l2_block_to_prune = prev_info
+            .as_ref()
+            .map_or(L2BlockNumber(0), |info| info.last_pruned_l2_block + 1);
+        let recorded_addresses: Vec<_> = retained_addresses
+            .iter()
+            .filter(|address| {
+                prev_info
+                    .as_ref()
+                    .map_or(true, |info| info.retained_addresses.contains(*address))
+            })
+            .map(Address::as_bytes)
+            .collect();
+        let stats = if first_l2_block_to_prune <= last_l2_block_to_prune {
+            let l2_blocks_to_prune = first_l2_block_to_prune..=last_l2_block_to_prune;
+            HistoryPruningStats {
+                deleted_events: self
+                    .delete_history_events(l2_blocks_to_prune.clone(), retained_addresses)
+                    .await?,
+                deleted_call_traces: self
+                    .delete_history_call_traces(l2_blocks_to_prune.clone(), retained_addresses)
+                    .await?,
+                cleared_transactions: self
+                    .clear_history_execution_info(l2_blocks_to_prune, retained_addresses)
+                    .await?,
+            }
+        } else {
+            HistoryPruningStats::default()
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                history_pruning_log (
+                    pruned_l1_batch,
+                    pruned_miniblock,
+                    retained_addresses,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, NOW(), NOW())
+            "#,
+            i64::from(last_l1_batch_to_prune.0),
+            i64::from(last_l2_block_to_prune.0),
+            &recorded_addresses as &[&[u8]]
+        )
+        .instrument("prune_history#insert_history_pruning_log")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("last_l2_block_to_prune", &last_l2_block_to_prune)
+        .with_arg("recorded_addresses.len", &recorded_addresses.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(stats)
+    }
+
+    async fn delete_history_events(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
+    ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND address <> ALL ($3)
+                AND NOT (
+                    address = $4
+                    AND topic1 = $5
+                )
+                AND tx_hash NOT IN (
+                    SELECT
+                        hash
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND contract_address = ANY ($3)
+                )
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_addresses as &[&[u8]],
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            VmEvent::DEPLOY_EVENT_SIGNATURE.as_bytes()
+        )
+        .instrument("prune_history#delete_events")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .with_arg("retained_addresses.len", &retained_addresses.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    async fn delete_history_call_traces(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
+    ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM call_traces
+            WHERE
+                tx_hash IN (
+                    SELECT
+                        hash
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND (
+                            contract_address IS NULL
+                            OR contract_address <> ALL ($3)
+                        )
+                )
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_addresses as &[&[u8]]
+        )
+        .instrument("prune_history#delete_call_traces")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .with_arg("retained_addresses.len", &retained_addresses.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
+    // Unlike `clear_transaction_fields()`, transaction `input` and `data` are retained, so that transactions
+    // and their receipts are still returned by the API.
+    async fn clear_history_execution_info(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
+    ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
+        let execution_result = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                execution_info = '{}',
+                updated_at = NOW()
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND execution_info != '{}'::jsonb
+                AND (
+                    contract_address IS NULL
+                    OR contract_address <> ALL ($3)
+                )
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_addresses as &[&[u8]]
+        )
+        .instrument("prune_history#clear_execution_info")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .with_arg("retained_addresses.len", &retained_addresses.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     async fn delete_events(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
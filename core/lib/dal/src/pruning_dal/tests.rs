@@ -503,3 +503,98 @@ async fn transactions_are_handled_correctly_after_pruning() {
         .unwrap();
     assert!(transaction_details.is_none(), "{transaction_details:?}");
 }
+
+async fn get_all_event_locations(conn: &mut Connection<'_, Core>) -> Vec<(L2BlockNumber, Address)> {
+    let logs = conn
+        .events_dal()
+        .get_logs_by_tx_hashes(&[H256([1; 32]), H256([2; 32])])
+        .await
+        .unwrap();
+    let mut locations: Vec<_> = logs
+        .into_values()
+        .flatten()
+        .map(|log| {
+            (
+                L2BlockNumber(log.block_number.unwrap().as_u32()),
+                log.address,
+            )
+        })
+        .collect();
+    locations.sort_unstable();
+    locations
+}
+
+#[tokio::test]
+async fn history_can_be_pruned() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_realistic_l1_batches(&mut conn, 10).await;
+    assert_eq!(
+        conn.pruning_dal()
+            .get_last_history_pruned_batch()
+            .await
+            .unwrap(),
+        None
+    );
+
+    let retained_address = Address::repeat_byte(1);
+    let stats = conn
+        .pruning_dal()
+        .prune_history(L1BatchNumber(2), L2BlockNumber(5), &[retained_address])
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_events, 24);
+    assert_eq!(
+        conn.pruning_dal()
+            .get_last_history_pruned_batch()
+            .await
+            .unwrap(),
+        Some((L1BatchNumber(2), L2BlockNumber(5)))
+    );
+    assert_eq!(
+        conn.pruning_dal().get_history_pruning_info().await.unwrap(),
+        Some(HistoryPruningInfo {
+            last_pruned_l1_batch: L1BatchNumber(2),
+            last_pruned_l2_block: L2BlockNumber(5),
+            retained_addresses: vec![retained_address],
+        })
+    );
+
+    let event_locations = get_all_event_locations(&mut conn).await;
+    assert_eq!(event_locations.len(), 100 - 24);
+    for (l2_block_number, address) in &event_locations {
+        if l2_block_number.0 <= 5 {
+            assert_eq!(*address, retained_address);
+        }
+    }
+
+    let other_address = Address::repeat_byte(0xff);
+    let stats = conn
+        .pruning_dal()
+        .prune_history(L1BatchNumber(4), L2BlockNumber(9), &[other_address])
+        .await
+        .unwrap();
+    // Events retained on the previous iteration are not affected.
+    assert_eq!(stats.deleted_events, 20);
+    // Only addresses retained on all iterations should be recorded.
+    let history_info = conn
+        .pruning_dal()
+        .get_history_pruning_info()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(history_info.last_pruned_l2_block, L2BlockNumber(9));
+    assert_eq!(history_info.retained_addresses, []);
+    let event_locations = get_all_event_locations(&mut conn).await;
+    assert_eq!(event_locations.len(), 100 - 24 - 20);
+    assert!(event_locations
+        .iter()
+        .all(|(l2_block_number, _)| l2_block_number.0 <= 5 || l2_block_number.0 >= 10));
+
+    // Blocks must not be affected by history pruning.
+    assert_l1_batch_objects_exists(&mut conn, L1BatchNumber(0)..=L1BatchNumber(10)).await;
+    assert_eq!(
+        conn.pruning_dal().get_pruning_info().await.unwrap(),
+        PruningInfo::default()
+    );
+}
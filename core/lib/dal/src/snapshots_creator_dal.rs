@@ -1,3 +1,5 @@
+use std::ops;
+
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
 use zksync_types::{
     snapshots::SnapshotStorageLog, AccountTreeId, Address, L1BatchNumber, L2BlockNumber,
@@ -107,6 +109,101 @@ impl SnapshotsCreatorDal<'_, '_> {
         Ok(storage_logs)
     }
 
+    /// Returns an upper bound on the number of storage keys modified in the specified L2 blocks (i.e., the number
+    /// of storage logs in these blocks). Used to choose chunking for delta snapshots.
+    pub async fn get_storage_logs_count_in_l2_blocks(
+        &mut self,
+        l2_block_numbers: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            "#,
+            i64::from(l2_block_numbers.start().0),
+            i64::from(l2_block_numbers.end().0)
+        )
+        .instrument("get_storage_logs_count_in_l2_blocks")
+        .with_arg("l2_block_numbers", &l2_block_numbers)
+        .report_latency()
+        .expect_slow_query()
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
+    /// Constructs a `storage_logs` chunk of a delta snapshot, i.e. the latest values of the storage slots modified
+    /// in the specified L2 blocks. The end of the range MUST be the last L2 block of the `l1_batch_number` batch.
+    pub async fn get_storage_logs_delta_chunk(
+        &mut self,
+        l2_block_numbers: ops::RangeInclusive<L2BlockNumber>,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys_range: ops::RangeInclusive<H256>,
+    ) -> DalResult<Vec<SnapshotStorageLog>> {
+        // Filtering by `l1_batch_number` is required for the same reason as in `get_storage_logs_chunk()`.
+        // Logs for deduplicated writes in the range (e.g., a write to a new slot and back to zero) are filtered
+        // out by the join with initial writes, or don't change the slot value.
+        let storage_logs = sqlx::query!(
+            r#"
+            SELECT
+                storage_logs.hashed_key AS "hashed_key!",
+                storage_logs.value AS "value!",
+                storage_logs.miniblock_number AS "miniblock_number!",
+                initial_writes.l1_batch_number AS "l1_batch_number!",
+                initial_writes.index
+            FROM
+                (
+                    SELECT
+                        hashed_key,
+                        MAX(ARRAY[miniblock_number, operation_number]::INT[]) AS op
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND hashed_key >= $4
+                        AND hashed_key <= $5
+                    GROUP BY
+                        hashed_key
+                    ORDER BY
+                        hashed_key
+                ) AS keys
+                INNER JOIN storage_logs ON keys.hashed_key = storage_logs.hashed_key
+                AND storage_logs.miniblock_number = keys.op[1]
+                AND storage_logs.operation_number = keys.op[2]
+                INNER JOIN initial_writes ON keys.hashed_key = initial_writes.hashed_key
+            WHERE
+                initial_writes.l1_batch_number <= $3
+            "#,
+            i64::from(l2_block_numbers.start().0),
+            i64::from(l2_block_numbers.end().0),
+            i64::from(l1_batch_number.0),
+            hashed_keys_range.start().as_bytes(),
+            hashed_keys_range.end().as_bytes()
+        )
+        .instrument("get_storage_logs_delta_chunk")
+        .with_arg("l2_block_numbers", &l2_block_numbers)
+        .with_arg("min_hashed_key", &hashed_keys_range.start())
+        .with_arg("max_hashed_key", &hashed_keys_range.end())
+        .report_latency()
+        .expect_slow_query()
+        .fetch_all(self.storage)
+        .await?
+        .iter()
+        .map(|row| SnapshotStorageLog {
+            key: H256::from_slice(&row.hashed_key),
+            value: H256::from_slice(&row.value),
+            l1_batch_number_of_initial_write: L1BatchNumber(row.l1_batch_number as u32),
+            enumeration_index: row.index as u64,
+        })
+        .collect();
+        Ok(storage_logs)
+    }
+
     /// Same as [`Self::get_storage_logs_chunk()`], but returns full keys.
     #[deprecated(
         note = "will fail if called on a node restored from a v1 snapshot; use `get_storage_logs_chunk()` instead"
@@ -204,6 +301,37 @@ impl SnapshotsCreatorDal<'_, '_> {
             .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
             .collect())
     }
+
+    /// Returns factory dependencies added in the specified L2 blocks.
+    pub async fn get_factory_deps_in_l2_blocks(
+        &mut self,
+        l2_block_numbers: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(H256, Vec<u8>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash,
+                bytecode
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            "#,
+            i64::from(l2_block_numbers.start().0),
+            i64::from(l2_block_numbers.end().0)
+        )
+        .instrument("get_factory_deps_in_l2_blocks")
+        .with_arg("l2_block_numbers", &l2_block_numbers)
+        .report_latency()
+        .expect_slow_query()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
+            .collect())
+    }
 }
 
 #[cfg(test)]
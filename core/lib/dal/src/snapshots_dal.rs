@@ -14,6 +14,7 @@ use crate::Core;
 struct StorageSnapshotMetadata {
     version: i32,
     l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
     storage_logs_filepaths: Vec<String>,
     factory_deps_filepath: String,
 }
@@ -28,6 +29,9 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
         Ok(Self {
             version,
             l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            base_l1_batch_number: row
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
             storage_logs_filepaths: row
                 .storage_logs_filepaths
                 .into_iter()
@@ -79,6 +83,80 @@ impl SnapshotsDal<'_, '_> {
         Ok(())
     }
 
+    /// Adds a delta snapshot based on the snapshot for `base_l1_batch_number`.
+    pub async fn add_delta_snapshot(
+        &mut self,
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: L1BatchNumber,
+        storage_logs_chunk_count: u64,
+        factory_deps_filepaths: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                snapshots (
+                    VERSION,
+                    l1_batch_number,
+                    base_l1_batch_number,
+                    storage_logs_filepaths,
+                    factory_deps_filepath,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, ARRAY_FILL(''::TEXT, ARRAY[$4::INTEGER]), $5, NOW(), NOW())
+            "#,
+            version as i32,
+            l1_batch_number.0 as i32,
+            base_l1_batch_number.0 as i32,
+            storage_logs_chunk_count as i32,
+            factory_deps_filepaths,
+        )
+        .instrument("add_delta_snapshot")
+        .with_arg("version", &version)
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("base_l1_batch_number", &base_l1_batch_number)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the newest complete snapshot strictly before the specified L1 batch.
+    pub async fn get_newest_complete_snapshot_before(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<SnapshotMetadata>> {
+        sqlx::query_as!(
+            StorageSnapshotMetadata,
+            r#"
+            SELECT
+                VERSION,
+                l1_batch_number,
+                base_l1_batch_number,
+                factory_deps_filepath,
+                storage_logs_filepaths
+            FROM
+                snapshots
+            WHERE
+                l1_batch_number < $1
+                AND NOT (''::TEXT = ANY (storage_logs_filepaths))
+            ORDER BY
+                l1_batch_number DESC
+            LIMIT
+                1
+            "#,
+            l1_batch_number.0 as i32
+        )
+        .try_map(SnapshotMetadata::try_from)
+        .instrument("get_newest_complete_snapshot_before")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .report_latency()
+        .fetch_optional(self.storage)
+        .await
+    }
+
     pub async fn add_storage_logs_filepath_for_snapshot(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -135,6 +213,36 @@ impl SnapshotsDal<'_, '_> {
         })
     }
 
+    /// Same as [`Self::get_all_complete_snapshots()`], but only returns full snapshots (i.e., excludes delta snapshots).
+    pub async fn get_all_complete_full_snapshots(&mut self) -> DalResult<AllSnapshots> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                snapshots
+            WHERE
+                NOT (''::TEXT = ANY (storage_logs_filepaths))
+                AND base_l1_batch_number IS NULL
+            ORDER BY
+                l1_batch_number DESC
+            "#
+        )
+        .instrument("get_all_complete_full_snapshots")
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        let snapshots_l1_batch_numbers = rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.l1_batch_number as u32))
+            .collect();
+
+        Ok(AllSnapshots {
+            snapshots_l1_batch_numbers,
+        })
+    }
+
     pub async fn get_newest_snapshot_metadata(&mut self) -> DalResult<Option<SnapshotMetadata>> {
         sqlx::query_as!(
             StorageSnapshotMetadata,
@@ -142,6 +250,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             FROM
@@ -169,6 +278,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             FROM
@@ -200,6 +310,7 @@ impl SnapshotsDal<'_, '_> {
             RETURNING
                 VERSION,
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             "#,
@@ -267,6 +378,79 @@ mod tests {
         assert_eq!(snapshot_metadata.l1_batch_number, l1_batch_number);
     }
 
+    #[tokio::test]
+    async fn adding_delta_snapshot() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let base_l1_batch_number = L1BatchNumber(100);
+        dal.add_snapshot(
+            SnapshotVersion::Version1,
+            base_l1_batch_number,
+            1,
+            "gs:///bucket/factory_deps.bin",
+        )
+        .await
+        .unwrap();
+        let l1_batch_number = L1BatchNumber(110);
+        dal.add_delta_snapshot(
+            SnapshotVersion::Version2,
+            l1_batch_number,
+            base_l1_batch_number,
+            1,
+            "gs:///bucket/factory_deps_delta.bin",
+        )
+        .await
+        .unwrap();
+
+        let base = dal
+            .get_newest_complete_snapshot_before(l1_batch_number)
+            .await
+            .unwrap();
+        assert!(base.is_none(), "{base:?}");
+        dal.add_storage_logs_filepath_for_snapshot(
+            base_l1_batch_number,
+            0,
+            "gs:///bucket/chunk.bin",
+        )
+        .await
+        .unwrap();
+        let base = dal
+            .get_newest_complete_snapshot_before(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no base snapshot");
+        assert_eq!(base.l1_batch_number, base_l1_batch_number);
+        assert!(!base.is_delta());
+
+        let snapshot_metadata = dal
+            .get_snapshot_metadata(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("snapshot is not persisted");
+        assert_eq!(
+            snapshot_metadata.base_l1_batch_number,
+            Some(base_l1_batch_number)
+        );
+        assert!(snapshot_metadata.is_delta());
+        assert!(!snapshot_metadata.is_complete());
+
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            0,
+            "gs:///bucket/delta_chunk.bin",
+        )
+        .await
+        .unwrap();
+        let snapshots = dal.get_all_complete_snapshots().await.unwrap();
+        assert_eq!(
+            snapshots.snapshots_l1_batch_numbers,
+            [l1_batch_number, base_l1_batch_number]
+        );
+        let snapshots = dal.get_all_complete_full_snapshots().await.unwrap();
+        assert_eq!(snapshots.snapshots_l1_batch_numbers, [base_l1_batch_number]);
+    }
+
     #[tokio::test]
     async fn deleting_snapshots() {
         let pool = ConnectionPool::<Core>::test_pool().await;
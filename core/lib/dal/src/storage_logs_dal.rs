@@ -151,6 +151,64 @@ impl StorageLogsDal<'_, '_> {
         copy.send(buffer.as_bytes()).await
     }
 
+    /// Upserts storage logs from a delta snapshot. Unlike [`Self::insert_storage_logs_from_snapshot()`],
+    /// overwrites values of logs already inserted for the same L2 block (e.g., from the base snapshot).
+    pub async fn upsert_storage_logs_from_snapshot(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        snapshot_storage_logs: &[SnapshotStorageLog],
+    ) -> DalResult<()> {
+        let mut hashed_keys = Vec::with_capacity(snapshot_storage_logs.len());
+        let mut values = Vec::with_capacity(snapshot_storage_logs.len());
+        let mut operation_numbers = Vec::with_capacity(snapshot_storage_logs.len());
+        for log in snapshot_storage_logs {
+            hashed_keys.push(log.key.as_bytes());
+            values.push(log.value.as_bytes());
+            operation_numbers.push(log.enumeration_index as i32);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                storage_logs (
+                    hashed_key,
+                    value,
+                    operation_number,
+                    tx_hash,
+                    miniblock_number,
+                    created_at,
+                    updated_at
+                )
+            SELECT
+                u.hashed_key,
+                u.value,
+                u.operation_number,
+                $4,
+                $5,
+                NOW(),
+                NOW()
+            FROM
+                UNNEST($1::bytea[], $2::bytea[], $3::INT[]) AS u (hashed_key, value, operation_number)
+            ON CONFLICT (hashed_key, miniblock_number, operation_number) DO
+            UPDATE
+            SET
+                value = excluded.value,
+                updated_at = NOW()
+            "#,
+            &hashed_keys as &[&[u8]],
+            &values as &[&[u8]],
+            &operation_numbers,
+            H256::zero().as_bytes(),
+            i64::from(l2_block_number.0)
+        )
+        .instrument("upsert_storage_logs_from_snapshot")
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("storage_logs.len", &snapshot_storage_logs.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn append_storage_logs(
         &mut self,
         block_number: L2BlockNumber,
@@ -804,6 +862,86 @@ impl StorageLogsDal<'_, '_> {
         });
         Ok(rows.collect())
     }
+
+    /// Returns all storage logs (hashed key, value, L2 block number) in the specified L2 block range ordered
+    /// by L2 block and operation number. Used to maintain the historical state archive.
+    pub async fn get_storage_logs_for_l2_blocks(
+        &mut self,
+        l2_block_numbers: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(H256, H256, L2BlockNumber)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hashed_key,
+                value,
+                miniblock_number
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number,
+                operation_number
+            "#,
+            i64::from(l2_block_numbers.start().0),
+            i64::from(l2_block_numbers.end().0)
+        )
+        .instrument("get_storage_logs_for_l2_blocks")
+        .with_arg("l2_block_numbers", &l2_block_numbers)
+        .fetch_all(self.storage)
+        .await?;
+
+        let rows = rows.into_iter().map(|row| {
+            (
+                H256::from_slice(&row.hashed_key),
+                H256::from_slice(&row.value),
+                L2BlockNumber(row.miniblock_number as u32),
+            )
+        });
+        Ok(rows.collect())
+    }
+
+    /// Returns latest values as of `l2_block_number` for all storage slots in the specified `key_range`.
+    /// Used to initialize the historical state archive.
+    pub async fn get_latest_values_for_key_range(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        key_range: ops::RangeInclusive<H256>,
+    ) -> DalResult<Vec<(H256, H256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                ON (hashed_key) hashed_key,
+                value
+            FROM
+                storage_logs
+            WHERE
+                hashed_key >= $1::bytea
+                AND hashed_key <= $2::bytea
+                AND miniblock_number <= $3
+            ORDER BY
+                hashed_key,
+                miniblock_number DESC,
+                operation_number DESC
+            "#,
+            key_range.start().as_bytes(),
+            key_range.end().as_bytes(),
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_latest_values_for_key_range")
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("key_range", &key_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        let rows = rows.into_iter().map(|row| {
+            (
+                H256::from_slice(&row.hashed_key),
+                H256::from_slice(&row.value),
+            )
+        });
+        Ok(rows.collect())
+    }
 }
 
 #[cfg(test)]
@@ -78,6 +78,46 @@ impl StorageLogsDedupDal<'_, '_> {
         copy.send(&bytes).await
     }
 
+    /// Same as [`Self::insert_initial_writes_from_snapshot()`], but skips initial writes that are already present
+    /// (e.g., were inserted from the base snapshot). Used when applying delta snapshots.
+    pub async fn upsert_initial_writes_from_snapshot(
+        &mut self,
+        snapshot_storage_logs: &[SnapshotStorageLog],
+    ) -> DalResult<()> {
+        let mut hashed_keys = Vec::with_capacity(snapshot_storage_logs.len());
+        let mut indices = Vec::with_capacity(snapshot_storage_logs.len());
+        let mut l1_batch_numbers = Vec::with_capacity(snapshot_storage_logs.len());
+        for log in snapshot_storage_logs {
+            hashed_keys.push(log.key.as_bytes());
+            indices.push(log.enumeration_index as i64);
+            l1_batch_numbers.push(i64::from(log.l1_batch_number_of_initial_write.0));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                initial_writes (hashed_key, INDEX, l1_batch_number, created_at, updated_at)
+            SELECT
+                u.hashed_key,
+                u.index,
+                u.l1_batch_number,
+                NOW(),
+                NOW()
+            FROM
+                UNNEST($1::bytea[], $2::BIGINT[], $3::BIGINT[]) AS u (hashed_key, INDEX, l1_batch_number)
+            ON CONFLICT (hashed_key) DO NOTHING
+            "#,
+            &hashed_keys as &[&[u8]],
+            &indices,
+            &l1_batch_numbers
+        )
+        .instrument("upsert_initial_writes_from_snapshot")
+        .with_arg("storage_logs.len", &snapshot_storage_logs.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn insert_initial_writes(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -249,6 +249,56 @@ async fn workflow_with_submit_tx_diff_hashes() {
     assert_eq!(result, L2TxSubmissionResult::Replaced);
 }
 
+#[tokio::test]
+async fn underpriced_replacement_is_rejected() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let storage = &mut connection_pool.connection().await.unwrap();
+    let mut transactions_dal = TransactionsDal { storage };
+
+    let mut tx = mock_l2_transaction();
+    tx.common_data.fee.max_priority_fee_per_gas = U256::from(100_000_000u32);
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(&tx, mock_tx_execution_metrics(), Some(10))
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::Added);
+
+    let replacement = |max_fee_per_gas: u32, max_priority_fee_per_gas: u32| {
+        let mut replacement = mock_l2_transaction();
+        replacement.common_data.nonce = tx.common_data.nonce;
+        replacement.common_data.initiator_address = tx.common_data.initiator_address;
+        replacement.common_data.fee.max_fee_per_gas = max_fee_per_gas.into();
+        replacement.common_data.fee.max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+        replacement
+    };
+
+    // Only `max_fee_per_gas` is bumped enough.
+    let underpriced_tx = replacement(275_000_000, 105_000_000);
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(&underpriced_tx, mock_tx_execution_metrics(), Some(10))
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::ReplacementUnderpriced);
+
+    let storage = transactions_dal.storage;
+    let mut transactions_web3_dal = TransactionsWeb3Dal { storage };
+    let stored_txs = transactions_web3_dal
+        .get_transactions(&[tx.hash(), underpriced_tx.hash()], L2ChainId::default())
+        .await
+        .unwrap();
+    assert_eq!(stored_txs.len(), 1);
+    assert_eq!(stored_txs[0].hash, tx.hash());
+
+    let storage = transactions_web3_dal.storage;
+    let mut transactions_dal = TransactionsDal { storage };
+    let replacement_tx = replacement(275_000_000, 110_000_000);
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(&replacement_tx, mock_tx_execution_metrics(), Some(10))
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::Replaced);
+}
+
 #[tokio::test]
 async fn remove_stuck_txs() {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
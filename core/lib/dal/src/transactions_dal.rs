@@ -11,7 +11,7 @@ use zksync_db_connection::{
 };
 use zksync_types::{
     block::L2BlockExecutionData, l1::L1Tx, l2::L2Tx, protocol_upgrade::ProtocolUpgradeTx, Address,
-    ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber, L2BlockNumber, PriorityOpId,
+    ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber, L2BlockNumber, Nonce, PriorityOpId,
     ProtocolVersionId, Transaction, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use zksync_utils::u256_to_big_decimal;
@@ -32,6 +32,8 @@ pub enum L2TxSubmissionResult {
     Duplicate,
     Proxied,
     InsertionInProgress,
+    /// Transaction replaces a pending transaction with the same nonce, but doesn't bump its fees enough.
+    ReplacementUnderpriced,
 }
 
 impl fmt::Display for L2TxSubmissionResult {
@@ -43,6 +45,7 @@ impl fmt::Display for L2TxSubmissionResult {
             Self::Duplicate => "duplicate",
             Self::Proxied => "proxied",
             Self::InsertionInProgress => "insertion_in_progress",
+            Self::ReplacementUnderpriced => "replacement_underpriced",
         })
     }
 }
@@ -259,6 +262,19 @@ impl TransactionsDal<'_, '_> {
         &mut self,
         tx: &L2Tx,
         exec_info: TransactionExecutionMetrics,
+    ) -> DalResult<L2TxSubmissionResult> {
+        self.insert_transaction_l2_with_fee_bump(tx, exec_info, None)
+            .await
+    }
+
+    /// Same as [`Self::insert_transaction_l2()`], but a pending transaction with the same initiator and nonce
+    /// is only replaced if both `max_fee_per_gas` and `max_priority_fee_per_gas` are bumped by at least
+    /// `min_replacement_fee_bump_percent`. Otherwise, [`L2TxSubmissionResult::ReplacementUnderpriced`] is returned.
+    pub async fn insert_transaction_l2_with_fee_bump(
+        &mut self,
+        tx: &L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        min_replacement_fee_bump_percent: Option<u64>,
     ) -> DalResult<L2TxSubmissionResult> {
         let tx_hash = tx.hash();
         let is_duplicate = sqlx::query!(
@@ -391,6 +407,13 @@ impl TransactionsDal<'_, '_> {
             WHERE
                 transactions.is_priority = FALSE
                 AND transactions.miniblock_number IS NULL
+                AND (
+                    $20::BIGINT IS NULL
+                    OR (
+                        $6 * 100 >= transactions.max_fee_per_gas * (100 + $20)
+                        AND $7 * 100 >= transactions.max_priority_fee_per_gas * (100 + $20)
+                    )
+                )
             RETURNING
                 (
                     SELECT
@@ -420,7 +443,8 @@ impl TransactionsDal<'_, '_> {
             exec_info.gas_used as i64,
             (exec_info.initial_storage_writes + exec_info.repeated_storage_writes) as i32,
             exec_info.contracts_used as i32,
-            received_at
+            received_at,
+            min_replacement_fee_bump_percent.map(|percent| percent as i64)
         )
         .instrument("insert_transaction_l2")
         .with_arg("tx_hash", &tx_hash)
@@ -432,6 +456,18 @@ impl TransactionsDal<'_, '_> {
             Ok(option_query_result) => match option_query_result {
                 Some(true) => L2TxSubmissionResult::Replaced,
                 Some(false) => L2TxSubmissionResult::Added,
+                // The update may be skipped either because the existing transaction is already executed,
+                // or because the replacement doesn't bump fees enough.
+                None if min_replacement_fee_bump_percent.is_some() => {
+                    let has_pending_tx = self
+                        .has_pending_transaction(initiator_address, tx.common_data.nonce)
+                        .await?;
+                    if has_pending_tx {
+                        L2TxSubmissionResult::ReplacementUnderpriced
+                    } else {
+                        L2TxSubmissionResult::AlreadyExecuted
+                    }
+                }
                 None => L2TxSubmissionResult::AlreadyExecuted,
             },
             Err(err) => {
@@ -467,6 +503,39 @@ impl TransactionsDal<'_, '_> {
         Ok(l2_tx_insertion_result)
     }
 
+    /// Checks whether there is a pending (i.e., not executed) L2 transaction with the specified initiator and nonce.
+    async fn has_pending_transaction(
+        &mut self,
+        initiator_address: Address,
+        nonce: Nonce,
+    ) -> DalResult<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transactions
+                    WHERE
+                        initiator_address = $1
+                        AND nonce = $2
+                        AND is_priority = FALSE
+                        AND miniblock_number IS NULL
+                ) AS "exists!"
+            "#,
+            initiator_address.as_bytes(),
+            i64::from(nonce.0)
+        )
+        .instrument("has_pending_transaction")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.exists)
+    }
+
     pub async fn mark_txs_as_executed_in_l1_batch(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -1832,6 +1901,94 @@ impl TransactionsDal<'_, '_> {
             .map(|number| L1BlockNumber(number as u32)))
     }
 
+    /// Returns the smallest L1 block number among priority operations not yet included into an L2 block.
+    pub async fn get_first_unexecuted_priority_op_l1_block(
+        &mut self,
+    ) -> DalResult<Option<L1BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(l1_block_number) AS "l1_block_number"
+            FROM
+                transactions
+            WHERE
+                is_priority = TRUE
+                AND miniblock_number IS NULL
+            "#
+        )
+        .instrument("get_first_unexecuted_priority_op_l1_block")
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row
+            .l1_block_number
+            .map(|number| L1BlockNumber(number as u32)))
+    }
+
+    /// Checks whether there are priority operations emitted in the specified L1 block or later.
+    pub async fn has_priority_ops_since_l1_block(
+        &mut self,
+        l1_block: L1BlockNumber,
+    ) -> DalResult<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transactions
+                    WHERE
+                        is_priority = TRUE
+                        AND l1_block_number >= $1
+                ) AS "exists!"
+            "#,
+            l1_block.0 as i32
+        )
+        .instrument("has_priority_ops_since_l1_block")
+        .with_arg("l1_block", &l1_block)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.exists)
+    }
+
+    /// Removes priority operations emitted in the specified L1 block or later that are neither included
+    /// into an L2 block nor loaded into the state keeper mempool. Returns IDs of the removed operations.
+    ///
+    /// Operations concurrently loaded into the mempool are skipped rather than removed, so the caller should check
+    /// that no operations remain via [`Self::has_priority_ops_since_l1_block()`] in the same DB transaction.
+    pub async fn remove_unexecuted_priority_ops_since_l1_block(
+        &mut self,
+        l1_block: L1BlockNumber,
+    ) -> DalResult<Vec<PriorityOpId>> {
+        let rows = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                is_priority = TRUE
+                AND l1_block_number >= $1
+                AND miniblock_number IS NULL
+                AND NOT in_mempool
+            RETURNING
+                priority_op_id
+            "#,
+            l1_block.0 as i32
+        )
+        .instrument("remove_unexecuted_priority_ops_since_l1_block")
+        .with_arg("l1_block", &l1_block)
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut removed_ids: Vec<_> = rows
+            .into_iter()
+            .filter_map(|row| row.priority_op_id)
+            .map(|id| PriorityOpId(id as u64))
+            .collect();
+        removed_ids.sort_unstable();
+        Ok(removed_ids)
+    }
+
     pub async fn last_priority_id(&mut self) -> DalResult<Option<PriorityOpId>> {
         let maybe_row = sqlx::query!(
             r#"
@@ -2174,7 +2331,9 @@ mod tests {
 
     use super::*;
     use crate::{
-        tests::{create_l2_block_header, mock_execution_result, mock_l2_transaction},
+        tests::{
+            create_l2_block_header, mock_execution_result, mock_l1_execute, mock_l2_transaction,
+        },
         ConnectionPool, Core, CoreDal,
     };
 
@@ -2255,4 +2414,106 @@ mod tests {
             .unwrap();
         assert_eq!(tx_from_db[0].hash, tx_hash);
     }
+
+    fn mock_priority_op(serial_id: u64) -> L1Tx {
+        let mut tx = mock_l1_execute();
+        tx.common_data.serial_id = PriorityOpId(serial_id);
+        tx.common_data.canonical_tx_hash = H256::from_low_u64_be(serial_id + 1);
+        tx
+    }
+
+    #[tokio::test]
+    async fn removing_unexecuted_priority_ops() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        for (serial_id, l1_block) in [(0, 10), (1, 20), (2, 20), (3, 30)] {
+            conn.transactions_dal()
+                .insert_transaction_l1(&mock_priority_op(serial_id), L1BlockNumber(l1_block))
+                .await
+                .unwrap();
+        }
+        let executed_tx = mock_priority_op(0);
+        let mut tx_result = mock_execution_result(mock_l2_transaction());
+        tx_result.hash = executed_tx.hash();
+        tx_result.transaction = executed_tx.into();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &[tx_result],
+                1.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let first_unexecuted_block = conn
+            .transactions_dal()
+            .get_first_unexecuted_priority_op_l1_block()
+            .await
+            .unwrap();
+        assert_eq!(first_unexecuted_block, Some(L1BlockNumber(20)));
+        let has_ops = conn
+            .transactions_dal()
+            .has_priority_ops_since_l1_block(L1BlockNumber(5))
+            .await
+            .unwrap();
+        assert!(has_ops);
+        let has_ops = conn
+            .transactions_dal()
+            .has_priority_ops_since_l1_block(L1BlockNumber(31))
+            .await
+            .unwrap();
+        assert!(!has_ops);
+
+        // Operations loaded into the mempool must not be removed.
+        let mempool_txs = conn
+            .transactions_dal()
+            .sync_mempool(&[], &[], 0, 0, 100)
+            .await
+            .unwrap();
+        assert_eq!(mempool_txs.len(), 3);
+        let removed_ids = conn
+            .transactions_dal()
+            .remove_unexecuted_priority_ops_since_l1_block(L1BlockNumber(20))
+            .await
+            .unwrap();
+        assert_eq!(removed_ids, []);
+        let has_ops = conn
+            .transactions_dal()
+            .has_priority_ops_since_l1_block(L1BlockNumber(20))
+            .await
+            .unwrap();
+        assert!(has_ops);
+
+        conn.transactions_dal().reset_mempool().await.unwrap();
+        let removed_ids = conn
+            .transactions_dal()
+            .remove_unexecuted_priority_ops_since_l1_block(L1BlockNumber(20))
+            .await
+            .unwrap();
+        assert_eq!(
+            removed_ids,
+            [PriorityOpId(1), PriorityOpId(2), PriorityOpId(3)]
+        );
+        let last_priority_id = conn.transactions_dal().last_priority_id().await.unwrap();
+        assert_eq!(last_priority_id, Some(PriorityOpId(0)));
+        let first_unexecuted_block = conn
+            .transactions_dal()
+            .get_first_unexecuted_priority_op_l1_block()
+            .await
+            .unwrap();
+        assert_eq!(first_unexecuted_block, None);
+        let has_ops = conn
+            .transactions_dal()
+            .has_priority_ops_since_l1_block(L1BlockNumber(20))
+            .await
+            .unwrap();
+        assert!(!has_ops);
+    }
 }
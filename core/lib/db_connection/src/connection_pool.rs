@@ -22,8 +22,28 @@ use crate::{
     connection::{Connection, ConnectionTags, DbMarker, TracedConnections},
     error::{DalConnectionError, DalResult},
     metrics::CONNECTION_METRICS,
+    replicas::ReplicaSet,
 };
 
+/// Maximum time to wait for a connection to a read-only replica. It's intentionally short, so that a dead replica
+/// doesn't stall connection acquisition; if it's exceeded, the connection is acquired from the main database instead.
+const REPLICA_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Splits `max_size` connections among the main pool and `replica_count` replica pools, so that the total
+/// number of connections doesn't exceed `max_size` (unless it's too small to give each pool at least 1 connection).
+/// Returns the sizes of the main pool and of each replica pool; the main pool receives the remainder.
+fn split_pool_size(max_size: u32, replica_count: usize) -> (u32, u32) {
+    let Ok(replica_count) = u32::try_from(replica_count) else {
+        return (1, 1);
+    };
+    if replica_count == 0 {
+        return (max_size, 0);
+    }
+    let replica_size = (max_size / (replica_count + 1)).max(1);
+    let main_size = max_size.saturating_sub(replica_size * replica_count).max(1);
+    (main_size, replica_size)
+}
+
 /// Builder for [`ConnectionPool`]s.
 #[derive(Clone)]
 pub struct ConnectionPoolBuilder<DB: DbMarker> {
@@ -31,6 +51,7 @@ pub struct ConnectionPoolBuilder<DB: DbMarker> {
     max_size: u32,
     acquire_timeout: Duration,
     statement_timeout: Option<Duration>,
+    replicas: Option<ReplicaSet>,
     _db: PhantomData<DB>,
 }
 
@@ -42,6 +63,7 @@ impl<DB: DbMarker> fmt::Debug for ConnectionPoolBuilder<DB> {
             .field("max_size", &self.max_size)
             .field("acquire_timeout", &self.acquire_timeout)
             .field("statement_timeout", &self.statement_timeout)
+            .field("replicas", &self.replicas)
             .field("db", &any::type_name::<DB>())
             .finish()
     }
@@ -72,18 +94,25 @@ impl<DB: DbMarker> ConnectionPoolBuilder<DB> {
         self
     }
 
+    /// Sets read-only replicas to load-balance connections across. Connections are acquired from an eligible
+    /// replica (i.e., a healthy one with acceptable replication lag) in the round-robin order; if there are
+    /// no eligible replicas, connections are acquired using the main database URL. Connections acquired
+    /// inside [`with_pinned_replica()`](crate::replicas::with_pinned_replica) are routed to the same replica.
+    ///
+    /// The maximum pool size is split among the main database and replicas. The replica set may be shared
+    /// among multiple pools; replica statuses are shared as well.
+    pub fn set_replicas(&mut self, replicas: ReplicaSet) -> &mut Self {
+        self.replicas = Some(replicas);
+        self
+    }
+
     /// Returns the maximum number of connections that can be allocated by the pool.
     pub fn max_size(&self) -> u32 {
         self.max_size
     }
 
-    /// Builds a connection pool from this builder.
-    pub async fn build(&self) -> anyhow::Result<ConnectionPool<DB>> {
-        let options = PgPoolOptions::new()
-            .max_connections(self.max_size)
-            .acquire_timeout(self.acquire_timeout);
-        let mut connect_options: PgConnectOptions = self
-            .database_url
+    fn connect_options(&self, database_url: &SensitiveUrl) -> anyhow::Result<PgConnectOptions> {
+        let mut connect_options: PgConnectOptions = database_url
             .expose_str()
             .parse()
             .context("Failed parsing database URL")?;
@@ -91,16 +120,53 @@ impl<DB: DbMarker> ConnectionPoolBuilder<DB> {
             let timeout_string = format!("{}s", timeout.as_secs());
             connect_options = connect_options.options([("statement_timeout", timeout_string)]);
         }
+        Ok(connect_options)
+    }
+
+    /// Builds a connection pool from this builder.
+    pub async fn build(&self) -> anyhow::Result<ConnectionPool<DB>> {
+        let replica_count = self.replicas.as_ref().map_or(0, ReplicaSet::len);
+        let (main_size, replica_size) = split_pool_size(self.max_size, replica_count);
+        let options = PgPoolOptions::new()
+            .max_connections(main_size)
+            .acquire_timeout(self.acquire_timeout);
+        let connect_options = self.connect_options(&self.database_url)?;
         let pool = options
             .connect_with(connect_options)
             .await
             .context("Failed connecting to database")?;
+
+        // Replica pools are created lazily, so that unavailable replicas don't prevent the pool from being built.
+        let replicas = if let Some(set) = &self.replicas {
+            let replica_options = PgPoolOptions::new()
+                .max_connections(replica_size)
+                .acquire_timeout(self.acquire_timeout.min(REPLICA_ACQUIRE_TIMEOUT));
+            let pools = set
+                .database_urls()
+                .enumerate()
+                .map(|(i, url)| {
+                    let connect_options = self
+                        .connect_options(url)
+                        .with_context(|| format!("replica #{i}"))?;
+                    Ok(replica_options.clone().connect_lazy_with(connect_options))
+                })
+                .collect::<anyhow::Result<_>>()?;
+            Some(ReplicaPools {
+                set: set.clone(),
+                pools,
+                max_size: replica_size,
+            })
+        } else {
+            None
+        };
+
         tracing::info!("Created DB pool with parameters {self:?}");
         Ok(ConnectionPool {
             database_url: self.database_url.clone(),
             inner: pool,
             max_size: self.max_size,
             traced_connections: None,
+            replicas,
             _db: PhantomData,
         })
     }
@@ -112,6 +178,7 @@ impl<DB: DbMarker> ConnectionPoolBuilder<DB> {
             max_size: 1,
             acquire_timeout: self.acquire_timeout,
             statement_timeout: self.statement_timeout,
+            replicas: self.replicas.clone(),
             _db: PhantomData,
         };
         singleton_builder.build().await
@@ -256,6 +323,16 @@ impl GlobalConnectionPoolConfig {
     }
 }
 
+/// Connection pools for read-only replicas.
+#[derive(Debug, Clone)]
+struct ReplicaPools {
+    set: ReplicaSet,
+    /// Pools for each replica in `set`, in the same order.
+    pools: Vec<PgPool>,
+    /// Maximum size of each pool in `pools`.
+    max_size: u32,
+}
+
 /// Pool of reusable database connections.
 #[derive(Clone)]
 pub struct ConnectionPool<DB: DbMarker> {
@@ -263,6 +340,7 @@ pub struct ConnectionPool<DB: DbMarker> {
     database_url: SensitiveUrl,
     max_size: u32,
     pub(crate) traced_connections: Option<Arc<TracedConnections>>,
+    replicas: Option<ReplicaPools>,
     _db: PhantomData<DB>,
 }
 
@@ -276,6 +354,10 @@ impl<DB: DbMarker> fmt::Debug for ConnectionPool<DB> {
             .field("num_idle", &self.inner.num_idle())
             .field("db", &any::type_name::<DB>())
             .field("traced_connections", &self.traced_connections)
+            .field(
+                "replicas",
+                &self.replicas.as_ref().map(|replicas| &replicas.set),
+            )
             .finish()
     }
 }
@@ -345,6 +427,7 @@ impl<DB: DbMarker> ConnectionPool<DB> {
             max_size: max_pool_size,
             acquire_timeout: Duration::from_secs(30), // Default value used by `sqlx`
             statement_timeout: None,
+            replicas: None,
             _db: PhantomData,
         }
     }
@@ -368,6 +451,35 @@ impl<DB: DbMarker> ConnectionPool<DB> {
         self.max_size
     }
 
+    /// Returns read-only replicas that this pool load-balances connections across, if any.
+    pub fn replica_set(&self) -> Option<&ReplicaSet> {
+        self.replicas.as_ref().map(|replicas| &replicas.set)
+    }
+
+    /// Returns a pool connecting to the replica with the specified index in [`Self::replica_set()`].
+    /// The returned pool shares connections with this pool and doesn't route connections to other replicas.
+    /// Returns `None` if there is no replica with the specified index.
+    pub fn replica_pool(&self, index: usize) -> Option<Self> {
+        let replicas = self.replicas.as_ref()?;
+        Some(Self {
+            inner: replicas.pools.get(index)?.clone(),
+            database_url: replicas.set.database_urls().nth(index)?.clone(),
+            max_size: replicas.max_size,
+            traced_connections: self.traced_connections.clone(),
+            replicas: None,
+            _db: PhantomData,
+        })
+    }
+
+    /// Returns a pool that shares connections with this pool, but always acquires connections using
+    /// the main database URL (i.e., without routing them to replicas).
+    pub fn without_replicas(&self) -> Self {
+        Self {
+            replicas: None,
+            ..self.clone()
+        }
+    }
+
     /// Creates a `Connection` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
     /// it will be able to recover the connection (or, if connection cannot
@@ -406,7 +518,10 @@ impl<DB: DbMarker> ConnectionPool<DB> {
         tags: Option<ConnectionTags>,
     ) -> DalResult<Connection<'static, DB>> {
         let acquire_latency = CONNECTION_METRICS.acquire.start();
-        let conn = self.acquire_connection_retried(tags.as_ref()).await?;
+        let conn = match self.acquire_replica_connection(tags.as_ref()).await {
+            Some(conn) => conn,
+            None => self.acquire_connection_retried(tags.as_ref()).await?,
+        };
         let elapsed = acquire_latency.observe();
         if let Some(tags) = &tags {
             CONNECTION_METRICS.acquire_tagged[&tags.requester].observe(elapsed);
@@ -419,6 +534,48 @@ impl<DB: DbMarker> ConnectionPool<DB> {
         ))
     }
 
+    /// Tries to acquire a connection from an eligible replica. Returns `None` if the pool has no replicas,
+    /// or there are no eligible replicas, or acquiring a connection from the selected replica has failed.
+    /// In the latter case, the replica is marked as unhealthy unless the failure is caused by the replica pool
+    /// being saturated.
+    async fn acquire_replica_connection(
+        &self,
+        tags: Option<&ConnectionTags>,
+    ) -> Option<PoolConnection<Postgres>> {
+        let replicas = self.replicas.as_ref()?;
+        let Some(index) = replicas.set.select_scoped() else {
+            CONNECTION_METRICS.replica_fallback.inc();
+            return None;
+        };
+
+        let pool = &replicas.pools[index];
+        match pool.acquire().await {
+            Ok(connection) => {
+                CONNECTION_METRICS.replica_acquire.inc();
+                Some(connection)
+            }
+            Err(err) => {
+                Self::report_connection_error(&err);
+                CONNECTION_METRICS.replica_fallback.inc();
+                let tags_display = ConnectionTags::display(tags);
+                // A timeout while the pool has live connections means that all of them are in use,
+                // rather than that the replica is down.
+                let is_saturated = matches!(err, sqlx::Error::PoolTimedOut) && pool.size() > 0;
+                if is_saturated {
+                    tracing::info!(
+                        "Connection pool for DB replica #{index} is saturated ({tags_display}); using main DB"
+                    );
+                } else {
+                    replicas.set.mark_unhealthy(index);
+                    tracing::warn!(
+                        "Failed to get connection to DB replica #{index} ({tags_display}), marked it as unhealthy: {err}"
+                    );
+                }
+                None
+            }
+        }
+    }
+
     async fn acquire_connection_retried(
         &self,
         tags: Option<&ConnectionTags>,
@@ -463,10 +620,15 @@ impl<DB: DbMarker> ConnectionPool<DB> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Instant;
+
     use assert_matches::assert_matches;
 
     use super::*;
-    use crate::utils::InternalMarker;
+    use crate::{
+        replicas::{with_pinned_replica, ReplicaStatus},
+        utils::InternalMarker,
+    };
 
     #[tokio::test]
     async fn setting_statement_timeout() {
@@ -494,4 +656,111 @@ mod tests {
             sqlx::Error::Database(db_err) if db_err.message().contains("statement timeout")
         );
     }
+
+    #[tokio::test]
+    async fn routing_connections_to_replicas() {
+        let db_url = TestTemplate::empty()
+            .unwrap()
+            .create_db::<InternalMarker>(1)
+            .await
+            .unwrap()
+            .database_url;
+        let mut unreachable_url = db_url.expose_url().clone();
+        unreachable_url.set_port(Some(1)).unwrap();
+        let replicas = ReplicaSet::new(vec![db_url.clone(), unreachable_url.into()], Duration::MAX);
+
+        let pool = ConnectionPool::<InternalMarker>::builder(db_url, 2)
+            .set_acquire_timeout(Some(Duration::from_secs(1)))
+            .set_replicas(replicas.clone())
+            .build()
+            .await
+            .unwrap();
+        let replica_pool = pool.replica_pool(0).unwrap();
+        assert!(pool.replica_pool(2).is_none());
+
+        // Replicas are not eligible until their status is reported.
+        drop(pool.connection().await.unwrap());
+        assert_eq!(replica_pool.inner.size(), 0);
+
+        replicas.set_lag(0, Duration::ZERO);
+        drop(pool.connection().await.unwrap());
+        assert_eq!(replica_pool.inner.size(), 1);
+
+        // The unreachable replica should be marked as unhealthy, with the connection acquired from the primary
+        // without waiting for the full acquire timeout.
+        replicas.mark_unhealthy(0);
+        replicas.set_lag(1, Duration::ZERO);
+        let started_at = Instant::now();
+        let mut conn = pool.connection().await.unwrap();
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        sqlx::query("SELECT 1").execute(conn.conn()).await.unwrap();
+        assert_eq!(replicas.status(1), ReplicaStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn dead_replica_fails_fast() {
+        let db_url = TestTemplate::empty()
+            .unwrap()
+            .create_db::<InternalMarker>(1)
+            .await
+            .unwrap()
+            .database_url;
+        let mut unreachable_url = db_url.expose_url().clone();
+        unreachable_url.set_port(Some(1)).unwrap();
+        let replicas = ReplicaSet::new(vec![unreachable_url.into()], Duration::MAX);
+        replicas.set_lag(0, Duration::ZERO);
+
+        let pool = ConnectionPool::<InternalMarker>::builder(db_url, 2)
+            .set_acquire_timeout(Some(Duration::from_secs(30)))
+            .set_replicas(replicas.clone())
+            .build()
+            .await
+            .unwrap();
+        let started_at = Instant::now();
+        drop(pool.connection().await.unwrap());
+        assert!(
+            started_at.elapsed() < Duration::from_secs(10),
+            "{:?}",
+            started_at.elapsed()
+        );
+        assert_eq!(replicas.status(0), ReplicaStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn pinning_replica_for_connections() {
+        let db_url = TestTemplate::empty()
+            .unwrap()
+            .create_db::<InternalMarker>(1)
+            .await
+            .unwrap()
+            .database_url;
+        let replicas = ReplicaSet::new(vec![db_url.clone(), db_url.clone()], Duration::MAX);
+        replicas.set_lag(0, Duration::ZERO);
+        replicas.set_lag(1, Duration::ZERO);
+        let pool = ConnectionPool::<InternalMarker>::builder(db_url, 9)
+            .set_replicas(replicas)
+            .build()
+            .await
+            .unwrap();
+        let replica_pools = [pool.replica_pool(0).unwrap(), pool.replica_pool(1).unwrap()];
+
+        with_pinned_replica(async {
+            let connections = [
+                pool.connection().await.unwrap(),
+                pool.connection().await.unwrap(),
+            ];
+            let sizes = [replica_pools[0].inner.size(), replica_pools[1].inner.size()];
+            assert!(sizes == [2, 0] || sizes == [0, 2], "{sizes:?}");
+            drop(connections);
+        })
+        .await;
+    }
+
+    #[test]
+    fn splitting_pool_size() {
+        assert_eq!(split_pool_size(10, 0), (10, 0));
+        assert_eq!(split_pool_size(10, 1), (5, 5));
+        assert_eq!(split_pool_size(10, 2), (4, 3));
+        assert_eq!(split_pool_size(1, 2), (1, 1));
+    }
 }
@@ -87,6 +87,11 @@ pub(crate) struct ConnectionMetrics {
     pub pool_idle: Histogram<usize>,
     /// Number of errors occurred when acquiring a DB connection.
     pub pool_acquire_error: Family<ConnectionErrorKind, Counter>,
+    /// Number of DB connections acquired from read-only replicas.
+    pub replica_acquire: Counter,
+    /// Number of DB connections acquired using the main DB URL because no replicas were eligible
+    /// or acquiring a connection from a replica has failed.
+    pub replica_fallback: Counter,
     /// Lifetime of a DB connection, tagged with the requester label.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds, labels = ["requester"])]
     pub lifetime: LabeledFamily<&'static str, Histogram<Duration>>,
@@ -0,0 +1,294 @@
+//! Load balancing of read-only connections across Postgres replicas.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::task::futures::TaskLocalFuture;
+use zksync_basic_types::url::SensitiveUrl;
+
+/// Status value for replicas that are unhealthy or were not checked yet.
+const UNHEALTHY: u64 = u64::MAX;
+
+#[derive(Debug)]
+struct ReplicaState {
+    database_url: SensitiveUrl,
+    /// Replication lag in milliseconds, or [`UNHEALTHY`].
+    status: AtomicU64,
+}
+
+#[derive(Debug)]
+struct ReplicaSetInner {
+    replicas: Vec<ReplicaState>,
+    max_lag: Duration,
+    next_index: AtomicUsize,
+}
+
+/// Set of read-only Postgres replicas shared among [`ConnectionPool`](crate::connection_pool::ConnectionPool)s.
+///
+/// The set tracks health and replication lag of each replica. Replicas are initially considered unhealthy;
+/// they become eligible for routing once their status is reported via [`Self::set_lag()`] (usually,
+/// by a task periodically checking replicas). Cloning the set is cheap; clones share replica statuses.
+#[derive(Clone)]
+pub struct ReplicaSet(Arc<ReplicaSetInner>);
+
+impl fmt::Debug for ReplicaSet {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let statuses: Vec<_> = (0..self.len()).map(|i| self.status(i)).collect();
+        formatter
+            .debug_struct("ReplicaSet")
+            .field("database_urls", &self.database_urls().collect::<Vec<_>>())
+            .field("statuses", &statuses)
+            .field("max_lag", &self.0.max_lag)
+            .finish()
+    }
+}
+
+/// Status of a single replica in a [`ReplicaSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicaStatus {
+    /// Replica is unreachable or was not checked yet.
+    Unhealthy,
+    /// Replica is healthy and has the specified replication lag.
+    Healthy { lag: Duration },
+}
+
+impl ReplicaSet {
+    /// Creates a set with the specified replica URLs. Replicas lagging behind the master by more than `max_lag`
+    /// are excluded from routing.
+    pub fn new(database_urls: Vec<SensitiveUrl>, max_lag: Duration) -> Self {
+        let replicas = database_urls
+            .into_iter()
+            .map(|url| ReplicaState {
+                database_url: url.with_sensitive_query_params(&["user", "password"]),
+                status: AtomicU64::new(UNHEALTHY),
+            })
+            .collect();
+        Self(Arc::new(ReplicaSetInner {
+            replicas,
+            max_lag,
+            next_index: AtomicUsize::new(0),
+        }))
+    }
+
+    /// Returns the number of replicas in this set.
+    pub fn len(&self) -> usize {
+        self.0.replicas.len()
+    }
+
+    /// Checks whether this set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.replicas.is_empty()
+    }
+
+    /// Returns URLs of all replicas in the set.
+    pub fn database_urls(&self) -> impl Iterator<Item = &SensitiveUrl> + '_ {
+        self.0.replicas.iter().map(|replica| &replica.database_url)
+    }
+
+    /// Returns the maximum replication lag for replicas to be eligible for routing.
+    pub fn max_lag(&self) -> Duration {
+        self.0.max_lag
+    }
+
+    /// Returns the current status of the replica with the specified index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn status(&self, index: usize) -> ReplicaStatus {
+        match self.0.replicas[index].status.load(Ordering::Relaxed) {
+            UNHEALTHY => ReplicaStatus::Unhealthy,
+            lag_ms => ReplicaStatus::Healthy {
+                lag: Duration::from_millis(lag_ms),
+            },
+        }
+    }
+
+    /// Marks the replica with the specified index as healthy with the specified replication lag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_lag(&self, index: usize, lag: Duration) {
+        // Saturate to the maximum representable lag so that it's never confused with `UNHEALTHY`.
+        let lag_ms =
+            u64::try_from(lag.as_millis()).map_or(UNHEALTHY - 1, |ms| ms.min(UNHEALTHY - 1));
+        self.0.replicas[index]
+            .status
+            .store(lag_ms, Ordering::Relaxed);
+    }
+
+    /// Marks the replica with the specified index as unhealthy, excluding it from routing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn mark_unhealthy(&self, index: usize) {
+        self.0.replicas[index]
+            .status
+            .store(UNHEALTHY, Ordering::Relaxed);
+    }
+
+    /// Checks whether the replica with the specified index can be routed to.
+    pub fn is_eligible(&self, index: usize) -> bool {
+        match self.status(index) {
+            ReplicaStatus::Unhealthy => false,
+            ReplicaStatus::Healthy { lag } => lag <= self.0.max_lag,
+        }
+    }
+
+    /// Selects the next eligible replica in the round-robin order.
+    pub(crate) fn select(&self) -> Option<usize> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        let start = self.0.next_index.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|offset| start.wrapping_add(offset) % len)
+            .find(|&index| self.is_eligible(index))
+    }
+
+    /// Selects a replica taking [`with_pinned_replica()`] scope into account. Inside a scope, the first selection
+    /// is reused by all subsequent ones. If the pinned replica becomes ineligible, or no replica was eligible initially,
+    /// `None` is returned for the rest of the scope, so that connections are acquired from the main database
+    /// (which is never behind the previously used replica).
+    pub(crate) fn select_scoped(&self) -> Option<usize> {
+        let scoped = PINNED_REPLICA.try_with(|scope| {
+            let mut pinned = scope.0.borrow_mut();
+            match &mut *pinned {
+                Some(pinned) if Arc::ptr_eq(&pinned.set.0, &self.0) => {
+                    pinned.index = pinned.index.filter(|&index| self.is_eligible(index));
+                    pinned.index
+                }
+                // Pinning is only supported for a single replica set per scope.
+                Some(_) => self.select(),
+                None => {
+                    let index = self.select();
+                    *pinned = Some(PinnedReplica {
+                        set: self.clone(),
+                        index,
+                    });
+                    index
+                }
+            }
+        });
+        scoped.unwrap_or_else(|_| self.select())
+    }
+}
+
+#[derive(Debug)]
+struct PinnedReplica {
+    set: ReplicaSet,
+    index: Option<usize>,
+}
+
+/// Task-local scope for [`with_pinned_replica()`].
+#[derive(Debug, Default)]
+pub struct ReplicaScope(RefCell<Option<PinnedReplica>>);
+
+tokio::task_local! {
+    static PINNED_REPLICA: ReplicaScope;
+}
+
+/// Future returned by [`with_pinned_replica()`].
+pub type WithPinnedReplica<F> = TaskLocalFuture<ReplicaScope, F>;
+
+/// Wraps the provided future so that all connections acquired in it from a pool with replicas are routed
+/// to the same replica. This ensures that reads within the scope (e.g., handling a single API request)
+/// observe a consistent database state rather than jumping between replicas with different lag.
+pub fn with_pinned_replica<F: Future>(future: F) -> WithPinnedReplica<F> {
+    PINNED_REPLICA.scope(ReplicaScope::default(), future)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_set(len: usize, max_lag: Duration) -> ReplicaSet {
+        let urls = (0..len)
+            .map(|i| {
+                format!("postgres://localhost:{}/db", 5432 + i)
+                    .parse()
+                    .unwrap()
+            })
+            .collect();
+        ReplicaSet::new(urls, max_lag)
+    }
+
+    #[test]
+    fn replicas_are_initially_ineligible() {
+        let replicas = test_set(2, Duration::MAX);
+        assert_eq!(replicas.status(0), ReplicaStatus::Unhealthy);
+        assert_eq!(replicas.select(), None);
+    }
+
+    #[test]
+    fn round_robin_selection() {
+        let replicas = test_set(3, Duration::from_secs(5));
+        for i in 0..3 {
+            replicas.set_lag(i, Duration::ZERO);
+        }
+        let selected: Vec<_> = (0..6).map(|_| replicas.select().unwrap()).collect();
+        assert_eq!(selected, [0, 1, 2, 0, 1, 2]);
+
+        replicas.mark_unhealthy(1);
+        replicas.set_lag(2, Duration::from_secs(10));
+        assert!(!replicas.is_eligible(2));
+        for _ in 0..5 {
+            assert_eq!(replicas.select(), Some(0));
+        }
+
+        replicas.mark_unhealthy(0);
+        assert_eq!(replicas.select(), None);
+    }
+
+    #[test]
+    fn statuses_are_shared_among_clones() {
+        let replicas = test_set(1, Duration::MAX);
+        let replicas_clone = replicas.clone();
+        replicas.set_lag(0, Duration::from_millis(500));
+        assert_eq!(
+            replicas_clone.status(0),
+            ReplicaStatus::Healthy {
+                lag: Duration::from_millis(500)
+            }
+        );
+        assert!(replicas_clone.is_eligible(0));
+    }
+
+    #[tokio::test]
+    async fn pinning_replica_in_scope() {
+        let replicas = test_set(3, Duration::from_secs(5));
+        for i in 0..3 {
+            replicas.set_lag(i, Duration::ZERO);
+        }
+
+        let scoped_replicas = replicas.clone();
+        let selected = with_pinned_replica(async move {
+            let first = scoped_replicas.select_scoped().unwrap();
+            for _ in 0..5 {
+                assert_eq!(scoped_replicas.select_scoped(), Some(first));
+            }
+            // If the pinned replica becomes ineligible, the scope should fall back to the main DB for good.
+            scoped_replicas.mark_unhealthy(first);
+            assert_eq!(scoped_replicas.select_scoped(), None);
+            scoped_replicas.set_lag(first, Duration::ZERO);
+            assert_eq!(scoped_replicas.select_scoped(), None);
+            first
+        })
+        .await;
+
+        // Outside the scope, replicas are selected in the round-robin order.
+        let next = replicas.select_scoped().unwrap();
+        assert_ne!(next, selected);
+    }
+}
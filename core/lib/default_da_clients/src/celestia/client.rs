@@ -0,0 +1,474 @@
+use std::{fmt, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use secrecy::{ExposeSecret as _, Secret};
+use serde::{de::DeserializeOwned, Serialize};
+use zksync_config::configs::{da_dispatcher::CelestiaConfig, CelestiaSecrets};
+use zksync_da_client::{
+    types::{DAError, DispatchResponse, InclusionData},
+    DataAvailabilityClient,
+};
+
+use crate::celestia::types::{
+    base64_bytes, Blob, BlobId, JsonRpcError, JsonRpcRequest, JsonRpcResponse, Namespace,
+    SubmitOptions, SubmittedBlob,
+};
+
+/// Share version used for all submitted blobs.
+const SHARE_VERSION: u8 = 0;
+/// Number of attempts to find the commitment of a successfully submitted blob. Errors at this stage are retried
+/// by the client itself since retrying the entire dispatch would submit (and pay for) the blob again.
+const FIND_COMMITMENT_ATTEMPTS: usize = 5;
+/// Initial interval between attempts to find the commitment; doubled after each failed attempt.
+const FIND_COMMITMENT_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// An implementation of the `DataAvailabilityClient` trait that posts the pubdata to Celestia
+/// via the JSON-RPC API of a Celestia light / bridge node.
+///
+/// Blobs are identified by the height of the Celestia block they were included in and their commitment.
+/// The inclusion data is the JSON-encoded namespaced Merkle tree proof returned by `blob.GetProof`.
+#[derive(Clone)]
+pub struct CelestiaClient {
+    client: reqwest::Client,
+    api_node_url: String,
+    auth_token: Option<Secret<String>>,
+    namespace: Namespace,
+    gas_price: Option<f64>,
+    blob_size_limit: usize,
+}
+
+impl fmt::Debug for CelestiaClient {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Auth token is intentionally not included.
+        formatter
+            .debug_struct("CelestiaClient")
+            .field("api_node_url", &self.api_node_url)
+            .field("namespace", &hex::encode(self.namespace.0))
+            .field("gas_price", &self.gas_price)
+            .field("blob_size_limit", &self.blob_size_limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CelestiaClient {
+    pub fn new(config: CelestiaConfig, secrets: Option<CelestiaSecrets>) -> anyhow::Result<Self> {
+        let namespace = Namespace::from_hex_id(&config.namespace)
+            .with_context(|| format!("invalid Celestia namespace `{}`", config.namespace))?;
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .build()
+            .context("failed creating HTTP client")?;
+        Ok(Self {
+            client,
+            api_node_url: config.api_node_url,
+            auth_token: secrets.map(|secrets| secrets.auth_token),
+            namespace,
+            gas_price: config.gas_price,
+            blob_size_limit: config.blob_size_limit,
+        })
+    }
+
+    async fn call<P, T>(&self, method: &str, params: P) -> Result<T, CallError>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+    {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params,
+        };
+        let mut request_builder = self.client.post(&self.api_node_url).json(&request);
+        if let Some(token) = &self.auth_token {
+            request_builder = request_builder.bearer_auth(token.expose_secret());
+        }
+
+        let response = request_builder.send().await.map_err(CallError::Http)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(CallError::Status(status));
+        }
+        let response: JsonRpcResponse<T> = response.json().await.map_err(CallError::Http)?;
+        match (response.result, response.error) {
+            (_, Some(err)) => Err(CallError::Rpc(err)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(CallError::Rpc(JsonRpcError {
+                code: 0,
+                message: "response contains neither result nor error".to_owned(),
+            })),
+        }
+    }
+
+    /// Finds the commitment of the submitted blob by reading all blobs in our namespace at the inclusion height.
+    async fn find_commitment(&self, height: u64, data: &[u8]) -> Result<Vec<u8>, DAError> {
+        let namespace = Base64(&self.namespace.0);
+        let blobs: Option<Vec<Blob>> = self
+            .call("blob.GetAll", (height, [namespace]))
+            .await
+            .map_err(|err| err.into_da_error("blob.GetAll"))?;
+        let blob = blobs
+            .unwrap_or_default()
+            .into_iter()
+            .find(|blob| blob.data == data)
+            .ok_or_else(|| DAError {
+                error: anyhow::anyhow!("submitted blob is not found at height {height}"),
+                is_retriable: false,
+            })?;
+        Ok(blob.commitment)
+    }
+
+    /// Wraps [`Self::find_commitment()`] with retries on transient errors.
+    async fn find_commitment_with_retries(
+        &self,
+        batch_number: u32,
+        height: u64,
+        data: &[u8],
+    ) -> Result<Vec<u8>, DAError> {
+        let mut retry_interval = FIND_COMMITMENT_RETRY_INTERVAL;
+        let mut attempt = 1;
+        loop {
+            match self.find_commitment(height, data).await {
+                Ok(commitment) => return Ok(commitment),
+                Err(err) if err.is_retriable && attempt < FIND_COMMITMENT_ATTEMPTS => {
+                    tracing::warn!(
+                        "Failed finding commitment for L1 batch #{batch_number} pubdata at Celestia height {height} \
+                         (attempt {attempt}/{FIND_COMMITMENT_ATTEMPTS}), retrying in {retry_interval:?}: {:#}",
+                        err.error
+                    );
+                    tokio::time::sleep(retry_interval).await;
+                    retry_interval *= 2;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for CelestiaClient {
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        if data.len() > self.blob_size_limit {
+            return Err(DAError {
+                error: anyhow::anyhow!(
+                    "pubdata for L1 batch #{batch_number} is too large: {} bytes, limit is {}",
+                    data.len(),
+                    self.blob_size_limit
+                ),
+                is_retriable: false,
+            });
+        }
+
+        let blob = SubmittedBlob {
+            namespace: self.namespace.0.to_vec(),
+            data,
+            share_version: SHARE_VERSION,
+        };
+        let options = SubmitOptions {
+            gas_price: self.gas_price,
+            is_gas_price_set: self.gas_price.is_some(),
+        };
+        let height: u64 = self
+            .call("blob.Submit", ([&blob], options))
+            .await
+            .map_err(|err| err.into_da_error("blob.Submit"))?;
+        tracing::debug!(
+            "Pubdata for L1 batch #{batch_number} is included at Celestia height {height}"
+        );
+
+        let commitment = self
+            .find_commitment_with_retries(batch_number, height, &blob.data)
+            .await?;
+        Ok(DispatchResponse {
+            blob_id: BlobId { height, commitment }.to_string(),
+        })
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError> {
+        let blob_id = BlobId::parse(blob_id).map_err(|err| DAError {
+            error: err.context(format!("Failed to parse blob ID: {blob_id}")),
+            is_retriable: false,
+        })?;
+
+        let params = (
+            blob_id.height,
+            Base64(&self.namespace.0),
+            Base64(&blob_id.commitment),
+        );
+        let proof: serde_json::Value = match self.call("blob.GetProof", params).await {
+            Ok(proof) => proof,
+            Err(CallError::Rpc(err)) if err.message.contains("not found") => return Ok(None),
+            Err(err) => return Err(err.into_da_error("blob.GetProof")),
+        };
+        let data = serde_json::to_vec(&proof).map_err(|err| DAError {
+            error: err.into(),
+            is_retriable: false,
+        })?;
+        Ok(Some(InclusionData { data }))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        Some(self.blob_size_limit)
+    }
+}
+
+/// Byte slice serialized as a base64 string, which is how the node API represents namespaces and commitments.
+#[derive(Debug, Clone, Copy)]
+struct Base64<'a>(&'a [u8]);
+
+impl Serialize for Base64<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        base64_bytes::serialize(self.0, serializer)
+    }
+}
+
+#[derive(Debug)]
+enum CallError {
+    Http(reqwest::Error),
+    Status(StatusCode),
+    Rpc(JsonRpcError),
+}
+
+impl CallError {
+    fn into_da_error(self, method: &str) -> DAError {
+        let (error, is_retriable) = match self {
+            Self::Http(err) => {
+                let is_retriable = err.is_timeout() || err.is_connect() || err.is_request();
+                (anyhow::Error::from(err), is_retriable)
+            }
+            Self::Status(status) => {
+                let is_retriable =
+                    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+                (anyhow::anyhow!("HTTP status {status}"), is_retriable)
+            }
+            Self::Rpc(err) => {
+                // Errors returned by the node (e.g., a transaction not being included in time) are generally transient,
+                // unlike errors caused by malformed requests.
+                let is_retriable = !matches!(err.code, -32602..=-32600);
+                let error = anyhow::anyhow!("JSON-RPC error {}: {}", err.code, err.message);
+                (error, is_retriable)
+            }
+        };
+        DAError {
+            error: error.context(format!("`{method}` call failed")),
+            is_retriable,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    const HEIGHT: u64 = 123;
+    const COMMITMENT: [u8; 32] = [0xcc; 32];
+
+    #[derive(Debug, Default)]
+    struct MockNodeState {
+        blobs: Vec<Blob>,
+        auth_headers: Vec<Option<String>>,
+        fail_with_status: Option<StatusCode>,
+        get_all_failures_left: usize,
+        submit_count: usize,
+    }
+
+    type SharedState = Arc<Mutex<MockNodeState>>;
+
+    async fn handle_rpc(
+        State(state): State<SharedState>,
+        headers: HeaderMap,
+        Json(request): Json<Value>,
+    ) -> Result<Json<Value>, StatusCode> {
+        let mut state = state.lock().unwrap();
+        let auth_header = headers
+            .get("authorization")
+            .map(|value| value.to_str().unwrap().to_owned());
+        state.auth_headers.push(auth_header);
+        if let Some(status) = state.fail_with_status {
+            return Err(status);
+        }
+
+        let params = &request["params"];
+        let response = match request["method"].as_str().unwrap() {
+            "blob.Submit" => {
+                state.submit_count += 1;
+                let mut blob = params[0][0].clone();
+                blob["commitment"] = json!(base64_encode(&COMMITMENT));
+                state.blobs.push(serde_json::from_value(blob).unwrap());
+                json!({ "jsonrpc": "2.0", "id": 1, "result": HEIGHT })
+            }
+            "blob.GetAll" => {
+                assert_eq!(params[0], HEIGHT);
+                if state.get_all_failures_left > 0 {
+                    state.get_all_failures_left -= 1;
+                    return Err(StatusCode::SERVICE_UNAVAILABLE);
+                }
+                json!({ "jsonrpc": "2.0", "id": 1, "result": state.blobs })
+            }
+            "blob.GetProof" => {
+                let commitment = params[2].as_str().unwrap();
+                if params[0] == HEIGHT && commitment == base64_encode(&COMMITMENT) {
+                    json!({ "jsonrpc": "2.0", "id": 1, "result": [{ "start": 0, "end": 1, "nodes": [] }] })
+                } else {
+                    json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": 1, "message": "blob: not found" } })
+                }
+            }
+            _ => {
+                json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "method not found" } })
+            }
+        };
+        Ok(Json(response))
+    }
+
+    fn base64_encode(bytes: &[u8]) -> String {
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        STANDARD.encode(bytes)
+    }
+
+    async fn spawn_mock_node(state: SharedState) -> String {
+        let router = Router::new().route("/", post(handle_rpc)).with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{addr}/")
+    }
+
+    fn test_config(api_node_url: String) -> CelestiaConfig {
+        CelestiaConfig {
+            api_node_url,
+            namespace: "7a6b73796e63".to_owned(),
+            gas_price: None,
+            request_timeout_ms: 5_000,
+            blob_size_limit: 1_024,
+        }
+    }
+
+    fn test_secrets() -> CelestiaSecrets {
+        CelestiaSecrets {
+            auth_token: "secret".to_owned().into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatching_blob_and_getting_inclusion_data() {
+        let state = SharedState::default();
+        let url = spawn_mock_node(state.clone()).await;
+        let client = CelestiaClient::new(test_config(url), Some(test_secrets())).unwrap();
+
+        let response = client.dispatch_blob(1, vec![1, 2, 3]).await.unwrap();
+        let expected_blob_id = BlobId {
+            height: HEIGHT,
+            commitment: COMMITMENT.to_vec(),
+        };
+        assert_eq!(response.blob_id, expected_blob_id.to_string());
+
+        {
+            let state = state.lock().unwrap();
+            assert_eq!(state.blobs.len(), 1);
+            assert_eq!(state.blobs[0].data, [1, 2, 3]);
+            assert_eq!(state.blobs[0].namespace, client.namespace.0);
+            assert!(state
+                .auth_headers
+                .iter()
+                .all(|header| header.as_deref() == Some("Bearer secret")));
+        }
+
+        let inclusion_data = client
+            .get_inclusion_data(&response.blob_id)
+            .await
+            .unwrap()
+            .expect("no inclusion data");
+        let proof: Value = serde_json::from_slice(&inclusion_data.data).unwrap();
+        assert_eq!(proof[0]["end"], 1);
+    }
+
+    #[tokio::test]
+    async fn missing_blob_has_no_inclusion_data() {
+        let url = spawn_mock_node(SharedState::default()).await;
+        let client = CelestiaClient::new(test_config(url), Some(test_secrets())).unwrap();
+
+        let blob_id = BlobId {
+            height: HEIGHT,
+            commitment: vec![0xee; 32],
+        };
+        let inclusion_data = client
+            .get_inclusion_data(&blob_id.to_string())
+            .await
+            .unwrap();
+        assert!(inclusion_data.is_none());
+
+        let err = client.get_inclusion_data("invalid").await.unwrap_err();
+        assert!(!err.is_retriable(), "{err}");
+    }
+
+    #[tokio::test]
+    async fn oversized_blob_is_rejected() {
+        let state = SharedState::default();
+        let url = spawn_mock_node(state.clone()).await;
+        let client = CelestiaClient::new(test_config(url), Some(test_secrets())).unwrap();
+
+        let err = client.dispatch_blob(1, vec![0; 1_025]).await.unwrap_err();
+        assert!(!err.is_retriable(), "{err}");
+        assert!(state.lock().unwrap().auth_headers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn node_errors_are_classified() {
+        let state = SharedState::default();
+        let url = spawn_mock_node(state.clone()).await;
+        let client = CelestiaClient::new(test_config(url), Some(test_secrets())).unwrap();
+
+        state.lock().unwrap().fail_with_status = Some(StatusCode::SERVICE_UNAVAILABLE);
+        let err = client.dispatch_blob(1, vec![1]).await.unwrap_err();
+        assert!(err.is_retriable(), "{err}");
+
+        state.lock().unwrap().fail_with_status = Some(StatusCode::UNAUTHORIZED);
+        let err = client.dispatch_blob(1, vec![1]).await.unwrap_err();
+        assert!(!err.is_retriable(), "{err}");
+    }
+
+    #[tokio::test]
+    async fn only_finding_commitment_is_retried_after_submission() {
+        let state = SharedState::default();
+        state.lock().unwrap().get_all_failures_left = 2;
+        let url = spawn_mock_node(state.clone()).await;
+        let client = CelestiaClient::new(test_config(url), Some(test_secrets())).unwrap();
+
+        let response = client.dispatch_blob(1, vec![1, 2, 3]).await.unwrap();
+        let expected_blob_id = BlobId {
+            height: HEIGHT,
+            commitment: COMMITMENT.to_vec(),
+        };
+        assert_eq!(response.blob_id, expected_blob_id.to_string());
+        let state = state.lock().unwrap();
+        assert_eq!(state.submit_count, 1);
+        assert_eq!(state.get_all_failures_left, 0);
+    }
+
+    #[tokio::test]
+    async fn auth_token_is_optional() {
+        let state = SharedState::default();
+        let url = spawn_mock_node(state.clone()).await;
+        let client = CelestiaClient::new(test_config(url), None).unwrap();
+
+        client.dispatch_blob(1, vec![1]).await.unwrap();
+        let state = state.lock().unwrap();
+        assert!(state.auth_headers.iter().all(Option::is_none));
+    }
+}
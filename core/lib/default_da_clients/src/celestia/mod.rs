@@ -0,0 +1,3 @@
+pub mod client;
+mod types;
+pub mod wiring_layer;
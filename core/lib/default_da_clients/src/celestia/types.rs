@@ -0,0 +1,153 @@
+//! Types of the Celestia node JSON-RPC API used by the client.
+
+use serde::{Deserialize, Serialize};
+
+/// Version 0 namespace as defined by the Celestia specification: a zero version byte, followed by
+/// 18 zero bytes and a 10-byte namespace ID.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Namespace(pub [u8; Self::SIZE]);
+
+impl Namespace {
+    const SIZE: usize = 29;
+    const ID_SIZE: usize = 10;
+
+    /// Parses a hex-encoded namespace ID. IDs shorter than 10 bytes are left-padded with zeros.
+    pub fn from_hex_id(id: &str) -> anyhow::Result<Self> {
+        let id = hex::decode(id.strip_prefix("0x").unwrap_or(id))?;
+        anyhow::ensure!(!id.is_empty(), "namespace ID is empty");
+        anyhow::ensure!(
+            id.len() <= Self::ID_SIZE,
+            "namespace ID is too long: {} bytes, expected at most {}",
+            id.len(),
+            Self::ID_SIZE
+        );
+        let mut bytes = [0_u8; Self::SIZE];
+        bytes[Self::SIZE - id.len()..].copy_from_slice(&id);
+        Ok(Self(bytes))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonRpcRequest<'a, P> {
+    pub jsonrpc: &'static str,
+    pub id: u64,
+    pub method: &'a str,
+    pub params: P,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct JsonRpcResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Blob as accepted by `blob.Submit`. The commitment is computed by the node.
+#[derive(Debug, Serialize)]
+pub(crate) struct SubmittedBlob {
+    #[serde(with = "base64_bytes")]
+    pub namespace: Vec<u8>,
+    #[serde(with = "base64_bytes")]
+    pub data: Vec<u8>,
+    pub share_version: u8,
+}
+
+/// Blob as returned by `blob.GetAll`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Blob {
+    #[serde(with = "base64_bytes")]
+    pub namespace: Vec<u8>,
+    #[serde(with = "base64_bytes")]
+    pub data: Vec<u8>,
+    pub share_version: u8,
+    #[serde(with = "base64_bytes")]
+    pub commitment: Vec<u8>,
+}
+
+/// Options for `blob.Submit`. If the gas price is not set, the node estimates it.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct SubmitOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<f64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_gas_price_set: bool,
+}
+
+/// Identifier of a blob posted to Celestia, serialized as `{height}:{hex commitment}`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BlobId {
+    pub height: u64,
+    pub commitment: Vec<u8>,
+}
+
+impl BlobId {
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let (height, commitment) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("blob ID must have `height:commitment` format"))?;
+        Ok(Self {
+            height: height.parse()?,
+            commitment: hex::decode(commitment)?,
+        })
+    }
+}
+
+impl std::fmt::Display for BlobId {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{}:{}",
+            self.height,
+            hex::encode(&self.commitment)
+        )
+    }
+}
+
+pub(crate) mod base64_bytes {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        STANDARD.decode(encoded).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_namespace() {
+        let namespace = Namespace::from_hex_id("0x7a6b").unwrap();
+        let mut expected = [0_u8; 29];
+        expected[27..].copy_from_slice(&[0x7a, 0x6b]);
+        assert_eq!(namespace.0, expected);
+
+        Namespace::from_hex_id("").unwrap_err();
+        Namespace::from_hex_id(&"ab".repeat(11)).unwrap_err();
+    }
+
+    #[test]
+    fn blob_id_roundtrip() {
+        let blob_id = BlobId {
+            height: 42,
+            commitment: vec![1, 2, 3],
+        };
+        let serialized = blob_id.to_string();
+        assert_eq!(serialized, "42:010203");
+        assert_eq!(BlobId::parse(&serialized).unwrap(), blob_id);
+
+        BlobId::parse("42").unwrap_err();
+        BlobId::parse("x:010203").unwrap_err();
+    }
+}
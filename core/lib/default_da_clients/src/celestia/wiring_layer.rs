@@ -0,0 +1,45 @@
+use zksync_config::configs::{da_dispatcher::CelestiaConfig, CelestiaSecrets};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_node_framework::{
+    implementations::resources::da_client::DAClientResource,
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+use crate::celestia::client::CelestiaClient;
+
+#[derive(Debug)]
+pub struct CelestiaClientWiringLayer {
+    config: CelestiaConfig,
+    secrets: Option<CelestiaSecrets>,
+}
+
+impl CelestiaClientWiringLayer {
+    pub fn new(config: CelestiaConfig, secrets: Option<CelestiaSecrets>) -> Self {
+        Self { config, secrets }
+    }
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    pub client: DAClientResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for CelestiaClientWiringLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "celestia_da_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client: Box<dyn DataAvailabilityClient> =
+            Box::new(CelestiaClient::new(self.config, self.secrets)?);
+
+        Ok(Output {
+            client: DAClientResource(client),
+        })
+    }
+}
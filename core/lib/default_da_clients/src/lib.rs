@@ -1,2 +1,3 @@
+pub mod celestia;
 pub mod no_da;
 pub mod object_store;
@@ -46,7 +46,10 @@ impl FromEnv for MerkleTreeApiConfig {
 
 #[cfg(test)]
 mod tests {
-    use std::num::{NonZeroU32, NonZeroUsize};
+    use std::{
+        net::IpAddr,
+        num::{NonZeroU32, NonZeroUsize},
+    };
 
     use super::*;
     use crate::test_utils::{addr, EnvMutex};
@@ -76,6 +79,9 @@ mod tests {
                 initial_writes_cache_size_mb: Some(32),
                 latest_values_cache_size_mb: Some(256),
                 fee_history_limit: Some(100),
+                struct_logger_memory_limit: Some(4096),
+                struct_logger_step_limit: Some(50000),
+                struct_logger_size_limit_mb: Some(32),
                 max_batch_request_size: Some(200),
                 max_response_body_size_mb: Some(10),
                 max_response_body_size_overrides_mb: [
@@ -86,6 +92,22 @@ mod tests {
                 .into_iter()
                 .collect(),
                 websocket_requests_per_minute_limit: Some(NonZeroU32::new(10).unwrap()),
+                http_requests_per_minute_per_ip: Some(NonZeroU32::new(600).unwrap()),
+                http_requests_per_minute_per_api_key: Some(NonZeroU32::new(6000).unwrap()),
+                http_api_keys: vec!["key1".to_owned(), "key2".to_owned()],
+                http_api_key_header: Some("x-rpc-key".to_owned()),
+                http_api_key_path_prefix: Some("/key/".to_owned()),
+                http_client_ip_header: Some("x-forwarded-for".to_owned()),
+                http_trusted_proxies: vec![
+                    IpAddr::from([10, 0, 0, 1]),
+                    IpAddr::from([10, 0, 0, 2]),
+                ],
+                rpc_method_costs: [
+                    ("debug_*", NonZeroU32::new(20).unwrap()),
+                    ("eth_getLogs", NonZeroU32::new(5).unwrap()),
+                ]
+                .into_iter()
+                .collect(),
                 tree_api_url: None,
                 mempool_cache_update_interval: Some(50),
                 mempool_cache_size: Some(10000),
@@ -106,7 +128,10 @@ mod tests {
                 slow_time_limit_ms: Some(250),
                 hard_time_limit_ms: Some(2_000),
             },
-            merkle_tree: MerkleTreeApiConfig { port: 8082 },
+            merkle_tree: MerkleTreeApiConfig {
+                port: 8082,
+                checkpoints_dir: Some("/db/tree_checkpoints".into()),
+            },
         }
     }
 
@@ -136,8 +161,19 @@ mod tests {
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
+            API_WEB3_JSON_RPC_STRUCT_LOGGER_MEMORY_LIMIT=4096
+            API_WEB3_JSON_RPC_STRUCT_LOGGER_STEP_LIMIT=50000
+            API_WEB3_JSON_RPC_STRUCT_LOGGER_SIZE_LIMIT_MB=32
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_HTTP_REQUESTS_PER_MINUTE_PER_IP=600
+            API_WEB3_JSON_RPC_HTTP_REQUESTS_PER_MINUTE_PER_API_KEY=6000
+            API_WEB3_JSON_RPC_HTTP_API_KEYS="key1,key2"
+            API_WEB3_JSON_RPC_HTTP_API_KEY_HEADER="x-rpc-key"
+            API_WEB3_JSON_RPC_HTTP_API_KEY_PATH_PREFIX="/key/"
+            API_WEB3_JSON_RPC_HTTP_CLIENT_IP_HEADER="x-forwarded-for"
+            API_WEB3_JSON_RPC_HTTP_TRUSTED_PROXIES="10.0.0.1,10.0.0.2"
+            API_WEB3_JSON_RPC_RPC_METHOD_COSTS="debug_*=20,eth_getLogs=5"
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_UPDATE_INTERVAL=50
             API_CONTRACT_VERIFICATION_PORT="3070"
@@ -151,6 +187,7 @@ mod tests {
             API_HEALTHCHECK_SLOW_TIME_LIMIT_MS=250
             API_HEALTHCHECK_HARD_TIME_LIMIT_MS=2000
             API_MERKLE_TREE_PORT=8082
+            API_MERKLE_TREE_CHECKPOINTS_DIR=/db/tree_checkpoints
         "#;
         lock.set_env(config);
 
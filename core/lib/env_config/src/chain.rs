@@ -12,7 +12,9 @@ impl FromEnv for NetworkConfig {
 
 impl FromEnv for StateKeeperConfig {
     fn from_env() -> anyhow::Result<Self> {
-        envy_load("state_keeper", "CHAIN_STATE_KEEPER_")
+        let config: Self = envy_load("state_keeper", "CHAIN_STATE_KEEPER_")?;
+        config.validate()?;
+        Ok(config)
     }
 }
 
@@ -37,7 +39,7 @@ impl FromEnv for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use zksync_basic_types::{commitment::L1BatchCommitmentMode, L2ChainId};
-    use zksync_config::configs::chain::FeeModelVersion;
+    use zksync_config::configs::chain::{FeeModelVersion, MempoolOrderingPolicy};
 
     use super::*;
     use crate::test_utils::{addr, hash, EnvMutex};
@@ -105,6 +107,9 @@ mod tests {
             l1_batch_commit_data_generator_mode,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            max_l1_batch_execution_time_ms: Some(500),
+            max_l2_block_execution_time_ms: None,
+            max_contract_gas_share: Some(0.25),
         }
     }
 
@@ -138,6 +143,8 @@ mod tests {
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
             CHAIN_STATE_KEEPER_DEFAULT_AA_HASH=0x0100055b041eb28aff6e3a6e0f37c31fd053fc9ef142683b05e5f0aee6934066
             CHAIN_STATE_KEEPER_PROTECTIVE_READS_PERSISTENCE_ENABLED=true
+            CHAIN_STATE_KEEPER_MAX_L1_BATCH_EXECUTION_TIME_MS=500
+            CHAIN_STATE_KEEPER_MAX_CONTRACT_GAS_SHARE=0.25
             CHAIN_STATE_KEEPER_L1_BATCH_COMMIT_DATA_GENERATOR_MODE="{l1_batch_commit_data_generator_mode}"
         "#
         )
@@ -163,6 +170,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn state_keeper_from_env_with_invalid_contract_gas_share() {
+        let mut lock = MUTEX.lock();
+        for share in ["0", "1.5"] {
+            let config = state_keeper_config(ROLLUP_L1_BATCH_COMMIT_DATA_GENERATOR_MODE).replace(
+                "CHAIN_STATE_KEEPER_MAX_CONTRACT_GAS_SHARE=0.25",
+                &format!("CHAIN_STATE_KEEPER_MAX_CONTRACT_GAS_SHARE={share}"),
+            );
+            lock.set_env(&config);
+            let err = StateKeeperConfig::from_env().unwrap_err().to_string();
+            assert!(err.contains("max_contract_gas_share"), "{err}");
+        }
+    }
+
     fn expected_mempool_config() -> MempoolConfig {
         MempoolConfig {
             sync_interval_ms: 10,
@@ -171,6 +192,9 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            ordering_policy: MempoolOrderingPolicy::PriorityFee,
+            min_replacement_fee_bump_percent: Some(10),
+            evict_cheapest_on_capacity: true,
         }
     }
 
@@ -184,6 +208,9 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_ORDERING_POLICY="PriorityFee"
+            CHAIN_MEMPOOL_MIN_REPLACEMENT_FEE_BUMP_PERCENT="10"
+            CHAIN_MEMPOOL_EVICT_CHEAPEST_ON_CAPACITY="true"
         "#;
         lock.set_env(config);
 
@@ -1,10 +1,45 @@
-use zksync_config::DADispatcherConfig;
+use std::env;
+
+use anyhow::Context as _;
+use zksync_config::{
+    configs::{da_dispatcher::CelestiaConfig, CelestiaSecrets},
+    DADispatcherConfig,
+};
 
 use crate::{envy_load, FromEnv};
 
+const CELESTIA_PREFIX: &str = "DA_DISPATCHER_CELESTIA_";
+const CELESTIA_AUTH_TOKEN_VAR: &str = "DA_DISPATCHER_CELESTIA_AUTH_TOKEN";
+
+impl FromEnv for CelestiaConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("da_dispatcher_celestia", CELESTIA_PREFIX)
+    }
+}
+
+impl FromEnv for CelestiaSecrets {
+    fn from_env() -> anyhow::Result<Self> {
+        let auth_token = env::var(CELESTIA_AUTH_TOKEN_VAR).context(CELESTIA_AUTH_TOKEN_VAR)?;
+        Ok(Self {
+            auth_token: auth_token.into(),
+        })
+    }
+}
+
 impl FromEnv for DADispatcherConfig {
     fn from_env() -> anyhow::Result<Self> {
-        envy_load("da_dispatcher", "DA_DISPATCHER_")
+        let mut config: DADispatcherConfig = envy_load("da_dispatcher", "DA_DISPATCHER_")?;
+        // The Celestia client is configured if any of its non-secret variables is set; in this case,
+        // an incomplete or malformed config is an error rather than a reason to silently disable the client.
+        let has_celestia_vars = env::vars_os().any(|(name, _)| {
+            name.to_str().is_some_and(|name| {
+                name.starts_with(CELESTIA_PREFIX) && name != CELESTIA_AUTH_TOKEN_VAR
+            })
+        });
+        if has_celestia_vars {
+            config.celestia = Some(CelestiaConfig::from_env()?);
+        }
+        Ok(config)
     }
 }
 
@@ -26,6 +61,7 @@ mod tests {
             polling_interval_ms: Some(interval),
             max_rows_to_dispatch: Some(rows_limit),
             max_retries: Some(max_retries),
+            celestia: None,
         }
     }
 
@@ -41,4 +77,59 @@ mod tests {
         let actual = DADispatcherConfig::from_env().unwrap();
         assert_eq!(actual, expected_da_layer_config(5000, 60, 7));
     }
+
+    #[test]
+    fn from_env_da_dispatcher_with_celestia() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            DA_DISPATCHER_POLLING_INTERVAL_MS=5000
+            DA_DISPATCHER_MAX_ROWS_TO_DISPATCH=60
+            DA_DISPATCHER_MAX_RETRIES=7
+            DA_DISPATCHER_CELESTIA_API_NODE_URL=http://localhost:26658
+            DA_DISPATCHER_CELESTIA_NAMESPACE=7a6b73796e63
+            DA_DISPATCHER_CELESTIA_REQUEST_TIMEOUT_MS=10000
+        "#;
+        lock.set_env(config);
+        let actual = DADispatcherConfig::from_env().unwrap();
+
+        let mut expected = expected_da_layer_config(5000, 60, 7);
+        expected.celestia = Some(CelestiaConfig {
+            api_node_url: "http://localhost:26658".to_owned(),
+            namespace: "7a6b73796e63".to_owned(),
+            gas_price: None,
+            request_timeout_ms: 10_000,
+            blob_size_limit: CelestiaConfig::default_blob_size_limit(),
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn from_env_da_dispatcher_with_incomplete_celestia() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            DA_DISPATCHER_POLLING_INTERVAL_MS=5000
+            DA_DISPATCHER_CELESTIA_API_NODE_URL=http://localhost:26658
+        "#;
+        lock.set_env(config);
+        lock.remove_env(&["DA_DISPATCHER_CELESTIA_NAMESPACE"]);
+        let err = DADispatcherConfig::from_env().unwrap_err();
+        assert!(format!("{err:#}").contains("namespace"), "{err:#}");
+    }
+
+    #[test]
+    fn from_env_celestia_secrets() {
+        let mut lock = MUTEX.lock();
+        lock.set_env("DA_DISPATCHER_CELESTIA_AUTH_TOKEN=token");
+        let secrets = CelestiaSecrets::from_env().unwrap();
+        assert_eq!(
+            secrets,
+            CelestiaSecrets {
+                auth_token: "token".to_owned().into(),
+            }
+        );
+
+        // The auth token alone doesn't enable the Celestia client.
+        let config = DADispatcherConfig::from_env().unwrap();
+        assert_eq!(config.celestia, None);
+    }
 }
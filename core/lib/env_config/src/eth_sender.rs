@@ -1,6 +1,9 @@
 use anyhow::Context as _;
 use zksync_config::{
-    configs::{eth_sender::SenderConfig, L1Secrets},
+    configs::{
+        eth_sender::{EthClientConfig, RemoteSignerConfig, SenderConfig},
+        L1Secrets,
+    },
     EthConfig, EthWatchConfig, GasAdjusterConfig,
 };
 
@@ -12,6 +15,8 @@ impl FromEnv for EthConfig {
             sender: SenderConfig::from_env().ok(),
             gas_adjuster: GasAdjusterConfig::from_env().ok(),
             watcher: EthWatchConfig::from_env().ok(),
+            remote_signer: RemoteSignerConfig::from_env().ok(),
+            client: EthClientConfig::from_env().ok(),
         })
     }
 }
@@ -23,6 +28,18 @@ impl FromEnv for L1Secrets {
                 .context("ETH_CLIENT_WEB3_URL")?
                 .parse()
                 .context("ETH_CLIENT_WEB3_URL")?,
+            fallback_l1_rpc_urls: std::env::var("ETH_CLIENT_FALLBACK_WEB3_URLS")
+                .ok()
+                .map(|s| {
+                    s.split(',')
+                        .map(str::trim)
+                        .filter(|url| !url.is_empty())
+                        .map(str::parse)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()
+                .context("ETH_CLIENT_FALLBACK_WEB3_URLS")?
+                .unwrap_or_default(),
         })
     }
 }
@@ -39,12 +56,24 @@ impl FromEnv for GasAdjusterConfig {
     }
 }
 
+impl FromEnv for RemoteSignerConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("eth_sender.remote_signer", "ETH_SENDER_REMOTE_SIGNER_")
+    }
+}
+
+impl FromEnv for EthClientConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("eth_client", "ETH_CLIENT_")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_config::configs::eth_sender::{ProofSendingMode, PubdataSendingMode};
 
     use super::*;
-    use crate::test_utils::{hash, EnvMutex};
+    use crate::test_utils::{addr, hash, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -71,6 +100,17 @@ mod tests {
                     max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                     pubdata_sending_mode: PubdataSendingMode::Calldata,
                     tx_aggregation_only_prove_and_execute: false,
+                    nonce_recovery_enabled: true,
+                    stuck_tx_timeout_blocks: Some(50),
+                    max_underpriced_resends: None,
+                    commit_hourly_budget_gwei: None,
+                    commit_daily_budget_gwei: Some(1_000_000_000),
+                    prove_hourly_budget_gwei: None,
+                    prove_daily_budget_gwei: None,
+                    execute_hourly_budget_gwei: Some(50_000_000),
+                    execute_daily_budget_gwei: None,
+                    non_urgent_base_fee_ceiling_gwei: Some(100),
+                    max_non_urgent_deferral_sec: Some(21_600),
                     tx_aggregation_paused: false,
                 }),
                 gas_adjuster: Some(GasAdjusterConfig {
@@ -92,9 +132,27 @@ mod tests {
                     confirmations_for_eth_event: Some(0),
                     eth_node_poll_interval: 300,
                 }),
+                remote_signer: Some(RemoteSignerConfig {
+                    url: "https://127.0.0.1:9000".to_owned(),
+                    operator_address: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
+                    blob_operator_address: None,
+                    request_timeout_ms: 5_000,
+                    tls_client_cert_path: Some("/etc/signer/client.pem".to_owned()),
+                    tls_client_key_path: Some("/etc/signer/client.key".to_owned()),
+                    tls_root_cert_path: None,
+                }),
+                client: Some(EthClientConfig {
+                    quorum: 2,
+                    health_recovery_half_life_ms: 60_000,
+                    quorum_request_timeout_ms: 5_000,
+                }),
             },
             L1Secrets {
                 l1_rpc_url: "http://127.0.0.1:8545".to_string().parse().unwrap(),
+                fallback_l1_rpc_urls: vec![
+                    "http://127.0.0.1:8546".parse().unwrap(),
+                    "http://127.0.0.1:8547".parse().unwrap(),
+                ],
             },
         )
     }
@@ -134,9 +192,23 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Calldata"
+            ETH_SENDER_SENDER_NONCE_RECOVERY_ENABLED="true"
+            ETH_SENDER_SENDER_STUCK_TX_TIMEOUT_BLOCKS="50"
+            ETH_SENDER_SENDER_COMMIT_DAILY_BUDGET_GWEI="1000000000"
+            ETH_SENDER_SENDER_EXECUTE_HOURLY_BUDGET_GWEI="50000000"
+            ETH_SENDER_SENDER_NON_URGENT_BASE_FEE_CEILING_GWEI="100"
+            ETH_SENDER_SENDER_MAX_NON_URGENT_DEFERRAL_SEC="21600"
             ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
             ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_CLIENT_FALLBACK_WEB3_URLS="http://127.0.0.1:8546,http://127.0.0.1:8547"
+            ETH_CLIENT_QUORUM="2"
+            ETH_CLIENT_QUORUM_REQUEST_TIMEOUT_MS="5000"
+            ETH_SENDER_REMOTE_SIGNER_URL="https://127.0.0.1:9000"
+            ETH_SENDER_REMOTE_SIGNER_OPERATOR_ADDRESS="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+            ETH_SENDER_REMOTE_SIGNER_REQUEST_TIMEOUT_MS="5000"
+            ETH_SENDER_REMOTE_SIGNER_TLS_CLIENT_CERT_PATH="/etc/signer/client.pem"
+            ETH_SENDER_REMOTE_SIGNER_TLS_CLIENT_KEY_PATH="/etc/signer/client.key"
 
         "#;
         lock.set_env(config);
@@ -1,14 +1,35 @@
 use zksync_config::configs::{
-    external_price_api_client::ForcedPriceClientConfig, ExternalPriceApiClientConfig,
+    external_price_api_client::{
+        AggregatedPriceClientConfig, ForcedPriceClientConfig, JsonPathPriceClientConfig,
+    },
+    ExternalPriceApiClientConfig,
 };
 
 use crate::{envy_load, FromEnv};
 
+const ENV_PREFIX: &str = "EXTERNAL_PRICE_API_CLIENT_";
+const AGGREGATED_ENV_PREFIX: &str = "EXTERNAL_PRICE_API_CLIENT_AGGREGATED_";
+
+/// Loads a client config with the specified env prefix, not including aggregation settings.
+fn load_client_config(prefix: &str) -> anyhow::Result<ExternalPriceApiClientConfig> {
+    let mut config: ExternalPriceApiClientConfig = envy_load("external_price_api_client", prefix)?;
+    config.forced = envy_load::<ForcedPriceClientConfig>(
+        "external_price_api_client_forced",
+        &format!("{prefix}FORCED_"),
+    )
+    .ok();
+    config.json_path = envy_load::<JsonPathPriceClientConfig>(
+        "external_price_api_client_json_path",
+        &format!("{prefix}JSON_PATH_"),
+    )
+    .ok();
+    Ok(config)
+}
+
 impl FromEnv for ExternalPriceApiClientConfig {
     fn from_env() -> anyhow::Result<Self> {
-        let mut config: ExternalPriceApiClientConfig =
-            envy_load("external_price_api_client", "EXTERNAL_PRICE_API_CLIENT_")?;
-        config.forced = ForcedPriceClientConfig::from_env().ok();
+        let mut config = load_client_config(ENV_PREFIX)?;
+        config.aggregated = AggregatedPriceClientConfig::from_env().ok();
         Ok(config)
     }
 }
@@ -22,6 +43,29 @@ impl FromEnv for ForcedPriceClientConfig {
     }
 }
 
+impl FromEnv for AggregatedPriceClientConfig {
+    /// Loads aggregation settings. Aggregated sources are specified using
+    /// `EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCE_{i}_` prefixes, where `i` starts from 0.
+    fn from_env() -> anyhow::Result<Self> {
+        let mut sources = vec![];
+        loop {
+            let prefix = format!("{AGGREGATED_ENV_PREFIX}SOURCE_{}_", sources.len());
+            if std::env::var(format!("{prefix}SOURCE")).is_err() {
+                break;
+            }
+            sources.push(load_client_config(&prefix)?);
+        }
+        anyhow::ensure!(!sources.is_empty(), "no aggregated sources specified");
+
+        let mut config: Self = envy_load(
+            "external_price_api_client_aggregated",
+            AGGREGATED_ENV_PREFIX,
+        )?;
+        config.sources = sources;
+        Ok(config)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_config::configs::external_price_api_client::{
@@ -44,6 +88,8 @@ mod tests {
                 denominator: Some(1),
                 fluctuation: Some(10),
             }),
+            json_path: None,
+            aggregated: None,
         }
     }
 
@@ -63,4 +109,51 @@ mod tests {
         let actual = ExternalPriceApiClientConfig::from_env().unwrap();
         assert_eq!(actual, expected_external_price_api_client_config());
     }
+
+    #[test]
+    fn from_env_aggregated_external_price_api_client() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            EXTERNAL_PRICE_API_CLIENT_SOURCE=aggregated
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_MIN_SOURCES=2
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_MAX_DEVIATION_PERCENT=5
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCE_0_SOURCE=coinmarketcap
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCE_0_API_KEY=cmc-key
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCE_1_SOURCE=json_path
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCE_1_BASE_URL=https://prices.example.com/{address}
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATED_SOURCE_1_JSON_PATH_PRICE_PATH=data.price
+        "#;
+        lock.set_env(config);
+
+        let actual = ExternalPriceApiClientConfig::from_env().unwrap();
+        assert_eq!(actual.source, "aggregated");
+        let aggregated = actual.aggregated.unwrap();
+        assert_eq!(aggregated.min_sources, 2);
+        assert_eq!(aggregated.max_deviation_percent, 5);
+        assert_eq!(
+            aggregated.max_staleness_ms,
+            AggregatedPriceClientConfig::default_max_staleness_ms()
+        );
+        assert_eq!(aggregated.sources.len(), 2);
+
+        let cmc_source = &aggregated.sources[0];
+        assert_eq!(cmc_source.source, "coinmarketcap");
+        assert_eq!(cmc_source.api_key.as_deref(), Some("cmc-key"));
+        assert!(cmc_source.aggregated.is_none());
+
+        let json_source = &aggregated.sources[1];
+        assert_eq!(json_source.source, "json_path");
+        assert_eq!(
+            json_source.base_url.as_deref(),
+            Some("https://prices.example.com/{address}")
+        );
+        assert_eq!(
+            json_source.json_path,
+            Some(JsonPathPriceClientConfig {
+                price_path: "data.price".to_owned(),
+                timestamp_path: None,
+                api_key_header: None,
+            })
+        );
+    }
 }
@@ -42,7 +42,10 @@ impl FromEnv for SnapshotsObjectStoreConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
+    use zksync_config::{
+        configs::object_store::{ObjectStoreMode, S3AddressingStyle},
+        ObjectStoreConfig,
+    };
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -92,6 +95,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn s3_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OBJECT_STORE_MODE="S3"
+            OBJECT_STORE_BUCKET_NAME="zksync-artifacts"
+            OBJECT_STORE_REGION="us-east-1"
+            OBJECT_STORE_ENDPOINT="http://localhost:9000"
+            OBJECT_STORE_ADDRESSING_STYLE="path"
+            OBJECT_STORE_MAX_RETRIES="3"
+        "#;
+        lock.set_env(config);
+        let actual = ObjectStoreConfig::from_env().unwrap();
+        assert_eq!(actual.max_retries, 3);
+        assert_eq!(
+            actual.mode,
+            ObjectStoreMode::S3 {
+                bucket_name: "zksync-artifacts".to_owned(),
+                region: "us-east-1".to_owned(),
+                endpoint: Some("http://localhost:9000".to_owned()),
+                addressing_style: Some(S3AddressingStyle::Path),
+                s3_credential_file_path: None,
+            }
+        );
+    }
+
     #[test]
     fn public_bucket_config_from_env() {
         let mut lock = MUTEX.lock();
@@ -1,8 +1,11 @@
 use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
+use zksync_config::configs::eth_sender::RemoteSignerConfig;
 use zksync_contracts::hyperchain_contract;
-use zksync_eth_signer::{EthereumSigner, PrivateKeySigner, TransactionParameters};
+use zksync_eth_signer::{
+    EthereumSigner, PrivateKeySigner, RemoteSigner, SignerError, TransactionParameters,
+};
 use zksync_types::{
     ethabi, web3, Address, K256PrivateKey, SLChainId, EIP_4844_TX_TYPE, H160, U256,
 };
@@ -40,6 +43,68 @@ impl PKSigningClient {
     }
 }
 
+/// HTTP-based Ethereum client, delegating transaction signing to a remote signer.
+pub type RemoteSigningClient = SigningClient<RemoteSigner>;
+
+impl RemoteSigningClient {
+    /// Creates a signer for the account with the specified `address` managed by the remote signer
+    /// described by `config`. TLS certificates and keys referenced by the config are read from the file system.
+    pub fn create_signer(
+        config: &RemoteSignerConfig,
+        address: Address,
+    ) -> Result<RemoteSigner, SignerError> {
+        let read_file = |path: &str, description: &str| {
+            std::fs::read(path).map_err(|err| {
+                SignerError::InvalidConfig(format!(
+                    "failed reading {description} from `{path}`: {err}"
+                ))
+            })
+        };
+
+        let mut builder = RemoteSigner::builder(config.url.clone(), address)
+            .with_request_timeout(config.request_timeout());
+        match (&config.tls_client_cert_path, &config.tls_client_key_path) {
+            (Some(cert_path), Some(key_path)) => {
+                let cert_pem = read_file(cert_path, "TLS client certificate")?;
+                let key_pem = read_file(key_path, "TLS client key")?;
+                builder = builder.with_client_identity(cert_pem, key_pem);
+            }
+            (None, None) => { /* no TLS client authentication */ }
+            _ => {
+                return Err(SignerError::InvalidConfig(
+                    "TLS client certificate and key for the remote signer must be specified together"
+                        .to_owned(),
+                ));
+            }
+        }
+        if let Some(root_cert_path) = &config.tls_root_cert_path {
+            let cert_pem = read_file(root_cert_path, "TLS root certificate")?;
+            builder = builder.with_root_certificate(cert_pem);
+        }
+        builder.build()
+    }
+
+    pub fn new_raw(
+        signer: RemoteSigner,
+        diamond_proxy_addr: Address,
+        default_priority_fee_per_gas: u64,
+        chain_id: SLChainId,
+        query_client: Box<DynClient<L1>>,
+    ) -> Self {
+        let operator_address = signer.address();
+        tracing::info!("Operator address: {operator_address:?} (remote signer: {signer:?})");
+        SigningClient::new(
+            query_client,
+            hyperchain_contract(),
+            operator_address,
+            signer,
+            diamond_proxy_addr,
+            default_priority_fee_per_gas.into(),
+            chain_id,
+        )
+    }
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -108,7 +108,10 @@ impl MockSettlementLayerInner {
         let nonce = self.current_nonce;
         self.current_nonce += 1;
         tracing::info!("Executing tx with hash {tx_hash:?} at block {}, success: {success}, current nonce: {}, confirmations: {confirmations}", self.block_number - confirmations, self.current_nonce);
-        let tx_nonce = self.sent_txs[&tx_hash].nonce;
+        let sent_tx = &self.sent_txs[&tx_hash];
+        let tx_nonce = sent_tx.nonce;
+        // For simplicity, transactions are assumed to pay their max fee.
+        let effective_gas_price = sent_tx.max_fee_per_gas;
 
         if non_ordering_confirmations {
             if tx_nonce >= nonce {
@@ -123,6 +126,7 @@ impl MockSettlementLayerInner {
             success,
             receipt: web3::TransactionReceipt {
                 gas_used: Some(21000u32.into()),
+                effective_gas_price: Some(effective_gas_price),
                 block_number: Some(block_number.into()),
                 transaction_hash: tx_hash,
                 status: Some(U64::from(if success { 1 } else { 0 })),
@@ -0,0 +1,783 @@
+//! Client wrapping several upstream L1 providers.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use futures::{stream::FuturesUnordered, StreamExt};
+use jsonrpsee::core::{
+    client::{BatchResponse, ClientT, Error},
+    params::BatchRequestBuilder,
+    traits::ToRpcParams,
+    JsonRawValue,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use vise::{EncodeLabelSet, EncodeLabelValue, Family, Gauge, LabeledFamily, Metrics};
+use zksync_types::{web3, H256};
+use zksync_web3_decl::client::{DynClient, ForWeb3Network, Network, TaggedClient};
+
+/// Weight of the latest request outcome in the provider health score.
+const HEALTH_UPDATE_WEIGHT: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+enum RequestOutcome {
+    Success,
+    Failure,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct ProviderLabels {
+    provider: usize,
+    outcome: RequestOutcome,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "eth_client_multi_provider")]
+struct MultiProviderMetrics {
+    /// Number of requests sent to each provider, grouped by outcome.
+    requests: Family<ProviderLabels, vise::Counter>,
+    /// Current health score of each provider (from 0 to 1).
+    #[metrics(labels = ["provider"])]
+    health_score: LabeledFamily<usize, Gauge<f64>>,
+    /// Number of quorum reads that failed because providers returned different responses.
+    #[metrics(labels = ["method"])]
+    quorum_mismatches: LabeledFamily<String, vise::Counter>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<MultiProviderMetrics> = vise::Global::new();
+
+/// Raw RPC params that can be sent to multiple providers.
+#[derive(Debug, Clone)]
+struct RawRpcParams(Option<Box<JsonRawValue>>);
+
+impl ToRpcParams for RawRpcParams {
+    fn to_rpc_params(self) -> Result<Option<Box<JsonRawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+/// Health of a single provider. The health score is an exponential moving average of request outcomes;
+/// penalties for failed requests decay over time so that failed providers are eventually retried.
+#[derive(Debug)]
+struct ProviderHealth {
+    penalty: f64,
+    updated_at: Instant,
+}
+
+impl ProviderHealth {
+    fn new(now: Instant) -> Self {
+        Self {
+            penalty: 0.0,
+            updated_at: now,
+        }
+    }
+
+    fn current_penalty(&self, now: Instant, half_life: Duration) -> f64 {
+        if half_life.is_zero() {
+            return 0.0;
+        }
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        self.penalty * 0.5_f64.powf(elapsed.as_secs_f64() / half_life.as_secs_f64())
+    }
+
+    fn score(&self, now: Instant, half_life: Duration) -> f64 {
+        1.0 - self.current_penalty(now, half_life)
+    }
+
+    fn observe(&mut self, success: bool, now: Instant, half_life: Duration) {
+        let penalty = self.current_penalty(now, half_life) * (1.0 - HEALTH_UPDATE_WEIGHT);
+        self.penalty = if success {
+            penalty
+        } else {
+            penalty + HEALTH_UPDATE_WEIGHT
+        };
+        self.updated_at = now;
+    }
+}
+
+#[derive(Debug)]
+struct Provider<Net: Network> {
+    client: Box<DynClient<Net>>,
+    health: Mutex<ProviderHealth>,
+}
+
+/// Returns `true` if the error denotes a provider malfunction, rather than a valid JSON-RPC error response.
+fn is_provider_failure(err: &Error) -> bool {
+    !matches!(err, Error::Call(_))
+}
+
+/// Kind of safety-critical read that must be cross-checked among providers if a quorum is configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuorumKind {
+    Logs,
+    Receipt,
+    Block { full_transactions: bool },
+}
+
+impl QuorumKind {
+    /// Checks whether the request must be cross-checked among providers. Blocks requested by a tag
+    /// (e.g., `latest`) are not cross-checked since providers may legitimately disagree on them.
+    fn detect(method: &str, params: &RawRpcParams) -> Option<Self> {
+        match method {
+            "eth_getLogs" => return Some(Self::Logs),
+            "eth_getTransactionReceipt" => return Some(Self::Receipt),
+            "eth_getBlockByHash" | "eth_getBlockByNumber" => { /* handled below */ }
+            _ => return None,
+        }
+
+        let params: Vec<serde_json::Value> = params
+            .0
+            .as_ref()
+            .and_then(|params| serde_json::from_str(params.get()).ok())
+            .unwrap_or_default();
+        if method == "eth_getBlockByNumber" {
+            let is_explicit_number = params
+                .first()
+                .and_then(serde_json::Value::as_str)
+                .is_some_and(|block| block.starts_with("0x"));
+            if !is_explicit_number {
+                return None;
+            }
+        }
+        let full_transactions = params
+            .get(1)
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        Some(Self::Block { full_transactions })
+    }
+
+    fn parse_response(
+        self,
+        value: &serde_json::Value,
+    ) -> Result<QuorumResponse, serde_json::Error> {
+        Ok(match self {
+            Self::Logs => QuorumResponse::Logs(Deserialize::deserialize(value)?),
+            Self::Receipt => QuorumResponse::Receipt(Deserialize::deserialize(value)?),
+            Self::Block {
+                full_transactions: false,
+            } => QuorumResponse::Block(Deserialize::deserialize(value)?),
+            Self::Block {
+                full_transactions: true,
+            } => QuorumResponse::FullBlock(Deserialize::deserialize(value)?),
+        })
+    }
+}
+
+/// Typed response to a quorum read. Providers' responses are compared in this form, so that providers encoding
+/// the same data differently (e.g., with extra fields) are considered to agree.
+#[derive(Debug, PartialEq)]
+enum QuorumResponse {
+    Logs(Vec<web3::Log>),
+    Receipt(Option<web3::TransactionReceipt>),
+    Block(Option<web3::Block<H256>>),
+    FullBlock(Option<web3::Block<web3::Transaction>>),
+}
+
+/// Builder for [`MultiProviderClient`].
+#[derive(Debug)]
+pub struct MultiProviderClientBuilder<Net: Network> {
+    network: Net,
+    providers: Vec<Box<DynClient<Net>>>,
+    quorum: usize,
+    health_half_life: Duration,
+    quorum_request_timeout: Duration,
+}
+
+impl<Net: Network> MultiProviderClientBuilder<Net> {
+    /// Adds an upstream provider. Providers are preferred in the order they are added as long as they are equally healthy.
+    #[must_use]
+    pub fn provider(mut self, client: Box<DynClient<Net>>) -> Self {
+        self.providers.push(client);
+        self
+    }
+
+    /// Sets the number of providers that must return identical responses for safety-critical reads
+    /// (logs, transaction receipts and blocks). By default, the quorum is 1, i.e., responses are not cross-checked.
+    #[must_use]
+    pub fn quorum(mut self, quorum: usize) -> Self {
+        self.quorum = quorum;
+        self
+    }
+
+    /// Sets the half-life of penalties for failed requests in provider health scores. The default value is 1 minute.
+    #[must_use]
+    pub fn health_half_life(mut self, half_life: Duration) -> Self {
+        self.health_half_life = half_life;
+        self
+    }
+
+    /// Sets the timeout for a single provider response in quorum reads. Providers not responding in time
+    /// are treated as failed. The default value is 10 seconds.
+    #[must_use]
+    pub fn quorum_request_timeout(mut self, timeout: Duration) -> Self {
+        self.quorum_request_timeout = timeout;
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no providers are specified, or the quorum is zero or exceeds the number of providers.
+    pub fn build(self) -> Result<MultiProviderClient<Net>, Error> {
+        if self.providers.is_empty() {
+            return Err(Error::Custom("no L1 providers specified".to_owned()));
+        }
+        if self.quorum == 0 || self.quorum > self.providers.len() {
+            return Err(Error::Custom(format!(
+                "invalid quorum {} for {} L1 providers",
+                self.quorum,
+                self.providers.len()
+            )));
+        }
+
+        let now = Instant::now();
+        let providers = self
+            .providers
+            .into_iter()
+            .map(|client| Provider {
+                client,
+                health: Mutex::new(ProviderHealth::new(now)),
+            })
+            .collect();
+        Ok(MultiProviderClient {
+            providers: Arc::new(providers),
+            quorum: self.quorum,
+            health_half_life: self.health_half_life,
+            quorum_request_timeout: self.quorum_request_timeout,
+            network: self.network,
+            component_name: "",
+        })
+    }
+}
+
+/// JSON-RPC client wrapping several upstream providers.
+///
+/// - Requests are sent to the healthiest provider and fail over to other providers on transport errors
+///   (JSON-RPC errors returned by a provider are considered valid responses and are not retried).
+/// - If a quorum is configured, safety-critical reads (`eth_getLogs`, `eth_getTransactionReceipt`, and
+///   `eth_getBlockByHash` / `eth_getBlockByNumber` with an explicit block number) are sent to all providers,
+///   and the response is returned as soon as `quorum` providers agree on it. Responses are compared after
+///   being parsed into the corresponding Web3 types.
+///
+/// Since the client implements JSON-RPC traits, it can be boxed into a [`DynClient`] and used
+/// wherever an ordinary L1 client is used (e.g., as an [`EthInterface`](crate::EthInterface)).
+#[derive(Clone)]
+pub struct MultiProviderClient<Net: Network> {
+    providers: Arc<Vec<Provider<Net>>>,
+    quorum: usize,
+    health_half_life: Duration,
+    quorum_request_timeout: Duration,
+    network: Net,
+    component_name: &'static str,
+}
+
+impl<Net: Network> fmt::Debug for MultiProviderClient<Net> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MultiProviderClient")
+            .field("providers", &self.providers.len())
+            .field("quorum", &self.quorum)
+            .field("health_half_life", &self.health_half_life)
+            .field("quorum_request_timeout", &self.quorum_request_timeout)
+            .field("network", &self.network)
+            .field("component_name", &self.component_name)
+            .finish()
+    }
+}
+
+impl<Net: Network> MultiProviderClient<Net> {
+    /// Creates a builder for the client.
+    pub fn builder(network: Net) -> MultiProviderClientBuilder<Net> {
+        MultiProviderClientBuilder {
+            network,
+            providers: vec![],
+            quorum: 1,
+            health_half_life: Duration::from_secs(60),
+            quorum_request_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Returns current health scores of all providers (from 0 to 1).
+    pub fn health_scores(&self) -> Vec<f64> {
+        let now = Instant::now();
+        self.providers
+            .iter()
+            .map(|provider| {
+                let health = provider.health.lock().expect("provider health is poisoned");
+                health.score(now, self.health_half_life)
+            })
+            .collect()
+    }
+
+    /// Returns provider indices ordered by decreasing health.
+    fn providers_by_health(&self) -> Vec<usize> {
+        let scores = self.health_scores();
+        let mut indices: Vec<_> = (0..self.providers.len()).collect();
+        // Sorting is stable, so equally healthy providers are ordered by their index.
+        indices.sort_by(|&i, &j| scores[j].total_cmp(&scores[i]));
+        indices
+    }
+
+    fn observe(&self, index: usize, success: bool) {
+        let mut health = self.providers[index]
+            .health
+            .lock()
+            .expect("provider health is poisoned");
+        let now = Instant::now();
+        health.observe(success, now, self.health_half_life);
+        let outcome = if success {
+            RequestOutcome::Success
+        } else {
+            RequestOutcome::Failure
+        };
+        METRICS.requests[&ProviderLabels {
+            provider: index,
+            outcome,
+        }]
+            .inc();
+        METRICS.health_score[&index].set(health.score(now, self.health_half_life));
+    }
+
+    async fn request_with_failover(
+        &self,
+        method: &str,
+        params: RawRpcParams,
+    ) -> Result<serde_json::Value, Error> {
+        let mut last_err = None;
+        for index in self.providers_by_health() {
+            let client = &self.providers[index].client;
+            let response = client
+                .request::<serde_json::Value, _>(method, params.clone())
+                .await;
+            match response {
+                Err(err) if is_provider_failure(&err) => {
+                    tracing::warn!("L1 provider #{index} failed on `{method}`: {err}");
+                    self.observe(index, false);
+                    last_err = Some(err);
+                }
+                response => {
+                    self.observe(index, true);
+                    return response;
+                }
+            }
+        }
+        Err(last_err.expect("no L1 providers"))
+    }
+
+    async fn request_with_quorum(
+        &self,
+        method: &str,
+        kind: QuorumKind,
+        params: RawRpcParams,
+    ) -> Result<serde_json::Value, Error> {
+        let timeout = self.quorum_request_timeout;
+        let mut requests: FuturesUnordered<_> = self
+            .providers
+            .iter()
+            .enumerate()
+            .map(|(index, provider)| {
+                let request = provider
+                    .client
+                    .request::<serde_json::Value, _>(method, params.clone());
+                async move {
+                    let response = tokio::time::timeout(timeout, request).await;
+                    (index, response.unwrap_or(Err(Error::RequestTimeout)))
+                }
+            })
+            .collect();
+
+        let mut votes: Vec<(QuorumResponse, serde_json::Value, usize)> = vec![];
+        let mut call_error = None;
+        let mut last_err = None;
+        while let Some((index, response)) = requests.next().await {
+            let response = response.and_then(|value| Ok((kind.parse_response(&value)?, value)));
+            match response {
+                Ok((parsed, value)) => {
+                    self.observe(index, true);
+                    let pos = votes.iter().position(|(voted, ..)| *voted == parsed);
+                    let pos = pos.unwrap_or_else(|| {
+                        votes.push((parsed, value, 0));
+                        votes.len() - 1
+                    });
+                    votes[pos].2 += 1;
+                    if votes[pos].2 >= self.quorum {
+                        // Remaining requests are cancelled by dropping their futures.
+                        return Ok(votes.swap_remove(pos).1);
+                    }
+                }
+                Err(err) if is_provider_failure(&err) => {
+                    tracing::warn!("L1 provider #{index} failed on `{method}`: {err}");
+                    self.observe(index, false);
+                    last_err = Some(err);
+                }
+                Err(err) => {
+                    self.observe(index, true);
+                    call_error.get_or_insert(err);
+                }
+            }
+
+            let max_votes = votes.iter().map(|(.., count)| *count).max().unwrap_or(0);
+            if max_votes + requests.len() < self.quorum {
+                break; // The quorum cannot be reached even if all pending providers agree
+            }
+        }
+
+        if votes.len() > 1 {
+            tracing::warn!(
+                "L1 providers returned {} distinct responses on `{method}`; quorum of {} is not reached",
+                votes.len(),
+                self.quorum
+            );
+            METRICS.quorum_mismatches[&method.to_owned()].inc();
+        }
+        // If providers agree on a JSON-RPC error (e.g., the log limit being exceeded), return it so that it can be handled by the caller.
+        if let Some(err) = call_error {
+            return Err(err);
+        }
+        Err(last_err.unwrap_or_else(|| {
+            Error::Custom(format!(
+                "quorum of {} L1 providers is not reached on `{method}`",
+                self.quorum
+            ))
+        }))
+    }
+}
+
+impl<Net: Network> ForWeb3Network for MultiProviderClient<Net> {
+    type Net = Net;
+
+    fn network(&self) -> Self::Net {
+        self.network
+    }
+
+    fn component(&self) -> &'static str {
+        self.component_name
+    }
+}
+
+impl<Net: Network> TaggedClient for MultiProviderClient<Net> {
+    fn set_component(&mut self, component_name: &'static str) {
+        self.component_name = component_name;
+    }
+}
+
+#[async_trait]
+impl<Net: Network> ClientT for MultiProviderClient<Net> {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let index = self.providers_by_health()[0];
+        let params = RawRpcParams(params.to_rpc_params()?);
+        self.providers[index]
+            .client
+            .notification(method, params)
+            .await
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = RawRpcParams(params.to_rpc_params()?);
+        let quorum_kind = if self.quorum > 1 {
+            QuorumKind::detect(method, &params)
+        } else {
+            None
+        };
+        let response = if let Some(kind) = quorum_kind {
+            self.request_with_quorum(method, kind, params).await?
+        } else {
+            self.request_with_failover(method, params).await?
+        };
+        Ok(serde_json::from_value(response)?)
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        // Batch requests aren't used by the L1 client, so we don't bother with failover for them.
+        let index = self.providers_by_health()[0];
+        self.providers[index].client.batch_request(batch).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use assert_matches::assert_matches;
+    use futures::future;
+    use jsonrpsee::types::ErrorObject;
+    use zksync_types::U64;
+    use zksync_web3_decl::client::{MockClient, L1};
+
+    use super::*;
+    use crate::EthInterface;
+
+    fn failing_provider(calls: Arc<AtomicUsize>) -> Box<DynClient<L1>> {
+        Box::new(
+            MockClient::builder(L1::default())
+                .method("eth_blockNumber", move || {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    Err::<U64, _>(Error::RequestTimeout)
+                })
+                .build(),
+        )
+    }
+
+    fn provider_with_block_number(number: u64) -> Box<DynClient<L1>> {
+        Box::new(
+            MockClient::builder(L1::default())
+                .method("eth_blockNumber", move || Ok(U64::from(number)))
+                .build(),
+        )
+    }
+
+    fn provider_with_receipt(block_number: Option<u64>) -> Box<DynClient<L1>> {
+        Box::new(
+            MockClient::builder(L1::default())
+                .method("eth_getTransactionReceipt", move |hash: H256| {
+                    Ok(block_number.map(|number| web3::TransactionReceipt {
+                        transaction_hash: hash,
+                        block_number: Some(number.into()),
+                        status: Some(1.into()),
+                        ..web3::TransactionReceipt::default()
+                    }))
+                })
+                .build(),
+        )
+    }
+
+    fn stalled_provider() -> Box<DynClient<L1>> {
+        Box::new(
+            MockClient::builder(L1::default())
+                .method("eth_getTransactionReceipt", |_: H256| {
+                    future::pending::<Result<Option<web3::TransactionReceipt>, Error>>()
+                })
+                .build(),
+        )
+    }
+
+    #[test]
+    fn provider_health_recovers_over_time() {
+        let start = Instant::now();
+        let half_life = Duration::from_secs(10);
+        let mut health = ProviderHealth::new(start);
+        assert_eq!(health.score(start, half_life), 1.0);
+
+        health.observe(false, start, half_life);
+        assert!((health.score(start, half_life) - 0.5).abs() < 1e-9);
+        let later = start + half_life;
+        assert!((health.score(later, half_life) - 0.75).abs() < 1e-9);
+
+        health.observe(true, later, half_life);
+        assert!((health.score(later, half_life) - 0.875).abs() < 1e-9);
+    }
+
+    #[test]
+    fn detecting_quorum_requests() {
+        let params = |value: serde_json::Value| {
+            RawRpcParams(Some(JsonRawValue::from_string(value.to_string()).unwrap()))
+        };
+
+        assert_eq!(
+            QuorumKind::detect("eth_getLogs", &params(serde_json::json!([{}]))),
+            Some(QuorumKind::Logs)
+        );
+        assert_eq!(
+            QuorumKind::detect(
+                "eth_getBlockByNumber",
+                &params(serde_json::json!(["0x10", false]))
+            ),
+            Some(QuorumKind::Block {
+                full_transactions: false
+            })
+        );
+        assert_eq!(
+            QuorumKind::detect(
+                "eth_getBlockByHash",
+                &params(serde_json::json!([H256::zero(), true]))
+            ),
+            Some(QuorumKind::Block {
+                full_transactions: true
+            })
+        );
+        assert_eq!(
+            QuorumKind::detect(
+                "eth_getBlockByNumber",
+                &params(serde_json::json!(["latest", false]))
+            ),
+            None
+        );
+        assert_eq!(
+            QuorumKind::detect("eth_blockNumber", &RawRpcParams(None)),
+            None
+        );
+    }
+
+    #[test]
+    fn building_client_with_invalid_quorum() {
+        let err = MultiProviderClient::builder(L1::default())
+            .provider(provider_with_block_number(1))
+            .quorum(2)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid quorum"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn failing_over_to_healthy_provider() {
+        let failing_calls = Arc::new(AtomicUsize::new(0));
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(failing_provider(failing_calls.clone()))
+            .provider(provider_with_block_number(42))
+            .build()
+            .unwrap();
+
+        let block_number = client.block_number().await.unwrap();
+        assert_eq!(block_number, 42.into());
+        assert_eq!(failing_calls.load(Ordering::Relaxed), 1);
+        let scores = client.health_scores();
+        assert!(scores[0] < scores[1], "{scores:?}");
+
+        // The failed provider should be deprioritized.
+        let block_number = client.block_number().await.unwrap();
+        assert_eq!(block_number, 42.into());
+        assert_eq!(failing_calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn call_errors_are_not_retried() {
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(Box::new(
+                MockClient::builder(L1::default())
+                    .method("eth_blockNumber", || {
+                        Err::<U64, _>(Error::Call(ErrorObject::owned(
+                            -32000,
+                            "execution reverted",
+                            None::<()>,
+                        )))
+                    })
+                    .build(),
+            ))
+            .provider(provider_with_block_number(42))
+            .build()
+            .unwrap();
+
+        let err = client.block_number().await.unwrap_err();
+        assert!(err.to_string().contains("execution reverted"), "{err}");
+        assert_eq!(client.health_scores(), [1.0, 1.0]);
+    }
+
+    #[tokio::test]
+    async fn quorum_reads() {
+        let tx_hash = H256::repeat_byte(1);
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(provider_with_receipt(Some(10)))
+            .provider(provider_with_receipt(None))
+            .provider(provider_with_receipt(Some(10)))
+            .quorum(2)
+            .build()
+            .unwrap();
+        let receipt = client.tx_receipt(tx_hash).await.unwrap().unwrap();
+        assert_eq!(receipt.block_number, Some(10.into()));
+
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(provider_with_receipt(Some(10)))
+            .provider(provider_with_receipt(Some(11)))
+            .provider(provider_with_receipt(None))
+            .quorum(2)
+            .build()
+            .unwrap();
+        let err = client.tx_receipt(tx_hash).await.unwrap_err();
+        assert!(err.to_string().contains("quorum"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn non_quorum_reads_use_single_provider() {
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(provider_with_block_number(42))
+            .provider(provider_with_block_number(43))
+            .quorum(2)
+            .build()
+            .unwrap();
+        let block_number = client.block_number().await.unwrap();
+        assert_eq!(block_number, 42.into());
+    }
+
+    #[tokio::test]
+    async fn quorum_reads_return_as_soon_as_quorum_is_reached() {
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(stalled_provider())
+            .provider(provider_with_receipt(Some(10)))
+            .provider(provider_with_receipt(Some(10)))
+            .quorum(2)
+            .quorum_request_timeout(Duration::from_secs(3_600))
+            .build()
+            .unwrap();
+        let receipt = client
+            .tx_receipt(H256::repeat_byte(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.block_number, Some(10.into()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stalled_providers_time_out_in_quorum_reads() {
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(provider_with_receipt(Some(10)))
+            .provider(stalled_provider())
+            .quorum(2)
+            .quorum_request_timeout(Duration::from_secs(1))
+            .build()
+            .unwrap();
+        let err = client.tx_receipt(H256::repeat_byte(1)).await.unwrap_err();
+        assert_matches!(err.as_ref(), Error::RequestTimeout);
+        let scores = client.health_scores();
+        assert!(scores[1] < scores[0], "{scores:?}");
+    }
+
+    #[tokio::test]
+    async fn quorum_reads_compare_typed_responses() {
+        let receipt_with_extra_field = |hash: H256| {
+            let receipt = web3::TransactionReceipt {
+                transaction_hash: hash,
+                block_number: Some(10.into()),
+                status: Some(1.into()),
+                ..web3::TransactionReceipt::default()
+            };
+            let mut receipt = serde_json::to_value(receipt).unwrap();
+            receipt["l1BlockNumber"] = "0x5".into();
+            Ok(receipt)
+        };
+        let client = MultiProviderClient::builder(L1::default())
+            .provider(provider_with_receipt(Some(10)))
+            .provider(Box::new(
+                MockClient::builder(L1::default())
+                    .method("eth_getTransactionReceipt", receipt_with_extra_field)
+                    .build(),
+            ))
+            .quorum(2)
+            .build()
+            .unwrap();
+        let receipt = client
+            .tx_receipt(H256::repeat_byte(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.block_number, Some(10.into()));
+    }
+}
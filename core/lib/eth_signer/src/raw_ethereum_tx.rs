@@ -9,11 +9,11 @@
 //! In the case where it will be possible to use only the web3 library without copy-paste, the changes will be small and simple
 //! Link to @Deniallugo's PR to web3: https://github.com/tomusdrw/rust-web3/pull/630
 
-use rlp::RlpStream;
+use rlp::{Rlp, RlpStream};
 use zksync_types::{
     ethabi::Address,
     web3::{keccak256, AccessList, Signature, SignedTransaction},
-    K256PrivateKey, H256, U256, U64,
+    K256PrivateKey, PackedEthSignature, H256, U256, U64,
 };
 
 const LEGACY_TX_ID: u64 = 0;
@@ -71,6 +71,25 @@ pub struct Transaction {
 }
 
 impl Transaction {
+    /// Creates a transaction from the provided parameters.
+    pub(crate) fn new(params: TransactionParameters) -> Self {
+        // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
+        // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
+        Self {
+            to: params.to,
+            nonce: params.nonce,
+            gas: params.gas,
+            gas_price: params.max_fee_per_gas,
+            value: params.value,
+            data: params.data,
+            transaction_type: params.transaction_type,
+            access_list: params.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: params.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: params.max_fee_per_blob_gas,
+            blob_versioned_hashes: params.blob_versioned_hashes,
+        }
+    }
+
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
         stream.append(&self.gas_price);
@@ -240,6 +259,98 @@ impl Transaction {
         }
     }
 
+    /// Checks that `signed_tx` is an RLP-encoded signed version of this transaction for the specified `chain_id`
+    /// signed by `expected_signer`. All transaction fields (nonce, recipient, value, data, gas, fees, chain ID, etc.)
+    /// must match exactly.
+    pub(crate) fn verify_signed(
+        &self,
+        chain_id: u64,
+        signed_tx: &[u8],
+        expected_signer: Address,
+    ) -> Result<(), String> {
+        let unsigned = self.encode(chain_id, None);
+        let message_hash = H256(keccak256(&unsigned));
+        let is_legacy = matches!(
+            self.transaction_type.map(|t| t.as_u64()),
+            Some(LEGACY_TX_ID) | None
+        );
+
+        let (payload, expected_payload) = if is_legacy {
+            (signed_tx, unsigned.as_slice())
+        } else {
+            if signed_tx.first() != unsigned.first() {
+                return Err(format!(
+                    "transaction type mismatch: expected {:?}, got {:?}",
+                    unsigned.first(),
+                    signed_tx.first()
+                ));
+            }
+            (&signed_tx[1..], &unsigned[1..])
+        };
+
+        let rlp = Rlp::new(payload);
+        let expected_rlp = Rlp::new(expected_payload);
+        let rlp_len = rlp.payload_info().map_err(|err| err.to_string())?.total();
+        if rlp_len != payload.len() {
+            return Err("transaction has trailing bytes".to_owned());
+        }
+        // For legacy transactions, the chain ID is encoded in the signature `v` value.
+        let field_count = if is_legacy {
+            6
+        } else {
+            expected_rlp.item_count().map_err(|err| err.to_string())?
+        };
+        let item_count = rlp.item_count().map_err(|err| err.to_string())?;
+        if item_count != field_count + 3 {
+            return Err(format!(
+                "unexpected number of transaction fields: expected {}, got {item_count}",
+                field_count + 3
+            ));
+        }
+        for i in 0..field_count {
+            let field = rlp.at(i).map_err(|err| err.to_string())?;
+            let expected_field = expected_rlp.at(i).map_err(|err| err.to_string())?;
+            if field.as_raw() != expected_field.as_raw() {
+                return Err(format!(
+                    "transaction field #{i} differs from the requested one"
+                ));
+            }
+        }
+
+        let v: u64 = rlp.val_at(field_count).map_err(|err| err.to_string())?;
+        let r: U256 = rlp.val_at(field_count + 1).map_err(|err| err.to_string())?;
+        let s: U256 = rlp.val_at(field_count + 2).map_err(|err| err.to_string())?;
+        let recovery_id = if is_legacy {
+            let (recovery_id, signed_chain_id) =
+                PackedEthSignature::unpack_v(v).map_err(|err| err.to_string())?;
+            if signed_chain_id != Some(chain_id) {
+                return Err(format!(
+                    "transaction is signed for chain {signed_chain_id:?}, expected {chain_id}"
+                ));
+            }
+            recovery_id
+        } else {
+            u8::try_from(v)
+                .ok()
+                .filter(|&v| v <= 1)
+                .ok_or_else(|| format!("invalid signature y-parity: {v}"))?
+        };
+        let mut r_bytes = H256::zero();
+        r.to_big_endian(r_bytes.as_bytes_mut());
+        let mut s_bytes = H256::zero();
+        s.to_big_endian(s_bytes.as_bytes_mut());
+        let signature = PackedEthSignature::from_rsv(&r_bytes, &s_bytes, recovery_id);
+        let signer = signature
+            .signature_recover_signer(&message_hash)
+            .map_err(|err| format!("cannot recover transaction signer: {err}"))?;
+        if signer != expected_signer {
+            return Err(format!(
+                "transaction is signed by {signer:?}, expected {expected_signer:?}"
+            ));
+        }
+        Ok(())
+    }
+
     /// Sign and return a raw signed transaction.
     pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
         let adjust_v_value = matches!(
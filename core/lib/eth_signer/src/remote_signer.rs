@@ -0,0 +1,540 @@
+//! Signer delegating signing to a remote service speaking the Ethereum JSON-RPC signing protocol,
+//! such as [Web3Signer](https://docs.web3signer.consensys.io/) or a KMS-backed proxy.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use zksync_types::{
+    web3::{AccessList, Bytes},
+    Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature, H256, U256, U64,
+};
+
+use crate::{
+    raw_ethereum_tx::{Transaction, TransactionParameters},
+    EthereumSigner, SignerError,
+};
+
+/// Default timeout for requests to the remote signer.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Builder for [`RemoteSigner`].
+pub struct RemoteSignerBuilder {
+    url: String,
+    address: Address,
+    request_timeout: Duration,
+    client_identity: Option<(Vec<u8>, Vec<u8>)>,
+    root_certificate: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for RemoteSignerBuilder {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The client identity contains a private key, so we don't output it.
+        formatter
+            .debug_struct("RemoteSignerBuilder")
+            .field("url", &self.url)
+            .field("address", &self.address)
+            .field("request_timeout", &self.request_timeout)
+            .field("has_client_identity", &self.client_identity.is_some())
+            .field("has_root_certificate", &self.root_certificate.is_some())
+            .finish()
+    }
+}
+
+impl RemoteSignerBuilder {
+    /// Sets the timeout for each request to the signer. By default, the timeout is 10 seconds.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Sets the PEM-encoded client certificate chain and PKCS #8 private key used for TLS client authentication.
+    pub fn with_client_identity(mut self, cert_pem: Vec<u8>, key_pem: Vec<u8>) -> Self {
+        self.client_identity = Some((cert_pem, key_pem));
+        self
+    }
+
+    /// Adds a PEM-encoded root certificate that the signer's TLS certificate will be verified against
+    /// (in addition to system roots).
+    pub fn with_root_certificate(mut self, cert_pem: Vec<u8>) -> Self {
+        self.root_certificate = Some(cert_pem);
+        self
+    }
+
+    /// Builds the signer. Does not perform any requests to the signer.
+    pub fn build(self) -> Result<RemoteSigner, SignerError> {
+        let mut client_builder = reqwest::Client::builder().timeout(self.request_timeout);
+        if let Some((cert_pem, key_pem)) = &self.client_identity {
+            let identity = reqwest::Identity::from_pkcs8_pem(cert_pem, key_pem).map_err(|err| {
+                SignerError::InvalidConfig(format!("invalid TLS client identity: {err}"))
+            })?;
+            client_builder = client_builder.use_native_tls().identity(identity);
+        }
+        if let Some(cert_pem) = &self.root_certificate {
+            let cert = reqwest::Certificate::from_pem(cert_pem).map_err(|err| {
+                SignerError::InvalidConfig(format!("invalid TLS root certificate: {err}"))
+            })?;
+            client_builder = client_builder.add_root_certificate(cert);
+        }
+        let client = client_builder.build().map_err(|err| {
+            SignerError::InvalidConfig(format!("cannot build HTTP client: {err}"))
+        })?;
+
+        Ok(RemoteSigner {
+            client,
+            url: self.url.into(),
+            address: self.address,
+            next_request_id: Arc::default(),
+        })
+    }
+}
+
+/// Signer delegating signing to a remote service supporting the `eth_signTransaction` JSON-RPC method,
+/// e.g. Web3Signer or a proxy for a cloud KMS.
+///
+/// The signer is expected to manage the account with the address provided when creating [`RemoteSigner`].
+/// Requests are made over HTTP(S); TLS client authentication is supported.
+#[derive(Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: Arc<str>,
+    address: Address,
+    next_request_id: Arc<AtomicU64>,
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RemoteSigner")
+            .field("url", &self.url)
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteSigner {
+    /// Creates a builder for a signer with the specified JSON-RPC URL managing the account with the specified `address`.
+    pub fn builder(url: impl Into<String>, address: Address) -> RemoteSignerBuilder {
+        RemoteSignerBuilder {
+            url: url.into(),
+            address,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            client_identity: None,
+            root_certificate: None,
+        }
+    }
+
+    /// Returns the address of the account managed by the signer.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn request<P: Serialize, R: DeserializeOwned>(
+        &self,
+        method: &'static str,
+        params: P,
+    ) -> Result<R, SignerError> {
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id,
+            method,
+            params,
+        };
+        let response = self
+            .client
+            .post(&*self.url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| SignerError::RequestFailed(format!("`{method}` request: {err}")))?;
+        let status = response.status();
+        let response: JsonRpcResponse<R> = response.json().await.map_err(|err| {
+            SignerError::RequestFailed(format!(
+                "cannot parse `{method}` response (HTTP status: {status}): {err}"
+            ))
+        })?;
+
+        match response {
+            JsonRpcResponse {
+                error: Some(error), ..
+            } => Err(SignerError::SigningFailed(format!(
+                "`{method}` failed with code {}: {}",
+                error.code, error.message
+            ))),
+            JsonRpcResponse {
+                result: Some(result),
+                ..
+            } => Ok(result),
+            JsonRpcResponse { .. } => Err(SignerError::RequestFailed(format!(
+                "`{method}` response contains neither result nor error"
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    /// Returns the address of the account managed by the remote signer.
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    /// Not supported: typed structures cannot be converted to the JSON representation
+    /// required by `eth_signTypedData`.
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        _domain: &Eip712Domain,
+        _typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        Err(SignerError::UnsupportedOperation(
+            "remote signer doesn't support signing typed data".to_owned(),
+        ))
+    }
+
+    /// Signs the transaction using `eth_signTransaction` and returns the RLP-encoded signed transaction.
+    /// The returned transaction is checked to match `raw_tx` and to be signed by the managed account.
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let expected_tx = Transaction::new(raw_tx.clone());
+        let request = SignTransactionRequest::new(self.address, raw_tx);
+        let response: SignTransactionResponse =
+            self.request("eth_signTransaction", [request]).await?;
+        let raw_transaction = match response {
+            SignTransactionResponse::Raw(bytes)
+            | SignTransactionResponse::Object { raw: bytes } => bytes.0,
+        };
+        if raw_transaction.is_empty() {
+            return Err(SignerError::SigningFailed(
+                "remote signer returned an empty transaction".to_owned(),
+            ));
+        }
+        expected_tx
+            .verify_signed(chain_id, &raw_transaction, self.address)
+            .map_err(|err| {
+                SignerError::SigningFailed(format!(
+                    "remote signer returned invalid transaction: {err}"
+                ))
+            })?;
+        Ok(raw_transaction)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<P> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'static str,
+    params: P,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<R> {
+    result: Option<R>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// Transaction object accepted by `eth_signTransaction`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignTransactionRequest {
+    from: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<Address>,
+    nonce: U256,
+    gas: U256,
+    value: U256,
+    data: Bytes,
+    chain_id: U64,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    transaction_type: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas_price: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_priority_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    access_list: Option<AccessList>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_fee_per_blob_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blob_versioned_hashes: Option<Vec<H256>>,
+}
+
+impl SignTransactionRequest {
+    fn new(from: Address, raw_tx: TransactionParameters) -> Self {
+        // Mirrors `PrivateKeySigner`: `max_fee_per_gas` is used as the gas price for legacy transactions.
+        let is_legacy = raw_tx.transaction_type.map_or(true, |ty| ty.as_u64() <= 1);
+        let (gas_price, max_fee_per_gas, max_priority_fee_per_gas) = if is_legacy {
+            (Some(raw_tx.max_fee_per_gas), None, None)
+        } else {
+            (
+                None,
+                Some(raw_tx.max_fee_per_gas),
+                Some(raw_tx.max_priority_fee_per_gas),
+            )
+        };
+        let access_list = raw_tx
+            .transaction_type
+            .is_some()
+            .then(|| raw_tx.access_list.unwrap_or_default());
+
+        Self {
+            from,
+            to: raw_tx.to,
+            nonce: raw_tx.nonce,
+            gas: raw_tx.gas,
+            value: raw_tx.value,
+            data: Bytes(raw_tx.data),
+            chain_id: raw_tx.chain_id.into(),
+            transaction_type: raw_tx.transaction_type,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            access_list,
+            max_fee_per_blob_gas: raw_tx.max_fee_per_blob_gas,
+            blob_versioned_hashes: raw_tx.blob_versioned_hashes,
+        }
+    }
+}
+
+/// Web3Signer returns the signed transaction as a hex string, while Geth-like signers return an object
+/// with the `raw` field.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SignTransactionResponse {
+    Raw(Bytes),
+    Object { raw: Bytes },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::{extract::State, routing::post, Json, Router};
+    use serde_json::{json, Value};
+    use zksync_types::{K256PrivateKey, H160};
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    type TamperFn = fn(&mut TransactionParameters);
+
+    /// Mock signing server backed by a private key. `tamper` is applied to transaction parameters before signing
+    /// to emulate a misbehaving signer.
+    async fn spawn_mock_server(
+        signer: PrivateKeySigner,
+        response_delay: Duration,
+        tamper: TamperFn,
+    ) -> SocketAddr {
+        async fn handle(
+            State((signer, response_delay, tamper)): State<(PrivateKeySigner, Duration, TamperFn)>,
+            Json(request): Json<Value>,
+        ) -> Json<Value> {
+            tokio::time::sleep(response_delay).await;
+            let id = request["id"].clone();
+            if request["method"] != "eth_signTransaction" {
+                return Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -32601, "message": "Method not found" },
+                }));
+            }
+
+            let tx = &request["params"][0];
+            let expected_from = format!("{:?}", signer.get_address().await.unwrap());
+            if tx["from"] != expected_from.as_str() {
+                return Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -32000, "message": "Unknown account" },
+                }));
+            }
+            let parse_u256 =
+                |value: &Value| -> U256 { serde_json::from_value(value.clone()).unwrap() };
+            let is_legacy = tx.get("gasPrice").is_some();
+            let max_fee_per_gas = if is_legacy {
+                parse_u256(&tx["gasPrice"])
+            } else {
+                parse_u256(&tx["maxFeePerGas"])
+            };
+            let mut params = TransactionParameters {
+                nonce: parse_u256(&tx["nonce"]),
+                to: serde_json::from_value(tx["to"].clone()).unwrap(),
+                gas: parse_u256(&tx["gas"]),
+                gas_price: None,
+                value: parse_u256(&tx["value"]),
+                data: serde_json::from_value::<Bytes>(tx["data"].clone())
+                    .unwrap()
+                    .0,
+                chain_id: serde_json::from_value::<U64>(tx["chainId"].clone())
+                    .unwrap()
+                    .as_u64(),
+                transaction_type: serde_json::from_value(tx["type"].clone()).unwrap(),
+                access_list: serde_json::from_value(tx["accessList"].clone()).unwrap(),
+                max_fee_per_gas,
+                max_priority_fee_per_gas: if is_legacy {
+                    U256::zero()
+                } else {
+                    parse_u256(&tx["maxPriorityFeePerGas"])
+                },
+                max_fee_per_blob_gas: None,
+                blob_versioned_hashes: None,
+            };
+            tamper(&mut params);
+            let raw_tx = signer.sign_transaction(params).await.unwrap();
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": Bytes(raw_tx),
+            }))
+        }
+
+        let app =
+            Router::new()
+                .route("/", post(handle))
+                .with_state((signer, response_delay, tamper));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        local_addr
+    }
+
+    fn test_transaction(transaction_type: Option<U64>) -> TransactionParameters {
+        TransactionParameters {
+            nonce: 1.into(),
+            to: Some(H160::repeat_byte(0x11)),
+            gas: 100_000.into(),
+            gas_price: None,
+            max_fee_per_gas: 2_000_000_000.into(),
+            max_priority_fee_per_gas: 1_000_000_000.into(),
+            value: 123.into(),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            transaction_type,
+            access_list: None,
+            blob_versioned_hashes: None,
+            max_fee_per_blob_gas: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_transactions_via_remote_signer() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let address = private_key.address();
+        let pk_signer = PrivateKeySigner::new(private_key);
+        let server_addr = spawn_mock_server(pk_signer.clone(), Duration::ZERO, |_| {}).await;
+        let signer = RemoteSigner::builder(format!("http://{server_addr}/"), address)
+            .build()
+            .unwrap();
+        assert_eq!(signer.get_address().await.unwrap(), address);
+
+        for transaction_type in [None, Some(1.into()), Some(2.into())] {
+            let tx = test_transaction(transaction_type);
+            let expected_raw_tx = pk_signer.sign_transaction(tx.clone()).await.unwrap();
+            let raw_tx = signer.sign_transaction(tx).await.unwrap();
+            assert_eq!(raw_tx, expected_raw_tx, "{transaction_type:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_signer_errors() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let pk_signer = PrivateKeySigner::new(private_key);
+        let server_addr = spawn_mock_server(pk_signer, Duration::ZERO, |_| {}).await;
+
+        let signer = RemoteSigner::builder(format!("http://{server_addr}/"), Address::zero())
+            .build()
+            .unwrap();
+        let err = signer
+            .sign_transaction(test_transaction(Some(2.into())))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, SignerError::SigningFailed(msg) if msg.contains("Unknown account")),
+            "{err:?}"
+        );
+
+        let domain = Eip712Domain::new(270.into());
+        let err = signer.sign_typed_data(&domain, &domain).await.unwrap_err();
+        assert!(
+            matches!(err, SignerError::UnsupportedOperation(_)),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_signer_returning_different_transaction() {
+        let tampers: [TamperFn; 6] = [
+            |tx| tx.nonce += U256::one(),
+            |tx| tx.to = Some(H160::repeat_byte(0x22)),
+            |tx| tx.data.push(0),
+            |tx| tx.max_fee_per_gas += U256::one(),
+            |tx| tx.chain_id += 1,
+            |tx| tx.transaction_type = Some(1.into()),
+        ];
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let address = private_key.address();
+        for (i, tamper) in tampers.into_iter().enumerate() {
+            let pk_signer = PrivateKeySigner::new(private_key.clone());
+            let server_addr = spawn_mock_server(pk_signer, Duration::ZERO, tamper).await;
+            let signer = RemoteSigner::builder(format!("http://{server_addr}/"), address)
+                .build()
+                .unwrap();
+
+            for transaction_type in [None, Some(2.into())] {
+                let err = signer
+                    .sign_transaction(test_transaction(transaction_type))
+                    .await
+                    .unwrap_err();
+                assert!(
+                    matches!(&err, SignerError::SigningFailed(msg) if msg.contains("invalid transaction")),
+                    "tamper #{i}, {transaction_type:?}: {err:?}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_signer_request_timeout() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let address = private_key.address();
+        let pk_signer = PrivateKeySigner::new(private_key);
+        let server_addr = spawn_mock_server(pk_signer, Duration::from_secs(10), |_| {}).await;
+
+        let signer = RemoteSigner::builder(format!("http://{server_addr}/"), address)
+            .with_request_timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let err = signer
+            .sign_transaction(test_transaction(None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SignerError::RequestFailed(_)), "{err:?}");
+    }
+
+    #[test]
+    fn invalid_client_identity_is_rejected() {
+        let err = RemoteSigner::builder("https://localhost/", Address::zero())
+            .with_client_identity(b"not a cert".to_vec(), b"not a key".to_vec())
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, SignerError::InvalidConfig(_)), "{err:?}");
+    }
+}
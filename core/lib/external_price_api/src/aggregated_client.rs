@@ -0,0 +1,302 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use zksync_config::configs::{
+    external_price_api_client::AggregatedPriceClientConfig, ExternalPriceApiClientConfig,
+};
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+
+use crate::{
+    cmc_api::CmcPriceApiClient, coingecko_api::CoinGeckoPriceAPIClient,
+    forced_price_client::ForcedPriceClient, json_path_api::JsonPathPriceAPIClient,
+    utils::get_fraction, NoOpPriceAPIClient, PriceAPIClient,
+};
+
+/// Client querying several price sources and aggregating their quotes. The aggregated quote is the median
+/// of valid quotes; a quote is considered valid if it's not stale and doesn't deviate from the median of all quotes
+/// too much.
+#[derive(Debug)]
+pub struct AggregatedPriceAPIClient {
+    sources: Vec<Arc<dyn PriceAPIClient>>,
+    min_sources: usize,
+    max_deviation_percent: u32,
+    max_staleness: Duration,
+}
+
+impl AggregatedPriceAPIClient {
+    /// Creates a client from the config. Sources are instantiated based on their `source` field.
+    pub fn new(config: ExternalPriceApiClientConfig) -> anyhow::Result<Self> {
+        let config = config
+            .aggregated
+            .ok_or_else(|| anyhow::anyhow!("aggregated price client started with no config"))?;
+        let sources = config
+            .sources
+            .iter()
+            .map(|source| Self::create_source(source.clone()))
+            .collect::<anyhow::Result<_>>()?;
+        Self::from_sources(sources, &config)
+    }
+
+    /// Creates a client aggregating the specified sources.
+    pub fn from_sources(
+        sources: Vec<Arc<dyn PriceAPIClient>>,
+        config: &AggregatedPriceClientConfig,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(config.min_sources > 0, "`min_sources` must be positive");
+        anyhow::ensure!(
+            sources.len() >= config.min_sources,
+            "aggregated price client has {} sources, which is less than `min_sources` ({})",
+            sources.len(),
+            config.min_sources
+        );
+        Ok(Self {
+            sources,
+            min_sources: config.min_sources,
+            max_deviation_percent: config.max_deviation_percent,
+            max_staleness: config.max_staleness(),
+        })
+    }
+
+    fn create_source(
+        config: ExternalPriceApiClientConfig,
+    ) -> anyhow::Result<Arc<dyn PriceAPIClient>> {
+        Ok(match config.source.as_str() {
+            "coingecko" => Arc::new(CoinGeckoPriceAPIClient::new(config)),
+            "coinmarketcap" => Arc::new(CmcPriceApiClient::new(config)),
+            "json_path" => Arc::new(JsonPathPriceAPIClient::new(config)),
+            "forced" => Arc::new(ForcedPriceClient::new(config)),
+            "no-op" => Arc::new(NoOpPriceAPIClient),
+            other => anyhow::bail!("unsupported aggregated price source: {other}"),
+        })
+    }
+
+    fn ratio_value(ratio: &BaseTokenAPIRatio) -> f64 {
+        ratio.numerator.get() as f64 / ratio.denominator.get() as f64
+    }
+
+    /// Computes the median of quotes sorted by their value.
+    fn median(sorted_quotes: &[BaseTokenAPIRatio]) -> BaseTokenAPIRatio {
+        let mid = sorted_quotes.len() / 2;
+        if sorted_quotes.len() % 2 == 1 {
+            return sorted_quotes[mid];
+        }
+
+        let (lower, upper) = (&sorted_quotes[mid - 1], &sorted_quotes[mid]);
+        let (numerator, denominator) =
+            get_fraction((Self::ratio_value(lower) + Self::ratio_value(upper)) / 2.0);
+        BaseTokenAPIRatio {
+            numerator,
+            denominator,
+            ratio_timestamp: lower.ratio_timestamp.min(upper.ratio_timestamp),
+        }
+    }
+
+    fn aggregate(&self, mut quotes: Vec<BaseTokenAPIRatio>) -> anyhow::Result<BaseTokenAPIRatio> {
+        anyhow::ensure!(
+            quotes.len() >= self.min_sources,
+            "only {} valid quotes were received, while at least {} are required",
+            quotes.len(),
+            self.min_sources
+        );
+        quotes.sort_unstable_by(|x, y| Self::ratio_value(x).total_cmp(&Self::ratio_value(y)));
+
+        let median = Self::ratio_value(&Self::median(&quotes));
+        let max_deviation = f64::from(self.max_deviation_percent) / 100.0;
+        quotes.retain(|quote| {
+            let deviation = (Self::ratio_value(quote) - median).abs() / median;
+            if deviation > max_deviation {
+                tracing::warn!(
+                    "Rejecting quote {quote:?} as an outlier; its deviation from median {median} is {:.2}%",
+                    deviation * 100.0
+                );
+                false
+            } else {
+                true
+            }
+        });
+        anyhow::ensure!(
+            quotes.len() >= self.min_sources,
+            "only {} quotes remain after rejecting outliers, while at least {} are required",
+            quotes.len(),
+            self.min_sources
+        );
+        Ok(Self::median(&quotes))
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for AggregatedPriceAPIClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let results = futures::future::join_all(
+            self.sources
+                .iter()
+                .map(|source| source.fetch_ratio(token_address)),
+        )
+        .await;
+
+        let now = Utc::now();
+        let quotes = self
+            .sources
+            .iter()
+            .zip(results)
+            .filter_map(|(source, result)| match result {
+                Ok(quote) => {
+                    let age = (now - quote.ratio_timestamp).to_std().unwrap_or_default();
+                    if age > self.max_staleness {
+                        tracing::warn!(
+                            "Rejecting stale quote {quote:?} from {source:?}: its age is {age:?}"
+                        );
+                        None
+                    } else {
+                        Some(quote)
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("Failed fetching quote from {source:?}: {err:#}");
+                    None
+                }
+            })
+            .collect();
+        self.aggregate(quotes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+    use zksync_config::configs::external_price_api_client::JsonPathPriceClientConfig;
+
+    use super::*;
+    use crate::utils::tests::spawn_mock_server;
+
+    #[derive(Debug)]
+    struct MockClient(anyhow::Result<BaseTokenAPIRatio>);
+
+    #[async_trait]
+    impl PriceAPIClient for MockClient {
+        async fn fetch_ratio(&self, _token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+            match &self.0 {
+                Ok(ratio) => Ok(*ratio),
+                Err(err) => Err(anyhow::anyhow!("{err}")),
+            }
+        }
+    }
+
+    fn quote(numerator: u64, denominator: u64) -> BaseTokenAPIRatio {
+        BaseTokenAPIRatio {
+            numerator: NonZeroU64::new(numerator).unwrap(),
+            denominator: NonZeroU64::new(denominator).unwrap(),
+            ratio_timestamp: Utc::now(),
+        }
+    }
+
+    fn aggregated_config(min_sources: usize) -> AggregatedPriceClientConfig {
+        AggregatedPriceClientConfig {
+            sources: vec![],
+            min_sources,
+            max_deviation_percent: 10,
+            max_staleness_ms: 60_000,
+        }
+    }
+
+    fn aggregated_client(
+        quotes: impl IntoIterator<Item = anyhow::Result<BaseTokenAPIRatio>>,
+        min_sources: usize,
+    ) -> AggregatedPriceAPIClient {
+        let sources = quotes
+            .into_iter()
+            .map(|quote| Arc::new(MockClient(quote)) as Arc<dyn PriceAPIClient>)
+            .collect();
+        AggregatedPriceAPIClient::from_sources(sources, &aggregated_config(min_sources)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn median_of_odd_number_of_quotes() {
+        let client = aggregated_client([Ok(quote(100, 1)), Ok(quote(102, 1)), Ok(quote(99, 1))], 2);
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (100, 1));
+    }
+
+    #[tokio::test]
+    async fn median_of_even_number_of_quotes() {
+        let client = aggregated_client([Ok(quote(100, 1)), Ok(quote(101, 1))], 2);
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (201, 2));
+    }
+
+    #[tokio::test]
+    async fn outliers_and_failed_sources_are_rejected() {
+        let client = aggregated_client(
+            [
+                Ok(quote(100, 1)),
+                Ok(quote(1_000, 1)),
+                Err(anyhow::anyhow!("source is down")),
+                Ok(quote(104, 1)),
+                Ok(quote(1, 1)),
+                Ok(quote(102, 1)),
+            ],
+            3,
+        );
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (102, 1));
+
+        let client =
+            aggregated_client([Ok(quote(100, 1)), Ok(quote(200, 1)), Ok(quote(400, 1))], 2);
+        let err = client.fetch_ratio(Address::zero()).await.unwrap_err();
+        assert!(err.to_string().contains("rejecting outliers"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn stale_quotes_are_rejected() {
+        let stale_quote = BaseTokenAPIRatio {
+            ratio_timestamp: Utc::now() - chrono::Duration::minutes(5),
+            ..quote(100, 1)
+        };
+        let client = aggregated_client([Ok(stale_quote), Ok(quote(101, 1))], 2);
+        let err = client.fetch_ratio(Address::zero()).await.unwrap_err();
+        assert!(err.to_string().contains("only 1 valid quotes"), "{err}");
+
+        let client = aggregated_client([Ok(stale_quote), Ok(quote(101, 1))], 1);
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (101, 1));
+    }
+
+    #[tokio::test]
+    async fn creating_client_from_config() {
+        let router = Router::new().route("/price", get(|| async { Json(json!({ "price": 0.5 })) }));
+        let base_url = spawn_mock_server(router).await;
+        let json_path_source = ExternalPriceApiClientConfig {
+            source: "json_path".to_owned(),
+            base_url: Some(format!("{base_url}price")),
+            api_key: None,
+            client_timeout_ms: 5_000,
+            forced: None,
+            json_path: Some(JsonPathPriceClientConfig {
+                price_path: "price".to_owned(),
+                timestamp_path: None,
+                api_key_header: None,
+            }),
+            aggregated: None,
+        };
+        let config = ExternalPriceApiClientConfig {
+            source: "aggregated".to_owned(),
+            base_url: None,
+            api_key: None,
+            client_timeout_ms: 5_000,
+            forced: None,
+            json_path: None,
+            aggregated: Some(AggregatedPriceClientConfig {
+                sources: vec![json_path_source.clone(), json_path_source],
+                ..aggregated_config(2)
+            }),
+        };
+
+        let client = AggregatedPriceAPIClient::new(config).unwrap();
+        let ratio = client.fetch_ratio(Address::zero()).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (1, 2));
+    }
+}
@@ -0,0 +1,263 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use url::Url;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+
+use crate::{address_to_string, utils::get_fraction, PriceAPIClient};
+
+const DEFAULT_CMC_API_URL: &str = "https://pro-api.coinmarketcap.com";
+const CMC_AUTH_HEADER: &str = "x-cmc_pro_api_key";
+/// CoinMarketCap ID of ETH; used to request quotes in ETH.
+const CMC_ETH_ID: u64 = 1027;
+
+/// Client fetching base token prices from CoinMarketCap.
+#[derive(Debug)]
+pub struct CmcPriceApiClient {
+    base_url: Url,
+    client: reqwest::Client,
+    /// Cache of CoinMarketCap IDs for token addresses. IDs are immutable, so the cache is never invalidated.
+    token_ids: Mutex<HashMap<Address, u64>>,
+}
+
+impl CmcPriceApiClient {
+    pub fn new(config: ExternalPriceApiClientConfig) -> Self {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(api_key) = &config.api_key {
+            headers.insert(
+                reqwest::header::HeaderName::from_static(CMC_AUTH_HEADER),
+                reqwest::header::HeaderValue::from_str(api_key)
+                    .expect("Failed to create header value"),
+            );
+        }
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(config.client_timeout())
+            .build()
+            .expect("Failed to build reqwest client");
+
+        let base_url = config.base_url.unwrap_or(DEFAULT_CMC_API_URL.to_string());
+
+        Self {
+            base_url: Url::parse(&base_url).expect("Failed to parse CoinMarketCap URL"),
+            client,
+            token_ids: Mutex::default(),
+        }
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(&self, url: Url) -> anyhow::Result<T> {
+        let response = self.client.get(url.clone()).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Http error while querying CoinMarketCap. Status: {}, url: {url}, msg: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            );
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn get_token_id(&self, address: Address) -> anyhow::Result<u64> {
+        if let Some(&id) = self.token_ids.lock().unwrap().get(&address) {
+            return Ok(id);
+        }
+
+        let address_str = address_to_string(&address);
+        let mut info_url = self
+            .base_url
+            .join("/v2/cryptocurrency/info")
+            .expect("failed to join URL path");
+        info_url
+            .query_pairs_mut()
+            .append_pair("address", &address_str);
+        let response: CmcResponse<CmcTokenInfo> = self.get(info_url).await?;
+
+        // There should be a single token for an address.
+        let mut tokens = response.data.into_values();
+        let token = tokens
+            .next()
+            .with_context(|| format!("Token not found on CoinMarketCap: {address_str}"))?;
+        anyhow::ensure!(
+            tokens.next().is_none(),
+            "Multiple CoinMarketCap tokens correspond to address {address_str}"
+        );
+
+        self.token_ids.lock().unwrap().insert(address, token.id);
+        Ok(token.id)
+    }
+
+    async fn get_token_price(&self, id: u64) -> anyhow::Result<CmcQuote> {
+        let mut quotes_url = self
+            .base_url
+            .join("/v2/cryptocurrency/quotes/latest")
+            .expect("failed to join URL path");
+        quotes_url
+            .query_pairs_mut()
+            .append_pair("id", &id.to_string())
+            .append_pair("convert_id", &CMC_ETH_ID.to_string());
+        let mut response: CmcResponse<CmcTokenQuotes> = self.get(quotes_url).await?;
+
+        let token = response
+            .data
+            .remove(&id.to_string())
+            .with_context(|| format!("No quotes returned for CoinMarketCap token #{id}"))?;
+        token
+            .quote
+            .get(&CMC_ETH_ID.to_string())
+            .copied()
+            .with_context(|| format!("No ETH quote returned for CoinMarketCap token #{id}"))
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for CmcPriceApiClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let id = self.get_token_id(token_address).await?;
+        let quote = self.get_token_price(id).await?;
+        anyhow::ensure!(
+            quote.price.is_finite() && quote.price > 0.0,
+            "Invalid price for CoinMarketCap token #{id}: {}",
+            quote.price
+        );
+        let (numerator, denominator) = get_fraction(quote.price);
+
+        Ok(BaseTokenAPIRatio {
+            numerator,
+            denominator,
+            ratio_timestamp: quote.last_updated,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcResponse<T> {
+    data: HashMap<String, T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcTokenInfo {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcTokenQuotes {
+    quote: HashMap<String, CmcQuote>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct CmcQuote {
+    price: f64,
+    last_updated: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use axum::{extract::Query, http::HeaderMap, routing::get, Json, Router};
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::tests::spawn_mock_server;
+
+    const TOKEN_ID: u64 = 123;
+
+    fn mock_router(info_requests: Arc<AtomicUsize>) -> Router {
+        let info_handler =
+            move |headers: HeaderMap, Query(params): Query<HashMap<String, String>>| {
+                let info_requests = info_requests.clone();
+                async move {
+                    info_requests.fetch_add(1, Ordering::SeqCst);
+                    assert_eq!(headers[CMC_AUTH_HEADER], "test-key");
+                    assert_eq!(
+                        params["address"],
+                        address_to_string(&Address::repeat_byte(1))
+                    );
+                    Json(json!({
+                        "data": {
+                            "123": { "id": TOKEN_ID, "symbol": "TEST" },
+                        },
+                    }))
+                }
+            };
+        let quotes_handler = |Query(params): Query<HashMap<String, String>>| async move {
+            assert_eq!(params["id"], TOKEN_ID.to_string());
+            assert_eq!(params["convert_id"], CMC_ETH_ID.to_string());
+            Json(json!({
+                "data": {
+                    "123": {
+                        "id": TOKEN_ID,
+                        "quote": {
+                            "1027": {
+                                "price": 0.25,
+                                "last_updated": "2024-08-01T12:00:00.000Z",
+                            },
+                        },
+                    },
+                },
+            }))
+        };
+
+        Router::new()
+            .route("/v2/cryptocurrency/info", get(info_handler))
+            .route("/v2/cryptocurrency/quotes/latest", get(quotes_handler))
+    }
+
+    #[tokio::test]
+    async fn fetching_price_from_cmc() {
+        let info_requests = Arc::<AtomicUsize>::default();
+        let base_url = spawn_mock_server(mock_router(info_requests.clone())).await;
+        let client = CmcPriceApiClient::new(ExternalPriceApiClientConfig {
+            source: "coinmarketcap".to_owned(),
+            base_url: Some(base_url),
+            api_key: Some("test-key".to_owned()),
+            client_timeout_ms: 5_000,
+            forced: None,
+            json_path: None,
+            aggregated: None,
+        });
+
+        for _ in 0..2 {
+            let ratio = client.fetch_ratio(Address::repeat_byte(1)).await.unwrap();
+            assert_eq!(ratio.numerator.get(), 1);
+            assert_eq!(ratio.denominator.get(), 4);
+            assert_eq!(
+                ratio.ratio_timestamp,
+                "2024-08-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap()
+            );
+        }
+        // The token ID should be cached.
+        assert_eq!(info_requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_token_error() {
+        let router = Router::new().route(
+            "/v2/cryptocurrency/info",
+            get(|| async { Json(json!({ "data": {} })) }),
+        );
+        let base_url = spawn_mock_server(router).await;
+        let client = CmcPriceApiClient::new(ExternalPriceApiClientConfig {
+            source: "coinmarketcap".to_owned(),
+            base_url: Some(base_url),
+            api_key: None,
+            client_timeout_ms: 5_000,
+            forced: None,
+            json_path: None,
+            aggregated: None,
+        });
+
+        let err = client
+            .fetch_ratio(Address::repeat_byte(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+}
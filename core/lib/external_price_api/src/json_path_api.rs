@@ -0,0 +1,226 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_types::{base_token_ratio::BaseTokenAPIRatio, Address};
+
+use crate::{address_to_string, utils::get_fraction, PriceAPIClient};
+
+/// Placeholder in the configured URL replaced with the base token address.
+const ADDRESS_PLACEHOLDER: &str = "{address}";
+
+/// Generic client fetching a JSON document over HTTP and extracting the base token price in ETH from it
+/// using a dot-separated path (e.g., `data.0.price`).
+#[derive(Debug)]
+pub struct JsonPathPriceAPIClient {
+    url_template: String,
+    price_path: Vec<String>,
+    timestamp_path: Option<Vec<String>>,
+    client: reqwest::Client,
+}
+
+impl JsonPathPriceAPIClient {
+    pub fn new(config: ExternalPriceApiClientConfig) -> Self {
+        let json_path_config = config
+            .json_path
+            .expect("JSON path price client started with no config");
+        let url_template = config
+            .base_url
+            .expect("JSON path price client started with no URL");
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let (Some(api_key), Some(header)) = (&config.api_key, &json_path_config.api_key_header) {
+            headers.insert(
+                reqwest::header::HeaderName::from_bytes(header.as_bytes())
+                    .expect("Failed to create header name"),
+                reqwest::header::HeaderValue::from_str(api_key)
+                    .expect("Failed to create header value"),
+            );
+        }
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(config.client_timeout())
+            .build()
+            .expect("Failed to build reqwest client");
+
+        Self {
+            url_template,
+            price_path: Self::parse_path(&json_path_config.price_path),
+            timestamp_path: json_path_config
+                .timestamp_path
+                .as_deref()
+                .map(Self::parse_path),
+            client,
+        }
+    }
+
+    fn parse_path(path: &str) -> Vec<String> {
+        path.split('.')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn select<'a>(value: &'a Value, path: &[String]) -> anyhow::Result<&'a Value> {
+        let mut current = value;
+        for (i, segment) in path.iter().enumerate() {
+            let next = match current {
+                Value::Object(map) => map.get(segment),
+                Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            };
+            current = next.with_context(|| {
+                format!("no value at path `{}` in response", path[..=i].join("."))
+            })?;
+        }
+        Ok(current)
+    }
+
+    fn parse_price(value: &Value) -> anyhow::Result<f64> {
+        let price = match value {
+            Value::Number(number) => number.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        };
+        let price = price.with_context(|| format!("price {value} is not a number"))?;
+        anyhow::ensure!(
+            price.is_finite() && price > 0.0,
+            "price {price} is not positive"
+        );
+        Ok(price)
+    }
+
+    fn parse_timestamp(value: &Value) -> anyhow::Result<DateTime<Utc>> {
+        let timestamp = match value {
+            Value::Number(number) => number.as_i64(),
+            Value::String(s) => {
+                if let Ok(timestamp) = s.parse::<DateTime<Utc>>() {
+                    return Ok(timestamp);
+                }
+                s.trim().parse().ok()
+            }
+            _ => None,
+        };
+        timestamp
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            .with_context(|| format!("timestamp {value} is invalid"))
+    }
+}
+
+#[async_trait]
+impl PriceAPIClient for JsonPathPriceAPIClient {
+    async fn fetch_ratio(&self, token_address: Address) -> anyhow::Result<BaseTokenAPIRatio> {
+        let url = self
+            .url_template
+            .replace(ADDRESS_PLACEHOLDER, &address_to_string(&token_address));
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Http error while fetching token price. Status: {}, url: {url}, msg: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            );
+        }
+        let response: Value = response.json().await?;
+
+        let price = Self::select(&response, &self.price_path)
+            .and_then(Self::parse_price)
+            .context("failed extracting price")?;
+        let ratio_timestamp = if let Some(path) = &self.timestamp_path {
+            Self::select(&response, path)
+                .and_then(Self::parse_timestamp)
+                .context("failed extracting timestamp")?
+        } else {
+            Utc::now()
+        };
+
+        let (numerator, denominator) = get_fraction(price);
+        Ok(BaseTokenAPIRatio {
+            numerator,
+            denominator,
+            ratio_timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::Path, http::HeaderMap, routing::get, Json, Router};
+    use serde_json::json;
+    use zksync_config::configs::external_price_api_client::JsonPathPriceClientConfig;
+
+    use super::*;
+    use crate::utils::tests::spawn_mock_server;
+
+    fn client_config(
+        base_url: String,
+        price_path: &str,
+        timestamp_path: Option<&str>,
+    ) -> ExternalPriceApiClientConfig {
+        ExternalPriceApiClientConfig {
+            source: "json_path".to_owned(),
+            base_url: Some(format!("{base_url}prices/{{address}}")),
+            api_key: Some("test-key".to_owned()),
+            client_timeout_ms: 5_000,
+            forced: None,
+            json_path: Some(JsonPathPriceClientConfig {
+                price_path: price_path.to_owned(),
+                timestamp_path: timestamp_path.map(str::to_owned),
+                api_key_header: Some("x-api-key".to_owned()),
+            }),
+            aggregated: None,
+        }
+    }
+
+    async fn spawn_server() -> String {
+        let router = Router::new().route(
+            "/prices/:address",
+            get(
+                |headers: HeaderMap, Path(address): Path<String>| async move {
+                    assert_eq!(headers["x-api-key"], "test-key");
+                    assert_eq!(address, address_to_string(&Address::repeat_byte(1)));
+                    Json(json!({
+                        "data": [
+                            { "price": "0.5", "updated_at": 1_722_513_600 },
+                            { "price": 0.125, "updated_at": "2024-08-01T12:00:00Z" },
+                        ],
+                    }))
+                },
+            ),
+        );
+        spawn_mock_server(router).await
+    }
+
+    #[tokio::test]
+    async fn fetching_price_using_json_path() {
+        let base_url = spawn_server().await;
+        let expected_timestamp = DateTime::from_timestamp(1_722_513_600, 0).unwrap();
+
+        let config = client_config(base_url.clone(), "data.0.price", Some("data.0.updated_at"));
+        let client = JsonPathPriceAPIClient::new(config);
+        let ratio = client.fetch_ratio(Address::repeat_byte(1)).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (1, 2));
+        assert_eq!(ratio.ratio_timestamp, expected_timestamp);
+
+        let config = client_config(base_url, "data.1.price", Some("data.1.updated_at"));
+        let client = JsonPathPriceAPIClient::new(config);
+        let ratio = client.fetch_ratio(Address::repeat_byte(1)).await.unwrap();
+        assert_eq!((ratio.numerator.get(), ratio.denominator.get()), (1, 8));
+        assert_eq!(ratio.ratio_timestamp, expected_timestamp);
+    }
+
+    #[tokio::test]
+    async fn missing_price_error() {
+        let base_url = spawn_server().await;
+        let client = JsonPathPriceAPIClient::new(client_config(base_url, "data.2.price", None));
+        let err = client
+            .fetch_ratio(Address::repeat_byte(1))
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("no value at path `data.2`"),
+            "{err:#}"
+        );
+    }
+}
@@ -1,5 +1,8 @@
+pub mod aggregated_client;
+pub mod cmc_api;
 pub mod coingecko_api;
 pub mod forced_price_client;
+pub mod json_path_api;
 mod utils;
 
 use std::fmt;
@@ -4,7 +4,7 @@ use zksync_types::{
     l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{AccountTransactions, L2TxFilter, MempoolPolicy, MempoolScore};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
@@ -17,6 +17,12 @@ pub struct MempoolStats {
     pub l1_transaction_count: usize,
     pub l2_transaction_count: u64,
     pub l2_priority_queue_size: usize,
+    /// Total number of L2 transactions replaced by transactions with the same nonce.
+    pub replaced_transaction_count: u64,
+    /// Total number of rejected L2 transaction replacements because of an insufficient fee bump.
+    pub rejected_replacement_count: u64,
+    /// Total number of L2 transactions evicted because the mempool has reached its capacity.
+    pub evicted_transaction_count: u64,
 }
 
 #[derive(Debug)]
@@ -33,6 +39,10 @@ pub struct MempoolStore {
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    policy: MempoolPolicy,
+    replaced_transaction_count: u64,
+    rejected_replacement_count: u64,
+    evicted_transaction_count: u64,
 }
 
 impl MempoolStore {
@@ -45,9 +55,19 @@ impl MempoolStore {
             stashed_accounts: vec![],
             size: 0,
             capacity,
+            policy: MempoolPolicy::default(),
+            replaced_transaction_count: 0,
+            rejected_replacement_count: 0,
+            evicted_transaction_count: 0,
         }
     }
 
+    /// Sets the ordering, replacement and eviction policy for L2 transactions.
+    pub fn with_policy(mut self, policy: MempoolPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -101,16 +121,30 @@ impl MempoolStore {
         initial_nonces: &HashMap<Address, Nonce>,
     ) {
         let account = transaction.initiator_account();
+        let min_fee_bump = self.policy.min_replacement_fee_bump_percent;
 
         let metadata = match self.l2_transactions_per_account.entry(account) {
-            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
+            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction, min_fee_bump),
             hash_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
                 entry
-                    .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
+                    .insert(AccountTransactions::new(
+                        account_nonce,
+                        self.policy.ordering,
+                    ))
+                    .insert(transaction, min_fee_bump)
             }
         };
+        if metadata.is_underpriced_replacement {
+            tracing::debug!(
+                "rejected replacement of transaction from {account:?}: fee bump is insufficient"
+            );
+            self.rejected_replacement_count += 1;
+            return;
+        }
+        if metadata.is_replacement {
+            self.replaced_transaction_count += 1;
+        }
         if let Some(score) = metadata.previous_score {
             self.l2_priority_queue.remove(&score);
         }
@@ -217,6 +251,9 @@ impl MempoolStore {
             l1_transaction_count: self.l1_transactions.len(),
             l2_transaction_count: self.size,
             l2_priority_queue_size: self.l2_priority_queue.len(),
+            replaced_transaction_count: self.replaced_transaction_count,
+            rejected_replacement_count: self.rejected_replacement_count,
+            evicted_transaction_count: self.evicted_transaction_count,
         }
     }
 
@@ -236,8 +273,45 @@ impl MempoolStore {
                 .l2_transactions_per_account
                 .iter()
                 .fold(0, |agg, (_, tnxs)| agg + tnxs.len() as u64);
-            return drained.into_keys().collect();
+            let mut purged_accounts: Vec<_> = drained.into_keys().collect();
+            if self.policy.evict_cheapest {
+                purged_accounts.extend(self.evict_cheapest());
+            }
+            return purged_accounts;
         }
         vec![]
     }
+
+    /// Evicts transactions of the accounts with the cheapest ready transactions until the mempool size
+    /// is below its capacity. Returns evicted accounts.
+    fn evict_cheapest(&mut self) -> Vec<Address> {
+        let mut evicted_accounts = vec![];
+        if self.size < self.capacity {
+            return evicted_accounts;
+        }
+
+        let mut pointers: Vec<_> = self.l2_priority_queue.iter().cloned().collect();
+        pointers.sort_unstable_by(MempoolScore::cmp_by_price);
+        for pointer in pointers {
+            if self.size < self.capacity {
+                break;
+            }
+            self.l2_priority_queue.remove(&pointer);
+            let evicted_count = self
+                .l2_transactions_per_account
+                .remove(&pointer.account)
+                .expect("mempool: dangling pointer in priority queue")
+                .len() as u64;
+            self.size -= evicted_count;
+            self.evicted_transaction_count += evicted_count;
+            evicted_accounts.push(pointer.account);
+        }
+        if !evicted_accounts.is_empty() {
+            tracing::info!(
+                "Evicted transactions of {} accounts from full mempool",
+                evicted_accounts.len()
+            );
+        }
+        evicted_accounts
+    }
 }
@@ -12,7 +12,10 @@ use zksync_types::{
     H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::MempoolStore,
+    types::{L2TxFilter, MempoolOrdering, MempoolPolicy},
+};
 
 #[test]
 fn basic_flow() {
@@ -365,6 +368,178 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn priority_fee_ordering() {
+    let policy = MempoolPolicy {
+        ordering: MempoolOrdering::PriorityFee,
+        ..MempoolPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_policy(policy);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account0, Nonce(0), now, 10, 1),
+            gen_l2_tx_with_fee(account1, Nonce(0), now + 1, 10, 5),
+            gen_l2_tx_with_fee(account2, Nonce(0), now + 2, 10, 5),
+            gen_l2_tx_with_fee(account1, Nonce(1), now + 3, 10, 0),
+        ],
+        HashMap::new(),
+    );
+
+    // Among transactions with the same priority fee, the earlier one goes first.
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account2, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 1)
+    );
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+}
+
+#[test]
+fn fifo_ordering_ignores_priority_fee() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account0, Nonce(0), now, 10, 1),
+            gen_l2_tx_with_fee(account1, Nonce(0), now + 1, 10, 5),
+        ],
+        HashMap::new(),
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 0)
+    );
+}
+
+#[test]
+fn replacement_with_min_fee_bump() {
+    let policy = MempoolPolicy {
+        min_replacement_fee_bump_percent: Some(10),
+        ..MempoolPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_policy(policy);
+    let account = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account, Nonce(0), now, 100, 10),
+            gen_l2_tx_with_fee(account, Nonce(1), now, 100, 10),
+        ],
+        HashMap::new(),
+    );
+
+    // Insufficient bump of the priority fee.
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), now + 1, 200, 10)],
+        HashMap::new(),
+    );
+    // Insufficient bump of the max fee.
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(1), now + 1, 109, 11)],
+        HashMap::new(),
+    );
+    let stats = mempool.stats();
+    assert_eq!(stats.rejected_replacement_count, 2);
+    assert_eq!(stats.replaced_transaction_count, 0);
+    assert_eq!(stats.l2_transaction_count, 2);
+
+    // Sufficient bump.
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(1), now + 2, 110, 11)],
+        HashMap::new(),
+    );
+    let stats = mempool.stats();
+    assert_eq!(stats.rejected_replacement_count, 2);
+    assert_eq!(stats.replaced_transaction_count, 1);
+    assert_eq!(stats.l2_transaction_count, 2);
+
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(fee(&tx).max_fee_per_gas, 100.into());
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(fee(&tx).max_fee_per_gas, 110.into());
+    assert_eq!(fee(&tx).max_priority_fee_per_gas, 11.into());
+}
+
+#[test]
+fn evicting_cheapest_transactions() {
+    let policy = MempoolPolicy {
+        evict_cheapest: true,
+        ..MempoolPolicy::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 4).with_policy(policy);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let account3 = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account0, Nonce(0), now, 100, 3),
+            gen_l2_tx_with_fee(account0, Nonce(1), now, 100, 3),
+            gen_l2_tx_with_fee(account1, Nonce(0), now, 100, 1),
+            gen_l2_tx_with_fee(account1, Nonce(1), now, 100, 1),
+            gen_l2_tx_with_fee(account2, Nonce(0), now, 100, 2),
+            // Not ready for execution; will be purged.
+            gen_l2_tx_with_fee(account3, Nonce(1), now, 100, 10),
+        ],
+        HashMap::new(),
+    );
+
+    let purged_accounts = mempool.get_mempool_info().purged_accounts;
+    assert_eq!(
+        HashSet::<_>::from_iter(purged_accounts),
+        HashSet::from([account1, account3])
+    );
+    let stats = mempool.stats();
+    assert_eq!(stats.l2_transaction_count, 3);
+    assert_eq!(stats.evicted_transaction_count, 2);
+
+    let mut accounts = vec![];
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        accounts.push(tx.initiator_account());
+    }
+    accounts.sort();
+    let mut expected_accounts = vec![account0, account0, account2];
+    expected_accounts.sort();
+    assert_eq!(accounts, expected_accounts);
+}
+
+#[test]
+fn no_eviction_without_policy() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 2);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    mempool.insert(
+        vec![gen_l2_tx(account0, Nonce(0)), gen_l2_tx(account1, Nonce(0))],
+        HashMap::new(),
+    );
+    assert!(mempool.get_mempool_info().purged_accounts.is_empty());
+    let stats = mempool.stats();
+    assert_eq!(stats.l2_transaction_count, 2);
+    assert_eq!(stats.evicted_transaction_count, 0);
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -384,6 +559,39 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_fee(
+    address: Address,
+    nonce: Nonce,
+    received_at_ms: u64,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+) -> Transaction {
+    let fee = Fee {
+        max_fee_per_gas: max_fee_per_gas.into(),
+        max_priority_fee_per_gas: max_priority_fee_per_gas.into(),
+        ..Fee::default()
+    };
+    let mut txn = L2Tx::new(
+        Address::default(),
+        Vec::new(),
+        nonce,
+        fee,
+        address,
+        U256::zero(),
+        vec![],
+        Default::default(),
+    );
+    txn.received_timestamp_ms = received_at_ms;
+    txn.into()
+}
+
+fn fee(transaction: &Transaction) -> &Fee {
+    match &transaction.common_data {
+        ExecuteTransactionCommon::L2(data) => &data.fee,
+        _ => unreachable!(),
+    }
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
@@ -1,9 +1,32 @@
 use std::{cmp::Ordering, collections::HashMap};
 
 use zksync_types::{
-    fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, Address, Nonce, Transaction, U256,
+    fee::Fee, fee_model::BatchFeeInput, l2::L2Tx, Address, Nonce, Transaction, H256, U256,
 };
 
+/// Policy used to order L2 transactions from different accounts in the mempool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MempoolOrdering {
+    /// Transactions are ordered by the time they were received.
+    #[default]
+    Fifo,
+    /// Transactions with higher `max_priority_fee_per_gas` go first; ties are resolved by the receiving time.
+    PriorityFee,
+}
+
+/// Policy for the mempool: transaction ordering, same-nonce replacement and eviction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MempoolPolicy {
+    /// Ordering of transactions from different accounts.
+    pub ordering: MempoolOrdering,
+    /// Minimum bump (in percent) of both `max_fee_per_gas` and `max_priority_fee_per_gas` required
+    /// to replace a pending transaction with the same nonce. If not set, replacements are always accepted.
+    pub min_replacement_fee_bump_percent: Option<u64>,
+    /// Whether transactions of the accounts with the cheapest ready transactions should be evicted
+    /// once the mempool reaches its capacity.
+    pub evict_cheapest: bool,
+}
+
 /// Pending mempool transactions of account
 #[derive(Debug)]
 pub(crate) struct AccountTransactions {
@@ -12,29 +35,52 @@ pub(crate) struct AccountTransactions {
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    /// Ordering used for scores of transactions.
+    ordering: MempoolOrdering,
 }
 
 impl AccountTransactions {
-    pub fn new(nonce: Nonce) -> Self {
+    pub fn new(nonce: Nonce, ordering: MempoolOrdering) -> Self {
         Self {
             transactions: HashMap::new(),
             nonce,
+            ordering,
         }
     }
 
     /// Inserts new transaction for given account. Returns insertion metadata
-    pub fn insert(&mut self, transaction: L2Tx) -> InsertionMetadata {
+    pub fn insert(
+        &mut self,
+        transaction: L2Tx,
+        min_replacement_fee_bump_percent: Option<u64>,
+    ) -> InsertionMetadata {
         let mut metadata = InsertionMetadata::default();
         let nonce = transaction.common_data.nonce;
         // skip insertion if transaction is old
         if nonce < self.nonce {
             return metadata;
         }
-        let new_score = Self::score_for_transaction(&transaction);
+        let replaced_tx = self
+            .transactions
+            .get(&nonce)
+            .filter(|tx| !is_same_transaction(tx, &transaction));
+        if let (Some(replaced_tx), Some(fee_bump_percent)) =
+            (replaced_tx, min_replacement_fee_bump_percent)
+        {
+            let replaced_fee = &replaced_tx.common_data.fee;
+            if !is_sufficient_fee_bump(replaced_fee, &transaction.common_data.fee, fee_bump_percent)
+            {
+                metadata.is_underpriced_replacement = true;
+                return metadata;
+            }
+        }
+        metadata.is_replacement = replaced_tx.is_some();
+
+        let new_score = self.score_for_transaction(&transaction);
         let previous_score = self
             .transactions
             .insert(nonce, transaction)
-            .map(|tx| Self::score_for_transaction(&tx));
+            .map(|tx| self.score_for_transaction(&tx));
         metadata.is_new = previous_score.is_none();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
@@ -54,7 +100,7 @@ impl AccountTransactions {
         let score = self
             .transactions
             .get(&self.nonce)
-            .map(Self::score_for_transaction);
+            .map(|tx| self.score_for_transaction(tx));
         (transaction, score)
     }
 
@@ -67,32 +113,62 @@ impl AccountTransactions {
         self.nonce = self.nonce.min(tx_nonce);
         self.transactions
             .get(&(tx_nonce + 1))
-            .map(Self::score_for_transaction)
+            .map(|tx| self.score_for_transaction(tx))
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
+    fn score_for_transaction(&self, transaction: &L2Tx) -> MempoolScore {
         MempoolScore {
             account: transaction.initiator_account(),
             received_at_ms: transaction.received_timestamp_ms,
             fee_data: transaction.common_data.fee.clone(),
+            ordering: self.ordering,
         }
     }
 }
 
-/// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Returns the transaction hash if the transaction has input data (which is always the case for real transactions).
+fn input_hash(transaction: &L2Tx) -> Option<H256> {
+    transaction
+        .common_data
+        .input
+        .as_ref()
+        .map(|input| input.hash)
+}
+
+fn is_same_transaction(lhs: &L2Tx, rhs: &L2Tx) -> bool {
+    let lhs_hash = input_hash(lhs);
+    lhs_hash.is_some() && lhs_hash == input_hash(rhs)
+}
+
+/// Checks whether both `max_fee_per_gas` and `max_priority_fee_per_gas` of the replacement transaction
+/// are greater than the original ones by at least `fee_bump_percent`.
+fn is_sufficient_fee_bump(original: &Fee, replacement: &Fee, fee_bump_percent: u64) -> bool {
+    let is_sufficient = |original: U256, replacement: U256| {
+        let required = original.saturating_mul(U256::from(100 + fee_bump_percent));
+        replacement.saturating_mul(U256::from(100)) >= required
+    };
+    is_sufficient(original.max_fee_per_gas, replacement.max_fee_per_gas)
+        && is_sufficient(
+            original.max_priority_fee_per_gas,
+            replacement.max_priority_fee_per_gas,
+        )
+}
+
+/// Mempool score of transaction. Used to prioritize L2 transactions in mempool.
+/// Greater scores are prioritized; the ordering depends on the [`MempoolOrdering`] policy.
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
     pub account: Address,
     pub received_at_ms: u64,
-    // Not used for actual scoring, but state keeper would request
+    // Used for scoring only with the `PriorityFee` ordering. Besides that, state keeper would request
     // transactions that have acceptable fee values (so transactions
     // with fee too low would be ignored until prices go down).
     pub fee_data: Fee,
+    pub ordering: MempoolOrdering,
 }
 
 impl MempoolScore {
@@ -101,10 +177,32 @@ impl MempoolScore {
         self.fee_data.max_fee_per_gas >= U256::from(filter.fee_per_gas)
             && self.fee_data.gas_per_pubdata_limit >= U256::from(filter.gas_per_pubdata)
     }
+
+    /// Compares scores by cheapness, i.e. by priority fee, then by max fee per gas. Scores for more recent
+    /// transactions are considered cheaper if fees are equal. Used to select transactions for eviction.
+    pub(crate) fn cmp_by_price(&self, other: &Self) -> Ordering {
+        let fee = &self.fee_data;
+        let other_fee = &other.fee_data;
+        fee.max_priority_fee_per_gas
+            .cmp(&other_fee.max_priority_fee_per_gas)
+            .then_with(|| fee.max_fee_per_gas.cmp(&other_fee.max_fee_per_gas))
+            .then_with(|| self.received_at_ms.cmp(&other.received_at_ms).reverse())
+            .then_with(|| self.account.cmp(&other.account))
+    }
 }
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        if self.ordering == MempoolOrdering::PriorityFee {
+            match self
+                .fee_data
+                .max_priority_fee_per_gas
+                .cmp(&other.fee_data.max_priority_fee_per_gas)
+            {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            }
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -124,6 +222,10 @@ pub(crate) struct InsertionMetadata {
     pub new_score: Option<MempoolScore>,
     pub previous_score: Option<MempoolScore>,
     pub is_new: bool,
+    /// Set if the transaction replaced a different transaction with the same nonce.
+    pub is_replacement: bool,
+    /// Set if the transaction wasn't inserted because it replaces a transaction without a sufficient fee bump.
+    pub is_underpriced_replacement: bool,
 }
 
 /// Structure that can be used by state keeper to describe
@@ -166,6 +268,7 @@ mod tests {
                 max_priority_fee_per_gas: U256::from(MAX_PRIORITY_FEE_PER_GAS),
                 gas_per_pubdata_limit: U256::from(GAS_PER_PUBDATA_LIMIT),
             },
+            ordering: MempoolOrdering::Fifo,
         };
 
         let noop_filter = filter(0, 0);
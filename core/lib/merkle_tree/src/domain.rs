@@ -1,5 +1,8 @@
 //! Tying the Merkle tree implementation to the problem domain.
 
+use std::path::Path;
+
+use anyhow::Context as _;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_prover_interface::inputs::{StorageLogMetadata, WitnessInputMerklePaths};
@@ -214,6 +217,42 @@ impl ZkSyncTree {
         self.tree.verify_consistency(version, true)
     }
 
+    /// Prepares a tree checkpoint (e.g., created using [`ZkSyncTreeReader::create_checkpoint()`]) for use:
+    /// truncates tree versions after `l1_batch_number` and verifies tree consistency at this L1 batch.
+    /// Returns the root hash of the tree at `l1_batch_number`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the checkpoint doesn't contain the specified L1 batch, if an inconsistency is detected,
+    /// or on database I/O errors.
+    pub fn finalize_checkpoint(
+        db: RocksDBWrapper,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<ValueHash> {
+        let mut tree = Self::new_lightweight(db)?;
+        let next_l1_batch_number = tree.next_l1_batch_number();
+        anyhow::ensure!(
+            l1_batch_number < next_l1_batch_number,
+            "tree checkpoint only contains L1 batches before #{next_l1_batch_number}, \
+             while L1 batch #{l1_batch_number} was requested"
+        );
+        let (root_hash, _) = tree.root_info(l1_batch_number).with_context(|| {
+            format!("L1 batch #{l1_batch_number} is pruned in the tree checkpoint")
+        })?;
+
+        if next_l1_batch_number > l1_batch_number + 1 {
+            tracing::info!(
+                "Truncating tree checkpoint from L1 batch #{} to #{l1_batch_number}",
+                next_l1_batch_number - 1
+            );
+            tree.roll_back_logs(l1_batch_number)?;
+            tree.save()?;
+        }
+        tree.verify_consistency(l1_batch_number)
+            .context("tree checkpoint is inconsistent")?;
+        Ok(root_hash)
+    }
+
     /// Processes an iterator of storage logs comprising a single L1 batch.
     ///
     /// # Errors
@@ -456,4 +495,32 @@ impl ZkSyncTreeReader {
         let version = l1_batch_number.0.into();
         self.0.verify_consistency(version, true)
     }
+
+    /// Creates a consistent checkpoint of the tree at the specified L1 batch in `path`, which must not exist.
+    /// Tree updates are not blocked while the checkpoint is created, so this method can be used for a tree
+    /// maintained by a running node. The checkpoint is post-processed using [`ZkSyncTree::finalize_checkpoint()`].
+    /// Returns the root hash of the tree at `l1_batch_number`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the tree doesn't contain the specified L1 batch, if the created checkpoint is inconsistent,
+    /// or on database I/O errors.
+    pub fn create_checkpoint(
+        &self,
+        path: &Path,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<ValueHash> {
+        let next_l1_batch_number = self.next_l1_batch_number();
+        anyhow::ensure!(
+            l1_batch_number < next_l1_batch_number,
+            "tree only contains L1 batches before #{next_l1_batch_number}, \
+             while L1 batch #{l1_batch_number} was requested"
+        );
+        self.0
+            .db
+            .create_checkpoint(path)
+            .context("failed creating RocksDB checkpoint")?;
+        let db = RocksDBWrapper::new(path).context("failed opening tree checkpoint")?;
+        ZkSyncTree::finalize_checkpoint(db, l1_batch_number)
+    }
 }
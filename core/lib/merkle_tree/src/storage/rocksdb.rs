@@ -157,6 +157,16 @@ impl RocksDBWrapper {
         })
     }
 
+    /// Creates a consistent checkpoint of the tree database at the specified `path`, which must not exist.
+    /// See [`RocksDB::create_checkpoint()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        self.db.create_checkpoint(path)
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -101,6 +101,44 @@ fn basic_workflow_multiblock() {
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(12));
 }
 
+#[test]
+fn creating_tree_checkpoint() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let logs = gen_storage_logs();
+    let db = RocksDB::new(&temp_dir.path().join("tree")).unwrap();
+    let mut tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    let root_hashes: Vec<_> = logs
+        .chunks(20)
+        .map(|block| tree.process_l1_batch(block).unwrap().root_hash)
+        .collect();
+    tree.save().unwrap();
+
+    let checkpoint_path = temp_dir.path().join("checkpoint");
+    let root_hash = tree
+        .reader()
+        .create_checkpoint(&checkpoint_path, L1BatchNumber(2))
+        .unwrap();
+    assert_eq!(root_hash, root_hashes[2]);
+    // The original tree must not be affected.
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(5));
+    tree.verify_consistency(L1BatchNumber(4)).unwrap();
+
+    let db = RocksDB::new(&checkpoint_path).unwrap();
+    let checkpoint_tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    assert_eq!(checkpoint_tree.next_l1_batch_number(), L1BatchNumber(3));
+    assert_eq!(checkpoint_tree.root_hash(), root_hashes[2]);
+    drop(checkpoint_tree);
+
+    // Finalizing a checkpoint for an L1 batch missing in it should fail.
+    let db = RocksDB::new(&checkpoint_path).unwrap();
+    ZkSyncTree::finalize_checkpoint(db.into(), L1BatchNumber(3)).unwrap_err();
+    let err = tree
+        .reader()
+        .create_checkpoint(&temp_dir.path().join("other"), L1BatchNumber(5))
+        .unwrap_err();
+    assert!(err.to_string().contains("L1 batch #5"), "{err:#}");
+}
+
 #[test]
 fn tree_with_single_leaf_works_correctly() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
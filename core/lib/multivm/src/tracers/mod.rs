@@ -3,6 +3,7 @@ pub use self::{
     multivm_dispatcher::TracerDispatcher,
     prestate_tracer::PrestateTracer,
     storage_invocation::StorageInvocations,
+    struct_logger::{StructLog, StructLogger, StructLoggerConfig},
     validator::{ValidationError, ValidationTracer, ValidationTracerParams},
 };
 
@@ -10,6 +11,7 @@ mod call_tracer;
 pub mod dynamic;
 mod multivm_dispatcher;
 pub mod old;
-mod prestate_tracer;
+pub mod prestate_tracer;
 mod storage_invocation;
+mod struct_logger;
 mod validator;
@@ -1,16 +1,24 @@
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, iter,
+    sync::Arc,
+};
 
 use once_cell::sync::OnceCell;
 use zksync_types::{
     get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey, StorageValue,
-    H160, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    ACCOUNT_CODE_STORAGE_ADDRESS, H256, L2_BASE_TOKEN_ADDRESS, NONCE_HOLDER_ADDRESS, U256,
 };
-use zksync_utils::{address_to_h256, h256_to_u256};
+use zksync_utils::{address_to_h256, h256_to_account_address, h256_to_u256};
 
-use crate::interface::storage::{StoragePtr, WriteStorage};
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::storage::{ReadStorage, StoragePtr, WriteStorage},
+};
 
 pub mod vm_1_4_1;
 pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
 pub mod vm_latest;
 pub mod vm_refunds_enhancement;
 pub mod vm_virtual_blocks;
@@ -46,7 +54,7 @@ impl fmt::Display for Account {
     }
 }
 
-type State = HashMap<Address, Account>;
+pub type State = HashMap<Address, Account>;
 
 #[derive(Debug, Clone)]
 pub struct PrestateTracer {
@@ -54,6 +62,7 @@ pub struct PrestateTracer {
     pub post: State,
     pub config: PrestateTracerConfig,
     pub result: Arc<OnceCell<(State, State)>>,
+    snapshot: Option<StorageSnapshot>,
 }
 
 impl PrestateTracer {
@@ -63,51 +72,159 @@ impl PrestateTracer {
             post: Default::default(),
             config: PrestateTracerConfig { diff_mode },
             result,
+            snapshot: None,
+        }
+    }
+
+    /// Snapshots the storage state at the start of the traced execution. The snapshot cannot be taken
+    /// when the tracer is created since a single VM may execute multiple transactions (e.g., when replaying
+    /// an L2 block), and the tracer must only capture changes made by the traced one.
+    fn snapshot_storage<S: WriteStorage>(&mut self, storage: &StoragePtr<S>) {
+        if self.snapshot.is_none() {
+            let storage = storage.borrow();
+            self.snapshot = Some(StorageSnapshot {
+                modified: storage.modified_storage_keys().clone(),
+                read: storage.read_storage_keys().keys().copied().collect(),
+            });
+        }
+    }
+
+    /// Collects the states of the accounts touched by the traced execution before and after it
+    /// and publishes them as the tracer result. In the diff mode, only accounts modified by the execution
+    /// are considered touched.
+    fn finalize<S: WriteStorage>(&mut self, storage: &StoragePtr<S>) {
+        let snapshot = self.snapshot.take().unwrap_or_default();
+        let mut storage = storage.borrow_mut();
+
+        let mut touched_keys: HashSet<_> = storage
+            .modified_storage_keys()
+            .iter()
+            .filter(|&(key, value)| snapshot.modified.get(key) != Some(value))
+            .map(|(key, _)| *key)
+            .collect();
+        if !self.config.diff_mode {
+            let read_keys = storage.read_storage_keys().keys();
+            touched_keys.extend(read_keys.filter(|key| !snapshot.read.contains(key)));
+        }
+
+        // Balances, nonces and bytecode hashes of accounts are stored in system contracts, so an account is touched
+        // if any of these values is touched, even if its own storage isn't. Since a balance key cannot be mapped
+        // to the account, candidate accounts are collected from all keys accessed by the VM.
+        let all_keys = storage
+            .modified_storage_keys()
+            .keys()
+            .chain(storage.read_storage_keys().keys());
+        let mut candidates: HashSet<Address> = all_keys.flat_map(key_addresses).collect();
+        candidates.extend(touched_keys.iter().flat_map(key_addresses));
+        let mut touched_slots = HashMap::<_, Vec<_>>::new();
+        for key in &touched_keys {
+            touched_slots.entry(*key.address()).or_default().push(*key);
+        }
+        let touched_accounts: Vec<_> = candidates
+            .into_iter()
+            .filter(|address| {
+                touched_slots.contains_key(address)
+                    || touched_keys.contains(&get_balance_key(&AccountTreeId::new(*address)))
+                    || touched_keys.contains(&get_nonce_key(address))
+                    || touched_keys.contains(&get_code_key(address))
+            })
+            .collect();
+
+        let pre = touched_accounts
+            .iter()
+            .map(|&address| {
+                let slots = touched_slots.get(&address).map_or(&[][..], Vec::as_slice);
+                let account = account_state(address, slots, |key| {
+                    pre_value(&snapshot, &mut *storage, key)
+                });
+                (address, account)
+            })
+            .collect();
+        let post = touched_accounts
+            .iter()
+            .map(|&address| {
+                let slots = touched_slots.get(&address).map_or(&[][..], Vec::as_slice);
+                let account = account_state(address, slots, |key| storage.read_value(key));
+                (address, account)
+            })
+            .collect();
+        drop(storage);
+
+        self.pre = pre;
+        self.post = post;
+        if self.config.diff_mode {
+            process_result(&self.result, self.pre.clone(), self.post.clone());
+        } else {
+            self.result
+                .set((self.pre.clone(), self.post.clone()))
+                .unwrap();
         }
     }
 }
 
+/// Prestate tracer is not supported for VM versions before virtual blocks, so the result
+/// will not be set for them.
+impl IntoOldVmTracer for PrestateTracer {}
+
 #[derive(Debug, Clone)]
 pub struct PrestateTracerConfig {
     diff_mode: bool,
 }
 
-pub fn process_modified_storage_keys<S>(
-    prestate: State,
-    storage: &StoragePtr<S>,
-) -> HashMap<H160, Account>
-where
-    S: WriteStorage,
-{
-    let cloned_storage = &storage.clone();
-    let mut initial_storage_ref = cloned_storage.as_ref().borrow_mut();
-
-    initial_storage_ref
-        .modified_storage_keys()
-        .clone()
-        .iter()
-        .filter(|k| !prestate.contains_key(k.0.account().address()))
-        .map(|k| {
-            (
-                *(k.0.account().address()),
-                Account {
-                    balance: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_balance_key(k.0.account())),
-                    )),
-                    code: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_code_key(k.0.account().address())),
-                    )),
-                    nonce: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_nonce_key(k.0.account().address())),
-                    )),
-                    storage: Some(get_storage_if_present(
-                        k.0.account(),
-                        initial_storage_ref.modified_storage_keys(),
-                    )),
-                },
-            )
-        })
-        .collect::<State>()
+/// Storage state at the start of the execution traced by [`PrestateTracer`].
+#[derive(Debug, Clone, Default)]
+struct StorageSnapshot {
+    /// Values of the keys modified before the traced execution.
+    modified: HashMap<StorageKey, StorageValue>,
+    /// Keys read before the traced execution.
+    read: HashSet<StorageKey>,
+}
+
+/// Returns the value of `key` before the traced execution.
+fn pre_value<S: WriteStorage>(
+    snapshot: &StorageSnapshot,
+    storage: &mut S,
+    key: &StorageKey,
+) -> StorageValue {
+    if let Some(value) = snapshot.modified.get(key) {
+        return *value;
+    }
+    if storage.modified_storage_keys().contains_key(key) {
+        // The key was first modified by the traced execution. Storage always reads a key before modifying it,
+        // so the initial value is cached.
+        if let Some(value) = storage.read_storage_keys().get(key) {
+            return *value;
+        }
+    }
+    // The key wasn't modified; its current value is the same as before the execution.
+    storage.read_value(key)
+}
+
+/// Returns addresses of accounts that a storage key may belong to: the account owning the key, and
+/// the account addressed by the key for nonce and bytecode hash storage.
+fn key_addresses(key: &StorageKey) -> impl Iterator<Item = Address> {
+    let owner = *key.address();
+    let addressed = [ACCOUNT_CODE_STORAGE_ADDRESS, NONCE_HOLDER_ADDRESS]
+        .contains(&owner)
+        .then(|| h256_to_account_address(key.key()));
+    iter::once(owner).chain(addressed)
+}
+
+fn account_state(
+    address: Address,
+    slots: &[StorageKey],
+    mut read_value: impl FnMut(&StorageKey) -> StorageValue,
+) -> Account {
+    let balance = read_value(&get_balance_key(&AccountTreeId::new(address)));
+    let code = read_value(&get_code_key(&address));
+    let nonce = read_value(&get_nonce_key(&address));
+    let storage = slots.iter().map(|key| (*key.key(), read_value(key)));
+    Account {
+        balance: Some(h256_to_u256(balance)),
+        code: Some(h256_to_u256(code)),
+        nonce: Some(h256_to_u256(nonce)),
+        storage: Some(storage.collect()),
+    }
 }
 
 fn get_balance_key(account: &AccountTreeId) -> StorageKey {
@@ -117,18 +234,6 @@ fn get_balance_key(account: &AccountTreeId) -> StorageKey {
     StorageKey::new(AccountTreeId::new(L2_BASE_TOKEN_ADDRESS), balance_key)
 }
 
-fn get_storage_if_present(
-    account: &AccountTreeId,
-    modified_storage_keys: &HashMap<StorageKey, StorageValue>,
-) -> HashMap<H256, H256> {
-    //check if there is a Storage Key struct with an account field that matches the account and return the key as the key and the Storage Value as the value
-    modified_storage_keys
-        .iter()
-        .filter(|(k, _)| k.account() == account)
-        .map(|(k, v)| (*k.key(), *v))
-        .collect()
-}
-
 fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post: State) {
     pre.retain(|k, v| {
         if let Some(post_v) = post.get(k) {
@@ -140,30 +245,3 @@ fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post:
     });
     result.set((pre, post)).unwrap();
 }
-
-fn get_account_data<T: StorageAccess>(
-    account_key: &StorageKey,
-    state: &T,
-    storage: &HashMap<StorageKey, StorageValue>,
-) -> (Address, Account) {
-    let address = *(account_key.account().address());
-    let balance = state.read_from_storage(&get_balance_key(account_key.account()));
-    let code = state.read_from_storage(&get_code_key(account_key.account().address()));
-    let nonce = state.read_from_storage(&get_nonce_key(account_key.account().address()));
-    let storage = get_storage_if_present(account_key.account(), storage);
-
-    (
-        address,
-        Account {
-            balance: Some(balance),
-            code: Some(code),
-            nonce: Some(nonce),
-            storage: Some(storage),
-        },
-    )
-}
-
-// Define a trait that abstracts storage access
-trait StorageAccess {
-    fn read_from_storage(&self, key: &StorageKey) -> U256;
-}
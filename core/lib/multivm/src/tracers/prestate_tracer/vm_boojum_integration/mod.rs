@@ -0,0 +1,31 @@
+use zk_evm_1_4_0::tracing::{BeforeExecutionData, VmLocalStateData};
+
+use super::PrestateTracer;
+use crate::{
+    interface::storage::{StoragePtr, WriteStorage},
+    tracers::dynamic::vm_1_4_0::DynTracer,
+    vm_boojum_integration::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {
+    fn before_execution(
+        &mut self,
+        _state: VmLocalStateData<'_>,
+        _data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        self.snapshot_storage(&storage);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: crate::interface::tracer::VmExecutionStopReason,
+    ) {
+        self.finalize(&state.storage.storage.get_ptr());
+    }
+}
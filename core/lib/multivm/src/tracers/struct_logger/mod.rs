@@ -0,0 +1,177 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    mem,
+    sync::Arc,
+};
+
+use once_cell::sync::OnceCell;
+use zksync_types::{vm::VmVersion, Address, H256, U256};
+use zksync_utils::u256_to_h256;
+
+use crate::glue::tracers::IntoOldVmTracer;
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Single executed instruction recorded by [`StructLogger`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLog {
+    /// Program counter of the instruction in the current frame.
+    pub pc: u16,
+    /// Human-readable opcode name.
+    pub op: String,
+    /// Gas (ergs) remaining in the current frame before executing the instruction.
+    pub gas: u32,
+    /// Gas (ergs) spent by the instruction, i.e. the difference between gas remaining before and after the instruction.
+    /// For far calls and returns, it's computed across different frames and is thus approximate.
+    pub gas_cost: u32,
+    /// Depth of the call stack. Unlike in EVM, near calls create frames as well.
+    pub depth: usize,
+    /// Values of the VM registers before executing the instruction. EraVM is a register machine,
+    /// so registers play the role of the EVM stack.
+    pub stack: Option<Vec<U256>>,
+    /// Heap of the current frame (as 32-byte words) before executing the instruction.
+    pub memory: Option<Vec<U256>>,
+    /// Storage slots of the current contract accessed so far. Only recorded for storage reads and writes.
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+impl StructLog {
+    /// Estimates the number of bytes occupied by this log in memory.
+    pub(crate) fn estimated_size(&self) -> usize {
+        const WORD_SIZE: usize = 32;
+
+        let stack_words = self.stack.as_ref().map_or(0, Vec::len);
+        let memory_words = self.memory.as_ref().map_or(0, Vec::len);
+        // Each storage entry is a key-value pair.
+        let storage_words = self.storage.as_ref().map_or(0, |storage| storage.len() * 2);
+        mem::size_of::<Self>()
+            + self.op.len()
+            + (stack_words + memory_words + storage_words) * WORD_SIZE
+    }
+}
+
+/// Configuration of [`StructLogger`].
+#[derive(Debug, Clone)]
+pub struct StructLoggerConfig {
+    /// Do not record VM registers.
+    pub disable_stack: bool,
+    /// Do not record accessed storage slots.
+    pub disable_storage: bool,
+    /// Record the heap of the current frame.
+    pub enable_memory: bool,
+    /// Maximum number of heap words recorded for a single instruction.
+    pub memory_limit: usize,
+    /// Maximum number of recorded instructions. 0 means no limit.
+    pub limit: usize,
+    /// Maximum total size of recorded data in bytes. Once the next instruction would exceed this size,
+    /// recording stops. 0 means no limit.
+    pub size_limit: usize,
+}
+
+impl Default for StructLoggerConfig {
+    fn default() -> Self {
+        Self {
+            disable_stack: false,
+            disable_storage: false,
+            enable_memory: false,
+            memory_limit: 1_024,
+            limit: 0,
+            size_limit: 0,
+        }
+    }
+}
+
+/// Tracer recording every instruction executed outside the bootloader, similar to the Geth struct logger.
+///
+/// Only the legacy `vm_latest` is supported; for older VM versions, the tracer is a no-op and doesn't set
+/// its result. Use [`Self::is_supported()`] to check support before execution.
+// TODO: support the fast VM. The pinned `vm2` revision has no per-instruction tracing hooks
+//  (`vm_fast::Vm::TracerDispatcher` is `()`), so this requires updating `vm2` first.
+#[derive(Debug, Clone)]
+pub struct StructLogger {
+    config: StructLoggerConfig,
+    logs: Vec<StructLog>,
+    /// Estimated total size of `logs` in bytes.
+    logs_size: usize,
+    /// Set once an instruction is dropped because of the size limit; no instructions are recorded afterwards.
+    size_limit_reached: bool,
+    /// Whether the gas cost of the last recorded instruction is not yet computed.
+    last_log_pending: bool,
+    accessed_storage: HashMap<Address, BTreeMap<H256, H256>>,
+    result: Arc<OnceCell<Vec<StructLog>>>,
+}
+
+impl StructLogger {
+    pub fn new(config: StructLoggerConfig, result: Arc<OnceCell<Vec<StructLog>>>) -> Self {
+        Self {
+            config,
+            logs: vec![],
+            logs_size: 0,
+            size_limit_reached: false,
+            last_log_pending: false,
+            accessed_storage: HashMap::new(),
+            result,
+        }
+    }
+
+    /// Checks whether the tracer records instructions for the specified (legacy) VM version.
+    pub fn is_supported(vm_version: VmVersion) -> bool {
+        matches!(
+            vm_version,
+            VmVersion::Vm1_5_0SmallBootloaderMemory | VmVersion::Vm1_5_0IncreasedBootloaderMemory
+        )
+    }
+
+    fn is_full(&self) -> bool {
+        self.size_limit_reached || (self.config.limit != 0 && self.logs.len() >= self.config.limit)
+    }
+
+    fn push_log(&mut self, log: StructLog) {
+        let log_size = log.estimated_size();
+        if self.config.size_limit != 0 && self.logs_size + log_size > self.config.size_limit {
+            self.size_limit_reached = true;
+            return;
+        }
+        self.logs_size += log_size;
+        self.logs.push(log);
+        self.last_log_pending = true;
+    }
+
+    /// Records access to a storage slot and returns all slots of the contract accessed so far.
+    fn record_storage_access(
+        &mut self,
+        address: Address,
+        key: U256,
+        value: H256,
+    ) -> Option<BTreeMap<H256, H256>> {
+        if self.config.disable_storage {
+            return None;
+        }
+        let storage = self.accessed_storage.entry(address).or_default();
+        storage.insert(u256_to_h256(key), value);
+        Some(storage.clone())
+    }
+
+    /// Sets the gas cost of the last recorded instruction based on the gas remaining after its execution.
+    fn finish_last_log(&mut self, gas_after: u32) {
+        if !std::mem::take(&mut self.last_log_pending) {
+            return;
+        }
+        if let Some(log) = self.logs.last_mut() {
+            log.gas_cost = log.gas.saturating_sub(gas_after);
+        }
+    }
+
+    fn store_result(&mut self) {
+        let logs = std::mem::take(&mut self.logs);
+        self.result.set(logs).unwrap();
+    }
+}
+
+/// Struct logger is not supported for VM versions before `vm_latest`, so the result will not be set for them.
+impl IntoOldVmTracer for StructLogger {}
@@ -0,0 +1,11 @@
+//! Instruction-level tracing is only implemented for the latest VM version; this VM version records nothing.
+
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_4_0::DynTracer, StructLogger},
+    vm_boojum_integration::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogger {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogger {}
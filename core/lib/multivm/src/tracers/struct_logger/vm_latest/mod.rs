@@ -0,0 +1,99 @@
+use zk_evm_1_5_0::{
+    tracing::{AfterExecutionData, BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
+use zksync_system_constants::BOOTLOADER_ADDRESS;
+use zksync_types::{AccountTreeId, StorageKey};
+use zksync_utils::u256_to_h256;
+
+use super::{StructLog, StructLogger};
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::VmExecutionStopReason,
+    },
+    tracers::dynamic::vm_1_5_0::DynTracer,
+    vm_latest::{
+        old_vm::utils::heap_page_from_base, BootloaderState, HistoryMode, SimpleMemory, VmTracer,
+        ZkSyncVmState,
+    },
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogger {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        let current = state.vm_local_state.callstack.current;
+        if current.code_address == BOOTLOADER_ADDRESS || self.is_full() {
+            return;
+        }
+
+        let stack = (!self.config.disable_stack).then(|| {
+            state
+                .vm_local_state
+                .registers
+                .iter()
+                .map(|register| register.value)
+                .collect()
+        });
+        let memory = self.config.enable_memory.then(|| {
+            let heap_page = heap_page_from_base(current.base_memory_page).0;
+            let heap_words = (current.heap_bound as usize).div_ceil(32);
+            let recorded_words = heap_words.min(self.config.memory_limit) as u32;
+            memory.dump_page_content_as_u256_words(heap_page, 0..recorded_words)
+        });
+
+        let opcode = data.opcode.variant.opcode;
+        let storage = match opcode {
+            Opcode::Log(LogOpcode::StorageRead) => {
+                let key = data.src0_value.value;
+                let storage_key =
+                    StorageKey::new(AccountTreeId::new(current.this_address), u256_to_h256(key));
+                let value = storage.borrow_mut().read_value(&storage_key);
+                self.record_storage_access(current.this_address, key, value)
+            }
+            Opcode::Log(LogOpcode::StorageWrite) => {
+                let key = data.src0_value.value;
+                let value = u256_to_h256(data.src1_value.value);
+                self.record_storage_access(current.this_address, key, value)
+            }
+            _ => None,
+        };
+
+        self.push_log(StructLog {
+            pc: current.pc,
+            op: format!("{opcode:?}"),
+            gas: current.ergs_remaining,
+            gas_cost: 0,
+            depth: state.vm_local_state.callstack.depth(),
+            stack,
+            memory,
+            storage,
+        });
+    }
+
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        _data: AfterExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        self.finish_last_log(state.vm_local_state.callstack.current.ergs_remaining);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogger {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result();
+    }
+}
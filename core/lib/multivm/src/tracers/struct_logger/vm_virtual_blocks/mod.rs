@@ -0,0 +1,17 @@
+//! Instruction-level tracing is only implemented for the latest VM version; this VM version records nothing.
+
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_3_3::DynTracer, StructLogger},
+    vm_virtual_blocks::{
+        ExecutionEndTracer, ExecutionProcessing, HistoryMode, SimpleMemory, VmTracer,
+    },
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogger {}
+
+impl<H: HistoryMode> ExecutionEndTracer<H> for StructLogger {}
+
+impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for StructLogger {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogger {}
@@ -20,7 +20,7 @@ pub use self::{
 mod bootloader_state;
 pub mod constants;
 mod implementation;
-mod old_vm;
+pub(crate) mod old_vm;
 mod oracles;
 #[cfg(test)]
 mod tests;
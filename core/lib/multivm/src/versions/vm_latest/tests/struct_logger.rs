@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_types::{Address, Execute};
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::{StructLog, StructLogger, StructLoggerConfig},
+    vm_latest::{
+        constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+        tests::{tester::VmTesterBuilder, utils::read_test_contract},
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+#[test]
+fn test_struct_logger() {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let config = StructLoggerConfig {
+        enable_memory: true,
+        memory_limit: 4,
+        ..StructLoggerConfig::default()
+    };
+    let tracer = StructLogger::new(config, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let logs = result.get().unwrap();
+    assert!(!logs.is_empty());
+    for log in logs {
+        assert_eq!(log.stack.as_ref().unwrap().len(), 15);
+        assert!(log.memory.as_ref().unwrap().len() <= 4);
+        assert!(log.gas_cost <= log.gas);
+    }
+    // The test contract writes the incremented value to its storage.
+    let storage_write = logs
+        .iter()
+        .find(|log| log.op.contains("StorageWrite") && log.storage.is_some())
+        .expect("no storage writes recorded");
+    assert!(!storage_write.storage.as_ref().unwrap().is_empty());
+}
+
+#[test]
+fn test_struct_logger_limit() {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: vec![],
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let config = StructLoggerConfig {
+        disable_stack: true,
+        limit: 10,
+        ..StructLoggerConfig::default()
+    };
+    let tracer = StructLogger::new(config, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+
+    let logs = result.get().unwrap();
+    assert_eq!(logs.len(), 10);
+    assert!(logs
+        .iter()
+        .all(|log| log.stack.is_none() && log.memory.is_none()));
+}
+
+#[test]
+fn test_struct_logger_size_limit() {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: vec![],
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    // Each recorded instruction contains at least 15 registers, i.e. 480 bytes.
+    let size_limit = 5_000;
+    let config = StructLoggerConfig {
+        size_limit,
+        ..StructLoggerConfig::default()
+    };
+    let tracer = StructLogger::new(config, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+
+    let logs = result.get().unwrap();
+    assert!(!logs.is_empty());
+    assert!(logs.len() < size_limit / 480);
+    let logs_size: usize = logs.iter().map(StructLog::estimated_size).sum();
+    assert!(logs_size <= size_limit, "{logs_size}");
+}
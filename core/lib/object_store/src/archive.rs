@@ -0,0 +1,490 @@
+//! Portable single-file archives of objects.
+//!
+//! An archive has the following layout:
+//!
+//! - 8-byte magic
+//! - Raw object bytes concatenated in the order they were appended
+//! - JSON-encoded [`ArchiveManifest`] listing all objects with their locations and SHA-256 hashes
+//! - 24-byte footer: big-endian `u64` manifest offset, big-endian `u64` manifest length, and the magic again
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::SeekFrom,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::{
+    fs,
+    io::{self, AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+};
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+
+const MAGIC: &[u8; 8] = b"ZKOBJAR1";
+const FOOTER_LEN: u64 = 24;
+const MANIFEST_VERSION: u32 = 1;
+
+/// Information about a single object in an archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedObject {
+    /// Bucket the object belongs to.
+    pub bucket: String,
+    /// Object key in the bucket.
+    pub key: String,
+    /// Offset of the object bytes from the start of the archive.
+    pub offset: u64,
+    /// Size of the object in bytes.
+    pub size: u64,
+    /// Hex-encoded SHA-256 digest of the object bytes.
+    pub sha256: String,
+}
+
+/// Archive manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    /// Version of the archive format.
+    pub version: u32,
+    /// Application-specific metadata provided when the archive was created.
+    pub metadata: serde_json::Value,
+    /// Objects in the archive.
+    pub objects: Vec<ArchivedObject>,
+}
+
+fn other_error(message: String) -> ObjectStoreError {
+    ObjectStoreError::Other {
+        is_retriable: false,
+        source: message.into(),
+    }
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Writer of object archives that can be read by [`ArchiveObjectStore`].
+#[derive(Debug)]
+pub struct ObjectStoreArchiveWriter {
+    path: PathBuf,
+    file: io::BufWriter<fs::File>,
+    position: u64,
+    objects: Vec<ArchivedObject>,
+    object_keys: HashSet<(String, String)>,
+}
+
+impl ObjectStoreArchiveWriter {
+    /// Creates a new archive at the specified path. If the file already exists, it is truncated.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors.
+    pub async fn create(path: impl Into<PathBuf>) -> Result<Self, ObjectStoreError> {
+        let path = path.into();
+        let mut file = io::BufWriter::new(fs::File::create(&path).await?);
+        file.write_all(MAGIC).await?;
+        Ok(Self {
+            path,
+            file,
+            position: MAGIC.len() as u64,
+            objects: vec![],
+            object_keys: HashSet::new(),
+        })
+    }
+
+    /// Appends an object to the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object with the same bucket and key is already archived, or on I/O errors.
+    pub async fn append(
+        &mut self,
+        bucket: Bucket,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), ObjectStoreError> {
+        if !self
+            .object_keys
+            .insert((bucket.to_string(), key.to_owned()))
+        {
+            return Err(other_error(format!(
+                "object `{key}` in bucket `{bucket}` is already archived"
+            )));
+        }
+
+        self.file.write_all(value).await?;
+        self.objects.push(ArchivedObject {
+            bucket: bucket.to_string(),
+            key: key.to_owned(),
+            offset: self.position,
+            size: value.len() as u64,
+            sha256: hex_digest(value),
+        });
+        self.position += value.len() as u64;
+        Ok(())
+    }
+
+    /// Copies an object from the specified store into the archive without deserializing it.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors fetching the object from the store and writing it to the archive.
+    pub async fn copy_from(
+        &mut self,
+        store: &dyn ObjectStore,
+        bucket: Bucket,
+        key: &str,
+    ) -> Result<(), ObjectStoreError> {
+        let value = store.get_raw(bucket, key).await?;
+        self.append(bucket, key, &value).await
+    }
+
+    /// Writes the manifest with the provided metadata and finalizes the archive.
+    ///
+    /// # Errors
+    ///
+    /// Propagates I/O errors.
+    pub async fn finish(
+        mut self,
+        metadata: serde_json::Value,
+    ) -> Result<ArchiveManifest, ObjectStoreError> {
+        let manifest = ArchiveManifest {
+            version: MANIFEST_VERSION,
+            metadata,
+            objects: self.objects,
+        };
+        let manifest_bytes = serde_json::to_vec(&manifest)
+            .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+
+        self.file.write_all(&manifest_bytes).await?;
+        self.file.write_all(&self.position.to_be_bytes()).await?;
+        self.file
+            .write_all(&(manifest_bytes.len() as u64).to_be_bytes())
+            .await?;
+        self.file.write_all(MAGIC).await?;
+        self.file.flush().await?;
+        self.file.get_ref().sync_all().await?;
+        tracing::info!(
+            "Finished archive at `{}` with {} objects",
+            self.path.display(),
+            manifest.objects.len()
+        );
+        Ok(manifest)
+    }
+}
+
+/// Read-only [`ObjectStore`] backed by an archive created by [`ObjectStoreArchiveWriter`].
+///
+/// Integrity of each object is checked against the SHA-256 digest from the archive manifest when the object is read.
+/// Attempts to modify the store result in an error.
+#[derive(Debug)]
+pub struct ArchiveObjectStore {
+    path: PathBuf,
+    metadata: serde_json::Value,
+    objects: HashMap<String, ArchivedObject>,
+}
+
+impl ArchiveObjectStore {
+    fn object_id(bucket: &str, key: &str) -> String {
+        format!("{bucket}/{key}")
+    }
+
+    /// Opens an archive at the specified path and reads its manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is not a valid archive, or on I/O errors.
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, ObjectStoreError> {
+        let path = path.into();
+        let manifest =
+            Self::read_manifest(&path)
+                .await
+                .map_err(|err| ObjectStoreError::Initialization {
+                    source: format!("failed reading archive `{}`: {err}", path.display()).into(),
+                    is_retriable: false,
+                })?;
+        if manifest.version != MANIFEST_VERSION {
+            return Err(ObjectStoreError::Initialization {
+                source: format!(
+                    "unsupported archive version {}, expected {MANIFEST_VERSION}",
+                    manifest.version
+                )
+                .into(),
+                is_retriable: false,
+            });
+        }
+
+        let objects = manifest
+            .objects
+            .into_iter()
+            .map(|object| (Self::object_id(&object.bucket, &object.key), object))
+            .collect();
+        Ok(Self {
+            path,
+            metadata: manifest.metadata,
+            objects,
+        })
+    }
+
+    async fn read_manifest(path: &Path) -> Result<ArchiveManifest, ObjectStoreError> {
+        let mut file = fs::File::open(path).await?;
+        let file_len = file.metadata().await?.len();
+        if file_len < MAGIC.len() as u64 + FOOTER_LEN {
+            return Err(other_error("file is too short".to_owned()));
+        }
+
+        let mut magic = [0_u8; 8];
+        file.read_exact(&mut magic).await?;
+        if magic != *MAGIC {
+            return Err(other_error("invalid magic at the file start".to_owned()));
+        }
+
+        file.seek(SeekFrom::Start(file_len - FOOTER_LEN)).await?;
+        let mut footer = [0_u8; FOOTER_LEN as usize];
+        file.read_exact(&mut footer).await?;
+        if footer[16..] != *MAGIC {
+            return Err(other_error("invalid magic at the file end".to_owned()));
+        }
+        let manifest_offset = u64::from_be_bytes(footer[..8].try_into().unwrap());
+        let manifest_len = u64::from_be_bytes(footer[8..16].try_into().unwrap());
+        if manifest_offset.checked_add(manifest_len) != Some(file_len - FOOTER_LEN) {
+            return Err(other_error("invalid manifest location".to_owned()));
+        }
+
+        file.seek(SeekFrom::Start(manifest_offset)).await?;
+        let mut manifest_bytes = vec![0_u8; usize::try_from(manifest_len).unwrap()];
+        file.read_exact(&mut manifest_bytes).await?;
+        let manifest: ArchiveManifest = serde_json::from_slice(&manifest_bytes)
+            .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+
+        // Objects must be located between the starting magic and the manifest.
+        for object in &manifest.objects {
+            let object_end = object.offset.checked_add(object.size);
+            let is_valid = object.offset >= MAGIC.len() as u64
+                && object_end.is_some_and(|end| end <= manifest_offset);
+            if !is_valid {
+                return Err(other_error(format!(
+                    "invalid location of object `{}` in bucket `{}`: offset {}, size {}",
+                    object.key, object.bucket, object.offset, object.size
+                )));
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Returns application-specific metadata stored in the archive manifest.
+    pub fn metadata(&self) -> &serde_json::Value {
+        &self.metadata
+    }
+
+    /// Returns the number of objects in the archive.
+    pub fn object_count(&self) -> usize {
+        self.objects.len()
+    }
+}
+
+#[async_trait]
+impl ObjectStore for ArchiveObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let object_id = Self::object_id(bucket.as_str(), key);
+        let Some(object) = self.objects.get(&object_id) else {
+            return Err(ObjectStoreError::KeyNotFound(
+                format!("object `{key}` in bucket `{bucket}` is not archived").into(),
+            ));
+        };
+
+        let mut file = fs::File::open(&self.path).await?;
+        file.seek(SeekFrom::Start(object.offset)).await?;
+        let mut value = vec![0_u8; usize::try_from(object.size).unwrap()];
+        file.read_exact(&mut value).await?;
+
+        let actual_digest = hex_digest(&value);
+        if actual_digest != object.sha256 {
+            return Err(other_error(format!(
+                "SHA-256 digest mismatch for object `{key}` in bucket `{bucket}`: expected {}, got {actual_digest}",
+                object.sha256
+            )));
+        }
+        Ok(value)
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        _value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        Err(other_error(format!(
+            "cannot put object `{key}` into bucket `{bucket}`: archive object store is read-only"
+        )))
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        Err(other_error(format!(
+            "cannot remove object `{key}` from bucket `{bucket}`: archive object store is read-only"
+        )))
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!("{}#{bucket}", self.path.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    #[tokio::test]
+    async fn archive_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.archive");
+        let source_store = MockObjectStore::arc();
+        source_store
+            .put_raw(Bucket::StorageSnapshot, "chunk-0", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        let mut writer = ObjectStoreArchiveWriter::create(&path).await.unwrap();
+        writer
+            .copy_from(&*source_store, Bucket::StorageSnapshot, "chunk-0")
+            .await
+            .unwrap();
+        writer
+            .append(Bucket::StorageSnapshot, "chunk-1", &[])
+            .await
+            .unwrap();
+        writer
+            .append(Bucket::ProofsFri, "chunk-0", &[4, 5])
+            .await
+            .unwrap();
+        let err = writer
+            .append(Bucket::ProofsFri, "chunk-0", &[6])
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::Other { .. });
+        let manifest = writer
+            .finish(serde_json::json!({ "test": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(manifest.objects.len(), 3);
+
+        let store = ArchiveObjectStore::open(&path).await.unwrap();
+        assert_eq!(store.object_count(), 3);
+        assert_eq!(*store.metadata(), serde_json::json!({ "test": 1 }));
+        let value = store
+            .get_raw(Bucket::StorageSnapshot, "chunk-0")
+            .await
+            .unwrap();
+        assert_eq!(value, [1, 2, 3]);
+        let value = store
+            .get_raw(Bucket::StorageSnapshot, "chunk-1")
+            .await
+            .unwrap();
+        assert_eq!(value, [] as [u8; 0]);
+        let value = store.get_raw(Bucket::ProofsFri, "chunk-0").await.unwrap();
+        assert_eq!(value, [4, 5]);
+
+        let err = store
+            .get_raw(Bucket::StorageSnapshot, "chunk-2")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+        let err = store
+            .put_raw(Bucket::StorageSnapshot, "chunk-2", vec![])
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::Other { .. });
+    }
+
+    #[tokio::test]
+    async fn corrupted_archive_object_is_detected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.archive");
+        let mut writer = ObjectStoreArchiveWriter::create(&path).await.unwrap();
+        writer
+            .append(Bucket::StorageSnapshot, "chunk-0", &[1, 2, 3])
+            .await
+            .unwrap();
+        writer.finish(serde_json::Value::Null).await.unwrap();
+
+        let mut bytes = fs::read(&path).await.unwrap();
+        bytes[MAGIC.len()] ^= 1;
+        fs::write(&path, bytes).await.unwrap();
+
+        let store = ArchiveObjectStore::open(&path).await.unwrap();
+        let err = store
+            .get_raw(Bucket::StorageSnapshot, "chunk-0")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("digest mismatch"), "{err}");
+    }
+
+    async fn write_archive(path: &Path, objects_bytes: &[u8], manifest: &ArchiveManifest) {
+        let manifest_bytes = serde_json::to_vec(manifest).unwrap();
+        let manifest_offset = (MAGIC.len() + objects_bytes.len()) as u64;
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(objects_bytes);
+        bytes.extend_from_slice(&manifest_bytes);
+        bytes.extend_from_slice(&manifest_offset.to_be_bytes());
+        bytes.extend_from_slice(&(manifest_bytes.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(MAGIC);
+        fs::write(path, bytes).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn archive_with_out_of_bounds_objects_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.archive");
+        let object_bytes = [1, 2, 3];
+        let valid_object = ArchivedObject {
+            bucket: Bucket::StorageSnapshot.to_string(),
+            key: "chunk-0".to_owned(),
+            offset: MAGIC.len() as u64,
+            size: 3,
+            sha256: hex_digest(&object_bytes),
+        };
+        let mut manifest = ArchiveManifest {
+            version: MANIFEST_VERSION,
+            metadata: serde_json::Value::Null,
+            objects: vec![valid_object.clone()],
+        };
+        write_archive(&path, &object_bytes, &manifest).await;
+        ArchiveObjectStore::open(&path).await.unwrap();
+
+        let invalid_locations = [
+            (0, 3),                         // overlaps with the starting magic
+            (MAGIC.len() as u64 + 1, 3),    // overlaps with the manifest
+            (MAGIC.len() as u64, 1_000),    // extends past the end of the file
+            (MAGIC.len() as u64, u64::MAX), // overflows
+            (u64::MAX, 0),                  // located past the end of the file
+        ];
+        for (offset, size) in invalid_locations {
+            manifest.objects = vec![ArchivedObject {
+                offset,
+                size,
+                ..valid_object.clone()
+            }];
+            write_archive(&path, &object_bytes, &manifest).await;
+            let err = ArchiveObjectStore::open(&path).await.unwrap_err();
+            assert_matches!(err, ObjectStoreError::Initialization { .. });
+            assert!(err.to_string().contains("invalid location"), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn opening_invalid_archive() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.archive");
+        fs::write(&path, b"not an archive at all, definitely not")
+            .await
+            .unwrap();
+
+        let err = ArchiveObjectStore::open(&path).await.unwrap_err();
+        assert_matches!(err, ObjectStoreError::Initialization { .. });
+    }
+}
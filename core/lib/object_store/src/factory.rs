@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use anyhow::Context as _;
 use tokio::sync::OnceCell;
-use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
+use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode, S3AddressingStyle};
 
 use crate::{
     file::FileBackedObjectStore,
@@ -10,6 +10,7 @@ use crate::{
     mirror::MirroringObjectStore,
     raw::{ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
+    s3::{S3BucketLocation, S3Store, S3StoreAuthMode},
 };
 
 /// Factory of [`ObjectStore`]s that caches the store instance once it's created. Used mainly for legacy reasons.
@@ -97,6 +98,39 @@ impl ObjectStoreFactory {
                 .await?;
                 Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
             }
+            ObjectStoreMode::S3 {
+                bucket_name,
+                region,
+                endpoint,
+                addressing_style,
+                s3_credential_file_path,
+            } => {
+                let auth_mode = match s3_credential_file_path {
+                    Some(path) => S3StoreAuthMode::AuthenticatedWithCredentialFile(path.clone()),
+                    None => S3StoreAuthMode::Authenticated,
+                };
+                let location =
+                    Self::s3_location(bucket_name, region, endpoint.as_ref(), *addressing_style);
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    S3Store::new(auth_mode.clone(), location.clone())
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+            }
+            ObjectStoreMode::S3AnonymousReadOnly {
+                bucket_name,
+                region,
+                endpoint,
+                addressing_style,
+            } => {
+                let location =
+                    Self::s3_location(bucket_name, region, endpoint.as_ref(), *addressing_style);
+                let store = StoreWithRetries::try_new(config.max_retries, || {
+                    S3Store::new(S3StoreAuthMode::Anonymous, location.clone())
+                })
+                .await?;
+                Self::wrap_mirroring(store, config.local_mirror_path.as_ref()).await
+            }
 
             ObjectStoreMode::FileBacked {
                 file_backed_base_path,
@@ -114,6 +148,26 @@ impl ObjectStoreFactory {
         }
     }
 
+    fn s3_location(
+        bucket_name: &str,
+        region: &str,
+        endpoint: Option<&String>,
+        addressing_style: Option<S3AddressingStyle>,
+    ) -> S3BucketLocation {
+        // Custom endpoints are usually S3-compatible stores (e.g., MinIO), which work best with path-style addressing.
+        let addressing_style = addressing_style.unwrap_or(if endpoint.is_some() {
+            S3AddressingStyle::Path
+        } else {
+            S3AddressingStyle::VirtualHosted
+        });
+        S3BucketLocation {
+            bucket_name: bucket_name.to_owned(),
+            region: region.to_owned(),
+            endpoint: endpoint.cloned(),
+            path_style: matches!(addressing_style, S3AddressingStyle::Path),
+        }
+    }
+
     async fn wrap_mirroring(
         store: impl ObjectStore,
         mirror_path: Option<&String>,
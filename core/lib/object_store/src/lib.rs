@@ -3,7 +3,10 @@
 //!
 //! - [File-backed store](FileBackedObjectStore) saving blobs as separate files in the local filesystem
 //! - [GCS-based store](GoogleCloudStore)
+//! - [S3-based store](S3Store), which also works with S3-compatible stores such as MinIO
 //! - [Mock in-memory store](MockObjectStore)
+//! - [Read-only archive-backed store](ArchiveObjectStore) reading blobs from a single portable file
+//!   created by [`ObjectStoreArchiveWriter`]
 //!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
@@ -23,6 +26,7 @@
     clippy::doc_markdown
 )]
 
+mod archive;
 mod factory;
 mod file;
 mod gcs;
@@ -32,6 +36,7 @@ mod mock;
 mod objects;
 mod raw;
 mod retries;
+mod s3;
 
 // Re-export `bincode` crate so that client binaries can conveniently use it.
 pub use bincode;
@@ -42,10 +47,12 @@ pub mod _reexports {
 }
 
 pub use self::{
+    archive::{ArchiveManifest, ArchiveObjectStore, ArchivedObject, ObjectStoreArchiveWriter},
     factory::ObjectStoreFactory,
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mock::MockObjectStore,
     objects::StoredObject,
     raw::{Bucket, ObjectStore, ObjectStoreError},
+    s3::{S3BucketLocation, S3Store, S3StoreAuthMode},
 };
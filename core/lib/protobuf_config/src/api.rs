@@ -1,4 +1,7 @@
-use std::num::NonZeroUsize;
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    path::PathBuf,
+};
 
 use anyhow::Context as _;
 use zksync_config::configs::{api, ApiConfig};
@@ -56,6 +59,21 @@ impl ProtoRepr for proto::Web3JsonRpc {
             })
             .collect::<anyhow::Result<_>>()
             .context("max_response_body_size_overrides")?;
+        let rpc_method_costs = self
+            .rpc_method_costs
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let method = required(&entry.method)
+                    .with_context(|| format!("[{i}].method"))?
+                    .clone();
+                let cost = required(&entry.cost)
+                    .and_then(|&cost| NonZeroU32::new(cost).context("cost is zero"))
+                    .with_context(|| format!("[{i}].cost"))?;
+                Ok((method, cost))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("rpc_method_costs")?;
         let api_namespaces = if self.api_namespaces.is_empty() {
             None
         } else {
@@ -113,6 +131,21 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .transpose()
                 .context("latest_values_cache_size_mb")?,
             fee_history_limit: self.fee_history_limit,
+            struct_logger_memory_limit: self
+                .struct_logger_memory_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("struct_logger_memory_limit")?,
+            struct_logger_step_limit: self
+                .struct_logger_step_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("struct_logger_step_limit")?,
+            struct_logger_size_limit_mb: self
+                .struct_logger_size_limit_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("struct_logger_size_limit_mb")?,
             max_batch_request_size: self
                 .max_batch_request_size
                 .map(|x| x.try_into())
@@ -129,6 +162,28 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("websocket_requests_per_minute_limit")?,
+            http_requests_per_minute_per_ip: self
+                .http_requests_per_minute_per_ip
+                .map(|x| x.try_into())
+                .transpose()
+                .context("http_requests_per_minute_per_ip")?,
+            http_requests_per_minute_per_api_key: self
+                .http_requests_per_minute_per_api_key
+                .map(|x| x.try_into())
+                .transpose()
+                .context("http_requests_per_minute_per_api_key")?,
+            http_api_keys: self.http_api_keys.clone(),
+            http_api_key_header: self.http_api_key_header.clone(),
+            http_api_key_path_prefix: self.http_api_key_path_prefix.clone(),
+            http_client_ip_header: self.http_client_ip_header.clone(),
+            http_trusted_proxies: self
+                .http_trusted_proxies
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| addr.parse().with_context(|| format!("[{i}]")))
+                .collect::<anyhow::Result<_>>()
+                .context("http_trusted_proxies")?,
+            rpc_method_costs,
             tree_api_url: self.tree_api_url.clone(),
             mempool_cache_update_interval: self.mempool_cache_update_interval,
             mempool_cache_size: self
@@ -182,6 +237,13 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .latest_values_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
             fee_history_limit: this.fee_history_limit,
+            struct_logger_memory_limit: this
+                .struct_logger_memory_limit
+                .map(|x| x.try_into().unwrap()),
+            struct_logger_step_limit: this.struct_logger_step_limit.map(|x| x.try_into().unwrap()),
+            struct_logger_size_limit_mb: this
+                .struct_logger_size_limit_mb
+                .map(|x| x.try_into().unwrap()),
             max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
             max_response_body_size_mb: this
                 .max_response_body_size_mb
@@ -201,6 +263,27 @@ impl ProtoRepr for proto::Web3JsonRpc {
             websocket_requests_per_minute_limit: this
                 .websocket_requests_per_minute_limit
                 .map(|x| x.into()),
+            http_requests_per_minute_per_ip: this.http_requests_per_minute_per_ip.map(|x| x.into()),
+            http_requests_per_minute_per_api_key: this
+                .http_requests_per_minute_per_api_key
+                .map(|x| x.into()),
+            http_api_keys: this.http_api_keys.clone(),
+            http_api_key_header: this.http_api_key_header.clone(),
+            http_api_key_path_prefix: this.http_api_key_path_prefix.clone(),
+            http_client_ip_header: this.http_client_ip_header.clone(),
+            http_trusted_proxies: this
+                .http_trusted_proxies
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            rpc_method_costs: this
+                .rpc_method_costs
+                .iter()
+                .map(|(method, cost)| proto::RpcMethodCost {
+                    method: Some(method.to_owned()),
+                    cost: Some(cost.get()),
+                })
+                .collect(),
             tree_api_url: this.tree_api_url.clone(),
             whitelisted_tokens_for_aa: this
                 .whitelisted_tokens_for_aa
@@ -242,11 +325,16 @@ impl ProtoRepr for proto::MerkleTreeApi {
             port: required(&self.port)
                 .and_then(|p| Ok((*p).try_into()?))
                 .context("port")?,
+            checkpoints_dir: self.checkpoints_dir.as_ref().map(PathBuf::from),
         })
     }
     fn build(this: &Self::Type) -> Self {
         Self {
             port: Some(this.port.into()),
+            checkpoints_dir: this
+                .checkpoints_dir
+                .as_ref()
+                .map(|dir| dir.to_string_lossy().into_owned()),
         }
     }
 }
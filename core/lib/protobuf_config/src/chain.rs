@@ -22,11 +22,29 @@ impl proto::FeeModelVersion {
     }
 }
 
+impl proto::MempoolOrderingPolicy {
+    fn new(n: &configs::chain::MempoolOrderingPolicy) -> Self {
+        use configs::chain::MempoolOrderingPolicy as From;
+        match n {
+            From::Fifo => Self::Fifo,
+            From::PriorityFee => Self::PriorityFee,
+        }
+    }
+
+    fn parse(&self) -> configs::chain::MempoolOrderingPolicy {
+        use configs::chain::MempoolOrderingPolicy as To;
+        match self {
+            Self::Fifo => To::Fifo,
+            Self::PriorityFee => To::PriorityFee,
+        }
+    }
+}
+
 impl ProtoRepr for proto::StateKeeper {
     type Type = configs::chain::StateKeeperConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
         #[allow(deprecated)]
-        Ok(Self::Type {
+        let config = Self::Type {
             transaction_slots: required(&self.transaction_slots)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("transaction_slots")?,
@@ -82,6 +100,9 @@ impl ProtoRepr for proto::StateKeeper {
                 &self.protective_reads_persistence_enabled,
             )
             .context("protective_reads_persistence_enabled")?,
+            max_l1_batch_execution_time_ms: self.max_l1_batch_execution_time_ms,
+            max_l2_block_execution_time_ms: self.max_l2_block_execution_time_ms,
+            max_contract_gas_share: self.max_contract_gas_share,
 
             // We need these values only for instantiating configs from environmental variables, so it's not
             // needed during the initialization from files
@@ -89,7 +110,9 @@ impl ProtoRepr for proto::StateKeeper {
             default_aa_hash: None,
             fee_account_addr: None,
             l1_batch_commit_data_generator_mode: Default::default(),
-        })
+        };
+        config.validate()?;
+        Ok(config)
     }
 
     fn build(this: &Self::Type) -> Self {
@@ -120,6 +143,9 @@ impl ProtoRepr for proto::StateKeeper {
             save_call_traces: Some(this.save_call_traces),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
             protective_reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
+            max_l1_batch_execution_time_ms: this.max_l1_batch_execution_time_ms,
+            max_l2_block_execution_time_ms: this.max_l2_block_execution_time_ms,
+            max_contract_gas_share: this.max_contract_gas_share,
         }
     }
 }
@@ -129,12 +155,24 @@ impl ProtoRepr for proto::OperationsManager {
     fn read(&self) -> anyhow::Result<Self::Type> {
         Ok(Self::Type {
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            ordering_policy: self
+                .ordering_policy
+                .map(proto::MempoolOrderingPolicy::try_from)
+                .transpose()
+                .context("ordering_policy")?
+                .map(|policy| policy.parse())
+                .unwrap_or_default(),
+            min_replacement_fee_bump_percent: self.min_replacement_fee_bump_percent,
+            evict_cheapest_on_capacity: self.evict_cheapest_on_capacity.unwrap_or(false),
         })
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             delay_interval: Some(this.delay_interval),
+            ordering_policy: Some(proto::MempoolOrderingPolicy::new(&this.ordering_policy).into()),
+            min_replacement_fee_bump_percent: this.min_replacement_fee_bump_percent,
+            evict_cheapest_on_capacity: Some(this.evict_cheapest_on_capacity),
         }
     }
 }
@@ -1,5 +1,6 @@
+use anyhow::Context as _;
 use zksync_config::configs::{self};
-use zksync_protobuf::ProtoRepr;
+use zksync_protobuf::{required, ProtoRepr};
 
 use crate::proto::da_dispatcher as proto;
 
@@ -11,6 +12,12 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
             polling_interval_ms: self.polling_interval_ms,
             max_rows_to_dispatch: self.max_rows_to_dispatch,
             max_retries: self.max_retries.map(|x| x as u16),
+            celestia: self
+                .celestia
+                .as_ref()
+                .map(ProtoRepr::read)
+                .transpose()
+                .context("celestia")?,
         })
     }
 
@@ -19,6 +26,40 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
             polling_interval_ms: this.polling_interval_ms,
             max_rows_to_dispatch: this.max_rows_to_dispatch,
             max_retries: this.max_retries.map(Into::into),
+            celestia: this.celestia.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
+
+impl ProtoRepr for proto::Celestia {
+    type Type = configs::da_dispatcher::CelestiaConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            api_node_url: required(&self.api_node_url)
+                .context("api_node_url")?
+                .clone(),
+            namespace: required(&self.namespace).context("namespace")?.clone(),
+            gas_price: self.gas_price,
+            request_timeout_ms: self
+                .request_timeout_ms
+                .unwrap_or_else(Self::Type::default_request_timeout_ms),
+            blob_size_limit: self
+                .blob_size_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("blob_size_limit")?
+                .unwrap_or_else(Self::Type::default_blob_size_limit),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            api_node_url: Some(this.api_node_url.clone()),
+            namespace: Some(this.namespace.clone()),
+            gas_price: this.gas_price,
+            request_timeout_ms: Some(this.request_timeout_ms),
+            blob_size_limit: Some(this.blob_size_limit.try_into().unwrap()),
         }
     }
 }
@@ -109,6 +109,8 @@ impl ProtoRepr for proto::Postgres {
             statement_timeout_sec: self.statement_timeout_sec,
             long_connection_threshold_ms: self.long_connection_threshold_ms,
             slow_query_threshold_ms: self.slow_query_threshold_ms,
+            max_replica_lag_sec: self.max_replica_lag_sec,
+            replica_health_check_interval_ms: self.replica_health_check_interval_ms,
             test_server_url,
             test_prover_url,
         })
@@ -122,6 +124,8 @@ impl ProtoRepr for proto::Postgres {
             statement_timeout_sec: this.statement_timeout_sec,
             long_connection_threshold_ms: this.long_connection_threshold_ms,
             slow_query_threshold_ms: this.slow_query_threshold_ms,
+            max_replica_lag_sec: this.max_replica_lag_sec,
+            replica_health_check_interval_ms: this.replica_health_check_interval_ms,
             test: Some(proto::TestDatabase {
                 server_url: this.test_server_url.clone(),
                 prover_url: this.test_prover_url.clone(),
@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs::{self};
 use zksync_protobuf::{required, ProtoRepr};
 
-use crate::{proto::eth as proto, read_optional_repr};
+use crate::{parse_h160, proto::eth as proto, read_optional_repr};
 
 impl proto::ProofSendingMode {
     fn new(x: &configs::eth_sender::ProofSendingMode) -> Self {
@@ -54,6 +54,8 @@ impl ProtoRepr for proto::Eth {
             sender: read_optional_repr(&self.sender),
             gas_adjuster: read_optional_repr(&self.gas_adjuster),
             watcher: read_optional_repr(&self.watcher),
+            remote_signer: read_optional_repr(&self.remote_signer),
+            client: read_optional_repr(&self.client),
         })
     }
 
@@ -62,6 +64,8 @@ impl ProtoRepr for proto::Eth {
             sender: this.sender.as_ref().map(ProtoRepr::build),
             gas_adjuster: this.gas_adjuster.as_ref().map(ProtoRepr::build),
             watcher: this.watcher.as_ref().map(ProtoRepr::build),
+            remote_signer: this.remote_signer.as_ref().map(ProtoRepr::build),
+            client: this.client.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -115,6 +119,17 @@ impl ProtoRepr for proto::Sender {
                 .parse(),
             tx_aggregation_only_prove_and_execute: self.tx_aggregation_paused.unwrap_or(false),
             tx_aggregation_paused: self.tx_aggregation_only_prove_and_execute.unwrap_or(false),
+            nonce_recovery_enabled: self.nonce_recovery_enabled.unwrap_or(false),
+            stuck_tx_timeout_blocks: self.stuck_tx_timeout_blocks,
+            max_underpriced_resends: self.max_underpriced_resends,
+            commit_hourly_budget_gwei: self.commit_hourly_budget_gwei,
+            commit_daily_budget_gwei: self.commit_daily_budget_gwei,
+            prove_hourly_budget_gwei: self.prove_hourly_budget_gwei,
+            prove_daily_budget_gwei: self.prove_daily_budget_gwei,
+            execute_hourly_budget_gwei: self.execute_hourly_budget_gwei,
+            execute_daily_budget_gwei: self.execute_daily_budget_gwei,
+            non_urgent_base_fee_ceiling_gwei: self.non_urgent_base_fee_ceiling_gwei,
+            max_non_urgent_deferral_sec: self.max_non_urgent_deferral_sec,
         })
     }
 
@@ -147,6 +162,17 @@ impl ProtoRepr for proto::Sender {
             ),
             tx_aggregation_only_prove_and_execute: Some(this.tx_aggregation_only_prove_and_execute),
             tx_aggregation_paused: Some(this.tx_aggregation_paused),
+            nonce_recovery_enabled: Some(this.nonce_recovery_enabled),
+            stuck_tx_timeout_blocks: this.stuck_tx_timeout_blocks,
+            max_underpriced_resends: this.max_underpriced_resends,
+            commit_hourly_budget_gwei: this.commit_hourly_budget_gwei,
+            commit_daily_budget_gwei: this.commit_daily_budget_gwei,
+            prove_hourly_budget_gwei: this.prove_hourly_budget_gwei,
+            prove_daily_budget_gwei: this.prove_daily_budget_gwei,
+            execute_hourly_budget_gwei: this.execute_hourly_budget_gwei,
+            execute_daily_budget_gwei: this.execute_daily_budget_gwei,
+            non_urgent_base_fee_ceiling_gwei: this.non_urgent_base_fee_ceiling_gwei,
+            max_non_urgent_deferral_sec: this.max_non_urgent_deferral_sec,
         }
     }
 }
@@ -225,3 +251,69 @@ impl ProtoRepr for proto::EthWatch {
         }
     }
 }
+
+impl ProtoRepr for proto::RemoteSigner {
+    type Type = configs::eth_sender::RemoteSignerConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            url: required(&self.url).context("url")?.clone(),
+            operator_address: required(&self.operator_address)
+                .and_then(|x| parse_h160(x))
+                .context("operator_address")?,
+            blob_operator_address: self
+                .blob_operator_address
+                .as_ref()
+                .map(|x| parse_h160(x))
+                .transpose()
+                .context("blob_operator_address")?,
+            request_timeout_ms: self
+                .request_timeout_ms
+                .unwrap_or_else(Self::Type::default_request_timeout_ms),
+            tls_client_cert_path: self.tls_client_cert_path.clone(),
+            tls_client_key_path: self.tls_client_key_path.clone(),
+            tls_root_cert_path: self.tls_root_cert_path.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            url: Some(this.url.clone()),
+            operator_address: Some(format!("{:?}", this.operator_address)),
+            blob_operator_address: this.blob_operator_address.map(|x| format!("{:?}", x)),
+            request_timeout_ms: Some(this.request_timeout_ms),
+            tls_client_cert_path: this.tls_client_cert_path.clone(),
+            tls_client_key_path: this.tls_client_key_path.clone(),
+            tls_root_cert_path: this.tls_root_cert_path.clone(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::EthClient {
+    type Type = configs::eth_sender::EthClientConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            quorum: self
+                .quorum
+                .map(|x| x.try_into())
+                .transpose()
+                .context("quorum")?
+                .unwrap_or_else(Self::Type::default_quorum),
+            health_recovery_half_life_ms: self
+                .health_recovery_half_life_ms
+                .unwrap_or_else(Self::Type::default_health_recovery_half_life_ms),
+            quorum_request_timeout_ms: self
+                .quorum_request_timeout_ms
+                .unwrap_or_else(Self::Type::default_quorum_request_timeout_ms),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            quorum: Some(this.quorum.try_into().unwrap()),
+            health_recovery_half_life_ms: Some(this.health_recovery_half_life_ms),
+            quorum_request_timeout_ms: Some(this.quorum_request_timeout_ms),
+        }
+    }
+}
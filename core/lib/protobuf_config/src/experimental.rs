@@ -29,6 +29,7 @@ impl ProtoRepr for proto::Db {
             include_indices_and_filters_in_block_cache: self
                 .include_indices_and_filters_in_block_cache
                 .unwrap_or_default(),
+            state_archive_path: self.state_archive_path.clone(),
         })
     }
 
@@ -47,6 +48,7 @@ impl ProtoRepr for proto::Db {
             include_indices_and_filters_in_block_cache: Some(
                 this.include_indices_and_filters_in_block_cache,
             ),
+            state_archive_path: this.state_archive_path.clone(),
         }
     }
 }
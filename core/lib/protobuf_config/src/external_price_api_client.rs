@@ -1,5 +1,11 @@
-use zksync_config::configs::{self, external_price_api_client::ForcedPriceClientConfig};
-use zksync_protobuf::ProtoRepr;
+use anyhow::Context as _;
+use zksync_config::configs::{
+    self,
+    external_price_api_client::{
+        AggregatedPriceClientConfig, ForcedPriceClientConfig, JsonPathPriceClientConfig,
+    },
+};
+use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::proto::external_price_api_client as proto;
 
@@ -18,6 +24,18 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
                     denominator: self.forced_denominator,
                     fluctuation: self.forced_fluctuation,
                 }),
+                json_path: self
+                    .json_path
+                    .as_ref()
+                    .map(ProtoRepr::read)
+                    .transpose()
+                    .context("json_path")?,
+                aggregated: self
+                    .aggregated
+                    .as_ref()
+                    .map(ProtoRepr::read)
+                    .transpose()
+                    .context("aggregated")?,
             },
         )
     }
@@ -35,6 +53,65 @@ impl ProtoRepr for proto::ExternalPriceApiClient {
             forced_numerator: numerator,
             forced_denominator: denominator,
             forced_fluctuation: fluctuation,
+            json_path: this.json_path.as_ref().map(ProtoRepr::build),
+            aggregated: this.aggregated.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
+
+impl ProtoRepr for proto::JsonPathPriceClient {
+    type Type = JsonPathPriceClientConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            price_path: required(&self.price_path).context("price_path")?.clone(),
+            timestamp_path: self.timestamp_path.clone(),
+            api_key_header: self.api_key_header.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            price_path: Some(this.price_path.clone()),
+            timestamp_path: this.timestamp_path.clone(),
+            api_key_header: this.api_key_header.clone(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::AggregatedPriceClient {
+    type Type = AggregatedPriceClientConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        let sources = self
+            .sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| source.read().with_context(|| format!("sources[{i}]")))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self::Type {
+            sources,
+            min_sources: self
+                .min_sources
+                .map(|x| x.try_into())
+                .transpose()
+                .context("min_sources")?
+                .unwrap_or(Self::Type::default_min_sources()),
+            max_deviation_percent: self
+                .max_deviation_percent
+                .unwrap_or(Self::Type::default_max_deviation_percent()),
+            max_staleness_ms: self
+                .max_staleness_ms
+                .unwrap_or(Self::Type::default_max_staleness_ms()),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            sources: this.sources.iter().map(ProtoRepr::build).collect(),
+            min_sources: Some(this.min_sources as u64),
+            max_deviation_percent: Some(this.max_deviation_percent),
+            max_staleness_ms: Some(this.max_staleness_ms),
         }
     }
 }
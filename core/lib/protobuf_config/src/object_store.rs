@@ -1,9 +1,33 @@
 use anyhow::Context as _;
-use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
+use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode, S3AddressingStyle};
 use zksync_protobuf::{repr::ProtoRepr, required};
 
 use crate::proto::object_store as proto;
 
+impl proto::S3AddressingStyle {
+    fn new(source: S3AddressingStyle) -> Self {
+        match source {
+            S3AddressingStyle::VirtualHosted => Self::VirtualHosted,
+            S3AddressingStyle::Path => Self::Path,
+        }
+    }
+
+    fn parse(&self) -> S3AddressingStyle {
+        match self {
+            Self::VirtualHosted => S3AddressingStyle::VirtualHosted,
+            Self::Path => S3AddressingStyle::Path,
+        }
+    }
+}
+
+fn read_addressing_style(raw: Option<i32>) -> anyhow::Result<Option<S3AddressingStyle>> {
+    Ok(raw
+        .map(proto::S3AddressingStyle::try_from)
+        .transpose()
+        .context("addressing_style")?
+        .map(|style| style.parse()))
+}
+
 impl ProtoRepr for proto::ObjectStore {
     type Type = ObjectStoreConfig;
 
@@ -37,6 +61,21 @@ impl ProtoRepr for proto::ObjectStore {
                     .context("file_backed_base_path")?
                     .clone(),
             },
+            proto::object_store::Mode::S3(mode) => ObjectStoreMode::S3 {
+                bucket_name: required(&mode.bucket_name).context("bucket_name")?.clone(),
+                region: required(&mode.region).context("region")?.clone(),
+                endpoint: mode.endpoint.clone(),
+                addressing_style: read_addressing_style(mode.addressing_style)?,
+                s3_credential_file_path: mode.s3_credential_file_path.clone(),
+            },
+            proto::object_store::Mode::S3AnonymousReadOnly(mode) => {
+                ObjectStoreMode::S3AnonymousReadOnly {
+                    bucket_name: required(&mode.bucket_name).context("bucket_name")?.clone(),
+                    region: required(&mode.region).context("region")?.clone(),
+                    endpoint: mode.endpoint.clone(),
+                    addressing_style: read_addressing_style(mode.addressing_style)?,
+                }
+            }
         };
 
         Ok(Self::Type {
@@ -76,6 +115,34 @@ impl ProtoRepr for proto::ObjectStore {
             } => proto::object_store::Mode::FileBacked(proto::object_store::FileBacked {
                 file_backed_base_path: Some(file_backed_base_path.clone()),
             }),
+            ObjectStoreMode::S3 {
+                bucket_name,
+                region,
+                endpoint,
+                addressing_style,
+                s3_credential_file_path,
+            } => proto::object_store::Mode::S3(proto::object_store::S3 {
+                bucket_name: Some(bucket_name.clone()),
+                region: Some(region.clone()),
+                endpoint: endpoint.clone(),
+                addressing_style: addressing_style
+                    .map(|style| proto::S3AddressingStyle::new(style).into()),
+                s3_credential_file_path: s3_credential_file_path.clone(),
+            }),
+            ObjectStoreMode::S3AnonymousReadOnly {
+                bucket_name,
+                region,
+                endpoint,
+                addressing_style,
+            } => proto::object_store::Mode::S3AnonymousReadOnly(
+                proto::object_store::S3AnonymousReadOnly {
+                    bucket_name: Some(bucket_name.clone()),
+                    region: Some(region.clone()),
+                    endpoint: endpoint.clone(),
+                    addressing_style: addressing_style
+                        .map(|style| proto::S3AddressingStyle::new(style).into()),
+                },
+            ),
         };
 
         Self {
@@ -6,7 +6,7 @@ use zksync_basic_types::url::SensitiveUrl;
 use zksync_config::configs::{
     consensus::{AttesterSecretKey, ConsensusSecrets, NodeSecretKey, ValidatorSecretKey},
     secrets::Secrets,
-    DatabaseSecrets, L1Secrets,
+    CelestiaSecrets, DatabaseSecrets, L1Secrets,
 };
 use zksync_protobuf::{required, ProtoRepr};
 
@@ -20,6 +20,7 @@ impl ProtoRepr for proto::Secrets {
             consensus: read_optional_repr(&self.consensus),
             database: read_optional_repr(&self.database),
             l1: read_optional_repr(&self.l1),
+            celestia: read_optional_repr(&self.celestia),
         })
     }
 
@@ -28,6 +29,7 @@ impl ProtoRepr for proto::Secrets {
             database: this.database.as_ref().map(ProtoRepr::build),
             l1: this.l1.as_ref().map(ProtoRepr::build),
             consensus: this.consensus.as_ref().map(ProtoRepr::build),
+            celestia: this.celestia.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -47,6 +49,12 @@ impl ProtoRepr for proto::DatabaseSecrets {
             .map(str::parse::<SensitiveUrl>)
             .transpose()
             .context("replica_url")?;
+        let server_replica_urls = self
+            .server_replica_urls
+            .iter()
+            .map(|url| url.parse::<SensitiveUrl>())
+            .collect::<Result<_, _>>()
+            .context("server_replica_urls")?;
         let prover_url = self
             .prover_url
             .as_deref()
@@ -57,6 +65,7 @@ impl ProtoRepr for proto::DatabaseSecrets {
             server_url,
             prover_url,
             server_replica_url,
+            server_replica_urls,
         })
     }
 
@@ -67,6 +76,11 @@ impl ProtoRepr for proto::DatabaseSecrets {
                 .server_replica_url
                 .as_ref()
                 .map(|a| a.expose_str().to_string()),
+            server_replica_urls: this
+                .server_replica_urls
+                .iter()
+                .map(|a| a.expose_str().to_string())
+                .collect(),
             prover_url: this.prover_url.as_ref().map(|a| a.expose_str().to_string()),
         }
     }
@@ -77,12 +91,41 @@ impl ProtoRepr for proto::L1Secrets {
     fn read(&self) -> anyhow::Result<Self::Type> {
         Ok(Self::Type {
             l1_rpc_url: SensitiveUrl::from_str(required(&self.l1_rpc_url).context("l1_rpc_url")?)?,
+            fallback_l1_rpc_urls: self
+                .fallback_l1_rpc_urls
+                .iter()
+                .map(|url| url.parse::<SensitiveUrl>())
+                .collect::<Result<_, _>>()
+                .context("fallback_l1_rpc_urls")?,
         })
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             l1_rpc_url: Some(this.l1_rpc_url.expose_str().to_string()),
+            fallback_l1_rpc_urls: this
+                .fallback_l1_rpc_urls
+                .iter()
+                .map(|url| url.expose_str().to_string())
+                .collect(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::CelestiaSecrets {
+    type Type = CelestiaSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            auth_token: required(&self.auth_token)
+                .context("auth_token")?
+                .clone()
+                .into(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            auth_token: Some(this.auth_token.expose_secret().clone()),
         }
     }
 }
@@ -57,6 +57,7 @@ impl ProtoRepr for proto::SnapshotRecovery {
             postgres: read_optional_repr(&self.postgres).unwrap_or_default(),
             l1_batch: self.l1_batch.map(L1BatchNumber),
             object_store: read_optional_repr(&self.object_store),
+            archive_path: self.archive_path.clone(),
             drop_storage_key_preimages: self
                 .experimental
                 .as_ref()
@@ -94,6 +95,7 @@ impl ProtoRepr for proto::SnapshotRecovery {
             experimental,
             l1_batch: this.l1_batch.map(|a| a.0),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
+            archive_path: this.archive_path.clone(),
         }
     }
 }
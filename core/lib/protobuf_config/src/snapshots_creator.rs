@@ -20,6 +20,10 @@ impl ProtoRepr for proto::SnapshotsCreator {
                 .try_into()
                 .context("version")?,
             l1_batch_number: self.l1_batch_number.map(L1BatchNumber),
+            delta: self.delta.unwrap_or_default(),
+            max_delta_chain_length: self
+                .max_delta_chain_length
+                .unwrap_or_else(Self::Type::max_delta_chain_length_default),
             storage_logs_chunk_size: *required(&self.storage_logs_chunk_size)
                 .context("storage_logs_chunk_size")?,
             concurrent_queries_count: *required(&self.concurrent_queries_count)
@@ -32,6 +36,8 @@ impl ProtoRepr for proto::SnapshotsCreator {
         Self {
             version: Some(this.version.into()),
             l1_batch_number: this.l1_batch_number.map(|num| num.0),
+            delta: Some(this.delta),
+            max_delta_chain_length: Some(this.max_delta_chain_length),
             storage_logs_chunk_size: Some(this.storage_logs_chunk_size),
             concurrent_queries_count: Some(this.concurrent_queries_count),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
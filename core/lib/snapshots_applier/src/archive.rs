@@ -0,0 +1,146 @@
+//! Recovery from portable snapshot archives.
+
+use std::{collections::HashMap, path::Path};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_object_store::ArchiveObjectStore;
+use zksync_types::{
+    api,
+    snapshots::{SnapshotArchiveBlockData, SnapshotArchiveMetadata, SnapshotHeader},
+    tokens::TokenInfo,
+    L1BatchNumber, L2BlockNumber,
+};
+use zksync_web3_decl::error::EnrichedClientResult;
+
+use crate::SnapshotsApplierMainNodeClient;
+
+/// Snapshot archive opened for recovery.
+#[derive(Debug)]
+pub(crate) struct SnapshotArchive {
+    pub store: ArchiveObjectStore,
+    snapshots: HashMap<L1BatchNumber, SnapshotHeader>,
+    l1_batches: HashMap<L1BatchNumber, api::L1BatchDetails>,
+    l2_blocks: HashMap<L2BlockNumber, api::BlockDetails>,
+    tokens: HashMap<L2BlockNumber, Vec<TokenInfo>>,
+    newest_l1_batch_number: L1BatchNumber,
+}
+
+impl SnapshotArchive {
+    /// Opens the archive and validates the snapshot chain in its metadata.
+    pub async fn open(path: &Path) -> anyhow::Result<Self> {
+        let store = ArchiveObjectStore::open(path)
+            .await
+            .with_context(|| format!("failed opening snapshot archive `{}`", path.display()))?;
+        let metadata: SnapshotArchiveMetadata = serde_json::from_value(store.metadata().clone())
+            .context("failed parsing snapshot archive metadata")?;
+
+        let mut prev_l1_batch_number = None;
+        for header in &metadata.snapshots {
+            anyhow::ensure!(
+                header.base_l1_batch_number == prev_l1_batch_number,
+                "snapshot archive contains an invalid snapshot chain: snapshot for L1 batch #{} has base {:?}, \
+                 while the preceding snapshot is for L1 batch {prev_l1_batch_number:?}",
+                header.l1_batch_number,
+                header.base_l1_batch_number
+            );
+            prev_l1_batch_number = Some(header.l1_batch_number);
+        }
+        let newest_l1_batch_number =
+            prev_l1_batch_number.context("snapshot archive contains no snapshots")?;
+        anyhow::ensure!(
+            metadata.blocks.len() == metadata.snapshots.len(),
+            "snapshot archive contains main node data for {} snapshots, while it contains {} snapshots",
+            metadata.blocks.len(),
+            metadata.snapshots.len()
+        );
+        for (header, block_data) in metadata.snapshots.iter().zip(&metadata.blocks) {
+            anyhow::ensure!(
+                block_data.l1_batch.number == header.l1_batch_number
+                    && block_data.l2_block.number == header.l2_block_number,
+                "snapshot archive contains mismatched main node data for snapshot for L1 batch #{}: \
+                 L1 batch #{}, L2 block #{} (expected L2 block #{})",
+                header.l1_batch_number,
+                block_data.l1_batch.number,
+                block_data.l2_block.number,
+                header.l2_block_number
+            );
+        }
+
+        tracing::info!(
+            "Opened snapshot archive `{}` with snapshots for L1 batches {:?} ({} objects)",
+            path.display(),
+            metadata
+                .snapshots
+                .iter()
+                .map(|header| header.l1_batch_number)
+                .collect::<Vec<_>>(),
+            store.object_count()
+        );
+        let mut l1_batches = HashMap::with_capacity(metadata.blocks.len());
+        let mut l2_blocks = HashMap::with_capacity(metadata.blocks.len());
+        let mut tokens = HashMap::with_capacity(metadata.blocks.len());
+        for block_data in metadata.blocks {
+            let SnapshotArchiveBlockData {
+                l1_batch,
+                l2_block,
+                tokens: block_tokens,
+            } = block_data;
+            tokens.insert(l2_block.number, block_tokens);
+            l1_batches.insert(l1_batch.number, l1_batch);
+            l2_blocks.insert(l2_block.number, l2_block);
+        }
+        let snapshots = metadata
+            .snapshots
+            .into_iter()
+            .map(|header| (header.l1_batch_number, header))
+            .collect();
+        Ok(Self {
+            store,
+            snapshots,
+            l1_batches,
+            l2_blocks,
+            tokens,
+            newest_l1_batch_number,
+        })
+    }
+}
+
+/// Serves all data necessary for recovery from the archive, so that the main node isn't queried.
+#[async_trait]
+impl SnapshotsApplierMainNodeClient for SnapshotArchive {
+    async fn fetch_l1_batch_details(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::L1BatchDetails>> {
+        Ok(self.l1_batches.get(&number).cloned())
+    }
+
+    async fn fetch_l2_block_details(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<api::BlockDetails>> {
+        Ok(self.l2_blocks.get(&number).cloned())
+    }
+
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>> {
+        Ok(Some(self.newest_l1_batch_number))
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        Ok(self.snapshots.get(&l1_batch_number).cloned())
+    }
+
+    async fn fetch_tokens(
+        &self,
+        at_l2_block: L2BlockNumber,
+    ) -> EnrichedClientResult<Vec<TokenInfo>> {
+        // Tokens are only stored for snapshot L2 blocks, which are the only ones queried during recovery.
+        Ok(self.tokens.get(&at_l2_block).cloned().unwrap_or_default())
+    }
+}
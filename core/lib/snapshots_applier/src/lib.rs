@@ -1,7 +1,8 @@
 //! Logic for applying application-level snapshots to Postgres storage.
 
 use std::{
-    cmp::Ordering, collections::HashMap, fmt, mem, num::NonZeroUsize, sync::Arc, time::Duration,
+    cmp::Ordering, collections::HashMap, fmt, mem, num::NonZeroUsize, path::PathBuf, sync::Arc,
+    time::Duration,
 };
 
 use anyhow::Context as _;
@@ -28,8 +29,12 @@ use zksync_web3_decl::{
     namespaces::{EnNamespaceClient, SnapshotsNamespaceClient, ZksNamespaceClient},
 };
 
-use self::metrics::{InitialStage, StorageLogsChunksStage, METRICS};
+use self::{
+    archive::SnapshotArchive,
+    metrics::{InitialStage, StorageLogsChunksStage, METRICS},
+};
 
+mod archive;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -168,7 +173,7 @@ impl SnapshotsApplierMainNodeClient for Box<DynClient<L2>> {
         &self,
     ) -> EnrichedClientResult<Option<L1BatchNumber>> {
         let snapshots = self
-            .get_all_snapshots()
+            .get_all_snapshots(Some(true))
             .rpc_context("get_all_snapshots")
             .await?;
         Ok(snapshots.snapshots_l1_batch_numbers.first().copied())
@@ -219,6 +224,12 @@ pub struct SnapshotsApplierConfig {
     /// Maximum concurrency factor when performing concurrent operations (for now, the only such operation
     /// is recovering chunks of storage logs).
     pub max_concurrency: NonZeroUsize,
+    /// Path to a portable snapshot archive (as created by the snapshots creator `export` command) to recover from.
+    /// If set, all data necessary for recovery is read from the archive rather than from the main node and the object store.
+    pub archive_path: Option<PathBuf>,
+    /// Drop storage key preimages when recovering storage logs from a snapshot with version 0.
+    /// This is a temporary option that will eventually be removed together with version 0 snapshot support.
+    pub drop_storage_key_preimages: bool,
 }
 
 impl Default for SnapshotsApplierConfig {
@@ -228,6 +239,8 @@ impl Default for SnapshotsApplierConfig {
             initial_retry_backoff: Duration::from_secs(2),
             retry_backoff_multiplier: 2.0,
             max_concurrency: NonZeroUsize::new(10).unwrap(),
+            archive_path: None,
+            drop_storage_key_preimages: false,
         }
     }
 }
@@ -259,7 +272,7 @@ pub struct SnapshotsApplierTask {
     health_updater: HealthUpdater,
     connection_pool: ConnectionPool<Core>,
     main_node_client: Box<dyn SnapshotsApplierMainNodeClient>,
-    blob_store: Arc<dyn ObjectStore>,
+    blob_store: Option<Arc<dyn ObjectStore>>,
 }
 
 impl SnapshotsApplierTask {
@@ -271,12 +284,31 @@ impl SnapshotsApplierTask {
     ) -> Self {
         Self {
             snapshot_l1_batch: None,
-            drop_storage_key_preimages: false,
+            drop_storage_key_preimages: config.drop_storage_key_preimages,
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
             connection_pool,
             main_node_client,
-            blob_store,
+            blob_store: Some(blob_store),
+        }
+    }
+
+    /// Creates a task recovering from the snapshot archive specified in [`SnapshotsApplierConfig::archive_path`].
+    /// Unlike [`Self::new()`], doesn't require an object store. If the archive path is not set,
+    /// [`Self::run()`] will return an error.
+    pub fn new_from_archive(
+        config: SnapshotsApplierConfig,
+        connection_pool: ConnectionPool<Core>,
+        main_node_client: Box<dyn SnapshotsApplierMainNodeClient>,
+    ) -> Self {
+        Self {
+            snapshot_l1_batch: None,
+            drop_storage_key_preimages: config.drop_storage_key_preimages,
+            config,
+            health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
+            connection_pool,
+            main_node_client,
+            blob_store: None,
         }
     }
 
@@ -324,12 +356,6 @@ impl SnapshotsApplierTask {
         self.snapshot_l1_batch = Some(number);
     }
 
-    /// Enables dropping storage key preimages when recovering storage logs from a snapshot with version 0.
-    /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
-    pub fn drop_storage_key_preimages(&mut self) {
-        self.drop_storage_key_preimages = true;
-    }
-
     /// Returns the health check for snapshot recovery.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -343,12 +369,31 @@ impl SnapshotsApplierTask {
     /// or under any of the following conditions:
     ///
     /// - There are no snapshots on the main node
+    /// - The snapshot archive specified in the config cannot be opened or is invalid
     pub async fn run(
         self,
         mut stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<SnapshotApplierTaskStats> {
         tracing::info!("Starting snapshot recovery with config: {:?}", self.config);
 
+        let archive = if let Some(archive_path) = &self.config.archive_path {
+            Some(SnapshotArchive::open(archive_path).await?)
+        } else {
+            None
+        };
+        let (main_node_client, blob_store): (
+            &dyn SnapshotsApplierMainNodeClient,
+            &dyn ObjectStore,
+        ) = if let Some(archive) = &archive {
+            (archive, &archive.store)
+        } else {
+            let blob_store = self
+                .blob_store
+                .as_deref()
+                .context("neither object store nor snapshot archive is specified")?;
+            (self.main_node_client.as_ref(), blob_store)
+        };
+
         let mut backoff = self.config.initial_retry_backoff;
         let mut last_error = None;
         for retry_id in 0..self.config.retry_count {
@@ -359,7 +404,13 @@ impl SnapshotsApplierTask {
                 });
             }
 
-            let result = SnapshotsApplier::load_snapshot(&self, &mut stop_receiver).await;
+            let result = SnapshotsApplier::load_snapshot(
+                &self,
+                main_node_client,
+                blob_store,
+                &mut stop_receiver,
+            )
+            .await;
 
             match result {
                 Ok((strategy, final_status)) => {
@@ -407,13 +458,115 @@ impl SnapshotsApplierTask {
     }
 }
 
-/// Strategy determining how snapshot recovery should proceed.
+/// Snapshot in a [`SnapshotChain`].
 #[derive(Debug, Clone, Copy)]
+struct SnapshotChainLink {
+    l1_batch_number: L1BatchNumber,
+    /// Index of the first storage logs chunk of this snapshot in `SnapshotRecoveryStatus.storage_logs_chunks_processed`.
+    chunk_offset: usize,
+    chunk_count: usize,
+    is_delta: bool,
+}
+
+/// Chain of snapshots to recover from: a full snapshot followed by zero or more delta snapshots, each of which
+/// is based on the previous snapshot in the chain. Storage logs chunks of all snapshots in the chain are tracked
+/// in a single `SnapshotRecoveryStatus.storage_logs_chunks_processed` list, in the chain order.
+#[derive(Debug, Clone)]
+struct SnapshotChain {
+    /// Version of the full snapshot at the start of the chain. Defines the format of storage logs for all snapshots
+    /// in the chain.
+    version: SnapshotVersion,
+    links: Vec<SnapshotChainLink>,
+}
+
+impl SnapshotChain {
+    /// Resolves the chain ending with the snapshot with the specified header by following links to base snapshots.
+    async fn resolve(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        header: SnapshotHeader,
+    ) -> Result<Self, SnapshotsApplierError> {
+        let mut version = Self::check_header(&header)?;
+        let mut headers = vec![header];
+        loop {
+            let last_header = headers.last().unwrap(); // `unwrap()` is safe: `headers` is never empty
+            let Some(base_l1_batch_number) = last_header.base_l1_batch_number else {
+                break;
+            };
+            let l1_batch_number = last_header.l1_batch_number;
+            if base_l1_batch_number >= l1_batch_number {
+                let err = anyhow::anyhow!(
+                    "snapshot for L1 batch #{l1_batch_number} refers to base snapshot for L1 batch #{base_l1_batch_number} \
+                     which is not older than it"
+                );
+                return Err(err.into());
+            }
+
+            let base_header = main_node_client
+                .fetch_snapshot(base_l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "base snapshot for L1 batch #{base_l1_batch_number} (referenced by snapshot for L1 batch \
+                         #{l1_batch_number}) is not present on main node"
+                    )
+                })?;
+            version = Self::check_header(&base_header)?;
+            headers.push(base_header);
+        }
+
+        if headers.len() > 1 && version != SnapshotVersion::Version1 {
+            let err = anyhow::anyhow!(
+                "Cannot recover from a delta snapshot based on a full snapshot with version {version:?}; \
+                 delta snapshots are only supported for {:?}",
+                SnapshotVersion::Version1
+            );
+            return Err(err.into());
+        }
+
+        let mut chunk_offset = 0;
+        let links = headers
+            .iter()
+            .rev()
+            .map(|header| {
+                let link = SnapshotChainLink {
+                    l1_batch_number: header.l1_batch_number,
+                    chunk_offset,
+                    chunk_count: header.storage_logs_chunks.len(),
+                    is_delta: header.base_l1_batch_number.is_some(),
+                };
+                chunk_offset += link.chunk_count;
+                link
+            })
+            .collect();
+        Ok(Self { version, links })
+    }
+
+    /// Checks that the header version is supported and is consistent with the header being a delta / full snapshot.
+    fn check_header(header: &SnapshotHeader) -> anyhow::Result<SnapshotVersion> {
+        let version = SnapshotRecoveryStrategy::check_snapshot_version(header.version)?;
+        let l1_batch_number = header.l1_batch_number;
+        anyhow::ensure!(
+            version.is_delta() == header.base_l1_batch_number.is_some(),
+            "Snapshot for L1 batch #{l1_batch_number} has version {version:?} inconsistent with its base snapshot \
+             ({:?}); delta snapshots must have version {:?}",
+            header.base_l1_batch_number,
+            SnapshotVersion::Version2
+        );
+        Ok(version)
+    }
+
+    fn chunk_count(&self) -> usize {
+        self.links.iter().map(|link| link.chunk_count).sum()
+    }
+}
+
+/// Strategy determining how snapshot recovery should proceed.
+#[derive(Debug, Clone)]
 enum SnapshotRecoveryStrategy {
     /// Snapshot recovery should proceed from scratch with the specified params.
-    New(SnapshotVersion),
+    New(SnapshotChain),
     /// Snapshot recovery should continue with the specified params.
-    Resumed(SnapshotVersion),
+    Resumed(SnapshotChain),
     /// Snapshot recovery has already been completed.
     Completed,
 }
@@ -446,11 +599,20 @@ impl SnapshotRecoveryStrategy {
                 })?;
             // Old snapshots can theoretically be removed by the node, but in this case the snapshot data may be removed as well,
             // so returning an error looks appropriate here.
-            let snapshot_version = Self::check_snapshot_version(snapshot_header.version)?;
+            let snapshot_chain = SnapshotChain::resolve(main_node_client, snapshot_header).await?;
+            let chunk_count = applied_snapshot_status.storage_logs_chunks_processed.len();
+            if snapshot_chain.chunk_count() != chunk_count {
+                let err = anyhow::anyhow!(
+                    "snapshot chain {snapshot_chain:?} returned by main node has {} storage logs chunks, while \
+                     the applied snapshot status has {chunk_count}",
+                    snapshot_chain.chunk_count()
+                );
+                return Err(SnapshotsApplierError::Fatal(err));
+            }
 
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed(snapshot_version), applied_snapshot_status))
+            Ok((Self::Resumed(snapshot_chain), applied_snapshot_status))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -460,7 +622,7 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let (recovery_status, snapshot_version) =
+            let (recovery_status, snapshot_chain) =
                 Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
 
             let storage_logs_count = storage
@@ -478,14 +640,14 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New(snapshot_version), recovery_status))
+            Ok((Self::New(snapshot_chain), recovery_status))
         }
     }
 
     async fn create_fresh_recovery_status(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotChain), SnapshotsApplierError> {
         let l1_batch_number = match snapshot_l1_batch {
             Some(num) => num,
             None => main_node_client
@@ -505,7 +667,13 @@ impl SnapshotRecoveryStrategy {
             version = snapshot.version,
             chunk_count = snapshot.storage_logs_chunks.len()
         );
-        let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
+        let snapshot_chain = SnapshotChain::resolve(main_node_client, snapshot.clone()).await?;
+        if snapshot_chain.links.len() > 1 {
+            tracing::info!(
+                "Snapshot is a delta snapshot; resolved snapshot chain: {:?}",
+                snapshot_chain.links
+            );
+        }
 
         let l1_batch = main_node_client
             .fetch_l1_batch_details(l1_batch_number)
@@ -541,23 +709,19 @@ impl SnapshotRecoveryStrategy {
             l2_block_timestamp: l2_block.base.timestamp,
             l2_block_hash,
             protocol_version,
-            storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
+            storage_logs_chunks_processed: vec![false; snapshot_chain.chunk_count()],
         };
-        Ok((status, snapshot_version))
+        Ok((status, snapshot_chain))
     }
 
     fn check_snapshot_version(raw_version: u16) -> anyhow::Result<SnapshotVersion> {
-        let version = SnapshotVersion::try_from(raw_version).with_context(|| {
+        // All known versions are supported. Unknown versions (e.g., ones introduced by newer main node versions)
+        // are rejected, so that the node doesn't misinterpret snapshot data.
+        SnapshotVersion::try_from(raw_version).with_context(|| {
             format!(
                 "Unrecognized snapshot version: {raw_version}; make sure you're running the latest version of the node"
             )
-        })?;
-        anyhow::ensure!(
-            matches!(version, SnapshotVersion::Version0 | SnapshotVersion::Version1),
-            "Cannot recover from a snapshot with version {version:?}; the only supported versions are {:?}",
-            [SnapshotVersion::Version0, SnapshotVersion::Version1]
-        );
-        Ok(version)
+        })
     }
 }
 
@@ -579,7 +743,7 @@ impl StorageLogs {
                 let logs: SnapshotStorageLogsChunk<StorageKey> = blob_store.get(key).await?;
                 Ok(Self::V0(logs.storage_logs))
             }
-            SnapshotVersion::Version1 => {
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
                 let logs: SnapshotStorageLogsChunk = blob_store.get(key).await?;
                 Ok(Self::V1(logs.storage_logs))
             }
@@ -651,7 +815,7 @@ struct SnapshotsApplier<'a> {
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
     health_updater: &'a HealthUpdater,
-    snapshot_version: SnapshotVersion,
+    snapshot_chain: SnapshotChain,
     max_concurrency: usize,
     drop_storage_key_preimages: bool,
     factory_deps_recovered: bool,
@@ -662,11 +826,12 @@ impl<'a> SnapshotsApplier<'a> {
     /// Returns final snapshot recovery status.
     async fn load_snapshot(
         task: &'a SnapshotsApplierTask,
+        main_node_client: &'a dyn SnapshotsApplierMainNodeClient,
+        blob_store: &'a dyn ObjectStore,
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> Result<(SnapshotRecoveryStrategy, SnapshotRecoveryStatus), SnapshotsApplierError> {
         let health_updater = &task.health_updater;
         let connection_pool = &task.connection_pool;
-        let main_node_client = task.main_node_client.as_ref();
 
         // While the recovery is in progress, the node is healthy (no error has occurred),
         // but is affected (its usual APIs don't work).
@@ -684,19 +849,19 @@ impl<'a> SnapshotsApplier<'a> {
         )
         .await?;
         tracing::info!("Chosen snapshot recovery strategy: {strategy:?} with status: {applied_snapshot_status:?}");
-        let (created_from_scratch, snapshot_version) = match strategy {
+        let (created_from_scratch, snapshot_chain) = match &strategy {
             SnapshotRecoveryStrategy::Completed => return Ok((strategy, applied_snapshot_status)),
-            SnapshotRecoveryStrategy::New(version) => (true, version),
-            SnapshotRecoveryStrategy::Resumed(version) => (false, version),
+            SnapshotRecoveryStrategy::New(chain) => (true, chain.clone()),
+            SnapshotRecoveryStrategy::Resumed(chain) => (false, chain.clone()),
         };
 
         let mut this = Self {
             connection_pool,
             main_node_client,
-            blob_store: task.blob_store.as_ref(),
+            blob_store,
             applied_snapshot_status,
             health_updater,
-            snapshot_version,
+            snapshot_chain,
             max_concurrency: task.config.max_concurrency.get(),
             drop_storage_key_preimages: task.drop_storage_key_preimages,
             factory_deps_recovered: !created_from_scratch,
@@ -782,35 +947,39 @@ impl<'a> SnapshotsApplier<'a> {
     ) -> Result<(), SnapshotsApplierError> {
         let latency = METRICS.initial_stage_duration[&InitialStage::ApplyFactoryDeps].start();
 
-        tracing::debug!("Fetching factory dependencies from object store");
-        let l1_batch_number = self.applied_snapshot_status.l1_batch_number;
-        let factory_deps: SnapshotFactoryDependencies =
-            self.blob_store.get(l1_batch_number).await.map_err(|err| {
-                let context = format!(
-                    "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
-                );
-                SnapshotsApplierError::object_store(err, context)
-            })?;
-        tracing::debug!(
-            "Fetched {} factory dependencies from object store",
-            factory_deps.factory_deps.len()
-        );
+        // Factory deps of delta snapshots only contain bytecodes added after the base snapshot,
+        // so we need to recover deps for all snapshots in the chain.
+        for link in &self.snapshot_chain.links {
+            tracing::debug!("Fetching factory dependencies from object store");
+            let l1_batch_number = link.l1_batch_number;
+            let factory_deps: SnapshotFactoryDependencies =
+                self.blob_store.get(l1_batch_number).await.map_err(|err| {
+                    let context = format!(
+                        "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
+                    );
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            tracing::debug!(
+                "Fetched {} factory dependencies from object store",
+                factory_deps.factory_deps.len()
+            );
 
-        // we cannot insert all factory deps because of field size limit triggered by UNNEST
-        // in underlying query, see `https://www.postgresql.org/docs/current/limits.html`
-        // there were around 100 thousand contracts on mainnet, where this issue first manifested
-        for chunk in factory_deps.factory_deps.chunks(1000) {
-            let chunk_deps_hashmap: HashMap<H256, Vec<u8>> = chunk
-                .iter()
-                .map(|dep| (hash_bytecode(&dep.bytecode.0), dep.bytecode.0.clone()))
-                .collect();
-            storage
-                .factory_deps_dal()
-                .insert_factory_deps(
-                    self.applied_snapshot_status.l2_block_number,
-                    &chunk_deps_hashmap,
-                )
-                .await?;
+            // we cannot insert all factory deps because of field size limit triggered by UNNEST
+            // in underlying query, see `https://www.postgresql.org/docs/current/limits.html`
+            // there were around 100 thousand contracts on mainnet, where this issue first manifested
+            for chunk in factory_deps.factory_deps.chunks(1000) {
+                let chunk_deps_hashmap: HashMap<H256, Vec<u8>> = chunk
+                    .iter()
+                    .map(|dep| (hash_bytecode(&dep.bytecode.0), dep.bytecode.0.clone()))
+                    .collect();
+                storage
+                    .factory_deps_dal()
+                    .insert_factory_deps(
+                        self.applied_snapshot_status.l2_block_number,
+                        &chunk_deps_hashmap,
+                    )
+                    .await?;
+            }
         }
 
         let latency = latency.observe();
@@ -822,21 +991,38 @@ impl<'a> SnapshotsApplier<'a> {
     async fn insert_initial_writes_chunk(
         &self,
         storage_logs: &[SnapshotStorageLog],
+        is_delta: bool,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), SnapshotsApplierError> {
-        storage
-            .storage_logs_dedup_dal()
-            .insert_initial_writes_from_snapshot(storage_logs)
-            .await?;
+        let mut dal = storage.storage_logs_dedup_dal();
+        if is_delta {
+            dal.upsert_initial_writes_from_snapshot(storage_logs)
+                .await?;
+        } else {
+            dal.insert_initial_writes_from_snapshot(storage_logs)
+                .await?;
+        }
         Ok(())
     }
 
     async fn insert_storage_logs_chunk(
         &self,
         storage_logs: &StorageLogs,
+        is_delta: bool,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), SnapshotsApplierError> {
         match storage_logs {
+            // Delta snapshots overwrite storage logs from the preceding snapshots in the chain; all logs are
+            // recovered at the snapshot L2 block, so that they are found by the Merkle tree recovery.
+            StorageLogs::V1(logs) if is_delta => {
+                storage
+                    .storage_logs_dal()
+                    .upsert_storage_logs_from_snapshot(
+                        self.applied_snapshot_status.l2_block_number,
+                        logs,
+                    )
+                    .await?;
+            }
             StorageLogs::V0(logs) => {
                 #[allow(deprecated)]
                 storage
@@ -864,21 +1050,25 @@ impl<'a> SnapshotsApplier<'a> {
     async fn recover_storage_logs_single_chunk(
         &self,
         semaphore: &Semaphore,
+        link: &SnapshotChainLink,
         chunk_id: u64,
     ) -> Result<(), SnapshotsApplierError> {
         // `unwrap()` is safe: the semaphore is never closed
         let _permit = semaphore.acquire().await.unwrap();
 
-        tracing::info!("Processing storage logs chunk {chunk_id}");
+        tracing::info!(
+            "Processing storage logs chunk {chunk_id} of snapshot for L1 batch #{}",
+            link.l1_batch_number
+        );
         let latency =
             METRICS.storage_logs_chunks_duration[&StorageLogsChunksStage::LoadFromGcs].start();
 
         let storage_key = SnapshotStorageLogsStorageKey {
             chunk_id,
-            l1_batch_number: self.applied_snapshot_status.l1_batch_number,
+            l1_batch_number: link.l1_batch_number,
         };
         let mut storage_logs =
-            StorageLogs::load(self.blob_store, storage_key, self.snapshot_version)
+            StorageLogs::load(self.blob_store, storage_key, self.snapshot_chain.version)
                 .await
                 .map_err(|err| {
                     let context =
@@ -907,15 +1097,15 @@ impl<'a> SnapshotsApplier<'a> {
 
         tracing::info!("Loading {} storage logs into Postgres", storage_logs.len());
 
-        self.insert_storage_logs_chunk(&storage_logs, &mut storage_transaction)
+        self.insert_storage_logs_chunk(&storage_logs, link.is_delta, &mut storage_transaction)
             .await?;
         let storage_logs = storage_logs.without_preimages();
-        self.insert_initial_writes_chunk(&storage_logs, &mut storage_transaction)
+        self.insert_initial_writes_chunk(&storage_logs, link.is_delta, &mut storage_transaction)
             .await?;
 
         storage_transaction
             .snapshot_recovery_dal()
-            .mark_storage_logs_chunk_as_processed(chunk_id)
+            .mark_storage_logs_chunk_as_processed(link.chunk_offset as u64 + chunk_id)
             .await?;
         storage_transaction.commit().await?;
 
@@ -937,23 +1127,27 @@ impl<'a> SnapshotsApplier<'a> {
         );
         let semaphore = Semaphore::new(effective_concurrency);
 
-        let tasks = self
-            .applied_snapshot_status
-            .storage_logs_chunks_processed
-            .iter()
-            .enumerate()
-            .filter(|(_, is_processed)| !**is_processed)
-            .map(|(chunk_id, _)| {
-                self.recover_storage_logs_single_chunk(&semaphore, chunk_id as u64)
-            });
-        let job_completion = futures::future::try_join_all(tasks);
-
-        tokio::select! {
-            res = job_completion => {
-                res?;
-            },
-            _ = stop_receiver.changed() => {
-                return Err(SnapshotsApplierError::Canceled);
+        // Snapshots in the chain must be applied sequentially since delta snapshots overwrite storage logs
+        // of the preceding snapshots.
+        for link in &self.snapshot_chain.links {
+            let chunks_processed = &self.applied_snapshot_status.storage_logs_chunks_processed
+                [link.chunk_offset..link.chunk_offset + link.chunk_count];
+            let tasks = chunks_processed
+                .iter()
+                .enumerate()
+                .filter(|(_, is_processed)| !**is_processed)
+                .map(|(chunk_id, _)| {
+                    self.recover_storage_logs_single_chunk(&semaphore, link, chunk_id as u64)
+                });
+            let job_completion = futures::future::try_join_all(tasks);
+
+            tokio::select! {
+                res = job_completion => {
+                    res?;
+                },
+                _ = stop_receiver.changed() => {
+                    return Err(SnapshotsApplierError::Canceled);
+                }
             }
         }
 
@@ -9,11 +9,13 @@ use assert_matches::assert_matches;
 use test_casing::test_casing;
 use tokio::sync::Barrier;
 use zksync_health_check::CheckHealth;
-use zksync_object_store::MockObjectStore;
+use zksync_object_store::{MockObjectStore, ObjectStoreArchiveWriter, StoredObject};
 use zksync_types::{
     api::{BlockDetails, L1BatchDetails},
     block::L1BatchHeader,
-    get_code_key, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
+    get_code_key,
+    snapshots::{SnapshotArchiveBlockData, SnapshotArchiveMetadata, SnapshotFactoryDependency},
+    L1BatchNumber, ProtocolVersion, ProtocolVersionId,
 };
 
 use self::utils::{
@@ -177,15 +179,11 @@ async fn applier_recovers_v0_snapshot(drop_storage_key_preimages: bool) {
     let storage_logs = random_storage_logs::<StorageKey>(expected_status.l1_batch_number, 200);
     let (object_store, client) = prepare_clients(&expected_status, &storage_logs).await;
 
-    let mut task = SnapshotsApplierTask::new(
-        SnapshotsApplierConfig::for_tests(),
-        pool.clone(),
-        Box::new(client),
-        object_store,
-    );
-    if drop_storage_key_preimages {
-        task.drop_storage_key_preimages();
-    }
+    let config = SnapshotsApplierConfig {
+        drop_storage_key_preimages,
+        ..SnapshotsApplierConfig::for_tests()
+    };
+    let task = SnapshotsApplierTask::new(config, pool.clone(), Box::new(client), object_store);
     let (_stop_sender, stop_receiver) = watch::channel(false);
     let stats = task.run(stop_receiver).await.unwrap();
     assert!(stats.done_work);
@@ -516,6 +514,29 @@ async fn applier_errors_with_unrecognized_snapshot_version() {
     task.run(stop_receiver).await.unwrap_err();
 }
 
+#[tokio::test]
+async fn applier_errors_with_delta_snapshot_under_full_snapshot_version() {
+    let pool = ConnectionPool::test_pool().await;
+    let object_store = MockObjectStore::arc();
+    let expected_status = mock_recovery_status();
+    let mut header = mock_snapshot_header(SnapshotVersion::Version1.into(), &expected_status);
+    header.base_l1_batch_number = Some(expected_status.l1_batch_number - 1);
+    let client = MockMainNodeClient {
+        fetch_newest_snapshot_response: Some(header),
+        ..MockMainNodeClient::default()
+    };
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    assert!(format!("{err:#}").contains("delta"), "{err:#}");
+}
+
 #[tokio::test]
 async fn applier_returns_error_on_fatal_object_store_error() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -691,3 +712,216 @@ async fn snapshot_applier_can_be_canceled() {
     assert!(result.canceled);
     assert!(!result.done_work);
 }
+
+#[tokio::test]
+async fn recovering_from_delta_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let base_l1_batch_number = L1BatchNumber(100);
+    let base_logs = random_storage_logs::<H256>(base_l1_batch_number, 100);
+
+    let mut expected_status = mock_recovery_status();
+    // Delta snapshot overwrites half of the base logs and adds 50 new ones.
+    let mut delta_logs: Vec<_> = base_logs[..50]
+        .iter()
+        .map(|log| SnapshotStorageLog {
+            value: H256::random(),
+            ..log.clone()
+        })
+        .collect();
+    delta_logs.extend(
+        random_storage_logs::<H256>(expected_status.l1_batch_number, 50)
+            .into_iter()
+            .map(|log| SnapshotStorageLog {
+                enumeration_index: log.enumeration_index + 100,
+                ..log
+            }),
+    );
+    let (object_store, mut client) = prepare_clients(&expected_status, &delta_logs).await;
+
+    let mut base_status = mock_recovery_status();
+    base_status.l1_batch_number = base_l1_batch_number;
+    for (chunk_id, chunk) in base_logs.chunks(50).enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: base_l1_batch_number,
+            chunk_id: chunk_id as u64,
+        };
+        let chunk = SnapshotStorageLogsChunk {
+            storage_logs: chunk.to_vec(),
+        };
+        object_store.put(key, &chunk).await.unwrap();
+    }
+    let base_factory_dep_bytes: Vec<u8> = (32..64).collect();
+    let base_factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![SnapshotFactoryDependency {
+            bytecode: base_factory_dep_bytes.clone().into(),
+        }],
+    };
+    object_store
+        .put(base_l1_batch_number, &base_factory_deps)
+        .await
+        .unwrap();
+    client.fetch_snapshot_responses.insert(
+        base_l1_batch_number,
+        mock_snapshot_header(SnapshotVersion::Version1.into(), &base_status),
+    );
+    let delta_header = client.fetch_newest_snapshot_response.as_mut().unwrap();
+    delta_header.version = SnapshotVersion::Version2.into();
+    delta_header.base_l1_batch_number = Some(base_l1_batch_number);
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .unwrap();
+    // Chunks of both snapshots in the chain are tracked.
+    expected_status.storage_logs_chunks_processed = vec![true; 4];
+    assert_eq!(status, expected_status);
+
+    let mut expected_logs: HashMap<_, _> =
+        base_logs.into_iter().map(|log| (log.key, log)).collect();
+    expected_logs.extend(delta_logs.into_iter().map(|log| (log.key, log)));
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let expected_log = &expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.l2_block_number, expected_status.l2_block_number);
+    }
+
+    let all_initial_writes = storage
+        .storage_logs_dedup_dal()
+        .dump_all_initial_writes_for_tests()
+        .await;
+    assert_eq!(all_initial_writes.len(), expected_logs.len());
+    for initial_write in all_initial_writes {
+        let log = &expected_logs[&initial_write.hashed_key];
+        assert_eq!(
+            initial_write.l1_batch_number,
+            log.l1_batch_number_of_initial_write
+        );
+        assert_eq!(initial_write.index, log.enumeration_index);
+    }
+
+    let base_factory_dep_hash = hash_bytecode(&base_factory_dep_bytes);
+    let base_factory_dep = storage
+        .factory_deps_dal()
+        .get_sealed_factory_dep(base_factory_dep_hash)
+        .await
+        .unwrap();
+    assert_eq!(base_factory_dep, Some(base_factory_dep_bytes));
+}
+
+#[tokio::test]
+async fn recovering_from_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 200);
+    let (object_store, client) = prepare_clients(&expected_status, &storage_logs).await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("snapshot.archive");
+    let mut writer = ObjectStoreArchiveWriter::create(&archive_path)
+        .await
+        .unwrap();
+    let factory_deps_key = SnapshotFactoryDependencies::encode_key(expected_status.l1_batch_number);
+    writer
+        .copy_from(
+            &*object_store,
+            SnapshotFactoryDependencies::BUCKET,
+            &factory_deps_key,
+        )
+        .await
+        .unwrap();
+    for chunk_id in 0..expected_status.storage_logs_chunks_processed.len() as u64 {
+        let chunk_key =
+            <SnapshotStorageLogsChunk as StoredObject>::encode_key(SnapshotStorageLogsStorageKey {
+                l1_batch_number: expected_status.l1_batch_number,
+                chunk_id,
+            });
+        writer
+            .copy_from(
+                &*object_store,
+                <SnapshotStorageLogsChunk as StoredObject>::BUCKET,
+                &chunk_key,
+            )
+            .await
+            .unwrap();
+    }
+    // All data necessary for recovery must be taken from the archive; the main node isn't queried.
+    let header = client.fetch_newest_snapshot_response.clone().unwrap();
+    let block_data = SnapshotArchiveBlockData {
+        l1_batch: client.fetch_l1_batch_responses[&header.l1_batch_number].clone(),
+        l2_block: client.fetch_l2_block_responses[&header.l2_block_number].clone(),
+        tokens: client.tokens_response.clone(),
+    };
+    let metadata = SnapshotArchiveMetadata {
+        snapshots: vec![header],
+        blocks: vec![block_data],
+    };
+    writer
+        .finish(serde_json::to_value(metadata).unwrap())
+        .await
+        .unwrap();
+
+    let config = SnapshotsApplierConfig {
+        archive_path: Some(archive_path),
+        ..SnapshotsApplierConfig::for_tests()
+    };
+    let task = SnapshotsApplierTask::new_from_archive(
+        config,
+        pool.clone(),
+        Box::<MockMainNodeClient>::default(),
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+    assert_eq!(
+        is_recovery_completed(&pool, &client).await,
+        RecoveryCompletionStatus::Completed
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let current_db_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(current_db_status.unwrap(), expected_status);
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), storage_logs.len());
+}
+
+#[tokio::test]
+async fn recovery_without_object_store_or_archive_fails() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let task = SnapshotsApplierTask::new_from_archive(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::<MockMainNodeClient>::default(),
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = task.run(stop_receiver).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("neither object store nor snapshot archive"),
+        "{err}"
+    );
+}
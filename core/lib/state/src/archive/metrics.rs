@@ -0,0 +1,21 @@
+//! Metrics for `StateArchive`.
+
+use std::time::Duration;
+
+use vise::{Buckets, Gauge, Histogram, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_state_archive")]
+pub(super) struct StateArchiveMetrics {
+    /// Latency of a single archive update iteration.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub update: Histogram<Duration>,
+    /// Number of values written to the archive during a single update iteration.
+    #[metrics(buckets = Buckets::exponential(1.0..=100_000.0, 10.0))]
+    pub written_values: Histogram<u64>,
+    /// Last L2 block processed by the archive.
+    pub last_l2_block: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<StateArchiveMetrics> = vise::Global::new();
@@ -0,0 +1,890 @@
+//! RocksDB-based archive of historical VM state.
+//!
+//! The archive stores a compact history of storage slot values: for each slot, only values that differ
+//! from the previous archived value are stored, at most one per L2 block. This allows answering historical
+//! storage reads (e.g., for `eth_call` or `eth_getStorageAt`) independently of how long storage logs
+//! are retained in Postgres.
+//!
+//! ## Storage layout
+//!
+//! This database has 4 column families:
+//!
+//! | Column   | Key                                              | Value                     | Description                                |
+//! | -------- | ------------------------------------------------ | ------------------------- | ------------------------------------------ |
+//! | Values   | hashed key (32 bytes) ++ `!l2_block` (4 bytes BE) | 32 bytes or empty         | Slot value written in the L2 block; empty value means zero |
+//! | Changes  | L2 block (4 bytes BE) ++ hashed key (32 bytes)    | empty                     | Slots changed in the L2 block; used for rollbacks |
+//! | Blocks   | L2 block (4 bytes BE)                            | [`StateArchiveBlockInfo`] (79 bytes) | Archived L2 blocks; used to detect reverts and to execute calls |
+//! | Metadata | 'first_l2_block', 'last_l2_block'                | L2 block (4 bytes BE)     | Range of archived L2 blocks                |
+//! | Metadata | 'gaps'                                           | (4 bytes BE, 4 bytes BE)* | Ranges of L2 blocks skipped because they were pruned in Postgres |
+//! | Metadata | 'recovery_l2_block', 'recovery_chunk'            | 4 bytes BE / 8 bytes BE   | Archive initialization progress            |
+//!
+//! L2 block numbers in the `Values` keys are bitwise inverted, so that the newest value of a slot as of a certain
+//! L2 block is the first entry returned by a forward iterator started from the corresponding key.
+//!
+//! If Postgres is pruned past the last archived L2 block, the archive catches up by applying the storage logs
+//! retained after pruning at the last pruned L2 block. L2 blocks between the last archived and last pruned blocks
+//! are recorded as a gap; the archive doesn't provide state for them.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops,
+    path::Path,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_storage::{db::NamedColumnFamily, RocksDB};
+use zksync_types::{
+    fee_model::{BatchFeeInput, L1PeggedBatchFeeModelInput, PubdataIndependentBatchFeeModelInput},
+    snapshots::uniform_hashed_keys_chunk,
+    L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageValue, H256,
+};
+
+use self::metrics::METRICS;
+use crate::RocksdbStorageOptions;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// RocksDB column families used by the [`StateArchive`].
+#[derive(Debug, Clone, Copy)]
+pub enum StateArchiveColumnFamily {
+    /// Versioned storage slot values.
+    Values,
+    /// Storage slots changed in each L2 block.
+    Changes,
+    /// Hashes of archived L2 blocks.
+    Blocks,
+    /// Archive metadata.
+    Metadata,
+}
+
+impl NamedColumnFamily for StateArchiveColumnFamily {
+    const DB_NAME: &'static str = "state_archive";
+    const ALL: &'static [Self] = &[Self::Values, Self::Changes, Self::Blocks, Self::Metadata];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Values => "values",
+            Self::Changes => "changes",
+            Self::Blocks => "blocks",
+            Self::Metadata => "metadata",
+        }
+    }
+}
+
+fn serialize_l2_block_number(number: L2BlockNumber) -> [u8; 4] {
+    number.0.to_be_bytes()
+}
+
+fn deserialize_l2_block_number(bytes: &[u8]) -> L2BlockNumber {
+    let bytes: [u8; 4] = bytes.try_into().expect("incorrect L2 block number format");
+    L2BlockNumber(u32::from_be_bytes(bytes))
+}
+
+/// Information about an archived L2 block necessary to execute calls on top of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateArchiveBlockInfo {
+    /// L2 block hash.
+    pub hash: H256,
+    /// L2 block timestamp.
+    pub timestamp: u64,
+    /// Number of the L1 batch the L2 block belongs to (or is expected to belong to, if the batch wasn't sealed yet
+    /// when the block was archived).
+    pub l1_batch_number: L1BatchNumber,
+    /// Timestamp of the L1 batch the L2 block belongs to.
+    pub l1_batch_timestamp: u64,
+    pub protocol_version: ProtocolVersionId,
+    pub batch_fee_input: BatchFeeInput,
+}
+
+impl StateArchiveBlockInfo {
+    const SERIALIZED_LEN: usize = 79;
+
+    fn serialize(&self) -> [u8; Self::SERIALIZED_LEN] {
+        let (fee_input_tag, fee_input) = match self.batch_fee_input {
+            BatchFeeInput::L1Pegged(input) => {
+                (0_u8, [input.l1_gas_price, input.fair_l2_gas_price, 0])
+            }
+            BatchFeeInput::PubdataIndependent(input) => (
+                1,
+                [
+                    input.l1_gas_price,
+                    input.fair_l2_gas_price,
+                    input.fair_pubdata_price,
+                ],
+            ),
+        };
+
+        let mut bytes = [0_u8; Self::SERIALIZED_LEN];
+        bytes[..32].copy_from_slice(self.hash.as_bytes());
+        bytes[32..40].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[40..44].copy_from_slice(&self.l1_batch_number.0.to_be_bytes());
+        bytes[44..52].copy_from_slice(&self.l1_batch_timestamp.to_be_bytes());
+        bytes[52..54].copy_from_slice(&(self.protocol_version as u16).to_be_bytes());
+        bytes[54] = fee_input_tag;
+        for (i, value) in fee_input.into_iter().enumerate() {
+            bytes[55 + i * 8..63 + i * 8].copy_from_slice(&value.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn deserialize(bytes: &[u8]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            bytes.len() == Self::SERIALIZED_LEN,
+            "unexpected archived L2 block info length: {}",
+            bytes.len()
+        );
+        let read_u64 =
+            |start: usize| u64::from_be_bytes(bytes[start..start + 8].try_into().unwrap());
+
+        let protocol_version = u16::from_be_bytes([bytes[52], bytes[53]]);
+        let protocol_version = ProtocolVersionId::try_from(protocol_version)
+            .with_context(|| format!("unknown protocol version: {protocol_version}"))?;
+        let batch_fee_input = match bytes[54] {
+            0 => BatchFeeInput::L1Pegged(L1PeggedBatchFeeModelInput {
+                l1_gas_price: read_u64(55),
+                fair_l2_gas_price: read_u64(63),
+            }),
+            1 => BatchFeeInput::PubdataIndependent(PubdataIndependentBatchFeeModelInput {
+                l1_gas_price: read_u64(55),
+                fair_l2_gas_price: read_u64(63),
+                fair_pubdata_price: read_u64(71),
+            }),
+            tag => anyhow::bail!("unknown batch fee input tag: {tag}"),
+        };
+        Ok(Self {
+            hash: H256::from_slice(&bytes[..32]),
+            timestamp: read_u64(32),
+            l1_batch_number: L1BatchNumber(u32::from_be_bytes(bytes[40..44].try_into().unwrap())),
+            l1_batch_timestamp: read_u64(44),
+            protocol_version,
+            batch_fee_input,
+        })
+    }
+}
+
+/// Archive of historical VM state backed by RocksDB. The archive is populated from Postgres by [`StateArchiveTask`].
+///
+/// The archive is cheaply cloneable; all clones share the same RocksDB instance.
+#[derive(Debug, Clone)]
+pub struct StateArchive {
+    db: RocksDB<StateArchiveColumnFamily>,
+}
+
+impl StateArchive {
+    const FIRST_L2_BLOCK_KEY: &'static [u8] = b"first_l2_block";
+    const LAST_L2_BLOCK_KEY: &'static [u8] = b"last_l2_block";
+    const GAPS_KEY: &'static [u8] = b"gaps";
+    const RECOVERY_L2_BLOCK_KEY: &'static [u8] = b"recovery_l2_block";
+    const RECOVERY_CHUNK_KEY: &'static [u8] = b"recovery_chunk";
+
+    /// Number of hashed key chunks loaded from Postgres during archive initialization.
+    /// This is intentionally not configurable because chunks must be the same for the entire initialization
+    /// (i.e., not changed after a node restart).
+    const RECOVERY_CHUNK_COUNT: u64 = 256;
+
+    /// Opens or creates an archive at the specified `path`.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub async fn new(path: &Path, options: RocksdbStorageOptions) -> anyhow::Result<Self> {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let db = RocksDB::with_options(&path, options.into_generic())
+                .context("failed initializing state archive RocksDB")?;
+            Ok(Self { db })
+        })
+        .await
+        .context("panicked initializing state archive RocksDB")?
+    }
+
+    fn values_key(hashed_key: H256, l2_block_number: L2BlockNumber) -> [u8; 36] {
+        let mut key = [0_u8; 36];
+        key[..32].copy_from_slice(hashed_key.as_bytes());
+        key[32..].copy_from_slice(&(!l2_block_number.0).to_be_bytes());
+        key
+    }
+
+    fn changes_key(l2_block_number: L2BlockNumber, hashed_key: H256) -> [u8; 36] {
+        let mut key = [0_u8; 36];
+        key[..4].copy_from_slice(&serialize_l2_block_number(l2_block_number));
+        key[4..].copy_from_slice(hashed_key.as_bytes());
+        key
+    }
+
+    fn get_metadata_l2_block(&self, key: &[u8]) -> Option<L2BlockNumber> {
+        self.db
+            .get_cf(StateArchiveColumnFamily::Metadata, key)
+            .expect("failed reading state archive metadata")
+            .map(|bytes| deserialize_l2_block_number(&bytes))
+    }
+
+    /// Returns the range of L2 blocks for which the archive can provide VM state, or `None` if the archive
+    /// is not initialized yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics on RocksDB errors.
+    pub fn l2_block_range(&self) -> Option<ops::RangeInclusive<L2BlockNumber>> {
+        let first = self.get_metadata_l2_block(Self::FIRST_L2_BLOCK_KEY)?;
+        let last = self.get_metadata_l2_block(Self::LAST_L2_BLOCK_KEY)?;
+        Some(first..=last)
+    }
+
+    fn gaps(&self) -> Vec<ops::RangeInclusive<L2BlockNumber>> {
+        let gaps = self
+            .db
+            .get_cf(StateArchiveColumnFamily::Metadata, Self::GAPS_KEY)
+            .expect("failed reading state archive metadata")
+            .unwrap_or_default();
+        gaps.chunks_exact(8)
+            .map(|gap| {
+                deserialize_l2_block_number(&gap[..4])..=deserialize_l2_block_number(&gap[4..])
+            })
+            .collect()
+    }
+
+    /// Checks whether the archive can provide VM state for the specified L2 block.
+    ///
+    /// # Panics
+    ///
+    /// Panics on RocksDB errors.
+    pub fn covers(&self, l2_block_number: L2BlockNumber) -> bool {
+        let Some(l2_block_range) = self.l2_block_range() else {
+            return false;
+        };
+        l2_block_range.contains(&l2_block_number)
+            && !self.gaps().iter().any(|gap| gap.contains(&l2_block_number))
+    }
+
+    /// Reads the value of a storage slot as of the end of the specified L2 block. Returns `None` if the archive
+    /// doesn't cover this L2 block.
+    ///
+    /// # Panics
+    ///
+    /// Panics on RocksDB errors.
+    pub fn read_value(
+        &self,
+        hashed_key: H256,
+        l2_block_number: L2BlockNumber,
+    ) -> Option<StorageValue> {
+        if !self.covers(l2_block_number) {
+            return None;
+        }
+        Some(self.read_value_unchecked(hashed_key, l2_block_number))
+    }
+
+    /// Reads information about the specified L2 block. Returns `None` if the archive doesn't cover this L2 block,
+    /// or doesn't have information about it (e.g., for the first archived block after snapshot recovery).
+    ///
+    /// # Panics
+    ///
+    /// Panics on RocksDB errors.
+    pub fn read_block_info(&self, l2_block_number: L2BlockNumber) -> Option<StateArchiveBlockInfo> {
+        if !self.covers(l2_block_number) {
+            return None;
+        }
+        let bytes = self
+            .db
+            .get_cf(
+                StateArchiveColumnFamily::Blocks,
+                &serialize_l2_block_number(l2_block_number),
+            )
+            .expect("failed reading L2 block info from state archive")?;
+        Some(StateArchiveBlockInfo::deserialize(&bytes).expect("invalid archived L2 block info"))
+    }
+
+    /// Asynchronous version of [`Self::read_block_info()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blocking read task panics.
+    pub async fn historical_block_info(
+        &self,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<Option<StateArchiveBlockInfo>> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.read_block_info(l2_block_number))
+            .await
+            .context("panicked reading L2 block info from state archive")
+    }
+
+    fn read_value_unchecked(
+        &self,
+        hashed_key: H256,
+        l2_block_number: L2BlockNumber,
+    ) -> StorageValue {
+        let start_key = Self::values_key(hashed_key, l2_block_number);
+        let mut iter = self
+            .db
+            .from_iterator_cf(StateArchiveColumnFamily::Values, &start_key);
+        let Some((key, value)) = iter.next() else {
+            return StorageValue::zero();
+        };
+        if key[..32] != *hashed_key.as_bytes() || value.is_empty() {
+            return StorageValue::zero();
+        }
+        H256::from_slice(&value)
+    }
+
+    /// Asynchronous version of [`Self::read_value()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blocking read task panics.
+    pub async fn historical_value(
+        &self,
+        hashed_key: H256,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<Option<StorageValue>> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.read_value(hashed_key, l2_block_number))
+            .await
+            .context("panicked reading value from state archive")
+    }
+
+    async fn range_async(&self) -> anyhow::Result<Option<ops::RangeInclusive<L2BlockNumber>>> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.l2_block_range())
+            .await
+            .context("panicked reading state archive metadata")
+    }
+
+    /// Updates the archive from Postgres, processing at most `max_l2_blocks` new L2 blocks.
+    /// Returns `false` if there's nothing to update.
+    async fn update_from_postgres(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        stop_receiver: &watch::Receiver<bool>,
+        max_l2_blocks: usize,
+    ) -> anyhow::Result<bool> {
+        let Some(l2_block_range) = self.range_async().await? else {
+            return self.initialize(storage, stop_receiver).await;
+        };
+        let mut last_l2_block = *l2_block_range.end();
+        let last_pruned_l2_block = Self::last_pruned_l2_block(storage).await?;
+        if let Some(last_pruned_l2_block) =
+            last_pruned_l2_block.filter(|&pruned| pruned > last_l2_block)
+        {
+            return self
+                .catch_up_after_pruning(storage, last_l2_block, last_pruned_l2_block)
+                .await;
+        }
+        if last_pruned_l2_block < Some(last_l2_block) {
+            last_l2_block = self
+                .roll_back_if_reverted(storage, *l2_block_range.start(), last_l2_block)
+                .await?;
+        }
+
+        let from_l2_block = last_l2_block + 1;
+        let (_, Some(to_l2_block)) = storage
+            .blocks_web3_dal()
+            .get_block_hashes_since(from_l2_block, max_l2_blocks)
+            .await?
+        else {
+            return Ok(false);
+        };
+        let latency = METRICS.update.start();
+        let mut block_infos = vec![];
+        for l2_block_number in from_l2_block.0..=to_l2_block.0 {
+            let Some(info) = Self::load_block_info(storage, L2BlockNumber(l2_block_number)).await?
+            else {
+                break; // The L2 block was reverted or pruned; we'll handle this on the next iteration
+            };
+            block_infos.push(info);
+        }
+        let Some(to_l2_block) = (block_infos.len() as u32)
+            .checked_sub(1)
+            .map(|offset| from_l2_block + offset)
+        else {
+            return Ok(false);
+        };
+        let storage_logs = storage
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_blocks(from_l2_block..=to_l2_block)
+            .await?;
+        // Logs may have been pruned while they were loaded; in this case, the loaded logs are incomplete.
+        // We'll catch up with pruning on the next iteration.
+        if Self::last_pruned_l2_block(storage).await? > Some(last_l2_block) {
+            return Ok(true);
+        }
+
+        let this = self.clone();
+        let written_values = tokio::task::spawn_blocking(move || {
+            this.write_l2_blocks(&block_infos, to_l2_block, storage_logs, None)
+        })
+        .await
+        .context("panicked writing L2 blocks to state archive")??;
+
+        latency.observe();
+        METRICS.written_values.observe(written_values);
+        METRICS.last_l2_block.set(to_l2_block.0.into());
+        tracing::debug!(
+            "Archived state for L2 blocks {from_l2_block}..={to_l2_block}, written {written_values} values"
+        );
+        Ok(true)
+    }
+
+    async fn last_pruned_l2_block(
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L2BlockNumber>> {
+        Ok(storage
+            .pruning_dal()
+            .get_pruning_info()
+            .await?
+            .last_hard_pruned_l2_block)
+    }
+
+    async fn load_block_info(
+        storage: &mut Connection<'_, Core>,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<Option<StateArchiveBlockInfo>> {
+        let Some(header) = storage
+            .blocks_dal()
+            .get_l2_block_header(l2_block_number)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let l1_batch = storage
+            .storage_web3_dal()
+            .resolve_l1_batch_number_of_l2_block(l2_block_number)
+            .await?;
+        let l1_batch_timestamp = storage
+            .blocks_web3_dal()
+            .get_expected_l1_batch_timestamp(&l1_batch)
+            .await?;
+        // The timestamp can only be missing before the genesis L1 batch is sealed, in which case the L2 block
+        // is the first block in its batch.
+        let l1_batch_timestamp = l1_batch_timestamp.unwrap_or(header.timestamp);
+        Ok(Some(StateArchiveBlockInfo {
+            hash: header.hash,
+            timestamp: header.timestamp,
+            l1_batch_number: l1_batch.expected_l1_batch(),
+            l1_batch_timestamp,
+            // Blocks without version specified are considered to be of `Version9`.
+            protocol_version: header
+                .protocol_version
+                .unwrap_or(ProtocolVersionId::last_potentially_undefined()),
+            batch_fee_input: header.batch_fee_input,
+        }))
+    }
+
+    /// Catches up with Postgres pruned past the last archived L2 block. Pruning retains the latest storage log
+    /// for each slot, so the state at the last pruned L2 block can be restored from the retained logs; the state
+    /// for the skipped L2 blocks is lost, and they are recorded as a gap.
+    async fn catch_up_after_pruning(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_l2_block: L2BlockNumber,
+        last_pruned_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<bool> {
+        tracing::warn!(
+            "Storage logs were pruned in Postgres up to L2 block #{last_pruned_l2_block} before the state archive \
+             has processed them (the archive is at L2 block #{last_l2_block}); catching up. The archive won't \
+             provide state for L2 blocks #{}..#{last_pruned_l2_block}. Make sure that the pruning data retention \
+             period is large enough",
+            last_l2_block + 1
+        );
+        let storage_logs = storage
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_blocks(last_l2_block + 1..=last_pruned_l2_block)
+            .await?;
+        if Self::last_pruned_l2_block(storage).await? != Some(last_pruned_l2_block) {
+            return Ok(true); // Pruning has progressed while loading logs; retry on the next iteration
+        }
+        // Retained logs may belong to different L2 blocks; we collapse them into the last pruned block.
+        let storage_logs = storage_logs
+            .into_iter()
+            .map(|(hashed_key, value, _)| (hashed_key, value, last_pruned_l2_block))
+            .collect();
+
+        let this = self.clone();
+        let gap = last_l2_block + 1..=last_pruned_l2_block;
+        let written_values = tokio::task::spawn_blocking(move || {
+            this.write_l2_blocks(&[], last_pruned_l2_block, storage_logs, Some(gap))
+        })
+        .await
+        .context("panicked writing L2 blocks to state archive")??;
+
+        METRICS.written_values.observe(written_values);
+        METRICS.last_l2_block.set(last_pruned_l2_block.0.into());
+        Ok(true)
+    }
+
+    fn write_l2_blocks(
+        &self,
+        block_infos: &[StateArchiveBlockInfo],
+        to_l2_block: L2BlockNumber,
+        storage_logs: Vec<(H256, H256, L2BlockNumber)>,
+        gap: Option<ops::RangeInclusive<L2BlockNumber>>,
+    ) -> anyhow::Result<u64> {
+        let from_l2_block = to_l2_block + 1 - block_infos.len() as u32;
+        // Only retain the last write to each slot in each L2 block.
+        let changes: BTreeMap<_, _> = storage_logs
+            .into_iter()
+            .map(|(hashed_key, value, l2_block_number)| ((l2_block_number, hashed_key), value))
+            .collect();
+
+        let mut batch = self.db.new_write_batch();
+        let mut latest_values = HashMap::<H256, StorageValue>::new();
+        let mut written_values = 0;
+        for ((l2_block_number, hashed_key), value) in changes {
+            let prev_value = latest_values
+                .get(&hashed_key)
+                .copied()
+                .unwrap_or_else(|| self.read_value_unchecked(hashed_key, l2_block_number - 1));
+            if prev_value == value {
+                continue; // No-op write; don't store it to keep the archive compact
+            }
+            latest_values.insert(hashed_key, value);
+
+            let value_bytes: &[u8] = if value.is_zero() {
+                &[]
+            } else {
+                value.as_bytes()
+            };
+            batch.put_cf(
+                StateArchiveColumnFamily::Values,
+                &Self::values_key(hashed_key, l2_block_number),
+                value_bytes,
+            );
+            batch.put_cf(
+                StateArchiveColumnFamily::Changes,
+                &Self::changes_key(l2_block_number, hashed_key),
+                &[],
+            );
+            written_values += 1;
+        }
+
+        for (l2_block_number, info) in (from_l2_block.0..=to_l2_block.0).zip(block_infos) {
+            batch.put_cf(
+                StateArchiveColumnFamily::Blocks,
+                &serialize_l2_block_number(L2BlockNumber(l2_block_number)),
+                &info.serialize(),
+            );
+        }
+        if let Some(gap) = gap {
+            let mut gaps = self
+                .db
+                .get_cf(StateArchiveColumnFamily::Metadata, Self::GAPS_KEY)
+                .context("failed reading state archive metadata")?
+                .unwrap_or_default();
+            gaps.extend_from_slice(&serialize_l2_block_number(*gap.start()));
+            gaps.extend_from_slice(&serialize_l2_block_number(*gap.end()));
+            batch.put_cf(StateArchiveColumnFamily::Metadata, Self::GAPS_KEY, &gaps);
+        }
+        batch.put_cf(
+            StateArchiveColumnFamily::Metadata,
+            Self::LAST_L2_BLOCK_KEY,
+            &serialize_l2_block_number(to_l2_block),
+        );
+        self.db
+            .write(batch)
+            .context("failed writing L2 blocks to state archive")?;
+        Ok(written_values)
+    }
+
+    /// Checks whether the last archived L2 block was reverted in Postgres and rolls back the archive if necessary.
+    /// Returns the last archived L2 block after the rollback.
+    async fn roll_back_if_reverted(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        first_l2_block: L2BlockNumber,
+        last_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<L2BlockNumber> {
+        let mut l2_block_number = last_l2_block;
+        loop {
+            let archived_info = self
+                .db
+                .get_cf(
+                    StateArchiveColumnFamily::Blocks,
+                    &serialize_l2_block_number(l2_block_number),
+                )
+                .context("failed reading L2 block info from state archive")?;
+            let Some(archived_info) = archived_info else {
+                // Info is not stored for the first archived L2 block if it's not present in Postgres
+                // (e.g., after snapshot recovery), or for L2 blocks pruned before they were archived.
+                // Such blocks cannot be reverted.
+                break;
+            };
+            let archived_hash = StateArchiveBlockInfo::deserialize(&archived_info)?.hash;
+            let hash = storage
+                .blocks_web3_dal()
+                .get_l2_block_hash(l2_block_number)
+                .await?;
+            if hash == Some(archived_hash) {
+                break;
+            }
+            anyhow::ensure!(
+                l2_block_number > first_l2_block,
+                "State archive has diverged from Postgres at its first L2 block #{first_l2_block}; \
+                 the archive must be re-created from scratch"
+            );
+            l2_block_number -= 1;
+        }
+
+        if l2_block_number < last_l2_block {
+            tracing::info!(
+                "L2 blocks #{}..={last_l2_block} were reverted in Postgres; rolling back state archive",
+                l2_block_number + 1
+            );
+            let this = self.clone();
+            tokio::task::spawn_blocking(move || this.roll_back(l2_block_number, last_l2_block))
+                .await
+                .context("panicked rolling back state archive")??;
+        }
+        Ok(l2_block_number)
+    }
+
+    fn roll_back(
+        &self,
+        last_l2_block_to_keep: L2BlockNumber,
+        last_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        let mut batch = self.db.new_write_batch();
+        let mut l2_block_number = last_l2_block_to_keep + 1;
+        while l2_block_number <= last_l2_block {
+            let prefix = serialize_l2_block_number(l2_block_number);
+            let changes = self
+                .db
+                .prefix_iterator_cf(StateArchiveColumnFamily::Changes, &prefix);
+            for (key, _) in changes {
+                let hashed_key = H256::from_slice(&key[4..]);
+                batch.delete_cf(
+                    StateArchiveColumnFamily::Values,
+                    &Self::values_key(hashed_key, l2_block_number),
+                );
+                batch.delete_cf(StateArchiveColumnFamily::Changes, &key);
+            }
+            batch.delete_cf(StateArchiveColumnFamily::Blocks, &prefix);
+            l2_block_number += 1;
+        }
+        batch.put_cf(
+            StateArchiveColumnFamily::Metadata,
+            Self::LAST_L2_BLOCK_KEY,
+            &serialize_l2_block_number(last_l2_block_to_keep),
+        );
+        self.db
+            .write(batch)
+            .context("failed rolling back state archive")
+    }
+
+    /// Initializes the archive with the VM state at the earliest L2 block fully available in Postgres
+    /// (i.e., the genesis, snapshot recovery or last pruned L2 block). Initialization is resumable after a restart.
+    async fn initialize(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<bool> {
+        let recovery_l2_block = self.get_metadata_l2_block(Self::RECOVERY_L2_BLOCK_KEY);
+        let recovery_l2_block = if let Some(number) = recovery_l2_block {
+            number
+        } else {
+            let Some(sealed_l2_block) = storage.blocks_dal().get_sealed_l2_block_number().await?
+            else {
+                return Ok(false); // No L2 blocks in Postgres yet
+            };
+            let snapshot_l2_block = storage
+                .snapshot_recovery_dal()
+                .get_applied_snapshot_status()
+                .await?
+                .map(|status| status.l2_block_number);
+            let last_pruned_l2_block = storage
+                .pruning_dal()
+                .get_pruning_info()
+                .await?
+                .last_hard_pruned_l2_block;
+            let start_l2_block = snapshot_l2_block
+                .max(last_pruned_l2_block)
+                .unwrap_or(L2BlockNumber(0))
+                .min(sealed_l2_block);
+            tracing::info!("Initializing state archive at L2 block #{start_l2_block}");
+            self.db
+                .put_cf(
+                    StateArchiveColumnFamily::Metadata,
+                    Self::RECOVERY_L2_BLOCK_KEY,
+                    &serialize_l2_block_number(start_l2_block),
+                )
+                .context("failed writing state archive metadata")?;
+            start_l2_block
+        };
+
+        let start_chunk = self
+            .db
+            .get_cf(StateArchiveColumnFamily::Metadata, Self::RECOVERY_CHUNK_KEY)
+            .context("failed reading state archive metadata")?
+            .map_or(0, |bytes| {
+                u64::from_be_bytes(bytes.try_into().expect("incorrect chunk index format"))
+            });
+        for chunk_id in start_chunk..Self::RECOVERY_CHUNK_COUNT {
+            if *stop_receiver.borrow() {
+                return Ok(false);
+            }
+            let key_range = uniform_hashed_keys_chunk(chunk_id, Self::RECOVERY_CHUNK_COUNT);
+            let values = storage
+                .storage_logs_dal()
+                .get_latest_values_for_key_range(recovery_l2_block, key_range)
+                .await?;
+
+            let last_pruned_l2_block = storage
+                .pruning_dal()
+                .get_pruning_info()
+                .await?
+                .last_hard_pruned_l2_block;
+            if last_pruned_l2_block > Some(recovery_l2_block) {
+                tracing::warn!(
+                    "Postgres was pruned up to L2 block #{last_pruned_l2_block:?} during state archive initialization \
+                     at L2 block #{recovery_l2_block}; restarting initialization"
+                );
+                self.reset_initialization()?;
+                return Ok(true);
+            }
+
+            let this = self.clone();
+            tokio::task::spawn_blocking(move || {
+                this.write_recovery_chunk(recovery_l2_block, chunk_id, values)
+            })
+            .await
+            .context("panicked writing state archive chunk")??;
+            tracing::debug!(
+                "Initialized state archive chunk {}/{}",
+                chunk_id + 1,
+                Self::RECOVERY_CHUNK_COUNT
+            );
+        }
+
+        let block_info = Self::load_block_info(storage, recovery_l2_block).await?;
+        let mut batch = self.db.new_write_batch();
+        if let Some(info) = block_info {
+            batch.put_cf(
+                StateArchiveColumnFamily::Blocks,
+                &serialize_l2_block_number(recovery_l2_block),
+                &info.serialize(),
+            );
+        }
+        let recovery_l2_block_bytes = serialize_l2_block_number(recovery_l2_block);
+        batch.put_cf(
+            StateArchiveColumnFamily::Metadata,
+            Self::FIRST_L2_BLOCK_KEY,
+            &recovery_l2_block_bytes,
+        );
+        batch.put_cf(
+            StateArchiveColumnFamily::Metadata,
+            Self::LAST_L2_BLOCK_KEY,
+            &recovery_l2_block_bytes,
+        );
+        batch.delete_cf(
+            StateArchiveColumnFamily::Metadata,
+            Self::RECOVERY_L2_BLOCK_KEY,
+        );
+        batch.delete_cf(StateArchiveColumnFamily::Metadata, Self::RECOVERY_CHUNK_KEY);
+        self.db
+            .write(batch)
+            .context("failed finalizing state archive initialization")?;
+        METRICS.last_l2_block.set(recovery_l2_block.0.into());
+        tracing::info!("Initialized state archive at L2 block #{recovery_l2_block}");
+        Ok(true)
+    }
+
+    fn write_recovery_chunk(
+        &self,
+        recovery_l2_block: L2BlockNumber,
+        chunk_id: u64,
+        values: Vec<(H256, StorageValue)>,
+    ) -> anyhow::Result<()> {
+        let mut batch = self.db.new_write_batch();
+        for (hashed_key, value) in values {
+            if !value.is_zero() {
+                batch.put_cf(
+                    StateArchiveColumnFamily::Values,
+                    &Self::values_key(hashed_key, recovery_l2_block),
+                    value.as_bytes(),
+                );
+            }
+        }
+        batch.put_cf(
+            StateArchiveColumnFamily::Metadata,
+            Self::RECOVERY_CHUNK_KEY,
+            &(chunk_id + 1).to_be_bytes(),
+        );
+        self.db
+            .write(batch)
+            .context("failed writing state archive chunk")
+    }
+
+    fn reset_initialization(&self) -> anyhow::Result<()> {
+        let mut batch = self.db.new_write_batch();
+        batch.delete_range_cf(
+            StateArchiveColumnFamily::Values,
+            &[0_u8; 36][..]..&[0xff_u8; 37][..],
+        );
+        batch.delete_cf(
+            StateArchiveColumnFamily::Metadata,
+            Self::RECOVERY_L2_BLOCK_KEY,
+        );
+        batch.delete_cf(StateArchiveColumnFamily::Metadata, Self::RECOVERY_CHUNK_KEY);
+        self.db
+            .write(batch)
+            .context("failed resetting state archive initialization")
+    }
+}
+
+/// Task that keeps a [`StateArchive`] in sync with Postgres.
+#[derive(Debug)]
+pub struct StateArchiveTask {
+    archive: StateArchive,
+    pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+    max_l2_blocks_per_iteration: usize,
+}
+
+impl StateArchiveTask {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+    const DEFAULT_MAX_L2_BLOCKS_PER_ITERATION: usize = 100;
+
+    /// Creates a new task for the specified archive.
+    pub fn new(archive: StateArchive, pool: ConnectionPool<Core>) -> Self {
+        Self {
+            archive,
+            pool,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            max_l2_blocks_per_iteration: Self::DEFAULT_MAX_L2_BLOCKS_PER_ITERATION,
+        }
+    }
+
+    /// Runs the task until a stop signal is received.
+    ///
+    /// # Errors
+    ///
+    /// Propagates Postgres and RocksDB errors.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            let mut storage = self.pool.connection_tagged("state_archive").await?;
+            let updated = self
+                .archive
+                .update_from_postgres(
+                    &mut storage,
+                    &stop_receiver,
+                    self.max_l2_blocks_per_iteration,
+                )
+                .await?;
+            drop(storage);
+
+            if !updated
+                && tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, state archive task is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+//! Tests for [`StateArchive`].
+
+use tempfile::TempDir;
+use zksync_dal::ConnectionPool;
+use zksync_types::{StorageKey, StorageLog};
+
+use super::*;
+use crate::test_utils::{
+    create_l1_batch, create_l2_block, gen_storage_logs, prepare_postgres,
+    prepare_postgres_for_snapshot_recovery,
+};
+
+async fn update_archive(archive: &StateArchive, conn: &mut Connection<'_, Core>) -> bool {
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    archive
+        .update_from_postgres(conn, &stop_receiver, 100)
+        .await
+        .unwrap()
+}
+
+fn assert_values(archive: &StateArchive, l2_block_number: L2BlockNumber, logs: &[StorageLog]) {
+    for log in logs {
+        assert_eq!(
+            archive.read_value(log.key.hashed_key(), l2_block_number),
+            Some(log.value),
+            "{log:?} at L2 block #{l2_block_number}"
+        );
+    }
+}
+
+fn overwrite_logs(logs: &[StorageLog], value: H256) -> Vec<StorageLog> {
+    logs.iter()
+        .map(|log| StorageLog::new_write_log(log.key, value))
+        .collect()
+}
+
+#[tokio::test]
+async fn archiving_state() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut conn).await;
+    let genesis_logs = gen_storage_logs(0..20);
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state archive");
+    let archive = StateArchive::new(dir.path(), RocksdbStorageOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(archive.l2_block_range(), None);
+    assert!(update_archive(&archive, &mut conn).await);
+    assert_eq!(
+        archive.l2_block_range(),
+        Some(L2BlockNumber(0)..=L2BlockNumber(0))
+    );
+    assert_values(&archive, L2BlockNumber(0), &genesis_logs);
+    assert!(!update_archive(&archive, &mut conn).await);
+
+    let new_logs = gen_storage_logs(20..30);
+    create_l2_block(&mut conn, L2BlockNumber(1), new_logs.clone()).await;
+    let overwritten_logs = overwrite_logs(&genesis_logs[..5], H256::repeat_byte(0xff));
+    let zeroed_logs = overwrite_logs(&genesis_logs[5..10], H256::zero());
+    create_l2_block(
+        &mut conn,
+        L2BlockNumber(2),
+        [overwritten_logs.clone(), zeroed_logs.clone()].concat(),
+    )
+    .await;
+
+    assert!(update_archive(&archive, &mut conn).await);
+    assert_eq!(
+        archive.l2_block_range(),
+        Some(L2BlockNumber(0)..=L2BlockNumber(2))
+    );
+    for log in &new_logs {
+        assert_eq!(
+            archive.read_value(log.key.hashed_key(), L2BlockNumber(0)),
+            Some(H256::zero())
+        );
+    }
+    assert_values(&archive, L2BlockNumber(1), &new_logs);
+    assert_values(&archive, L2BlockNumber(1), &genesis_logs);
+    assert_values(&archive, L2BlockNumber(2), &new_logs);
+    assert_values(&archive, L2BlockNumber(2), &overwritten_logs);
+    assert_values(&archive, L2BlockNumber(2), &zeroed_logs);
+    assert_values(&archive, L2BlockNumber(2), &genesis_logs[10..]);
+
+    let missing_key = StorageKey::new(Default::default(), H256::repeat_byte(1));
+    assert_eq!(
+        archive.read_value(missing_key.hashed_key(), L2BlockNumber(2)),
+        Some(H256::zero())
+    );
+    assert_eq!(
+        archive.read_value(genesis_logs[0].key.hashed_key(), L2BlockNumber(3)),
+        None
+    );
+}
+
+#[tokio::test]
+async fn archive_is_rolled_back_after_revert() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut conn).await;
+    let genesis_logs = gen_storage_logs(0..20);
+    let new_logs = overwrite_logs(&genesis_logs[..10], H256::repeat_byte(0xff));
+    create_l2_block(&mut conn, L2BlockNumber(1), new_logs.clone()).await;
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state archive");
+    let archive = StateArchive::new(dir.path(), RocksdbStorageOptions::default())
+        .await
+        .unwrap();
+    while update_archive(&archive, &mut conn).await {
+        // Keep updating
+    }
+    assert_eq!(
+        archive.l2_block_range(),
+        Some(L2BlockNumber(0)..=L2BlockNumber(1))
+    );
+    assert_values(&archive, L2BlockNumber(1), &new_logs);
+
+    // Revert L2 block #1 in Postgres.
+    conn.storage_logs_dal()
+        .roll_back_storage_logs(L2BlockNumber(0))
+        .await
+        .unwrap();
+    conn.blocks_dal()
+        .delete_l2_blocks(L2BlockNumber(0))
+        .await
+        .unwrap();
+
+    assert!(!update_archive(&archive, &mut conn).await);
+    assert_eq!(
+        archive.l2_block_range(),
+        Some(L2BlockNumber(0)..=L2BlockNumber(0))
+    );
+    assert_values(&archive, L2BlockNumber(0), &genesis_logs);
+
+    let new_logs = overwrite_logs(&genesis_logs[10..], H256::repeat_byte(0xee));
+    create_l2_block(&mut conn, L2BlockNumber(1), new_logs.clone()).await;
+    assert!(update_archive(&archive, &mut conn).await);
+    assert_values(&archive, L2BlockNumber(1), &genesis_logs[..10]);
+    assert_values(&archive, L2BlockNumber(1), &new_logs);
+}
+
+#[tokio::test]
+async fn archive_initialization_after_snapshot_recovery() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    let (snapshot_recovery, snapshot_logs) =
+        prepare_postgres_for_snapshot_recovery(&mut conn).await;
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state archive");
+    let archive = StateArchive::new(dir.path(), RocksdbStorageOptions::default())
+        .await
+        .unwrap();
+    // There are no L2 blocks in Postgres yet, so the archive cannot be initialized.
+    assert!(!update_archive(&archive, &mut conn).await);
+    assert_eq!(archive.l2_block_range(), None);
+
+    let next_l2_block = snapshot_recovery.l2_block_number + 1;
+    let new_logs = overwrite_logs(&snapshot_logs[..10], H256::repeat_byte(0xff));
+    create_l2_block(&mut conn, next_l2_block, new_logs.clone()).await;
+    while update_archive(&archive, &mut conn).await {
+        // Keep updating
+    }
+    assert_eq!(
+        archive.l2_block_range(),
+        Some(snapshot_recovery.l2_block_number..=next_l2_block)
+    );
+    assert_values(&archive, snapshot_recovery.l2_block_number, &snapshot_logs);
+    assert_values(&archive, next_l2_block, &new_logs);
+    assert_values(&archive, next_l2_block, &snapshot_logs[10..]);
+    assert_eq!(
+        archive.read_value(
+            snapshot_logs[0].key.hashed_key(),
+            snapshot_recovery.l2_block_number - 1
+        ),
+        None
+    );
+}
+
+#[tokio::test]
+async fn archiving_block_info() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut conn).await;
+    create_l2_block(&mut conn, L2BlockNumber(1), gen_storage_logs(20..30)).await;
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state archive");
+    let archive = StateArchive::new(dir.path(), RocksdbStorageOptions::default())
+        .await
+        .unwrap();
+    while update_archive(&archive, &mut conn).await {
+        // Keep updating
+    }
+
+    let block_info = archive.read_block_info(L2BlockNumber(1)).unwrap();
+    let header = conn
+        .blocks_dal()
+        .get_l2_block_header(L2BlockNumber(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(block_info.hash, header.hash);
+    assert_eq!(block_info.timestamp, header.timestamp);
+    assert_eq!(block_info.l1_batch_number, L1BatchNumber(1));
+    assert_eq!(block_info.batch_fee_input, header.batch_fee_input);
+    assert_eq!(
+        StateArchiveBlockInfo::deserialize(&block_info.serialize()).unwrap(),
+        block_info
+    );
+    assert_eq!(archive.read_block_info(L2BlockNumber(2)), None);
+}
+
+#[tokio::test]
+async fn archive_catches_up_after_pruning() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut conn).await;
+    let genesis_logs = gen_storage_logs(0..20);
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state archive");
+    let archive = StateArchive::new(dir.path(), RocksdbStorageOptions::default())
+        .await
+        .unwrap();
+    assert!(update_archive(&archive, &mut conn).await);
+    assert_eq!(
+        archive.l2_block_range(),
+        Some(L2BlockNumber(0)..=L2BlockNumber(0))
+    );
+
+    // Create and prune L2 blocks before the archive processes them.
+    let overwritten_logs = overwrite_logs(&genesis_logs[..10], H256::repeat_byte(0xff));
+    create_l2_block(&mut conn, L2BlockNumber(1), overwritten_logs).await;
+    create_l1_batch(&mut conn, L1BatchNumber(1), &[]).await;
+    let final_logs = overwrite_logs(&genesis_logs[..5], H256::repeat_byte(0xee));
+    create_l2_block(&mut conn, L2BlockNumber(2), final_logs.clone()).await;
+    create_l1_batch(&mut conn, L1BatchNumber(2), &[]).await;
+    let new_logs = gen_storage_logs(20..30);
+    create_l2_block(&mut conn, L2BlockNumber(3), new_logs.clone()).await;
+    conn.pruning_dal()
+        .soft_prune_batches_range(L1BatchNumber(2), L2BlockNumber(2))
+        .await
+        .unwrap();
+    conn.pruning_dal()
+        .hard_prune_batches_range(L1BatchNumber(2), L2BlockNumber(2))
+        .await
+        .unwrap();
+
+    while update_archive(&archive, &mut conn).await {
+        // Keep updating
+    }
+    assert_eq!(
+        archive.l2_block_range(),
+        Some(L2BlockNumber(0)..=L2BlockNumber(3))
+    );
+    // History for L2 blocks pruned before they were archived is lost.
+    assert!(archive.covers(L2BlockNumber(0)));
+    assert!(!archive.covers(L2BlockNumber(1)));
+    assert!(!archive.covers(L2BlockNumber(2)));
+    assert_eq!(
+        archive.read_value(genesis_logs[0].key.hashed_key(), L2BlockNumber(1)),
+        None
+    );
+    assert_eq!(archive.read_block_info(L2BlockNumber(2)), None);
+
+    assert_values(&archive, L2BlockNumber(0), &genesis_logs);
+    assert_values(&archive, L2BlockNumber(3), &final_logs);
+    let overwritten_logs = overwrite_logs(&genesis_logs[5..10], H256::repeat_byte(0xff));
+    assert_values(&archive, L2BlockNumber(3), &overwritten_logs);
+    assert_values(&archive, L2BlockNumber(3), &genesis_logs[10..]);
+    assert_values(&archive, L2BlockNumber(3), &new_logs);
+    assert!(archive.read_block_info(L2BlockNumber(3)).is_some());
+}
@@ -12,6 +12,7 @@
 pub use zksync_vm_interface::storage as interface;
 
 pub use self::{
+    archive::{StateArchive, StateArchiveBlockInfo, StateArchiveColumnFamily, StateArchiveTask},
     cache::sequential_cache::SequentialCache,
     catchup::{AsyncCatchupTask, RocksdbCell},
     postgres::{PostgresStorage, PostgresStorageCaches, PostgresStorageCachesTask},
@@ -24,6 +25,7 @@ pub use self::{
     },
 };
 
+mod archive;
 mod cache;
 mod catchup;
 mod postgres;
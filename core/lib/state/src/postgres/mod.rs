@@ -18,7 +18,10 @@ use zksync_types::{L1BatchNumber, L2BlockNumber, StorageKey, StorageValue, H256}
 use zksync_vm_interface::storage::ReadStorage;
 
 use self::metrics::{Method, ValuesUpdateStage, CACHE_METRICS, STORAGE_METRICS};
-use crate::cache::{lru_cache::LruCache, CacheValue};
+use crate::{
+    cache::{lru_cache::LruCache, CacheValue},
+    StateArchive,
+};
 
 mod metrics;
 #[cfg(test)]
@@ -263,6 +266,7 @@ pub struct PostgresStorageCaches {
     // it wasn't written to at the point that interests us.
     negative_initial_writes: InitialWritesCache,
     values: Option<ValuesCacheAndUpdater>,
+    archive: Option<StateArchive>,
 }
 
 impl PostgresStorageCaches {
@@ -284,9 +288,24 @@ impl PostgresStorageCaches {
                 initial_writes_capacity / 2,
             ),
             values: None,
+            archive: None,
         }
     }
 
+    /// Sets the historical state archive used to read storage values for past L2 blocks covered by the archive
+    /// instead of querying Postgres. The archive must be kept up to date separately (e.g., using [`StateArchiveTask`]).
+    ///
+    /// [`StateArchiveTask`]: crate::StateArchiveTask
+    pub fn set_state_archive(&mut self, archive: StateArchive) {
+        tracing::debug!("Using historical state archive for VM storage reads");
+        self.archive = Some(archive);
+    }
+
+    /// Returns the historical state archive, if one is set.
+    pub fn state_archive(&self) -> Option<&StateArchive> {
+        self.archive.as_ref()
+    }
+
     /// Configures the VM storage values cache. The returned closure is the background task that will update
     /// the cache according to [`Self::schedule_values_update()`] calls. It should be spawned on a separate thread
     /// or a blocking Tokio task.
@@ -454,6 +473,17 @@ impl<'a> PostgresStorage<'a> {
         })
     }
 
+    /// Overrides the L1 batch that the L2 block this storage is created for belongs to. This is necessary
+    /// for L2 blocks pruned in Postgres (e.g., ones with state provided by [`StateArchive`]), for which the batch
+    /// cannot be resolved.
+    #[must_use]
+    pub fn with_l1_batch_number(self, l1_batch_number: L1BatchNumber) -> Self {
+        Self {
+            l1_batch_number_for_l2_block: l1_batch_number,
+            ..self
+        }
+    }
+
     /// Sets the caches to use with the storage.
     #[must_use]
     pub fn with_caches(self, caches: PostgresStorageCaches) -> Self {
@@ -485,8 +515,12 @@ impl ReadStorage for PostgresStorage<'_> {
         let hashed_key = key.hashed_key();
         let latency = STORAGE_METRICS.storage[&Method::ReadValue].start();
         let values_cache = self.values_cache();
-        let cached_value =
-            values_cache.and_then(|cache| cache.get(self.l2_block_number, hashed_key));
+        let cached_value = values_cache
+            .and_then(|cache| cache.get(self.l2_block_number, hashed_key))
+            .or_else(|| {
+                let archive = self.caches.as_ref()?.archive.as_ref()?;
+                archive.read_value(hashed_key, self.l2_block_number)
+            });
 
         let value = cached_value.unwrap_or_else(|| {
             const RETRY_INTERVAL: Duration = Duration::from_millis(500);
@@ -145,7 +145,7 @@ impl Default for RocksdbStorageOptions {
 }
 
 impl RocksdbStorageOptions {
-    fn into_generic(self) -> RocksDBOptions {
+    pub(crate) fn into_generic(self) -> RocksDBOptions {
         RocksDBOptions {
             block_cache_capacity: Some(self.block_cache_capacity),
             max_open_files: self.max_open_files,
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
@@ -15,6 +17,8 @@ pub use crate::transaction_request::{
 use crate::{protocol_version::L1VerifierConfig, Address, L2BlockNumber, ProtocolVersionId};
 
 pub mod en;
+pub mod proofs;
+pub mod simulate;
 pub mod state_override;
 
 /// Block Number
@@ -596,8 +600,8 @@ pub struct GetLogsFilter {
 /// For some reasons geth returns result as {result: DebugCall}
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct ResultDebugCall {
-    pub result: DebugCall,
+pub struct ResultDebugCall<T = DebugCall> {
+    pub result: T,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
@@ -700,16 +704,22 @@ impl ProtocolVersion {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
     CallTracer,
+    PrestateTracer,
+    StructLogger,
 }
 
+/// Tracer-specific options. Options not applicable to the selected tracer are ignored.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct CallTracerConfig {
+    /// For `callTracer`: return only the top-level call without nested calls.
     pub only_top_call: bool,
+    /// For `prestateTracer`: return both the state before and after execution, limited to the changed values.
+    pub diff_mode: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -718,6 +728,101 @@ pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
     pub tracer_config: CallTracerConfig,
+    /// Options for `structLogger`. As in Geth, they are specified at the top level rather than in `tracer_config`.
+    #[serde(flatten)]
+    pub struct_logger_config: StructLoggerConfig,
+}
+
+/// Options of the `structLogger` tracer.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StructLoggerConfig {
+    /// Do not record VM registers (which serve as the stack in EraVM).
+    pub disable_stack: bool,
+    /// Do not record accessed storage slots.
+    pub disable_storage: bool,
+    /// Record heap of the current frame.
+    pub enable_memory: bool,
+    /// Maximum number of heap words recorded for each step. If not specified, a server default is used.
+    /// The value is capped by the server limit.
+    pub memory_limit: Option<usize>,
+    /// Maximum number of recorded steps. 0 means the server limit.
+    pub limit: usize,
+}
+
+/// Account state as returned by `prestateTracer`. The shape is compatible with Geth.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// For EOAs, this is the transaction nonce; for contracts, the deployment nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Output of `prestateTracer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// Output in the diff mode: states of the changed accounts before and after execution.
+    Diff {
+        pre: BTreeMap<Address, PrestateAccount>,
+        post: BTreeMap<Address, PrestateAccount>,
+    },
+    /// States of all accounts touched during execution.
+    Prestate(BTreeMap<Address, PrestateAccount>),
+}
+
+/// Single execution step recorded by `structLogger`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: String,
+    pub gas: u64,
+    pub gas_cost: u64,
+    pub depth: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<H256>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Output of `structLogger`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogsTrace {
+    /// Gas used by the transaction.
+    pub gas: u64,
+    pub failed: bool,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Output of `debug_trace*` methods, depending on the requested tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TracerResult {
+    CallTrace(DebugCall),
+    StructLogs(StructLogsTrace),
+    PrestateTrace(PrestateTrace),
+}
+
+impl TracerResult {
+    /// Returns the call trace if this result was produced by `callTracer`.
+    pub fn into_call_trace(self) -> Option<DebugCall> {
+        match self {
+            Self::CallTrace(call) => Some(call),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -796,6 +901,38 @@ pub struct TeeProof {
     pub attestation: Option<Vec<u8>>,
 }
 
+/// L1 cost of a settlement layer transaction (commit, prove or execute) covering an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1TxCost {
+    pub tx_hash: H256,
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+    pub blob_gas_used: Option<U256>,
+    pub blob_gas_price: Option<U256>,
+    /// Total cost of the transaction in wei.
+    pub total_cost: U256,
+    /// Number of L1 batches covered by the transaction.
+    pub l1_batch_count: u32,
+    /// Part of the total cost attributed to the L1 batch; the cost is split evenly among all covered batches.
+    pub l1_batch_cost: U256,
+}
+
+/// Actual L1 costs of an L1 batch computed from receipts of its commit, prove and execute transactions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchL1Costs {
+    pub l1_batch_number: L1BatchNumber,
+    /// `None` if the commit transaction is not confirmed yet.
+    pub commit: Option<L1TxCost>,
+    /// `None` if the prove transaction is not confirmed yet.
+    pub prove: Option<L1TxCost>,
+    /// `None` if the execute transaction is not confirmed yet.
+    pub execute: Option<L1TxCost>,
+    /// Sum of costs attributed to the L1 batch by confirmed transactions, in wei.
+    pub total_cost: U256,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionDetailedResult {
@@ -864,4 +1001,66 @@ mod tests {
         serde_json::from_str::<OldProtocolVersion>(&serde_json::to_string(&new_version).unwrap())
             .unwrap();
     }
+
+    #[test]
+    fn serializing_prestate_trace() {
+        let account = PrestateAccount {
+            balance: Some(1_000.into()),
+            nonce: Some(1),
+            code: None,
+            storage: BTreeMap::from([(H256::zero(), H256::repeat_byte(1))]),
+        };
+        let trace = TracerResult::PrestateTrace(PrestateTrace::Prestate(BTreeMap::from([(
+            Address::repeat_byte(0x11),
+            account.clone(),
+        )])));
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "0x1111111111111111111111111111111111111111": {
+                    "balance": "0x3e8",
+                    "nonce": 1,
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000":
+                            "0x0101010101010101010101010101010101010101010101010101010101010101",
+                    },
+                },
+            })
+        );
+        assert_eq!(serde_json::from_value::<TracerResult>(json).unwrap(), trace);
+
+        let diff = TracerResult::PrestateTrace(PrestateTrace::Diff {
+            pre: BTreeMap::from([(Address::repeat_byte(0x11), account)]),
+            post: BTreeMap::new(),
+        });
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["post"], serde_json::json!({}));
+        assert_eq!(serde_json::from_value::<TracerResult>(json).unwrap(), diff);
+    }
+
+    #[test]
+    fn deserializing_struct_logger_config() {
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "structLogger",
+            "enableMemory": true,
+            "memoryLimit": 16,
+            "limit": 100,
+        }))
+        .unwrap();
+        assert_eq!(config.tracer, SupportedTracers::StructLogger);
+        let struct_logger_config = config.struct_logger_config;
+        assert!(struct_logger_config.enable_memory);
+        assert!(!struct_logger_config.disable_stack);
+        assert_eq!(struct_logger_config.memory_limit, Some(16));
+        assert_eq!(struct_logger_config.limit, 100);
+
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "callTracer",
+            "tracerConfig": { "onlyTopCall": true },
+        }))
+        .unwrap();
+        assert!(config.tracer_config.only_top_call);
+        assert!(!config.struct_logger_config.enable_memory);
+    }
 }
@@ -0,0 +1,439 @@
+//! Types used by the `eth_getProof` method, and client-side verification of Merkle proofs returned by `eth_getProof`
+//! and `zks_getProof`.
+//!
+//! # Mapping to EIP-1186
+//!
+//! ZKsync state is stored in a single sparse Merkle tree rather than in a Merkle Patricia trie per account.
+//! The tree has depth 256 and uses Blake2s-256 for hashing; its leaves are indexed by *hashed* storage keys
+//! (i.e., `blake2s(address_padded_to_32_bytes ++ slot)`, see [`StorageKey::hashed_key()`]). A leaf hash
+//! is `blake2s(leaf_index_be_u64 ++ value)`, where `leaf_index` is the 1-based enumeration index of the key
+//! in the tree (0 for missing keys, which have zero values). Internal nodes are hashed as `blake2s(lhs ++ rhs)`;
+//! bit `i` of the hashed key (interpreted as a little-endian 256-bit integer, with bits counted from the least
+//! significant one) determines whether the node at height `i` on the path from the leaf is a right child.
+//! The tree is only updated at L1 batch boundaries, so proofs are provided for the state after a certain L1 batch.
+//!
+//! Fields of [`EthProof`] are mapped as follows:
+//!
+//! - `storageHash` is the root hash of the entire tree after the L1 batch, since there are no per-account
+//!   storage tries.
+//! - `accountProof` is always empty since there is no account trie. Account fields are stored in slots
+//!   of system contracts: the nonce in `NonceHolder`, the base token balance in `L2BaseToken`, and the bytecode
+//!   hash in `AccountCodeStorage`. Proofs for these slots are provided in `accountFieldProofs`.
+//! - Each `proof` in storage proofs is a Merkle path from the root to the leaf (i.e., in the same order as
+//!   in Ethereum). Hashes of empty subtrees adjacent to the leaf may be omitted from the end of the path.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{L1BatchNumber, H256, U256};
+use zksync_crypto_primitives::hasher::{blake2::Blake2Hasher, Hasher};
+use zksync_utils::{h256_to_u256, u256_to_h256};
+
+use super::StorageProof;
+use crate::{
+    get_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, Address, StorageKey,
+};
+
+/// Depth of the ZKsync sparse Merkle tree.
+const TREE_DEPTH: usize = 256;
+
+/// Proof for a single storage slot returned by `eth_getProof`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStorageProof {
+    /// Storage slot (not hashed).
+    pub key: H256,
+    /// Value of the slot.
+    pub value: U256,
+    /// Merkle path from the tree root to the leaf.
+    pub proof: Vec<H256>,
+    /// Enumeration index of the slot in the tree; 0 if the slot is missing from the tree. Not a part of EIP-1186.
+    pub index: u64,
+}
+
+/// Proofs for system contract slots storing account fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthAccountFieldProofs {
+    /// Proof for the full nonce slot of the account in the `NonceHolder` system contract.
+    pub nonce: EthStorageProof,
+    /// Proof for the base token balance slot of the account in the `L2BaseToken` system contract.
+    pub balance: EthStorageProof,
+    /// Proof for the bytecode hash slot of the account in the `AccountCodeStorage` system contract.
+    pub code_hash: EthStorageProof,
+}
+
+/// Account and storage proofs returned by `eth_getProof`. See the [module docs](self) for the mapping
+/// of ZKsync state onto EIP-1186 fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthProof {
+    pub address: Address,
+    /// Always empty; see [`Self::account_field_proofs`].
+    pub account_proof: Vec<H256>,
+    /// Base token balance of the account.
+    pub balance: U256,
+    /// Versioned bytecode hash of the account; zero for accounts without a deployed contract.
+    pub code_hash: H256,
+    /// Account nonce (i.e., the transaction nonce without the deployment nonce).
+    pub nonce: U256,
+    /// Root hash of the Merkle tree after [`Self::l1_batch_number`].
+    pub storage_hash: H256,
+    pub storage_proof: Vec<EthStorageProof>,
+    /// L1 batch that the proofs are provided for. Not a part of EIP-1186.
+    pub l1_batch_number: L1BatchNumber,
+    /// Proofs for account fields. Not a part of EIP-1186.
+    pub account_field_proofs: EthAccountFieldProofs,
+}
+
+/// Errors that can occur when verifying Merkle proofs.
+#[derive(Debug, thiserror::Error)]
+pub enum ProofVerificationError {
+    #[error("Merkle path has {0} hashes, more than the tree depth ({TREE_DEPTH})")]
+    MerklePathTooLong(usize),
+    #[error("missing storage slot {0:?} has non-zero value")]
+    NonZeroMissingValue(H256),
+    #[error("root hash mismatch for storage slot {key:?}: expected {expected:?}, got {actual:?}")]
+    RootHashMismatch {
+        key: H256,
+        expected: H256,
+        actual: H256,
+    },
+    #[error("proof for account {field} is provided for unexpected slot {actual:?} (expected {expected:?})")]
+    AccountFieldSlotMismatch {
+        field: &'static str,
+        expected: H256,
+        actual: H256,
+    },
+    #[error("account {field} does not match the proven storage value")]
+    AccountFieldMismatch { field: &'static str },
+}
+
+fn empty_subtree_hash(height: usize) -> H256 {
+    static EMPTY_SUBTREE_HASHES: Lazy<Vec<H256>> = Lazy::new(|| {
+        let empty_leaf_hash = Blake2Hasher.hash_bytes(&[0_u8; 40]);
+        std::iter::successors(Some(empty_leaf_hash), |hash| {
+            Some(Blake2Hasher.compress(hash, hash))
+        })
+        .take(TREE_DEPTH + 1)
+        .collect()
+    });
+    EMPTY_SUBTREE_HASHES[height]
+}
+
+fn hash_leaf(value: &H256, leaf_index: u64) -> H256 {
+    let mut bytes = [0_u8; 40];
+    bytes[..8].copy_from_slice(&leaf_index.to_be_bytes());
+    bytes[8..].copy_from_slice(value.as_bytes());
+    Blake2Hasher.hash_bytes(&bytes)
+}
+
+/// Computes the root hash of the Merkle tree from a leaf and its Merkle path (enumerated from the root to the leaf,
+/// as returned by `zks_getProof` and `eth_getProof`).
+pub fn compute_tree_root_hash(
+    storage_key: &StorageKey,
+    value: &H256,
+    leaf_index: u64,
+    merkle_path: &[H256],
+) -> Result<H256, ProofVerificationError> {
+    if merkle_path.len() > TREE_DEPTH {
+        return Err(ProofVerificationError::MerklePathTooLong(merkle_path.len()));
+    }
+    if leaf_index == 0 && !value.is_zero() {
+        return Err(ProofVerificationError::NonZeroMissingValue(
+            *storage_key.key(),
+        ));
+    }
+
+    let hashed_key = storage_key.hashed_key_u256();
+    let empty_hash_count = TREE_DEPTH - merkle_path.len();
+    let full_path = (0..empty_hash_count)
+        .map(empty_subtree_hash)
+        .chain(merkle_path.iter().rev().copied());
+    let mut hash = hash_leaf(value, leaf_index);
+    for (height, adjacent_hash) in full_path.enumerate() {
+        hash = if hashed_key.bit(height) {
+            Blake2Hasher.compress(&adjacent_hash, &hash)
+        } else {
+            Blake2Hasher.compress(&hash, &adjacent_hash)
+        };
+    }
+    Ok(hash)
+}
+
+fn verify_slot(
+    storage_key: &StorageKey,
+    value: &H256,
+    leaf_index: u64,
+    merkle_path: &[H256],
+    root_hash: H256,
+) -> Result<(), ProofVerificationError> {
+    let actual = compute_tree_root_hash(storage_key, value, leaf_index, merkle_path)?;
+    if actual == root_hash {
+        Ok(())
+    } else {
+        Err(ProofVerificationError::RootHashMismatch {
+            key: *storage_key.key(),
+            expected: root_hash,
+            actual,
+        })
+    }
+}
+
+impl StorageProof {
+    /// Verifies this proof (as returned by `zks_getProof`) for a storage slot of `address` against
+    /// the tree root hash of the corresponding L1 batch.
+    pub fn verify(&self, address: Address, root_hash: H256) -> Result<(), ProofVerificationError> {
+        let storage_key = StorageKey::new(AccountTreeId::new(address), self.key);
+        verify_slot(
+            &storage_key,
+            &self.value,
+            self.index,
+            &self.proof,
+            root_hash,
+        )
+    }
+}
+
+impl EthStorageProof {
+    /// Verifies this proof for a storage slot of `address` against the tree root hash.
+    pub fn verify(&self, address: Address, root_hash: H256) -> Result<(), ProofVerificationError> {
+        let storage_key = StorageKey::new(AccountTreeId::new(address), self.key);
+        let value = u256_to_h256(self.value);
+        verify_slot(&storage_key, &value, self.index, &self.proof, root_hash)
+    }
+
+    fn verify_account_field(
+        &self,
+        field: &'static str,
+        expected_key: StorageKey,
+        root_hash: H256,
+    ) -> Result<(), ProofVerificationError> {
+        if self.key != *expected_key.key() {
+            return Err(ProofVerificationError::AccountFieldSlotMismatch {
+                field,
+                expected: *expected_key.key(),
+                actual: self.key,
+            });
+        }
+        self.verify(*expected_key.address(), root_hash)
+    }
+}
+
+impl EthProof {
+    /// Verifies all proofs against [`Self::storage_hash`] and checks that account fields match the proven values.
+    /// The caller is responsible for checking that `storage_hash` is the root hash of the expected L1 batch
+    /// (e.g., by comparing it with the root hash committed on L1).
+    pub fn verify(&self) -> Result<(), ProofVerificationError> {
+        let root_hash = self.storage_hash;
+        let field_proofs = &self.account_field_proofs;
+
+        field_proofs.nonce.verify_account_field(
+            "nonce",
+            get_nonce_key(&self.address),
+            root_hash,
+        )?;
+        let (account_nonce, _) = decompose_full_nonce(field_proofs.nonce.value);
+        if account_nonce != self.nonce {
+            return Err(ProofVerificationError::AccountFieldMismatch { field: "nonce" });
+        }
+
+        field_proofs.balance.verify_account_field(
+            "balance",
+            storage_key_for_eth_balance(&self.address),
+            root_hash,
+        )?;
+        if field_proofs.balance.value != self.balance {
+            return Err(ProofVerificationError::AccountFieldMismatch { field: "balance" });
+        }
+
+        field_proofs.code_hash.verify_account_field(
+            "code hash",
+            get_code_key(&self.address),
+            root_hash,
+        )?;
+        if field_proofs.code_hash.value != h256_to_u256(self.code_hash) {
+            return Err(ProofVerificationError::AccountFieldMismatch { field: "code hash" });
+        }
+
+        for proof in &self.storage_proof {
+            proof.verify(self.address, root_hash)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the hash of a subtree with the specified height containing `leaves`.
+    fn subtree_hash(leaves: &[(U256, H256)], height: usize) -> H256 {
+        if leaves.is_empty() {
+            return empty_subtree_hash(height);
+        }
+        if height == 0 {
+            assert_eq!(leaves.len(), 1);
+            return leaves[0].1;
+        }
+        let (left, right): (Vec<_>, Vec<_>) = leaves
+            .iter()
+            .copied()
+            .partition(|(key, _)| !key.bit(height - 1));
+        Blake2Hasher.compress(
+            &subtree_hash(&left, height - 1),
+            &subtree_hash(&right, height - 1),
+        )
+    }
+
+    /// Builds a tree from `(key, value)` entries; returns the root hash and `(leaf_index, path)` for each entry.
+    fn build_tree(entries: &[(StorageKey, H256)]) -> (H256, Vec<(u64, Vec<H256>)>) {
+        let leaves: Vec<_> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| (key.hashed_key_u256(), hash_leaf(value, i as u64 + 1)))
+            .collect();
+        let root_hash = subtree_hash(&leaves, TREE_DEPTH);
+        let proofs = leaves
+            .iter()
+            .enumerate()
+            .map(|(i, (hashed_key, _))| {
+                let path = (0..TREE_DEPTH).map(|height| {
+                    // Siblings of the node at `height` share key bits above `height` and differ in bit `height`.
+                    let sibling_leaves: Vec<_> = leaves
+                        .iter()
+                        .copied()
+                        .filter(|(key, _)| {
+                            (height + 1..TREE_DEPTH).all(|bit| key.bit(bit) == hashed_key.bit(bit))
+                                && key.bit(height) != hashed_key.bit(height)
+                        })
+                        .collect();
+                    subtree_hash(&sibling_leaves, height)
+                });
+                let mut path: Vec<_> = path.collect();
+                path.reverse();
+                (i as u64 + 1, path)
+            })
+            .collect();
+        (root_hash, proofs)
+    }
+
+    fn storage_proof(
+        key: &StorageKey,
+        value: H256,
+        (index, proof): (u64, Vec<H256>),
+    ) -> EthStorageProof {
+        EthStorageProof {
+            key: *key.key(),
+            value: h256_to_u256(value),
+            proof,
+            index,
+        }
+    }
+
+    #[test]
+    fn verifying_single_slot_proof() {
+        let address = Address::repeat_byte(1);
+        let key = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(2));
+        let value = H256::repeat_byte(3);
+        let root_hash = compute_tree_root_hash(&key, &value, 1, &[]).unwrap();
+
+        let proof = StorageProof {
+            key: *key.key(),
+            proof: vec![],
+            value,
+            index: 1,
+        };
+        proof.verify(address, root_hash).unwrap();
+        proof
+            .verify(Address::repeat_byte(2), root_hash)
+            .unwrap_err();
+
+        let tampered_proof = StorageProof {
+            value: H256::repeat_byte(4),
+            ..proof.clone()
+        };
+        let err = tampered_proof.verify(address, root_hash).unwrap_err();
+        assert!(matches!(
+            err,
+            ProofVerificationError::RootHashMismatch { .. }
+        ));
+
+        let missing_proof = StorageProof { index: 0, ..proof };
+        let err = missing_proof.verify(address, root_hash).unwrap_err();
+        assert!(matches!(
+            err,
+            ProofVerificationError::NonZeroMissingValue(_)
+        ));
+    }
+
+    #[test]
+    fn verifying_eth_proof() {
+        let address = Address::repeat_byte(0x23);
+        let nonce_key = get_nonce_key(&address);
+        let balance_key = storage_key_for_eth_balance(&address);
+        let code_key = get_code_key(&address);
+        let slot_key = StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(1));
+        let full_nonce = U256::from(5) + (U256::from(2) << 128);
+        let entries = [
+            (nonce_key, u256_to_h256(full_nonce)),
+            (balance_key, H256::from_low_u64_be(1_000)),
+            (code_key, H256::repeat_byte(0x11)),
+            (slot_key, H256::repeat_byte(0x42)),
+        ];
+        let (root_hash, mut proofs) = build_tree(&entries);
+        let mut proofs = proofs.drain(..);
+
+        let mut proof = EthProof {
+            address,
+            account_proof: vec![],
+            balance: 1_000.into(),
+            code_hash: H256::repeat_byte(0x11),
+            nonce: 5.into(),
+            storage_hash: root_hash,
+            l1_batch_number: L1BatchNumber(1),
+            account_field_proofs: EthAccountFieldProofs {
+                nonce: storage_proof(&nonce_key, entries[0].1, proofs.next().unwrap()),
+                balance: storage_proof(&balance_key, entries[1].1, proofs.next().unwrap()),
+                code_hash: storage_proof(&code_key, entries[2].1, proofs.next().unwrap()),
+            },
+            storage_proof: vec![storage_proof(
+                &slot_key,
+                entries[3].1,
+                proofs.next().unwrap(),
+            )],
+        };
+        proof.verify().unwrap();
+
+        // Check that trimming empty subtree hashes from the path is supported.
+        let slot_proof = &mut proof.storage_proof[0].proof;
+        let empty_hash_count = slot_proof
+            .iter()
+            .rev()
+            .enumerate()
+            .take_while(|&(height, hash)| *hash == empty_subtree_hash(height))
+            .count();
+        assert!(empty_hash_count > 0);
+        slot_proof.truncate(TREE_DEPTH - empty_hash_count);
+        proof.verify().unwrap();
+
+        proof.nonce = 6.into();
+        let err = proof.verify().unwrap_err();
+        assert!(matches!(
+            err,
+            ProofVerificationError::AccountFieldMismatch { field: "nonce" }
+        ));
+        proof.nonce = 5.into();
+
+        proof.account_field_proofs.balance = proof.storage_proof[0].clone();
+        let err = proof.verify().unwrap_err();
+        assert!(matches!(
+            err,
+            ProofVerificationError::AccountFieldSlotMismatch {
+                field: "balance",
+                ..
+            }
+        ));
+    }
+}
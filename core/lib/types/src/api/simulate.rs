@@ -0,0 +1,194 @@
+//! Types used by the `eth_simulateV1` method.
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, H256, U256, U64};
+
+use super::{state_override::StateOverride, Block, Log};
+use crate::transaction_request::CallRequest;
+
+/// Payload of the `eth_simulateV1` method.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Simulated L2 blocks. Blocks are executed sequentially on top of the base block, so that each block
+    /// observes changes made by the preceding ones.
+    pub block_state_calls: Vec<SimulatedBlockCalls>,
+    /// Whether base token transfers should be reported as logs. Base token transfers always emit
+    /// `Transfer` events on ZKsync, so this flag doesn't influence the result.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether calls should be validated like transactions (nonces, fees, etc.). Not supported.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether full transactions should be returned in simulated blocks. Not supported; only transaction hashes
+    /// are returned.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// Calls executed in a single simulated L2 block together with block and state overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlockCalls {
+    /// Overrides for the block header.
+    #[serde(default)]
+    pub block_overrides: Option<BlockOverrides>,
+    /// State overrides applied before executing calls in the block.
+    #[serde(default)]
+    pub state_overrides: Option<StateOverride>,
+    /// Calls executed in the block in the specified order.
+    #[serde(default)]
+    pub calls: Vec<CallRequest>,
+}
+
+/// Overrides for a simulated L2 block header.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlockOverrides {
+    /// Block number. Must be the next number after the previous block.
+    pub number: Option<U64>,
+    /// Block timestamp in seconds. Must be greater than the timestamp of the previous block.
+    pub time: Option<U64>,
+    /// Base fee per gas. Since the base fee is defined per L1 batch, it can only be set for the first
+    /// simulated block.
+    pub base_fee_per_gas: Option<U256>,
+}
+
+/// Simulated L2 block returned by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    /// Block header. Transactions are represented by their hashes.
+    #[serde(flatten)]
+    pub inner: Block<H256>,
+    /// Results of the calls executed in the block.
+    pub calls: Vec<SimulatedCallResult>,
+}
+
+/// Result of a single call executed in a simulated block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// 1 if the call succeeded, 0 if it was reverted.
+    pub status: U64,
+    /// Data returned by the call, or revert data if the call was reverted.
+    pub return_data: Bytes,
+    /// Gas used by the call.
+    pub gas_used: U256,
+    /// Logs emitted by the call. Empty if the call was reverted.
+    pub logs: Vec<Log>,
+    /// Error information; set only for reverted calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Error information for a reverted simulated call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedCallError {
+    /// Error code. Follows the `eth_call` convention, i.e. 3 for reverted calls.
+    pub code: i64,
+    /// Human-readable error message.
+    pub message: String,
+    /// Encoded revert data, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Address;
+
+    #[test]
+    fn deserializing_simulate_payload() {
+        let json = serde_json::json!({
+            "blockStateCalls": [
+                {
+                    "blockOverrides": { "time": "0x100", "baseFeePerGas": "0x5f5e100" },
+                    "stateOverrides": {
+                        "0x0123456789abcdef0123456789abcdef01234567": { "balance": "0x123" },
+                    },
+                    "calls": [
+                        {
+                            "from": "0x0123456789abcdef0123456789abcdef01234567",
+                            "to": "0x123456789abcdef0123456789abcdef012345678",
+                            "value": "0x1",
+                        },
+                    ],
+                },
+                { "calls": [] },
+            ],
+            "traceTransfers": true,
+        });
+
+        let payload: SimulatePayload = serde_json::from_value(json).unwrap();
+        assert!(payload.trace_transfers);
+        assert!(!payload.validation);
+        assert_eq!(payload.block_state_calls.len(), 2);
+
+        let first_block = &payload.block_state_calls[0];
+        assert_eq!(
+            first_block.block_overrides,
+            Some(BlockOverrides {
+                number: None,
+                time: Some(0x100.into()),
+                base_fee_per_gas: Some(100_000_000.into()),
+            })
+        );
+        let sender: Address = "0x0123456789abcdef0123456789abcdef01234567"
+            .parse()
+            .unwrap();
+        let state_override = first_block.state_overrides.as_ref().unwrap();
+        assert_eq!(
+            state_override.get(&sender).unwrap().balance,
+            Some(0x123.into())
+        );
+        assert_eq!(first_block.calls.len(), 1);
+        assert_eq!(first_block.calls[0].from, Some(sender));
+
+        let second_block = &payload.block_state_calls[1];
+        assert!(second_block.block_overrides.is_none());
+        assert!(second_block.calls.is_empty());
+    }
+
+    #[test]
+    fn unsupported_block_overrides_are_rejected() {
+        let json = serde_json::json!({ "gasLimit": "0x1000" });
+        let err = serde_json::from_value::<BlockOverrides>(json).unwrap_err();
+        assert!(err.to_string().contains("gasLimit"), "{err}");
+    }
+
+    #[test]
+    fn serializing_simulated_block() {
+        let block = SimulatedBlock {
+            inner: Block {
+                number: 5.into(),
+                transactions: vec![H256::repeat_byte(1)],
+                ..Block::default()
+            },
+            calls: vec![SimulatedCallResult {
+                status: 0.into(),
+                return_data: Bytes(vec![1, 2]),
+                gas_used: 21_000.into(),
+                logs: vec![],
+                error: Some(SimulatedCallError {
+                    code: 3,
+                    message: "execution reverted".to_owned(),
+                    data: Some(Bytes(vec![1, 2])),
+                }),
+            }],
+        };
+
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["number"], "0x5");
+        assert_eq!(
+            json["transactions"][0],
+            format!("{:?}", H256::repeat_byte(1))
+        );
+        assert_eq!(json["calls"][0]["status"], "0x0");
+        assert_eq!(json["calls"][0]["returnData"], "0x0102");
+        assert_eq!(json["calls"][0]["error"]["code"], 3);
+
+        let restored: SimulatedBlock = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, block);
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::SLChainId;
 
-use crate::{aggregated_operations::AggregatedActionType, Address, Nonce, H256};
+use crate::{aggregated_operations::AggregatedActionType, Address, Nonce, H256, U256};
 
 /// A forward-compatible `enum` describing a EIP4844 sidecar
 ///
@@ -13,6 +13,15 @@ pub enum EthTxBlobSidecar {
     EthTxBlobSidecarV1(EthTxBlobSidecarV1),
 }
 
+impl EthTxBlobSidecar {
+    /// Returns the number of blobs in this sidecar.
+    pub fn blob_count(&self) -> usize {
+        match self {
+            Self::EthTxBlobSidecarV1(sidecar) => sidecar.blobs.len(),
+        }
+    }
+}
+
 impl From<EthTxBlobSidecarV1> for EthTxBlobSidecar {
     fn from(value: EthTxBlobSidecarV1) -> Self {
         Self::EthTxBlobSidecarV1(value)
@@ -83,6 +92,61 @@ pub struct TxHistory {
     pub sent_at_block: Option<u32>,
 }
 
+/// Placeholder transaction (a zero-value self-transfer) sent by an operator to occupy a nonce.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaceholderTx {
+    pub nonce: Nonce,
+    /// ID of the [`EthTx`] replaced by this placeholder, or `None` if the placeholder fills a nonce gap.
+    pub cancelled_eth_tx_id: Option<u32>,
+    pub tx_hash: H256,
+    pub base_fee_per_gas: u64,
+    pub priority_fee_per_gas: u64,
+    pub blob_base_fee_per_gas: Option<u64>,
+    pub sent_at_block: u32,
+}
+
+/// Actual L1 cost of a confirmed [`EthTx`] computed from its receipt.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EthTxCost {
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+    /// Blob gas used by the transaction; only set for EIP-4844 transactions.
+    pub blob_gas_used: Option<U256>,
+    /// Blob gas price paid by the transaction; only set for EIP-4844 transactions.
+    pub blob_gas_price: Option<U256>,
+}
+
+impl EthTxCost {
+    /// Blob gas used by a single EIP-4844 blob.
+    pub const GAS_PER_BLOB: u64 = 1 << 17;
+
+    /// Returns the maximum cost of a transaction, i.e. the cost if it uses all of `gas_limit` and pays
+    /// the maximum specified fees.
+    pub fn upper_bound(
+        gas_limit: u64,
+        max_fee_per_gas: u64,
+        blob_count: usize,
+        max_fee_per_blob_gas: Option<u64>,
+    ) -> Self {
+        let blob_gas_used = (blob_count > 0).then(|| U256::from(blob_count) * Self::GAS_PER_BLOB);
+        Self {
+            gas_used: gas_limit.into(),
+            effective_gas_price: max_fee_per_gas.into(),
+            blob_gas_used,
+            blob_gas_price: max_fee_per_blob_gas.map(U256::from),
+        }
+    }
+
+    /// Returns the total cost of the transaction in wei.
+    pub fn total_cost(&self) -> U256 {
+        let blob_cost = match (self.blob_gas_used, self.blob_gas_price) {
+            (Some(used), Some(price)) => used * price,
+            _ => U256::zero(),
+        };
+        self.gas_used * self.effective_gas_price + blob_cost
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TxHistoryToSend {
     pub id: u32,
@@ -38,3 +38,17 @@ pub struct DataAvailabilityBlob {
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: DateTime<Utc>,
 }
+
+/// Represents a part of the L1 batch pubdata dispatched as a separate blob because the pubdata
+/// exceeds the blob size limit of the data availability layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataAvailabilityBlobPart {
+    pub l1_batch_number: L1BatchNumber,
+    pub part_index: u32,
+    /// Size of chunks the pubdata was split into; the part covers bytes
+    /// `[part_index * chunk_size, (part_index + 1) * chunk_size)` of the pubdata.
+    pub chunk_size: u64,
+    pub blob_id: String,
+    pub inclusion_data: Option<Vec<u8>>,
+    pub sent_at: DateTime<Utc>,
+}
@@ -7,7 +7,9 @@ use zksync_basic_types::{AccountTreeId, L1BatchNumber, L2BlockNumber, H256};
 use zksync_protobuf::{required, ProtoFmt};
 use zksync_utils::u256_to_h256;
 
-use crate::{utils, web3::Bytes, ProtocolVersionId, StorageKey, StorageValue, U256};
+use crate::{
+    api, tokens::TokenInfo, utils, web3::Bytes, ProtocolVersionId, StorageKey, StorageValue, U256,
+};
 
 /// Information about all snapshots persisted by the node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +30,17 @@ pub enum SnapshotVersion {
     /// Snapshot version made compatible with L1 recovery. Differs from `Version0` by including
     /// hashed keys in storage logs instead of `(address, key)` pairs.
     Version1 = 1,
+    /// Delta snapshot based on another snapshot (either a full `Version1` snapshot, or another delta snapshot).
+    /// Storage logs have the same format as in `Version1`. Uses a separate version so that nodes not aware of deltas
+    /// refuse to recover from them instead of treating them as full snapshots.
+    Version2 = 2,
+}
+
+impl SnapshotVersion {
+    /// Checks whether snapshots with this version are delta snapshots.
+    pub fn is_delta(self) -> bool {
+        matches!(self, Self::Version2)
+    }
 }
 
 /// Storage snapshot metadata. Used in DAL to fetch certain snapshot data.
@@ -36,6 +49,9 @@ pub struct SnapshotMetadata {
     pub version: SnapshotVersion,
     /// L1 batch for the snapshot. The data in the snapshot captures node storage at the end of this batch.
     pub l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot for a delta snapshot; `None` for full snapshots. A delta snapshot
+    /// only contains storage logs and factory deps changed since its base snapshot, and has [`SnapshotVersion::Version2`].
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Path to the factory dependencies blob.
     pub factory_deps_filepath: String,
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
@@ -48,6 +64,11 @@ impl SnapshotMetadata {
     pub fn is_complete(&self) -> bool {
         self.storage_logs_filepaths.iter().all(Option::is_some)
     }
+
+    /// Checks whether this is a delta snapshot.
+    pub fn is_delta(&self) -> bool {
+        self.base_l1_batch_number.is_some()
+    }
 }
 
 /// Snapshot data returned by using JSON-RPC API.
@@ -61,11 +82,39 @@ pub struct SnapshotHeader {
     pub l1_batch_number: L1BatchNumber,
     #[serde(rename = "miniblockNumber")] // legacy naming
     pub l2_block_number: L2BlockNumber,
+    /// L1 batch of the base snapshot if this is a delta snapshot (i.e., has [`SnapshotVersion::Version2`]).
+    /// To recover from a delta snapshot, its base snapshot (which can be a delta snapshot itself) must be applied first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
 }
 
+/// Metadata of a portable snapshot archive stored in the archive manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotArchiveMetadata {
+    /// Headers of all snapshots in the archive. Ordered from the full snapshot to the exported (newest) one;
+    /// all snapshots except for the first one are deltas based on the preceding snapshot.
+    pub snapshots: Vec<SnapshotHeader>,
+    /// Main node data for each snapshot in [`Self::snapshots`] (in the same order). Allows recovering
+    /// from the archive without querying the main node.
+    pub blocks: Vec<SnapshotArchiveBlockData>,
+}
+
+/// Main node data for a single snapshot in a portable snapshot archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotArchiveBlockData {
+    /// Details of the L1 batch the snapshot is created for.
+    pub l1_batch: api::L1BatchDetails,
+    /// Details of the last L2 block in the snapshot L1 batch.
+    pub l2_block: api::BlockDetails,
+    /// Tokens deployed as of the snapshot L2 block.
+    pub tokens: Vec<TokenInfo>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotStorageLogsChunkMetadata {
@@ -39,6 +39,10 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("Invalid simulation request: {0}")]
+    InvalidSimulation(String),
+    #[error("Tracer is not supported: {0}")]
+    UnsupportedTracer(String),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
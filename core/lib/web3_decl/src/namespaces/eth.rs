@@ -3,8 +3,10 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockId, BlockIdVariant, BlockNumber, FeeHistory,
-        Transaction, TransactionVariant,
+        proofs::EthProof,
+        simulate::{SimulatePayload, SimulatedBlock},
+        state_override::StateOverride,
+        BlockId, BlockIdVariant, BlockNumber, FeeHistory, Transaction, TransactionVariant,
     },
     transaction_request::CallRequest,
     Address, H256,
@@ -48,6 +50,13 @@ pub trait EthNamespace {
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256>;
 
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
+
     #[method(name = "gasPrice")]
     async fn gas_price(&self) -> RpcResult<U256>;
 
@@ -119,6 +128,14 @@ pub trait EthNamespace {
         block: Option<BlockIdVariant>,
     ) -> RpcResult<H256>;
 
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<EthProof>;
+
     #[method(name = "getTransactionCount")]
     async fn get_transaction_count(
         &self,
@@ -17,8 +17,10 @@ use crate::client::{ForWeb3Network, L2};
     rpc(client, namespace = "snapshots", client_bounds(Self: ForWeb3Network<Net = L2>))
 )]
 pub trait SnapshotsNamespace {
+    /// Returns L1 batch numbers of all complete snapshots, newest first. Delta snapshots are only included
+    /// if `include_deltas` is set; clients not aware of delta snapshots should never set it.
     #[method(name = "getAllSnapshots")]
-    async fn get_all_snapshots(&self) -> RpcResult<AllSnapshots>;
+    async fn get_all_snapshots(&self, include_deltas: Option<bool>) -> RpcResult<AllSnapshots>;
 
     #[method(name = "getSnapshot")]
     async fn get_snapshot_by_l1_batch_number(
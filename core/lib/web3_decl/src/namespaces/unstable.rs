@@ -2,7 +2,7 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{TeeProof, TransactionExecutionInfo},
+    api::{L1BatchL1Costs, TeeProof, TransactionExecutionInfo},
     tee_types::TeeType,
     L1BatchNumber, H256,
 };
@@ -31,4 +31,11 @@ pub trait UnstableNamespace {
         l1_batch_number: L1BatchNumber,
         tee_type: Option<TeeType>,
     ) -> RpcResult<Vec<TeeProof>>;
+
+    /// Returns actual L1 costs of the specified L1 batch computed from receipts of its commit, prove and execute transactions.
+    #[method(name = "getL1BatchL1Costs")]
+    async fn l1_batch_l1_costs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchL1Costs>>;
 }
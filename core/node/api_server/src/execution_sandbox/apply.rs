@@ -16,11 +16,11 @@ use zksync_multivm::{
         storage::{ReadStorage, StoragePtr, StorageView, WriteStorage},
         L1BatchEnv, L2BlockEnv, SystemEnv, VmInterface,
     },
-    utils::adjust_pubdata_price_for_tx,
+    utils::{adjust_pubdata_price_for_tx, get_batch_base_fee},
     vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, HistoryDisabled},
-    VmInstance,
+    HistoryMode, VmInstance,
 };
-use zksync_state::PostgresStorage;
+use zksync_state::{PostgresStorage, StateArchive, StateArchiveBlockInfo};
 use zksync_system_constants::{
     SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
     SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION, ZKPORTER_IS_AVAILABLE,
@@ -42,8 +42,8 @@ use super::{
     BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
 
-type VmStorageView<'a> = StorageView<StorageWithOverrides<PostgresStorage<'a>>>;
-type BoxedVm<'a> = Box<VmInstance<StorageWithOverrides<PostgresStorage<'a>>, HistoryDisabled>>;
+pub(super) type VmStorageView<'a> = StorageView<StorageWithOverrides<PostgresStorage<'a>>>;
+type BoxedVm<'a, H> = Box<VmInstance<StorageWithOverrides<PostgresStorage<'a>>, H>>;
 
 #[derive(Debug)]
 struct Sandbox<'a> {
@@ -61,6 +61,7 @@ impl<'a> Sandbox<'a> {
         execution_args: &'a TxExecutionArgs,
         block_args: BlockArgs,
         state_override: &StateOverride,
+        deferred_state_overrides: &[StateOverride],
     ) -> anyhow::Result<Sandbox<'a>> {
         let resolve_started_at = Instant::now();
         let resolved_block_info = block_args
@@ -79,24 +80,33 @@ impl<'a> Sandbox<'a> {
                 .schedule_values_update(resolved_block_info.state_l2_block_number);
         }
 
+        // Only use the state archive for blocks pruned in Postgres; otherwise, the archive may lag behind Postgres.
+        let archive = block_args
+            .archived_block
+            .and(shared_args.caches.state_archive());
         let (next_l2_block_info, l2_block_info_to_reset) = Self::load_l2_block_info(
             &mut connection,
+            archive,
             block_args.is_pending_l2_block(),
             &resolved_block_info,
         )
         .await?;
 
-        let storage = PostgresStorage::new_async(
+        let mut storage = PostgresStorage::new_async(
             Handle::current(),
             connection,
             resolved_block_info.state_l2_block_number,
             false,
         )
         .await
-        .context("cannot create `PostgresStorage`")?
-        .with_caches(shared_args.caches.clone());
+        .context("cannot create `PostgresStorage`")?;
+        if archive.is_some() {
+            storage = storage.with_l1_batch_number(resolved_block_info.vm_l1_batch_number);
+        }
+        let storage = storage.with_caches(shared_args.caches.clone());
 
-        let storage_with_overrides = StorageWithOverrides::new(storage, state_override);
+        let storage_with_overrides = StorageWithOverrides::new(storage, state_override)
+            .with_deferred_code_overrides(deferred_state_overrides);
         let storage_view = StorageView::new(storage_with_overrides);
         let (system_env, l1_batch_env) = Self::prepare_env(
             shared_args,
@@ -116,19 +126,31 @@ impl<'a> Sandbox<'a> {
 
     async fn load_l2_block_info(
         connection: &mut Connection<'_, Core>,
+        archive: Option<&StateArchive>,
         is_pending_block: bool,
         resolved_block_info: &ResolvedBlockInfo,
     ) -> anyhow::Result<(L2BlockEnv, Option<StoredL2BlockInfo>)> {
         let mut l2_block_info_to_reset = None;
         let current_l2_block_info = StoredL2BlockInfo::new(
             connection,
+            archive,
             resolved_block_info.state_l2_block_number,
             Some(resolved_block_info.state_l2_block_hash),
         )
         .await
         .context("failed reading L2 block info")?;
 
-        let next_l2_block_info = if is_pending_block {
+        let new_block_timestamp = resolved_block_info.new_l2_block_timestamp;
+        let next_l2_block_info = if let Some(timestamp) = new_block_timestamp {
+            // The state corresponds to the end of the L2 block preceding the new (replayed or simulated) block,
+            // so the new block is started on top of it; the stored L2 block info is already correct and doesn't need to be reset.
+            L2BlockEnv {
+                number: current_l2_block_info.l2_block_number + 1,
+                timestamp,
+                prev_block_hash: current_l2_block_info.l2_block_hash,
+                max_virtual_blocks_to_create: 1,
+            }
+        } else if is_pending_block {
             L2BlockEnv {
                 number: current_l2_block_info.l2_block_number + 1,
                 timestamp: resolved_block_info.l1_batch_timestamp,
@@ -153,6 +175,7 @@ impl<'a> Sandbox<'a> {
             // Actual resetting will be done after `storage_view` is created.
             let prev_l2_block_info = StoredL2BlockInfo::new(
                 connection,
+                archive,
                 resolved_block_info.state_l2_block_number - 1,
                 None,
             )
@@ -262,11 +285,11 @@ impl<'a> Sandbox<'a> {
     }
 
     /// This method is blocking.
-    fn into_vm(
+    fn into_vm<H: HistoryMode>(
         mut self,
         tx: &Transaction,
         adjust_pubdata_price: bool,
-    ) -> (BoxedVm<'a>, StoragePtr<VmStorageView<'a>>) {
+    ) -> (BoxedVm<'a, H>, StoragePtr<VmStorageView<'a>>, SandboxEnv) {
         self.setup_storage_view(tx);
         let protocol_version = self.system_env.version;
         if adjust_pubdata_price {
@@ -278,6 +301,11 @@ impl<'a> Sandbox<'a> {
             );
         };
 
+        let env = SandboxEnv {
+            protocol_version,
+            first_l2_block: self.l1_batch_env.first_l2_block,
+            base_fee: get_batch_base_fee(&self.l1_batch_env, protocol_version.into()),
+        };
         let storage_view = self.storage_view.to_rc_ptr();
         let vm = Box::new(VmInstance::new_with_specific_version(
             self.l1_batch_env,
@@ -286,10 +314,20 @@ impl<'a> Sandbox<'a> {
             protocol_version.into_api_vm_version(),
         ));
 
-        (vm, storage_view)
+        (vm, storage_view, env)
     }
 }
 
+/// Information about the VM environment in the sandbox.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct SandboxEnv {
+    pub protocol_version: ProtocolVersionId,
+    /// Parameters of the first L2 block executed in the VM.
+    pub first_l2_block: L2BlockEnv,
+    /// Base fee used by the VM.
+    pub base_fee: u64,
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(super) fn apply_vm_in_sandbox<T>(
     vm_permit: VmPermit,
@@ -308,6 +346,44 @@ pub(super) fn apply_vm_in_sandbox<T>(
         Transaction,
         ProtocolVersionId,
     ) -> T,
+) -> anyhow::Result<T> {
+    apply_vm_with_storage_in_sandbox::<HistoryDisabled, _>(
+        vm_permit,
+        shared_args,
+        adjust_pubdata_price,
+        execution_args,
+        connection_pool,
+        tx,
+        block_args,
+        state_override,
+        &[],
+        |vm, _, env, tx| apply(vm, tx, env.protocol_version),
+    )
+}
+
+/// Same as [`apply_vm_in_sandbox()`], but additionally provides the closure with access to the VM storage
+/// and environment. This allows changing the storage between transactions executed in the VM.
+///
+/// Code overrides from `deferred_state_overrides` are made available to the VM (but not applied); this allows
+/// applying these overrides during execution. Unlike other methods, the VM history mode is configurable, so that
+/// the closure can roll back transactions.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn apply_vm_with_storage_in_sandbox<H: HistoryMode, T>(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    adjust_pubdata_price: bool,
+    execution_args: &TxExecutionArgs,
+    connection_pool: &ConnectionPool<Core>,
+    tx: Transaction,
+    block_args: BlockArgs,
+    state_override: Option<StateOverride>,
+    deferred_state_overrides: &[StateOverride],
+    apply: impl FnOnce(
+        &mut VmInstance<StorageWithOverrides<PostgresStorage<'_>>, H>,
+        &StoragePtr<VmStorageView<'_>>,
+        SandboxEnv,
+        Transaction,
+    ) -> T,
 ) -> anyhow::Result<T> {
     let stage_started_at = Instant::now();
     let span = tracing::debug_span!("initialization").entered();
@@ -328,9 +404,9 @@ pub(super) fn apply_vm_in_sandbox<T>(
         execution_args,
         block_args,
         state_override.as_ref().unwrap_or(&StateOverride::default()),
+        deferred_state_overrides,
     ))?;
-    let protocol_version = sandbox.system_env.version;
-    let (mut vm, storage_view) = sandbox.into_vm(&tx, adjust_pubdata_price);
+    let (mut vm, storage_view, env) = sandbox.into_vm(&tx, adjust_pubdata_price);
 
     SANDBOX_METRICS.sandbox[&SandboxStage::Initialization].observe(stage_started_at.elapsed());
     span.exit();
@@ -342,7 +418,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
     );
 
     let execution_latency = SANDBOX_METRICS.sandbox[&SandboxStage::Execution].start();
-    let result = apply(&mut vm, tx, protocol_version);
+    let result = apply(&mut vm, &storage_view, env, tx);
     let vm_execution_took = execution_latency.observe();
 
     let memory_metrics = vm.record_vm_memory_metrics();
@@ -364,21 +440,29 @@ struct StoredL2BlockInfo {
 }
 
 impl StoredL2BlockInfo {
-    /// If `l2_block_hash` is `None`, it needs to be fetched from the storage.
+    /// If `l2_block_hash` is `None`, it needs to be fetched from the storage. If `archive` is specified,
+    /// the data is read from it if possible rather than from Postgres.
     async fn new(
         connection: &mut Connection<'_, Core>,
+        archive: Option<&StateArchive>,
         l2_block_number: L2BlockNumber,
         l2_block_hash: Option<H256>,
     ) -> anyhow::Result<Self> {
+        let archive = if let Some(archive) = archive {
+            let block_info = archive.historical_block_info(l2_block_number).await?;
+            block_info.map(|info| (archive, info))
+        } else {
+            None
+        };
+
         let l2_block_info_key = StorageKey::new(
             AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
             SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
         );
-        let l2_block_info = connection
-            .storage_web3_dal()
-            .get_historical_value_unchecked(l2_block_info_key.hashed_key(), l2_block_number)
-            .await
-            .context("failed reading L2 block info from VM state")?;
+        let l2_block_info =
+            Self::read_value(connection, archive, l2_block_info_key, l2_block_number)
+                .await
+                .context("failed reading L2 block info from VM state")?;
         let (l2_block_number_from_state, l2_block_timestamp) =
             unpack_block_info(h256_to_u256(l2_block_info));
 
@@ -386,17 +470,19 @@ impl StoredL2BlockInfo {
             AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
             SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
         );
-        let txs_rolling_hash = connection
-            .storage_web3_dal()
-            .get_historical_value_unchecked(
-                l2_block_txs_rolling_hash_key.hashed_key(),
-                l2_block_number,
-            )
-            .await
-            .context("failed reading transaction rolling hash from VM state")?;
+        let txs_rolling_hash = Self::read_value(
+            connection,
+            archive,
+            l2_block_txs_rolling_hash_key,
+            l2_block_number,
+        )
+        .await
+        .context("failed reading transaction rolling hash from VM state")?;
 
         let l2_block_hash = if let Some(hash) = l2_block_hash {
             hash
+        } else if let Some((_, info)) = archive {
+            info.hash
         } else {
             connection
                 .blocks_web3_dal()
@@ -413,6 +499,26 @@ impl StoredL2BlockInfo {
             txs_rolling_hash,
         })
     }
+
+    async fn read_value(
+        connection: &mut Connection<'_, Core>,
+        archive: Option<(&StateArchive, StateArchiveBlockInfo)>,
+        key: StorageKey,
+        l2_block_number: L2BlockNumber,
+    ) -> anyhow::Result<H256> {
+        if let Some((archive, _)) = archive {
+            let value = archive
+                .historical_value(key.hashed_key(), l2_block_number)
+                .await?;
+            if let Some(value) = value {
+                return Ok(value);
+            }
+        }
+        Ok(connection
+            .storage_web3_dal()
+            .get_historical_value_unchecked(key.hashed_key(), l2_block_number)
+            .await?)
+    }
 }
 
 #[derive(Debug)]
@@ -423,6 +529,9 @@ pub(crate) struct ResolvedBlockInfo {
     l1_batch_timestamp: u64,
     pub(crate) protocol_version: ProtocolVersionId,
     historical_fee_input: Option<BatchFeeInput>,
+    /// Timestamp of a new L2 block started on top of the state L2 block; set only if the block args are used
+    /// for replaying or simulating blocks.
+    new_l2_block_timestamp: Option<u64>,
 }
 
 impl BlockArgs {
@@ -446,7 +555,11 @@ impl BlockArgs {
         &self,
         connection: &mut Connection<'_, Core>,
     ) -> anyhow::Result<ResolvedBlockInfo> {
-        let (state_l2_block_number, vm_l1_batch_number, l1_batch_timestamp);
+        if let Some(info) = &self.archived_block {
+            return Ok(self.resolve_archived_block_info(info));
+        }
+
+        let (mut state_l2_block_number, vm_l1_batch_number, mut l1_batch_timestamp);
 
         let l2_block_header = if self.is_pending_l2_block() {
             vm_l1_batch_number = connection
@@ -494,6 +607,25 @@ impl BlockArgs {
             None
         };
 
+        let mut state_l2_block_hash = l2_block_header.hash;
+        let mut new_l2_block_timestamp = None;
+        if self.replays_block {
+            state_l2_block_number = self.resolved_block_number - 1;
+            state_l2_block_hash = connection
+                .blocks_web3_dal()
+                .get_l2_block_hash(state_l2_block_number)
+                .await
+                .map_err(DalError::generalize)?
+                .with_context(|| {
+                    format!("L2 block #{state_l2_block_number} preceding the replayed block is not in storage")
+                })?;
+            new_l2_block_timestamp = Some(l2_block_header.timestamp);
+        } else if let Some(timestamp) = self.simulated_l2_block_timestamp {
+            new_l2_block_timestamp = Some(timestamp);
+            // The L1 batch timestamp must not exceed the timestamp of the first L2 block in the batch.
+            l1_batch_timestamp = l1_batch_timestamp.min(timestamp);
+        }
+
         // Blocks without version specified are considered to be of `Version9`.
         // TODO: remove `unwrap_or` when protocol version ID will be assigned for each block.
         let protocol_version = l2_block_header
@@ -502,11 +634,31 @@ impl BlockArgs {
 
         Ok(ResolvedBlockInfo {
             state_l2_block_number,
-            state_l2_block_hash: l2_block_header.hash,
+            state_l2_block_hash,
             vm_l1_batch_number,
             l1_batch_timestamp,
             protocol_version,
             historical_fee_input,
+            new_l2_block_timestamp,
         })
     }
+
+    /// Resolves info for a block pruned in Postgres using the state archive. Archived block args are never pending
+    /// or used for replays.
+    fn resolve_archived_block_info(&self, info: &StateArchiveBlockInfo) -> ResolvedBlockInfo {
+        let mut l1_batch_timestamp = info.l1_batch_timestamp;
+        let new_l2_block_timestamp = self.simulated_l2_block_timestamp;
+        if let Some(timestamp) = new_l2_block_timestamp {
+            l1_batch_timestamp = l1_batch_timestamp.min(timestamp);
+        }
+        ResolvedBlockInfo {
+            state_l2_block_number: self.resolved_block_number,
+            state_l2_block_hash: info.hash,
+            vm_l1_batch_number: info.l1_batch_number,
+            l1_batch_timestamp,
+            protocol_version: info.protocol_version,
+            historical_fee_input: Some(info.batch_fee_input),
+            new_l2_block_timestamp,
+        }
+    }
 }
@@ -5,19 +5,28 @@ use tracing::{span, Level};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_multivm::{
     interface::{
-        TransactionExecutionMetrics, TxExecutionMode, VmExecutionResultAndLogs, VmInterface,
+        storage::ReadStorage, ExecutionResult, L2BlockEnv, TransactionExecutionMetrics,
+        TxExecutionMode, VmExecutionResultAndLogs, VmInterface, VmInterfaceHistoryEnabled,
     },
     tracers::StorageInvocations,
+    vm_latest::HistoryEnabled,
     MultiVMTracer,
 };
+use zksync_system_constants::{
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+};
 use zksync_types::{
-    l2::L2Tx, transaction_request::CallOverrides, ExecuteTransactionCommon, Nonce,
-    PackedEthSignature, Transaction, U256,
+    block::L2BlockHasher,
+    fee::Fee,
+    l2::L2Tx,
+    transaction_request::{CallOverrides, PaymasterParams, TransactionRequest},
+    AccountTreeId, Address, ExecuteTransactionCommon, L2BlockNumber, L2ChainId, Nonce,
+    PackedEthSignature, ProtocolVersionId, StorageKey, Transaction, H256, U256,
 };
 
 use super::{
-    apply, testonly::MockTransactionExecutor, vm_metrics, ApiTracer, BlockArgs, TxSharedArgs,
-    VmPermit,
+    apply, storage::apply_deferred_state_override, testonly::MockTransactionExecutor, vm_metrics,
+    ApiTracer, BlockArgs, TxSharedArgs, VmPermit,
 };
 use crate::execution_sandbox::api::state_override::StateOverride;
 
@@ -55,6 +64,17 @@ impl TxExecutionArgs {
         }
     }
 
+    fn for_replay(vm_execution_cache_misses_limit: Option<usize>) -> Self {
+        let missed_storage_invocation_limit = vm_execution_cache_misses_limit.unwrap_or(usize::MAX);
+        Self {
+            execution_mode: TxExecutionMode::VerifyExecute,
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            enforced_base_fee: None,
+            missed_storage_invocation_limit,
+        }
+    }
+
     pub fn for_gas_estimate(
         vm_execution_cache_misses_limit: Option<usize>,
         tx: &Transaction,
@@ -79,6 +99,31 @@ impl TxExecutionArgs {
     }
 }
 
+/// L2 block simulated in the sandbox.
+#[derive(Debug)]
+pub(crate) struct SimulatedL2Block {
+    /// Block timestamp. Must be greater than the timestamp of the previous block.
+    pub timestamp: u64,
+    /// State override applied before executing transactions in the block.
+    pub state_override: Option<StateOverride>,
+    /// Transactions executed in the block. Transactions are executed in the `eth_call` mode.
+    pub txs: Vec<L2Tx>,
+}
+
+/// Output of an L2 block simulated in the sandbox.
+#[derive(Debug)]
+pub(crate) struct SimulatedL2BlockOutput {
+    /// Environment of the block, incl. its number and the hash of the previous block.
+    pub env: L2BlockEnv,
+    /// Hash of the block.
+    pub hash: H256,
+    /// Base fee used for the block.
+    pub base_fee: u64,
+    /// Hashes and execution results for all executed transactions. Changes made by halted transactions
+    /// are rolled back, so they don't influence subsequent transactions.
+    pub results: Vec<(H256, VmExecutionResultAndLogs)>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct TransactionExecutionOutput {
     /// Output of the VM.
@@ -164,6 +209,260 @@ impl TransactionExecutor {
         })
     }
 
+    /// Re-executes transactions from a sealed L2 block on top of the state preceding this block.
+    /// Transactions are executed in the provided order in a single VM, each with its own set of custom tracers,
+    /// so that each transaction observes the state changes made by the preceding ones.
+    ///
+    /// `block_args` are expected to be created using [`BlockArgs::for_replay()`].
+    #[tracing::instrument(skip_all)]
+    pub async fn replay_txs_in_sandbox(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        block_args: BlockArgs,
+        txs: Vec<(Transaction, Vec<ApiTracer>)>,
+        vm_execution_cache_misses_limit: Option<usize>,
+    ) -> anyhow::Result<Vec<VmExecutionResultAndLogs>> {
+        if let Self::Mock(mock_executor) = self {
+            return txs
+                .iter()
+                .map(|(tx, tracers)| mock_executor.replay_tx(tx, tracers, &block_args))
+                .collect();
+        }
+        let Some((first_tx, _)) = txs.first() else {
+            return Ok(vec![]);
+        };
+        let first_tx = first_tx.clone();
+        let execution_args = TxExecutionArgs::for_replay(vm_execution_cache_misses_limit);
+
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "replay_in_sandbox").entered();
+            let result = apply::apply_vm_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx,
+                block_args,
+                None,
+                |vm, _, _| {
+                    txs.into_iter()
+                        .map(|(tx, custom_tracers)| {
+                            let storage_invocation_tracer = StorageInvocations::new(
+                                execution_args.missed_storage_invocation_limit,
+                            );
+                            let custom_tracers: Vec<_> = custom_tracers
+                                .into_iter()
+                                .map(|tracer| tracer.into_boxed())
+                                .chain(vec![storage_invocation_tracer.into_tracer_pointer()])
+                                .collect();
+                            let (_, result) = vm.inspect_transaction_with_bytecode_compression(
+                                custom_tracers.into(),
+                                tx,
+                                true,
+                            );
+                            result
+                        })
+                        .collect()
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("transaction replay panicked")?
+    }
+
+    /// Simulates a sequence of L2 blocks on top of the state of the block specified by `block_args`. Blocks are executed
+    /// in a single VM, so that each block observes the state changes made by the preceding ones. Blocks may have
+    /// no transactions; such blocks still advance the block number and timestamp.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all)]
+    pub async fn simulate_blocks_in_sandbox(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        block_args: BlockArgs,
+        blocks: Vec<SimulatedL2Block>,
+        enforced_base_fee: Option<u64>,
+        vm_execution_cache_misses_limit: Option<usize>,
+    ) -> anyhow::Result<Vec<SimulatedL2BlockOutput>> {
+        let chain_id = shared_args.chain_id;
+        let blocks = blocks
+            .into_iter()
+            .map(|block| {
+                let txs = block
+                    .txs
+                    .into_iter()
+                    .map(|mut tx| {
+                        if tx.common_data.signature.is_empty() {
+                            tx.common_data.signature =
+                                PackedEthSignature::default().serialize_packed().into();
+                        }
+                        Ok((simulated_tx_hash(&tx, chain_id)?, tx))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok((block.timestamp, block.state_override, txs))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if let Self::Mock(mock_executor) = self {
+            let mut prev_block_hash = H256::zero();
+            let mut outputs = Vec::with_capacity(blocks.len());
+            for (i, (timestamp, _, txs)) in blocks.into_iter().enumerate() {
+                let env = L2BlockEnv {
+                    number: block_args.simulation_base_block_number().0 + 1 + i as u32,
+                    timestamp,
+                    prev_block_hash,
+                    max_virtual_blocks_to_create: 1,
+                };
+                let mut hasher =
+                    L2BlockHasher::new(L2BlockNumber(env.number), timestamp, prev_block_hash);
+                let results = txs
+                    .into_iter()
+                    .map(|(hash, tx)| {
+                        hasher.push_tx_hash(hash);
+                        Ok((hash, mock_executor.execute_tx(&tx.into(), &block_args)?.vm))
+                    })
+                    .collect::<anyhow::Result<_>>()?;
+                prev_block_hash = hasher.finalize(ProtocolVersionId::latest());
+                outputs.push(SimulatedL2BlockOutput {
+                    env,
+                    hash: prev_block_hash,
+                    base_fee: enforced_base_fee.unwrap_or(0),
+                    results,
+                });
+            }
+            return Ok(outputs);
+        }
+
+        let (first_timestamp, first_state_override) = blocks
+            .first()
+            .map(|(timestamp, state_override, _)| (*timestamp, state_override.clone()))
+            .context("no blocks to simulate")?;
+        // The transaction is only used to initialize the VM; if no block has transactions, use a no-op one.
+        let first_tx = blocks
+            .iter()
+            .find_map(|(_, _, txs)| txs.first())
+            .map(|(_, tx)| tx.clone())
+            .unwrap_or_else(|| {
+                L2Tx::new(
+                    Address::zero(),
+                    vec![],
+                    Nonce(0),
+                    Fee::default(),
+                    Address::zero(),
+                    U256::zero(),
+                    vec![],
+                    PaymasterParams::default(),
+                )
+            });
+        let deferred_state_overrides: Vec<_> = blocks
+            .iter()
+            .skip(1)
+            .filter_map(|(_, state_override, _)| state_override.clone())
+            .collect();
+        let block_args = block_args.for_simulation(first_timestamp);
+        let execution_args =
+            TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit);
+
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "simulate_in_sandbox").entered();
+            let result = apply::apply_vm_with_storage_in_sandbox::<HistoryEnabled, _>(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx.into(),
+                block_args,
+                first_state_override,
+                &deferred_state_overrides,
+                |vm, storage, env, _| {
+                    let rolling_hash_key = StorageKey::new(
+                        AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+                        SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+                    );
+                    let mut outputs: Vec<SimulatedL2BlockOutput> = Vec::with_capacity(blocks.len());
+                    for (timestamp, state_override, txs) in blocks {
+                        let block_env = if let Some(prev_output) = outputs.last() {
+                            let block_env = L2BlockEnv {
+                                number: prev_output.env.number + 1,
+                                timestamp,
+                                prev_block_hash: prev_output.hash,
+                                max_virtual_blocks_to_create: 1,
+                            };
+                            vm.start_new_l2_block(block_env);
+                            if let Some(state_override) = &state_override {
+                                apply_deferred_state_override(
+                                    &mut *storage.borrow_mut(),
+                                    state_override,
+                                )?;
+                            }
+                            block_env
+                        } else {
+                            // The first block is started on VM initialization, and its state override is applied
+                            // to the VM storage.
+                            env.first_l2_block
+                        };
+
+                        let mut results = Vec::with_capacity(txs.len());
+                        let mut has_included_txs = false;
+                        for (tx_hash, tx) in txs {
+                            let storage_invocation_tracer = StorageInvocations::new(
+                                execution_args.missed_storage_invocation_limit,
+                            );
+                            vm.make_snapshot();
+                            let (_, result) = vm.inspect_transaction_with_bytecode_compression(
+                                vec![storage_invocation_tracer.into_tracer_pointer()].into(),
+                                tx.into(),
+                                true,
+                            );
+                            // A halted transaction leaves the VM in an unusable state, so it's rolled back
+                            // similarly to how the state keeper treats rejected transactions.
+                            if matches!(result.result, ExecutionResult::Halt { .. }) {
+                                vm.rollback_to_the_latest_snapshot();
+                            } else {
+                                vm.pop_snapshot_no_rollback();
+                                has_included_txs = true;
+                            }
+                            results.push((tx_hash, result));
+                        }
+
+                        // The rolling hash of transactions is maintained by the system context
+                        // and is reset only when the first transaction in a block is processed.
+                        let txs_rolling_hash = if !has_included_txs {
+                            H256::zero()
+                        } else {
+                            storage.borrow_mut().read_value(&rolling_hash_key)
+                        };
+                        let hash = L2BlockHasher::hash(
+                            L2BlockNumber(block_env.number),
+                            block_env.timestamp,
+                            block_env.prev_block_hash,
+                            txs_rolling_hash,
+                            env.protocol_version,
+                        );
+                        outputs.push(SimulatedL2BlockOutput {
+                            env: block_env,
+                            hash,
+                            base_fee: env.base_fee,
+                            results,
+                        });
+                    }
+                    Ok(outputs)
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("block simulation panicked")??
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn execute_tx_eth_call(
         &self,
@@ -202,3 +501,12 @@ impl TransactionExecutor {
         Ok(output.vm)
     }
 }
+
+/// Computes the hash of a simulated transaction in the same way as the VM does.
+fn simulated_tx_hash(tx: &L2Tx, chain_id: L2ChainId) -> anyhow::Result<H256> {
+    let mut request = TransactionRequest::from(tx.clone());
+    request.chain_id = Some(chain_id.as_u64());
+    request
+        .get_tx_hash()
+        .context("cannot compute hash for simulated transaction")
+}
@@ -6,8 +6,11 @@ use std::{
 use anyhow::Context as _;
 use rand::{thread_rng, Rng};
 use tokio::runtime::Handle;
-use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
-use zksync_state::PostgresStorageCaches;
+use zksync_dal::{
+    pruning_dal::{HistoryPruningInfo, PruningInfo},
+    Connection, Core, CoreDal, DalError,
+};
+use zksync_state::{PostgresStorageCaches, StateArchive, StateArchiveBlockInfo};
 use zksync_types::{
     api, fee_model::BatchFeeInput, AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId,
 };
@@ -15,8 +18,8 @@ use zksync_types::{
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{TransactionExecutor, TxExecutionArgs},
-    tracers::ApiTracer,
+    execute::{SimulatedL2Block, SimulatedL2BlockOutput, TransactionExecutor, TxExecutionArgs},
+    tracers::{ApiTracer, PrestateTracerResult, StructLoggerResult},
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
 };
@@ -190,9 +193,10 @@ impl TxSharedArgs {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct BlockStartInfoInner {
     info: PruningInfo,
+    history_info: Option<Arc<HistoryPruningInfo>>,
     cached_at: Instant,
 }
 
@@ -218,6 +222,7 @@ impl BlockStartInfoInner {
 pub(crate) struct BlockStartInfo {
     cached_pruning_info: Arc<RwLock<BlockStartInfoInner>>,
     max_cache_age: Duration,
+    state_archive: Option<StateArchive>,
 }
 
 impl BlockStartInfo {
@@ -226,59 +231,88 @@ impl BlockStartInfo {
         max_cache_age: Duration,
     ) -> anyhow::Result<Self> {
         let info = storage.pruning_dal().get_pruning_info().await?;
+        let history_info = storage.pruning_dal().get_history_pruning_info().await?;
         Ok(Self {
             cached_pruning_info: Arc::new(RwLock::new(BlockStartInfoInner {
                 info,
+                history_info: history_info.map(Arc::new),
                 cached_at: Instant::now(),
             })),
             max_cache_age,
+            state_archive: None,
         })
     }
 
-    fn copy_inner(&self) -> BlockStartInfoInner {
-        *self
-            .cached_pruning_info
+    /// Sets the historical state archive. L2 blocks pruned in Postgres, but covered by the archive
+    /// can be used for VM execution (e.g., `eth_call`).
+    #[must_use]
+    pub fn with_state_archive(self, state_archive: Option<StateArchive>) -> Self {
+        Self {
+            state_archive,
+            ..self
+        }
+    }
+
+    fn clone_inner(&self) -> BlockStartInfoInner {
+        self.cached_pruning_info
             .read()
             .expect("BlockStartInfo is poisoned")
+            .clone()
     }
 
     async fn update_cache(
         &self,
         storage: &mut Connection<'_, Core>,
         now: Instant,
-    ) -> anyhow::Result<PruningInfo> {
+    ) -> anyhow::Result<BlockStartInfoInner> {
         let info = storage.pruning_dal().get_pruning_info().await?;
+        let history_info = storage.pruning_dal().get_history_pruning_info().await?;
 
         let mut new_cached_pruning_info = self
             .cached_pruning_info
             .write()
             .map_err(|_| anyhow::anyhow!("BlockStartInfo is poisoned"))?;
-        Ok(if new_cached_pruning_info.cached_at < now {
+        if new_cached_pruning_info.cached_at < now {
             *new_cached_pruning_info = BlockStartInfoInner {
                 info,
+                history_info: history_info.map(Arc::new),
                 cached_at: now,
             };
-            info
-        } else {
-            // Got a newer cache already; no need to update it again.
-            new_cached_pruning_info.info
-        })
+        }
+        // Otherwise, got a newer cache already; no need to update it again.
+        Ok(new_cached_pruning_info.clone())
     }
 
-    async fn get_pruning_info(
+    async fn get_inner(
         &self,
         storage: &mut Connection<'_, Core>,
-    ) -> anyhow::Result<PruningInfo> {
-        let inner = self.copy_inner();
+    ) -> anyhow::Result<BlockStartInfoInner> {
+        let inner = self.clone_inner();
         let now = Instant::now();
         if inner.is_expired(now, self.max_cache_age) {
             // Multiple threads may execute this query if we're very unlucky
             self.update_cache(storage, now).await
         } else {
-            Ok(inner.info)
+            Ok(inner)
         }
     }
 
+    async fn get_pruning_info(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<PruningInfo> {
+        Ok(self.get_inner(storage).await?.info)
+    }
+
+    /// Returns information about history pruning (i.e., pruning of events, call traces and transaction execution info
+    /// while retaining blocks), or `None` if history was never pruned.
+    pub async fn history_pruning_info(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<Arc<HistoryPruningInfo>>> {
+        Ok(self.get_inner(storage).await?.history_info)
+    }
+
     pub async fn first_l2_block(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -328,6 +362,35 @@ impl BlockStartInfo {
             _ => Ok(()),
         }
     }
+
+    /// Returns information about an L2 block with the specified ID if the block is pruned in Postgres,
+    /// but is covered by the state archive. In this case, the block can be used for VM execution despite being pruned.
+    async fn archived_block(
+        &self,
+        block: api::BlockId,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<Option<(L2BlockNumber, StateArchiveBlockInfo)>, BlockArgsError> {
+        let Some(archive) = &self.state_archive else {
+            return Ok(None);
+        };
+        let api::BlockId::Number(api::BlockNumber::Number(number)) = block else {
+            return Ok(None);
+        };
+        let first_l2_block = self
+            .first_l2_block(storage)
+            .await
+            .map_err(BlockArgsError::Database)?;
+        if number >= first_l2_block.0.into() {
+            return Ok(None);
+        }
+        // `as_u32()` is safe: `number` is less than `first_l2_block`
+        let number = L2BlockNumber(number.as_u32());
+        let block_info = archive
+            .historical_block_info(number)
+            .await
+            .map_err(BlockArgsError::Database)?;
+        Ok(block_info.map(|info| (number, info)))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -346,6 +409,14 @@ pub(crate) struct BlockArgs {
     block_id: api::BlockId,
     resolved_block_number: L2BlockNumber,
     l1_batch_timestamp_s: Option<u64>,
+    /// If set, the VM is initialized with the state *before* the resolved L2 block, so that transactions
+    /// from this block can be re-executed in their original context.
+    replays_block: bool,
+    /// If set, the VM starts a new L2 block with the specified timestamp on top of the state at the end
+    /// of the resolved block (or the latest sealed block for pending block args).
+    simulated_l2_block_timestamp: Option<u64>,
+    /// Information about the resolved block if it's pruned in Postgres and is taken from the state archive.
+    archived_block: Option<StateArchiveBlockInfo>,
 }
 
 impl BlockArgs {
@@ -355,6 +426,9 @@ impl BlockArgs {
             block_id,
             resolved_block_number,
             l1_batch_timestamp_s: None,
+            replays_block: false,
+            simulated_l2_block_timestamp: None,
+            archived_block: None,
         })
     }
 
@@ -364,6 +438,20 @@ impl BlockArgs {
         block_id: api::BlockId,
         start_info: &BlockStartInfo,
     ) -> Result<Self, BlockArgsError> {
+        // Pruned blocks covered by the state archive are resolved using the archive.
+        if let Some((resolved_block_number, info)) =
+            start_info.archived_block(block_id, connection).await?
+        {
+            return Ok(Self {
+                block_id,
+                resolved_block_number,
+                l1_batch_timestamp_s: Some(info.l1_batch_timestamp),
+                replays_block: false,
+                simulated_l2_block_timestamp: None,
+                archived_block: Some(info),
+            });
+        }
+
         // We need to check that `block_id` is present in Postgres or can be present in the future
         // (i.e., it does not refer to a pruned block). If called for a pruned block, the returned value
         // (specifically, `l1_batch_timestamp_s`) will be nonsensical.
@@ -401,9 +489,60 @@ impl BlockArgs {
             block_id,
             resolved_block_number,
             l1_batch_timestamp_s: Some(l1_batch_timestamp),
+            replays_block: false,
+            simulated_l2_block_timestamp: None,
+            archived_block: None,
+        })
+    }
+
+    /// Loads block information necessary to replay transactions from the specified sealed L2 block.
+    /// The VM state will correspond to the end of the previous L2 block, so the previous block must not be pruned.
+    pub async fn for_replay(
+        connection: &mut Connection<'_, Core>,
+        block_number: L2BlockNumber,
+        start_info: &BlockStartInfo,
+    ) -> Result<Self, BlockArgsError> {
+        let Some(prev_block_number) = block_number.0.checked_sub(1) else {
+            return Err(BlockArgsError::Database(anyhow::anyhow!(
+                "genesis L2 block cannot be replayed"
+            )));
+        };
+        start_info
+            .ensure_not_pruned_block(
+                api::BlockId::Number(api::BlockNumber::Number(prev_block_number.into())),
+                connection,
+            )
+            .await?;
+
+        let block_id = api::BlockId::Number(api::BlockNumber::Number(block_number.0.into()));
+        let block_args = Self::new(connection, block_id, start_info).await?;
+        Ok(Self {
+            replays_block: true,
+            ..block_args
         })
     }
 
+    /// Modifies these block args so that the VM starts a new L2 block with the specified timestamp on top
+    /// of the block state, rather than executing transactions in the context of the resolved block.
+    /// This is used to simulate L2 blocks following the resolved one. For pending block args,
+    /// the new block is started on top of the latest sealed L2 block.
+    pub fn for_simulation(self, timestamp: u64) -> Self {
+        Self {
+            simulated_l2_block_timestamp: Some(timestamp),
+            ..self
+        }
+    }
+
+    /// Returns the number of the L2 block, on top of which a new block is started if the block args
+    /// are modified using [`Self::for_simulation()`].
+    pub fn simulation_base_block_number(&self) -> L2BlockNumber {
+        if self.block_id == api::BlockId::Number(api::BlockNumber::Pending) {
+            L2BlockNumber(self.resolved_block_number.0.saturating_sub(1))
+        } else {
+            self.resolved_block_number
+        }
+    }
+
     pub fn resolved_block_number(&self) -> L2BlockNumber {
         self.resolved_block_number
     }
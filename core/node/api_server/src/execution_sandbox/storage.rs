@@ -5,7 +5,8 @@ use std::{
     fmt,
 };
 
-use zksync_multivm::interface::storage::ReadStorage;
+use anyhow::Context as _;
+use zksync_multivm::interface::storage::{ReadStorage, WriteStorage};
 use zksync_types::{
     api::state_override::{OverrideState, StateOverride},
     get_code_key, get_nonce_key,
@@ -36,6 +37,22 @@ impl<S: ReadStorage> StorageWithOverrides<S> {
         this
     }
 
+    /// Makes bytecodes from code overrides in `state_overrides` available to the VM without applying the overrides.
+    /// This allows applying these overrides during VM execution via [`apply_deferred_state_override()`].
+    pub(super) fn with_deferred_code_overrides(
+        mut self,
+        state_overrides: &[StateOverride],
+    ) -> Self {
+        let codes = state_overrides
+            .iter()
+            .flat_map(StateOverride::iter)
+            .filter_map(|(_, overrides)| overrides.code.as_ref());
+        for code in codes {
+            self.store_factory_dep(code.hash(), code.clone().into_bytes());
+        }
+        self
+    }
+
     fn apply_state_override(&mut self, state_override: &StateOverride) {
         for (account, overrides) in state_override.iter() {
             if let Some(balance) = overrides.balance {
@@ -90,6 +107,55 @@ impl<S: ReadStorage> StorageWithOverrides<S> {
     }
 }
 
+/// Applies a state override to the storage that is already used by the VM. Bytecodes for code overrides
+/// must be registered beforehand using [`StorageWithOverrides::with_deferred_code_overrides()`].
+///
+/// Unlike overrides applied in [`StorageWithOverrides`], full account state replacement is not supported
+/// since the storage cannot enumerate account slots.
+pub(super) fn apply_deferred_state_override(
+    storage: &mut impl WriteStorage,
+    state_override: &StateOverride,
+) -> anyhow::Result<()> {
+    for (account, overrides) in state_override.iter() {
+        if let Some(balance) = overrides.balance {
+            let balance_key = storage_key_for_eth_balance(account);
+            storage.set_value(balance_key, u256_to_h256(balance));
+        }
+
+        if let Some(nonce) = overrides.nonce {
+            let nonce_key = get_nonce_key(account);
+            let full_nonce = storage.read_value(&nonce_key);
+            let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+            let new_full_nonce = u256_to_h256(nonces_to_full_nonce(nonce, deployment_nonce));
+            storage.set_value(nonce_key, new_full_nonce);
+        }
+
+        if let Some(code) = &overrides.code {
+            let code_hash = code.hash();
+            storage.load_factory_dep(code_hash).with_context(|| {
+                format!("bytecode for code override of {account:?} is not registered")
+            })?;
+            storage.set_value(get_code_key(account), code_hash);
+        }
+
+        match &overrides.state {
+            Some(OverrideState::State(_)) => {
+                anyhow::bail!(
+                    "full state override for {account:?} cannot be applied to the used storage"
+                );
+            }
+            Some(OverrideState::StateDiff(state_diff)) => {
+                let account = AccountTreeId::new(*account);
+                for (&slot, &value) in state_diff {
+                    storage.set_value(StorageKey::new(account, slot), value);
+                }
+            }
+            None => { /* do nothing */ }
+        }
+    }
+    Ok(())
+}
+
 impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
     fn read_value(&mut self, key: &StorageKey) -> StorageValue {
         if let Some(value) = self.overridden_slots.get(key) {
@@ -119,7 +185,7 @@ impl<S: ReadStorage + fmt::Debug> ReadStorage for StorageWithOverrides<S> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_multivm::interface::storage::InMemoryStorage;
+    use zksync_multivm::interface::storage::{InMemoryStorage, StorageView};
     use zksync_types::{
         api::state_override::{Bytecode, OverrideAccount},
         Address,
@@ -198,4 +264,86 @@ mod tests {
         let erased_value = storage.read_value(&erased_key);
         assert_eq!(erased_value, H256::zero());
     }
+
+    #[test]
+    fn applying_deferred_state_override() {
+        let bytecode = Bytecode::new((0..32).collect()).unwrap();
+        let code_hash = bytecode.hash();
+        let deferred_overrides = StateOverride::new(HashMap::from([
+            (
+                Address::repeat_byte(1),
+                OverrideAccount {
+                    balance: Some(1.into()),
+                    nonce: Some(3.into()),
+                    ..OverrideAccount::default()
+                },
+            ),
+            (
+                Address::repeat_byte(2),
+                OverrideAccount {
+                    code: Some(bytecode),
+                    state: Some(OverrideState::StateDiff(HashMap::from([(
+                        H256::zero(),
+                        H256::repeat_byte(1),
+                    )]))),
+                    ..OverrideAccount::default()
+                },
+            ),
+        ]));
+
+        let mut storage = InMemoryStorage::default();
+        let nonce_key = get_nonce_key(&Address::repeat_byte(1));
+        // Set the deployment nonce to 5.
+        let full_nonce = nonces_to_full_nonce(1.into(), 5.into());
+        storage.set_value(nonce_key, u256_to_h256(full_nonce));
+        let retained_key = StorageKey::new(
+            AccountTreeId::new(Address::repeat_byte(2)),
+            H256::from_low_u64_be(1),
+        );
+        storage.set_value(retained_key, H256::repeat_byte(0xfe));
+        let storage = StorageWithOverrides::new(storage, &StateOverride::default())
+            .with_deferred_code_overrides(&[deferred_overrides.clone()]);
+        let mut storage = StorageView::new(storage);
+
+        // Deferred overrides must not be applied until requested.
+        let code_key = get_code_key(&Address::repeat_byte(2));
+        assert_eq!(storage.read_value(&code_key), H256::zero());
+        assert!(storage.load_factory_dep(code_hash).is_some());
+
+        apply_deferred_state_override(&mut storage, &deferred_overrides).unwrap();
+
+        let balance = storage.read_value(&storage_key_for_eth_balance(&Address::repeat_byte(1)));
+        assert_eq!(balance, H256::from_low_u64_be(1));
+        let full_nonce = h256_to_u256(storage.read_value(&nonce_key));
+        assert_eq!(decompose_full_nonce(full_nonce), (3.into(), 5.into()));
+        assert_eq!(storage.read_value(&code_key), code_hash);
+        let overridden_key =
+            StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        assert_eq!(storage.read_value(&overridden_key), H256::repeat_byte(1));
+        assert_eq!(storage.read_value(&retained_key), H256::repeat_byte(0xfe));
+
+        // Full state overrides and unregistered bytecodes cannot be applied.
+        let full_state_override = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(3),
+            OverrideAccount {
+                state: Some(OverrideState::State(HashMap::new())),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let err = apply_deferred_state_override(&mut storage, &full_state_override).unwrap_err();
+        assert!(err.to_string().contains("full state override"), "{err:#}");
+
+        let unregistered_code_override = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(3),
+            OverrideAccount {
+                code: Some(Bytecode::new((32..64).collect()).unwrap()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let err =
+            apply_deferred_state_override(&mut storage, &unregistered_code_override).unwrap_err();
+        assert!(err.to_string().contains("not registered"), "{err:#}");
+        let code_key = get_code_key(&Address::repeat_byte(3));
+        assert_eq!(storage.read_value(&code_key), H256::zero());
+    }
 }
@@ -1,21 +1,28 @@
 use std::fmt;
 
-use zksync_multivm::interface::{
-    ExecutionResult, TransactionExecutionMetrics, VmExecutionResultAndLogs,
+use zksync_multivm::{
+    interface::{ExecutionResult, TransactionExecutionMetrics, VmExecutionResultAndLogs},
+    tracers::{prestate_tracer, StructLog, StructLoggerConfig},
 };
 use zksync_types::{l2::L2Tx, ExecuteTransactionCommon, Transaction};
 
 use super::{
     execute::{TransactionExecutionOutput, TransactionExecutor},
     validate::ValidationError,
-    BlockArgs,
+    ApiTracer, BlockArgs,
 };
 
 type TxResponseFn = dyn Fn(&Transaction, &BlockArgs) -> VmExecutionResultAndLogs + Send + Sync;
+type PrestateResponseFn =
+    dyn Fn(&Transaction) -> (prestate_tracer::State, prestate_tracer::State) + Send + Sync;
+type StructLogResponseFn =
+    dyn Fn(&Transaction, &StructLoggerConfig) -> Vec<StructLog> + Send + Sync;
 
 pub struct MockTransactionExecutor {
     call_responses: Box<TxResponseFn>,
     tx_responses: Box<TxResponseFn>,
+    prestate_responses: Option<Box<PrestateResponseFn>>,
+    struct_log_responses: Option<Box<StructLogResponseFn>>,
 }
 
 impl fmt::Debug for MockTransactionExecutor {
@@ -38,6 +45,8 @@ impl Default for MockTransactionExecutor {
             tx_responses: Box::new(|tx, _| {
                 panic!("Unexpect transaction call: {tx:?}");
             }),
+            prestate_responses: None,
+            struct_log_responses: None,
         }
     }
 }
@@ -84,6 +93,27 @@ impl MockTransactionExecutor {
         self.tx_responses = Box::new(responses);
     }
 
+    /// Sets the output of prestate tracers for replayed transactions.
+    #[cfg(test)]
+    pub(crate) fn set_prestate_responses<F>(&mut self, responses: F)
+    where
+        F: Fn(&Transaction) -> (prestate_tracer::State, prestate_tracer::State)
+            + 'static
+            + Send
+            + Sync,
+    {
+        self.prestate_responses = Some(Box::new(responses));
+    }
+
+    /// Sets the output of struct loggers for replayed transactions.
+    #[cfg(test)]
+    pub(crate) fn set_struct_log_responses<F>(&mut self, responses: F)
+    where
+        F: Fn(&Transaction, &StructLoggerConfig) -> Vec<StructLog> + 'static + Send + Sync,
+    {
+        self.struct_log_responses = Some(Box::new(responses));
+    }
+
     pub(crate) fn validate_tx(
         &self,
         tx: L2Tx,
@@ -113,6 +143,26 @@ impl MockTransactionExecutor {
         Ok(output)
     }
 
+    pub(crate) fn replay_tx(
+        &self,
+        tx: &Transaction,
+        tracers: &[ApiTracer],
+        block_args: &BlockArgs,
+    ) -> anyhow::Result<VmExecutionResultAndLogs> {
+        for tracer in tracers {
+            match (tracer, &self.prestate_responses, &self.struct_log_responses) {
+                (ApiTracer::PrestateTracer { result, .. }, Some(responses), _) => {
+                    result.set(responses(tx)).ok();
+                }
+                (ApiTracer::StructLogger { config, result }, _, Some(responses)) => {
+                    result.set(responses(tx, config)).ok();
+                }
+                _ => { /* do nothing */ }
+            }
+        }
+        Ok(self.execute_tx(tx, block_args)?.vm)
+    }
+
     fn get_execution_result(
         &self,
         tx: &Transaction,
@@ -1,9 +1,13 @@
 //! Tests for the VM execution sandbox.
 
 use assert_matches::assert_matches;
+use tokio::sync::watch;
 use zksync_dal::ConnectionPool;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
-use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
+use zksync_node_test_utils::{
+    create_l1_batch, create_l2_block, create_l2_transaction, prepare_recovery_snapshot,
+};
+use zksync_state::{RocksdbStorageOptions, StateArchiveTask};
 
 use super::*;
 use crate::{execution_sandbox::apply::apply_vm_in_sandbox, tx_sender::ApiContracts};
@@ -182,14 +186,26 @@ async fn instantiating_vm() {
 }
 
 async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs) {
+    test_instantiating_vm_with_caches(pool, block_args, PostgresStorageCaches::new(1, 1)).await;
+}
+
+async fn test_instantiating_vm_with_caches(
+    pool: ConnectionPool<Core>,
+    block_args: BlockArgs,
+    caches: PostgresStorageCaches,
+) {
     let (vm_concurrency_limiter, _) = VmConcurrencyLimiter::new(1);
     let vm_permit = vm_concurrency_limiter.acquire().await.unwrap();
     let transaction = create_l2_transaction(10, 100).into();
     let estimate_gas_contracts = ApiContracts::load_from_disk().await.unwrap().estimate_gas;
+    let shared_args = TxSharedArgs {
+        caches,
+        ..TxSharedArgs::mock(estimate_gas_contracts)
+    };
     tokio::task::spawn_blocking(move || {
         apply_vm_in_sandbox(
             vm_permit,
-            TxSharedArgs::mock(estimate_gas_contracts),
+            shared_args,
             true,
             &TxExecutionArgs::for_gas_estimate(None, &transaction, 123),
             &pool,
@@ -205,3 +221,79 @@ async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs
     .expect("VM instantiation panicked")
     .expect("VM instantiation errored");
 }
+
+#[tokio::test]
+async fn instantiating_vm_for_pruned_block_from_state_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    for number in 1..=2 {
+        storage
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block(number))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(number))
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+    }
+
+    let archive_dir = tempfile::TempDir::new().unwrap();
+    let archive = StateArchive::new(archive_dir.path(), RocksdbStorageOptions::default())
+        .await
+        .unwrap();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let archive_task =
+        tokio::spawn(StateArchiveTask::new(archive.clone(), pool.clone()).run(stop_receiver));
+    while archive
+        .l2_block_range()
+        .map_or(true, |range| *range.end() < L2BlockNumber(2))
+    {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    stop_sender.send_replace(true);
+    archive_task.await.unwrap().unwrap();
+
+    storage
+        .pruning_dal()
+        .soft_prune_batches_range(L1BatchNumber(1), L2BlockNumber(1))
+        .await
+        .unwrap();
+    storage
+        .pruning_dal()
+        .hard_prune_batches_range(L1BatchNumber(1), L2BlockNumber(1))
+        .await
+        .unwrap();
+
+    let pruned_block = api::BlockId::Number(1.into());
+    let start_info = BlockStartInfo::new(&mut storage, Duration::MAX)
+        .await
+        .unwrap();
+    let err = BlockArgs::new(&mut storage, pruned_block, &start_info)
+        .await
+        .unwrap_err();
+    assert_matches!(err, BlockArgsError::Pruned(L2BlockNumber(2)));
+
+    let start_info = start_info.with_state_archive(Some(archive.clone()));
+    let block_args = BlockArgs::new(&mut storage, pruned_block, &start_info)
+        .await
+        .unwrap();
+    assert_eq!(block_args.resolved_block_number, L2BlockNumber(1));
+    let archived_block = block_args.archived_block.unwrap();
+    assert_eq!(archived_block.l1_batch_number, L1BatchNumber(1));
+    assert_eq!(archived_block.hash, create_l2_block(1).hash);
+    drop(storage);
+
+    let mut caches = PostgresStorageCaches::new(1, 1);
+    caches.set_state_archive(archive);
+    test_instantiating_vm_with_caches(pool, block_args, caches).await;
+}
@@ -3,15 +3,32 @@ use std::sync::Arc;
 use once_cell::sync::OnceCell;
 use zksync_multivm::{
     interface::{storage::WriteStorage, Call},
-    tracers::CallTracer,
+    tracers::{
+        prestate_tracer, CallTracer, PrestateTracer, StructLog, StructLogger, StructLoggerConfig,
+    },
     vm_latest::HistoryMode,
     MultiVMTracer, MultiVmTracerPointer,
 };
 
+/// Output of [`ApiTracer::PrestateTracer`]: account states before and after execution.
+pub(crate) type PrestateTracerResult =
+    Arc<OnceCell<(prestate_tracer::State, prestate_tracer::State)>>;
+
+/// Output of [`ApiTracer::StructLogger`].
+pub(crate) type StructLoggerResult = Arc<OnceCell<Vec<StructLog>>>;
+
 /// Custom tracers supported by our API
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer {
+        diff_mode: bool,
+        result: PrestateTracerResult,
+    },
+    StructLogger {
+        config: StructLoggerConfig,
+        result: StructLoggerResult,
+    },
 }
 
 impl ApiTracer {
@@ -23,6 +40,12 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer { diff_mode, result } => {
+                PrestateTracer::new(diff_mode, result).into_tracer_pointer()
+            }
+            ApiTracer::StructLogger { config, result } => {
+                StructLogger::new(config, result).into_tracer_pointer()
+            }
         }
     }
 }
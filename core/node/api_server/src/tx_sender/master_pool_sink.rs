@@ -14,6 +14,7 @@ use crate::web3::metrics::API_METRICS;
 pub struct MasterPoolSink {
     master_pool: ConnectionPool<Core>,
     inflight_requests: Mutex<HashMap<(Address, Nonce), H256>>,
+    min_replacement_fee_bump_percent: Option<u64>,
 }
 
 impl MasterPoolSink {
@@ -21,8 +22,16 @@ impl MasterPoolSink {
         Self {
             master_pool,
             inflight_requests: Mutex::new(HashMap::new()),
+            min_replacement_fee_bump_percent: None,
         }
     }
+
+    /// Sets the minimum fee bump (in percent) required to replace a pending transaction with the same nonce.
+    /// Should be consistent with the mempool policy used by the state keeper.
+    pub fn with_min_replacement_fee_bump_percent(mut self, percent: Option<u64>) -> Self {
+        self.min_replacement_fee_bump_percent = percent;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -55,7 +64,11 @@ impl TxSink for MasterPoolSink {
         let result = match self.master_pool.connection_tagged("api").await {
             Ok(mut connection) => connection
                 .transactions_dal()
-                .insert_transaction_l2(tx, execution_metrics)
+                .insert_transaction_l2_with_fee_bump(
+                    tx,
+                    execution_metrics,
+                    self.min_replacement_fee_bump_percent,
+                )
                 .await
                 .inspect(|submission_res_handle| {
                     APP_METRICS.processed_txs[&TxStage::Mempool(*submission_res_handle)].inc();
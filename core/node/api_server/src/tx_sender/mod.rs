@@ -18,7 +18,7 @@ use zksync_multivm::{
     vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use zksync_node_fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider};
-use zksync_state::PostgresStorageCaches;
+use zksync_state::{PostgresStorageCaches, StateArchive};
 use zksync_state_keeper::{
     seal_criteria::{ConditionalSealer, NoopSealer, SealData},
     SequencerSealer,
@@ -41,8 +41,9 @@ pub(super) use self::result::SubmitTxError;
 use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::{
     execution_sandbox::{
-        BlockArgs, SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs,
-        VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+        BlockArgs, SimulatedL2Block, SimulatedL2BlockOutput, SubmitTxStage, TransactionExecutor,
+        TxExecutionArgs, TxSharedArgs, VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit,
+        SANDBOX_METRICS,
     },
     tx_sender::result::ApiCallResult,
 };
@@ -342,6 +343,10 @@ impl TxSender {
         self.0.storage_caches.clone()
     }
 
+    pub(crate) fn state_archive(&self) -> Option<&StateArchive> {
+        self.0.storage_caches.state_archive()
+    }
+
     pub(crate) async fn read_whitelisted_tokens_for_aa_cache(&self) -> Vec<Address> {
         self.0.whitelisted_tokens_for_aa_cache.read().await.clone()
     }
@@ -448,6 +453,9 @@ impl TxSender {
                 Err(SubmitTxError::IncorrectTx(TxDuplication(tx.hash())))
             }
             L2TxSubmissionResult::InsertionInProgress => Err(SubmitTxError::InsertionInProgress),
+            L2TxSubmissionResult::ReplacementUnderpriced => {
+                Err(SubmitTxError::ReplacementUnderpriced)
+            }
             L2TxSubmissionResult::Proxied => {
                 stage_latency.set_stage(SubmitTxStage::TxProxy);
                 stage_latency.observe();
@@ -1017,6 +1025,34 @@ impl TxSender {
             .into_api_call_result()
     }
 
+    /// Simulates a sequence of L2 blocks on top of the block specified by `block_args`. Halted transactions
+    /// are reported in the outputs rather than as an error.
+    pub(super) async fn simulate_blocks(
+        &self,
+        block_args: BlockArgs,
+        blocks: Vec<SimulatedL2Block>,
+        enforced_base_fee: Option<u64>,
+    ) -> Result<Vec<SimulatedL2BlockOutput>, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let outputs = self
+            .0
+            .executor
+            .simulate_blocks_in_sandbox(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                block_args,
+                blocks,
+                enforced_base_fee,
+                vm_execution_cache_misses_limit,
+            )
+            .await?;
+        Ok(outputs)
+    }
+
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
         let mut connection = self.acquire_replica_connection().await?;
         let protocol_version = connection
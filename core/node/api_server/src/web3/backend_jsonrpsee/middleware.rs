@@ -1,7 +1,9 @@
 use std::{
     cell::RefCell,
     collections::HashSet,
+    fmt,
     future::Future,
+    net::{IpAddr, SocketAddr},
     num::NonZeroU32,
     pin::Pin,
     sync::Arc,
@@ -12,17 +14,19 @@ use std::{
 use governor::{
     clock::DefaultClock,
     middleware::NoOpMiddleware,
-    state::{InMemoryState, NotKeyed},
+    state::{keyed::DefaultKeyedStateStore, InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
 use once_cell::sync::OnceCell;
 use pin_project_lite::pin_project;
 use rand::{rngs::SmallRng, RngCore, SeedableRng};
-use tokio::sync::watch;
+use tokio::{sync::watch, task::futures::TaskLocalFuture};
 use tracing::instrument::{Instrument, Instrumented};
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram, Metrics,
 };
+use zksync_config::configs::api::HttpRateLimits;
+use zksync_db_connection::replicas::{with_pinned_replica, WithPinnedReplica};
 use zksync_web3_decl::jsonrpsee::{
     server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
     types::{error::ErrorCode, ErrorObject, Request},
@@ -30,7 +34,7 @@ use zksync_web3_decl::jsonrpsee::{
 };
 
 use super::metadata::{MethodCall, MethodTracer};
-use crate::web3::metrics::{ObservedRpcParams, API_METRICS};
+use crate::web3::metrics::{ObservedRpcParams, RateLimitKind, API_METRICS};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "transport", rename_all = "snake_case")]
@@ -105,6 +109,332 @@ where
     }
 }
 
+/// Identity of an HTTP client used for rate limiting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HttpClient {
+    /// Client with a recognized API key.
+    ApiKey(Arc<str>),
+    /// Client with an API key not recognized by the server.
+    InvalidApiKey,
+    /// Client without an API key identified by its IP address.
+    Ip(IpAddr),
+    /// Client without an API key for which the peer address is unknown.
+    UnknownIp,
+}
+
+/// Address of the peer of an HTTP connection. Attached to each HTTP request by the server.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PeerAddr(pub SocketAddr);
+
+/// HTTP middleware attaching [`PeerAddr`] to each request on a connection.
+#[derive(Debug, Clone)]
+pub(crate) struct PeerAddrMiddleware<S> {
+    inner: S,
+    peer_addr: SocketAddr,
+}
+
+impl<S> PeerAddrMiddleware<S> {
+    pub(crate) fn new(inner: S, peer_addr: SocketAddr) -> Self {
+        Self { inner, peer_addr }
+    }
+}
+
+impl<S, B> tower::Service<http::Request<B>> for PeerAddrMiddleware<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        request.extensions_mut().insert(PeerAddr(self.peer_addr));
+        self.inner.call(request)
+    }
+}
+
+tokio::task_local! {
+    /// Client performing the current HTTP request. Set by [`HttpClientMiddleware`] and read by [`HttpLimitMiddleware`].
+    static HTTP_CLIENT: HttpClient;
+}
+
+/// Shared state of HTTP rate limiting.
+pub(crate) struct HttpRateLimiter {
+    limits: HttpRateLimits,
+    per_ip: Option<KeyedRateLimiter<IpAddr>>,
+    per_api_key: Option<KeyedRateLimiter<Arc<str>>>,
+    /// Limiter shared by all clients without a determined IP address.
+    unknown_ip: Option<RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>,
+}
+
+impl fmt::Debug for HttpRateLimiter {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("HttpRateLimiter")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+type KeyedRateLimiter<K> = RateLimiter<K, DefaultKeyedStateStore<K>, DefaultClock, NoOpMiddleware>;
+
+impl HttpRateLimiter {
+    /// Interval between pruning limiter state for clients that weren't recently active.
+    pub(crate) const PRUNING_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub(crate) fn new(limits: HttpRateLimits) -> Self {
+        Self {
+            per_ip: limits
+                .requests_per_minute_per_ip
+                .map(|limit| RateLimiter::keyed(Quota::per_minute(limit))),
+            per_api_key: limits
+                .requests_per_minute_per_api_key
+                .map(|limit| RateLimiter::keyed(Quota::per_minute(limit))),
+            unknown_ip: limits
+                .requests_per_minute_per_ip
+                .map(|limit| RateLimiter::direct(Quota::per_minute(limit))),
+            limits,
+        }
+    }
+
+    /// Identifies the client sending the specified request. If the API key is supplied in the URL path,
+    /// the path is reset to `/` so that the key doesn't leak into the downstream processing.
+    fn identify<B>(&self, request: &mut http::Request<B>) -> HttpClient {
+        if let Some(key) = self.extract_api_key(request) {
+            return if self.limits.api_keys.contains(key.as_ref()) {
+                HttpClient::ApiKey(key)
+            } else {
+                HttpClient::InvalidApiKey
+            };
+        }
+
+        let peer_ip = request
+            .extensions()
+            .get::<PeerAddr>()
+            .map(|addr| addr.0.ip());
+        peer_ip.map_or(HttpClient::UnknownIp, |peer_ip| {
+            HttpClient::Ip(self.client_ip(request.headers(), peer_ip))
+        })
+    }
+
+    /// Determines the client IP address for a request received from `peer_ip`. The client IP header is only trusted
+    /// if the peer is a trusted proxy. In this case, the header is traversed from the right, skipping addresses
+    /// of trusted proxies; the first address not belonging to a trusted proxy is the client address. Entries
+    /// to the left of it are set by the client and are ignored.
+    fn client_ip(&self, headers: &http::HeaderMap, peer_ip: IpAddr) -> IpAddr {
+        let Some(header_name) = &self.limits.client_ip_header else {
+            return peer_ip;
+        };
+        if !self.limits.trusted_proxies.contains(&peer_ip) {
+            return peer_ip;
+        }
+
+        // The header may be repeated; values are concatenated in the order they were added.
+        let hops: Vec<_> = headers
+            .get_all(header_name.as_str())
+            .iter()
+            .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+            .collect();
+        let mut client_ip = peer_ip;
+        for hop in hops.into_iter().rev() {
+            let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+                // The hop was added by a trusted proxy, so the client is the proxy itself.
+                break;
+            };
+            client_ip = ip;
+            if !self.limits.trusted_proxies.contains(&ip) {
+                break;
+            }
+        }
+        client_ip
+    }
+
+    fn extract_api_key<B>(&self, request: &mut http::Request<B>) -> Option<Arc<str>> {
+        if self.limits.api_keys.is_empty() {
+            // API keys are not used; don't interpret the URL path.
+            return None;
+        }
+
+        let path_key = self
+            .limits
+            .api_key_path_prefix
+            .as_deref()
+            .and_then(|prefix| {
+                let key = request
+                    .uri()
+                    .path()
+                    .strip_prefix(prefix.trim_end_matches('/'))?
+                    .strip_prefix('/')?
+                    .trim_end_matches('/');
+                (!key.is_empty()).then(|| Arc::<str>::from(key))
+            });
+        if path_key.is_some() {
+            let mut uri_parts = request.uri().clone().into_parts();
+            uri_parts.path_and_query = Some(http::uri::PathAndQuery::from_static("/"));
+            if let Ok(uri) = http::Uri::from_parts(uri_parts) {
+                *request.uri_mut() = uri;
+            }
+        }
+
+        let header_key = request
+            .headers()
+            .get(self.limits.api_key_header.as_str())
+            .and_then(|value| value.to_str().ok())
+            .map(|key| Arc::<str>::from(key.trim()));
+        header_key.or(path_key)
+    }
+
+    /// Checks whether a call of the specified method by `client` is allowed.
+    fn check(&self, client: &HttpClient, method_name: &str) -> Result<(), RateLimitKind> {
+        let cost = self.limits.method_costs.get(method_name);
+        let (is_allowed, kind) = match client {
+            HttpClient::ApiKey(key) => {
+                let is_allowed = self
+                    .per_api_key
+                    .as_ref()
+                    .map_or(true, |limiter| limiter.check_key_n(key, cost).is_ok());
+                (is_allowed, RateLimitKind::ApiKey)
+            }
+            HttpClient::InvalidApiKey => (false, RateLimitKind::InvalidApiKey),
+            HttpClient::Ip(ip) => {
+                let is_allowed = self
+                    .per_ip
+                    .as_ref()
+                    .map_or(true, |limiter| limiter.check_key_n(ip, cost).is_ok());
+                (is_allowed, RateLimitKind::Ip)
+            }
+            HttpClient::UnknownIp => {
+                let is_allowed = self
+                    .unknown_ip
+                    .as_ref()
+                    .map_or(true, |limiter| limiter.check_n(cost).is_ok());
+                (is_allowed, RateLimitKind::Ip)
+            }
+        };
+        if is_allowed {
+            Ok(())
+        } else {
+            Err(kind)
+        }
+    }
+
+    /// Removes state for clients that have fully replenished their quota, so that the state doesn't grow indefinitely.
+    pub(crate) fn prune(&self) {
+        if let Some(limiter) = &self.per_ip {
+            limiter.retain_recent();
+        }
+        if let Some(limiter) = &self.per_api_key {
+            limiter.retain_recent();
+        }
+    }
+}
+
+/// [`tower`] HTTP middleware layer that identifies clients for HTTP rate limiting.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpClientLayer {
+    limiter: Arc<HttpRateLimiter>,
+}
+
+impl HttpClientLayer {
+    pub fn new(limiter: Arc<HttpRateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<Svc> tower::Layer<Svc> for HttpClientLayer {
+    type Service = HttpClientMiddleware<Svc>;
+
+    fn layer(&self, inner: Svc) -> Self::Service {
+        HttpClientMiddleware {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// HTTP middleware that identifies the client and makes it available to [`HttpLimitMiddleware`] for the duration
+/// of request processing.
+///
+/// # Implementation notes
+///
+/// `jsonrpsee` processes all RPC calls in an HTTP request (including batched ones) within the future returned
+/// by the HTTP service, so a task-local variable is visible to the RPC-level middleware.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpClientMiddleware<S> {
+    inner: S,
+    limiter: Arc<HttpRateLimiter>,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for HttpClientMiddleware<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<HttpClient, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let client = self.limiter.identify(&mut request);
+        HTTP_CLIENT.scope(client, self.inner.call(request))
+    }
+}
+
+/// Rate-limiting middleware for the HTTP server. Unlike [`LimitMiddleware`], limits are shared among all requests
+/// from the same client (identified by an API key or the IP address), and each method call consumes
+/// the quota according to its cost.
+pub(crate) struct HttpLimitMiddleware<S> {
+    inner: S,
+    limiter: Arc<HttpRateLimiter>,
+}
+
+impl<S> HttpLimitMiddleware<S> {
+    pub(crate) fn new(inner: S, limiter: Arc<HttpRateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for HttpLimitMiddleware<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let Ok(client) = HTTP_CLIENT.try_with(HttpClient::clone) else {
+            // Shouldn't happen if the middleware is configured correctly.
+            return ResponseFuture::future(self.inner.call(request));
+        };
+
+        if let Err(kind) = self.limiter.check(&client, request.method_name()) {
+            API_METRICS.web3_rate_limited_requests[&kind].inc();
+            let (status, message) = match kind {
+                RateLimitKind::InvalidApiKey => (http::StatusCode::UNAUTHORIZED, "Invalid API key"),
+                RateLimitKind::Ip | RateLimitKind::ApiKey => {
+                    (http::StatusCode::TOO_MANY_REQUESTS, "Too many requests")
+                }
+            };
+            let rp = MethodResponse::error(
+                request.id,
+                ErrorObject::borrowed(
+                    ErrorCode::ServerError(status.as_u16().into()).code(),
+                    message,
+                    None,
+                ),
+            );
+            return ResponseFuture::ready(rp);
+        }
+        ResponseFuture::future(self.inner.call(request))
+    }
+}
+
 /// RPC-level middleware that adds [`MethodCall`] metadata to method logic. Method handlers can then access this metadata
 /// using [`MethodTracer`], which is a part of `RpcState`. When the handler completes or is dropped, the results are reported
 /// as metrics.
@@ -126,7 +456,7 @@ impl<'a, S, const TRACE_PARAMS: bool> RpcServiceT<'a> for MetadataMiddleware<S,
 where
     S: Send + Sync + RpcServiceT<'a>,
 {
-    type Future = WithMethodCall<'a, S::Future>;
+    type Future = WithMethodCall<'a, WithPinnedReplica<S::Future>>;
 
     fn call(&self, request: Request<'a>) -> Self::Future {
         // "Normalize" the method name by searching it in the set of all registered methods. This extends the lifetime
@@ -143,7 +473,9 @@ where
             ObservedRpcParams::Unknown
         };
         let call = self.method_tracer.new_call(method_name, observed_params);
-        WithMethodCall::new(self.inner.call(request), call)
+        // Pin a single DB replica for the entire call, so that it reads consistent data.
+        let inner = with_pinned_replica(self.inner.call(request));
+        WithMethodCall::new(inner, call)
     }
 }
 
@@ -334,6 +666,7 @@ where
 mod tests {
     use std::time::Duration;
 
+    use futures::future;
     use rand::{thread_rng, Rng};
     use test_casing::{test_casing, Product};
     use zksync_types::api;
@@ -398,6 +731,201 @@ mod tests {
         }
     }
 
+    fn test_rate_limits() -> HttpRateLimits {
+        HttpRateLimits {
+            requests_per_minute_per_ip: NonZeroU32::new(10),
+            requests_per_minute_per_api_key: NonZeroU32::new(100),
+            api_keys: HashSet::from(["secret".to_owned()]),
+            api_key_header: "x-api-key".to_owned(),
+            api_key_path_prefix: Some("/key/".to_owned()),
+            client_ip_header: Some("x-forwarded-for".to_owned()),
+            trusted_proxies: HashSet::from([
+                IpAddr::from([10, 1, 0, 1]),
+                IpAddr::from([10, 1, 0, 2]),
+            ]),
+            method_costs: [("debug_*", NonZeroU32::new(5).unwrap())]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn request_from(peer_ip: [u8; 4], path: &str) -> http::request::Builder {
+        http::Request::post(path).extension(PeerAddr(SocketAddr::from((peer_ip, 12345))))
+    }
+
+    #[test]
+    fn identifying_http_clients() {
+        let limiter = HttpRateLimiter::new(test_rate_limits());
+
+        let mut request = http::Request::post("/").body(()).unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::UnknownIp);
+
+        let mut request = request_from([10, 0, 0, 1], "/").body(()).unwrap();
+        let expected_ip = IpAddr::from([10, 0, 0, 1]);
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(expected_ip));
+
+        let mut request = request_from([10, 0, 0, 1], "/")
+            .header("x-api-key", "secret")
+            .body(())
+            .unwrap();
+        assert_eq!(
+            limiter.identify(&mut request),
+            HttpClient::ApiKey("secret".into())
+        );
+
+        let mut request = request_from([10, 0, 0, 1], "/key/secret/")
+            .body(())
+            .unwrap();
+        assert_eq!(
+            limiter.identify(&mut request),
+            HttpClient::ApiKey("secret".into())
+        );
+        assert_eq!(request.uri().path(), "/");
+
+        let mut request = request_from([10, 0, 0, 1], "/key/wrong").body(()).unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::InvalidApiKey);
+
+        // Paths outside the API key prefix are not interpreted as keys.
+        let mut request = request_from([10, 0, 0, 1], "/secret").body(()).unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(expected_ip));
+        assert_eq!(request.uri().path(), "/secret");
+    }
+
+    #[test]
+    fn client_ip_header_is_only_trusted_from_proxies() {
+        let limiter = HttpRateLimiter::new(test_rate_limits());
+        let client_ip = IpAddr::from([192, 168, 0, 1]);
+
+        // Untrusted peers cannot spoof their address.
+        let mut request = request_from([10, 0, 0, 1], "/")
+            .header("x-forwarded-for", "192.168.0.1")
+            .body(())
+            .unwrap();
+        let expected_ip = IpAddr::from([10, 0, 0, 1]);
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(expected_ip));
+
+        let mut request = request_from([10, 1, 0, 1], "/")
+            .header("x-forwarded-for", "192.168.0.1")
+            .body(())
+            .unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(client_ip));
+
+        // Entries prepended by the client are ignored; trusted proxies are skipped.
+        let mut request = request_from([10, 1, 0, 1], "/")
+            .header("x-forwarded-for", "1.2.3.4, 192.168.0.1, 10.1.0.2")
+            .body(())
+            .unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(client_ip));
+        let mut request = request_from([10, 1, 0, 1], "/")
+            .header("x-forwarded-for", "1.2.3.4, 192.168.0.1")
+            .header("x-forwarded-for", "10.1.0.2")
+            .body(())
+            .unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(client_ip));
+
+        // Missing or malformed header falls back to the proxy address.
+        let mut request = request_from([10, 1, 0, 1], "/").body(()).unwrap();
+        let proxy_ip = IpAddr::from([10, 1, 0, 1]);
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(proxy_ip));
+        let mut request = request_from([10, 1, 0, 1], "/")
+            .header("x-forwarded-for", "unknown")
+            .body(())
+            .unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(proxy_ip));
+
+        // Without the header configured, the peer address is always used.
+        let limiter = HttpRateLimiter::new(HttpRateLimits {
+            client_ip_header: None,
+            ..test_rate_limits()
+        });
+        let mut request = request_from([10, 1, 0, 1], "/")
+            .header("x-forwarded-for", "192.168.0.1")
+            .body(())
+            .unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::Ip(proxy_ip));
+    }
+
+    #[test]
+    fn url_path_is_ignored_without_api_keys() {
+        let limiter = HttpRateLimiter::new(HttpRateLimits {
+            api_keys: HashSet::new(),
+            ..test_rate_limits()
+        });
+        let mut request = http::Request::post("/key/v1").body(()).unwrap();
+        assert_eq!(limiter.identify(&mut request), HttpClient::UnknownIp);
+        assert_eq!(request.uri().path(), "/key/v1");
+    }
+
+    #[test]
+    fn http_rate_limiting() {
+        let limiter = HttpRateLimiter::new(test_rate_limits());
+        let first_client = HttpClient::Ip(IpAddr::from([10, 0, 0, 1]));
+        let second_client = HttpClient::Ip(IpAddr::from([10, 0, 0, 2]));
+
+        for _ in 0..10 {
+            limiter.check(&first_client, "eth_blockNumber").unwrap();
+        }
+        assert_eq!(
+            limiter.check(&first_client, "eth_blockNumber"),
+            Err(RateLimitKind::Ip)
+        );
+        // Limits are tracked separately for each IP address.
+        limiter.check(&second_client, "eth_blockNumber").unwrap();
+
+        // Method costs are taken into account.
+        limiter
+            .check(&second_client, "debug_traceTransaction")
+            .unwrap();
+        assert_eq!(
+            limiter.check(&second_client, "debug_traceCall"),
+            Err(RateLimitKind::Ip)
+        );
+        limiter.check(&second_client, "eth_call").unwrap();
+
+        // Clients with API keys are not subject to per-IP limits.
+        let api_key_client = HttpClient::ApiKey("secret".into());
+        for _ in 0..20 {
+            limiter
+                .check(&api_key_client, "debug_traceTransaction")
+                .unwrap();
+        }
+        assert_eq!(
+            limiter.check(&api_key_client, "eth_blockNumber"),
+            Err(RateLimitKind::ApiKey)
+        );
+
+        assert_eq!(
+            limiter.check(&HttpClient::InvalidApiKey, "eth_blockNumber"),
+            Err(RateLimitKind::InvalidApiKey)
+        );
+    }
+
+    #[tokio::test]
+    async fn http_client_is_available_during_request_processing() {
+        #[derive(Debug, Clone)]
+        struct ClientReader;
+
+        impl tower::Service<http::Request<()>> for ClientReader {
+            type Response = Option<HttpClient>;
+            type Error = ();
+            type Future = future::Ready<Result<Self::Response, ()>>;
+
+            fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn call(&mut self, _request: http::Request<()>) -> Self::Future {
+                future::ready(Ok(HTTP_CLIENT.try_with(HttpClient::clone).ok()))
+            }
+        }
+
+        let limiter = Arc::new(HttpRateLimiter::new(test_rate_limits()));
+        let mut service = tower::Layer::layer(&HttpClientLayer::new(limiter), ClientReader);
+        let request = http::Request::post("/key/secret").body(()).unwrap();
+        let client = tower::Service::call(&mut service, request).await.unwrap();
+        assert_eq!(client, Some(HttpClient::ApiKey("secret".into())));
+    }
+
     #[tokio::test]
     async fn traffic_tracker_basics() {
         let traffic_tracker = TrafficTracker::default();
@@ -1,7 +1,10 @@
 use zksync_types::{
     api::{
-        state_override::StateOverride, Block, BlockId, BlockIdVariant, BlockNumber, FeeHistory,
-        Log, Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        proofs::EthProof,
+        simulate::{SimulatePayload, SimulatedBlock},
+        state_override::StateOverride,
+        Block, BlockId, BlockIdVariant, BlockNumber, FeeHistory, Log, Transaction, TransactionId,
+        TransactionReceipt, TransactionVariant,
     },
     transaction_request::CallRequest,
     web3::{Bytes, Index, SyncState},
@@ -49,6 +52,16 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        self.simulate_v1_impl(payload, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn gas_price(&self) -> RpcResult<U256> {
         self.gas_price_impl()
             .await
@@ -171,6 +184,17 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<EthProof> {
+        self.get_proof_impl(address, keys, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_transaction_count(
         &self,
         address: Address,
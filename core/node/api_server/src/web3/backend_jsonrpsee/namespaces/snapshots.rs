@@ -9,8 +9,8 @@ use crate::web3::namespaces::SnapshotsNamespace;
 
 #[async_trait]
 impl SnapshotsNamespaceServer for SnapshotsNamespace {
-    async fn get_all_snapshots(&self) -> RpcResult<AllSnapshots> {
-        self.get_all_snapshots_impl()
+    async fn get_all_snapshots(&self, include_deltas: Option<bool>) -> RpcResult<AllSnapshots> {
+        self.get_all_snapshots_impl(include_deltas.unwrap_or(false))
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
@@ -1,5 +1,5 @@
 use zksync_types::{
-    api::{TeeProof, TransactionExecutionInfo},
+    api::{L1BatchL1Costs, TeeProof, TransactionExecutionInfo},
     tee_types::TeeType,
     L1BatchNumber, H256,
 };
@@ -30,4 +30,13 @@ impl UnstableNamespaceServer for UnstableNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn l1_batch_l1_costs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchL1Costs>> {
+        self.get_l1_batch_l1_costs_impl(l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
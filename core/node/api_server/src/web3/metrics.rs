@@ -170,6 +170,8 @@ enum Web3ErrorKind {
     FilterNotFound,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    InvalidSimulation,
+    UnsupportedTracer,
     TreeApiUnavailable,
     Internal,
 }
@@ -186,6 +188,8 @@ impl Web3ErrorKind {
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::InvalidSimulation(_) => Self::InvalidSimulation,
+            Web3Error::UnsupportedTracer(_) => Self::UnsupportedTracer,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
@@ -212,6 +216,18 @@ struct Web3ErrorLabels {
     kind: Web3ErrorKind,
 }
 
+/// Reason of rejecting a call by HTTP rate limiting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(crate) enum RateLimitKind {
+    /// Per-IP limit was exceeded.
+    Ip,
+    /// Per-API key limit was exceeded.
+    ApiKey,
+    /// Supplied API key is not recognized.
+    InvalidApiKey,
+}
+
 #[derive(Debug, EncodeLabelSet)]
 struct Web3ConfigLabels {
     #[metrics(unit = Unit::Seconds)]
@@ -225,6 +241,8 @@ struct Web3ConfigLabels {
     #[metrics(unit = Unit::Bytes)]
     response_body_size_limit: Option<usize>,
     websocket_requests_per_minute_limit: Option<u32>,
+    http_requests_per_minute_per_ip: Option<u32>,
+    http_requests_per_minute_per_api_key: Option<u32>,
 }
 
 /// Roughly exponential buckets for the `web3_call_block_diff` metric. The distribution should be skewed towards lower values.
@@ -265,6 +283,8 @@ pub(crate) struct ApiMetrics {
 
     #[metrics(buckets = Buckets::exponential(1.0..=128.0, 2.0))]
     pub web3_in_flight_requests: Family<ApiTransportLabel, Histogram<usize>>,
+    /// Number of HTTP calls rejected by rate limiting.
+    pub web3_rate_limited_requests: Family<RateLimitKind, Counter>,
     /// Number of currently open WebSocket sessions.
     pub ws_open_sessions: Gauge,
     /// Number of currently inserted into DB transactions.
@@ -293,6 +313,16 @@ impl ApiMetrics {
             websocket_requests_per_minute_limit: optional
                 .websocket_requests_per_minute_limit
                 .map(Into::into),
+            http_requests_per_minute_per_ip: optional
+                .http_rate_limits
+                .as_ref()
+                .and_then(|limits| limits.requests_per_minute_per_ip)
+                .map(Into::into),
+            http_requests_per_minute_per_api_key: optional
+                .http_rate_limits
+                .as_ref()
+                .and_then(|limits| limits.requests_per_minute_per_api_key)
+                .map(Into::into),
         };
         tracing::info!("{transport:?} Web3 server is configured with options: {config_labels:?}");
         if self.web3_info[&transport].set(config_labels).is_err() {
@@ -9,7 +9,7 @@ use tokio::{
     task::JoinHandle,
 };
 use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
-use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
+use zksync_config::configs::api::{HttpRateLimits, MaxResponseSize, MaxResponseSizeOverrides};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
@@ -18,7 +18,8 @@ use zksync_types::L2BlockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
         server::{
-            middleware::rpc::either::Either, BatchRequestConfig, RpcServiceBuilder, ServerBuilder,
+            middleware::rpc::either::Either, serve_with_graceful_shutdown, stop_channel,
+            BatchRequestConfig, RpcServiceBuilder, ServerBuilder,
         },
         MethodCallback, Methods, RpcModule,
     },
@@ -32,7 +33,8 @@ use zksync_web3_decl::{
 
 use self::{
     backend_jsonrpsee::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
+        CorrelationMiddleware, HttpClientLayer, HttpLimitMiddleware, HttpRateLimiter,
+        LimitMiddleware, MetadataLayer, MethodTracer, PeerAddrMiddleware, ShutdownMiddleware,
         TrafficTracker,
     },
     mempool_cache::MempoolCache,
@@ -132,6 +134,7 @@ struct OptionalApiParams {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<MaxResponseSize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    http_rate_limits: Option<HttpRateLimits>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
     extended_tracing: bool,
@@ -249,6 +252,12 @@ impl ApiBuilder {
         self
     }
 
+    /// Configures per-client rate limiting for the HTTP server. Has no effect for the WS server.
+    pub fn with_http_rate_limits(mut self, http_rate_limits: HttpRateLimits) -> Self {
+        self.optional.http_rate_limits = Some(http_rate_limits);
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -340,8 +349,9 @@ impl ApiServer {
         last_sealed_l2_block: SealedL2BlockNumber,
     ) -> anyhow::Result<RpcState> {
         let mut storage = self.updaters_pool.connection_tagged("api").await?;
-        let start_info =
-            BlockStartInfo::new(&mut storage, self.pruning_info_refresh_interval).await?;
+        let start_info = BlockStartInfo::new(&mut storage, self.pruning_info_refresh_interval)
+            .await?
+            .with_state_archive(self.tx_sender.state_archive().cloned());
         drop(storage);
 
         // Disable filter API for HTTP endpoints, WS endpoints are unaffected by the `filters_disabled` flag
@@ -630,6 +640,18 @@ impl ApiServer {
                 (u32::MAX, MaxResponseSizeOverrides::empty())
             };
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let http_rate_limiter = self
+            .optional
+            .http_rate_limits
+            .clone()
+            .filter(|_| is_http)
+            .map(|limits| Arc::new(HttpRateLimiter::new(limits)));
+        let api_key_header = self.optional.http_rate_limits.as_ref().and_then(|limits| {
+            let has_api_keys = !limits.api_keys.is_empty();
+            has_api_keys
+                .then(|| http::HeaderName::try_from(limits.api_key_header.as_str()).ok())
+                .flatten()
+        });
         let subscriptions_limit = self.optional.subscriptions_limit;
         let vm_barrier = self.optional.vm_barrier.clone();
         let health_updater = self.health_updater.clone();
@@ -655,7 +677,12 @@ impl ApiServer {
                 .allow_methods([http::Method::POST])
                 // Allow requests from any origin
                 .allow_origin(tower_http::cors::Any)
-                .allow_headers([http::header::CONTENT_TYPE])
+                .allow_headers(
+                    [http::header::CONTENT_TYPE]
+                        .into_iter()
+                        .chain(api_key_header)
+                        .collect::<Vec<_>>(),
+                )
         });
         // Setup metrics for the number of in-flight requests.
         let (in_flight_requests, counter) = InFlightRequestsLayer::pair();
@@ -665,10 +692,25 @@ impl ApiServer {
                 future::ready(())
             }),
         );
+        if let Some(limiter) = &http_rate_limiter {
+            // Periodically prune the limiter state; the task terminates once the server is dropped.
+            let limiter = Arc::downgrade(limiter);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(HttpRateLimiter::PRUNING_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let Some(limiter) = limiter.upgrade() else {
+                        break;
+                    };
+                    limiter.prune();
+                }
+            });
+        }
         // Assemble server middleware.
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            .option_layer(http_rate_limiter.clone().map(HttpClientLayer::new));
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -701,6 +743,9 @@ impl ApiServer {
                 tower::layer::layer_fn(move |svc| {
                     LimitMiddleware::new(svc, websocket_requests_per_minute_limit)
                 })
+            }))
+            .option_layer(http_rate_limiter.map(|limiter| {
+                tower::layer::layer_fn(move |svc| HttpLimitMiddleware::new(svc, limiter.clone()))
             }));
 
         let server_builder = ServerBuilder::default()
@@ -711,13 +756,44 @@ impl ApiServer {
             .set_rpc_middleware(rpc_middleware);
 
         let (local_addr, server_handle) = if is_http {
-            // HTTP-specific settings
-            let server = server_builder
-                .http_only()
-                .build(addr)
+            // HTTP-specific settings. Connections are accepted manually so that the peer address of each connection
+            // is available to the HTTP middleware (it is used to identify clients for rate limiting).
+            let listener = tokio::net::TcpListener::bind(addr)
                 .await
                 .context("Failed building HTTP JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+            let local_addr = listener.local_addr();
+            let (stop_handle, server_handle) = stop_channel();
+            let service = server_builder
+                .http_only()
+                .to_service_builder()
+                .build(rpc, stop_handle.clone());
+            tokio::spawn(async move {
+                loop {
+                    let (stream, peer_addr) = tokio::select! {
+                        res = listener.accept() => match res {
+                            Ok(conn) => conn,
+                            Err(err) => {
+                                tracing::warn!("Failed accepting HTTP JSON-RPC connection: {err}");
+                                tokio::time::sleep(Duration::from_millis(100)).await;
+                                continue;
+                            }
+                        },
+                        () = stop_handle.clone().shutdown() => break,
+                    };
+                    let service = PeerAddrMiddleware::new(service.clone(), peer_addr);
+                    let stopped = stop_handle.clone().shutdown();
+                    tokio::spawn(async move {
+                        if let Err(err) =
+                            serve_with_graceful_shutdown(stream, service, stopped).await
+                        {
+                            tracing::debug!(
+                                "HTTP JSON-RPC connection from {peer_addr} failed: {err}"
+                            );
+                        }
+                    });
+                }
+            });
+            (local_addr, server_handle)
         } else {
             // WS-specific settings
             let server = server_builder
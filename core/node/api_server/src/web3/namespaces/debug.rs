@@ -1,29 +1,136 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 use anyhow::Context as _;
 use once_cell::sync::OnceCell;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_multivm::{
-    interface::{Call, CallType, ExecutionResult},
+    interface::{Call, CallType, ExecutionResult, VmExecutionResultAndLogs},
+    tracers::{prestate_tracer, StructLogger, StructLoggerConfig},
     vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, DebugCallType, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugCallType, PrestateAccount, PrestateTrace,
+        ResultDebugCall, StructLog, StructLogsTrace, SupportedTracers, TracerConfig, TracerResult,
+    },
     debug_flat_call::{flatten_debug_calls, DebugCallFlat},
     fee_model::BatchFeeInput,
     l2::L2Tx,
     transaction_request::CallRequest,
-    web3, AccountTreeId, H256, U256,
+    utils::decompose_full_nonce,
+    web3, AccountTreeId, Address, L2BlockNumber, ProtocolVersionId, Transaction, H256, U256,
 };
+use zksync_utils::u256_to_h256;
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
-    execution_sandbox::{ApiTracer, TxSharedArgs},
+    execution_sandbox::{
+        ApiTracer, BlockArgs, PrestateTracerResult, StructLoggerResult, TxSharedArgs,
+    },
     tx_sender::{ApiContracts, TxSenderConfig},
-    web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
+    web3::{
+        backend_jsonrpsee::MethodTracer,
+        state::{InternalApiConfig, RpcState},
+    },
 };
 
+/// Tracer that requires executing transactions in the sandbox. Unlike other traces, call traces are persisted
+/// when transactions are executed by the state keeper, so they are not included.
+#[derive(Debug, Clone)]
+enum ReplayedTracer {
+    Prestate { diff_mode: bool },
+    StructLogger(StructLoggerConfig),
+}
+
+impl ReplayedTracer {
+    /// Creates a tracer for the specified options. Struct logger limits requested by the client are capped
+    /// by the server-side limits from `api_config`.
+    fn new(options: &TracerConfig, api_config: &InternalApiConfig) -> Option<Self> {
+        match options.tracer {
+            SupportedTracers::CallTracer => None,
+            SupportedTracers::PrestateTracer => Some(Self::Prestate {
+                diff_mode: options.tracer_config.diff_mode,
+            }),
+            SupportedTracers::StructLogger => {
+                let config = &options.struct_logger_config;
+                let max_limit = api_config.struct_logger_step_limit;
+                let limit = if config.limit == 0 {
+                    max_limit
+                } else {
+                    config.limit.min(max_limit)
+                };
+                Some(Self::StructLogger(StructLoggerConfig {
+                    disable_stack: config.disable_stack,
+                    disable_storage: config.disable_storage,
+                    enable_memory: config.enable_memory,
+                    memory_limit: config
+                        .memory_limit
+                        .unwrap_or(StructLoggerConfig::default().memory_limit)
+                        .min(api_config.struct_logger_memory_limit),
+                    limit,
+                    size_limit: api_config.struct_logger_size_limit,
+                }))
+            }
+        }
+    }
+
+    /// Checks that the tracer can be run for a block with the specified protocol version.
+    /// The API sandbox always uses legacy VM instances, so support only depends on the VM version.
+    fn ensure_supported(&self, protocol_version: ProtocolVersionId) -> Result<(), Web3Error> {
+        match self {
+            Self::Prestate { .. } => Ok(()),
+            Self::StructLogger(_) => {
+                if StructLogger::is_supported(protocol_version.into_api_vm_version()) {
+                    Ok(())
+                } else {
+                    Err(Web3Error::UnsupportedTracer(format!(
+                        "struct logger is not supported for blocks with protocol version {protocol_version:?}"
+                    )))
+                }
+            }
+        }
+    }
+
+    fn instantiate(&self) -> (ReplayedTracerOutput, ApiTracer) {
+        match self {
+            Self::Prestate { diff_mode } => {
+                let result = PrestateTracerResult::default();
+                let tracer = ApiTracer::PrestateTracer {
+                    diff_mode: *diff_mode,
+                    result: result.clone(),
+                };
+                let output = ReplayedTracerOutput::Prestate {
+                    diff_mode: *diff_mode,
+                    result,
+                };
+                (output, tracer)
+            }
+            Self::StructLogger(config) => {
+                let result = StructLoggerResult::default();
+                let tracer = ApiTracer::StructLogger {
+                    config: config.clone(),
+                    result: result.clone(),
+                };
+                (ReplayedTracerOutput::StructLogs(result), tracer)
+            }
+        }
+    }
+}
+
+/// Handle to the output of a [`ReplayedTracer`].
+#[derive(Debug)]
+enum ReplayedTracerOutput {
+    Prestate {
+        diff_mode: bool,
+        result: PrestateTracerResult,
+    },
+    StructLogs(StructLoggerResult),
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct DebugNamespace {
     batch_fee_input: BatchFeeInput,
@@ -84,6 +191,10 @@ impl DebugNamespace {
         &self.state.tx_sender.0.sender_config
     }
 
+    fn replayed_tracer(&self, options: Option<&TracerConfig>) -> Option<ReplayedTracer> {
+        ReplayedTracer::new(options?, &self.state.api_config)
+    }
+
     pub(crate) fn current_method(&self) -> &MethodTracer {
         &self.state.current_method
     }
@@ -92,20 +203,56 @@ impl DebugNamespace {
         &self,
         block_id: BlockId,
         options: Option<TracerConfig>,
-    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
-        self.current_method().set_block_id(block_id);
-        if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
-            // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
-            return Ok(vec![]);
+    ) -> Result<Vec<ResultDebugCall<TracerResult>>, Web3Error> {
+        if let Some(tracer) = self.replayed_tracer(options.as_ref()) {
+            let traces = self.debug_trace_block_replayed(block_id, &tracer).await?;
+            return Ok(traces
+                .into_iter()
+                .map(|result| ResultDebugCall { result })
+                .collect());
         }
 
         let only_top_call = options
             .map(|options| options.tracer_config.only_top_call)
             .unwrap_or(false);
-        let mut connection = self.state.acquire_connection().await?;
-        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        let call_traces = self
+            .debug_trace_block_calls(block_id, only_top_call)
+            .await?;
+        Ok(call_traces
+            .into_iter()
+            .map(|call| ResultDebugCall {
+                result: TracerResult::CallTrace(call.result),
+            })
+            .collect())
+    }
+
+    /// Resolves the block to be traced. Returns `None` for the pending block, which has no transactions.
+    async fn resolve_traced_block(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        block_id: BlockId,
+    ) -> Result<Option<L2BlockNumber>, Web3Error> {
+        self.current_method().set_block_id(block_id);
+        if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
+            // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
+            return Ok(None);
+        }
+
+        let block_number = self.state.resolve_block(connection, block_id).await?;
         self.current_method()
             .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+        Ok(Some(block_number))
+    }
+
+    async fn debug_trace_block_calls(
+        &self,
+        block_id: BlockId,
+        only_top_call: bool,
+    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let Some(block_number) = self.resolve_traced_block(&mut connection, block_id).await? else {
+            return Ok(vec![]);
+        };
 
         let call_traces = connection
             .blocks_web3_dal()
@@ -122,12 +269,59 @@ impl DebugNamespace {
         Ok(call_trace)
     }
 
+    /// Replays all transactions in the block with the requested tracer.
+    async fn debug_trace_block_replayed(
+        &self,
+        block_id: BlockId,
+        tracer: &ReplayedTracer,
+    ) -> Result<Vec<TracerResult>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let Some(block_number) = self.resolve_traced_block(&mut connection, block_id).await? else {
+            return Ok(vec![]);
+        };
+        let txs = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        if txs.is_empty() {
+            return Ok(vec![]);
+        }
+        let block_args =
+            BlockArgs::for_replay(&mut connection, block_number, &self.state.start_info).await?;
+        let block_info = block_args.resolve_block_info(&mut connection).await?;
+        tracer.ensure_supported(block_info.protocol_version)?;
+        drop(connection);
+
+        let mut outputs = Vec::with_capacity(txs.len());
+        let txs = txs
+            .into_iter()
+            .map(|tx| {
+                let (output, tracer) = tracer.instantiate();
+                outputs.push(output);
+                (tx, vec![tracer])
+            })
+            .collect();
+        let vm_results = self.replay_txs(block_args, txs).await?;
+
+        let mut traces = Vec::with_capacity(outputs.len());
+        for (output, vm_result) in outputs.into_iter().zip(&vm_results) {
+            traces.push(self.map_replayed_output(output, vm_result).await?);
+        }
+        Ok(traces)
+    }
+
     pub async fn debug_trace_block_flat_impl(
         &self,
         block_id: BlockId,
         options: Option<TracerConfig>,
     ) -> Result<Vec<DebugCallFlat>, Web3Error> {
-        let call_trace = self.debug_trace_block_impl(block_id, options).await?;
+        let only_top_call = options
+            .map(|options| options.tracer_config.only_top_call)
+            .unwrap_or(false);
+        let call_trace = self
+            .debug_trace_block_calls(block_id, only_top_call)
+            .await?;
         let call_trace_flat = flatten_debug_calls(call_trace);
         Ok(call_trace_flat)
     }
@@ -136,7 +330,13 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<Option<DebugCall>, Web3Error> {
+    ) -> Result<Option<TracerResult>, Web3Error> {
+        if let Some(tracer) = self.replayed_tracer(options.as_ref()) {
+            return self
+                .debug_trace_transaction_replayed(tx_hash, &tracer)
+                .await;
+        }
+
         let only_top_call = options
             .map(|options| options.tracer_config.only_top_call)
             .unwrap_or(false);
@@ -146,7 +346,215 @@ impl DebugNamespace {
             .get_call_trace(tx_hash)
             .await
             .map_err(DalError::generalize)?;
-        Ok(call_trace.map(|call_trace| Self::map_call(call_trace, only_top_call)))
+        Ok(call_trace
+            .map(|call_trace| TracerResult::CallTrace(Self::map_call(call_trace, only_top_call))))
+    }
+
+    /// Replays the transaction together with all preceding transactions in its block; only the target transaction is traced.
+    async fn debug_trace_transaction_replayed(
+        &self,
+        tx_hash: H256,
+        tracer: &ReplayedTracer,
+    ) -> Result<Option<TracerResult>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = connection
+            .transactions_web3_dal()
+            .get_transaction_by_hash(tx_hash, self.sender_config().chain_id)
+            .await
+            .map_err(DalError::generalize)?
+            .and_then(|tx| tx.block_number);
+        let Some(block_number) = block_number else {
+            // The transaction is either unknown or not executed yet.
+            return Ok(None);
+        };
+        let block_number = L2BlockNumber(block_number.as_u32());
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+
+        let mut txs = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let tx_index = txs
+            .iter()
+            .position(|tx| tx.hash() == tx_hash)
+            .context("transaction is missing from its L2 block")?;
+        txs.truncate(tx_index + 1);
+        let block_args =
+            BlockArgs::for_replay(&mut connection, block_number, &self.state.start_info).await?;
+        let block_info = block_args.resolve_block_info(&mut connection).await?;
+        tracer.ensure_supported(block_info.protocol_version)?;
+        drop(connection);
+
+        let (output, tracer) = tracer.instantiate();
+        let mut tracer = Some(tracer);
+        let txs = txs
+            .into_iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                let tracers = if i == tx_index {
+                    tracer.take().into_iter().collect()
+                } else {
+                    vec![]
+                };
+                (tx, tracers)
+            })
+            .collect();
+        let vm_results = self.replay_txs(block_args, txs).await?;
+        let vm_result = vm_results
+            .last()
+            .context("no VM result for the traced transaction")?;
+
+        let trace = self.map_replayed_output(output, vm_result).await?;
+        Ok(Some(trace))
+    }
+
+    async fn replay_txs(
+        &self,
+        block_args: BlockArgs,
+        txs: Vec<(Transaction, Vec<ApiTracer>)>,
+    ) -> Result<Vec<VmExecutionResultAndLogs>, Web3Error> {
+        let shared_args = self.shared_args().await;
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let executor = &self.state.tx_sender.0.executor;
+        let vm_results = executor
+            .replay_txs_in_sandbox(
+                vm_permit,
+                shared_args,
+                self.state.connection_pool.clone(),
+                block_args,
+                txs,
+                self.sender_config().vm_execution_cache_misses_limit,
+            )
+            .await?;
+        Ok(vm_results)
+    }
+
+    async fn map_replayed_output(
+        &self,
+        output: ReplayedTracerOutput,
+        vm_result: &VmExecutionResultAndLogs,
+    ) -> Result<TracerResult, Web3Error> {
+        Ok(match output {
+            ReplayedTracerOutput::Prestate { diff_mode, result } => {
+                TracerResult::PrestateTrace(self.map_prestate_result(result, diff_mode).await?)
+            }
+            ReplayedTracerOutput::StructLogs(result) => {
+                TracerResult::StructLogs(Self::map_struct_logs(result, vm_result)?)
+            }
+        })
+    }
+
+    fn map_struct_logs(
+        result: StructLoggerResult,
+        vm_result: &VmExecutionResultAndLogs,
+    ) -> Result<StructLogsTrace, Web3Error> {
+        let logs = result.get().cloned().ok_or_else(|| {
+            Web3Error::InternalError(anyhow::anyhow!(
+                "struct logger is not supported for the protocol version of the traced block"
+            ))
+        })?;
+        let return_value = match &vm_result.result {
+            ExecutionResult::Success { output } => output.clone(),
+            ExecutionResult::Revert { output } => output.encoded_data(),
+            ExecutionResult::Halt { .. } => vec![],
+        };
+        let struct_logs = logs
+            .into_iter()
+            .map(|log| StructLog {
+                pc: log.pc.into(),
+                op: log.op,
+                gas: log.gas.into(),
+                gas_cost: log.gas_cost.into(),
+                depth: log.depth as u64,
+                stack: log.stack,
+                memory: log
+                    .memory
+                    .map(|words| words.into_iter().map(u256_to_h256).collect()),
+                storage: log.storage,
+            })
+            .collect();
+
+        Ok(StructLogsTrace {
+            gas: vm_result.statistics.gas_used,
+            failed: vm_result.result.is_failed(),
+            return_value: return_value.into(),
+            struct_logs,
+        })
+    }
+
+    /// Converts output of the prestate tracer to the API format, loading the bytecodes of the traced contracts.
+    async fn map_prestate_result(
+        &self,
+        result: PrestateTracerResult,
+        diff_mode: bool,
+    ) -> Result<PrestateTrace, Web3Error> {
+        let (pre, post) = result.get().cloned().ok_or_else(|| {
+            Web3Error::InternalError(anyhow::anyhow!(
+                "prestate tracer is not supported for the protocol version of the traced block"
+            ))
+        })?;
+
+        let mut connection = self.state.acquire_connection().await?;
+        let mut bytecodes = HashMap::new();
+        let pre = Self::map_prestate_accounts(&mut connection, &mut bytecodes, pre).await?;
+        Ok(if diff_mode {
+            let post = Self::map_prestate_accounts(&mut connection, &mut bytecodes, post).await?;
+            PrestateTrace::Diff { pre, post }
+        } else {
+            // In the non-diff mode, only the states of touched accounts before the transaction are returned.
+            PrestateTrace::Prestate(pre)
+        })
+    }
+
+    async fn map_prestate_accounts(
+        connection: &mut Connection<'_, Core>,
+        bytecodes: &mut HashMap<H256, Option<web3::Bytes>>,
+        state: prestate_tracer::State,
+    ) -> Result<BTreeMap<Address, PrestateAccount>, Web3Error> {
+        let mut accounts = BTreeMap::new();
+        for (address, account) in state {
+            let bytecode_hash = account.code.map(u256_to_h256).unwrap_or_default();
+            let code = if bytecode_hash.is_zero() {
+                None
+            } else if let Some(code) = bytecodes.get(&bytecode_hash) {
+                code.clone()
+            } else {
+                let code = connection
+                    .factory_deps_dal()
+                    .get_sealed_factory_dep(bytecode_hash)
+                    .await
+                    .map_err(DalError::generalize)?
+                    .map(web3::Bytes::from);
+                bytecodes.insert(bytecode_hash, code.clone());
+                code
+            };
+            let nonce = account.nonce.map(|full_nonce| {
+                let (account_nonce, deployment_nonce) = decompose_full_nonce(full_nonce);
+                if code.is_some() {
+                    deployment_nonce.as_u64()
+                } else {
+                    account_nonce.as_u64()
+                }
+            });
+
+            let account = PrestateAccount {
+                balance: account.balance,
+                nonce,
+                code,
+                storage: account.storage.unwrap_or_default().into_iter().collect(),
+            };
+            accounts.insert(address, account);
+        }
+        Ok(accounts)
     }
 
     pub async fn debug_trace_call_impl(
@@ -154,10 +562,11 @@ impl DebugNamespace {
         mut request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<TracerResult, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
+        let replayed_tracer = self.replayed_tracer(options.as_ref());
         let only_top_call = options
             .map(|options| options.tracer_config.only_top_call)
             .unwrap_or(false);
@@ -167,6 +576,10 @@ impl DebugNamespace {
             .state
             .resolve_block_args(&mut connection, block_id)
             .await?;
+        if let Some(tracer) = &replayed_tracer {
+            let block_info = block_args.resolve_block_info(&mut connection).await?;
+            tracer.ensure_supported(block_info.protocol_version)?;
+        }
         drop(connection);
 
         self.current_method().set_block_diff(
@@ -200,10 +613,16 @@ impl DebugNamespace {
 
         // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
+        let (replayed_output, custom_tracers) = if let Some(tracer) = replayed_tracer {
+            let (output, tracer) = tracer.instantiate();
+            (Some(output), vec![tracer])
+        } else if only_top_call {
+            (None, vec![])
         } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+            (
+                None,
+                vec![ApiTracer::CallTracer(call_tracer_result.clone())],
+            )
         };
 
         let executor = &self.state.tx_sender.0.executor;
@@ -221,6 +640,10 @@ impl DebugNamespace {
             )
             .await?;
 
+        if let Some(output) = replayed_output {
+            return self.map_replayed_output(output, &result).await;
+        }
+
         let (output, revert_reason) = match result.result {
             ExecutionResult::Success { output, .. } => (output, None),
             ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
@@ -246,7 +669,7 @@ impl DebugNamespace {
             revert_reason,
             trace,
         );
-        Ok(Self::map_call(call, false))
+        Ok(TracerResult::CallTrace(Self::map_call(call, false)))
     }
 
     async fn shared_args(&self) -> TxSharedArgs {
@@ -1,30 +1,41 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockId, BlockNumber, FeeHistory, GetLogsFilter,
-        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
+        self,
+        proofs::{EthAccountFieldProofs, EthProof, EthStorageProof},
+        simulate::{SimulatePayload, SimulatedBlock, SimulatedCallError, SimulatedCallResult},
+        state_override::{OverrideState, StateOverride},
+        BlockId, BlockNumber, FeeHistory, GetLogsFilter, Transaction, TransactionId,
+        TransactionReceipt, TransactionVariant,
     },
+    get_code_key, get_nonce_key,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
-    utils::decompose_full_nonce,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
     web3::{self, Bytes, SyncInfo, SyncState},
-    AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    AccountTreeId, L1BatchNumber, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{h256_to_u256, time::seconds_since_epoch, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
 };
 
 use crate::{
+    execution_sandbox::{SimulatedL2Block, SimulatedL2BlockOutput},
     utils::open_readonly_transaction,
     web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
+/// Maximum number of blocks that can be simulated in a single `eth_simulateV1` call.
+const MAX_SIMULATED_BLOCKS: usize = 256;
+/// Maximum total number of calls in all blocks simulated in a single `eth_simulateV1` call.
+const MAX_SIMULATED_CALLS: usize = 1_000;
 
 #[derive(Debug)]
 pub(crate) struct EthNamespace {
@@ -94,6 +105,250 @@ impl EthNamespace {
         Ok(call_result.into())
     }
 
+    pub async fn simulate_v1_impl(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Web3Error> {
+        if payload.validation {
+            return Err(Web3Error::InvalidSimulation(
+                "validation mode is not supported".to_owned(),
+            ));
+        }
+        if payload.return_full_transactions {
+            return Err(Web3Error::InvalidSimulation(
+                "returning full transactions is not supported".to_owned(),
+            ));
+        }
+        if payload.block_state_calls.is_empty() {
+            return Err(Web3Error::InvalidSimulation(
+                "no blocks to simulate".to_owned(),
+            ));
+        }
+        if payload.block_state_calls.len() > MAX_SIMULATED_BLOCKS {
+            return Err(Web3Error::InvalidSimulation(format!(
+                "too many blocks to simulate; the limit is {MAX_SIMULATED_BLOCKS}"
+            )));
+        }
+        let call_count: usize = payload
+            .block_state_calls
+            .iter()
+            .map(|block_calls| block_calls.calls.len())
+            .sum();
+        if call_count > MAX_SIMULATED_CALLS {
+            return Err(Web3Error::InvalidSimulation(format!(
+                "too many calls to simulate; the limit is {MAX_SIMULATED_CALLS}"
+            )));
+        }
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        let base_block_number = block_args.simulation_base_block_number();
+        let base_block = connection
+            .blocks_dal()
+            .get_l2_block_header(base_block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("L2 block #{base_block_number} is not in storage"))?;
+        drop(connection);
+
+        let default_gas = self
+            .state
+            .tx_sender
+            .get_default_eth_call_gas(block_args)
+            .await
+            .map_err(Web3Error::InternalError)?;
+
+        let mut prev_number = base_block.number;
+        let mut prev_timestamp = base_block.timestamp;
+        let mut enforced_base_fee = None;
+        let mut blocks = Vec::with_capacity(payload.block_state_calls.len());
+        for (i, block_calls) in payload.block_state_calls.into_iter().enumerate() {
+            let number = prev_number + 1;
+            let block_overrides = block_calls.block_overrides.unwrap_or_default();
+            if let Some(requested_number) = block_overrides.number {
+                if requested_number != U64::from(number.0) {
+                    return Err(Web3Error::InvalidSimulation(format!(
+                        "block #{i} must have number {number} (block numbers must be sequential), got {requested_number}"
+                    )));
+                }
+            }
+
+            let timestamp = if let Some(time) = block_overrides.time {
+                let time = time.as_u64();
+                if time <= prev_timestamp {
+                    return Err(Web3Error::InvalidSimulation(format!(
+                        "block #{i} must have timestamp greater than {prev_timestamp}, got {time}"
+                    )));
+                }
+                time
+            } else if i == 0 && block_id == BlockId::Number(BlockNumber::Pending) {
+                // Use the same timestamp as for the pending block in `eth_call`.
+                seconds_since_epoch().max(prev_timestamp + 1)
+            } else {
+                prev_timestamp + 1
+            };
+
+            if let Some(base_fee) = block_overrides.base_fee_per_gas {
+                if i == 0 {
+                    let base_fee = u64::try_from(base_fee).map_err(|_| {
+                        Web3Error::InvalidSimulation("base fee override is too large".to_owned())
+                    })?;
+                    enforced_base_fee = Some(base_fee);
+                } else if enforced_base_fee.map(U256::from) != Some(base_fee) {
+                    return Err(Web3Error::InvalidSimulation(format!(
+                        "block #{i} overrides base fee; base fee can only be overridden for the first block"
+                    )));
+                }
+            }
+
+            if i > 0 {
+                let has_full_state_override = block_calls.state_overrides.iter().any(|overrides| {
+                    overrides
+                        .iter()
+                        .any(|(_, account)| matches!(account.state, Some(OverrideState::State(_))))
+                });
+                if has_full_state_override {
+                    return Err(Web3Error::InvalidSimulation(format!(
+                        "block #{i} overrides full account state; `state` overrides are only supported \
+                         for the first block, use `stateDiff` instead"
+                    )));
+                }
+            }
+
+            let txs = block_calls
+                .calls
+                .into_iter()
+                .map(|mut request| {
+                    if request.gas.is_none() {
+                        request.gas = Some(default_gas.into());
+                    }
+                    L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)
+                })
+                .collect::<Result<_, _>>()?;
+            blocks.push(SimulatedL2Block {
+                timestamp,
+                state_override: block_calls.state_overrides,
+                txs,
+            });
+            prev_number = number;
+            prev_timestamp = timestamp;
+        }
+
+        let outputs = self
+            .state
+            .tx_sender
+            .simulate_blocks(block_args, blocks, enforced_base_fee)
+            .await?;
+        Ok(outputs.into_iter().map(Self::map_simulated_block).collect())
+    }
+
+    fn map_simulated_block(output: SimulatedL2BlockOutput) -> SimulatedBlock {
+        let block_number = output.env.number.into();
+        let block_timestamp = output.env.timestamp.into();
+        let mut block_log_index = 0_u64;
+        let mut gas_used = U256::zero();
+        let mut transactions = Vec::with_capacity(output.results.len());
+        let mut calls = Vec::with_capacity(output.results.len());
+
+        for (tx_index, (tx_hash, result)) in output.results.into_iter().enumerate() {
+            let VmExecutionResultAndLogs {
+                result,
+                logs,
+                statistics,
+                ..
+            } = result;
+            let logs = logs
+                .events
+                .into_iter()
+                .enumerate()
+                .map(|(tx_log_index, event)| {
+                    let log = api::Log {
+                        address: event.address,
+                        topics: event.indexed_topics,
+                        data: Bytes(event.value),
+                        block_hash: Some(output.hash),
+                        block_number: Some(block_number),
+                        l1_batch_number: None,
+                        transaction_hash: Some(tx_hash),
+                        transaction_index: Some(tx_index.into()),
+                        log_index: Some(block_log_index.into()),
+                        transaction_log_index: Some(tx_log_index.into()),
+                        log_type: None,
+                        removed: Some(false),
+                        block_timestamp: Some(block_timestamp),
+                    };
+                    block_log_index += 1;
+                    log
+                })
+                .collect();
+
+            let call_gas_used = U256::from(statistics.gas_used);
+            gas_used += call_gas_used;
+            transactions.push(tx_hash);
+            calls.push(match result {
+                ExecutionResult::Success { output } => SimulatedCallResult {
+                    status: 1.into(),
+                    return_data: output.into(),
+                    gas_used: call_gas_used,
+                    logs,
+                    error: None,
+                },
+                ExecutionResult::Revert { output } => {
+                    let data = output.encoded_data();
+                    SimulatedCallResult {
+                        status: 0.into(),
+                        return_data: data.clone().into(),
+                        gas_used: call_gas_used,
+                        logs,
+                        error: Some(SimulatedCallError {
+                            code: 3,
+                            message: output.to_user_friendly_string(),
+                            data: Some(data.into()),
+                        }),
+                    }
+                }
+                // Halted transactions are rolled back by the sandbox, so they don't affect subsequent calls.
+                ExecutionResult::Halt { reason } => SimulatedCallResult {
+                    status: 0.into(),
+                    return_data: Bytes::default(),
+                    gas_used: call_gas_used,
+                    logs,
+                    error: Some(SimulatedCallError {
+                        code: -32015,
+                        message: reason.to_string(),
+                        data: None,
+                    }),
+                },
+            });
+        }
+
+        SimulatedBlock {
+            inner: api::Block {
+                hash: output.hash,
+                parent_hash: output.env.prev_block_hash,
+                number: block_number,
+                gas_used,
+                base_fee_per_gas: output.base_fee.into(),
+                timestamp: block_timestamp.as_u64().into(),
+                transactions,
+                ..api::Block::default()
+            },
+            calls,
+        }
+    }
+
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
@@ -371,6 +626,19 @@ impl EthNamespace {
             .get_transaction_receipts(&block.transactions)
             .await
             .with_context(|| format!("get_transaction_receipts({block_number})"))?;
+        if !receipts.is_empty() {
+            // Receipts are complete only if all transactions in the block call retained addresses.
+            let called_addresses: Option<Vec<_>> =
+                receipts.iter().map(|receipt| receipt.to).collect();
+            self.state
+                .start_info
+                .ensure_history_not_pruned(
+                    block_number,
+                    &called_addresses.unwrap_or_default(),
+                    &mut storage,
+                )
+                .await?;
+        }
         receipts.sort_unstable_by_key(|receipt| receipt.transaction_index);
         Ok(Some(receipts))
     }
@@ -409,6 +677,20 @@ impl EthNamespace {
         self.current_method().set_block_id(block_id);
 
         let storage_key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(idx));
+        if let (BlockId::Number(BlockNumber::Number(number)), Some(archive)) =
+            (block_id, self.state.tx_sender.state_archive())
+        {
+            // The archive may cover L2 blocks already pruned from Postgres, so it's queried before resolving the block.
+            let block_number = L2BlockNumber(u32::try_from(number).unwrap_or(u32::MAX));
+            if let Some(value) = archive
+                .historical_value(storage_key.hashed_key(), block_number)
+                .await?
+            {
+                self.set_block_diff(block_number);
+                return Ok(value);
+            }
+        }
+
         let mut connection = self.state.acquire_connection().await?;
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.set_block_diff(block_number);
@@ -420,6 +702,129 @@ impl EthNamespace {
         Ok(value)
     }
 
+    /// Returns EIP-1186-compatible proofs for the account and the specified storage slots. Since the Merkle tree
+    /// is only updated at L1 batch boundaries, proofs are provided for the newest L1 batch processed by the tree,
+    /// which doesn't contain L2 blocks after the requested one. See [`api::proofs`] for details on the response format.
+    pub async fn get_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: Option<BlockId>,
+    ) -> Result<EthProof, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.set_block_diff(block_number);
+        let l1_batch_number = Self::resolve_proof_l1_batch(&mut connection, block_number).await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(l1_batch_number, &mut connection)
+            .await?;
+        let root_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no root hash"))?;
+        drop(connection);
+
+        let account_field_keys = [
+            get_nonce_key(&address),
+            storage_key_for_eth_balance(&address),
+            get_code_key(&address),
+        ];
+        let storage_keys = keys
+            .iter()
+            .map(|&key| StorageKey::new(AccountTreeId::new(address), key));
+        let all_keys: Vec<_> = account_field_keys.into_iter().chain(storage_keys).collect();
+        let hashed_keys = all_keys.iter().map(StorageKey::hashed_key_u256).collect();
+        let entries = self
+            .state
+            .get_tree_proofs(l1_batch_number, hashed_keys)
+            .await?
+            .ok_or(Web3Error::TreeApiUnavailable)?;
+        if entries.len() != all_keys.len() {
+            let err = anyhow::anyhow!(
+                "Merkle tree returned {} proofs for {} keys",
+                entries.len(),
+                all_keys.len()
+            );
+            return Err(err.into());
+        }
+
+        let mut proofs = all_keys
+            .iter()
+            .zip(entries)
+            .map(|(key, entry)| EthStorageProof {
+                key: *key.key(),
+                value: h256_to_u256(entry.value),
+                proof: entry.merkle_path,
+                index: entry.index,
+            });
+        // `unwrap()`s are safe: we've checked the number of proofs above
+        let account_field_proofs = EthAccountFieldProofs {
+            nonce: proofs.next().unwrap(),
+            balance: proofs.next().unwrap(),
+            code_hash: proofs.next().unwrap(),
+        };
+        let (nonce, _) = decompose_full_nonce(account_field_proofs.nonce.value);
+        Ok(EthProof {
+            address,
+            account_proof: vec![],
+            balance: account_field_proofs.balance.value,
+            code_hash: u256_to_h256(account_field_proofs.code_hash.value),
+            nonce,
+            storage_hash: root_hash,
+            storage_proof: proofs.collect(),
+            l1_batch_number,
+            account_field_proofs,
+        })
+    }
+
+    /// Resolves the newest L1 batch processed by the Merkle tree that doesn't include L2 blocks after `block_number`.
+    async fn resolve_proof_l1_batch(
+        connection: &mut Connection<'_, Core>,
+        block_number: L2BlockNumber,
+    ) -> Result<L1BatchNumber, Web3Error> {
+        let resolved = connection
+            .storage_web3_dal()
+            .resolve_l1_batch_number_of_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let l1_batch_number = if let Some(l1_batch_number) = resolved.block_l1_batch {
+            let (_, last_l2_block) = connection
+                .blocks_web3_dal()
+                .get_l2_block_range_of_l1_batch(l1_batch_number)
+                .await
+                .map_err(DalError::generalize)?
+                .with_context(|| format!("L1 batch #{l1_batch_number} has no L2 blocks"))?;
+            if last_l2_block == block_number {
+                Some(l1_batch_number)
+            } else {
+                l1_batch_number.0.checked_sub(1).map(L1BatchNumber)
+            }
+        } else {
+            // The L2 block belongs to the pending L1 batch
+            resolved
+                .pending_l1_batch
+                .0
+                .checked_sub(1)
+                .map(L1BatchNumber)
+        };
+
+        let last_l1_batch_with_tree_data = connection
+            .blocks_dal()
+            .get_last_l1_batch_number_with_tree_data()
+            .await
+            .map_err(DalError::generalize)?;
+        match (l1_batch_number, last_l1_batch_with_tree_data) {
+            (Some(number), Some(last_number)) => Ok(number.min(last_number)),
+            _ => Err(Web3Error::NoBlock),
+        }
+    }
+
     /// Account nonce.
     pub async fn get_transaction_count_impl(
         &self,
@@ -523,7 +928,16 @@ impl EthNamespace {
             .get_transaction_receipts(&[hash])
             .await
             .context("get_transaction_receipts")?;
-        Ok(receipts.into_iter().next())
+        let Some(receipt) = receipts.into_iter().next() else {
+            return Ok(None);
+        };
+        // Logs and execution info are only retained for transactions calling retained addresses.
+        let block_number = L2BlockNumber(receipt.block_number.as_u32());
+        self.state
+            .start_info
+            .ensure_history_not_pruned(block_number, receipt.to.as_slice(), &mut storage)
+            .await?;
+        Ok(Some(receipt))
     }
 
     pub async fn new_block_filter_impl(&self) -> Result<U256, Web3Error> {
@@ -809,6 +1223,15 @@ impl EthNamespace {
                 };
 
                 let mut storage = self.state.acquire_connection().await?;
+                // Events for blocks with pruned history may be incomplete, unless they are emitted by retained addresses.
+                self.state
+                    .start_info
+                    .ensure_history_not_pruned(
+                        *from_block,
+                        &get_logs_filter.addresses,
+                        &mut storage,
+                    )
+                    .await?;
 
                 // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
                 // In this case we should return error and suggest requesting logs with smaller block range.
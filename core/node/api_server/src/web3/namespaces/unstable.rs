@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use zksync_dal::{CoreDal, DalError};
 use zksync_types::{
-    api::{TeeProof, TransactionExecutionInfo},
+    api::{L1BatchL1Costs, TeeProof, TransactionExecutionInfo},
     tee_types::TeeType,
     L1BatchNumber,
 };
@@ -59,4 +59,16 @@ impl UnstableNamespace {
             })
             .collect::<Vec<_>>())
     }
+
+    pub async fn get_l1_batch_l1_costs_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<L1BatchL1Costs>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .eth_sender_dal()
+            .get_l1_batch_l1_costs(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
 }
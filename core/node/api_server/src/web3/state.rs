@@ -17,7 +17,7 @@ use zksync_config::{
     GenesisConfig,
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
-use zksync_metadata_calculator::api_server::TreeApiClient;
+use zksync_metadata_calculator::api_server::{TreeApiClient, TreeApiError, TreeEntryWithProof};
 use zksync_node_sync::SyncState;
 use zksync_types::{
     api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
@@ -87,6 +87,34 @@ impl BlockStartInfo {
             }
         }
     }
+
+    /// Checks whether history (events, call traces and transaction execution info) for the specified L2 block
+    /// is pruned, and returns an error if it is. History is considered complete if `addresses` are non-empty
+    /// and all of them were retained during history pruning.
+    pub(super) async fn ensure_history_not_pruned(
+        &self,
+        block_number: L2BlockNumber,
+        addresses: &[Address],
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), Web3Error> {
+        let Some(history_info) = self.history_pruning_info(storage).await? else {
+            return Ok(());
+        };
+        if block_number > history_info.last_pruned_l2_block {
+            return Ok(());
+        }
+        let is_retained = !addresses.is_empty()
+            && addresses
+                .iter()
+                .all(|address| history_info.retained_addresses.contains(address));
+        if is_retained {
+            Ok(())
+        } else {
+            Err(Web3Error::PrunedBlock(
+                history_info.last_pruned_l2_block + 1,
+            ))
+        }
+    }
 }
 
 /// Configuration values for the API.
@@ -109,6 +137,9 @@ pub struct InternalApiConfig {
     pub l2_testnet_paymaster_addr: Option<Address>,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    pub struct_logger_memory_limit: usize,
+    pub struct_logger_step_limit: usize,
+    pub struct_logger_size_limit: usize,
     pub base_token_address: Option<Address>,
     pub filters_disabled: bool,
     pub dummy_verifier: bool,
@@ -160,6 +191,9 @@ impl InternalApiConfig {
             l2_testnet_paymaster_addr: contracts_config.l2_testnet_paymaster_addr,
             req_entities_limit: web3_config.req_entities_limit(),
             fee_history_limit: web3_config.fee_history_limit(),
+            struct_logger_memory_limit: web3_config.struct_logger_memory_limit(),
+            struct_logger_step_limit: web3_config.struct_logger_step_limit(),
+            struct_logger_size_limit: web3_config.struct_logger_size_limit(),
             base_token_address: contracts_config.base_token_addr,
             filters_disabled: web3_config.filters_disabled,
             dummy_verifier: genesis_config.dummy_verifier,
@@ -293,6 +327,39 @@ impl RpcState {
             .map_err(|err| err.generalize().into())
     }
 
+    /// Obtains Merkle tree proofs for the specified hashed storage keys after the specified L1 batch.
+    /// Returns `None` if the L1 batch is not processed by the tree yet.
+    pub(crate) async fn get_tree_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Option<Vec<TreeEntryWithProof>>, Web3Error> {
+        let tree_api = self
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+        match tree_api.get_proofs(l1_batch_number, hashed_keys).await {
+            Ok(proofs) => Ok(Some(proofs)),
+            Err(TreeApiError::NotReady(_)) => Err(Web3Error::TreeApiUnavailable),
+            Err(TreeApiError::NoVersion(err)) => {
+                if err.missing_version > err.version_count {
+                    Ok(None)
+                } else {
+                    Err(Web3Error::InternalError(anyhow::anyhow!(
+                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                    )))
+                }
+            }
+            Err(TreeApiError::Internal(err)) => Err(Web3Error::InternalError(err)),
+            Err(_) => {
+                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
+                Err(Web3Error::InternalError(anyhow::anyhow!(
+                    "Unspecified tree API error"
+                )))
+            }
+        }
+    }
+
     /// Resolves the specified block ID to a block number, which is guaranteed to be present in the node storage.
     pub(crate) async fn resolve_block(
         &self,
@@ -1,6 +1,9 @@
 //! Tests for the `debug` Web3 namespace.
 
-use zksync_multivm::interface::{Call, TransactionExecutionResult};
+use zksync_multivm::{
+    interface::{Call, ExecutionResult, TransactionExecutionResult},
+    tracers::{prestate_tracer, StructLog},
+};
 use zksync_types::BOOTLOADER_ADDRESS;
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -62,7 +65,9 @@ impl HttpTest for TraceBlockTest {
 
             assert_eq!(block_traces.len(), tx_results.len()); // equals to the number of transactions in the block
             for (trace, tx_result) in block_traces.iter().zip(&tx_results) {
-                let api::ResultDebugCall { result } = trace;
+                let api::TracerResult::CallTrace(result) = &trace.result else {
+                    panic!("Unexpected trace: {trace:?}");
+                };
                 assert_eq!(result.from, Address::zero());
                 assert_eq!(result.to, BOOTLOADER_ADDRESS);
                 assert_eq!(result.gas, tx_result.transaction.gas_limit());
@@ -204,7 +209,9 @@ impl HttpTest for TraceTransactionTest {
         let result = client
             .trace_transaction(tx_results[0].hash, None)
             .await?
-            .context("no transaction traces")?;
+            .context("no transaction traces")?
+            .into_call_trace()
+            .context("unexpected trace type")?;
         assert_eq!(result.from, Address::zero());
         assert_eq!(result.to, BOOTLOADER_ADDRESS);
         assert_eq!(result.gas, tx_results[0].transaction.gas_limit());
@@ -259,3 +266,180 @@ impl HttpTest for TraceBlockTestWithSnapshotRecovery {
 async fn tracing_block_after_snapshot_recovery() {
     test_http_server(TraceBlockTestWithSnapshotRecovery).await;
 }
+
+#[derive(Debug)]
+struct TracePrestateTest;
+
+impl TracePrestateTest {
+    const ACCOUNT: Address = Address::repeat_byte(0x11);
+    const SLOT: H256 = H256::repeat_byte(0x22);
+
+    fn account_state(balance: u64, nonce: u64, slot_value: u8) -> prestate_tracer::Account {
+        prestate_tracer::Account {
+            balance: Some(balance.into()),
+            code: Some(U256::zero()),
+            nonce: Some(nonce.into()),
+            storage: Some(HashMap::from([(Self::SLOT, H256::repeat_byte(slot_value))])),
+        }
+    }
+
+    fn expected_account(balance: u64, nonce: u64, slot_value: u8) -> api::PrestateAccount {
+        api::PrestateAccount {
+            balance: Some(balance.into()),
+            nonce: Some(nonce),
+            code: None,
+            storage: [(Self::SLOT, H256::repeat_byte(slot_value))].into(),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for TracePrestateTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|_, _| ExecutionResult::Success { output: vec![] });
+        tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+        tx_executor.set_prestate_responses(|_| {
+            let pre = HashMap::from([(Self::ACCOUNT, Self::account_state(100, 1, 1))]);
+            let post = HashMap::from([(Self::ACCOUNT, Self::account_state(50, 2, 2))]);
+            (pre, post)
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [
+            execute_l2_transaction(create_l2_transaction(1, 2)),
+            execute_l2_transaction(create_l2_transaction(1, 2)),
+        ];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let mut options = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::CallTracerConfig::default(),
+            struct_logger_config: api::StructLoggerConfig::default(),
+        };
+        let trace = client
+            .trace_transaction(tx_results[1].hash, Some(options.clone()))
+            .await?
+            .context("no transaction traces")?;
+        // In the non-diff mode, the state *before* the transaction must be returned.
+        let expected_trace = api::PrestateTrace::Prestate(
+            [(Self::ACCOUNT, Self::expected_account(100, 1, 1))].into(),
+        );
+        assert_eq!(trace, api::TracerResult::PrestateTrace(expected_trace));
+
+        options.tracer_config.diff_mode = true;
+        let trace = client
+            .trace_transaction(tx_results[1].hash, Some(options))
+            .await?
+            .context("no transaction traces")?;
+        let expected_trace = api::PrestateTrace::Diff {
+            pre: [(Self::ACCOUNT, Self::expected_account(100, 1, 1))].into(),
+            post: [(Self::ACCOUNT, Self::expected_account(50, 2, 2))].into(),
+        };
+        assert_eq!(trace, api::TracerResult::PrestateTrace(expected_trace));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_prestate_tracer() {
+    test_http_server(TracePrestateTest).await;
+}
+
+#[derive(Debug)]
+struct TraceStructLogsTest;
+
+#[async_trait]
+impl HttpTest for TraceStructLogsTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+        tx_executor.set_struct_log_responses(|_, config| {
+            // The step limit isn't specified in the request, so the server limit must be used.
+            let web3_config = Web3JsonRpcConfig::for_tests();
+            assert_eq!(config.limit, web3_config.struct_logger_step_limit());
+            assert_eq!(config.size_limit, web3_config.struct_logger_size_limit());
+            vec![StructLog {
+                pc: 1,
+                op: "Add".to_owned(),
+                gas: 100,
+                gas_cost: 6,
+                depth: 1,
+                stack: None,
+                memory: Some(vec![U256::zero(); config.memory_limit]),
+                storage: None,
+            }]
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_result = execute_l2_transaction(create_l2_transaction(1, 2));
+        let legacy_tx_result = execute_l2_transaction(create_l2_transaction(1, 2));
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[tx_result.clone()]).await?;
+        // Blocks without a protocol version are executed with a VM not supported by the struct logger.
+        let legacy_block = L2BlockHeader {
+            protocol_version: None,
+            ..create_l2_block(2)
+        };
+        store_custom_l2_block(&mut storage, &legacy_block, &[legacy_tx_result.clone()]).await?;
+        drop(storage);
+
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::StructLogger,
+            tracer_config: api::CallTracerConfig::default(),
+            struct_logger_config: api::StructLoggerConfig {
+                enable_memory: true,
+                memory_limit: Some(1_000_000),
+                ..api::StructLoggerConfig::default()
+            },
+        };
+        let trace = client
+            .trace_transaction(tx_result.hash, Some(options.clone()))
+            .await?
+            .context("no transaction traces")?;
+        let api::TracerResult::StructLogs(trace) = trace else {
+            panic!("Unexpected trace: {trace:?}");
+        };
+        assert_eq!(trace.struct_logs.len(), 1);
+        // The requested memory limit must be clamped to the server limit.
+        let memory = trace.struct_logs[0].memory.as_ref().unwrap();
+        assert_eq!(
+            memory.len(),
+            Web3JsonRpcConfig::for_tests().struct_logger_memory_limit()
+        );
+
+        let error = client
+            .trace_transaction(legacy_tx_result.hash, Some(options))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+            assert!(
+                error.message().contains("struct logger is not supported"),
+                "{error:?}"
+            );
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_struct_logger() {
+    test_http_server(TraceStructLogsTest).await;
+}
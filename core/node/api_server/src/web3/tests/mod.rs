@@ -135,6 +135,11 @@ trait HttpTest: Send + Sync {
         StorageInitialization::Genesis
     }
 
+    /// Performs additional storage preparation after [`Self::storage_initialization()`], but before the server is started.
+    async fn prepare_storage(&self, _storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn transaction_executor(&self) -> MockTransactionExecutor {
         MockTransactionExecutor::default()
     }
@@ -219,6 +224,9 @@ async fn test_http_server(test: impl HttpTest) {
         .prepare_storage(&network_config, &mut storage)
         .await
         .expect("Failed preparing storage for test");
+    test.prepare_storage(&mut storage)
+        .await
+        .expect("Failed preparing storage for test");
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
@@ -288,6 +296,16 @@ async fn store_l2_block(
     number: L2BlockNumber,
     transaction_results: &[TransactionExecutionResult],
 ) -> anyhow::Result<L2BlockHeader> {
+    let new_l2_block = create_l2_block(number.0);
+    store_custom_l2_block(storage, &new_l2_block, transaction_results).await?;
+    Ok(new_l2_block)
+}
+
+async fn store_custom_l2_block(
+    storage: &mut Connection<'_, Core>,
+    header: &L2BlockHeader,
+    transaction_results: &[TransactionExecutionResult],
+) -> anyhow::Result<()> {
     for result in transaction_results {
         let l2_tx = result.transaction.clone().try_into().unwrap();
         let tx_submission_result = storage
@@ -298,19 +316,18 @@ async fn store_l2_block(
         assert_matches!(tx_submission_result, L2TxSubmissionResult::Added);
     }
 
-    let new_l2_block = create_l2_block(number.0);
-    storage.blocks_dal().insert_l2_block(&new_l2_block).await?;
+    storage.blocks_dal().insert_l2_block(header).await?;
     storage
         .transactions_dal()
         .mark_txs_as_executed_in_l2_block(
-            new_l2_block.number,
+            header.number,
             transaction_results,
             1.into(),
             ProtocolVersionId::latest(),
             false,
         )
         .await?;
-    Ok(new_l2_block)
+    Ok(())
 }
 
 async fn seal_l1_batch(
@@ -861,6 +878,144 @@ async fn transaction_receipts() {
     test_http_server(TransactionReceiptsTest).await;
 }
 
+#[derive(Debug)]
+struct HistoryPruningTest {
+    retained_tx: L2Tx,
+    pruned_tx: L2Tx,
+}
+
+impl HistoryPruningTest {
+    fn new() -> Self {
+        Self {
+            retained_tx: create_l2_transaction(10, 200),
+            pruned_tx: create_l2_transaction(10, 200),
+        }
+    }
+
+    fn retained_address(&self) -> Address {
+        self.retained_tx.execute.contract_address
+    }
+}
+
+#[async_trait]
+impl HttpTest for HistoryPruningTest {
+    async fn prepare_storage(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        let tx_results = [
+            execute_l2_transaction(self.retained_tx.clone()),
+            execute_l2_transaction(self.pruned_tx.clone()),
+        ];
+        store_l2_block(storage, L2BlockNumber(1), &tx_results).await?;
+        let events: Vec<_> = tx_results
+            .iter()
+            .enumerate()
+            .map(|(i, tx_result)| VmEvent {
+                location: (L1BatchNumber(1), i as u32),
+                address: tx_result.transaction.execute.contract_address,
+                indexed_topics: vec![H256::repeat_byte(42)],
+                value: vec![],
+            })
+            .collect();
+        let events: Vec<_> = tx_results
+            .iter()
+            .zip(&events)
+            .enumerate()
+            .map(|(i, (tx_result, event))| {
+                let location = IncludedTxLocation {
+                    tx_hash: tx_result.hash,
+                    tx_index_in_l2_block: i as u32,
+                    tx_initiator_address: tx_result.transaction.initiator_account(),
+                };
+                (location, vec![event])
+            })
+            .collect();
+        storage
+            .events_dal()
+            .save_events(L2BlockNumber(1), &events)
+            .await?;
+        seal_l1_batch(storage, L1BatchNumber(1)).await?;
+        store_l2_block(storage, L2BlockNumber(2), &[]).await?;
+        seal_l1_batch(storage, L1BatchNumber(2)).await?;
+
+        storage
+            .pruning_dal()
+            .prune_history(
+                L1BatchNumber(1),
+                L2BlockNumber(1),
+                &[self.retained_address()],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let first_retained_block = L2BlockNumber(2);
+        let pruned_block_filter = Filter {
+            from_block: Some(api::BlockNumber::Number(1.into())),
+            ..Filter::default()
+        };
+        let error = client
+            .get_logs(pruned_block_filter.clone())
+            .await
+            .unwrap_err();
+        assert_pruned_block_error(&error, first_retained_block);
+        let error = client
+            .get_logs(Filter {
+                address: Some(self.pruned_tx.execute.contract_address.into()),
+                ..pruned_block_filter.clone()
+            })
+            .await
+            .unwrap_err();
+        assert_pruned_block_error(&error, first_retained_block);
+
+        let logs = client
+            .get_logs(Filter {
+                address: Some(self.retained_address().into()),
+                ..pruned_block_filter
+            })
+            .await?;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, self.retained_address());
+        let logs = client
+            .get_logs(Filter {
+                from_block: Some(api::BlockNumber::Number(first_retained_block.0.into())),
+                ..Filter::default()
+            })
+            .await?;
+        assert!(logs.is_empty(), "{logs:?}");
+
+        let receipt = client
+            .get_transaction_receipt(self.retained_tx.hash())
+            .await?
+            .context("no receipt")?;
+        assert_eq!(receipt.logs.len(), 1);
+        let error = client
+            .get_transaction_receipt(self.pruned_tx.hash())
+            .await
+            .unwrap_err();
+        assert_pruned_block_error(&error, first_retained_block);
+        let error = client
+            .get_block_receipts(api::BlockId::Number(1.into()))
+            .await
+            .unwrap_err();
+        assert_pruned_block_error(&error, first_retained_block);
+        let receipts = client
+            .get_block_receipts(api::BlockId::Number(2.into()))
+            .await?
+            .context("no receipts")?;
+        assert!(receipts.is_empty());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn history_pruning() {
+    test_http_server(HistoryPruningTest::new()).await;
+}
+
 #[derive(Debug)]
 struct AllAccountBalancesTest;
 
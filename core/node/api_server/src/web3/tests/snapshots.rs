@@ -59,7 +59,7 @@ impl HttpTest for SnapshotBasicsTest {
                 .await?;
         }
 
-        let all_snapshots = client.get_all_snapshots().await?;
+        let all_snapshots = client.get_all_snapshots(None).await?;
         if self.is_complete_snapshot() {
             assert_eq!(all_snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(1)]);
         } else {
@@ -109,3 +109,77 @@ async fn snapshot_with_some_chunks() {
 async fn snapshot_with_all_chunks() {
     test_http_server(SnapshotBasicsTest::new(0..SnapshotBasicsTest::CHUNK_COUNT)).await;
 }
+
+#[derive(Debug)]
+struct DeltaSnapshotsTest;
+
+#[async_trait]
+impl HttpTest for DeltaSnapshotsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await.unwrap();
+        for number in 1..=2 {
+            store_l2_block(
+                &mut storage,
+                L2BlockNumber(number),
+                &[execute_l2_transaction(create_l2_transaction(1, 2))],
+            )
+            .await?;
+            seal_l1_batch(&mut storage, L1BatchNumber(number)).await?;
+        }
+        storage
+            .snapshots_dal()
+            .add_snapshot(
+                SnapshotVersion::Version1,
+                L1BatchNumber(1),
+                1,
+                "file:///factory_deps",
+            )
+            .await?;
+        storage
+            .snapshots_dal()
+            .add_delta_snapshot(
+                SnapshotVersion::Version2,
+                L1BatchNumber(2),
+                L1BatchNumber(1),
+                1,
+                "file:///factory_deps_delta",
+            )
+            .await?;
+        for number in 1..=2 {
+            storage
+                .snapshots_dal()
+                .add_storage_logs_filepath_for_snapshot(
+                    L1BatchNumber(number),
+                    0,
+                    "file:///storage_logs/chunk0",
+                )
+                .await?;
+        }
+
+        // Delta snapshots must not be returned to clients that haven't explicitly asked for them.
+        let all_snapshots = client.get_all_snapshots(None).await?;
+        assert_eq!(all_snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(1)]);
+        let all_snapshots = client.get_all_snapshots(Some(true)).await?;
+        assert_eq!(
+            all_snapshots.snapshots_l1_batch_numbers,
+            [L1BatchNumber(2), L1BatchNumber(1)]
+        );
+
+        let delta_header = client
+            .get_snapshot_by_l1_batch_number(L1BatchNumber(2))
+            .await?
+            .context("no snapshot for L1 batch #2")?;
+        assert_eq!(delta_header.version, SnapshotVersion::Version2 as u16);
+        assert_eq!(delta_header.base_l1_batch_number, Some(L1BatchNumber(1)));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn delta_snapshots_are_only_returned_on_request() {
+    test_http_server(DeltaSnapshotsTest).await;
+}
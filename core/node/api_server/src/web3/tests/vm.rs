@@ -2,9 +2,12 @@
 
 use std::sync::atomic::{AtomicU32, Ordering};
 
-use api::state_override::{OverrideAccount, StateOverride};
+use api::{
+    simulate::{BlockOverrides, SimulatePayload, SimulatedBlockCalls},
+    state_override::{OverrideAccount, StateOverride},
+};
 use zksync_multivm::interface::{
-    ExecutionResult, VmExecutionLogs, VmExecutionResultAndLogs, VmRevertReason,
+    ExecutionResult, Halt, VmExecutionLogs, VmExecutionResultAndLogs, VmRevertReason,
 };
 use zksync_types::{
     api::ApiStorageLog, get_intrinsic_constants, transaction_request::CallRequest, K256PrivateKey,
@@ -388,7 +391,10 @@ async fn send_raw_transaction_with_detailed_output() {
 struct TraceCallTest;
 
 impl TraceCallTest {
-    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugCall) {
+    fn assert_debug_call(call_request: &CallRequest, call_result: &api::TracerResult) {
+        let api::TracerResult::CallTrace(call_result) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
         assert_eq!(call_result.from, Address::zero());
         assert_eq!(call_result.gas, call_request.gas.unwrap());
         assert_eq!(call_result.value, call_request.value.unwrap());
@@ -737,3 +743,171 @@ impl HttpTest for EstimateGasWithStateOverrideTest {
 async fn estimate_gas_with_state_override() {
     test_http_server(EstimateGasWithStateOverrideTest::new(false)).await;
 }
+
+#[derive(Debug)]
+struct SimulateV1Test;
+
+impl SimulateV1Test {
+    fn call_request(data: &[u8]) -> CallRequest {
+        CallRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Address::repeat_byte(2)),
+            data: Some(data.to_vec().into()),
+            ..CallRequest::default()
+        }
+    }
+
+    fn assert_invalid_params(error: ClientError) {
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for SimulateV1Test {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, block_args| {
+            assert_eq!(block_args.simulation_base_block_number(), L2BlockNumber(0));
+            match tx.execute.calldata() {
+                b"success" => ExecutionResult::Success {
+                    output: b"output".to_vec(),
+                },
+                b"revert" => ExecutionResult::Revert {
+                    output: VmRevertReason::General {
+                        msg: "oops".to_owned(),
+                        data: vec![1, 2, 3],
+                    },
+                },
+                b"halt" => ExecutionResult::Halt {
+                    reason: Halt::FailedToChargeFee(VmRevertReason::General {
+                        msg: "no funds".to_owned(),
+                        data: vec![],
+                    }),
+                },
+                data => panic!("Unexpected calldata: {data:?}"),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let payload = SimulatePayload {
+            block_state_calls: vec![
+                SimulatedBlockCalls {
+                    block_overrides: Some(BlockOverrides {
+                        time: Some(1_000.into()),
+                        base_fee_per_gas: Some(100.into()),
+                        ..BlockOverrides::default()
+                    }),
+                    state_overrides: None,
+                    calls: vec![
+                        Self::call_request(b"success"),
+                        Self::call_request(b"revert"),
+                    ],
+                },
+                SimulatedBlockCalls {
+                    block_overrides: Some(BlockOverrides {
+                        number: Some(2.into()),
+                        ..BlockOverrides::default()
+                    }),
+                    state_overrides: None,
+                    calls: vec![Self::call_request(b"halt"), Self::call_request(b"success")],
+                },
+            ],
+            ..SimulatePayload::default()
+        };
+        let blocks = client.simulate_v1(payload.clone(), None).await?;
+        assert_eq!(blocks.len(), 2);
+
+        let first_block = &blocks[0];
+        assert_eq!(first_block.inner.number, 1.into());
+        assert_eq!(first_block.inner.timestamp, 1_000.into());
+        assert_eq!(first_block.inner.base_fee_per_gas, 100.into());
+        assert_eq!(first_block.inner.transactions.len(), 2);
+        assert_eq!(first_block.calls.len(), 2);
+        assert_eq!(first_block.calls[0].status, 1.into());
+        assert_eq!(first_block.calls[0].return_data.0, b"output");
+        assert!(first_block.calls[0].error.is_none());
+        assert_eq!(first_block.calls[1].status, 0.into());
+        assert_eq!(first_block.calls[1].return_data.0, [1, 2, 3]);
+        let error = first_block.calls[1].error.as_ref().unwrap();
+        assert_eq!(error.code, 3);
+        assert_eq!(error.message, "oops");
+
+        let second_block = &blocks[1];
+        assert_eq!(second_block.inner.number, 2.into());
+        assert_eq!(second_block.inner.timestamp, 1_001.into());
+        assert_eq!(second_block.inner.parent_hash, first_block.inner.hash);
+        assert_ne!(second_block.inner.hash, first_block.inner.hash);
+        // A halted call must not abort the simulation.
+        assert_eq!(second_block.calls.len(), 2);
+        assert_eq!(second_block.calls[0].status, 0.into());
+        let error = second_block.calls[0].error.as_ref().unwrap();
+        assert_eq!(error.code, -32015);
+        assert!(error.message.contains("no funds"), "{error:?}");
+        assert_eq!(second_block.calls[1].status, 1.into());
+
+        // Blocks without calls still advance the block number and timestamp.
+        let mut payload_with_empty_block = payload.clone();
+        payload_with_empty_block.block_state_calls[1].calls.clear();
+        payload_with_empty_block
+            .block_state_calls
+            .push(SimulatedBlockCalls {
+                block_overrides: None,
+                state_overrides: None,
+                calls: vec![Self::call_request(b"success")],
+            });
+        let blocks = client.simulate_v1(payload_with_empty_block, None).await?;
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].inner.number, 2.into());
+        assert_eq!(blocks[1].inner.timestamp, 1_001.into());
+        assert!(blocks[1].calls.is_empty());
+        assert!(blocks[1].inner.transactions.is_empty());
+        assert_eq!(blocks[2].inner.number, 3.into());
+        assert_eq!(blocks[2].inner.timestamp, 1_002.into());
+        assert_eq!(blocks[2].inner.parent_hash, blocks[1].inner.hash);
+        assert_eq!(blocks[2].calls.len(), 1);
+
+        let mut invalid_payload = payload.clone();
+        invalid_payload.block_state_calls[1].block_overrides = Some(BlockOverrides {
+            number: Some(5.into()),
+            ..BlockOverrides::default()
+        });
+        let error = client.simulate_v1(invalid_payload, None).await.unwrap_err();
+        Self::assert_invalid_params(error);
+
+        let mut invalid_payload = payload.clone();
+        invalid_payload.block_state_calls[1].block_overrides = Some(BlockOverrides {
+            time: Some(1_000.into()),
+            ..BlockOverrides::default()
+        });
+        let error = client.simulate_v1(invalid_payload, None).await.unwrap_err();
+        Self::assert_invalid_params(error);
+
+        let mut invalid_payload = payload.clone();
+        invalid_payload.block_state_calls[1].calls = vec![Self::call_request(b"success"); 1_000];
+        let error = client.simulate_v1(invalid_payload, None).await.unwrap_err();
+        Self::assert_invalid_params(error);
+
+        let invalid_payload = SimulatePayload {
+            validation: true,
+            ..payload
+        };
+        let error = client.simulate_v1(invalid_payload, None).await.unwrap_err();
+        Self::assert_invalid_params(error);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulate_v1_basics() {
+    test_http_server(SimulateV1Test).await;
+}
@@ -7,7 +7,7 @@ use tokio::sync::watch::Receiver;
 use zksync_config::DADispatcherConfig;
 use zksync_da_client::{types::DAError, DataAvailabilityClient};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::L1BatchNumber;
+use zksync_types::{pubdata_da::DataAvailabilityBlobPart, L1BatchNumber};
 
 use crate::metrics::METRICS;
 
@@ -80,29 +80,30 @@ impl DataAvailabilityDispatcher {
 
         for batch in batches {
             let dispatch_latency = METRICS.blob_dispatch_latency.start();
-            let dispatch_response = retry(self.config.max_retries(), batch.l1_batch_number, || {
-                self.client
-                    .dispatch_blob(batch.l1_batch_number.0, batch.pubdata.clone())
-            })
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to dispatch a blob with batch_number: {}, pubdata_len: {}",
-                    batch.l1_batch_number,
-                    batch.pubdata.len()
-                )
-            })?;
+            let blob_id = match self.client.blob_size_limit() {
+                Some(limit) if batch.pubdata.len() > limit => {
+                    self.dispatch_parts(batch.l1_batch_number, &batch.pubdata, limit)
+                        .await?
+                }
+                _ => {
+                    // Parts may be left over if the pubdata was split with a lower blob size limit before.
+                    let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+                    conn.data_availability_dal()
+                        .delete_l1_batch_da_parts(batch.l1_batch_number)
+                        .await?;
+                    drop(conn);
+
+                    self.dispatch_blob(batch.l1_batch_number, batch.pubdata.clone())
+                        .await?
+                }
+            };
             let dispatch_latency_duration = dispatch_latency.observe();
 
             let sent_at = Utc::now().naive_utc();
 
             let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
             conn.data_availability_dal()
-                .insert_l1_batch_da(
-                    batch.l1_batch_number,
-                    dispatch_response.blob_id.as_str(),
-                    sent_at,
-                )
+                .insert_l1_batch_da(batch.l1_batch_number, blob_id.as_str(), sent_at)
                 .await?;
             drop(conn);
 
@@ -120,6 +121,91 @@ impl DataAvailabilityDispatcher {
         Ok(())
     }
 
+    async fn dispatch_blob(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        data: Vec<u8>,
+    ) -> anyhow::Result<String> {
+        let data_len = data.len();
+        let dispatch_response = retry(self.config.max_retries(), l1_batch_number, || {
+            self.client.dispatch_blob(l1_batch_number.0, data.clone())
+        })
+        .await
+        .with_context(|| {
+            format!(
+                "failed to dispatch a blob with batch_number: {l1_batch_number}, pubdata_len: {data_len}"
+            )
+        })?;
+        Ok(dispatch_response.blob_id)
+    }
+
+    /// Dispatches the pubdata exceeding the blob size limit as several blobs, each of which is saved in the database
+    /// once dispatched. Parts dispatched before the dispatcher restart are not dispatched again, unless the pubdata
+    /// was split with a different blob size limit; in this case, all parts are discarded and dispatched from scratch.
+    ///
+    /// Returns the blob_id for the entire pubdata, which consists of blob IDs of all parts.
+    async fn dispatch_parts(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        pubdata: &[u8],
+        blob_size_limit: usize,
+    ) -> anyhow::Result<String> {
+        let chunks = split_pubdata(pubdata, blob_size_limit);
+        let chunk_size = blob_size_limit as u64;
+
+        let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+        let mut dispatched_parts = conn
+            .data_availability_dal()
+            .get_l1_batch_da_parts(l1_batch_number)
+            .await?;
+        let parts_match_chunks = dispatched_parts
+            .iter()
+            .all(|part| part.chunk_size == chunk_size && (part.part_index as usize) < chunks.len());
+        if !parts_match_chunks {
+            tracing::warn!(
+                "Pubdata parts dispatched for L1 batch #{l1_batch_number} don't match the current blob size limit \
+                 {blob_size_limit}; dispatching pubdata from scratch"
+            );
+            conn.data_availability_dal()
+                .delete_l1_batch_da_parts(l1_batch_number)
+                .await?;
+            dispatched_parts.clear();
+        }
+        drop(conn);
+
+        let mut blob_ids = Vec::with_capacity(chunks.len());
+        for (part_index, chunk) in chunks.into_iter().enumerate() {
+            let part_index = part_index as u32;
+            if let Some(part) = dispatched_parts
+                .iter()
+                .find(|part| part.part_index == part_index)
+            {
+                blob_ids.push(part.blob_id.clone());
+                continue;
+            }
+
+            let blob_id = self
+                .dispatch_blob(l1_batch_number, chunk.to_vec())
+                .await
+                .with_context(|| format!("failed dispatching pubdata part {part_index}"))?;
+            let sent_at = Utc::now().naive_utc();
+
+            let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+            conn.data_availability_dal()
+                .insert_l1_batch_da_part(l1_batch_number, part_index, chunk_size, &blob_id, sent_at)
+                .await?;
+            drop(conn);
+
+            METRICS.blob_part_size.observe(chunk.len());
+            tracing::debug!(
+                "Dispatched pubdata part {part_index} for batch_number: {l1_batch_number}, blob_id: {blob_id}"
+            );
+            blob_ids.push(blob_id);
+        }
+        METRICS.blob_parts.observe(blob_ids.len());
+        Ok(blob_ids.join(BLOB_ID_SEPARATOR))
+    }
+
     /// Polls the data availability layer for inclusion data, and saves it in the database.
     async fn poll_for_inclusion(&self) -> anyhow::Result<()> {
         let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
@@ -127,22 +213,29 @@ impl DataAvailabilityDispatcher {
             .data_availability_dal()
             .get_first_da_blob_awaiting_inclusion()
             .await?;
-        drop(conn);
-
         let Some(blob_info) = blob_info else {
             return Ok(());
         };
+        let parts = conn
+            .data_availability_dal()
+            .get_l1_batch_da_parts(blob_info.l1_batch_number)
+            .await?;
+        drop(conn);
 
-        let inclusion_data = self
-            .client
-            .get_inclusion_data(blob_info.blob_id.as_str())
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to get inclusion data for blob_id: {}, batch_number: {}",
-                    blob_info.blob_id, blob_info.l1_batch_number
-                )
-            })?;
+        let inclusion_data = if parts.is_empty() {
+            self.client
+                .get_inclusion_data(blob_info.blob_id.as_str())
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to get inclusion data for blob_id: {}, batch_number: {}",
+                        blob_info.blob_id, blob_info.l1_batch_number
+                    )
+                })?
+                .map(|inclusion_data| inclusion_data.data)
+        } else {
+            self.poll_for_parts_inclusion(parts).await?
+        };
 
         let Some(inclusion_data) = inclusion_data else {
             return Ok(());
@@ -152,7 +245,7 @@ impl DataAvailabilityDispatcher {
         conn.data_availability_dal()
             .save_l1_batch_inclusion_data(
                 L1BatchNumber(blob_info.l1_batch_number.0),
-                inclusion_data.data.as_slice(),
+                inclusion_data.as_slice(),
             )
             .await?;
         drop(conn);
@@ -173,6 +266,72 @@ impl DataAvailabilityDispatcher {
 
         Ok(())
     }
+
+    /// Polls the data availability layer for inclusion data of the pubdata parts that don't have it yet.
+    /// Returns the combined inclusion data once all parts are included.
+    async fn poll_for_parts_inclusion(
+        &self,
+        mut parts: Vec<DataAvailabilityBlobPart>,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        for part in parts
+            .iter_mut()
+            .filter(|part| part.inclusion_data.is_none())
+        {
+            let inclusion_data = self
+                .client
+                .get_inclusion_data(part.blob_id.as_str())
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to get inclusion data for blob_id: {}, batch_number: {}, part: {}",
+                        part.blob_id, part.l1_batch_number, part.part_index
+                    )
+                })?;
+            let Some(inclusion_data) = inclusion_data else {
+                return Ok(None);
+            };
+
+            let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+            conn.data_availability_dal()
+                .save_l1_batch_da_part_inclusion_data(
+                    part.l1_batch_number,
+                    part.part_index,
+                    inclusion_data.data.as_slice(),
+                )
+                .await?;
+            drop(conn);
+
+            tracing::debug!(
+                "Received an inclusion data for part {} of batch_number: {}",
+                part.part_index,
+                part.l1_batch_number
+            );
+            part.inclusion_data = Some(inclusion_data.data);
+        }
+
+        let parts_inclusion_data = parts
+            .into_iter()
+            .map(|part| part.inclusion_data.unwrap_or_default());
+        Ok(Some(combine_inclusion_data(parts_inclusion_data)))
+    }
+}
+
+/// Separator for blob IDs of the pubdata parts in the combined blob_id.
+const BLOB_ID_SEPARATOR: &str = ",";
+
+fn split_pubdata(pubdata: &[u8], blob_size_limit: usize) -> Vec<&[u8]> {
+    pubdata.chunks(blob_size_limit).collect()
+}
+
+/// Combines the inclusion data of the pubdata parts ordered by the part index. Inclusion data of each part is prefixed
+/// with its length encoded as a 4-byte big-endian integer.
+fn combine_inclusion_data(parts_inclusion_data: impl Iterator<Item = Vec<u8>>) -> Vec<u8> {
+    let mut combined = vec![];
+    for data in parts_inclusion_data {
+        combined.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        combined.extend_from_slice(&data);
+    }
+    combined
 }
 
 async fn retry<T, Fut, F>(
@@ -208,3 +367,24 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitting_pubdata() {
+        let pubdata: Vec<u8> = (0..10).collect();
+        let parts = split_pubdata(&pubdata, 4);
+        assert_eq!(parts, [&pubdata[..4], &pubdata[4..8], &pubdata[8..]]);
+
+        let parts = split_pubdata(&pubdata, 10);
+        assert_eq!(parts, [&pubdata[..]]);
+    }
+
+    #[test]
+    fn combining_inclusion_data() {
+        let combined = combine_inclusion_data([vec![1, 2], vec![], vec![3]].into_iter());
+        assert_eq!(combined, [0, 0, 0, 2, 1, 2, 0, 0, 0, 0, 0, 0, 0, 1, 3]);
+    }
+}
@@ -19,6 +19,12 @@ pub(super) struct DataAvailabilityDispatcherMetrics {
     /// Buckets are bytes ranging from 1 KB to 16 MB, which has to satisfy all blob size values.
     #[metrics(buckets = Buckets::exponential(1_024.0..=16.0 * 1_024.0 * 1_024.0, 2.0), unit = Unit::Bytes)]
     pub blob_size: Histogram<usize>,
+    /// Size of a part of the pubdata dispatched as a separate blob because the pubdata exceeds the blob size limit.
+    #[metrics(buckets = Buckets::exponential(1_024.0..=16.0 * 1_024.0 * 1_024.0, 2.0), unit = Unit::Bytes)]
+    pub blob_part_size: Histogram<usize>,
+    /// Number of blobs the pubdata exceeding the blob size limit is split into.
+    #[metrics(buckets = Buckets::exponential(1.0..=256.0, 2.0))]
+    pub blob_parts: Histogram<usize>,
 
     /// Number of transactions resent by the DA dispatcher.
     #[metrics(buckets = Buckets::linear(0.0..=10.0, 1.0))]
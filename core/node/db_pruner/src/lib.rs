@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 use zksync_dal::{pruning_dal::PruningInfo, Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_types::{L1BatchNumber, L2BlockNumber};
+use zksync_types::{Address, L1BatchNumber, L2BlockNumber};
 
 use self::{
     metrics::{ConditionOutcome, PruneType, METRICS},
@@ -36,6 +36,23 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// Whether L1 batches should be pruned. If set to `false`, the pruner will only prune history
+    /// if [`Self::history_pruning`] is specified.
+    pub prune_l1_batches: bool,
+    /// History pruning configuration. If not specified, history is only removed together with pruned L1 batches.
+    pub history_pruning: Option<HistoryPruningConfig>,
+}
+
+/// Configuration of history pruning ("history-light" mode). History pruning removes events, call traces
+/// and transaction execution info for old L1 batches, but retains L1 batches, L2 blocks, transactions and storage state.
+#[derive(Debug, Clone)]
+pub struct HistoryPruningConfig {
+    /// Minimum age of an L1 batch in order for its history to be pruned. Setting this to zero
+    /// will effectively disable this pruning criterion.
+    pub minimum_l1_batch_age: Duration,
+    /// Addresses with retained history: events emitted by these addresses, and events, call traces
+    /// and execution info of transactions calling these addresses are not pruned.
+    pub retained_addresses: Vec<Address>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,11 +96,12 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    history_prune_conditions: Vec<Arc<dyn PruneCondition>>,
 }
 
 impl DbPruner {
     pub fn new(config: DbPrunerConfig, connection_pool: ConnectionPool<Core>) -> Self {
-        let mut conditions: Vec<Arc<dyn PruneCondition>> = vec![
+        let base_conditions: Vec<Arc<dyn PruneCondition>> = vec![
             Arc::new(L1BatchExistsCondition {
                 pool: connection_pool.clone(),
             }),
@@ -97,6 +115,7 @@ impl DbPruner {
                 pool: connection_pool.clone(),
             }),
         ];
+        let mut conditions = base_conditions.clone();
         if config.minimum_l1_batch_age > Duration::ZERO {
             // Do not add a condition if it's trivial in order to not clutter logs.
             conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
@@ -105,9 +124,22 @@ impl DbPruner {
             }));
         }
 
-        Self::with_conditions(config, connection_pool, conditions)
+        let mut history_conditions = base_conditions;
+        if let Some(history_config) = &config.history_pruning {
+            if history_config.minimum_l1_batch_age > Duration::ZERO {
+                history_conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
+                    minimum_age: history_config.minimum_l1_batch_age,
+                    pool: connection_pool.clone(),
+                }));
+            }
+        }
+
+        let mut this = Self::with_conditions(config, connection_pool, conditions);
+        this.history_prune_conditions = history_conditions;
+        this
     }
 
+    /// Creates a pruner with the same conditions for L1 batch and history pruning.
     fn with_conditions(
         config: DbPrunerConfig,
         connection_pool: ConnectionPool<Core>,
@@ -117,6 +149,7 @@ impl DbPruner {
             config,
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
+            history_prune_conditions: prune_conditions.clone(),
             prune_conditions,
         }
     }
@@ -126,11 +159,22 @@ impl DbPruner {
     }
 
     async fn is_l1_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> bool {
+        Self::check_conditions(&self.prune_conditions, l1_batch_number).await
+    }
+
+    async fn is_l1_batch_history_prunable(&self, l1_batch_number: L1BatchNumber) -> bool {
+        Self::check_conditions(&self.history_prune_conditions, l1_batch_number).await
+    }
+
+    async fn check_conditions(
+        conditions: &[Arc<dyn PruneCondition>],
+        l1_batch_number: L1BatchNumber,
+    ) -> bool {
         let mut successful_conditions = vec![];
         let mut failed_conditions = vec![];
         let mut errored_conditions = vec![];
 
-        for condition in &self.prune_conditions {
+        for condition in conditions {
             let outcome = match condition.is_batch_prunable(l1_batch_number).await {
                 Ok(true) => {
                     successful_conditions.push(condition.to_string());
@@ -271,11 +315,77 @@ impl DbPruner {
         Ok(PruningIterationOutcome::Pruned)
     }
 
+    /// Prunes history for the next chunk of L1 batches. Returns `false` if there's nothing to prune.
+    async fn prune_history(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        config: &HistoryPruningConfig,
+    ) -> anyhow::Result<bool> {
+        let start = Instant::now();
+        let mut transaction = storage.start_transaction().await?;
+
+        let last_history_pruned_l1_batch = transaction
+            .pruning_dal()
+            .get_last_history_pruned_batch()
+            .await?
+            .map(|(l1_batch, _)| l1_batch);
+        // History for hard-pruned L1 batches is already removed, so there's no need to process these batches again.
+        let last_hard_pruned_l1_batch = transaction
+            .pruning_dal()
+            .get_pruning_info()
+            .await?
+            .last_hard_pruned_l1_batch;
+        let last_pruned_l1_batch = last_history_pruned_l1_batch
+            .max(last_hard_pruned_l1_batch)
+            .unwrap_or(L1BatchNumber(0));
+        let next_l1_batch_to_prune = last_pruned_l1_batch + self.config.pruned_batch_chunk_size;
+        if !self
+            .is_l1_batch_history_prunable(next_l1_batch_to_prune)
+            .await
+        {
+            return Ok(false);
+        }
+
+        let (_, next_l2_block_to_prune) = transaction
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(next_l1_batch_to_prune)
+            .await?
+            .with_context(|| format!("L1 batch #{next_l1_batch_to_prune} is ready to be history-pruned, but has no L2 blocks"))?;
+        let stats = transaction
+            .pruning_dal()
+            .prune_history(
+                next_l1_batch_to_prune,
+                next_l2_block_to_prune,
+                &config.retained_addresses,
+            )
+            .await?;
+        transaction.commit().await?;
+
+        let latency = start.elapsed();
+        METRICS.pruning_chunk_duration[&PruneType::History].observe(latency);
+        METRICS.observe_history_pruning(stats);
+        tracing::info!(
+            "Pruned history for l1_batches up to {next_l1_batch_to_prune} and L2 blocks up to {next_l2_block_to_prune}, \
+             operation took {latency:?}"
+        );
+        Ok(true)
+    }
+
     async fn run_single_iteration(
         &self,
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> anyhow::Result<PruningIterationOutcome> {
         let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        let mut outcome = PruningIterationOutcome::NoOp;
+        if let Some(history_config) = &self.config.history_pruning {
+            if self.prune_history(&mut storage, history_config).await? {
+                outcome = PruningIterationOutcome::Pruned;
+            }
+        }
+        if !self.config.prune_l1_batches {
+            return Ok(outcome);
+        }
+
         let current_pruning_info = storage.pruning_dal().get_pruning_info().await?;
         self.update_health(current_pruning_info);
 
@@ -285,7 +395,7 @@ impl DbPruner {
         {
             let pruning_done = self.soft_prune(&mut storage).await?;
             if !pruning_done {
-                return Ok(PruningIterationOutcome::NoOp);
+                return Ok(outcome);
             }
         }
         drop(storage); // Don't hold a connection across a timeout
@@ -304,9 +414,13 @@ impl DbPruner {
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let next_iteration_delay = self.config.removal_delay / 2;
         tracing::info!(
-            "Starting Postgres pruning with configuration {:?}, prune conditions {:?}",
+            "Starting Postgres pruning with configuration {:?}, prune conditions {:?}, history prune conditions {:?}",
             self.config,
             self.prune_conditions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            self.history_prune_conditions
                 .iter()
                 .map(ToString::to_string)
                 .collect::<Vec<_>>()
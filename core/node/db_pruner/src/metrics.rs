@@ -3,7 +3,7 @@ use std::time::Duration;
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
 };
-use zksync_dal::pruning_dal::HardPruningStats;
+use zksync_dal::pruning_dal::{HardPruningStats, HistoryPruningStats};
 
 use crate::prune_conditions::PruneCondition;
 
@@ -13,6 +13,7 @@ pub(super) enum PruneType {
     NoOp,
     Soft,
     Hard,
+    History,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
@@ -24,6 +25,7 @@ enum PrunedEntityType {
     Event,
     L2ToL1Log,
     CallTrace,
+    TransactionExecutionInfo,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
@@ -56,6 +58,9 @@ pub(super) struct DbPrunerMetrics {
     /// Number of entities deleted during a single hard pruning iteration, grouped by entity type.
     #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
+    /// Number of entities removed during a single history pruning iteration, grouped by entity type.
+    #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
+    history_pruned_entities: Family<PrunedEntityType, Histogram<u64>>,
     /// Number of times a certain condition has resulted in a specific outcome (succeeded, failed, or errored).
     condition_outcomes: Family<ConditionOutcomeLabels, Counter>,
 }
@@ -84,6 +89,23 @@ impl DbPrunerMetrics {
         self.deleted_entities[&PrunedEntityType::CallTrace].observe(deleted_call_traces);
     }
 
+    pub fn observe_history_pruning(&self, stats: HistoryPruningStats) {
+        let HistoryPruningStats {
+            deleted_events,
+            deleted_call_traces,
+            cleared_transactions,
+        } = stats;
+        tracing::info!(
+            "Performed history pruning of database, deleted {deleted_events} events, {deleted_call_traces} call traces, \
+             cleared execution info for {cleared_transactions} transactions"
+        );
+
+        self.history_pruned_entities[&PrunedEntityType::Event].observe(deleted_events);
+        self.history_pruned_entities[&PrunedEntityType::CallTrace].observe(deleted_call_traces);
+        self.history_pruned_entities[&PrunedEntityType::TransactionExecutionInfo]
+            .observe(cleared_transactions);
+    }
+
     pub fn observe_condition(&self, condition: &dyn PruneCondition, outcome: ConditionOutcome) {
         let labels = ConditionOutcomeLabels {
             condition: condition.metric_label(),
@@ -79,6 +79,8 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -153,6 +155,8 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -192,6 +196,8 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -228,6 +234,66 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
     );
 }
 
+#[test(tokio::test)]
+async fn history_pruning_without_l1_batch_pruning() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let condition = Arc::new(
+        ConditionMock::name("first chunk prunable")
+            .with_response(L1BatchNumber(3), true)
+            .with_response(L1BatchNumber(6), false),
+    );
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: false,
+            history_pruning: Some(HistoryPruningConfig {
+                minimum_l1_batch_age: Duration::ZERO,
+                retained_addresses: vec![],
+            }),
+        },
+        pool.clone(),
+        vec![condition],
+    );
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::Pruned);
+    assert_eq!(
+        conn.pruning_dal()
+            .get_last_history_pruned_batch()
+            .await
+            .unwrap(),
+        Some((L1BatchNumber(3), L2BlockNumber(7)))
+    );
+    // L1 batches must not be pruned.
+    assert_eq!(
+        PruningInfo::default(),
+        conn.pruning_dal().get_pruning_info().await.unwrap()
+    );
+
+    // The next chunk is not prunable.
+    let outcome = pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_matches!(outcome, PruningIterationOutcome::NoOp);
+    assert_eq!(
+        conn.pruning_dal()
+            .get_last_history_pruned_batch()
+            .await
+            .unwrap(),
+        Some((L1BatchNumber(3), L2BlockNumber(7)))
+    );
+}
+
 #[test(tokio::test)]
 async fn unconstrained_pruner_with_fresh_database() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -240,6 +306,8 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -290,6 +358,8 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -340,6 +410,8 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -509,6 +581,8 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        prune_l1_batches: true,
+        history_pruning: None,
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -565,6 +639,8 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -594,6 +670,8 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            prune_l1_batches: true,
+            history_pruning: None,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
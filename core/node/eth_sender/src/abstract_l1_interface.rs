@@ -1,6 +1,7 @@
 use std::fmt;
 
 use async_trait::async_trait;
+use serde::Serialize;
 use vise::{EncodeLabelSet, EncodeLabelValue};
 use zksync_eth_client::{
     BoundEthInterface, EnrichedClientResult, EthInterface, ExecutedTxStatus, FailureInfo, Options,
@@ -31,14 +32,38 @@ pub(crate) struct L1BlockNumbers {
     pub latest: L1BlockNumber,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    EncodeLabelSet,
+    EncodeLabelValue,
+)]
 #[metrics(label = "type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub(crate) enum OperatorType {
     NonBlob,
     Blob,
     Gateway,
 }
 
+impl OperatorType {
+    /// Returns the name of this operator type used as a key for data persisted in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NonBlob => "non_blob",
+            Self::Blob => "blob",
+            Self::Gateway => "gateway",
+        }
+    }
+}
+
 #[async_trait]
 pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
     fn supported_operator_types(&self) -> Vec<OperatorType>;
@@ -86,12 +111,40 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
         operator_type: OperatorType,
     ) -> SignedCallResult;
 
+    /// Signs a zero-value self-transfer occupying the specified nonce of the operator. Such a transaction
+    /// is used to cancel a stuck transaction or to fill a nonce gap.
+    ///
+    /// Blob operators must provide a blob sidecar since L1 nodes do not accept
+    /// non-blob transactions from accounts with pending blob transactions.
+    async fn sign_cancellation_tx(
+        &self,
+        nonce: Nonce,
+        base_fee_per_gas: u64,
+        priority_fee_per_gas: u64,
+        blob_gas_price: Option<U256>,
+        blob_sidecar: Option<&EthTxBlobSidecar>,
+        operator_type: OperatorType,
+    ) -> SignedCallResult;
+
     async fn get_l1_block_numbers(
         &self,
         operator_type: OperatorType,
     ) -> Result<L1BlockNumbers, EthSenderError>;
 }
 
+/// Gas limit for a plain value transfer.
+const CANCELLATION_TX_GAS: u64 = 21_000;
+
+fn blob_versioned_hashes(blob_sidecar: &EthTxBlobSidecar) -> Vec<H256> {
+    match blob_sidecar {
+        EthTxBlobSidecar::EthTxBlobSidecarV1(s) => s
+            .blobs
+            .iter()
+            .map(|blob| H256::from_slice(&blob.versioned_hash))
+            .collect(),
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct RealL1Interface {
     pub ethereum_gateway: Option<Box<dyn BoundEthInterface>>,
@@ -226,13 +279,8 @@ impl AbstractL1Interface for RealL1Interface {
                     if tx.blob_sidecar.is_some() {
                         opt.transaction_type = Some(EIP_4844_TX_TYPE.into());
                         opt.max_fee_per_blob_gas = blob_gas_price;
-                        opt.blob_versioned_hashes = tx.blob_sidecar.as_ref().map(|s| match s {
-                            EthTxBlobSidecar::EthTxBlobSidecarV1(s) => s
-                                .blobs
-                                .iter()
-                                .map(|blob| H256::from_slice(&blob.versioned_hash))
-                                .collect(),
-                        });
+                        opt.blob_versioned_hashes =
+                            tx.blob_sidecar.as_ref().map(blob_versioned_hashes);
                     }
                 }),
             )
@@ -240,6 +288,38 @@ impl AbstractL1Interface for RealL1Interface {
             .expect("Failed to sign transaction")
     }
 
+    async fn sign_cancellation_tx(
+        &self,
+        nonce: Nonce,
+        base_fee_per_gas: u64,
+        priority_fee_per_gas: u64,
+        blob_gas_price: Option<U256>,
+        blob_sidecar: Option<&EthTxBlobSidecar>,
+        operator_type: OperatorType,
+    ) -> SignedCallResult {
+        let client = self.bound_query_client(operator_type);
+        client
+            .sign_prepared_tx_for_addr(
+                vec![],
+                client.sender_account(),
+                Options::with(|opt| {
+                    opt.gas = Some(CANCELLATION_TX_GAS.into());
+                    opt.value = Some(U256::zero());
+                    opt.max_fee_per_gas = Some(U256::from(base_fee_per_gas + priority_fee_per_gas));
+                    opt.max_priority_fee_per_gas = Some(U256::from(priority_fee_per_gas));
+                    opt.nonce = Some(nonce.0.into());
+                    opt.transaction_type = Some(EIP_1559_TX_TYPE.into());
+                    if let Some(blob_sidecar) = blob_sidecar {
+                        opt.transaction_type = Some(EIP_4844_TX_TYPE.into());
+                        opt.max_fee_per_blob_gas = blob_gas_price;
+                        opt.blob_versioned_hashes = Some(blob_versioned_hashes(blob_sidecar));
+                    }
+                }),
+            )
+            .await
+            .expect("Failed to sign cancellation transaction")
+    }
+
     async fn get_l1_block_numbers(
         &self,
         operator_type: OperatorType,
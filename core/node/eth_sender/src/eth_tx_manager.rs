@@ -6,9 +6,13 @@ use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
     encode_blob_tx_with_sidecar, BoundEthInterface, ExecutedTxStatus, RawTransactionBytes,
 };
+use zksync_health_check::ReactiveHealthCheck;
 use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
-use zksync_types::{eth_sender::EthTx, Address, L1BlockNumber, H256, U256};
+use zksync_types::{
+    eth_sender::{EthTx, EthTxBlobSidecar, EthTxCost, TxHistory},
+    Address, L1BlockNumber, Nonce, H256, U256,
+};
 use zksync_utils::time::seconds_since_epoch;
 
 use super::{metrics::METRICS, EthSenderError};
@@ -17,20 +21,30 @@ use crate::{
         AbstractL1Interface, L1BlockNumbers, OperatorNonce, OperatorType, RealL1Interface,
     },
     eth_fees_oracle::{EthFees, EthFeesOracle, GasAdjusterFeesOracle},
-    metrics::TransactionType,
+    metrics::{DeferralLabels, NonceAnomaly, RecoveryAction, TransactionType},
+    nonce_recovery::{NonceRecovery, Placeholder},
+    spend_limits::SpendLimits,
 };
 
 /// The component is responsible for managing sending eth_txs attempts:
 /// Based on eth_tx queue the component generates new attempt with the minimum possible fee,
 /// save it to the database, and send it to Ethereum.
 /// Based on eth_tx_history queue the component can mark txs as stuck and create the new attempt
-/// with higher gas price
+/// with higher gas price.
+/// If nonce recovery is enabled, the component also repairs the operator nonce: it fills nonce gaps
+/// and cancels stuck txs with zero-value self-transfers, and re-queues txs whose nonce was consumed
+/// by another transaction.
+/// New txs may be deferred if L1 spending exceeds configured budgets, or if L1 fees are too high
+/// for non-urgent operations.
 #[derive(Debug)]
 pub struct EthTxManager {
     l1_interface: Box<dyn AbstractL1Interface>,
     config: SenderConfig,
     fees_oracle: Box<dyn EthFeesOracle>,
     pool: ConnectionPool<Core>,
+    nonce_recovery: NonceRecovery,
+    health_check: ReactiveHealthCheck,
+    spend_limits: SpendLimits,
 }
 
 impl EthTxManager {
@@ -49,6 +63,8 @@ impl EthTxManager {
             gas_adjuster,
             max_acceptable_priority_fee_in_gwei: config.max_acceptable_priority_fee_in_gwei,
         };
+        let (health_check, nonce_recovery) = NonceRecovery::new(&config);
+        let spend_limits = SpendLimits::new(&config);
         Self {
             l1_interface: Box::new(RealL1Interface {
                 ethereum_gateway,
@@ -59,14 +75,27 @@ impl EthTxManager {
             config,
             fees_oracle: Box::new(fees_oracle),
             pool,
+            nonce_recovery,
+            health_check,
+            spend_limits,
         }
     }
 
+    /// Returns the health check for this component.
+    pub fn health_check(&self) -> &ReactiveHealthCheck {
+        &self.health_check
+    }
+
     #[cfg(test)]
     pub(crate) fn l1_interface(&self) -> &dyn AbstractL1Interface {
         self.l1_interface.as_ref()
     }
 
+    #[cfg(test)]
+    pub(crate) fn nonce_recovery(&self) -> &NonceRecovery {
+        &self.nonce_recovery
+    }
+
     async fn check_all_sending_attempts(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -102,6 +131,8 @@ impl EthTxManager {
         Ok(None)
     }
 
+    /// Sends a new attempt of the specified transaction. If a fee bump of an already sent transaction is deferred
+    /// because of spend limits, no new attempt is made and the hash of the previous attempt is returned.
     pub(crate) async fn send_eth_tx(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -115,17 +146,41 @@ impl EthTxManager {
             .await
             .unwrap();
 
-        let EthFees {
-            base_fee_per_gas,
-            priority_fee_per_gas,
-            blob_base_fee_per_gas,
-            pubdata_price: _,
-        } = self.fees_oracle.calculate_fees(
+        let fees = self.fees_oracle.calculate_fees(
             &previous_sent_tx,
             time_in_mempool,
             self.operator_type(tx),
         )?;
 
+        if let Some(previous_sent_tx) = &previous_sent_tx {
+            if self.spend_limits.is_enabled() {
+                let deferral_reason = self
+                    .spend_limits
+                    .resend_deferral_reason(storage, tx, previous_sent_tx, &fees)
+                    .await
+                    .unwrap();
+                if let Some(reason) = deferral_reason {
+                    tracing::debug!(
+                        "Deferring resending eth_tx {} ({}) with bumped fees: {reason:?}",
+                        tx.id,
+                        tx.tx_type
+                    );
+                    let labels = DeferralLabels {
+                        op: tx.tx_type.into(),
+                        reason,
+                    };
+                    METRICS.deferred_eth_txs[&labels].inc();
+                    return Ok(previous_sent_tx.tx_hash);
+                }
+            }
+        }
+
+        let EthFees {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas,
+            pubdata_price: _,
+        } = fees;
         let operator_type = self.operator_type(tx);
 
         if let Some(previous_sent_tx) = previous_sent_tx {
@@ -212,10 +267,17 @@ impl EthTxManager {
             .await
             .unwrap()
         {
-            if let Err(error) = self
+            let send_result = self
                 .send_raw_transaction(storage, tx_history_id, signed_tx.raw_tx, operator_type)
-                .await
-            {
+                .await;
+            self.nonce_recovery.report_send_result(
+                operator_type,
+                tx.id,
+                tx.nonce,
+                current_block,
+                &send_result,
+            );
+            if let Err(error) = send_result {
                 tracing::warn!(
                     "Error Sending {operator_type:?} tx {} (nonce {}) at block {current_block} with \
                     base_fee_per_gas {base_fee_per_gas:?}, \
@@ -276,6 +338,16 @@ impl EthTxManager {
             .get_operator_nonce(l1_block_numbers, operator_type)
             .await?;
 
+        if !self.nonce_recovery.is_loaded(operator_type) {
+            let placeholders = storage
+                .eth_sender_dal()
+                .get_placeholder_txs(operator_type.as_str())
+                .await
+                .unwrap();
+            self.nonce_recovery
+                .load_placeholders(operator_type, placeholders);
+        }
+
         if let Some(operator_nonce) = operator_nonce {
             let inflight_txs = storage
                 .eth_sender_dal()
@@ -287,14 +359,23 @@ impl EthTxManager {
                 .unwrap();
             METRICS.number_of_inflight_txs[&operator_type].set(inflight_txs.len());
 
-            Ok(self
+            let tx_to_resend = self
                 .apply_inflight_txs_statuses_and_get_first_to_resend(
                     storage,
                     l1_block_numbers,
                     operator_nonce,
+                    operator_type,
                     inflight_txs,
                 )
-                .await?)
+                .await?;
+            self.nonce_recovery
+                .prune_placeholders(operator_type, operator_nonce.finalized);
+            storage
+                .eth_sender_dal()
+                .remove_placeholder_txs_before(operator_type.as_str(), operator_nonce.finalized)
+                .await
+                .unwrap();
+            Ok(tx_to_resend)
         } else {
             Ok(None)
         }
@@ -305,6 +386,7 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         l1_block_numbers: L1BlockNumbers,
         operator_nonce: OperatorNonce,
+        operator_type: OperatorType,
         inflight_txs: Vec<EthTx>,
     ) -> Result<Option<(EthTx, u32)>, EthSenderError> {
         tracing::trace!(
@@ -317,8 +399,9 @@ impl EthTxManager {
             operator_nonce.finalized,
         );
 
+        let mut head_nonce_checked = false;
         // Not confirmed transactions, ordered by nonce
-        for tx in inflight_txs {
+        for (i, tx) in inflight_txs.iter().enumerate() {
             tracing::info!(
                 "Checking tx id: {}, operator_nonce: {:?}, tx nonce: {}",
                 tx.id,
@@ -330,6 +413,23 @@ impl EthTxManager {
             // that `tx` is not mined and we should resend it.
             // We only resend the first un-mined transaction.
             if operator_nonce.latest <= tx.nonce {
+                // Only the first un-mined transaction can reveal problems with the operator nonce.
+                if !head_nonce_checked {
+                    head_nonce_checked = true;
+                    let is_handled = self
+                        .recover_head_nonce(
+                            storage,
+                            l1_block_numbers,
+                            operator_nonce,
+                            operator_type,
+                            tx,
+                        )
+                        .await?;
+                    if is_handled {
+                        return Ok(None);
+                    }
+                }
+
                 let last_sent_at_block = storage
                     .eth_sender_dal()
                     .get_block_number_on_last_sent_attempt(tx.id)
@@ -347,7 +447,7 @@ impl EthTxManager {
                     .await
                     .unwrap();
                 return Ok(Some((
-                    tx,
+                    tx.clone(),
                     first_sent_at_block.unwrap_or(l1_block_numbers.latest.0),
                 )));
             }
@@ -373,19 +473,48 @@ impl EthTxManager {
                 tx.tx_type,
                 tx.nonce
             );
-            match self.check_all_sending_attempts(storage, &tx).await {
+            match self.check_all_sending_attempts(storage, tx).await {
                 Ok(Some(tx_status)) => {
-                    self.apply_tx_status(storage, &tx, tx_status, l1_block_numbers.finalized)
+                    self.apply_tx_status(storage, tx, tx_status, l1_block_numbers.finalized)
                         .await;
                 }
                 Ok(None) => {
-                    // The nonce has increased but we did not find the receipt.
+                    // The nonce has increased but we did not find the receipt. Unless the nonce was consumed
+                    // by our own placeholder transaction, it was consumed by a transaction we don't track.
+                    let is_cancelled = self
+                        .nonce_recovery
+                        .placeholder(operator_type, tx.nonce)
+                        .is_some_and(|placeholder| placeholder.cancelled_eth_tx_id == Some(tx.id));
+                    if !is_cancelled {
+                        self.nonce_recovery.report_anomaly(
+                            operator_type,
+                            NonceAnomaly::ForeignNonceUse,
+                            tx.nonce,
+                            Some(tx.id),
+                            l1_block_numbers.latest,
+                        );
+                    }
+
+                    if self.nonce_recovery.is_enabled() {
+                        self.requeue_txs(
+                            storage,
+                            l1_block_numbers,
+                            operator_nonce,
+                            operator_type,
+                            tx,
+                            &inflight_txs[i + 1..],
+                        )
+                        .await?;
+                        // Nonces of the remaining in-flight txs may have changed; they will be checked
+                        // on the next iteration.
+                        return Ok(None);
+                    }
                     // This is an error because such a big re-org may cause transactions that were
                     // previously recorded as confirmed to become pending again and we have to
                     // make sure it's not the case - otherwise `eth_sender` may not work properly.
                     tracing::error!(
                         "Possible block reorgs: finalized nonce increase detected, but no tx receipt found for tx {:?}",
-                        &tx
+                        tx
                     );
                 }
                 Err(err) => {
@@ -400,8 +529,315 @@ impl EthTxManager {
         Ok(None)
     }
 
+    /// Checks the operator nonce on the latest block against the first un-mined transaction `head_tx`,
+    /// and fills nonce gaps or cancels stuck transactions if necessary.
+    ///
+    /// Returns `true` if the head nonce is handled by placeholder transactions, i.e., `head_tx` must not be resent.
+    async fn recover_head_nonce(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        l1_block_numbers: L1BlockNumbers,
+        operator_nonce: OperatorNonce,
+        operator_type: OperatorType,
+        head_tx: &EthTx,
+    ) -> Result<bool, EthSenderError> {
+        let current_block = l1_block_numbers.latest;
+        let gap = operator_nonce.latest.0..head_tx.nonce.0;
+        let is_gap_unhandled = self
+            .nonce_recovery
+            .placeholder(operator_type, operator_nonce.latest)
+            .is_none();
+        if !gap.is_empty() && is_gap_unhandled {
+            self.nonce_recovery.report_anomaly(
+                operator_type,
+                NonceAnomaly::Gap,
+                operator_nonce.latest,
+                None,
+                current_block,
+            );
+        }
+        if !self.nonce_recovery.is_enabled() {
+            return Ok(false);
+        }
+
+        let is_cancelling = if self
+            .nonce_recovery
+            .placeholder(operator_type, head_tx.nonce)
+            .is_some()
+        {
+            true
+        } else if head_tx.nonce == operator_nonce.latest {
+            let first_sent_at_block = storage
+                .eth_sender_dal()
+                .get_block_number_on_first_sent_attempt(head_tx.id)
+                .await
+                .unwrap();
+            let is_stuck =
+                self.nonce_recovery
+                    .is_stuck(head_tx.id, first_sent_at_block, current_block);
+            if is_stuck {
+                self.nonce_recovery.report_anomaly(
+                    operator_type,
+                    NonceAnomaly::StuckTx,
+                    head_tx.nonce,
+                    Some(head_tx.id),
+                    current_block,
+                );
+            }
+            is_stuck
+        } else {
+            false
+        };
+        if gap.is_empty() && !is_cancelling {
+            return Ok(false);
+        }
+
+        // Blob operators cannot have non-blob txs in the mempool, so placeholders reuse the sidecar of the head tx.
+        for nonce in gap.map(Nonce) {
+            let placeholder = self.nonce_recovery.placeholder(operator_type, nonce);
+            let previous_attempt = placeholder.map(Placeholder::as_tx_history);
+            self.send_placeholder_tx_if_needed(
+                storage,
+                operator_nonce,
+                operator_type,
+                nonce,
+                None,
+                previous_attempt,
+                head_tx.blob_sidecar.as_ref(),
+                current_block,
+            )
+            .await?;
+        }
+
+        if is_cancelling {
+            // All sent txs following the cancelled one must be cancelled as well; otherwise,
+            // they would be mined in the wrong order after the cancellation.
+            let inflight_txs = storage
+                .eth_sender_dal()
+                .get_inflight_txs(
+                    self.operator_address(operator_type),
+                    operator_type == OperatorType::Gateway,
+                )
+                .await
+                .unwrap();
+            for tx in inflight_txs {
+                if tx.nonce < head_tx.nonce {
+                    continue;
+                }
+                let previous_attempt =
+                    match self.nonce_recovery.placeholder(operator_type, tx.nonce) {
+                        Some(placeholder) => Some(placeholder.as_tx_history()),
+                        None => storage
+                            .eth_sender_dal()
+                            .get_last_sent_eth_tx(tx.id)
+                            .await
+                            .unwrap(),
+                    };
+                let Some(previous_attempt) = previous_attempt else {
+                    // The tx was never sent, so it doesn't occupy its nonce.
+                    continue;
+                };
+                self.send_placeholder_tx_if_needed(
+                    storage,
+                    operator_nonce,
+                    operator_type,
+                    tx.nonce,
+                    Some(tx.id),
+                    Some(previous_attempt),
+                    tx.blob_sidecar.as_ref().or(head_tx.blob_sidecar.as_ref()),
+                    current_block,
+                )
+                .await?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Sends a placeholder tx occupying the specified nonce. If there's a previous placeholder for the nonce,
+    /// it is only replaced if it blocks the operator nonce and wasn't sent on the current block.
+    #[allow(clippy::too_many_arguments)]
+    async fn send_placeholder_tx_if_needed(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        operator_nonce: OperatorNonce,
+        operator_type: OperatorType,
+        nonce: Nonce,
+        cancelled_eth_tx_id: Option<u32>,
+        previous_attempt: Option<TxHistory>,
+        blob_sidecar: Option<&EthTxBlobSidecar>,
+        current_block: L1BlockNumber,
+    ) -> Result<(), EthSenderError> {
+        let action =
+            if let Some(placeholder) = self.nonce_recovery.placeholder(operator_type, nonce) {
+                if nonce != operator_nonce.latest || placeholder.sent_at_block >= current_block {
+                    return Ok(());
+                }
+                RecoveryAction::ResendPlaceholder
+            } else if cancelled_eth_tx_id.is_some() {
+                RecoveryAction::CancelTx
+            } else {
+                RecoveryAction::FillGap
+            };
+        let cancelled_eth_tx_id = self
+            .nonce_recovery
+            .placeholder(operator_type, nonce)
+            .map_or(cancelled_eth_tx_id, |placeholder| {
+                placeholder.cancelled_eth_tx_id
+            });
+
+        let time_in_mempool = previous_attempt
+            .as_ref()
+            .and_then(|attempt| attempt.sent_at_block)
+            .map_or(0, |sent_at_block| {
+                current_block.0.saturating_sub(sent_at_block)
+            });
+        let EthFees {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas,
+            pubdata_price: _,
+        } = self
+            .fees_oracle
+            .calculate_fees(&previous_attempt, time_in_mempool, operator_type)?;
+        let blob_gas_price = if blob_sidecar.is_some() {
+            Some(
+                blob_base_fee_per_gas
+                    .expect("always ready to query blob gas price for blob transactions; qed")
+                    .into(),
+            )
+        } else {
+            None
+        };
+
+        let mut signed_tx = self
+            .l1_interface
+            .sign_cancellation_tx(
+                nonce,
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                blob_gas_price,
+                blob_sidecar,
+                operator_type,
+            )
+            .await;
+        if let Some(blob_sidecar) = blob_sidecar {
+            signed_tx.raw_tx = RawTransactionBytes::new_unchecked(encode_blob_tx_with_sidecar(
+                signed_tx.raw_tx.as_ref(),
+                blob_sidecar,
+            ));
+        }
+        tracing::info!(
+            "Sending {operator_type:?} placeholder tx {:?} (nonce {nonce}, cancelled eth_tx {cancelled_eth_tx_id:?}) \
+             at block {current_block} with \
+             base_fee_per_gas {base_fee_per_gas:?}, \
+             priority_fee_per_gas {priority_fee_per_gas:?}, \
+             blob_fee_per_gas {blob_base_fee_per_gas:?}",
+            signed_tx.hash
+        );
+        self.l1_interface
+            .send_raw_tx(signed_tx.raw_tx, operator_type)
+            .await?;
+
+        let placeholder = Placeholder {
+            operator: operator_type,
+            nonce,
+            cancelled_eth_tx_id,
+            tx_hash: signed_tx.hash,
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            blob_base_fee_per_gas: blob_sidecar.and(blob_base_fee_per_gas),
+            sent_at_block: current_block,
+        };
+        storage
+            .eth_sender_dal()
+            .save_placeholder_tx(operator_type.as_str(), &placeholder.to_stored())
+            .await
+            .unwrap();
+        self.nonce_recovery.insert_placeholder(placeholder);
+        self.nonce_recovery.report_action(
+            operator_type,
+            action,
+            nonce,
+            cancelled_eth_tx_id,
+            current_block,
+        );
+        Ok(())
+    }
+
+    /// Re-queues `tx` whose nonce was consumed by another transaction. Re-queued txs get nonces
+    /// not occupied by placeholders and are sent anew.
+    ///
+    /// Besides `tx`, the following unconfirmed txs of the operator are re-queued if their nonces are either
+    /// not used yet (i.e., at least the latest operator nonce), or consumed by other transactions on a finalized block.
+    /// Txs that are mined (i.e., have a receipt) are left intact. If a tx has no receipt, but its nonce
+    /// is not finalized yet, re-queueing is postponed, since otherwise re-queued txs could be reordered.
+    /// Unconfirmed txs of the operator that are not sent yet and are queued behind the re-queued txs are re-queued
+    /// together with them, so that no two txs share a nonce.
+    #[allow(clippy::too_many_arguments)]
+    async fn requeue_txs(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        l1_block_numbers: L1BlockNumbers,
+        operator_nonce: OperatorNonce,
+        operator_type: OperatorType,
+        tx: &EthTx,
+        following_txs: &[EthTx],
+    ) -> Result<(), EthSenderError> {
+        let mut requeued_tx_ids = vec![tx.id];
+        for following_tx in following_txs {
+            if following_tx.nonce >= operator_nonce.latest {
+                requeued_tx_ids.push(following_tx.id);
+                continue;
+            }
+            if self
+                .check_all_sending_attempts(storage, following_tx)
+                .await?
+                .is_some()
+            {
+                // The tx is mined; its status will be applied once it's finalized.
+                continue;
+            }
+            if following_tx.nonce >= operator_nonce.finalized {
+                tracing::info!(
+                    "Postponing re-queueing {operator_type:?} txs starting from tx {} with nonce {}: \
+                     tx {} with nonce {} has no receipt, but its nonce is not finalized yet",
+                    tx.id,
+                    tx.nonce,
+                    following_tx.id,
+                    following_tx.nonce
+                );
+                return Ok(());
+            }
+            requeued_tx_ids.push(following_tx.id);
+        }
+
+        let new_first_nonce = self
+            .nonce_recovery
+            .next_free_nonce(operator_type, operator_nonce.latest);
+        let requeued_tx_ids = storage
+            .eth_sender_dal()
+            .requeue_txs(&requeued_tx_ids, new_first_nonce.0.into())
+            .await
+            .unwrap();
+        tracing::warn!(
+            "Re-queued {operator_type:?} txs {requeued_tx_ids:?} starting from tx {} with nonce {}; \
+             new nonces start from {new_first_nonce}",
+            tx.id,
+            tx.nonce
+        );
+        self.nonce_recovery.report_requeued_txs(&requeued_tx_ids);
+        self.nonce_recovery.report_action(
+            operator_type,
+            RecoveryAction::RequeueTxs,
+            tx.nonce,
+            Some(tx.id),
+            l1_block_numbers.latest,
+        );
+        Ok(())
+    }
+
     async fn apply_tx_status(
-        &self,
+        &mut self,
         storage: &mut Connection<'_, Core>,
         tx: &EthTx,
         tx_status: ExecutedTxStatus,
@@ -459,7 +895,7 @@ impl EthTxManager {
     }
 
     pub async fn confirm_tx(
-        &self,
+        &mut self,
         storage: &mut Connection<'_, Core>,
         tx: &EthTx,
         tx_status: ExecutedTxStatus,
@@ -475,6 +911,28 @@ impl EthTxManager {
             .confirm_tx(tx_status.tx_hash, gas_used)
             .await
             .unwrap();
+        self.nonce_recovery.report_confirmed_tx(tx.id);
+
+        if let Some(effective_gas_price) = tx_status.receipt.effective_gas_price {
+            let cost = EthTxCost {
+                gas_used,
+                effective_gas_price,
+                blob_gas_used: tx_status.receipt.blob_gas_used,
+                blob_gas_price: tx_status.receipt.blob_gas_price,
+            };
+            storage
+                .eth_sender_dal()
+                .save_tx_cost(tx.id, &cost)
+                .await
+                .unwrap();
+            let cost_in_gwei = cost.total_cost() / 1_000_000_000_u64;
+            METRICS.l1_spent_gwei[&tx.tx_type.into()].inc_by(cost_in_gwei.low_u64());
+        } else {
+            tracing::warn!(
+                "Receipt for eth_tx {} doesn't contain effective gas price; L1 cost of the tx is not recorded",
+                tx.id
+            );
+        }
 
         METRICS
             .track_eth_tx_metrics(storage, BlockL1Stage::Mined, tx)
@@ -511,6 +969,7 @@ impl EthTxManager {
 
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let pool = self.pool.clone();
+        self.nonce_recovery.initialize();
 
         loop {
             let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
@@ -571,7 +1030,43 @@ impl EthTxManager {
             } else {
                 tracing::debug!("No new {operator_type:?} transactions to send");
             }
+            // Fees are only needed to check spend limits, so we don't calculate them if there are no limits.
+            let fees = if self.spend_limits.is_enabled() {
+                match self.fees_oracle.calculate_fees(&None, 0, operator_type) {
+                    Ok(fees) => Some(fees),
+                    Err(err) => {
+                        tracing::info!(
+                            "Skipping sending new transactions because fees cannot be calculated: {err}"
+                        );
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+
             for tx in new_eth_tx {
+                if let Some(fees) = &fees {
+                    let deferral_reason = self
+                        .spend_limits
+                        .deferral_reason(storage, &tx, fees)
+                        .await
+                        .unwrap();
+                    if let Some(reason) = deferral_reason {
+                        tracing::debug!(
+                            "Deferring sending {operator_type:?} eth_tx {} ({}) and subsequent transactions: {reason:?}",
+                            tx.id,
+                            tx.tx_type
+                        );
+                        let labels = DeferralLabels {
+                            op: tx.tx_type.into(),
+                            reason,
+                        };
+                        METRICS.deferred_eth_txs[&labels].inc();
+                        break;
+                    }
+                }
+
                 let result = self.send_eth_tx(storage, &tx, 0, current_block).await;
                 // If one of the transactions doesn't succeed, this means we should return
                 // as new transactions have increasing nonces, so they will also result in an error
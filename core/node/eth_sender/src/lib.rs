@@ -4,7 +4,9 @@ mod error;
 mod eth_tx_aggregator;
 mod eth_tx_manager;
 mod metrics;
+mod nonce_recovery;
 mod publish_criterion;
+mod spend_limits;
 mod utils;
 mod zksync_functions;
 
@@ -2,6 +2,7 @@
 
 use std::{fmt, time::Duration};
 
+use serde::Serialize;
 use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_shared_metrics::{BlockL1Stage, BlockStage, APP_METRICS};
@@ -40,6 +41,62 @@ pub(super) enum TransactionType {
     Regular,
 }
 
+/// Kind of inconsistency between the operator nonce on L1 and the locally tracked transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub(super) enum NonceAnomaly {
+    /// No transaction occupies the next operator nonce, while transactions with greater nonces are in flight.
+    Gap,
+    /// Operator nonce was consumed by a transaction not tracked by the Ethereum sender.
+    ForeignNonceUse,
+    /// Transaction blocks the operator nonce for too long.
+    StuckTx,
+    /// Resending a transaction was rejected as an underpriced replacement.
+    ReplacementUnderpriced,
+}
+
+/// Action taken by the Ethereum sender to recover the operator nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub(super) enum RecoveryAction {
+    FillGap,
+    CancelTx,
+    ResendPlaceholder,
+    RequeueTxs,
+}
+
+/// Reason for deferring sending of a new transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum DeferralReason {
+    /// Spending on the operation type during the last hour reached the configured budget.
+    HourlyBudget,
+    /// Spending on the operation type during the last 24 hours reached the configured budget.
+    DailyBudget,
+    /// L1 base fee is above the ceiling configured for non-urgent operations.
+    FeeCeiling,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct DeferralLabels {
+    pub op: ActionTypeLabel,
+    pub reason: DeferralReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct NonceAnomalyLabels {
+    pub operator: OperatorType,
+    pub kind: NonceAnomaly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct RecoveryActionLabels {
+    pub operator: OperatorType,
+    pub action: RecoveryAction,
+}
+
 impl From<AggregatedActionType> for ActionTypeLabel {
     fn from(action_type: AggregatedActionType) -> Self {
         Self(action_type)
@@ -108,6 +165,16 @@ pub(super) struct EthSenderMetrics {
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
     pub l1_transient_errors: Counter,
+    /// Number of detected inconsistencies of operator nonces.
+    pub nonce_anomalies: Family<NonceAnomalyLabels, Counter>,
+    /// Number of actions taken to recover operator nonces.
+    pub nonce_recovery_actions: Family<RecoveryActionLabels, Counter>,
+    /// Number of placeholder transactions (zero-value self-transfers) awaiting finalization.
+    pub placeholder_txs: Family<OperatorType, Gauge<usize>>,
+    /// Number of times sending a new transaction was deferred because of L1 spend limits.
+    pub deferred_eth_txs: Family<DeferralLabels, Counter>,
+    /// Total L1 cost of confirmed transactions in gwei.
+    pub l1_spent_gwei: Family<ActionTypeLabel, Counter>,
 }
 
 impl EthSenderMetrics {
@@ -0,0 +1,364 @@
+//! Bookkeeping for recovery of operator nonces in [`EthTxManager`](crate::EthTxManager).
+//!
+//! The operator nonce may get out of sync with `eth_txs` in several ways: a transaction may get stuck in the mempool,
+//! an external transaction may consume the nonce of a tracked transaction, or a nonce may be left unoccupied.
+//! The manager recovers from these situations by sending *placeholder* transactions (zero-value self-transfers)
+//! and re-queueing affected `eth_txs`; this module keeps track of the placeholders and reports decisions
+//! via metrics and the `eth_tx_manager` health check. Placeholders are persisted in the database by the manager,
+//! so that they survive restarts.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+use zksync_config::configs::eth_sender::SenderConfig;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::{
+    eth_sender::{PlaceholderTx, TxHistory},
+    L1BlockNumber, Nonce, H256,
+};
+
+use crate::{
+    abstract_l1_interface::OperatorType,
+    metrics::{NonceAnomaly, NonceAnomalyLabels, RecoveryAction, RecoveryActionLabels, METRICS},
+    EthSenderError,
+};
+
+/// Zero-value self-transfer sent by the operator to occupy a nonce.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Placeholder {
+    pub operator: OperatorType,
+    pub nonce: Nonce,
+    /// ID of the `eth_tx` replaced by this placeholder, or `None` if the placeholder fills a nonce gap.
+    pub cancelled_eth_tx_id: Option<u32>,
+    pub tx_hash: H256,
+    pub base_fee_per_gas: u64,
+    pub priority_fee_per_gas: u64,
+    pub blob_base_fee_per_gas: Option<u64>,
+    pub sent_at_block: L1BlockNumber,
+}
+
+impl Placeholder {
+    pub fn from_stored(operator: OperatorType, stored: PlaceholderTx) -> Self {
+        Self {
+            operator,
+            nonce: stored.nonce,
+            cancelled_eth_tx_id: stored.cancelled_eth_tx_id,
+            tx_hash: stored.tx_hash,
+            base_fee_per_gas: stored.base_fee_per_gas,
+            priority_fee_per_gas: stored.priority_fee_per_gas,
+            blob_base_fee_per_gas: stored.blob_base_fee_per_gas,
+            sent_at_block: L1BlockNumber(stored.sent_at_block),
+        }
+    }
+
+    pub fn to_stored(&self) -> PlaceholderTx {
+        PlaceholderTx {
+            nonce: self.nonce,
+            cancelled_eth_tx_id: self.cancelled_eth_tx_id,
+            tx_hash: self.tx_hash,
+            base_fee_per_gas: self.base_fee_per_gas,
+            priority_fee_per_gas: self.priority_fee_per_gas,
+            blob_base_fee_per_gas: self.blob_base_fee_per_gas,
+            sent_at_block: self.sent_at_block.0,
+        }
+    }
+
+    /// Represents this placeholder as a sending attempt so that fees for its replacement are bumped properly.
+    pub fn as_tx_history(&self) -> TxHistory {
+        TxHistory {
+            id: 0,
+            eth_tx_id: self.cancelled_eth_tx_id.unwrap_or(0),
+            base_fee_per_gas: self.base_fee_per_gas,
+            priority_fee_per_gas: self.priority_fee_per_gas,
+            blob_base_fee_per_gas: self.blob_base_fee_per_gas,
+            tx_hash: self.tx_hash,
+            signed_raw_tx: vec![],
+            sent_at_block: Some(self.sent_at_block.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct NonceEvent<K> {
+    operator: OperatorType,
+    kind: K,
+    nonce: Nonce,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eth_tx_id: Option<u32>,
+    l1_block: L1BlockNumber,
+}
+
+impl<K: PartialEq> NonceEvent<K> {
+    fn is_repeated_by(&self, other: &Self) -> bool {
+        self.operator == other.operator
+            && self.kind == other.kind
+            && self.nonce == other.nonce
+            && self.eth_tx_id == other.eth_tx_id
+    }
+}
+
+/// Health details reported by [`EthTxManager`](crate::EthTxManager).
+#[derive(Debug, Serialize)]
+struct NonceRecoveryDetails {
+    nonce_recovery_enabled: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    placeholders: Vec<Placeholder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_anomaly: Option<NonceEvent<NonceAnomaly>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_action: Option<NonceEvent<RecoveryAction>>,
+    /// Whether the last anomaly was neither handled by a recovery action nor followed by a confirmed transaction.
+    has_unresolved_anomaly: bool,
+}
+
+impl NonceRecoveryDetails {
+    fn health(&self) -> Health {
+        let status = if self.placeholders.is_empty() && !self.has_unresolved_anomaly {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::Affected
+        };
+        Health::from(status).with_details(self)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct NonceRecovery {
+    enabled: bool,
+    stuck_tx_timeout_blocks: Option<u64>,
+    max_underpriced_resends: Option<u32>,
+    placeholders: BTreeMap<(OperatorType, Nonce), Placeholder>,
+    /// Operators for which placeholders were loaded from the database.
+    loaded_operators: BTreeSet<OperatorType>,
+    /// Number of consecutive underpriced replacement errors per `eth_tx` ID.
+    underpriced_resends: HashMap<u32, u32>,
+    details: NonceRecoveryDetails,
+    health_updater: HealthUpdater,
+}
+
+impl NonceRecovery {
+    pub fn new(config: &SenderConfig) -> (ReactiveHealthCheck, Self) {
+        let (health_check, health_updater) = ReactiveHealthCheck::new("eth_tx_manager");
+        let this = Self {
+            enabled: config.nonce_recovery_enabled,
+            stuck_tx_timeout_blocks: config.stuck_tx_timeout_blocks,
+            max_underpriced_resends: config.max_underpriced_resends,
+            placeholders: BTreeMap::new(),
+            loaded_operators: BTreeSet::new(),
+            underpriced_resends: HashMap::new(),
+            details: NonceRecoveryDetails {
+                nonce_recovery_enabled: config.nonce_recovery_enabled,
+                placeholders: vec![],
+                last_anomaly: None,
+                last_action: None,
+                has_unresolved_anomaly: false,
+            },
+            health_updater,
+        };
+        (health_check, this)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn initialize(&mut self) {
+        self.health_updater.update(self.details.health());
+    }
+
+    pub fn is_loaded(&self, operator: OperatorType) -> bool {
+        self.loaded_operators.contains(&operator)
+    }
+
+    /// Loads placeholders of the specified operator persisted in the database.
+    pub fn load_placeholders(&mut self, operator: OperatorType, stored: Vec<PlaceholderTx>) {
+        self.loaded_operators.insert(operator);
+        if stored.is_empty() {
+            return;
+        }
+        tracing::info!(
+            "Loaded {} {operator:?} placeholder txs with nonces {:?}",
+            stored.len(),
+            stored.iter().map(|tx| tx.nonce).collect::<Vec<_>>()
+        );
+        for stored in stored {
+            let placeholder = Placeholder::from_stored(operator, stored);
+            self.placeholders
+                .insert((operator, placeholder.nonce), placeholder);
+        }
+        self.update_placeholders(operator);
+    }
+
+    pub fn placeholder(&self, operator: OperatorType, nonce: Nonce) -> Option<&Placeholder> {
+        self.placeholders.get(&(operator, nonce))
+    }
+
+    /// Returns the minimum nonce not occupied by a placeholder, given the nonce of the operator on the latest block.
+    pub fn next_free_nonce(&self, operator: OperatorType, latest: Nonce) -> Nonce {
+        let last_placeholder_nonce = self
+            .placeholders
+            .range((operator, Nonce(0))..=(operator, Nonce(u32::MAX)))
+            .next_back()
+            .map(|((_, nonce), _)| *nonce);
+        match last_placeholder_nonce {
+            Some(nonce) if nonce >= latest => nonce + 1,
+            _ => latest,
+        }
+    }
+
+    pub fn insert_placeholder(&mut self, placeholder: Placeholder) {
+        let operator = placeholder.operator;
+        self.placeholders
+            .insert((operator, placeholder.nonce), placeholder);
+        self.update_placeholders(operator);
+    }
+
+    /// Removes placeholders with nonces below the operator nonce on the finalized block.
+    pub fn prune_placeholders(&mut self, operator: OperatorType, finalized: Nonce) {
+        let len_before = self.placeholders.len();
+        self.placeholders
+            .retain(|&(op, nonce), _| op != operator || nonce >= finalized);
+        if self.placeholders.len() != len_before {
+            self.update_placeholders(operator);
+        }
+    }
+
+    /// Checks whether the specified transaction blocking the operator nonce should be cancelled.
+    pub fn is_stuck(
+        &self,
+        eth_tx_id: u32,
+        first_sent_at_block: Option<u32>,
+        current_block: L1BlockNumber,
+    ) -> bool {
+        let timed_out = matches!(
+            (self.stuck_tx_timeout_blocks, first_sent_at_block),
+            (Some(timeout), Some(sent_at_block))
+                if u64::from(current_block.0.saturating_sub(sent_at_block)) >= timeout
+        );
+        let underpriced_resends = self
+            .underpriced_resends
+            .get(&eth_tx_id)
+            .copied()
+            .unwrap_or(0);
+        let too_many_underpriced = self
+            .max_underpriced_resends
+            .is_some_and(|max| underpriced_resends >= max);
+        timed_out || too_many_underpriced
+    }
+
+    pub fn report_send_result(
+        &mut self,
+        operator: OperatorType,
+        eth_tx_id: u32,
+        nonce: Nonce,
+        current_block: L1BlockNumber,
+        result: &Result<(), EthSenderError>,
+    ) {
+        match result {
+            Ok(()) => {
+                self.underpriced_resends.remove(&eth_tx_id);
+            }
+            Err(err) if is_underpriced_replacement(err) => {
+                *self.underpriced_resends.entry(eth_tx_id).or_default() += 1;
+                self.report_anomaly(
+                    operator,
+                    NonceAnomaly::ReplacementUnderpriced,
+                    nonce,
+                    Some(eth_tx_id),
+                    current_block,
+                );
+            }
+            // Other errors are not related to nonce management.
+            Err(_) => {}
+        }
+    }
+
+    /// Reports that the specified `eth_tx` was confirmed.
+    pub fn report_confirmed_tx(&mut self, eth_tx_id: u32) {
+        self.underpriced_resends.remove(&eth_tx_id);
+        if self.details.has_unresolved_anomaly {
+            self.details.has_unresolved_anomaly = false;
+            self.health_updater.update(self.details.health());
+        }
+    }
+
+    /// Reports that the specified `eth_txs` were re-queued and will be sent anew.
+    pub fn report_requeued_txs(&mut self, eth_tx_ids: &[u32]) {
+        for eth_tx_id in eth_tx_ids {
+            self.underpriced_resends.remove(eth_tx_id);
+        }
+    }
+
+    pub fn report_anomaly(
+        &mut self,
+        operator: OperatorType,
+        kind: NonceAnomaly,
+        nonce: Nonce,
+        eth_tx_id: Option<u32>,
+        current_block: L1BlockNumber,
+    ) {
+        let event = NonceEvent {
+            operator,
+            kind,
+            nonce,
+            eth_tx_id,
+            l1_block: current_block,
+        };
+        let is_repeated = self
+            .details
+            .last_anomaly
+            .as_ref()
+            .is_some_and(|last| last.is_repeated_by(&event));
+        if is_repeated {
+            return;
+        }
+
+        tracing::warn!(
+            "Detected nonce anomaly {kind:?} for {operator:?} operator at nonce {nonce} \
+             (eth_tx: {eth_tx_id:?}, L1 block: {current_block})"
+        );
+        METRICS.nonce_anomalies[&NonceAnomalyLabels { operator, kind }].inc();
+        self.details.last_anomaly = Some(event);
+        self.details.has_unresolved_anomaly = true;
+        self.health_updater.update(self.details.health());
+    }
+
+    pub fn report_action(
+        &mut self,
+        operator: OperatorType,
+        action: RecoveryAction,
+        nonce: Nonce,
+        eth_tx_id: Option<u32>,
+        current_block: L1BlockNumber,
+    ) {
+        tracing::info!(
+            "Taken nonce recovery action {action:?} for {operator:?} operator at nonce {nonce} \
+             (eth_tx: {eth_tx_id:?}, L1 block: {current_block})"
+        );
+        METRICS.nonce_recovery_actions[&RecoveryActionLabels { operator, action }].inc();
+        self.details.last_action = Some(NonceEvent {
+            operator,
+            kind: action,
+            nonce,
+            eth_tx_id,
+            l1_block: current_block,
+        });
+        self.details.has_unresolved_anomaly = false;
+        self.health_updater.update(self.details.health());
+    }
+
+    fn update_placeholders(&mut self, operator: OperatorType) {
+        let operator_placeholders = self
+            .placeholders
+            .keys()
+            .filter(|(op, _)| *op == operator)
+            .count();
+        METRICS.placeholder_txs[&operator].set(operator_placeholders);
+        self.details.placeholders = self.placeholders.values().cloned().collect();
+        self.health_updater.update(self.details.health());
+    }
+}
+
+fn is_underpriced_replacement(err: &EthSenderError) -> bool {
+    err.to_string().to_lowercase().contains("underpriced")
+}
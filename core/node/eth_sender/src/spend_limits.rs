@@ -0,0 +1,212 @@
+//! L1 spend limits for [`EthTxManager`](crate::EthTxManager).
+//!
+//! New transactions are deferred if spending on their operation type during the last hour or day
+//! would exceed the configured budget, or if they are non-urgent (proofs and executions) and the L1 base fee
+//! is above the configured ceiling. Since transactions of an operator are sent in the nonce order, deferring a transaction
+//! also defers all transactions queued after it. Fee bumps of sent transactions are deferred if they would exceed
+//! the budget as well.
+//!
+//! Spending includes the actual cost of confirmed transactions and the worst-case cost of sent, but not yet confirmed
+//! transactions (i.e., the cost if a transaction uses its entire gas limit and pays the maximum fees).
+
+use std::time::Duration;
+
+use zksync_config::configs::eth_sender::SenderConfig;
+use zksync_dal::{Connection, Core, CoreDal, SqlxError};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    eth_sender::{EthTx, EthTxCost, TxHistory},
+    U256,
+};
+use zksync_utils::time::seconds_since_epoch;
+
+use crate::{eth_fees_oracle::EthFees, metrics::DeferralReason};
+
+const GWEI: u64 = 1_000_000_000;
+const HOUR: Duration = Duration::from_secs(3_600);
+const DAY: Duration = Duration::from_secs(86_400);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct SpendBudget {
+    hourly: Option<U256>,
+    daily: Option<U256>,
+}
+
+impl SpendBudget {
+    fn new(hourly_gwei: Option<u64>, daily_gwei: Option<u64>) -> Self {
+        let to_wei = |gwei: u64| U256::from(gwei) * GWEI;
+        Self {
+            hourly: hourly_gwei.map(to_wei),
+            daily: daily_gwei.map(to_wei),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.hourly.is_none() && self.daily.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SpendLimits {
+    commit: SpendBudget,
+    prove: SpendBudget,
+    execute: SpendBudget,
+    /// Ceiling for the L1 base fee in wei.
+    non_urgent_base_fee_ceiling: Option<u64>,
+    max_non_urgent_deferral: Option<Duration>,
+    /// Gas limit used for all transactions.
+    gas_limit: u64,
+}
+
+impl SpendLimits {
+    pub fn new(config: &SenderConfig) -> Self {
+        Self {
+            commit: SpendBudget::new(
+                config.commit_hourly_budget_gwei,
+                config.commit_daily_budget_gwei,
+            ),
+            prove: SpendBudget::new(
+                config.prove_hourly_budget_gwei,
+                config.prove_daily_budget_gwei,
+            ),
+            execute: SpendBudget::new(
+                config.execute_hourly_budget_gwei,
+                config.execute_daily_budget_gwei,
+            ),
+            non_urgent_base_fee_ceiling: config
+                .non_urgent_base_fee_ceiling_gwei
+                .map(|gwei| gwei.saturating_mul(GWEI)),
+            max_non_urgent_deferral: config.max_non_urgent_deferral(),
+            gas_limit: config.max_aggregated_tx_gas.into(),
+        }
+    }
+
+    /// Returns `false` if no limits are configured, in which case checking limits can be skipped altogether.
+    pub fn is_enabled(&self) -> bool {
+        !self.commit.is_empty()
+            || !self.prove.is_empty()
+            || !self.execute.is_empty()
+            || self.non_urgent_base_fee_ceiling.is_some()
+    }
+
+    fn budget(&self, tx_type: AggregatedActionType) -> SpendBudget {
+        match tx_type {
+            AggregatedActionType::Commit => self.commit,
+            AggregatedActionType::PublishProofOnchain => self.prove,
+            AggregatedActionType::Execute => self.execute,
+        }
+    }
+
+    fn is_urgent(tx_type: AggregatedActionType) -> bool {
+        matches!(tx_type, AggregatedActionType::Commit)
+    }
+
+    fn max_tx_cost(&self, tx: &EthTx, fees: &EthFees) -> U256 {
+        let blob_count = tx
+            .blob_sidecar
+            .as_ref()
+            .map_or(0, |sidecar| sidecar.blob_count());
+        let blob_fee = fees.blob_base_fee_per_gas.filter(|_| blob_count > 0);
+        EthTxCost::upper_bound(
+            self.gas_limit,
+            fees.base_fee_per_gas + fees.priority_fee_per_gas,
+            blob_count,
+            blob_fee,
+        )
+        .total_cost()
+    }
+
+    /// Checks whether sending the specified new transaction with the specified fees should be deferred.
+    pub async fn deferral_reason(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        fees: &EthFees,
+    ) -> Result<Option<DeferralReason>, SqlxError> {
+        if let Some(ceiling) = self.non_urgent_base_fee_ceiling {
+            let tx_age =
+                Duration::from_secs(seconds_since_epoch().saturating_sub(tx.created_at_timestamp));
+            let deferred_for_too_long = self
+                .max_non_urgent_deferral
+                .is_some_and(|max_deferral| tx_age >= max_deferral);
+            if !Self::is_urgent(tx.tx_type)
+                && fees.base_fee_per_gas > ceiling
+                && !deferred_for_too_long
+            {
+                return Ok(Some(DeferralReason::FeeCeiling));
+            }
+        }
+
+        let tx_cost = self.max_tx_cost(tx, fees);
+        self.budget_deferral_reason(storage, tx.tx_type, tx_cost, true)
+            .await
+    }
+
+    /// Checks whether resending the specified transaction with the specified (bumped) fees should be deferred
+    /// because of budget limits.
+    pub async fn resend_deferral_reason(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        previous_attempt: &TxHistory,
+        fees: &EthFees,
+    ) -> Result<Option<DeferralReason>, SqlxError> {
+        let previous_fees = EthFees {
+            base_fee_per_gas: previous_attempt.base_fee_per_gas,
+            priority_fee_per_gas: previous_attempt.priority_fee_per_gas,
+            blob_base_fee_per_gas: previous_attempt.blob_base_fee_per_gas,
+            pubdata_price: None,
+        };
+        // The previous attempt is already accounted for in pending spending.
+        let cost_increase = self
+            .max_tx_cost(tx, fees)
+            .saturating_sub(self.max_tx_cost(tx, &previous_fees));
+        if cost_increase.is_zero() {
+            return Ok(None);
+        }
+        self.budget_deferral_reason(storage, tx.tx_type, cost_increase, false)
+            .await
+    }
+
+    /// Checks whether spending `extra_cost` on top of the current spending would exceed the budget. If `allow_if_idle`
+    /// is set, spending is allowed if nothing was spent or is pending within a window, so that a budget lower
+    /// than a single transaction cost doesn't stall the operator forever.
+    async fn budget_deferral_reason(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx_type: AggregatedActionType,
+        extra_cost: U256,
+        allow_if_idle: bool,
+    ) -> Result<Option<DeferralReason>, SqlxError> {
+        let budget = self.budget(tx_type);
+        if budget.is_empty() {
+            return Ok(None);
+        }
+        let pending = storage
+            .eth_sender_dal()
+            .get_pending_l1_fees_upper_bound(tx_type, self.gas_limit)
+            .await?;
+
+        let windows = [
+            (budget.hourly, HOUR, DeferralReason::HourlyBudget),
+            (budget.daily, DAY, DeferralReason::DailyBudget),
+        ];
+        for (limit, window, reason) in windows {
+            let Some(limit) = limit else {
+                continue;
+            };
+            let spent = storage
+                .eth_sender_dal()
+                .get_spent_l1_fees(tx_type, window)
+                .await?
+                + pending;
+            if allow_if_idle && spent.is_zero() {
+                continue;
+            }
+            if spent + extra_cost > limit {
+                return Ok(Some(reason));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -316,6 +316,26 @@ impl EthSenderTester {
         tracing::info!("Switched eth-sender tester to use Gateway!");
     }
 
+    pub fn enable_nonce_recovery(&mut self, stuck_tx_timeout_blocks: Option<u64>) {
+        self.set_sender_config(SenderConfig {
+            nonce_recovery_enabled: true,
+            stuck_tx_timeout_blocks,
+            ..EthConfig::for_tests().sender.unwrap()
+        });
+    }
+
+    /// Recreates the tx manager with the specified config.
+    pub fn set_sender_config(&mut self, config: SenderConfig) {
+        self.manager = EthTxManager::new(
+            self.conn.clone(),
+            config,
+            self.gas_adjuster.clone(),
+            Some(self.gateway.clone()),
+            Some(self.gateway_blobs.clone()),
+            None,
+        );
+    }
+
     pub async fn storage(&self) -> Connection<'_, Core> {
         self.conn.connection().await.unwrap()
     }
@@ -1,6 +1,11 @@
+use std::time::Duration;
+
 use assert_matches::assert_matches;
 use test_casing::{test_casing, Product};
+use zksync_config::{configs::eth_sender::SenderConfig, EthConfig};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_eth_client::{EthInterface, Options};
+use zksync_health_check::{CheckHealth, HealthStatus};
 use zksync_l1_contract_interface::i_executor::methods::ExecuteBatches;
 use zksync_node_test_utils::create_l1_batch;
 use zksync_types::{
@@ -12,7 +17,7 @@ use zksync_types::{
     ethabi::Token,
     helpers::unix_timestamp_ms,
     web3::contract::Error,
-    ProtocolVersionId, H256,
+    Address, Nonce, ProtocolVersionId, H256, U256,
 };
 
 use crate::{
@@ -493,6 +498,496 @@ async fn switching_to_gateway_works_for_most_basic_scenario() {
     tester.assert_inflight_txs_count_equals(0).await;
 }
 
+#[test_log::test(tokio::test)]
+async fn transactions_are_requeued_after_foreign_nonce_use() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.enable_nonce_recovery(None);
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let eth_tx = tester.save_commit_tx(first_l1_batch.number).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+
+    // Consume the operator nonce with a transaction not tracked by the eth sender.
+    let foreign_tx = tester
+        .gateway
+        .sign_prepared_tx(
+            vec![],
+            Address::random(),
+            Options::with(|opt| opt.nonce = Some(eth_tx.nonce.0.into())),
+        )
+        .unwrap();
+    let l1_client: &dyn EthInterface = (*tester.gateway).as_ref();
+    l1_client.send_raw_tx(foreign_tx.raw_tx).await.unwrap();
+    tester
+        .gateway
+        .execute_tx(foreign_tx.hash, true, EthSenderTester::WAIT_CONFIRMATIONS);
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // The commit tx must be re-queued with the next nonce.
+    tester.assert_just_sent_tx_count_equals(0).await;
+    tester.assert_inflight_txs_count_equals(0).await;
+    let requeued_tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_eth_tx(eth_tx.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(requeued_tx.nonce, eth_tx.nonce + 1);
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+    first_l1_batch.execute_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_inflight_txs_count_equals(0).await;
+
+    let health = tester.manager.health_check().check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+}
+
+#[test_log::test(tokio::test)]
+async fn unsent_transactions_are_requeued_together_with_inflight_ones() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.enable_nonce_recovery(None);
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let third_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_tx = tester.save_commit_tx(first_l1_batch.number).await;
+    let second_tx = tester.save_commit_tx(second_l1_batch.number).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(2).await;
+    // This tx is created by the aggregator, but not sent yet.
+    let unsent_tx = tester.save_commit_tx(third_l1_batch.number).await;
+    assert_eq!(unsent_tx.nonce, second_tx.nonce + 1);
+
+    // Consume the nonce of the first tx with a foreign transaction.
+    let foreign_tx = tester
+        .gateway
+        .sign_prepared_tx(
+            vec![],
+            Address::random(),
+            Options::with(|opt| opt.nonce = Some(first_tx.nonce.0.into())),
+        )
+        .unwrap();
+    let l1_client: &dyn EthInterface = (*tester.gateway).as_ref();
+    l1_client.send_raw_tx(foreign_tx.raw_tx).await.unwrap();
+    tester
+        .gateway
+        .execute_tx(foreign_tx.hash, true, EthSenderTester::WAIT_CONFIRMATIONS);
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // All txs must be shifted by one nonce, including the unsent one, preserving their order.
+    let mut storage = tester.storage().await;
+    let mut nonces = vec![];
+    for eth_tx in [&first_tx, &second_tx, &unsent_tx] {
+        let eth_tx = storage
+            .eth_sender_dal()
+            .get_eth_tx(eth_tx.id)
+            .await
+            .unwrap()
+            .unwrap();
+        nonces.push(eth_tx.nonce);
+    }
+    assert_eq!(
+        nonces,
+        [first_tx.nonce + 1, second_tx.nonce + 1, unsent_tx.nonce + 1]
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn mined_transactions_are_not_requeued_after_foreign_nonce_use() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.enable_nonce_recovery(None);
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_tx = tester.save_commit_tx(first_l1_batch.number).await;
+    let second_tx = tester.save_commit_tx(second_l1_batch.number).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(2).await;
+
+    // Consume the nonce of the first tx with a foreign transaction; the second tx is mined, but not finalized.
+    let foreign_tx = tester
+        .gateway
+        .sign_prepared_tx(
+            vec![],
+            Address::random(),
+            Options::with(|opt| opt.nonce = Some(first_tx.nonce.0.into())),
+        )
+        .unwrap();
+    let l1_client: &dyn EthInterface = (*tester.gateway).as_ref();
+    l1_client.send_raw_tx(foreign_tx.raw_tx).await.unwrap();
+    tester
+        .gateway
+        .execute_tx(foreign_tx.hash, true, EthSenderTester::WAIT_CONFIRMATIONS);
+    tester
+        .execute_tx(
+            second_l1_batch.number,
+            AggregatedActionType::Commit,
+            true,
+            0,
+        )
+        .await;
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // Only the first tx must be re-queued; the mined tx must keep its nonce and sending history.
+    let mut storage = tester.storage().await;
+    let requeued_tx = storage
+        .eth_sender_dal()
+        .get_eth_tx(first_tx.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(requeued_tx.nonce, second_tx.nonce + 1);
+    let mined_tx = storage
+        .eth_sender_dal()
+        .get_eth_tx(second_tx.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(mined_tx.nonce, second_tx.nonce);
+    let mined_tx_history = storage
+        .eth_sender_dal()
+        .get_tx_history_to_check(second_tx.id)
+        .await
+        .unwrap();
+    assert!(!mined_tx_history.is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn stuck_transactions_are_cancelled_and_requeued() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.enable_nonce_recovery(Some(3));
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_tx = tester.save_commit_tx(first_l1_batch.number).await;
+    let second_tx = tester.save_commit_tx(second_l1_batch.number).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(2).await;
+
+    for _ in 0..2 {
+        tester.run_eth_sender_tx_manager_iteration().await;
+        // Only the first tx is resent
+        tester.assert_just_sent_tx_count_equals(1).await;
+    }
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // Both txs must be cancelled
+    tester.assert_just_sent_tx_count_equals(2).await;
+    let nonce_recovery = tester.manager.nonce_recovery();
+    let first_placeholder = nonce_recovery
+        .placeholder(OperatorType::NonBlob, first_tx.nonce)
+        .unwrap();
+    assert_eq!(first_placeholder.cancelled_eth_tx_id, Some(first_tx.id));
+    let second_placeholder = nonce_recovery
+        .placeholder(OperatorType::NonBlob, second_tx.nonce)
+        .unwrap();
+    assert_eq!(second_placeholder.cancelled_eth_tx_id, Some(second_tx.id));
+    let health = tester.manager.health_check().check_health().await;
+    assert_matches!(health.status(), HealthStatus::Affected);
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // The first placeholder is resent with increased fees
+    tester.assert_just_sent_tx_count_equals(1).await;
+
+    let nonce_recovery = tester.manager.nonce_recovery();
+    let first_placeholder_hash = nonce_recovery
+        .placeholder(OperatorType::NonBlob, first_tx.nonce)
+        .unwrap()
+        .tx_hash;
+    let second_placeholder_hash = nonce_recovery
+        .placeholder(OperatorType::NonBlob, second_tx.nonce)
+        .unwrap()
+        .tx_hash;
+    tester.gateway.execute_tx(first_placeholder_hash, true, 0);
+    tester.gateway.execute_tx(
+        second_placeholder_hash,
+        true,
+        EthSenderTester::WAIT_CONFIRMATIONS,
+    );
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(0).await;
+    tester.assert_inflight_txs_count_equals(0).await;
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // Cancelled txs are sent anew with the following nonces
+    tester.assert_just_sent_tx_count_equals(2).await;
+    let mut storage = tester.storage().await;
+    for (tx, expected_nonce) in [(&first_tx, Nonce(2)), (&second_tx, Nonce(3))] {
+        let requeued_tx = storage
+            .eth_sender_dal()
+            .get_eth_tx(tx.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(requeued_tx.nonce, expected_nonce);
+    }
+    drop(storage);
+
+    first_l1_batch.execute_commit_tx(&mut tester).await;
+    second_l1_batch.execute_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_inflight_txs_count_equals(0).await;
+
+    let health = tester.manager.health_check().check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+}
+
+#[test_log::test(tokio::test)]
+async fn placeholders_are_restored_after_restart() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.enable_nonce_recovery(Some(3));
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_tx = tester.save_commit_tx(first_l1_batch.number).await;
+    let second_tx = tester.save_commit_tx(second_l1_batch.number).await;
+    for _ in 0..3 {
+        tester.run_eth_sender_tx_manager_iteration().await;
+    }
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // Both txs are cancelled
+    tester.assert_just_sent_tx_count_equals(2).await;
+
+    // Emulate a restart of the tx manager.
+    tester.enable_nonce_recovery(Some(3));
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // Only the first placeholder is resent; txs must not be cancelled again.
+    tester.assert_just_sent_tx_count_equals(1).await;
+    let nonce_recovery = tester.manager.nonce_recovery();
+    for tx in [&first_tx, &second_tx] {
+        let placeholder = nonce_recovery
+            .placeholder(OperatorType::NonBlob, tx.nonce)
+            .unwrap();
+        assert_eq!(placeholder.cancelled_eth_tx_id, Some(tx.id));
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn nonce_gap_is_filled_with_placeholder() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.enable_nonce_recovery(None);
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    first_l1_batch.save_commit_tx(&mut tester).await;
+    second_l1_batch.save_commit_tx(&mut tester).await;
+
+    // Shift nonces of both txs so that the operator nonce 0 is not occupied.
+    let mut storage = tester.storage().await;
+    let inflight_tx_ids: Vec<_> = storage
+        .eth_sender_dal()
+        .get_inflight_txs(None, false)
+        .await
+        .unwrap()
+        .iter()
+        .map(|tx| tx.id)
+        .collect();
+    let requeued_tx_ids = storage
+        .eth_sender_dal()
+        .requeue_txs(&inflight_tx_ids, 1)
+        .await
+        .unwrap();
+    assert_eq!(requeued_tx_ids.len(), 2);
+    drop(storage);
+
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // Both txs and a placeholder filling the gap must be sent
+    tester.assert_just_sent_tx_count_equals(3).await;
+    let placeholder = tester
+        .manager
+        .nonce_recovery()
+        .placeholder(OperatorType::NonBlob, Nonce(0))
+        .unwrap();
+    assert_eq!(placeholder.cancelled_eth_tx_id, None);
+    let placeholder_hash = placeholder.tx_hash;
+
+    tester.gateway.execute_tx(placeholder_hash, true, 0);
+    first_l1_batch.execute_commit_tx(&mut tester).await;
+    second_l1_batch.execute_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_inflight_txs_count_equals(0).await;
+
+    let health = tester.manager.health_check().check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+}
+
+#[test_log::test(tokio::test)]
+async fn new_transactions_are_deferred_once_spend_budget_is_exhausted() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.set_sender_config(SenderConfig {
+        commit_hourly_budget_gwei: Some(1),
+        ..EthConfig::for_tests().sender.unwrap()
+    });
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    first_l1_batch.save_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+
+    first_l1_batch.execute_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_inflight_txs_count_equals(0).await;
+    let spent_on_commits = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_spent_l1_fees(AggregatedActionType::Commit, Duration::from_secs(3_600))
+        .await
+        .unwrap();
+    assert!(spent_on_commits >= U256::from(1_000_000_000_u64));
+
+    // The hourly budget is exhausted, so the second commit tx must not be sent.
+    second_l1_batch.save_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(0).await;
+}
+
+#[test_log::test(tokio::test)]
+async fn inflight_transactions_count_towards_spend_budget() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    tester.set_sender_config(SenderConfig {
+        commit_hourly_budget_gwei: Some(1),
+        ..EthConfig::for_tests().sender.unwrap()
+    });
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let second_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    first_l1_batch.save_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+
+    let pending_commit_fees = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_pending_l1_fees_upper_bound(AggregatedActionType::Commit, 4_000_000)
+        .await
+        .unwrap();
+    assert!(pending_commit_fees >= U256::from(1_000_000_000_u64));
+
+    // The first commit tx is neither mined nor finalized, but its worst-case cost already exhausts
+    // the budget. Thus, neither the second commit tx nor a fee-bumped resend of the first one must be sent.
+    second_l1_batch.save_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(0).await;
+    tester.assert_inflight_txs_count_equals(1).await;
+}
+
+#[test_log::test(tokio::test)]
+async fn non_urgent_transactions_are_deferred_while_fees_are_above_ceiling() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    let config = SenderConfig {
+        non_urgent_base_fee_ceiling_gwei: Some(0),
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    tester.set_sender_config(config.clone());
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    first_l1_batch.save_commit_tx(&mut tester).await;
+    first_l1_batch.save_prove_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    // Only the commit tx is sent; the prove tx is deferred because of the fee ceiling.
+    tester.assert_just_sent_tx_count_equals(1).await;
+    first_l1_batch.assert_commit_tx_just_sent(&mut tester).await;
+    first_l1_batch.execute_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(0).await;
+    tester.assert_inflight_txs_count_equals(0).await;
+
+    // Once the tx is deferred for too long, it's sent regardless of the ceiling.
+    tester.set_sender_config(SenderConfig {
+        max_non_urgent_deferral_sec: Some(0),
+        ..config
+    });
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+    tester
+        .assert_tx_was_sent_in_last_iteration(
+            first_l1_batch.number,
+            AggregatedActionType::PublishProofOnchain,
+        )
+        .await;
+}
+
 #[test_casing(2, COMMITMENT_MODES)]
 #[test_log::test(tokio::test)]
 async fn correct_order_for_confirmations(
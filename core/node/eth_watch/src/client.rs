@@ -25,6 +25,8 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
     ) -> EnrichedClientResult<Vec<Log>>;
     /// Returns finalized L1 block number.
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64>;
+    /// Returns hash of the canonical L1 block with the specified number, or `None` if there is no such block.
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>>;
     /// Returns scheduler verification key hash by verifier address.
     async fn scheduler_vk_hash(&self, verifier_address: Address)
         -> Result<H256, ContractCallError>;
@@ -35,6 +37,8 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
     ) -> EnrichedClientResult<Option<Vec<u8>>>;
     /// Sets list of topics to return events for.
     fn set_topics(&mut self, topics: Vec<H256>);
+    /// Sets addresses of contracts to return events for in addition to the core ZKsync contracts.
+    fn set_extra_addresses(&mut self, addresses: Vec<Address>);
 }
 
 pub const RETRY_LIMIT: usize = 5;
@@ -46,6 +50,7 @@ const TOO_MANY_RESULTS_ALCHEMY: &str = "response size exceeded";
 pub struct EthHttpQueryClient {
     client: Box<DynClient<L1>>,
     topics: Vec<H256>,
+    extra_addresses: Vec<Address>,
     diamond_proxy_addr: Address,
     governance_address: Address,
     new_upgrade_cut_data_signature: H256,
@@ -73,6 +78,7 @@ impl EthHttpQueryClient {
         Self {
             client: client.for_component("watch"),
             topics: Vec::new(),
+            extra_addresses: Vec::new(),
             diamond_proxy_addr,
             state_transition_manager_address,
             chain_admin_address,
@@ -103,6 +109,7 @@ impl EthHttpQueryClient {
                 ]
                 .into_iter()
                 .flatten()
+                .chain(self.extra_addresses.iter().copied())
                 .collect(),
             )
             .from_block(from)
@@ -245,7 +252,19 @@ impl EthClient for EthHttpQueryClient {
         }
     }
 
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        let block = self
+            .client
+            .block(BlockId::Number(BlockNumber::Number(number.into())))
+            .await?;
+        Ok(block.and_then(|block| block.hash))
+    }
+
     fn set_topics(&mut self, topics: Vec<H256>) {
         self.topics = topics;
     }
+
+    fn set_extra_addresses(&mut self, addresses: Vec<Address>) {
+        self.extra_addresses = addresses;
+    }
 }
@@ -37,6 +37,10 @@ impl DecentralizedUpgradesEventProcessor {
 
 #[async_trait::async_trait]
 impl EventProcessor for DecentralizedUpgradesEventProcessor {
+    fn name(&self) -> &'static str {
+        "decentralized_upgrades"
+    }
+
     async fn process_events(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -2,7 +2,7 @@ use std::fmt;
 
 use zksync_dal::{Connection, Core};
 use zksync_eth_client::{ContractCallError, EnrichedClientError};
-use zksync_types::{web3::Log, H256};
+use zksync_types::{web3::Log, Address, H256};
 
 pub(crate) use self::{
     decentralized_upgrades::DecentralizedUpgradesEventProcessor,
@@ -16,7 +16,7 @@ mod priority_ops;
 
 /// Errors issued by an [`EventProcessor`].
 #[derive(Debug, thiserror::Error)]
-pub(super) enum EventProcessorError {
+pub enum EventProcessorError {
     #[error("failed parsing a log into {log_kind}: {source:?}")]
     LogParse {
         log_kind: &'static str,
@@ -41,11 +41,22 @@ impl EventProcessorError {
     }
 }
 
-/// Processor for a single type of events emitted by the L1 contract. [`EthWatch`](crate::EthWatch)
+/// Processor for a single type of events emitted by an L1 contract. [`EthWatch`](crate::EthWatch)
 /// feeds events to all processors one-by-one.
+///
+/// Each processor has its own cursor (the last processed L1 block) persisted in the database. Events are fed
+/// to the processor and the cursor is advanced in a single database transaction, and only events from finalized
+/// L1 blocks (as reported by [`EthClient::finalized_block_number()`]) are fed. If an L1 reorg affecting processed
+/// blocks is detected nonetheless, [`Self::handle_l1_reorg()`] is called, and events from the reorged blocks
+/// are fed to the processor again.
 #[async_trait::async_trait]
-pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
-    /// Processes given events. All events are guaranteed to match [`Self::relevant_topic()`].
+pub trait EventProcessor: 'static + fmt::Debug + Send + Sync {
+    /// Unique name of the processor. Used as a key for the persisted cursor of the processor, so it must not change
+    /// between restarts.
+    fn name(&self) -> &'static str;
+
+    /// Processes given events. All events are guaranteed to match [`Self::relevant_topic()`]
+    /// and [`Self::relevant_address()`] (if the latter is specified).
     async fn process_events(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -55,4 +66,23 @@ pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
 
     /// Relevant topic which defines what events to be processed
     fn relevant_topic(&self) -> H256;
+
+    /// Address of the contract emitting relevant events. If not specified, events are taken from the core ZKsync contracts
+    /// (the diamond proxy, governance, state transition manager and chain admin).
+    fn relevant_address(&self) -> Option<Address> {
+        None
+    }
+
+    /// Handles an L1 reorg starting from `first_reorged_block` (inclusive). Called in the same database transaction
+    /// that rewinds the processor cursor to the block preceding `first_reorged_block`.
+    ///
+    /// Since hashes are not saved for all L1 blocks, `first_reorged_block` is a lower bound for the fork point;
+    /// the blocks immediately following it may be unaffected by the reorg.
+    async fn handle_l1_reorg(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        _first_reorged_block: u64,
+    ) -> Result<(), EventProcessorError> {
+        Ok(())
+    }
 }
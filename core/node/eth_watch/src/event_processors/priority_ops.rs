@@ -4,7 +4,7 @@ use anyhow::Context;
 use zksync_contracts::hyperchain_contract;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{l1::L1Tx, web3::Log, PriorityOpId, H256};
+use zksync_types::{l1::L1Tx, web3::Log, L1BlockNumber, PriorityOpId, H256};
 
 use crate::{
     client::EthClient,
@@ -33,6 +33,10 @@ impl PriorityOpsEventProcessor {
 
 #[async_trait::async_trait]
 impl EventProcessor for PriorityOpsEventProcessor {
+    fn name(&self) -> &'static str {
+        "priority_ops"
+    }
+
     async fn process_events(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -99,4 +103,50 @@ impl EventProcessor for PriorityOpsEventProcessor {
     fn relevant_topic(&self) -> H256 {
         self.new_priority_request_signature
     }
+
+    /// Rolls back priority ops emitted in reorged L1 blocks, provided that they are neither executed
+    /// nor loaded into the state keeper mempool.
+    async fn handle_l1_reorg(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        first_reorged_block: u64,
+    ) -> Result<(), EventProcessorError> {
+        let first_reorged_block = u32::try_from(first_reorged_block)
+            .map(L1BlockNumber)
+            .context("L1 block number overflow")?;
+        let removed_ids = storage
+            .transactions_dal()
+            .remove_unexecuted_priority_ops_since_l1_block(first_reorged_block)
+            .await
+            .map_err(DalError::generalize)?;
+        // Ops loaded into the mempool may be executed by the state keeper at any moment, so they cannot be
+        // rolled back safely. The error drops the DB transaction, so removals above are not persisted either.
+        let has_remaining_ops = storage
+            .transactions_dal()
+            .has_priority_ops_since_l1_block(first_reorged_block)
+            .await
+            .map_err(DalError::generalize)?;
+        if has_remaining_ops {
+            let err = anyhow::anyhow!(
+                "L1 reorg starting from block #{first_reorged_block} affects priority ops that are already executed \
+                 or loaded into the state keeper mempool; manual intervention is required"
+            );
+            return Err(err.into());
+        }
+
+        let (Some(&first_removed), Some(&last_removed)) = (removed_ids.first(), removed_ids.last())
+        else {
+            return Ok(());
+        };
+        tracing::error!(
+            "Rolled back {} priority ops with serial ids {first_removed} - {last_removed} emitted in reorged L1 blocks \
+             starting from #{first_reorged_block}",
+            removed_ids.len()
+        );
+        METRICS
+            .rolled_back_priority_ops
+            .inc_by(removed_ids.len() as u64);
+        self.next_expected_priority_id = first_removed;
+        Ok(())
+    }
 }
@@ -2,27 +2,34 @@
 //! protocol upgrades etc.
 //! New events are accepted to the ZKsync network once they have the sufficient amount of L1 confirmations.
 
-use std::time::Duration;
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
-    ethabi::Contract, protocol_version::ProtocolSemanticVersion,
-    web3::BlockNumber as Web3BlockNumber, Address, PriorityOpId,
+    ethabi::Contract,
+    protocol_version::ProtocolSemanticVersion,
+    web3::{BlockNumber as Web3BlockNumber, Log},
+    Address, PriorityOpId,
 };
 
-pub use self::client::EthHttpQueryClient;
 use self::{
-    client::{EthClient, RETRY_LIMIT},
+    client::RETRY_LIMIT,
     event_processors::{
-        EventProcessor, EventProcessorError, GovernanceUpgradesEventProcessor,
+        DecentralizedUpgradesEventProcessor, GovernanceUpgradesEventProcessor,
         PriorityOpsEventProcessor,
     },
-    metrics::{PollStage, METRICS},
+    metrics::{PollStage, ProcessorLabels, METRICS},
+};
+pub use self::{
+    client::{EthClient, EthHttpQueryClient},
+    event_processors::{EventProcessor, EventProcessorError},
 };
-use crate::event_processors::DecentralizedUpgradesEventProcessor;
 
 mod client;
 mod event_processors;
@@ -37,13 +44,22 @@ struct EthWatchState {
     last_processed_ethereum_block: u64,
 }
 
+/// Event processor together with its cursor.
+#[derive(Debug)]
+struct ProcessorWithCursor {
+    processor: Box<dyn EventProcessor>,
+    /// Last L1 block processed by the processor, or `None` if the cursor is not loaded from the storage yet.
+    last_processed_block: Option<u64>,
+}
+
 /// Ethereum watcher component.
 #[derive(Debug)]
 pub struct EthWatch {
     client: Box<dyn EthClient>,
     poll_interval: Duration,
-    event_processors: Vec<Box<dyn EventProcessor>>,
-    last_processed_ethereum_block: u64,
+    event_processors: Vec<ProcessorWithCursor>,
+    /// Last processed L1 block for processors that haven't persisted their cursor yet.
+    default_last_processed_block: u64,
     pool: ConnectionPool<Core>,
 }
 
@@ -52,7 +68,7 @@ impl EthWatch {
         diamond_proxy_addr: Address,
         governance_contract: &Contract,
         chain_admin_contract: &Contract,
-        mut client: Box<dyn EthClient>,
+        client: Box<dyn EthClient>,
         pool: ConnectionPool<Core>,
         poll_interval: Duration,
     ) -> anyhow::Result<Self> {
@@ -72,25 +88,60 @@ impl EthWatch {
             state.last_seen_protocol_version,
             chain_admin_contract,
         );
-        let event_processors: Vec<Box<dyn EventProcessor>> = vec![
-            Box::new(priority_ops_processor),
-            Box::new(governance_upgrades_processor),
-            Box::new(decentralized_upgrades_processor),
-        ];
 
-        let topics = event_processors
-            .iter()
-            .map(|processor| processor.relevant_topic())
-            .collect();
-        client.set_topics(topics);
-
-        Ok(Self {
+        let mut this = Self {
             client,
             poll_interval,
-            event_processors,
-            last_processed_ethereum_block: state.last_processed_ethereum_block,
+            event_processors: vec![],
+            default_last_processed_block: state.last_processed_ethereum_block,
             pool,
-        })
+        };
+        this.add_event_processor(Box::new(priority_ops_processor))?;
+        this.add_event_processor(Box::new(governance_upgrades_processor))?;
+        this.add_event_processor(Box::new(decentralized_upgrades_processor))?;
+        Ok(this)
+    }
+
+    /// Adds a custom event processor. If the processor hasn't persisted its cursor yet, it will receive events
+    /// starting from the same L1 block as built-in processors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a processor with the same name is already registered.
+    pub fn add_event_processor(
+        &mut self,
+        processor: Box<dyn EventProcessor>,
+    ) -> anyhow::Result<()> {
+        let name = processor.name();
+        let is_duplicate = self
+            .event_processors
+            .iter()
+            .any(|existing| existing.processor.name() == name);
+        anyhow::ensure!(
+            !is_duplicate,
+            "event processor `{name}` is already registered"
+        );
+
+        self.event_processors.push(ProcessorWithCursor {
+            processor,
+            last_processed_block: None,
+        });
+        let topics = self
+            .event_processors
+            .iter()
+            .map(|processor| processor.processor.relevant_topic())
+            .collect();
+        self.client.set_topics(topics);
+        self.client
+            .set_extra_addresses(self.extra_addresses().into_iter().collect());
+        Ok(())
+    }
+
+    fn extra_addresses(&self) -> HashSet<Address> {
+        self.event_processors
+            .iter()
+            .filter_map(|processor| processor.processor.relevant_address())
+            .collect()
     }
 
     #[tracing::instrument(name = "EthWatch::initialize_state", skip_all)]
@@ -155,10 +206,14 @@ impl EthWatch {
                     // This is an error because otherwise we could potentially miss a priority operation
                     // thus entering priority mode, which is not desired.
                     tracing::error!("Failed to process new blocks: {err}");
-                    self.last_processed_ethereum_block =
+                    self.default_last_processed_block =
                         Self::initialize_state(&*self.client, &mut storage)
                             .await?
                             .last_processed_ethereum_block;
+                    // Cursors will be reloaded from the storage on the next iteration.
+                    for processor in &mut self.event_processors {
+                        processor.last_processed_block = None;
+                    }
                 }
             }
         }
@@ -172,34 +227,251 @@ impl EthWatch {
         &mut self,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), EventProcessorError> {
+        self.load_cursors(storage).await?;
+        self.check_for_l1_reorg(storage).await?;
+
         let stage_latency = METRICS.poll_eth_node[&PollStage::Request].start();
+        // Only finalized blocks are processed, so L1 reorgs affecting processed events are unlikely
+        // (but are still detected by `check_for_l1_reorg()`).
         let to_block = self.client.finalized_block_number().await?;
-        if to_block <= self.last_processed_ethereum_block {
+
+        let from_block = self
+            .event_processors
+            .iter()
+            .map(|processor| {
+                processor
+                    .last_processed_block
+                    .expect("cursor must be loaded")
+            })
+            .min();
+        let Some(from_block) = from_block else {
+            return Ok(());
+        };
+        if to_block <= from_block {
             return Ok(());
         }
 
         let events = self
             .client
             .get_events(
-                Web3BlockNumber::Number(self.last_processed_ethereum_block.into()),
+                Web3BlockNumber::Number((from_block + 1).into()),
                 Web3BlockNumber::Number(to_block.into()),
                 RETRY_LIMIT,
             )
             .await?;
         stage_latency.observe();
+        self.save_l1_block_hashes(storage, &events, to_block)
+            .await?;
 
+        let extra_addresses = self.extra_addresses();
         for processor in &mut self.event_processors {
-            let relevant_topic = processor.relevant_topic();
+            let name = processor.processor.name();
+            let last_processed_block = processor
+                .last_processed_block
+                .expect("cursor must be loaded");
+            if to_block <= last_processed_block {
+                continue;
+            }
+
+            let relevant_topic = processor.processor.relevant_topic();
+            let relevant_address = processor.processor.relevant_address();
             let processor_events = events
                 .iter()
-                .filter(|event| event.topics.first() == Some(&relevant_topic))
+                .filter(|event| {
+                    let is_from_relevant_contract = match relevant_address {
+                        Some(address) => event.address == address,
+                        None => !extra_addresses.contains(&event.address),
+                    };
+                    let is_new = event
+                        .block_number
+                        .map_or(true, |number| number.as_u64() > last_processed_block);
+                    event.topics.first() == Some(&relevant_topic)
+                        && is_from_relevant_contract
+                        && is_new
+                })
                 .cloned()
                 .collect();
+
+            // Events are processed and the cursor is advanced atomically.
+            let mut transaction = storage
+                .start_transaction()
+                .await
+                .map_err(DalError::generalize)?;
+            processor
+                .processor
+                .process_events(&mut transaction, &*self.client, processor_events)
+                .await?;
+            transaction
+                .eth_watcher_dal()
+                .set_last_processed_l1_block(name, to_block)
+                .await
+                .map_err(DalError::generalize)?;
+            transaction.commit().await.map_err(DalError::generalize)?;
+
+            processor.last_processed_block = Some(to_block);
+            METRICS.last_processed_l1_block[&ProcessorLabels { processor: name }].set(to_block);
+        }
+        Ok(())
+    }
+
+    async fn load_cursors(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), EventProcessorError> {
+        for processor in &mut self.event_processors {
+            if processor.last_processed_block.is_some() {
+                continue;
+            }
+            let persisted_block = storage
+                .eth_watcher_dal()
+                .get_last_processed_l1_block(processor.processor.name())
+                .await
+                .map_err(DalError::generalize)?;
+            processor.last_processed_block =
+                Some(persisted_block.unwrap_or(self.default_last_processed_block));
+        }
+        Ok(())
+    }
+
+    /// Saves hashes of L1 blocks containing processed events, and of the last block in the processed range.
+    async fn save_l1_block_hashes(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        events: &[Log],
+        to_block: u64,
+    ) -> Result<(), EventProcessorError> {
+        let mut block_hashes: BTreeMap<_, _> = events
+            .iter()
+            .filter_map(|event| Some((event.block_number?.as_u64(), event.block_hash?)))
+            .collect();
+        if let Some(hash) = self.client.block_hash(to_block).await? {
+            block_hashes.insert(to_block, hash);
+        } else {
+            tracing::warn!("L1 client returned no hash for finalized L1 block #{to_block}");
+        }
+
+        for (number, hash) in block_hashes {
+            storage
+                .eth_watcher_dal()
+                .insert_l1_block_hash(number, hash)
+                .await
+                .map_err(DalError::generalize)?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether saved hashes of L1 blocks still match the canonical L1 chain. Only the last processed block,
+    /// blocks containing not yet executed priority ops, and the last saved block preceding them are checked;
+    /// hashes of older blocks are pruned.
+    ///
+    /// If an L1 reorg is detected, all processors that have processed blocks after the last block with a matching hash
+    /// are notified and rewound to this block, so that events from the new canonical chain will be fed to them
+    /// on the next iteration.
+    #[tracing::instrument(name = "EthWatch::check_for_l1_reorg", skip_all)]
+    async fn check_for_l1_reorg(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), EventProcessorError> {
+        let Some(last_block_with_hash) = storage
+            .eth_watcher_dal()
+            .get_last_l1_block_with_hash()
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(());
+        };
+        let first_unexecuted_op_block = storage
+            .transactions_dal()
+            .get_first_unexecuted_priority_op_l1_block()
+            .await
+            .map_err(DalError::generalize)?;
+        let from_block = first_unexecuted_op_block.map_or(last_block_with_hash, |block| {
+            u64::from(block.0).min(last_block_with_hash)
+        });
+        // The preceding saved block serves as a lower bound for the fork point if the reorg affects `from_block`.
+        let from_block = storage
+            .eth_watcher_dal()
+            .get_last_l1_block_with_hash_before(from_block)
+            .await
+            .map_err(DalError::generalize)?
+            .unwrap_or(from_block);
+
+        let saved_hashes = storage
+            .eth_watcher_dal()
+            .get_l1_block_hashes(from_block)
+            .await
+            .map_err(DalError::generalize)?;
+        let mut last_matching_block = None;
+        let mut mismatched_block = None;
+        for (number, saved_hash) in saved_hashes {
+            let hash = self.client.block_hash(number).await?;
+            if hash != Some(saved_hash) {
+                tracing::error!(
+                    "L1 reorg detected: hash of L1 block #{number} has changed from {saved_hash:?} to {hash:?}"
+                );
+                mismatched_block = Some(number);
+                break;
+            }
+            last_matching_block = Some(number);
+        }
+
+        let Some(mismatched_block) = mismatched_block else {
+            storage
+                .eth_watcher_dal()
+                .prune_l1_block_hashes(from_block)
+                .await
+                .map_err(DalError::generalize)?;
+            return Ok(());
+        };
+        let Some(rewound_block) = last_matching_block else {
+            let err = anyhow::anyhow!(
+                "L1 reorg affects the oldest L1 block with a saved hash (#{mismatched_block}), so the fork point \
+                 cannot be determined; manual intervention is required"
+            );
+            return Err(err.into());
+        };
+        // Hashes of blocks between the last matching and the mismatched block are not saved, so the fork
+        // can be anywhere in this range. Rewinding to the last matching block ensures that no events are missed.
+        let first_reorged_block = rewound_block + 1;
+        METRICS.l1_reorgs.inc();
+
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+        for processor in &mut self.event_processors {
+            let last_processed_block = processor
+                .last_processed_block
+                .expect("cursor must be loaded");
+            if last_processed_block < first_reorged_block {
+                continue;
+            }
+            let name = processor.processor.name();
+            tracing::warn!(
+                "Rewinding event processor `{name}` from L1 block #{last_processed_block} to #{rewound_block}"
+            );
             processor
-                .process_events(storage, &*self.client, processor_events)
+                .processor
+                .handle_l1_reorg(&mut transaction, first_reorged_block)
                 .await?;
+            transaction
+                .eth_watcher_dal()
+                .set_last_processed_l1_block(name, rewound_block)
+                .await
+                .map_err(DalError::generalize)?;
+        }
+        transaction
+            .eth_watcher_dal()
+            .remove_l1_block_hashes_since(first_reorged_block)
+            .await
+            .map_err(DalError::generalize)?;
+        transaction.commit().await.map_err(DalError::generalize)?;
+
+        for processor in &mut self.event_processors {
+            if let Some(block) = &mut processor.last_processed_block {
+                *block = (*block).min(rewound_block);
+            }
         }
-        self.last_processed_ethereum_block = to_block;
         Ok(())
     }
 }
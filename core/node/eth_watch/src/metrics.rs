@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -12,6 +12,11 @@ pub(super) enum PollStage {
     PersistUpgrades,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct ProcessorLabels {
+    pub processor: &'static str,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_eth_watch")]
 pub(super) struct EthWatcherMetrics {
@@ -20,6 +25,12 @@ pub(super) struct EthWatcherMetrics {
     /// Latency of polling and processing events split by stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
+    /// Last L1 block processed by an event processor.
+    pub last_processed_l1_block: Family<ProcessorLabels, Gauge<u64>>,
+    /// Number of detected L1 reorgs affecting processed L1 blocks.
+    pub l1_reorgs: Counter,
+    /// Number of priority operations rolled back because of L1 reorgs.
+    pub rolled_back_priority_ops: Counter,
 }
 
 #[vise::register]
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, convert::TryInto, sync::Arc};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    sync::{Arc, Mutex},
+};
 
 use tokio::sync::RwLock;
 use zksync_contracts::{chain_admin_contract, governance_contract, hyperchain_contract};
@@ -15,14 +19,17 @@ use zksync_types::{
     ProtocolVersionId, Transaction, H256, U256,
 };
 
-use crate::{client::EthClient, EthWatch};
+use crate::{client::EthClient, EthWatch, EventProcessor, EventProcessorError};
 
 #[derive(Debug)]
 struct FakeEthClientData {
     transactions: HashMap<u64, Vec<Log>>,
     diamond_upgrades: HashMap<u64, Vec<Log>>,
     governance_upgrades: HashMap<u64, Vec<Log>>,
+    custom_logs: HashMap<u64, Vec<Log>>,
     last_finalized_block_number: u64,
+    /// First blocks of emulated L1 reorgs.
+    reorgs: Vec<u64>,
 }
 
 impl FakeEthClientData {
@@ -31,26 +38,57 @@ impl FakeEthClientData {
             transactions: Default::default(),
             diamond_upgrades: Default::default(),
             governance_upgrades: Default::default(),
+            custom_logs: Default::default(),
             last_finalized_block_number: 0,
+            reorgs: vec![],
+        }
+    }
+
+    fn block_hash(&self, number: u64) -> H256 {
+        let fork_id = self.reorgs.iter().filter(|&&start| number >= start).count();
+        let mut hash = H256::from_low_u64_be(number);
+        hash.0[0] = fork_id as u8;
+        hash
+    }
+
+    fn reorg(&mut self, first_block: u64) {
+        self.reorgs.push(first_block);
+        for logs in [
+            &mut self.transactions,
+            &mut self.diamond_upgrades,
+            &mut self.governance_upgrades,
+            &mut self.custom_logs,
+        ] {
+            logs.retain(|&number, _| number < first_block);
         }
     }
 
     fn add_transactions(&mut self, transactions: &[L1Tx]) {
         for transaction in transactions {
-            let eth_block = transaction.eth_block();
-            self.transactions
-                .entry(eth_block.0 as u64)
-                .or_default()
-                .push(tx_into_log(transaction.clone()));
+            let eth_block = transaction.eth_block().0.into();
+            let mut log = tx_into_log(transaction.clone());
+            log.block_hash = Some(self.block_hash(eth_block));
+            self.transactions.entry(eth_block).or_default().push(log);
         }
     }
 
     fn add_governance_upgrades(&mut self, upgrades: &[(ProtocolUpgrade, u64)]) {
         for (upgrade, eth_block) in upgrades {
+            let mut log = upgrade_into_governor_log(upgrade.clone(), *eth_block);
+            log.block_hash = Some(self.block_hash(*eth_block));
             self.governance_upgrades
                 .entry(*eth_block)
                 .or_default()
-                .push(upgrade_into_governor_log(upgrade.clone(), *eth_block));
+                .push(log);
+        }
+    }
+
+    fn add_custom_logs(&mut self, logs: &[Log]) {
+        for log in logs {
+            let eth_block = log.block_number.expect("no block number").as_u64();
+            let mut log = log.clone();
+            log.block_hash = Some(self.block_hash(eth_block));
+            self.custom_logs.entry(eth_block).or_default().push(log);
         }
     }
 
@@ -79,6 +117,16 @@ impl MockEthClient {
         self.inner.write().await.add_governance_upgrades(upgrades);
     }
 
+    async fn add_custom_logs(&mut self, logs: &[Log]) {
+        self.inner.write().await.add_custom_logs(logs);
+    }
+
+    /// Emulates an L1 reorg starting from the specified block: hashes of this and all following blocks change,
+    /// and all logs emitted in these blocks are dropped.
+    async fn reorg(&mut self, first_block: u64) {
+        self.inner.write().await.reorg(first_block);
+    }
+
     async fn set_last_finalized_block_number(&mut self, number: u64) {
         self.inner
             .write()
@@ -119,12 +167,17 @@ impl EthClient for MockEthClient {
             if let Some(ops) = self.inner.read().await.governance_upgrades.get(&number) {
                 logs.extend_from_slice(ops);
             }
+            if let Some(ops) = self.inner.read().await.custom_logs.get(&number) {
+                logs.extend_from_slice(ops);
+            }
         }
         Ok(logs)
     }
 
     fn set_topics(&mut self, _topics: Vec<Hash>) {}
 
+    fn set_extra_addresses(&mut self, _addresses: Vec<Address>) {}
+
     async fn scheduler_vk_hash(
         &self,
         _verifier_address: Address,
@@ -136,6 +189,10 @@ impl EthClient for MockEthClient {
         Ok(self.inner.read().await.last_finalized_block_number)
     }
 
+    async fn block_hash(&self, number: u64) -> EnrichedClientResult<Option<H256>> {
+        Ok(Some(self.inner.read().await.block_hash(number)))
+    }
+
     async fn diamond_cut_by_version(
         &self,
         _packed_version: H256,
@@ -467,13 +524,310 @@ async fn test_overlapping_batches() {
     assert_eq!(tx.common_data.serial_id.0, 4);
 }
 
+const CUSTOM_CONTRACT_ADDRESS: Address = Address::repeat_byte(0x22);
+
+/// Processor recording L1 block numbers of all events fed to it.
+#[derive(Debug, Default)]
+struct RecordingEventProcessor {
+    processed_blocks: Arc<Mutex<Vec<u64>>>,
+}
+
+impl RecordingEventProcessor {
+    fn topic() -> H256 {
+        H256::repeat_byte(0xaa)
+    }
+}
+
+#[async_trait::async_trait]
+impl EventProcessor for RecordingEventProcessor {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    async fn process_events(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        _client: &dyn EthClient,
+        events: Vec<Log>,
+    ) -> Result<(), EventProcessorError> {
+        let mut processed_blocks = self.processed_blocks.lock().unwrap();
+        for event in events {
+            assert_eq!(event.address, CUSTOM_CONTRACT_ADDRESS);
+            assert_eq!(event.topics[0], Self::topic());
+            processed_blocks.push(event.block_number.unwrap().as_u64());
+        }
+        Ok(())
+    }
+
+    fn relevant_topic(&self) -> H256 {
+        Self::topic()
+    }
+
+    fn relevant_address(&self) -> Option<Address> {
+        Some(CUSTOM_CONTRACT_ADDRESS)
+    }
+}
+
+fn custom_log(address: Address, eth_block: u64) -> Log {
+    Log {
+        address,
+        topics: vec![RecordingEventProcessor::topic()],
+        data: vec![].into(),
+        block_hash: Some(H256::repeat_byte(0x11)),
+        block_number: Some(eth_block.into()),
+        transaction_hash: Some(H256::random()),
+        transaction_index: Some(0u64.into()),
+        log_index: Some(0u64.into()),
+        transaction_log_index: Some(0u64.into()),
+        log_type: None,
+        removed: None,
+        block_timestamp: None,
+    }
+}
+
+#[tokio::test]
+async fn custom_event_processor_is_fed_finalized_events() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    let processor = RecordingEventProcessor::default();
+    let processed_blocks = processor.processed_blocks.clone();
+    watcher.add_event_processor(Box::new(processor)).unwrap();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_custom_logs(&[
+            custom_log(CUSTOM_CONTRACT_ADDRESS, 5),
+            // Event with the same topic, but from an unrelated contract.
+            custom_log(Address::repeat_byte(0x33), 6),
+            custom_log(CUSTOM_CONTRACT_ADDRESS, 12),
+        ])
+        .await;
+    client
+        .add_transactions(&[build_l1_tx(0, 7), build_l1_tx(1, 12)])
+        .await;
+    client.set_last_finalized_block_number(10).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(*processed_blocks.lock().unwrap(), [5]);
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(*processed_blocks.lock().unwrap(), [5, 12]);
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+
+    let cursor = storage
+        .eth_watcher_dal()
+        .get_last_processed_l1_block("recording")
+        .await
+        .unwrap();
+    assert_eq!(cursor, Some(15));
+}
+
+#[tokio::test]
+async fn custom_event_processor_resumes_from_persisted_cursor() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    watcher
+        .add_event_processor(Box::<RecordingEventProcessor>::default())
+        .unwrap();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_custom_logs(&[
+            custom_log(CUSTOM_CONTRACT_ADDRESS, 5),
+            custom_log(CUSTOM_CONTRACT_ADDRESS, 18),
+        ])
+        .await;
+    client.set_last_finalized_block_number(10).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    drop(watcher);
+
+    // Emulate a restart of the watcher.
+    let mut watcher = EthWatch::new(
+        Address::default(),
+        &governance_contract(),
+        &chain_admin_contract(),
+        Box::new(client.clone()),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+    )
+    .await
+    .unwrap();
+    let processor = RecordingEventProcessor::default();
+    let processed_blocks = processor.processed_blocks.clone();
+    watcher.add_event_processor(Box::new(processor)).unwrap();
+
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert!(processed_blocks.lock().unwrap().is_empty());
+
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(*processed_blocks.lock().unwrap(), [18]);
+}
+
+#[tokio::test]
+async fn event_processors_with_duplicate_names_are_rejected() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, _client) = create_test_watcher(connection_pool).await;
+    watcher
+        .add_event_processor(Box::<RecordingEventProcessor>::default())
+        .unwrap();
+    let err = watcher
+        .add_event_processor(Box::<RecordingEventProcessor>::default())
+        .unwrap_err();
+    assert!(err.to_string().contains("already registered"), "{err}");
+}
+
+#[tokio::test]
+async fn unexecuted_priority_ops_are_rolled_back_after_l1_reorg() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14), build_l1_tx(2, 14)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 3);
+
+    // Priority ops from block #14 are reorged; one of them is re-emitted in a later block.
+    client.reorg(14).await;
+    client.add_transactions(&[build_l1_tx(1, 16)]).await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    let serial_ids_and_blocks: Vec<_> = db_txs
+        .iter()
+        .map(|tx| (tx.common_data.serial_id.0, tx.eth_block().0))
+        .collect();
+    assert_eq!(serial_ids_and_blocks, [(0, 10), (1, 16)]);
+
+    let cursor = storage
+        .eth_watcher_dal()
+        .get_last_processed_l1_block("priority_ops")
+        .await
+        .unwrap();
+    assert_eq!(cursor, Some(20));
+
+    // The next priority op must be accepted after the rollback.
+    client.add_transactions(&[build_l1_tx(2, 22)]).await;
+    client.set_last_finalized_block_number(25).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 3);
+}
+
+#[tokio::test]
+async fn l1_reorg_is_propagated_to_custom_event_processors() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    let processor = RecordingEventProcessor::default();
+    let processed_blocks = processor.processed_blocks.clone();
+    watcher.add_event_processor(Box::new(processor)).unwrap();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_custom_logs(&[custom_log(CUSTOM_CONTRACT_ADDRESS, 12)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(*processed_blocks.lock().unwrap(), [12]);
+
+    // Only the last processed block is tracked since there are no pending priority ops.
+    client.reorg(15).await;
+    client
+        .add_custom_logs(&[custom_log(CUSTOM_CONTRACT_ADDRESS, 15)])
+        .await;
+    client.set_last_finalized_block_number(16).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(*processed_blocks.lock().unwrap(), [12, 15]);
+}
+
+#[tokio::test]
+async fn l1_reorg_rewinds_processors_to_last_block_with_matching_hash() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    let processor = RecordingEventProcessor::default();
+    let processed_blocks = processor.processed_blocks.clone();
+    watcher.add_event_processor(Box::new(processor)).unwrap();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_custom_logs(&[custom_log(CUSTOM_CONTRACT_ADDRESS, 12)])
+        .await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(*processed_blocks.lock().unwrap(), [12]);
+
+    // Hashes are saved only for blocks #12 and #20, so the reorg is detected at block #20. The event in block #16
+    // from the new canonical chain must not be missed nonetheless.
+    client.reorg(15).await;
+    client
+        .add_custom_logs(&[custom_log(CUSTOM_CONTRACT_ADDRESS, 16)])
+        .await;
+    client.set_last_finalized_block_number(22).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(*processed_blocks.lock().unwrap(), [12, 16]);
+}
+
+#[tokio::test]
+async fn priority_ops_loaded_into_mempool_are_not_rolled_back() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    // Emulate the state keeper loading priority ops into its mempool.
+    let mempool_txs = storage
+        .transactions_dal()
+        .sync_mempool(&[], &[], 0, 0, 1000)
+        .await
+        .unwrap();
+    assert_eq!(mempool_txs.len(), 2);
+
+    client.reorg(14).await;
+    client.set_last_finalized_block_number(20).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(matches!(err, EventProcessorError::Internal(_)), "{err:?}");
+    assert!(err.to_string().contains("mempool"), "{err}");
+
+    // Neither priority ops nor the cursor must be rolled back.
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+    let cursor = storage
+        .eth_watcher_dal()
+        .get_last_processed_l1_block("priority_ops")
+        .await
+        .unwrap();
+    assert_eq!(cursor, Some(15));
+}
+
 async fn get_all_db_txs(storage: &mut Connection<'_, Core>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
-    storage
+    let txs = storage
         .transactions_dal()
         .sync_mempool(&[], &[], 0, 0, 1000)
         .await
-        .unwrap()
+        .unwrap();
+    // Transactions must not remain marked as loaded into the mempool, since this prevents L1 reorg handling.
+    storage.transactions_dal().reset_mempool().await.unwrap();
+    txs
 }
 
 fn tx_into_log(tx: L1Tx) -> Log {
@@ -1,6 +1,14 @@
 //! Primitive Merkle tree API used internally to fetch proofs.
 
-use std::{fmt, future::Future, net::SocketAddr, pin::Pin};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    net::SocketAddr,
+    path::{Component, Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
@@ -11,7 +19,7 @@ use axum::{
     routing, Json, Router,
 };
 use serde::{Deserialize, Serialize};
-use tokio::sync::watch;
+use tokio::sync::{watch, Mutex};
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_health_check::{CheckHealth, Health, HealthStatus};
 use zksync_merkle_tree::NoVersionError;
@@ -24,6 +32,12 @@ mod metrics;
 #[cfg(test)]
 mod tests;
 
+/// Maximum number of L1 batches in a range for which range proofs can be requested.
+const MAX_RANGE_PROOFS_L1_BATCH_COUNT: u32 = 1_000;
+/// Maximum total number of proofs (i.e., the number of keys multiplied by the number of L1 batches in the range)
+/// that can be requested in a single range proofs request.
+const MAX_RANGE_PROOFS_ENTRY_COUNT: usize = 10_000;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct TreeProofsRequest {
     l1_batch_number: L1BatchNumber,
@@ -35,6 +49,37 @@ struct TreeProofsResponse {
     entries: Vec<TreeEntryWithProof>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeRangeProofsRequest {
+    from_l1_batch_number: L1BatchNumber,
+    to_l1_batch_number: L1BatchNumber,
+    hashed_keys: Vec<U256>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeRangeProofsResponse {
+    entries: Vec<TreeEntryRangeProof>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeCheckpointRequest {
+    l1_batch_number: L1BatchNumber,
+    /// Checkpoint name. Must be a single relative path component; it is resolved inside the checkpoints directory
+    /// configured on the server.
+    name: String,
+}
+
+/// Information about a tree checkpoint created via the tree API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeCheckpointInfo {
+    /// L1 batch number the checkpoint corresponds to.
+    pub l1_batch_number: L1BatchNumber,
+    /// Root hash of the tree at the L1 batch.
+    pub root_hash: H256,
+    /// Path to the checkpoint on the tree API server.
+    pub path: PathBuf,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TreeEntryWithProof {
     #[serde(default, skip_serializing_if = "H256::is_zero")]
@@ -77,10 +122,157 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Proofs for a tree entry at each L1 batch in a range.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeEntryRangeProof {
+    /// Entries with proofs ordered by the L1 batch number, starting from the first L1 batch in the range.
+    pub entries: Vec<TreeEntryWithProof>,
+}
+
+impl TreeEntryRangeProof {
+    /// Verifies proofs for all L1 batches in the range. `trusted_root_hashes` must contain root hashes
+    /// for each L1 batch in the range ordered by the L1 batch number.
+    pub fn verify(&self, key: U256, trusted_root_hashes: &[H256]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.entries.len() == trusted_root_hashes.len(),
+            "number of proofs ({}) differs from the number of root hashes ({})",
+            self.entries.len(),
+            trusted_root_hashes.len()
+        );
+        for (i, (entry, &root_hash)) in self.entries.iter().zip(trusted_root_hashes).enumerate() {
+            entry
+                .verify(key, root_hash)
+                .with_context(|| format!("invalid proof at position {i} in the range"))?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether the entry is the same for all L1 batches in the range. The result is only trustworthy
+    /// if the proof was [verified](Self::verify()).
+    pub fn is_unchanged(&self) -> bool {
+        let Some((first, rest)) = self.entries.split_first() else {
+            return true;
+        };
+        rest.iter()
+            .all(|entry| entry.value == first.value && entry.index == first.index)
+    }
+}
+
+/// Compact proof for multiple tree entries at the same L1 batch. Merkle paths of entries reference
+/// a deduplicated list of hashes, so that hashes shared by entries with common key prefixes are only transferred once.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TreeMultiProof {
+    /// Unique hashes referenced by Merkle paths of the entries.
+    pub hashes: Vec<H256>,
+    /// Entries in the same order as the requested keys.
+    pub entries: Vec<TreeMultiProofEntry>,
+}
+
+/// Entry in a [`TreeMultiProof`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeMultiProofEntry {
+    #[serde(default, skip_serializing_if = "H256::is_zero")]
+    pub value: H256,
+    #[serde(default, skip_serializing_if = "TreeEntryWithProof::is_zero")]
+    pub index: u64,
+    /// Merkle path in the root-to-leaf direction; each hash is specified as an index in [`TreeMultiProof::hashes`].
+    pub merkle_path: Vec<u32>,
+}
+
+impl TreeMultiProof {
+    fn new(entries: Vec<zksync_merkle_tree::TreeEntryWithProof>) -> Self {
+        let mut hashes = vec![];
+        let mut hash_indices = HashMap::new();
+        let mut multi_proof_entries = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let entry = TreeEntryWithProof::new(entry);
+            let merkle_path = entry
+                .merkle_path
+                .into_iter()
+                .map(|hash| {
+                    *hash_indices.entry(hash).or_insert_with(|| {
+                        hashes.push(hash);
+                        u32::try_from(hashes.len() - 1).expect("too many hashes in multi-proof")
+                    })
+                })
+                .collect();
+            multi_proof_entries.push(TreeMultiProofEntry {
+                value: entry.value,
+                index: entry.index,
+                merkle_path,
+            });
+        }
+        Self {
+            hashes,
+            entries: multi_proof_entries,
+        }
+    }
+
+    /// Expands this multi-proof into proofs for individual entries.
+    pub fn into_entries(self) -> anyhow::Result<Vec<TreeEntryWithProof>> {
+        let hashes = self.hashes;
+        self.entries
+            .into_iter()
+            .map(|entry| {
+                let merkle_path = entry
+                    .merkle_path
+                    .iter()
+                    .map(|&idx| {
+                        hashes
+                            .get(idx as usize)
+                            .copied()
+                            .with_context(|| format!("hash index {idx} is out of bounds"))
+                    })
+                    .collect::<anyhow::Result<_>>()?;
+                Ok(TreeEntryWithProof {
+                    value: entry.value,
+                    index: entry.index,
+                    merkle_path,
+                })
+            })
+            .collect()
+    }
+
+    /// Verifies this multi-proof for the specified keys (in the same order as they were requested).
+    pub fn verify(self, keys: &[U256], trusted_root_hash: H256) -> anyhow::Result<()> {
+        let entries = self.into_entries()?;
+        anyhow::ensure!(
+            entries.len() == keys.len(),
+            "number of entries in multi-proof ({}) differs from the number of keys ({})",
+            entries.len(),
+            keys.len()
+        );
+        for (entry, &key) in entries.iter().zip(keys) {
+            entry
+                .verify(key, trusted_root_hash)
+                .with_context(|| format!("invalid proof for key {key:#x}"))?;
+        }
+        Ok(())
+    }
+}
+
 /// Server-side tree API error.
 #[derive(Debug)]
 enum TreeApiServerError {
     NoTreeVersion(NoVersionError),
+    InvalidRequest(String),
+    Internal(anyhow::Error),
+}
+
+impl From<NoVersionError> for TreeApiServerError {
+    fn from(err: NoVersionError) -> Self {
+        Self::NoTreeVersion(err)
+    }
+}
+
+impl From<TreeApiServerError> for TreeApiError {
+    fn from(err: TreeApiServerError) -> Self {
+        match err {
+            TreeApiServerError::NoTreeVersion(err) => Self::NoVersion(err),
+            TreeApiServerError::InvalidRequest(message) => Self::InvalidRequest(message),
+            TreeApiServerError::Internal(err) => Self::Internal(err),
+        }
+    }
 }
 
 // Contains the same fields as `NoVersionError` and is serializable.
@@ -108,6 +300,11 @@ impl From<NoVersionErrorData> for NoVersionError {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct InvalidRequestData {
+    detail: String,
+}
+
 // Loosely conforms to HTTP Problem Details RFC: <https://datatracker.ietf.org/doc/html/rfc7807>
 #[derive(Debug, Serialize)]
 struct Problem<T> {
@@ -133,6 +330,24 @@ impl IntoResponse for TreeApiServerError {
                 };
                 (StatusCode::NOT_FOUND, headers, Json(body)).into_response()
             }
+            Self::InvalidRequest(detail) => {
+                let body = Problem {
+                    r#type: "/errors#invalid-request",
+                    title: "Invalid request",
+                    detail,
+                    data: serde_json::Map::new(),
+                };
+                (StatusCode::BAD_REQUEST, headers, Json(body)).into_response()
+            }
+            Self::Internal(err) => {
+                let body = Problem {
+                    r#type: "/errors#internal",
+                    title: "Internal error",
+                    detail: format!("{err:#}"),
+                    data: serde_json::Map::new(),
+                };
+                (StatusCode::INTERNAL_SERVER_ERROR, headers, Json(body)).into_response()
+            }
         }
     }
 }
@@ -145,6 +360,8 @@ pub enum TreeApiError {
     NoVersion(NoVersionError),
     #[error("tree API is temporarily unavailable")]
     NotReady(#[source] Option<anyhow::Error>),
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
     /// Catch-all variant for internal errors.
     #[error("internal error")]
     Internal(#[from] anyhow::Error),
@@ -175,6 +392,22 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains proofs for the specified `hashed_keys` at each L1 batch in the specified range.
+    /// The number of keys multiplied by the number of L1 batches in the range is bounded.
+    async fn get_range_proofs(
+        &self,
+        from_l1_batch_number: L1BatchNumber,
+        to_l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryRangeProof>, TreeApiError>;
+
+    /// Obtains a compact multi-proof for the specified `hashed_keys` at the specified tree version (= L1 batch number).
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, TreeApiError>;
 }
 
 /// In-memory client implementation.
@@ -202,6 +435,35 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady(None))
         }
     }
+
+    async fn get_range_proofs(
+        &self,
+        from_l1_batch_number: L1BatchNumber,
+        to_l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryRangeProof>, TreeApiError> {
+        if let Some(reader) = self.read() {
+            Ok(reader
+                .get_range_proofs_inner(from_l1_batch_number, to_l1_batch_number, hashed_keys)
+                .await?)
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
+
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, TreeApiError> {
+        if let Some(reader) = self.read() {
+            Ok(reader
+                .get_multi_proof_inner(l1_batch_number, hashed_keys)
+                .await?)
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -210,6 +472,9 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    range_proofs_url: String,
+    multi_proof_url: String,
+    checkpoints_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -224,7 +489,80 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            range_proofs_url: format!("{url_base}/proofs/range"),
+            multi_proof_url: format!("{url_base}/proofs/multi"),
+            checkpoints_url: format!("{url_base}/checkpoints"),
+        }
+    }
+
+    /// Creates a consistent checkpoint of the tree at the specified L1 batch. The checkpoint is created
+    /// in a subdirectory `name` of the checkpoints directory configured on the tree API server; this subdirectory
+    /// must not exist.
+    pub async fn create_checkpoint(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        name: String,
+    ) -> Result<TreeCheckpointInfo, TreeApiError> {
+        let request = TreeCheckpointRequest {
+            l1_batch_number,
+            name,
+        };
+        self.post(
+            &self.checkpoints_url,
+            &request,
+            &format!("tree checkpoint for L1 batch #{l1_batch_number}"),
+        )
+        .await
+    }
+
+    /// Converts problem responses returned by the server to the corresponding [`TreeApiError`]s.
+    async fn check_problem(
+        response: reqwest::Response,
+        request_description: &str,
+    ) -> Result<reqwest::Response, TreeApiError> {
+        let is_problem = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
+        if response.status() == StatusCode::NOT_FOUND && is_problem {
+            // Try to parse `NoVersionError` from the response body.
+            let problem_data: NoVersionErrorData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            return Err(TreeApiError::NoVersion(problem_data.into()));
+        }
+        if response.status() == StatusCode::BAD_REQUEST && is_problem {
+            let problem_data: InvalidRequestData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            return Err(TreeApiError::InvalidRequest(problem_data.detail));
         }
+
+        Ok(response.error_for_status().with_context(|| {
+            format!("requesting {request_description} returned non-OK response")
+        })?)
+    }
+
+    async fn post<Req: Serialize + Sync, Resp: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        request: &Req,
+        request_description: &str,
+    ) -> Result<Resp, TreeApiError> {
+        let response = self
+            .inner
+            .post(url)
+            .json(request)
+            .send()
+            .await
+            .map_err(|err| TreeApiError::for_request(err, request_description))?;
+        let response = Self::check_problem(response, request_description).await?;
+        Ok(response
+            .json()
+            .await
+            .with_context(|| format!("failed deserializing {request_description}"))?)
     }
 }
 
@@ -270,43 +608,59 @@ impl TreeApiClient for TreeApiHttpClient {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
-        let response = self
-            .inner
-            .post(&self.proofs_url)
-            .json(&TreeProofsRequest {
-                l1_batch_number,
-                hashed_keys,
-            })
-            .send()
-            .await
-            .map_err(|err| {
-                TreeApiError::for_request(
-                    err,
-                    format_args!("proofs for L1 batch #{l1_batch_number}"),
-                )
-            })?;
-
-        let is_problem = response
-            .headers()
-            .get(header::CONTENT_TYPE)
-            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
-        if response.status() == StatusCode::NOT_FOUND && is_problem {
-            // Try to parse `NoVersionError` from the response body.
-            let problem_data: NoVersionErrorData = response
-                .json()
-                .await
-                .context("failed parsing error response")?;
-            return Err(TreeApiError::NoVersion(problem_data.into()));
-        }
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        let response: TreeProofsResponse = self
+            .post(
+                &self.proofs_url,
+                &request,
+                &format!("proofs for L1 batch #{l1_batch_number}"),
+            )
+            .await?;
+        Ok(response.entries)
+    }
 
-        let response = response.error_for_status().with_context(|| {
-            format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
-        })?;
-        let response: TreeProofsResponse = response.json().await.with_context(|| {
-            format!("failed deserializing proofs for L1 batch #{l1_batch_number}")
-        })?;
+    async fn get_range_proofs(
+        &self,
+        from_l1_batch_number: L1BatchNumber,
+        to_l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryRangeProof>, TreeApiError> {
+        let request = TreeRangeProofsRequest {
+            from_l1_batch_number,
+            to_l1_batch_number,
+            hashed_keys,
+        };
+        let response: TreeRangeProofsResponse = self
+            .post(
+                &self.range_proofs_url,
+                &request,
+                &format!(
+                    "range proofs for L1 batches #{from_l1_batch_number}..=#{to_l1_batch_number}"
+                ),
+            )
+            .await?;
         Ok(response.entries)
     }
+
+    async fn get_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, TreeApiError> {
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        self.post(
+            &self.multi_proof_url,
+            &request,
+            &format!("multi-proof for L1 batch #{l1_batch_number}"),
+        )
+        .await
+    }
 }
 
 impl AsyncTreeReader {
@@ -343,17 +697,126 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
+    async fn get_range_proofs_inner(
+        &self,
+        from_l1_batch_number: L1BatchNumber,
+        to_l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryRangeProof>, TreeApiServerError> {
+        if from_l1_batch_number > to_l1_batch_number {
+            return Err(TreeApiServerError::InvalidRequest(format!(
+                "L1 batch range #{from_l1_batch_number}..=#{to_l1_batch_number} is empty"
+            )));
+        }
+        let l1_batch_count = to_l1_batch_number.0 - from_l1_batch_number.0 + 1;
+        if l1_batch_count > MAX_RANGE_PROOFS_L1_BATCH_COUNT {
+            return Err(TreeApiServerError::InvalidRequest(format!(
+                "L1 batch range #{from_l1_batch_number}..=#{to_l1_batch_number} contains {l1_batch_count} batches; \
+                 at most {MAX_RANGE_PROOFS_L1_BATCH_COUNT} batches are allowed"
+            )));
+        }
+
+        let entry_count = hashed_keys.len().saturating_mul(l1_batch_count as usize);
+        if entry_count > MAX_RANGE_PROOFS_ENTRY_COUNT {
+            return Err(TreeApiServerError::InvalidRequest(format!(
+                "requested {} keys for {l1_batch_count} L1 batches, which results in {entry_count} proofs; \
+                 at most {MAX_RANGE_PROOFS_ENTRY_COUNT} proofs are allowed",
+                hashed_keys.len()
+            )));
+        }
+
+        let key_count = hashed_keys.len();
+        let proofs_by_batch = self
+            .clone()
+            .entries_with_proofs_for_range(from_l1_batch_number..=to_l1_batch_number, hashed_keys)
+            .await?;
+        let mut entries: Vec<_> = (0..key_count)
+            .map(|_| TreeEntryRangeProof {
+                entries: Vec::with_capacity(l1_batch_count as usize),
+            })
+            .collect();
+        for batch_proofs in proofs_by_batch {
+            for (range_proof, proof) in entries.iter_mut().zip(batch_proofs) {
+                range_proof.entries.push(TreeEntryWithProof::new(proof));
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn get_range_proofs_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeRangeProofsRequest>,
+    ) -> Result<Json<TreeRangeProofsResponse>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetRangeProofs].start();
+        let entries = this
+            .get_range_proofs_inner(
+                request.from_l1_batch_number,
+                request.to_l1_batch_number,
+                request.hashed_keys,
+            )
+            .await?;
+        let response = TreeRangeProofsResponse { entries };
+        latency.observe();
+        Ok(Json(response))
+    }
+
+    async fn get_multi_proof_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiProof, TreeApiServerError> {
+        let proofs = self
+            .clone()
+            .entries_with_proofs(l1_batch_number, hashed_keys)
+            .await?;
+        Ok(TreeMultiProof::new(proofs))
+    }
+
+    async fn get_multi_proof_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeProofsRequest>,
+    ) -> Result<Json<TreeMultiProof>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetMultiProof].start();
+        let multi_proof = this
+            .get_multi_proof_inner(request.l1_batch_number, request.hashed_keys)
+            .await?;
+        latency.observe();
+        Ok(Json(multi_proof))
+    }
+
     async fn create_api_server(
         self,
         bind_address: &SocketAddr,
+        checkpoints_dir: Option<PathBuf>,
         mut stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<MerkleTreeServer> {
         tracing::debug!("Starting Merkle tree API server on {bind_address}");
 
+        let checkpoints = CheckpointsState {
+            reader: self.clone(),
+            dir: checkpoints_dir.map(Arc::from),
+            lock: Arc::default(),
+        };
+        let checkpoints_router = Router::new()
+            .route(
+                "/checkpoints",
+                routing::post(CheckpointsState::create_checkpoint_handler),
+            )
+            .with_state(checkpoints);
+
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
-            .with_state(self);
+            .route(
+                "/proofs/range",
+                routing::post(Self::get_range_proofs_handler),
+            )
+            .route(
+                "/proofs/multi",
+                routing::post(Self::get_multi_proof_handler),
+            )
+            .with_state(self)
+            .merge(checkpoints_router);
 
         let listener = tokio::net::TcpListener::bind(bind_address)
             .await
@@ -383,18 +846,106 @@ impl AsyncTreeReader {
     }
 
     /// Runs the HTTP API server.
+    ///
+    /// Tree checkpoints can only be created via the API if `checkpoints_dir` is specified.
     pub async fn run_api_server(
         self,
         bind_address: SocketAddr,
+        checkpoints_dir: Option<PathBuf>,
         stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
-        self.create_api_server(&bind_address, stop_receiver)
+        self.create_api_server(&bind_address, checkpoints_dir, stop_receiver)
             .await?
             .run()
             .await
     }
 }
 
+/// State for the checkpoint creation endpoint.
+#[derive(Debug, Clone)]
+struct CheckpointsState {
+    reader: AsyncTreeReader,
+    /// Directory to create checkpoints in. If not set, checkpoint creation is disabled.
+    dir: Option<Arc<Path>>,
+    /// Ensures that at most one checkpoint is being created at a time.
+    lock: Arc<Mutex<()>>,
+}
+
+impl CheckpointsState {
+    /// Resolves the checkpoint path for a client-provided `name`, which must consist of a single normal path component
+    /// so that the checkpoint cannot be created outside `dir`.
+    fn resolve_checkpoint_path(dir: &Path, name: &str) -> Result<PathBuf, TreeApiServerError> {
+        let mut components = Path::new(name).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(_)), None) => Ok(dir.join(name)),
+            _ => Err(TreeApiServerError::InvalidRequest(format!(
+                "checkpoint name `{name}` must be a single relative path component"
+            ))),
+        }
+    }
+
+    async fn create_checkpoint_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeCheckpointRequest>,
+    ) -> Result<Json<TreeCheckpointInfo>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::CreateCheckpoint].start();
+        let l1_batch_number = request.l1_batch_number;
+        let Some(dir) = &this.dir else {
+            return Err(TreeApiServerError::InvalidRequest(
+                "checkpoint creation is disabled on the server".to_owned(),
+            ));
+        };
+        let path = Self::resolve_checkpoint_path(dir, &request.name)?;
+        let Ok(_guard) = this.lock.try_lock() else {
+            return Err(TreeApiServerError::InvalidRequest(
+                "another checkpoint is being created".to_owned(),
+            ));
+        };
+        if path.exists() {
+            return Err(TreeApiServerError::InvalidRequest(format!(
+                "checkpoint `{}` already exists",
+                request.name
+            )));
+        }
+
+        let info = this.reader.clone().info().await;
+        let min_l1_batch_number = info.min_l1_batch_number.unwrap_or(L1BatchNumber(0));
+        if l1_batch_number >= info.next_l1_batch_number || l1_batch_number < min_l1_batch_number {
+            return Err(TreeApiServerError::NoTreeVersion(NoVersionError {
+                missing_version: l1_batch_number.0.into(),
+                version_count: info.next_l1_batch_number.0.into(),
+            }));
+        }
+
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("failed creating checkpoints directory `{}`", dir.display()))
+            .map_err(TreeApiServerError::Internal)?;
+        tracing::info!(
+            "Creating tree checkpoint for L1 batch #{l1_batch_number} at `{}`",
+            path.display()
+        );
+        let root_hash = this
+            .reader
+            .create_checkpoint(path.clone(), l1_batch_number)
+            .await
+            .map_err(|err| {
+                tracing::warn!("Failed creating tree checkpoint: {err:#}");
+                TreeApiServerError::Internal(err)
+            })?;
+        tracing::info!(
+            "Created tree checkpoint for L1 batch #{l1_batch_number} at `{}` with root hash {root_hash:?}",
+            path.display()
+        );
+        latency.observe();
+        Ok(Json(TreeCheckpointInfo {
+            l1_batch_number,
+            root_hash,
+            path,
+        }))
+    }
+}
+
 /// `axum`-powered REST server for Merkle tree API.
 #[must_use = "Server must be `run()`"]
 struct MerkleTreeServer {
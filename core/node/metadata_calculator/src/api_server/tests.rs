@@ -8,7 +8,7 @@ use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpSocket},
 };
-use zksync_dal::{ConnectionPool, Core};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
 
 use super::*;
 use crate::tests::{gen_storage_logs, reset_db_state, run_calculator, setup_calculator};
@@ -24,12 +24,18 @@ async fn merkle_tree_api() {
     let tree_reader = calculator.tree_reader();
     let calculator_task = tokio::spawn(run_calculator(calculator));
 
+    let checkpoint_dir = TempDir::new().unwrap();
+    let checkpoints_path = checkpoint_dir.path().join("checkpoints");
     let (stop_sender, stop_receiver) = watch::channel(false);
     let api_server = tree_reader
         .wait()
         .await
         .unwrap()
-        .create_api_server(&api_addr, stop_receiver.clone())
+        .create_api_server(
+            &api_addr,
+            Some(checkpoints_path.clone()),
+            stop_receiver.clone(),
+        )
         .await
         .unwrap();
     let local_addr = *api_server.local_addr();
@@ -52,16 +58,115 @@ async fn merkle_tree_api() {
     hashed_keys.extend((0_u8..10).map(|byte| U256::from_big_endian(&[byte; 32])));
 
     let proofs = api_client
-        .get_proofs(L1BatchNumber(5), hashed_keys)
+        .get_proofs(L1BatchNumber(5), hashed_keys.clone())
         .await
         .unwrap();
     assert_eq!(proofs.len(), 20);
-    for (i, proof) in proofs.into_iter().enumerate() {
+    for (i, proof) in proofs.iter().enumerate() {
         let should_be_present = i < 10;
         assert_eq!(proof.index == 0, !should_be_present);
         assert!(!proof.merkle_path.is_empty());
     }
 
+    let mut storage = pool.connection().await.unwrap();
+    let mut root_hashes = vec![];
+    for number in 1..=5 {
+        let root_hash = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(L1BatchNumber(number))
+            .await
+            .unwrap()
+            .expect("no root hash");
+        root_hashes.push(root_hash);
+    }
+    drop(storage);
+
+    let multi_proof = api_client
+        .get_multi_proof(L1BatchNumber(5), hashed_keys.clone())
+        .await
+        .unwrap();
+    let total_path_len: usize = proofs.iter().map(|proof| proof.merkle_path.len()).sum();
+    assert!(multi_proof.hashes.len() < total_path_len);
+    multi_proof
+        .clone()
+        .verify(&hashed_keys, root_hashes[4])
+        .unwrap();
+    let expanded_proofs = multi_proof.into_entries().unwrap();
+    for (expanded, proof) in expanded_proofs.iter().zip(&proofs) {
+        assert_eq!(expanded.value, proof.value);
+        assert_eq!(expanded.index, proof.index);
+        assert_eq!(expanded.merkle_path, proof.merkle_path);
+    }
+
+    let range_proofs = api_client
+        .get_range_proofs(L1BatchNumber(1), L1BatchNumber(5), hashed_keys.clone())
+        .await
+        .unwrap();
+    assert_eq!(range_proofs.len(), 20);
+    for (i, (range_proof, &key)) in range_proofs.iter().zip(&hashed_keys).enumerate() {
+        assert_eq!(range_proof.entries.len(), 5);
+        range_proof.verify(key, &root_hashes).unwrap();
+        range_proof.verify(key, &root_hashes[..4]).unwrap_err();
+        // The first 2 keys are written in L1 batch #1, the other existing keys in later batches.
+        let should_be_unchanged = i < 2 || i >= 10;
+        assert_eq!(
+            range_proof.is_unchanged(),
+            should_be_unchanged,
+            "{range_proof:?}"
+        );
+    }
+
+    let too_many_keys: Vec<_> = (0..=MAX_RANGE_PROOFS_ENTRY_COUNT / 5)
+        .map(U256::from)
+        .collect();
+    let err = api_client
+        .get_range_proofs(L1BatchNumber(1), L1BatchNumber(5), too_many_keys)
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::InvalidRequest(_));
+    let err = api_client
+        .get_range_proofs(L1BatchNumber(5), L1BatchNumber(1), hashed_keys)
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::InvalidRequest(_));
+
+    let checkpoint = api_client
+        .create_checkpoint(L1BatchNumber(5), "checkpoint".to_owned())
+        .await
+        .unwrap();
+    assert_eq!(checkpoint.l1_batch_number, L1BatchNumber(5));
+    assert_eq!(checkpoint.root_hash, root_hashes[4]);
+    assert_eq!(checkpoint.path, checkpoints_path.join("checkpoint"));
+    assert!(checkpoint.path.join("CURRENT").exists());
+
+    let err = api_client
+        .create_checkpoint(L1BatchNumber(5), "checkpoint".to_owned())
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::InvalidRequest(_));
+    let err = api_client
+        .create_checkpoint(L1BatchNumber(10), "other".to_owned())
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::NoVersion(_));
+
+    let outside_path = checkpoint_dir.path().join("outside");
+    let invalid_names = [
+        String::new(),
+        ".".to_owned(),
+        "../outside".to_owned(),
+        "nested/checkpoint".to_owned(),
+        outside_path.to_str().unwrap().to_owned(),
+    ];
+    for name in invalid_names {
+        let err = api_client
+            .create_checkpoint(L1BatchNumber(5), name.clone())
+            .await
+            .unwrap_err();
+        assert_matches!(err, TreeApiError::InvalidRequest(_), "{name}");
+    }
+    assert!(!outside_path.exists());
+
     let err = api_client
         .get_proofs(L1BatchNumber(10), vec![])
         .await
@@ -77,6 +182,40 @@ async fn merkle_tree_api() {
     api_server_task.await.unwrap().unwrap();
 }
 
+#[tokio::test]
+async fn checkpoints_are_disabled_without_configured_dir() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    let api_addr = (Ipv4Addr::LOCALHOST, 0).into();
+
+    reset_db_state(&pool, 1).await;
+    let tree_reader = calculator.tree_reader();
+    let calculator_task = tokio::spawn(run_calculator(calculator));
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let api_server = tree_reader
+        .wait()
+        .await
+        .unwrap()
+        .create_api_server(&api_addr, None, stop_receiver)
+        .await
+        .unwrap();
+    let local_addr = *api_server.local_addr();
+    let api_server_task = tokio::spawn(api_server.run());
+    let api_client = TreeApiHttpClient::new(&format!("http://{local_addr}"));
+    calculator_task.await.unwrap();
+
+    let err = api_client
+        .create_checkpoint(L1BatchNumber(1), "checkpoint".to_owned())
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::InvalidRequest(_));
+
+    stop_sender.send_replace(true);
+    api_server_task.await.unwrap().unwrap();
+}
+
 #[tokio::test]
 async fn api_client_connection_error() {
     // Use an address that will definitely fail on a timeout.
@@ -3,7 +3,8 @@
 use std::{
     collections::{BTreeMap, HashSet},
     future::Future,
-    path::Path,
+    ops,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
@@ -366,6 +367,34 @@ impl AsyncTreeReader {
             .await
             .unwrap()
     }
+
+    /// Creates a consistent checkpoint of the tree at the specified L1 batch in `path`. Returns the root hash of the tree
+    /// at this L1 batch.
+    pub async fn create_checkpoint(
+        self,
+        path: PathBuf,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<H256> {
+        tokio::task::spawn_blocking(move || self.inner.create_checkpoint(&path, l1_batch_number))
+            .await
+            .context("tree checkpoint creation panicked")?
+    }
+
+    /// Returns entries with proofs for the specified keys at each L1 batch in the range, ordered by the L1 batch number.
+    pub async fn entries_with_proofs_for_range(
+        self,
+        l1_batch_numbers: ops::RangeInclusive<L1BatchNumber>,
+        keys: Vec<Key>,
+    ) -> Result<Vec<Vec<TreeEntryWithProof>>, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            let (start, end) = l1_batch_numbers.into_inner();
+            (start.0..=end.0)
+                .map(|number| self.inner.entries_with_proofs(L1BatchNumber(number), &keys))
+                .collect()
+        })
+        .await
+        .unwrap()
+    }
 }
 
 /// Version of async tree reader that holds a weak reference to RocksDB. Used in [`MerkleTreeHealthCheck`].
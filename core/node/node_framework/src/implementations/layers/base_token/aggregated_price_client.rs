@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_external_price_api::aggregated_client::AggregatedPriceAPIClient;
+
+use crate::{
+    implementations::resources::price_api_client::PriceAPIClientResource,
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for `AggregatedPriceAPIClient`
+///
+/// Responsible for inserting a resource with a client aggregating base token prices from multiple sources
+/// to be used by the `BaseTokenRatioPersister`.
+#[derive(Debug)]
+pub struct AggregatedPriceClientLayer {
+    config: ExternalPriceApiClientConfig,
+}
+
+impl AggregatedPriceClientLayer {
+    /// Identifier of used client type.
+    /// Can be used to choose the layer for the client based on configuration variables.
+    pub const CLIENT_NAME: &'static str = "aggregated";
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub price_api_client: PriceAPIClientResource,
+}
+
+impl AggregatedPriceClientLayer {
+    pub fn new(config: ExternalPriceApiClientConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for AggregatedPriceClientLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "aggregated_price_api_client"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client = AggregatedPriceAPIClient::new(self.config)
+            .context("failed creating aggregated price API client")?;
+        let client = Arc::new(client);
+
+        Ok(Output {
+            price_api_client: client.into(),
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_external_price_api::json_path_api::JsonPathPriceAPIClient;
+
+use crate::{
+    implementations::resources::price_api_client::PriceAPIClientResource,
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for `JsonPathPriceAPIClient`
+///
+/// Responsible for inserting a resource with a client to get base token prices from a generic JSON HTTP API
+/// to be used by the `BaseTokenRatioPersister`.
+#[derive(Debug)]
+pub struct JsonPathPriceClientLayer {
+    config: ExternalPriceApiClientConfig,
+}
+
+impl JsonPathPriceClientLayer {
+    /// Identifier of used client type.
+    /// Can be used to choose the layer for the client based on configuration variables.
+    pub const CLIENT_NAME: &'static str = "json_path";
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub price_api_client: PriceAPIClientResource,
+}
+
+impl JsonPathPriceClientLayer {
+    pub fn new(config: ExternalPriceApiClientConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for JsonPathPriceClientLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "json_path_price_api_client"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client = Arc::new(JsonPathPriceAPIClient::new(self.config));
+
+        Ok(Output {
+            price_api_client: client.into(),
+        })
+    }
+}
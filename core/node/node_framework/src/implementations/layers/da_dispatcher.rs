@@ -1,4 +1,4 @@
-use zksync_config::configs::{chain::StateKeeperConfig, da_dispatcher::DADispatcherConfig};
+use zksync_config::configs::da_dispatcher::DADispatcherConfig;
 use zksync_da_dispatcher::DataAvailabilityDispatcher;
 
 use crate::{
@@ -15,7 +15,6 @@ use crate::{
 /// A layer that wires the data availability dispatcher task.
 #[derive(Debug)]
 pub struct DataAvailabilityDispatcherLayer {
-    state_keeper_config: StateKeeperConfig,
     da_config: DADispatcherConfig,
 }
 
@@ -34,11 +33,8 @@ pub struct Output {
 }
 
 impl DataAvailabilityDispatcherLayer {
-    pub fn new(state_keeper_config: StateKeeperConfig, da_config: DADispatcherConfig) -> Self {
-        Self {
-            state_keeper_config,
-            da_config,
-        }
+    pub fn new(da_config: DADispatcherConfig) -> Self {
+        Self { da_config }
     }
 }
 
@@ -56,13 +52,11 @@ impl WiringLayer for DataAvailabilityDispatcherLayer {
         let master_pool = input.master_pool.get_custom(2).await?;
         let da_client = input.da_client.0;
 
-        if let Some(limit) = da_client.blob_size_limit() {
-            if self.state_keeper_config.max_pubdata_per_batch > limit as u64 {
-                return Err(WiringError::Configuration(format!(
-                    "Max pubdata per batch is greater than the blob size limit: {} > {}",
-                    self.state_keeper_config.max_pubdata_per_batch, limit
-                )));
-            }
+        // Pubdata exceeding the blob size limit is split into several blobs by the dispatcher.
+        if da_client.blob_size_limit() == Some(0) {
+            return Err(WiringError::Configuration(
+                "DA client blob size limit must be positive".to_owned(),
+            ));
         }
 
         let da_dispatcher_task =
@@ -11,6 +11,7 @@ use crate::{
             BoundEthInterfaceResource,
         },
         gas_adjuster::GasAdjusterResource,
+        healthcheck::AppHealthCheckResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
     },
     service::StopReceiver,
@@ -32,6 +33,7 @@ use crate::{
 /// - `BoundEthInterfaceForBlobsResource` (optional)
 /// - `TxParamsResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
+/// - `AppHealthCheckResource` (adds a health check)
 ///
 /// ## Adds tasks
 ///
@@ -52,6 +54,8 @@ pub struct Input {
     pub gas_adjuster: GasAdjusterResource,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -105,6 +109,12 @@ impl WiringLayer for EthTxManagerLayer {
             .insert(Box::new(FailedL1TransactionChecker { pool: replica_pool }))
             .await;
 
+        input
+            .app_health
+            .0
+            .insert_component(eth_tx_manager.health_check().clone())
+            .map_err(WiringError::internal)?;
+
         Ok(Output { eth_tx_manager })
     }
 }
@@ -5,6 +5,7 @@ use zksync_eth_watch::{EthHttpQueryClient, EthWatch};
 use crate::{
     implementations::resources::{
         eth_interface::EthInterfaceResource,
+        eth_watch::EthWatchEventProcessorsResource,
         pools::{MasterPool, PoolResource},
     },
     service::StopReceiver,
@@ -17,6 +18,9 @@ use crate::{
 ///
 /// Responsible for initializing and running of [`EthWatch`] component, that polls the Ethereum node for the relevant events,
 /// such as priority operations (aka L1 transactions), protocol upgrades etc.
+///
+/// Custom event processors registered via [`EthWatchEventProcessorsResource`] are added to the watcher
+/// when its task starts.
 #[derive(Debug)]
 pub struct EthWatchLayer {
     eth_watch_config: EthWatchConfig,
@@ -28,13 +32,15 @@ pub struct EthWatchLayer {
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub eth_client: EthInterfaceResource,
+    #[context(default)]
+    pub event_processors: EthWatchEventProcessorsResource,
 }
 
 #[derive(Debug, IntoContext)]
 #[context(crate = crate)]
 pub struct Output {
     #[context(task)]
-    pub eth_watch: EthWatch,
+    pub eth_watch: EthWatchTask,
 }
 
 impl EthWatchLayer {
@@ -80,17 +86,33 @@ impl WiringLayer for EthWatchLayer {
         )
         .await?;
 
-        Ok(Output { eth_watch })
+        Ok(Output {
+            eth_watch: EthWatchTask {
+                eth_watch,
+                event_processors: input.event_processors,
+            },
+        })
     }
 }
 
+/// Task running [`EthWatch`] together with custom event processors.
+#[derive(Debug)]
+pub struct EthWatchTask {
+    eth_watch: EthWatch,
+    event_processors: EthWatchEventProcessorsResource,
+}
+
 #[async_trait::async_trait]
-impl Task for EthWatch {
+impl Task for EthWatchTask {
     fn id(&self) -> TaskId {
         "eth_watch".into()
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        (*self).run(stop_receiver.0).await
+        let mut eth_watch = self.eth_watch;
+        for processor in self.event_processors.take() {
+            eth_watch.add_event_processor(processor)?;
+        }
+        eth_watch.run(stop_receiver.0).await
     }
 }
@@ -1,5 +1,6 @@
 use std::{
     net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
     sync::Arc,
     time::Duration,
 };
@@ -123,6 +124,7 @@ impl WiringLayer for MetadataCalculatorLayer {
             let tree_reader = metadata_calculator.tree_reader();
             TreeApiTask {
                 bind_addr,
+                checkpoints_dir: tree_api_config.checkpoints_dir,
                 tree_reader,
             }
         });
@@ -173,6 +175,7 @@ impl Task for MetadataCalculator {
 #[derive(Debug)]
 pub struct TreeApiTask {
     bind_addr: SocketAddr,
+    checkpoints_dir: Option<PathBuf>,
     tree_reader: LazyAsyncTreeReader,
 }
 
@@ -184,7 +187,9 @@ impl Task for TreeApiTask {
 
     async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
         if let Some(reader) = self.tree_reader.wait().await {
-            reader.run_api_server(self.bind_addr, stop_receiver.0).await
+            reader
+                .run_api_server(self.bind_addr, self.checkpoints_dir, stop_receiver.0)
+                .await
         } else {
             // Tree is dropped before initialized, e.g. because the node is getting shut down.
             // We don't want to treat this as an error since it could mask the real shutdown cause in logs etc.
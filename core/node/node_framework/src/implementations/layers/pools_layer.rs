@@ -1,8 +1,12 @@
+use zksync_circuit_breaker::replication_lag::ReplicaSetMonitor;
 use zksync_config::configs::{DatabaseSecrets, PostgresConfig};
 use zksync_dal::{ConnectionPool, Core};
+use zksync_db_connection::replicas::ReplicaSet;
 
 use crate::{
     implementations::resources::pools::{MasterPool, PoolResource, ProverPool, ReplicaPool},
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     IntoContext,
 };
@@ -68,6 +72,10 @@ impl PoolsLayerBuilder {
 /// - `PoolResource::<MasterPool>` (if master pool is enabled)
 /// - `PoolResource::<ReplicaPool>` (if replica pool is enabled)
 /// - `PoolResource::<ProverPool>` (if prover pool is enabled)
+///
+/// ## Adds tasks
+///
+/// - `ReplicaSetMonitorTask` (if replica pool is enabled and additional replicas are configured)
 #[derive(Debug)]
 pub struct PoolsLayer {
     config: PostgresConfig,
@@ -83,6 +91,8 @@ pub struct Output {
     pub master_pool: Option<PoolResource<MasterPool>>,
     pub replica_pool: Option<PoolResource<ReplicaPool>>,
     pub prover_pool: Option<PoolResource<ProverPool>>,
+    #[context(task)]
+    pub replica_set_monitor: Option<ReplicaSetMonitorTask>,
 }
 
 #[async_trait::async_trait]
@@ -124,15 +134,29 @@ impl WiringLayer for PoolsLayer {
             None
         };
 
+        let mut replica_set_monitor = None;
         let replica_pool = if self.with_replica {
             // We're most interested in setting acquire / statement timeouts for the API server, which puts the most load
             // on Postgres.
-            Some(PoolResource::<ReplicaPool>::new(
+            let mut replica_pool = PoolResource::<ReplicaPool>::new(
                 self.secrets.replica_url()?,
                 self.config.max_connections()?,
                 self.config.statement_timeout(),
                 self.config.acquire_timeout(),
-            ))
+            );
+            if !self.secrets.server_replica_urls.is_empty() {
+                let replicas = ReplicaSet::new(
+                    self.secrets.server_replica_urls.clone(),
+                    self.config.max_replica_lag(),
+                );
+                replica_pool = replica_pool.with_replicas(replicas);
+                let monitor = ReplicaSetMonitor::new(
+                    replica_pool.get_singleton().await?,
+                    self.config.replica_health_check_interval(),
+                )?;
+                replica_set_monitor = Some(ReplicaSetMonitorTask(monitor));
+            }
+            Some(replica_pool)
         } else {
             None
         };
@@ -152,6 +176,22 @@ impl WiringLayer for PoolsLayer {
             master_pool,
             replica_pool,
             prover_pool,
+            replica_set_monitor,
         })
     }
 }
+
+/// Task updating health and replication lag statuses of additional read-only replicas.
+#[derive(Debug)]
+pub struct ReplicaSetMonitorTask(ReplicaSetMonitor);
+
+#[async_trait::async_trait]
+impl Task for ReplicaSetMonitorTask {
+    fn id(&self) -> TaskId {
+        "replica_set_monitor".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
+use zksync_node_db_pruner::{DbPruner, DbPrunerConfig, HistoryPruningConfig};
 
 use crate::{
     implementations::resources::{
@@ -19,6 +19,8 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    prune_l1_batches: bool,
+    history_pruning: Option<HistoryPruningConfig>,
 }
 
 #[derive(Debug, FromContext)]
@@ -46,8 +48,22 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            prune_l1_batches: true,
+            history_pruning: None,
         }
     }
+
+    /// Enables or disables pruning of L1 batches. L1 batch pruning is enabled by default.
+    pub fn with_l1_batch_pruning(mut self, enabled: bool) -> Self {
+        self.prune_l1_batches = enabled;
+        self
+    }
+
+    /// Enables history pruning with the specified config.
+    pub fn with_history_pruning(mut self, config: HistoryPruningConfig) -> Self {
+        self.history_pruning = Some(config);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -67,6 +83,8 @@ impl WiringLayer for PruningLayer {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                prune_l1_batches: self.prune_l1_batches,
+                history_pruning: self.history_pruning,
             },
             main_pool,
         );
@@ -1,6 +1,8 @@
 use anyhow::Context;
+use zksync_config::configs::eth_sender::EthClientConfig;
+use zksync_eth_client::clients::MultiProviderClient;
 use zksync_types::{settlement::SettlementMode, url::SensitiveUrl, L2ChainId, SLChainId};
-use zksync_web3_decl::client::Client;
+use zksync_web3_decl::client::{Client, DynClient, L1};
 
 use crate::{
     implementations::resources::eth_interface::{EthInterfaceResource, L2InterfaceResource},
@@ -13,6 +15,8 @@ use crate::{
 pub struct QueryEthClientLayer {
     chain_id: SLChainId,
     web3_url: SensitiveUrl,
+    fallback_web3_urls: Vec<SensitiveUrl>,
+    client_config: EthClientConfig,
     settlement_mode: SettlementMode,
 }
 
@@ -25,9 +29,58 @@ impl QueryEthClientLayer {
         Self {
             chain_id,
             web3_url,
+            fallback_web3_urls: vec![],
+            client_config: EthClientConfig::default(),
             settlement_mode,
         }
     }
+
+    /// Adds fallback L1 providers. If any are specified, the L1 client fails over to them if the main provider is unhealthy.
+    pub fn with_fallback_urls(mut self, fallback_web3_urls: Vec<SensitiveUrl>) -> Self {
+        self.fallback_web3_urls = fallback_web3_urls;
+        self
+    }
+
+    /// Sets the L1 client config (e.g., the quorum for safety-critical reads).
+    pub fn with_client_config(mut self, client_config: EthClientConfig) -> Self {
+        self.client_config = client_config;
+        self
+    }
+
+    fn create_l1_client(&self) -> anyhow::Result<Box<DynClient<L1>>> {
+        let network = L1::from(self.chain_id);
+        if self.fallback_web3_urls.is_empty() && self.client_config.quorum <= 1 {
+            let client = Client::http(self.web3_url.clone())
+                .context("Client::new()")?
+                .for_network(network)
+                .build();
+            return Ok(Box::new(client));
+        }
+
+        let provider_count = self.fallback_web3_urls.len() + 1;
+        anyhow::ensure!(
+            self.client_config.quorum <= provider_count,
+            "L1 client quorum ({}) exceeds the number of L1 providers ({provider_count})",
+            self.client_config.quorum
+        );
+        let mut builder = MultiProviderClient::builder(network)
+            .quorum(self.client_config.quorum)
+            .health_half_life(self.client_config.health_recovery_half_life())
+            .quorum_request_timeout(self.client_config.quorum_request_timeout());
+        for url in [&self.web3_url].into_iter().chain(&self.fallback_web3_urls) {
+            let client = Client::http(url.clone())
+                .context("Client::new()")?
+                .for_network(network)
+                .build();
+            builder = builder.provider(Box::new(client));
+        }
+        let client = builder.build().context("MultiProviderClient::build()")?;
+        tracing::info!(
+            "Using {provider_count} L1 providers with quorum {}",
+            self.client_config.quorum
+        );
+        Ok(Box::new(client))
+    }
 }
 
 #[derive(Debug, IntoContext)]
@@ -49,12 +102,7 @@ impl WiringLayer for QueryEthClientLayer {
     async fn wire(self, _input: Self::Input) -> Result<Output, WiringError> {
         // Both the L1 and L2 client have the same URL, but provide different type guarantees.
         Ok(Output {
-            query_client_l1: EthInterfaceResource(Box::new(
-                Client::http(self.web3_url.clone())
-                    .context("Client::new()")?
-                    .for_network(self.chain_id.into())
-                    .build(),
-            )),
+            query_client_l1: EthInterfaceResource(self.create_l1_client()?),
             query_client_l2: if self.settlement_mode.is_gateway() {
                 Some(L2InterfaceResource(Box::new(
                     Client::http(self.web3_url.clone())
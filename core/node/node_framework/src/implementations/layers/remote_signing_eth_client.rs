@@ -0,0 +1,115 @@
+use anyhow::Context as _;
+use zksync_config::{
+    configs::{eth_sender::RemoteSignerConfig, ContractsConfig},
+    EthConfig,
+};
+use zksync_eth_client::clients::RemoteSigningClient;
+use zksync_eth_signer::RemoteSigner;
+use zksync_types::{Address, SLChainId};
+
+use crate::{
+    implementations::resources::eth_interface::{
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource, EthInterfaceResource,
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for [`RemoteSigningClient`]. An alternative to [`PKSigningEthClientLayer`] that doesn't require
+/// operator private keys to be present in the node config.
+///
+/// [`PKSigningEthClientLayer`]: super::pk_signing_eth_client::PKSigningEthClientLayer
+#[derive(Debug)]
+pub struct RemoteSigningEthClientLayer {
+    eth_sender_config: EthConfig,
+    contracts_config: ContractsConfig,
+    sl_chain_id: SLChainId,
+    remote_signer_config: RemoteSignerConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub eth_client: EthInterfaceResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub signing_client: BoundEthInterfaceResource,
+    /// Only provided if the blob operator address is provided to the layer.
+    pub signing_client_for_blobs: Option<BoundEthInterfaceForBlobsResource>,
+}
+
+impl RemoteSigningEthClientLayer {
+    pub fn new(
+        eth_sender_config: EthConfig,
+        contracts_config: ContractsConfig,
+        sl_chain_id: SLChainId,
+        remote_signer_config: RemoteSignerConfig,
+    ) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+            sl_chain_id,
+            remote_signer_config,
+        }
+    }
+
+    fn create_signer(&self, address: Address) -> anyhow::Result<RemoteSigner> {
+        RemoteSigningClient::create_signer(&self.remote_signer_config, address)
+            .context("failed creating remote signer")
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for RemoteSigningEthClientLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "remote_signing_eth_client_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let gas_adjuster_config = self
+            .eth_sender_config
+            .gas_adjuster
+            .as_ref()
+            .context("gas_adjuster config is missing")?;
+        let EthInterfaceResource(query_client) = input.eth_client;
+
+        let signer = self.create_signer(self.remote_signer_config.operator_address)?;
+        let signing_client = RemoteSigningClient::new_raw(
+            signer,
+            self.contracts_config.diamond_proxy_addr,
+            gas_adjuster_config.default_priority_fee_per_gas,
+            self.sl_chain_id,
+            query_client.clone(),
+        );
+        let signing_client = BoundEthInterfaceResource(Box::new(signing_client));
+
+        let signing_client_for_blobs = self
+            .remote_signer_config
+            .blob_operator_address
+            .map(|address| {
+                let signer = self.create_signer(address)?;
+                let signing_client_for_blobs = RemoteSigningClient::new_raw(
+                    signer,
+                    self.contracts_config.diamond_proxy_addr,
+                    gas_adjuster_config.default_priority_fee_per_gas,
+                    self.sl_chain_id,
+                    query_client,
+                );
+                anyhow::Ok(BoundEthInterfaceForBlobsResource(Box::new(
+                    signing_client_for_blobs,
+                )))
+            })
+            .transpose()?;
+
+        Ok(Output {
+            signing_client,
+            signing_client_for_blobs,
+        })
+    }
+}
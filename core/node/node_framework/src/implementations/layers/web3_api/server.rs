@@ -2,7 +2,7 @@ use std::{num::NonZeroU32, time::Duration};
 
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
-use zksync_config::configs::api::MaxResponseSize;
+use zksync_config::configs::api::{HttpRateLimits, MaxResponseSize};
 use zksync_node_api_server::web3::{state::InternalApiConfig, ApiBuilder, ApiServer, Namespace};
 
 use crate::{
@@ -28,6 +28,7 @@ pub struct Web3ServerOptionalConfig {
     pub batch_request_size_limit: Option<usize>,
     pub response_body_size_limit: Option<MaxResponseSize>,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    pub http_rate_limits: Option<HttpRateLimits>,
     pub with_extended_tracing: bool,
     // Used by circuit breaker.
     pub replication_lag_limit: Option<Duration>,
@@ -58,6 +59,9 @@ impl Web3ServerOptionalConfig {
             api_builder = api_builder
                 .with_websocket_requests_per_minute_limit(websocket_requests_per_minute_limit);
         }
+        if let Some(http_rate_limits) = self.http_rate_limits {
+            api_builder = api_builder.with_http_rate_limits(http_rate_limits);
+        }
         if let Some(polling_interval) = self.polling_interval {
             api_builder = api_builder.with_polling_interval(polling_interval);
         }
@@ -214,7 +218,8 @@ impl WiringLayer for Web3ServerLayer {
             .circuit_breakers
             .breakers
             .insert(Box::new(ReplicationLagChecker {
-                pool: replica_pool,
+                // Replicas load-balanced by the pool are monitored separately.
+                pool: replica_pool.without_replicas(),
                 replication_lag_limit,
             }))
             .await;
@@ -1,11 +1,14 @@
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use tokio::sync::RwLock;
 use zksync_node_api_server::{
     execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
     tx_sender::{ApiContracts, TxSenderBuilder, TxSenderConfig},
 };
-use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
+use zksync_state::{
+    PostgresStorageCaches, PostgresStorageCachesTask, RocksdbStorageOptions, StateArchive,
+    StateArchiveTask,
+};
 use zksync_types::Address;
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -51,6 +54,7 @@ pub struct PostgresStorageCachesConfig {
 /// ## Adds tasks
 ///
 /// - `PostgresStorageCachesTask`
+/// - `StateArchiveTask` (optional)
 /// - `VmConcurrencyBarrierTask`
 /// - `WhitelistedTokensForAaUpdateTask` (optional)
 #[derive(Debug)]
@@ -60,6 +64,7 @@ pub struct TxSenderLayer {
     max_vm_concurrency: usize,
     api_contracts: ApiContracts,
     whitelisted_tokens_for_aa_cache: bool,
+    state_archive_path: Option<PathBuf>,
 }
 
 #[derive(Debug, FromContext)]
@@ -81,6 +86,8 @@ pub struct Output {
     #[context(task)]
     pub postgres_storage_caches_task: Option<PostgresStorageCachesTask>,
     #[context(task)]
+    pub state_archive_task: Option<StateArchiveTask>,
+    #[context(task)]
     pub whitelisted_tokens_for_aa_update_task: Option<WhitelistedTokensForAaUpdateTask>,
 }
 
@@ -97,6 +104,7 @@ impl TxSenderLayer {
             max_vm_concurrency,
             api_contracts,
             whitelisted_tokens_for_aa_cache: false,
+            state_archive_path: None,
         }
     }
 
@@ -108,6 +116,13 @@ impl TxSenderLayer {
         self.whitelisted_tokens_for_aa_cache = value;
         self
     }
+
+    /// Enables the historical state archive stored in RocksDB at the specified path. The archive is used
+    /// to serve storage reads for past L2 blocks in the API sandbox and `eth_getStorageAt`. Disabled by default.
+    pub fn with_state_archive(mut self, path: PathBuf) -> Self {
+        self.state_archive_path = Some(path);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -144,6 +159,16 @@ impl WiringLayer for TxSenderLayer {
             None
         };
 
+        let state_archive_task = if let Some(path) = &self.state_archive_path {
+            let archive = StateArchive::new(path, RocksdbStorageOptions::default())
+                .await
+                .map_err(WiringError::Internal)?;
+            storage_caches.set_state_archive(archive.clone());
+            Some(StateArchiveTask::new(archive, replica_pool.clone()))
+        } else {
+            None
+        };
+
         // Initialize `VmConcurrencyLimiter`.
         let (vm_concurrency_limiter, vm_concurrency_barrier) =
             VmConcurrencyLimiter::new(self.max_vm_concurrency);
@@ -183,6 +208,7 @@ impl WiringLayer for TxSenderLayer {
         Ok(Output {
             tx_sender: tx_sender.into(),
             postgres_storage_caches_task,
+            state_archive_task,
             vm_concurrency_barrier,
             whitelisted_tokens_for_aa_update_task,
         })
@@ -200,6 +226,17 @@ impl Task for PostgresStorageCachesTask {
     }
 }
 
+#[async_trait::async_trait]
+impl Task for StateArchiveTask {
+    fn id(&self) -> TaskId {
+        "state_archive".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for VmConcurrencyBarrier {
     fn id(&self) -> TaskId {
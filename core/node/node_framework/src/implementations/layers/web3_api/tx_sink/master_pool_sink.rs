@@ -10,7 +10,20 @@ use crate::{
 };
 
 /// Wiring layer for [`MasterPoolSink`], [`TxSink`](zksync_node_api_server::tx_sender::tx_sink::TxSink) implementation.
-pub struct MasterPoolSinkLayer;
+#[derive(Debug, Default)]
+pub struct MasterPoolSinkLayer {
+    min_replacement_fee_bump_percent: Option<u64>,
+}
+
+impl MasterPoolSinkLayer {
+    /// Creates a layer enforcing the specified minimum fee bump for same-nonce replacements
+    /// (see `MempoolConfig::min_replacement_fee_bump_percent`).
+    pub fn new(min_replacement_fee_bump_percent: Option<u64>) -> Self {
+        Self {
+            min_replacement_fee_bump_percent,
+        }
+    }
+}
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
@@ -36,7 +49,9 @@ impl WiringLayer for MasterPoolSinkLayer {
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let pool = input.master_pool.get().await?;
         Ok(Output {
-            tx_sink: MasterPoolSink::new(pool).into(),
+            tx_sink: MasterPoolSink::new(pool)
+                .with_min_replacement_fee_bump_percent(self.min_replacement_fee_bump_percent)
+                .into(),
         })
     }
 }
@@ -0,0 +1,29 @@
+use std::sync::{Arc, Mutex};
+
+use zksync_eth_watch::EventProcessor;
+
+use crate::resource::Resource;
+
+/// A resource allowing to register custom [`EventProcessor`]s for the Ethereum watcher.
+///
+/// Processors are added to the watcher when its task starts, i.e. after all layers are wired. Thus, layers
+/// registering processors may be added to the service in any order relative to the Ethereum watcher layer.
+#[derive(Debug, Clone, Default)]
+pub struct EthWatchEventProcessorsResource(Arc<Mutex<Vec<Box<dyn EventProcessor>>>>);
+
+impl Resource for EthWatchEventProcessorsResource {
+    fn name() -> String {
+        "common/eth_watch_event_processors".into()
+    }
+}
+
+impl EthWatchEventProcessorsResource {
+    /// Registers a custom event processor.
+    pub fn add(&self, processor: Box<dyn EventProcessor>) {
+        self.0.lock().unwrap().push(processor);
+    }
+
+    pub(crate) fn take(&self) -> Vec<Box<dyn EventProcessor>> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
@@ -3,6 +3,7 @@ pub mod base_token_ratio_provider;
 pub mod circuit_breakers;
 pub mod da_client;
 pub mod eth_interface;
+pub mod eth_watch;
 pub mod fee_input;
 pub mod gas_adjuster;
 pub mod healthcheck;
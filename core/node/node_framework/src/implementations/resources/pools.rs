@@ -8,7 +8,7 @@ use std::{
 
 use tokio::sync::Mutex;
 use zksync_dal::{ConnectionPool, Core};
-use zksync_db_connection::connection_pool::ConnectionPoolBuilder;
+use zksync_db_connection::{connection_pool::ConnectionPoolBuilder, replicas::ReplicaSet};
 use zksync_prover_dal::Prover;
 use zksync_types::url::SensitiveUrl;
 
@@ -22,6 +22,7 @@ pub struct PoolResource<P: PoolKind> {
     max_connections: u32,
     statement_timeout: Option<Duration>,
     acquire_timeout: Option<Duration>,
+    replicas: Option<ReplicaSet>,
     unbound_pool: Arc<Mutex<Option<ConnectionPool<P::DbMarker>>>>,
     _kind: std::marker::PhantomData<P>,
 }
@@ -45,15 +46,25 @@ impl<P: PoolKind> PoolResource<P> {
             max_connections,
             statement_timeout,
             acquire_timeout,
+            replicas: None,
             unbound_pool: Arc::new(Mutex::new(None)),
             _kind: std::marker::PhantomData,
         }
     }
 
+    /// Sets read-only replicas that all pools created by this resource will load-balance connections across.
+    pub fn with_replicas(mut self, replicas: ReplicaSet) -> Self {
+        self.replicas = Some(replicas);
+        self
+    }
+
     fn builder(&self) -> ConnectionPoolBuilder<P::DbMarker> {
         let mut builder = ConnectionPool::builder(self.url.clone(), self.max_connections);
         builder.set_statement_timeout(self.statement_timeout);
         builder.set_acquire_timeout(self.acquire_timeout);
+        if let Some(replicas) = &self.replicas {
+            builder.set_replicas(replicas.clone());
+        }
         builder
     }
 
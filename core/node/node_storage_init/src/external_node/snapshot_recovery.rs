@@ -26,21 +26,38 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
     async fn initialize_storage(&self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let pool = self.pool.clone();
         tracing::warn!("Proceeding with snapshot recovery. This is an experimental feature; use at your own risk");
-        let object_store_config =
-            self.recovery_config.object_store_config.clone().context(
-                "Snapshot object store must be presented if snapshot recovery is activated",
-            )?;
-        let object_store = ObjectStoreFactory::new(object_store_config)
-            .create_store()
-            .await?;
-
-        let config = SnapshotsApplierConfig::default();
-        let mut snapshots_applier_task = SnapshotsApplierTask::new(
-            config,
-            pool,
-            Box::new(self.client.clone().for_component("snapshot_recovery")),
-            object_store,
-        );
+        let main_node_client = Box::new(self.client.clone().for_component("snapshot_recovery"));
+        let drop_storage_key_preimages = self.recovery_config.drop_storage_key_preimages;
+        if drop_storage_key_preimages {
+            tracing::info!("Dropping storage key preimages for snapshot storage logs");
+        }
+        let mut snapshots_applier_task = if let Some(archive_path) =
+            &self.recovery_config.archive_path
+        {
+            tracing::info!(
+                "Recovering from snapshot archive `{}`",
+                archive_path.display()
+            );
+            let config = SnapshotsApplierConfig {
+                archive_path: Some(archive_path.clone()),
+                drop_storage_key_preimages,
+                ..SnapshotsApplierConfig::default()
+            };
+            SnapshotsApplierTask::new_from_archive(config, pool, main_node_client)
+        } else {
+            let object_store_config =
+                self.recovery_config.object_store_config.clone().context(
+                    "Snapshot object store or archive must be presented if snapshot recovery is activated",
+                )?;
+            let object_store = ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?;
+            let config = SnapshotsApplierConfig {
+                drop_storage_key_preimages,
+                ..SnapshotsApplierConfig::default()
+            };
+            SnapshotsApplierTask::new(config, pool, main_node_client, object_store)
+        };
         if let Some(snapshot_l1_batch) = self.recovery_config.snapshot_l1_batch_override {
             tracing::info!(
                 "Using a specific snapshot with L1 batch #{snapshot_l1_batch}; this may not work \
@@ -48,10 +65,6 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             );
             snapshots_applier_task.set_snapshot_l1_batch(snapshot_l1_batch);
         }
-        if self.recovery_config.drop_storage_key_preimages {
-            tracing::info!("Dropping storage key preimages for snapshot storage logs");
-            snapshots_applier_task.drop_storage_key_preimages();
-        }
         self.app_health
             .insert_component(snapshots_applier_task.health_check())?;
 
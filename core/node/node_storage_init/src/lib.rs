@@ -1,4 +1,4 @@
-use std::{future::Future, sync::Arc, time::Duration};
+use std::{future::Future, path::PathBuf, sync::Arc, time::Duration};
 
 use tokio::sync::watch;
 use zksync_config::ObjectStoreConfig;
@@ -17,6 +17,8 @@ pub struct SnapshotRecoveryConfig {
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
     pub drop_storage_key_preimages: bool,
     pub object_store_config: Option<ObjectStoreConfig>,
+    /// Path to a portable snapshot archive to recover from. If specified, `object_store_config` is not used.
+    pub archive_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -187,7 +187,7 @@ impl CommandReceiver {
         } else {
             self.execute_tx_in_vm(tx, vm)?
         };
-        latency.observe();
+        let execution_time = latency.observe();
         APP_METRICS.processed_txs[&TxStage::StateKeeper].inc();
         APP_METRICS.processed_l1_txs[&TxStage::StateKeeper].inc_by(tx.is_l1().into());
 
@@ -213,6 +213,7 @@ impl CommandReceiver {
             compressed_bytecodes,
             call_tracer_result: calls,
             gas_remaining,
+            execution_time,
         })
     }
 
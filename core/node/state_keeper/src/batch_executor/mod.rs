@@ -1,4 +1,4 @@
-use std::{error::Error as StdError, fmt, sync::Arc};
+use std::{error::Error as StdError, fmt, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::{
@@ -31,6 +31,8 @@ pub enum TxExecutionResult {
         compressed_bytecodes: Vec<CompressedBytecodeInfo>,
         call_tracer_result: Vec<Call>,
         gas_remaining: u32,
+        /// Wall-clock time spent on executing the transaction in the VM.
+        execution_time: Duration,
     },
     /// The VM rejected the tx for some reason.
     RejectedByVm { reason: Halt },
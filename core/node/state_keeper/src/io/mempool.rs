@@ -30,7 +30,8 @@ use crate::{
     mempool_actor::l2_tx_filter,
     metrics::{L2BlockSealReason, AGGREGATION_METRICS, KEEPER_METRICS},
     seal_criteria::{
-        IoSealCriteria, L2BlockMaxPayloadSizeSealer, TimeoutSealer, UnexecutableReason,
+        IoSealCriteria, L2BlockExecutionTimeSealer, L2BlockMaxPayloadSizeSealer, TimeoutSealer,
+        UnexecutableReason,
     },
     updates::UpdatesManager,
     MempoolGuard,
@@ -46,6 +47,7 @@ pub struct MempoolIO {
     pool: ConnectionPool<Core>,
     timeout_sealer: TimeoutSealer,
     l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer,
+    l2_block_execution_time_sealer: L2BlockExecutionTimeSealer,
     filter: L2TxFilter,
     l1_batch_params_provider: L1BatchParamsProvider,
     fee_account: Address,
@@ -77,6 +79,14 @@ impl IoSealCriteria for MempoolIO {
             return true;
         }
 
+        if self
+            .l2_block_execution_time_sealer
+            .should_seal_l2_block(manager)
+        {
+            AGGREGATION_METRICS.l2_block_reason_inc(&L2BlockSealReason::ExecutionTime);
+            return true;
+        }
+
         false
     }
 }
@@ -434,6 +444,7 @@ impl MempoolIO {
             pool,
             timeout_sealer: TimeoutSealer::new(config),
             l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer::new(config),
+            l2_block_execution_time_sealer: L2BlockExecutionTimeSealer::new(config),
             filter: L2TxFilter::default(),
             // ^ Will be initialized properly on the first newly opened batch
             l1_batch_params_provider: L1BatchParamsProvider::new(),
@@ -540,6 +540,8 @@ mod tests {
                 block_execution_metrics: Default::default(),
                 txs_encoding_size: Default::default(),
                 payload_encoding_size: Default::default(),
+                execution_time: Default::default(),
+                gas_used_by_contract: Default::default(),
                 timestamp: 1,
                 number: L2BlockNumber(1),
                 prev_block_hash: Default::default(),
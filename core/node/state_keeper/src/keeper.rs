@@ -497,6 +497,7 @@ impl ZkSyncStateKeeper {
                     tx_metrics,
                     compressed_bytecodes,
                     call_tracer_result,
+                    execution_time,
                     ..
                 } = result
                 else {
@@ -529,6 +530,7 @@ impl ZkSyncStateKeeper {
                     tx_execution_metrics,
                     call_tracer_result,
                 );
+                updates_manager.extend_execution_time(execution_time);
 
                 tracing::debug!(
                     "Finished re-executing tx {tx_hash} by {initiator_account} (is_l1: {is_l1}, \
@@ -636,6 +638,7 @@ impl ZkSyncStateKeeper {
                         tx_metrics,
                         call_tracer_result,
                         compressed_bytecodes,
+                        execution_time,
                         ..
                     } = exec_result
                     else {
@@ -655,6 +658,7 @@ impl ZkSyncStateKeeper {
                         tx_execution_metrics,
                         call_tracer_result,
                     );
+                    updates_manager.extend_execution_time(execution_time);
                 }
                 SealResolution::ExcludeAndSeal => {
                     batch_executor.rollback_last_tx().await.with_context(|| {
@@ -710,6 +714,7 @@ impl ZkSyncStateKeeper {
                     tx_result,
                     tx_metrics,
                     compressed_bytecodes,
+                    execution_time,
                     ..
                 } = exec_result
                 else {
@@ -735,6 +740,7 @@ impl ZkSyncStateKeeper {
                     tx_execution_metrics,
                     vec![],
                 );
+                updates_manager.extend_execution_time(execution_time);
                 Ok(())
             }
             SealResolution::ExcludeAndSeal => {
@@ -814,6 +820,7 @@ impl ZkSyncStateKeeper {
                 tx_result,
                 tx_metrics,
                 gas_remaining,
+                execution_time,
                 ..
             } => {
                 let tx_execution_status = &tx_result.result;
@@ -857,6 +864,7 @@ impl ZkSyncStateKeeper {
                 let tx_writes_l1_gas =
                     gas_count_from_writes(&tx_writes_metrics, updates_manager.protocol_version());
                 let tx_gas_excluding_writes = tx_l1_gas_this_tx;
+                let tx_contract_address = &tx.execute.contract_address;
 
                 let tx_data = SealData {
                     execution_metrics: tx_execution_metrics,
@@ -864,6 +872,8 @@ impl ZkSyncStateKeeper {
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    execution_time: *execution_time,
+                    contract_gas_used: tx_result.statistics.gas_used,
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -875,6 +885,10 @@ impl ZkSyncStateKeeper {
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    execution_time: tx_data.execution_time
+                        + updates_manager.pending_execution_time(),
+                    contract_gas_used: tx_data.contract_gas_used
+                        + updates_manager.pending_contract_gas_used(tx_contract_address),
                 };
 
                 self.sealer.should_seal_l1_batch(
@@ -158,6 +158,7 @@ async fn get_transaction_nonces(
 
 #[cfg(test)]
 mod tests {
+    use zksync_config::configs::chain::MempoolOrderingPolicy;
     use zksync_multivm::interface::TransactionExecutionMetrics;
     use zksync_node_fee_model::MockBatchFeeParamsProvider;
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
@@ -174,6 +175,9 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        ordering_policy: MempoolOrderingPolicy::Fifo,
+        min_replacement_fee_bump_percent: None,
+        evict_cheapest_on_capacity: false,
     };
 
     #[tokio::test]
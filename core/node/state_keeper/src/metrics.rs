@@ -159,6 +159,12 @@ pub(super) struct StateKeeperGauges {
     mempool_l2_size: Gauge<u64>,
     /// Current size of the L2 priority queue.
     l2_priority_queue_size: Gauge<usize>,
+    /// Total number of L2 transactions replaced by transactions with the same nonce.
+    mempool_replaced_txs: Gauge<u64>,
+    /// Total number of rejected L2 transaction replacements because of an insufficient fee bump.
+    mempool_rejected_replacements: Gauge<u64>,
+    /// Total number of L2 transactions evicted from the full mempool.
+    mempool_evicted_txs: Gauge<u64>,
 }
 
 impl StateKeeperGauges {
@@ -178,6 +184,15 @@ impl StateKeeperGauges {
                     .l2_priority_queue_size
                     .set(stats.l2_priority_queue_size);
                 gauges
+                    .mempool_replaced_txs
+                    .set(stats.replaced_transaction_count);
+                gauges
+                    .mempool_rejected_replacements
+                    .set(stats.rejected_replacement_count);
+                gauges
+                    .mempool_evicted_txs
+                    .set(stats.evicted_transaction_count);
+                gauges
             })
         });
         if res.is_err() {
@@ -213,6 +228,7 @@ impl From<&SealResolution> for SealResolutionLabel {
 pub(super) enum L2BlockSealReason {
     Timeout,
     PayloadSize,
+    ExecutionTime,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
@@ -139,6 +139,8 @@ impl SequencerSealer {
             Box::new(criteria::CircuitsCriterion),
             Box::new(criteria::TxEncodingSizeCriterion),
             Box::new(criteria::GasForBatchTipCriterion),
+            Box::new(criteria::ExecutionTimeCriterion),
+            Box::new(criteria::ContractGasShareCriterion),
         ]
     }
 }
@@ -0,0 +1,103 @@
+use zksync_types::ProtocolVersionId;
+
+use crate::seal_criteria::{SealCriterion, SealData, SealResolution, StateKeeperConfig};
+
+/// Checks whether we should seal the batch because transactions calling a single contract use more than
+/// the configured share of the batch gas. This prevents batches from being dominated by a single hot contract.
+///
+/// The first transaction calling a contract in the batch is always included (excluding it wouldn't reduce
+/// the contract share in the next batch). Once a following transaction calling the contract exceeds the share,
+/// it is excluded and the batch is sealed, so that the transaction starts the next batch. Note that this seals
+/// the entire batch; transactions calling other contracts are not considered for the batch either.
+#[derive(Debug)]
+pub(crate) struct ContractGasShareCriterion;
+
+impl SealCriterion for ContractGasShareCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let Some(max_share) = config.max_contract_gas_share else {
+            return SealResolution::NoSeal;
+        };
+        let contract_gas_limit = (config.max_gas_per_batch as f64 * max_share).round() as u64;
+
+        let is_first_contract_tx = block_data.contract_gas_used == tx_data.contract_gas_used;
+        if block_data.contract_gas_used > contract_gas_limit && !is_first_contract_tx {
+            SealResolution::ExcludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "contract_gas_share"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seal_data(contract_gas_used: u64) -> SealData {
+        SealData {
+            contract_gas_used,
+            ..SealData::default()
+        }
+    }
+
+    fn should_seal(config: &StateKeeperConfig, block_gas: u64, tx_gas: u64) -> SealResolution {
+        ContractGasShareCriterion.should_seal(
+            config,
+            0,
+            10,
+            &seal_data(block_gas),
+            &seal_data(tx_gas),
+            ProtocolVersionId::latest(),
+        )
+    }
+
+    #[test]
+    fn contract_gas_share_seal_criterion() {
+        let config = StateKeeperConfig {
+            max_gas_per_batch: 1_000_000,
+            max_contract_gas_share: Some(0.25),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            should_seal(&config, 200_000, 50_000),
+            SealResolution::NoSeal
+        );
+        assert_eq!(
+            should_seal(&config, 250_000, 50_000),
+            SealResolution::NoSeal
+        );
+        assert_eq!(
+            should_seal(&config, 260_000, 50_000),
+            SealResolution::ExcludeAndSeal
+        );
+        // The first transaction calling the contract should be included regardless of the gas it uses.
+        assert_eq!(
+            should_seal(&config, 400_000, 400_000),
+            SealResolution::NoSeal
+        );
+    }
+
+    #[test]
+    fn contract_gas_share_is_not_limited_by_default() {
+        let config = StateKeeperConfig {
+            max_gas_per_batch: 1_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            should_seal(&config, 1_000_000, 50_000),
+            SealResolution::NoSeal
+        );
+    }
+}
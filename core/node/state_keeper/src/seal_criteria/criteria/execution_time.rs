@@ -0,0 +1,120 @@
+use zksync_types::ProtocolVersionId;
+
+use crate::seal_criteria::{SealCriterion, SealData, SealResolution, StateKeeperConfig};
+
+/// Checks whether we should seal the batch because the cumulative VM execution time of its transactions
+/// has exceeded the configured limit.
+///
+/// Since execution time isn't deterministic, this criterion never marks transactions as unexecutable;
+/// a transaction exceeding the limit on its own is included into the batch and the batch is sealed after it.
+#[derive(Debug)]
+pub(crate) struct ExecutionTimeCriterion;
+
+impl SealCriterion for ExecutionTimeCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        tx_count: usize,
+        block_data: &SealData,
+        _tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let Some(max_execution_time) = config.max_l1_batch_execution_time() else {
+            return SealResolution::NoSeal;
+        };
+
+        if block_data.execution_time < max_execution_time {
+            SealResolution::NoSeal
+        } else if block_data.execution_time > max_execution_time && tx_count > 1 {
+            SealResolution::ExcludeAndSeal
+        } else {
+            SealResolution::IncludeAndSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "execution_time"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn seal_data(execution_time: Duration) -> SealData {
+        SealData {
+            execution_time,
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn execution_time_seal_criterion() {
+        let config = StateKeeperConfig {
+            max_l1_batch_execution_time_ms: Some(1_000),
+            ..Default::default()
+        };
+        let criterion = ExecutionTimeCriterion;
+        let tx_data = seal_data(Duration::from_millis(100));
+
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            5,
+            &seal_data(Duration::from_millis(900)),
+            &tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            5,
+            &seal_data(Duration::from_millis(1_000)),
+            &tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            5,
+            &seal_data(Duration::from_millis(1_050)),
+            &tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        // A single slow transaction should be included.
+        let slow_tx_data = seal_data(Duration::from_secs(2));
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            1,
+            &slow_tx_data,
+            &slow_tx_data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+    }
+
+    #[test]
+    fn execution_time_is_not_limited_by_default() {
+        let config = StateKeeperConfig::default();
+        let data = seal_data(Duration::from_secs(3_600));
+        let resolution = ExecutionTimeCriterion.should_seal(
+            &config,
+            0,
+            10,
+            &data,
+            &data,
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+    }
+}
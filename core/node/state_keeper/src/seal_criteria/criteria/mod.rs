@@ -1,3 +1,5 @@
+mod contract_gas_share;
+mod execution_time;
 mod gas;
 mod gas_for_batch_tip;
 mod geometry_seal_criteria;
@@ -6,6 +8,7 @@ mod slots;
 mod tx_encoding_size;
 
 pub(crate) use self::{
+    contract_gas_share::ContractGasShareCriterion, execution_time::ExecutionTimeCriterion,
     gas::GasCriterion, gas_for_batch_tip::GasForBatchTipCriterion,
     geometry_seal_criteria::CircuitsCriterion, pubdata_bytes::PubDataBytesCriterion,
     slots::SlotsCriterion, tx_encoding_size::TxEncodingSizeCriterion,
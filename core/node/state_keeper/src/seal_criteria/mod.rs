@@ -10,7 +10,7 @@
 //! Maintaining all the criteria in one place has proven itself to be very error-prone,
 //! thus now every criterion is independent of the others.
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_multivm::{
@@ -163,6 +163,10 @@ pub struct SealData {
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
     pub(super) gas_remaining: u32,
+    /// Wall-clock time spent on executing transactions in the VM.
+    pub(super) execution_time: Duration,
+    /// Gas used by transactions calling the same contract as the latest transaction.
+    pub(super) contract_gas_used: u64,
 }
 
 impl SealData {
@@ -183,6 +187,9 @@ impl SealData {
             cumulative_size: transaction.bootloader_encoding_size(),
             writes_metrics,
             gas_remaining: tx_metrics.gas_remaining,
+            // Execution time isn't deterministic, so it's not used to determine whether a transaction is executable.
+            execution_time: Duration::ZERO,
+            contract_gas_used: tx_metrics.gas_used as u64,
         }
     }
 }
@@ -275,6 +282,24 @@ impl L2BlockMaxPayloadSizeSealer {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub(super) struct L2BlockExecutionTimeSealer {
+    max_execution_time: Option<Duration>,
+}
+
+impl L2BlockExecutionTimeSealer {
+    pub fn new(config: &StateKeeperConfig) -> Self {
+        Self {
+            max_execution_time: config.max_l2_block_execution_time(),
+        }
+    }
+
+    pub fn should_seal_l2_block(&mut self, manager: &UpdatesManager) -> bool {
+        self.max_execution_time
+            .is_some_and(|max_time| manager.l2_block.execution_time >= max_time)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_utils::time::seconds_since_epoch;
@@ -348,4 +373,36 @@ mod tests {
             "L2 block with payload encoding size equal or greater than max payload size should be sealed"
         );
     }
+
+    #[test]
+    fn execution_time_l2_block_sealer() {
+        let mut execution_time_sealer = L2BlockExecutionTimeSealer {
+            max_execution_time: Some(Duration::from_millis(100)),
+        };
+
+        let mut manager = create_updates_manager();
+        assert!(
+            !execution_time_sealer.should_seal_l2_block(&manager),
+            "Empty L2 block shouldn't be sealed"
+        );
+
+        apply_tx_to_manager(create_transaction(10, 100), &mut manager);
+        manager.extend_execution_time(Duration::from_millis(60));
+        assert!(
+            !execution_time_sealer.should_seal_l2_block(&manager),
+            "L2 block with execution time less than the limit shouldn't be sealed"
+        );
+
+        apply_tx_to_manager(create_transaction(10, 100), &mut manager);
+        manager.extend_execution_time(Duration::from_millis(60));
+        assert!(
+            execution_time_sealer.should_seal_l2_block(&manager),
+            "L2 block with execution time exceeding the limit should be sealed"
+        );
+
+        let mut unlimited_sealer = L2BlockExecutionTimeSealer {
+            max_execution_time: None,
+        };
+        assert!(!unlimited_sealer.should_seal_l2_block(&manager));
+    }
 }
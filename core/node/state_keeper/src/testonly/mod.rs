@@ -66,6 +66,7 @@ pub(crate) fn successful_exec() -> TxExecutionResult {
         compressed_bytecodes: vec![],
         call_tracer_result: vec![],
         gas_remaining: Default::default(),
+        execution_time: Default::default(),
     }
 }
 
@@ -3,8 +3,9 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use zksync_config::configs::chain::{MempoolConfig, MempoolOrderingPolicy};
 use zksync_dal::{Connection, Core, CoreDal};
-use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
+use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolOrdering, MempoolPolicy, MempoolStore};
 use zksync_multivm::interface::{VmExecutionMetrics, VmExecutionResultAndLogs};
 use zksync_types::{block::BlockGasCount, Address, Nonce, PriorityOpId, Transaction};
 
@@ -17,16 +18,31 @@ use super::{
 pub struct MempoolGuard(Arc<Mutex<MempoolStore>>);
 
 impl MempoolGuard {
-    pub async fn from_storage(storage_processor: &mut Connection<'_, Core>, capacity: u64) -> Self {
+    pub async fn from_storage(
+        storage_processor: &mut Connection<'_, Core>,
+        config: &MempoolConfig,
+    ) -> Self {
         let next_priority_id = storage_processor
             .transactions_dal()
             .next_priority_id()
             .await;
-        Self::new(next_priority_id, capacity)
+        let policy = MempoolPolicy {
+            ordering: match config.ordering_policy {
+                MempoolOrderingPolicy::Fifo => MempoolOrdering::Fifo,
+                MempoolOrderingPolicy::PriorityFee => MempoolOrdering::PriorityFee,
+            },
+            min_replacement_fee_bump_percent: config.min_replacement_fee_bump_percent,
+            evict_cheapest: config.evict_cheapest_on_capacity,
+        };
+        Self::with_policy(next_priority_id, config.capacity, policy)
     }
 
     pub(super) fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
-        let store = MempoolStore::new(next_priority_id, capacity);
+        Self::with_policy(next_priority_id, capacity, MempoolPolicy::default())
+    }
+
+    fn with_policy(next_priority_id: PriorityOpId, capacity: u64, policy: MempoolPolicy) -> Self {
+        let store = MempoolStore::new(next_priority_id, capacity).with_policy(policy);
         Self(Arc::new(Mutex::new(store)))
     }
 
@@ -1,6 +1,8 @@
+use std::{collections::HashMap, time::Duration};
+
 use zksync_multivm::interface::{FinishedL1Batch, TransactionExecutionResult, VmExecutionMetrics};
 use zksync_types::{
-    block::BlockGasCount, priority_op_onchain_data::PriorityOpOnchainData,
+    block::BlockGasCount, priority_op_onchain_data::PriorityOpOnchainData, Address,
     ExecuteTransactionCommon, L1BatchNumber,
 };
 
@@ -15,6 +17,10 @@ pub struct L1BatchUpdates {
     // how much L1 gas will it take to submit this block?
     pub l1_gas_count: BlockGasCount,
     pub txs_encoding_size: usize,
+    /// Cumulative VM execution time of transactions in sealed L2 blocks of this batch.
+    pub execution_time: Duration,
+    /// Gas used by transactions in sealed L2 blocks of this batch grouped by the called contract.
+    pub gas_used_by_contract: HashMap<Address, u64>,
     pub finished: Option<FinishedL1Batch>,
 }
 
@@ -27,6 +33,8 @@ impl L1BatchUpdates {
             block_execution_metrics: Default::default(),
             l1_gas_count: new_block_gas_count(),
             txs_encoding_size: 0,
+            execution_time: Duration::ZERO,
+            gas_used_by_contract: HashMap::new(),
             finished: None,
         }
     }
@@ -44,6 +52,13 @@ impl L1BatchUpdates {
         self.l1_gas_count += l2_block_updates.l1_gas_count;
         self.block_execution_metrics += l2_block_updates.block_execution_metrics;
         self.txs_encoding_size += l2_block_updates.txs_encoding_size;
+        self.execution_time += l2_block_updates.execution_time;
+        for (contract_address, gas_used) in l2_block_updates.gas_used_by_contract {
+            *self
+                .gas_used_by_contract
+                .entry(contract_address)
+                .or_default() += gas_used;
+        }
     }
 }
 
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use once_cell::sync::Lazy;
 use zksync_multivm::{
@@ -13,7 +13,7 @@ use zksync_types::{
     block::{BlockGasCount, L2BlockHasher},
     ethabi,
     l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
-    L2BlockNumber, ProtocolVersionId, StorageLogWithPreviousValue, Transaction, H256,
+    Address, L2BlockNumber, ProtocolVersionId, StorageLogWithPreviousValue, Transaction, H256,
 };
 use zksync_utils::bytecode::hash_bytecode;
 
@@ -53,6 +53,10 @@ pub struct L2BlockUpdates {
     pub block_execution_metrics: VmExecutionMetrics,
     pub txs_encoding_size: usize,
     pub payload_encoding_size: usize,
+    /// Cumulative VM execution time of transactions in this block.
+    pub execution_time: Duration,
+    /// Gas used by transactions in this block grouped by the called contract.
+    pub gas_used_by_contract: HashMap<Address, u64>,
     pub timestamp: u64,
     pub number: L2BlockNumber,
     pub prev_block_hash: H256,
@@ -79,6 +83,8 @@ impl L2BlockUpdates {
             block_execution_metrics: VmExecutionMetrics::default(),
             txs_encoding_size: 0,
             payload_encoding_size: 0,
+            execution_time: Duration::ZERO,
+            gas_used_by_contract: HashMap::new(),
             timestamp,
             number,
             prev_block_hash,
@@ -165,6 +171,10 @@ impl L2BlockUpdates {
 
         self.l1_gas_count += tx_l1_gas_this_tx;
         self.block_execution_metrics += execution_metrics;
+        *self
+            .gas_used_by_contract
+            .entry(tx.execute.contract_address)
+            .or_default() += tx_execution_result.statistics.gas_used;
         self.txs_encoding_size += tx.bootloader_encoding_size();
         self.payload_encoding_size +=
             zksync_protobuf::repr::encode::<zksync_dal::consensus::proto::Transaction>(&tx).len();
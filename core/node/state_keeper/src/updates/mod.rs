@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_multivm::{
     interface::{
@@ -129,6 +131,11 @@ impl UpdatesManager {
         latency.observe();
     }
 
+    /// Accounts for the VM execution time of the latest transaction included into the pending L2 block.
+    pub(crate) fn extend_execution_time(&mut self, execution_time: Duration) {
+        self.l2_block.execution_time += execution_time;
+    }
+
     pub fn finish_batch(&mut self, finished_batch: FinishedL1Batch) {
         let latency = UPDATES_MANAGER_METRICS.finish_batch.start();
         assert!(
@@ -193,6 +200,20 @@ impl UpdatesManager {
     pub(crate) fn pending_txs_encoding_size(&self) -> usize {
         self.l1_batch.txs_encoding_size + self.l2_block.txs_encoding_size
     }
+
+    pub(crate) fn pending_execution_time(&self) -> Duration {
+        self.l1_batch.execution_time + self.l2_block.execution_time
+    }
+
+    pub(crate) fn pending_contract_gas_used(&self, contract_address: &Address) -> u64 {
+        [
+            &self.l1_batch.gas_used_by_contract,
+            &self.l2_block.gas_used_by_contract,
+        ]
+        .into_iter()
+        .filter_map(|gas_used_by_contract| gas_used_by_contract.get(contract_address))
+        .sum()
+    }
 }
 
 /// Command to seal an L2 block containing all necessary data for it.
@@ -255,4 +276,53 @@ mod tests {
         assert_eq!(updates_manager.l2_block.executed_transactions.len(), 0);
         assert_eq!(updates_manager.l1_batch.executed_transactions.len(), 1);
     }
+
+    #[test]
+    fn accumulating_execution_time_and_contract_gas() {
+        let mut updates_manager = create_updates_manager();
+        let tx = create_transaction(10, 100);
+        let contract_address = tx.execute.contract_address;
+        let mut execution_result = create_execution_result([]);
+        execution_result.statistics.gas_used = 1_000;
+
+        updates_manager.extend_from_executed_transaction(
+            tx.clone(),
+            execution_result.clone(),
+            vec![],
+            new_block_gas_count(),
+            VmExecutionMetrics::default(),
+            vec![],
+        );
+        updates_manager.extend_execution_time(Duration::from_millis(10));
+        updates_manager.push_l2_block(L2BlockParams {
+            timestamp: 2,
+            virtual_blocks: 1,
+        });
+        updates_manager.extend_from_executed_transaction(
+            tx,
+            execution_result,
+            vec![],
+            new_block_gas_count(),
+            VmExecutionMetrics::default(),
+            vec![],
+        );
+        updates_manager.extend_execution_time(Duration::from_millis(5));
+
+        assert_eq!(
+            updates_manager.pending_contract_gas_used(&contract_address),
+            2_000
+        );
+        assert_eq!(
+            updates_manager.pending_contract_gas_used(&Address::repeat_byte(1)),
+            0
+        );
+        assert_eq!(
+            updates_manager.pending_execution_time(),
+            Duration::from_millis(15)
+        );
+        assert_eq!(
+            updates_manager.l2_block.execution_time,
+            Duration::from_millis(5)
+        );
+    }
 }
@@ -77,9 +77,11 @@ fn prepare_configs(
             server_url: Some(args.db.full_url().into()),
             prover_url: None,
             server_replica_url: None,
+            server_replica_urls: vec![],
         }),
         l1: Some(L1Secrets {
             l1_rpc_url: SensitiveUrl::from_str(&args.l1_rpc_url).context("l1_rpc_url")?,
+            fallback_l1_rpc_urls: vec![],
         }),
     };
     secrets.save_with_base_path(shell, en_configs_path)?;